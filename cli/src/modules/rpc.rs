@@ -38,7 +38,7 @@ impl Rpc {
                 tprintln!(ctx, "ok");
             }
             RpcApiOps::GetMetrics => {
-                let result = rpc.get_metrics(true, true, true, true, true, true).await?;
+                let result = rpc.get_metrics(true, true, true, true, true, true, true).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::GetSystemInfo => {
@@ -83,12 +83,7 @@ impl Rpc {
             // }
             RpcApiOps::GetMempoolEntries => {
                 // TODO
-                let result = rpc
-                    .get_mempool_entries_call(
-                        None,
-                        GetMempoolEntriesRequest { include_orphan_pool: true, filter_transaction_pool: true },
-                    )
-                    .await?;
+                let result = rpc.get_mempool_entries_call(None, GetMempoolEntriesRequest::new(true, true)).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::GetConnectedPeerInfo => {
@@ -269,6 +264,62 @@ impl Rpc {
                 let result = rpc.get_current_block_color_call(None, GetCurrentBlockColorRequest { hash }).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetTransaction => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id argument"));
+                }
+                let transaction_id = argv.remove(0);
+                let transaction_id = RpcHash::from_hex(transaction_id.as_str())?;
+                let result =
+                    rpc.get_transaction_call(None, GetTransactionRequest { transaction_id, include_transaction: true }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetTransactionsByIds => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id arguments"));
+                }
+                let transaction_ids =
+                    argv.iter().map(|id| RpcHash::from_hex(id.as_str())).collect::<std::result::Result<Vec<_>, _>>()?;
+                let result = rpc
+                    .get_transactions_by_ids_call(None, GetTransactionsByIdsRequest { transaction_ids, include_transactions: true })
+                    .await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetTransactionsByAddresses => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify at least one address"));
+                }
+                let addresses = argv.iter().map(|s| Address::try_from(s.as_str())).collect::<std::result::Result<Vec<_>, _>>()?;
+                let result = rpc
+                    .get_transactions_by_addresses_call(
+                        None,
+                        GetTransactionsByAddressesRequest { addresses, cursor: None, limit: 100 },
+                    )
+                    .await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetChainBlockByDaaScore => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify a daa_score"));
+                }
+                let daa_score = argv.remove(0).parse::<u64>().map_err(|_| Error::custom("Could not parse daa_score to u64"))?;
+                let result = rpc.get_chain_block_by_daa_score_call(None, GetChainBlockByDaaScoreRequest { daa_score }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetChainBlocksInBlueScoreRange => {
+                if argv.len() < 2 {
+                    return Err(Error::custom("Please specify a low and a high blue_score"));
+                }
+                let low_blue_score = argv.remove(0).parse::<u64>().map_err(|_| Error::custom("Could not parse blue_score to u64"))?;
+                let high_blue_score = argv.remove(0).parse::<u64>().map_err(|_| Error::custom("Could not parse blue_score to u64"))?;
+                let result = rpc
+                    .get_chain_blocks_in_blue_score_range_call(
+                        None,
+                        GetChainBlocksInBlueScoreRangeRequest { low_blue_score, high_blue_score },
+                    )
+                    .await?;
+                self.println(&ctx, result);
+            }
             _ => {
                 tprintln!(ctx, "rpc method exists but is not supported by the cli: '{op_str}'\r\n");
                 return Ok(());
@@ -20,7 +20,7 @@ use thiserror::Error;
 use vecno_consensus_core::config::Config;
 use vecno_core::{debug, info, task::tick::TickService, time::unix_now, warn};
 use vecno_database::prelude::{CachePolicy, StoreResultExtensions, DB};
-use vecno_utils::networking::IpAddress;
+use vecno_utils::networking::{AddressNetwork, IpAddress};
 
 pub use stores::NetAddress;
 
@@ -75,7 +75,8 @@ impl AddressManager {
     fn init_local_addresses(&mut self, tick_service: Arc<TickService>) -> Option<Extender> {
         self.local_net_addresses = self.local_addresses().collect();
 
-        let extender = if self.local_net_addresses.is_empty() && !self.config.disable_upnp {
+        // UPnP would reveal the IP of the node when connecting through a proxy
+        let extender = if self.local_net_addresses.is_empty() && !self.config.disable_upnp && self.config.p2p_proxy.is_none() {
             let (net_address, ExtendHelper { gateway, local_addr, external_port }) = match self.upnp() {
                 Err(err) => {
                     warn!("[UPnP] Error adding port mapping: {err}");
@@ -115,7 +116,7 @@ impl AddressManager {
     fn local_addresses(&self) -> impl Iterator<Item = NetAddress> + '_ {
         match self.config.externalip {
             // An external IP was passed, we will try to bind that if it's valid
-            Some(local_net_address) if local_net_address.ip.is_publicly_routable() => {
+            Some(local_net_address) if local_net_address.is_onion() || local_net_address.ip.is_publicly_routable() => {
                 info!("External address is publicly routable {}", local_net_address);
                 return Left(iter::once(local_net_address));
             }
@@ -132,7 +133,11 @@ impl AddressManager {
         // check whatever was passed as listen address (if routable)
         // otherwise(listen_address === 0.0.0.0) check all interfaces
         let listen_address = self.config.p2p_listen_address.normalize(self.config.default_p2p_port());
-        if listen_address.ip.is_publicly_routable() {
+        if self.config.p2p_proxy.is_some() {
+            // The IP of the node is not revealed when connecting through a proxy
+            info!("Local addresses are not advertised while connecting through a proxy");
+            Left(Right(iter::empty()))
+        } else if listen_address.ip.is_publicly_routable() {
             info!("Publicly routable local address found: {}", listen_address.ip);
             Left(Left(iter::once(listen_address)))
         } else if listen_address.ip.is_unspecified() {
@@ -210,7 +215,7 @@ impl AddressManager {
             let port =
                 gateway.add_any_port(igd::PortMappingProtocol::TCP, local_addr, UPNP_DEADLINE_SEC as u32, UPNP_REGISTRATION_NAME)?;
             info!("[UPnP] Added port mapping to random external port: {ip}:{port}");
-            return Ok(Some((NetAddress { ip, port }, ExtendHelper { gateway, local_addr, external_port: port })));
+            return Ok(Some((NetAddress::new(ip, port), ExtendHelper { gateway, local_addr, external_port: port })));
         }
 
         match gateway.add_port(
@@ -223,7 +228,7 @@ impl AddressManager {
             Ok(_) => {
                 info!("[UPnP] Added port mapping to default external port: {ip}:{desired_external_port}");
                 Ok(Some((
-                    NetAddress { ip, port: desired_external_port },
+                    NetAddress::new(ip, desired_external_port),
                     ExtendHelper { gateway, local_addr, external_port: desired_external_port },
                 )))
             }
@@ -235,7 +240,7 @@ impl AddressManager {
                     UPNP_REGISTRATION_NAME,
                 )?;
                 info!("[UPnP] Added port mapping to random external port: {ip}:{port}");
-                Ok(Some((NetAddress { ip, port }, ExtendHelper { gateway, local_addr, external_port: port })))
+                Ok(Some((NetAddress::new(ip, port), ExtendHelper { gateway, local_addr, external_port: port })))
             }
             Err(err) => Err(err.into()),
         }
@@ -257,6 +262,11 @@ impl AddressManager {
             return;
         }
 
+        if address.ip.is_onion() && !address.is_onion() {
+            debug!("[Address manager] skipping virtual onion IP {} lacking its onion address", address.ip);
+            return;
+        }
+
         if self.address_store.has(address) {
             return;
         }
@@ -290,12 +300,27 @@ impl AddressManager {
         self.address_store.iterate_addresses()
     }
 
+    /// Iterates the addresses to connect to, restricted to the reachable networks
     pub fn iterate_prioritized_random_addresses(&self, exceptions: HashSet<NetAddress>) -> impl ExactSizeIterator<Item = NetAddress> {
-        self.address_store.iterate_prioritized_random_addresses(exceptions)
+        self.address_store.iterate_prioritized_random_addresses(exceptions, |address| self.is_reachable(address.network()))
+    }
+
+    /// Whether outbound connections can be made to addresses of `network`, which requires onion services to be
+    /// connected to through a proxy and `network` to be allowed by the `--onlynet` setting
+    pub fn is_reachable(&self, network: AddressNetwork) -> bool {
+        if network == AddressNetwork::Onion && self.config.p2p_onion_proxy.is_none() {
+            return false;
+        }
+        self.config.p2p_only_networks.is_empty() || self.config.p2p_only_networks.contains(&network)
     }
 
-    pub fn ban(&mut self, ip: IpAddress) {
-        self.banned_address_store.set(ip.into(), ConnectionBanTimestamp(unix_now())).unwrap();
+    /// Bans `ip` for `duration`, a ban in force being extended if it expires earlier
+    pub fn ban(&mut self, ip: IpAddress, duration: Duration) {
+        let mut expiry = unix_now().saturating_add(duration.as_millis() as u64);
+        if let Some(current) = self.banned_address_store.get(ip.into()).unwrap_option() {
+            expiry = expiry.max(current.0);
+        }
+        self.banned_address_store.set(ip.into(), ConnectionBanTimestamp(expiry)).unwrap();
         self.address_store.remove_by_ip(ip.into());
     }
 
@@ -304,10 +329,9 @@ impl AddressManager {
     }
 
     pub fn is_banned(&mut self, ip: IpAddress) -> bool {
-        const MAX_BANNED_TIME: u64 = 24 * 60 * 60 * 1000;
         match self.banned_address_store.get(ip.into()).unwrap_option() {
-            Some(timestamp) => {
-                if unix_now() - timestamp.0 > MAX_BANNED_TIME {
+            Some(expiry) => {
+                if unix_now() >= expiry.0 {
                     self.unban(ip);
                     false
                 } else {
@@ -325,6 +349,21 @@ impl AddressManager {
     pub fn get_all_banned_addresses(&self) -> Vec<IpAddress> {
         self.banned_address_store.iterator().map(|x| IpAddress::from(x.unwrap().0)).collect_vec()
     }
+
+    /// Returns the banned addresses along with the unix time, in milliseconds, their ban expires at, lifting the expired bans
+    pub fn get_banned_addresses_with_expiry(&mut self) -> Vec<(IpAddress, u64)> {
+        let now = unix_now();
+        let (banned, expired): (Vec<_>, Vec<_>) = self
+            .banned_address_store
+            .iterator()
+            .map(|x| x.unwrap())
+            .map(|(ip, expiry)| (IpAddress::from(ip), expiry.0))
+            .partition(|(_, expiry)| now < *expiry);
+        for (ip, _) in expired {
+            self.unban(ip);
+        }
+        banned
+    }
 }
 
 mod address_store_with_cache {
@@ -428,13 +467,14 @@ mod address_store_with_cache {
         pub fn iterate_prioritized_random_addresses(
             &self,
             exceptions: HashSet<NetAddress>,
+            is_reachable: impl Fn(&NetAddress) -> bool,
         ) -> impl ExactSizeIterator<Item = NetAddress> {
             let exceptions: HashSet<AddressKey> = exceptions.into_iter().map(|addr| addr.into()).collect();
             let mut prefix_counter: HashMap<PrefixBucket, usize> = HashMap::new();
             let (mut weights, filtered_addresses): (Vec<f64>, Vec<NetAddress>) = self
                 .addresses
                 .iter()
-                .filter(|(addr_key, _)| !exceptions.contains(addr_key))
+                .filter(|(addr_key, e)| !exceptions.contains(addr_key) && is_reachable(&e.address))
                 .map(|(_, e)| {
                     let count = prefix_counter.entry(e.address.prefix_bucket()).or_insert(0);
                     *count += 1;
@@ -517,11 +557,12 @@ mod address_store_with_cache {
         use address_manager::AddressManager;
         use rv::{dist::Uniform, misc::ks_test as one_way_ks_test, traits::Cdf};
         use std::net::{IpAddr, Ipv6Addr};
+        use std::time::Duration;
         use vecno_consensus_core::config::{params::SIMNET_PARAMS, Config};
-        use vecno_core::task::tick::TickService;
+        use vecno_core::{task::tick::TickService, time::unix_now};
         use vecno_database::create_temp_db;
         use vecno_database::prelude::ConnBuilder;
-        use vecno_utils::networking::IpAddress;
+        use vecno_utils::networking::{AddressNetwork, IpAddress};
 
         #[test]
         fn test_weighted_iterator() {
@@ -535,6 +576,64 @@ mod address_store_with_cache {
             assert_eq!(iter.count(), 0);
         }
 
+        #[test]
+        fn test_ban_expiry() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Config::new(SIMNET_PARAMS);
+            let (am, _) = AddressManager::new(Arc::new(config), db.1, Arc::new(TickService::default()));
+            let mut am = am.lock();
+
+            let ip = IpAddress::from_str("1.2.3.4").unwrap();
+            let other_ip = IpAddress::from_str("5.6.7.8").unwrap();
+            am.ban(ip, Duration::from_secs(3600));
+            am.ban(other_ip, Duration::ZERO);
+            assert!(am.is_banned(ip));
+            assert!(!am.is_banned(other_ip));
+
+            // A shorter ban does not cut the one in force
+            am.ban(ip, Duration::ZERO);
+            let banned = am.get_banned_addresses_with_expiry();
+            assert_eq!(banned.len(), 1);
+            assert_eq!(banned[0].0, ip);
+            assert!(banned[0].1 > unix_now());
+
+            am.unban(ip);
+            assert!(!am.is_banned(ip));
+            assert!(am.get_banned_addresses_with_expiry().is_empty());
+        }
+
+        #[test]
+        fn test_onion_addresses() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let onion = NetAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:7111").unwrap();
+            let ip = NetAddress::from_str("1.2.3.4:7111").unwrap();
+            let mut config = Config::new(SIMNET_PARAMS);
+            config.disable_upnp = true;
+            let reachable = |config: Config| {
+                let (am, _) = AddressManager::new(Arc::new(config), db.1.clone(), Arc::new(TickService::default()));
+                let am = am.lock();
+                am.iterate_prioritized_random_addresses(HashSet::new()).collect::<HashSet<_>>()
+            };
+
+            let (am, _) = AddressManager::new(Arc::new(config.clone()), db.1.clone(), Arc::new(TickService::default()));
+            am.lock().add_address(onion);
+            am.lock().add_address(ip);
+            // Virtual onion IPs are only stored along with their onion address
+            am.lock().add_address(NetAddress::new(onion.ip, 7111));
+            assert_eq!(am.lock().get_all_addresses().len(), 2);
+            am.lock().mark_connection_failure(onion);
+            drop(am);
+
+            // Onion services are only reachable through an onion proxy, and are reloaded from the database
+            assert_eq!(reachable(config.clone()), HashSet::from([ip]));
+            config.p2p_onion_proxy = Some(NetAddress::from_str("127.0.0.1:9050").unwrap());
+            assert_eq!(reachable(config.clone()), HashSet::from([ip, onion]));
+            config.p2p_only_networks = vec![AddressNetwork::Onion];
+            assert_eq!(reachable(config.clone()), HashSet::from([onion]));
+            config.p2p_only_networks = vec![AddressNetwork::Ipv6];
+            assert!(reachable(config).is_empty());
+        }
+
         #[test]
         fn test_network_distribution_weighting() {
             vecno_core::log::try_init_logger("info");
@@ -7,18 +7,51 @@ use vecno_database::{
     prelude::{CachedDbAccess, DirectDbWriter},
     registry::DatabaseStorePrefixes,
 };
-use vecno_utils::mem_size::MemSizeEstimator;
+use vecno_utils::{
+    mem_size::MemSizeEstimator,
+    networking::{IpAddress, OnionAddress},
+};
 
 use super::AddressKey;
 use crate::NetAddress;
 
-#[derive(Clone, Copy, Serialize, Deserialize)]
+#[derive(Clone, Copy)]
 pub struct Entry {
     pub connection_failed_count: u64,
     pub address: NetAddress,
 }
 
-impl MemSizeEstimator for Entry {}
+/// Entry of an IP address, with the layout entries had before onion addresses were supported
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct DbIpEntry {
+    connection_failed_count: u64,
+    ip: IpAddress,
+    port: u16,
+}
+
+impl MemSizeEstimator for DbIpEntry {}
+
+/// Entry of an onion service, keyed by its virtual IP
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct DbOnionEntry {
+    connection_failed_count: u64,
+    onion: OnionAddress,
+    port: u16,
+}
+
+impl MemSizeEstimator for DbOnionEntry {}
+
+impl From<DbIpEntry> for Entry {
+    fn from(entry: DbIpEntry) -> Self {
+        Self { connection_failed_count: entry.connection_failed_count, address: NetAddress::new(entry.ip, entry.port) }
+    }
+}
+
+impl From<DbOnionEntry> for Entry {
+    fn from(entry: DbOnionEntry) -> Self {
+        Self { connection_failed_count: entry.connection_failed_count, address: NetAddress::new_onion(entry.onion, entry.port) }
+    }
+}
 
 pub trait AddressesStoreReader {
     #[allow(dead_code)]
@@ -72,45 +105,69 @@ impl From<DbAddressKey> for AddressKey {
     }
 }
 
+fn parse_entry<T: Into<Entry>>(key_bytes: &[u8], entry: T) -> Result<(AddressKey, Entry), Box<dyn Error>> {
+    let key = DbAddressKey(<[u8; ADDRESS_KEY_SIZE]>::try_from(key_bytes)?);
+    Ok((key.into(), entry.into()))
+}
+
+/// Stores the IP addresses and the onion services apart, so that the entries of IP addresses keep their layout
 #[derive(Clone)]
 pub struct DbAddressesStore {
     db: Arc<DB>,
-    access: CachedDbAccess<DbAddressKey, Entry>,
+    access: CachedDbAccess<DbAddressKey, DbIpEntry>,
+    onion_access: CachedDbAccess<DbAddressKey, DbOnionEntry>,
 }
 
 impl DbAddressesStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
-        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::Addresses.into()) }
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db.clone(), cache_policy, DatabaseStorePrefixes::Addresses.into()),
+            onion_access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::OnionAddresses.into()),
+        }
     }
 
     pub fn iterator(&self) -> impl Iterator<Item = Result<(AddressKey, Entry), Box<dyn Error>>> + '_ {
-        self.access.iterator().map(|iter_result| match iter_result {
-            Ok((key_bytes, connection_failed_count)) => match <[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]) {
-                Ok(address_key_slice) => {
-                    let addr_key = DbAddressKey(address_key_slice);
-                    let address: AddressKey = addr_key.into();
-                    Ok((address, connection_failed_count))
-                }
-                Err(e) => Err(e.into()),
-            },
-            Err(e) => Err(e),
-        })
+        let ip_entries = self.access.iterator().map(|iter_result| iter_result.and_then(|(key, entry)| parse_entry(&key, entry)));
+        let onion_entries =
+            self.onion_access.iterator().map(|iter_result| iter_result.and_then(|(key, entry)| parse_entry(&key, entry)));
+        ip_entries.chain(onion_entries)
     }
 }
 
 impl AddressesStoreReader for DbAddressesStore {
     fn get(&self, key: AddressKey) -> Result<Entry, StoreError> {
-        self.access.read(key.into())
+        if key.is_onion() {
+            self.onion_access.read(key.into()).map(Entry::from)
+        } else {
+            self.access.read(key.into()).map(Entry::from)
+        }
     }
 }
 
 impl AddressesStore for DbAddressesStore {
     fn set(&mut self, key: AddressKey, entry: Entry) -> StoreResult<()> {
-        self.access.write(DirectDbWriter::new(&self.db), key.into(), entry)
+        let Entry { connection_failed_count, address } = entry;
+        match address.onion {
+            Some(onion) => self.onion_access.write(
+                DirectDbWriter::new(&self.db),
+                key.into(),
+                DbOnionEntry { connection_failed_count, onion, port: address.port },
+            ),
+            None => self.access.write(
+                DirectDbWriter::new(&self.db),
+                key.into(),
+                DbIpEntry { connection_failed_count, ip: address.ip, port: address.port },
+            ),
+        }
     }
 
     fn remove(&mut self, key: AddressKey) -> StoreResult<()> {
-        self.access.delete(DirectDbWriter::new(&self.db), key.into())
+        if key.is_onion() {
+            self.onion_access.delete(DirectDbWriter::new(&self.db), key.into())
+        } else {
+            self.access.delete(DirectDbWriter::new(&self.db), key.into())
+        }
     }
 
     fn set_failed_count(&mut self, key: AddressKey, connection_failed_count: u64) -> StoreResult<()> {
@@ -8,6 +8,9 @@ use vecno_database::{
 };
 use vecno_utils::mem_size::MemSizeEstimator;
 
+/// Unix time, in milliseconds, at which the ban of an address expires.
+///
+/// Records written by previous versions hold the time of the ban instead, so they are found expired and get lifted.
 #[derive(Clone, Copy, Serialize, Deserialize)]
 pub struct ConnectionBanTimestamp(pub u64);
 
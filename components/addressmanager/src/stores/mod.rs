@@ -1,5 +1,6 @@
 use std::net::{IpAddr, Ipv6Addr};
 
+use vecno_utils::networking::IpAddress;
 pub use vecno_utils::networking::NetAddress;
 
 pub(super) mod address_store;
@@ -13,6 +14,11 @@ impl AddressKey {
         Self(ip, port)
     }
 
+    /// Whether the key is the one of an onion service, identified by its virtual IP
+    pub fn is_onion(&self) -> bool {
+        IpAddress::from(self.0).is_onion()
+    }
+
     pub fn is_ip(&self, ip: IpAddr) -> bool {
         match ip {
             IpAddr::V4(ip) => ip.to_ipv6_mapped() == self.0,
@@ -31,7 +31,7 @@ pub struct ConnectionManager {
     peers: &'static [&'static str], // Changed from dns_seeders to peers
     default_port: u16,
     address_manager: Arc<ParkingLotMutex<AddressManager>>,
-    connection_requests: TokioMutex<HashMap<SocketAddr, ConnectionRequest>>,
+    connection_requests: TokioMutex<HashMap<NetAddress, ConnectionRequest>>,
     force_next_iteration: UnboundedSender<()>,
     shutdown_signal: SingleTrigger,
 }
@@ -107,7 +107,7 @@ impl ConnectionManager {
         self.handle_inbound_connections(&peer_by_address).await;
     }
 
-    pub async fn add_connection_request(&self, address: SocketAddr, is_permanent: bool) {
+    pub async fn add_connection_request(&self, address: NetAddress, is_permanent: bool) {
         // If the request already exists, it resets the attempts count and overrides the `is_permanent` setting.
         self.connection_requests.lock().await.insert(address, ConnectionRequest::new(is_permanent));
         self.force_next_iteration.send(()).unwrap(); // We force the next iteration of the connection loop.
@@ -123,7 +123,7 @@ impl ConnectionManager {
         for (address, request) in requests.iter() {
             let address = *address;
             let request = request.clone();
-            let is_connected = peer_by_address.contains_key(&address);
+            let is_connected = peer_by_address.contains_key(&SocketAddr::from(address));
             if is_connected && !request.is_permanent {
                 // The peer is connected and the request is not permanent - no need to keep the request
                 continue;
@@ -186,10 +186,9 @@ impl ConnectionManager {
                     connecting = false;
                     break;
                 };
-                let socket_addr = SocketAddr::new(net_addr.ip.into(), net_addr.port).to_string();
-                debug!("Connecting to {}", &socket_addr);
+                debug!("Connecting to {}", net_addr);
                 addrs_to_connect.push(net_addr);
-                jobs.push(self.p2p_adaptor.connect_peer(socket_addr.clone()));
+                jobs.push(self.p2p_adaptor.connect_peer(net_addr.to_string()));
             }
 
             if progressing && !jobs.is_empty() {
@@ -273,8 +272,8 @@ impl ConnectionManager {
         }
     }
 
-    /// Bans the given IP and disconnects from all the peers with that IP.
-    pub async fn ban(&self, ip: IpAddr) {
+    /// Bans the given IP for `duration` and disconnects from all the peers with that IP.
+    pub async fn ban(&self, ip: IpAddr, duration: Duration) {
         if self.ip_has_permanent_connection(ip).await {
             return;
         }
@@ -283,7 +282,7 @@ impl ConnectionManager {
                 self.p2p_adaptor.terminate(peer.key()).await;
             }
         }
-        self.address_manager.lock().ban(ip.into());
+        self.address_manager.lock().ban(ip.into(), duration);
     }
 
     /// Returns whether the given address is banned.
@@ -293,11 +292,11 @@ impl ConnectionManager {
 
     /// Returns whether the given address is a permanent request.
     pub async fn is_permanent(&self, address: &SocketAddr) -> bool {
-        self.connection_requests.lock().await.contains_key(address)
+        self.connection_requests.lock().await.keys().any(|request_address| SocketAddr::from(*request_address) == *address)
     }
 
     /// Returns whether the given IP has some permanent request.
     pub async fn ip_has_permanent_connection(&self, ip: IpAddr) -> bool {
-        self.connection_requests.lock().await.iter().any(|(address, request)| request.is_permanent && address.ip() == ip)
+        self.connection_requests.lock().await.iter().any(|(address, request)| request.is_permanent && IpAddr::from(address.ip) == ip)
     }
 }
@@ -148,10 +148,13 @@ impl ConsensusManager {
     }
 
     fn worker(&self) {
-        let handles = self.inner.read().current.ctl.clone().start();
-        self.inner.write().handles.extend(handles);
-        // If current consensus is switched, this loop will join the replaced handles, and will switch to waiting for the new ones
         let mut g = self.inner.write();
+        // A staging consensus committed before the manager started (e.g. when importing a snapshot) is already running
+        if g.handles.is_empty() {
+            let handles = g.current.ctl.clone().start();
+            g.handles.extend(handles);
+        }
+        // If current consensus is switched, this loop will join the replaced handles, and will switch to waiting for the new ones
         while let Some(handle) = g.handles.pop_front() {
             drop(g);
             handle.join().unwrap();
@@ -173,6 +173,10 @@ impl ConsensusSessionOwned {
         self.consensus.validate_and_insert_block(block)
     }
 
+    pub fn validate_and_insert_header_chunk(&self, headers: Vec<Arc<Header>>) -> Vec<BlockValidationFutures> {
+        self.consensus.validate_and_insert_header_chunk(headers)
+    }
+
     pub fn validate_and_insert_block_batch(&self, mut batch: Vec<Block>) -> BlockProcessingBatch {
         // Sort by blue work in order to ensure topological order
         batch.sort_by(|a, b| a.header.blue_work.partial_cmp(&b.header.blue_work).unwrap());
@@ -397,6 +401,19 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(move |c| c.is_chain_block(hash)).await
     }
 
+    pub async fn async_get_chain_block_by_daa_score(&self, daa_score: u64) -> ConsensusResult<Arc<Header>> {
+        self.clone().spawn_blocking(move |c| c.get_chain_block_by_daa_score(daa_score)).await
+    }
+
+    pub async fn async_get_chain_blocks_in_blue_score_range(
+        &self,
+        low_blue_score: u64,
+        high_blue_score: u64,
+        limit: usize,
+    ) -> ConsensusResult<Vec<Arc<Header>>> {
+        self.clone().spawn_blocking(move |c| c.get_chain_blocks_in_blue_score_range(low_blue_score, high_blue_score, limit)).await
+    }
+
     pub async fn async_get_pruning_point_utxos(
         &self,
         expected_pruning_point: Hash,
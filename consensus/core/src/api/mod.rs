@@ -20,6 +20,7 @@ use crate::{
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
+    utxo::utxo_diff::UtxoDiff,
     BlockHashSet, BlueWorkType, ChainPath,
 };
 use vecno_hashes::Hash;
@@ -59,6 +60,12 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Same as [`ConsensusApi::validate_and_insert_block`] for each header of a chunk of header-only blocks. The PoW of the
+    /// whole chunk is verified in parallel first, so that header processing does not verify it again.
+    fn validate_and_insert_header_chunk(&self, headers: Vec<Arc<Header>>) -> Vec<BlockValidationFutures> {
+        unimplemented!()
+    }
+
     fn validate_and_insert_trusted_block(&self, tb: TrustedBlock) -> BlockValidationFutures {
         unimplemented!()
     }
@@ -310,10 +317,37 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the UTXO diff applied by the mergeset of a chain block on top of its selected parent.
+    ///
+    /// The diff remains available for blocks which were reorged out of the chain, until they are pruned.
+    fn get_chain_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        unimplemented!()
+    }
+
     fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
         unimplemented!()
     }
 
+    /// Returns the header of the selected chain block with the highest DAA score not exceeding `daa_score`.
+    ///
+    /// Only the selected chain from the pruning point up to the sink is searched, any other `daa_score` results in an error.
+    fn get_chain_block_by_daa_score(&self, daa_score: u64) -> ConsensusResult<Arc<Header>> {
+        unimplemented!()
+    }
+
+    /// Returns the headers of at most `limit` selected chain blocks with a blue score within `[low_blue_score, high_blue_score]`,
+    /// ordered by blue score.
+    ///
+    /// Only the selected chain from the pruning point up to the sink is searched.
+    fn get_chain_blocks_in_blue_score_range(
+        &self,
+        low_blue_score: u64,
+        high_blue_score: u64,
+        limit: usize,
+    ) -> ConsensusResult<Vec<Arc<Header>>> {
+        unimplemented!()
+    }
+
     fn get_pruning_point_utxos(
         &self,
         expected_pruning_point: Hash,
@@ -3,7 +3,7 @@ pub mod constants;
 pub mod genesis;
 pub mod params;
 
-use vecno_utils::networking::{ContextualNetAddress, NetAddress};
+use vecno_utils::networking::{AddressNetwork, ContextualNetAddress, NetAddress};
 
 #[cfg(feature = "devnet-prealloc")]
 use crate::utxo::utxo_collection::UtxoCollection;
@@ -42,6 +42,12 @@ pub struct Config {
     /// Enable the UTXO index
     pub utxoindex: bool,
 
+    /// Enable the transaction index
+    pub txindex: bool,
+
+    /// Enable the address history index
+    pub addressindex: bool,
+
     /// Enable RPC commands which affect the state of the node
     pub unsafe_rpc: bool,
 
@@ -66,6 +72,21 @@ pub struct Config {
 
     pub disable_upnp: bool,
 
+    /// Offer peers to encrypt the p2p connections
+    pub p2p_encryption: bool,
+
+    /// Reject the peers not encrypting the p2p connections
+    pub p2p_require_encryption: bool,
+
+    /// SOCKS5 proxy the outbound p2p connections are made through
+    pub p2p_proxy: Option<NetAddress>,
+
+    /// SOCKS5 proxy the outbound p2p connections to onion services are made through
+    pub p2p_onion_proxy: Option<NetAddress>,
+
+    /// Networks the outbound p2p connections are restricted to, all networks if empty
+    pub p2p_only_networks: Vec<AddressNetwork>,
+
     /// A scale factor to apply to memory allocation bounds
     pub ram_scale: f64,
 }
@@ -83,6 +104,8 @@ impl Config {
             is_archival: false,
             enable_sanity_checks: false,
             utxoindex: false,
+            txindex: false,
+            addressindex: false,
             unsafe_rpc: false,
             enable_unsynced_mining: false,
             enable_mainnet_mining: false,
@@ -94,6 +117,11 @@ impl Config {
             #[cfg(feature = "devnet-prealloc")]
             initial_utxo_set: Default::default(),
             disable_upnp: false,
+            p2p_encryption: false,
+            p2p_require_encryption: false,
+            p2p_proxy: None,
+            p2p_onion_proxy: None,
+            p2p_only_networks: Default::default(),
             ram_scale: 1.0,
         }
     }
@@ -17,6 +17,51 @@ pub enum ConfigError {
     #[error("Configuration: --max-tracked-addresses cannot be set above {0}")]
     MaxTrackedAddressesTooHigh(usize),
 
+    #[error("Configuration: --stratum-listen requires --stratum-pay-address")]
+    MissingStratumPayAddress,
+
+    #[error("Configuration: --stratum-pay-address {0} is not a valid address of the selected network")]
+    InvalidStratumPayAddress(String),
+
+    #[error("Configuration: --stratum-difficulty cannot be set below {0}")]
+    StratumDifficultyTooLow(f64),
+
+    #[error("Configuration: --mine-to is only available on devnet and simnet")]
+    MiningOnNonDevnet,
+
+    #[error("Configuration: --mine-to {0} is not a valid address of the selected network")]
+    InvalidMineToAddress(String),
+
+    #[error("Configuration: --miner-threads cannot be set below 1")]
+    MinerThreadsTooLow,
+
+    #[error("Configuration: --export-snapshot and --import-snapshot cannot be used together")]
+    MixedExportAndImportSnapshot,
+
+    #[error("Configuration: mempool-policy blocked address {0} is not an address of the selected network")]
+    InvalidMempoolPolicyAddress(String),
+
+    #[error("Configuration: mempool-policy minimum-feerate must be a positive number")]
+    InvalidMempoolPolicyFeerate,
+
+    #[error("Configuration: template-policy prioritised-mass-share must be between 0 and 1")]
+    InvalidTemplatePolicyMassShare,
+
+    #[error("Configuration: --banthreshold, --banduration and --banscorehalflife must be above 0")]
+    InvalidBanSettings,
+
+    #[error("Configuration: --dandelionembargo must be above 0 and --dandelionfluffprobability between 0 and 1")]
+    InvalidDandelionSettings,
+
+    #[error("Configuration: --proxy and --onion-proxy must be IP addresses")]
+    InvalidProxyAddress,
+
+    #[error("Configuration: --onlynet=onion requires --proxy or --onion-proxy")]
+    OnionNetworkWithoutProxy,
+
+    #[error("Configuration: --proxy requires --nodnsseed or --connect, since DNS seeds cannot be resolved through the proxy")]
+    DnsSeedingWithProxy,
+
     #[cfg(feature = "devnet-prealloc")]
     #[error("Cannot preallocate UTXOs on any network except devnet")]
     PreallocUtxosOnNonDevnet,
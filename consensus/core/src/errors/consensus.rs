@@ -17,6 +17,12 @@ pub enum ConsensusError {
     #[error("some data is missing for block {0}")]
     MissingData(Hash),
 
+    #[error("DAA score {0} is not covered by the retained selected chain")]
+    DaaScoreNotInSelectedChain(u64),
+
+    #[error("cannot find the selected chain block at index {0}")]
+    SelectedChainIndexNotFound(u64),
+
     #[error("got unexpected pruning point")]
     UnexpectedPruningPoint,
 
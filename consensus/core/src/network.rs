@@ -66,6 +66,24 @@ impl NetworkType {
         }
     }
 
+    pub fn default_prometheus_port(&self) -> u16 {
+        match self {
+            NetworkType::Mainnet => 7112,
+            NetworkType::Testnet => 7212,
+            NetworkType::Simnet => 7312,
+            NetworkType::Devnet => 7412,
+        }
+    }
+
+    pub fn default_stratum_port(&self) -> u16 {
+        match self {
+            NetworkType::Mainnet => 7113,
+            NetworkType::Testnet => 7213,
+            NetworkType::Simnet => 7313,
+            NetworkType::Devnet => 7413,
+        }
+    }
+
     pub fn iter() -> impl Iterator<Item = Self> {
         static NETWORK_TYPES: [NetworkType; 4] =
             [NetworkType::Mainnet, NetworkType::Testnet, NetworkType::Devnet, NetworkType::Simnet];
@@ -1,6 +1,11 @@
 use derive_more::Display;
 use std::sync::Arc;
-use vecno_consensus_core::{acceptance_data::AcceptanceData, block::Block, utxo::utxo_diff::UtxoDiff};
+use vecno_consensus_core::{
+    acceptance_data::AcceptanceData,
+    block::Block,
+    tx::{ScriptPublicKey, TransactionId},
+    utxo::utxo_diff::UtxoDiff,
+};
 use vecno_hashes::Hash;
 use vecno_notify::{
     events::EventType,
@@ -42,6 +47,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "MempoolTransactionRemoved notification: transaction {} ({})", "_0.transaction_id", "_0.reason")]
+    MempoolTransactionRemoved(MempoolTransactionRemovedNotification),
 }
 }
 
@@ -87,6 +95,15 @@ impl NotificationTrait for Notification {
         Some(self.clone())
     }
 
+    fn apply_mempool_transaction_removed_subscription(
+        &self,
+        _subscription: &UtxosChangedSubscription,
+        _context: &SubscriptionContext,
+    ) -> Option<Self> {
+        // Same as for UtxosChanged, addresses are applied farther along the notification backbone.
+        Some(self.clone())
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
@@ -181,3 +198,40 @@ pub struct PruningPointUtxoSetOverrideNotification {}
 
 #[derive(Debug, Clone)]
 pub struct NewBlockTemplateNotification {}
+
+/// Reason why a transaction was removed from the mempool without being accepted by consensus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum MempoolRemovalReason {
+    /// Evicted to make room for transactions paying a higher fee rate
+    #[display(fmt = "evicted")]
+    Evicted,
+    /// Expired after staying in the mempool for too long
+    #[display(fmt = "expired")]
+    Expired,
+    /// Double spends a transaction accepted by consensus
+    #[display(fmt = "double spend")]
+    DoubleSpend,
+    /// Replaced by a transaction paying a higher fee (RBF)
+    #[display(fmt = "replaced by fee")]
+    ReplacedByFee,
+    /// Found invalid while building a block template
+    #[display(fmt = "invalid in block template")]
+    InvalidInBlockTemplate,
+    /// Spends outpoints which are no longer available
+    #[display(fmt = "missing outpoints")]
+    MissingOutpoints,
+}
+
+#[derive(Debug, Clone)]
+pub struct MempoolTransactionRemovedNotification {
+    pub transaction_id: TransactionId,
+    pub reason: MempoolRemovalReason,
+    /// Script public keys of the transaction outputs and of the spent UTXOs, when known
+    pub script_public_keys: Arc<Vec<ScriptPublicKey>>,
+}
+
+impl MempoolTransactionRemovedNotification {
+    pub fn new(transaction_id: TransactionId, reason: MempoolRemovalReason, script_public_keys: Arc<Vec<ScriptPublicKey>>) -> Self {
+        Self { transaction_id, reason, script_public_keys }
+    }
+}
@@ -1,33 +1,62 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 
+use vecno_consensus_core::header::Header;
 use vecno_hashes::Hash;
-use vecno_pow::{matrix::Matrix, xoshiro::XoShiRo256PlusPlus};
+use vecno_pow::{check_headers_pow_batch, mem_hash::mem_hash, State};
 
 // Big enough to make the cache not very useful
 const ITERS: usize = 1024;
 
-fn bench_pow(c: &mut Criterion) {
-    let mut gen = XoShiRo256PlusPlus::new(Hash::from_bytes([42; 32]));
-    let gen_hash = |gen: &mut XoShiRo256PlusPlus| Hash::from_le_u64([gen.u64(), gen.u64(), gen.u64(), gen.u64()]);
-    let matrices: Vec<_> = (0..ITERS).map(|_| Matrix::generate(gen_hash(&mut gen))).collect();
-    let hashes: Vec<_> = (0..ITERS).map(|_| gen_hash(&mut gen)).collect();
+const NONCES: u64 = 1024;
+
+fn header(hash_merkle_root: Hash, timestamp: u64) -> Header {
+    let mut header = Header::from_precomputed_hash(Hash::default(), vec![]);
+    header.hash_merkle_root = hash_merkle_root;
+    header.timestamp = timestamp;
+    header.bits = 0x1e7fffff;
+    header
+}
 
-    c.bench_function("Compute Rank", |b| {
+fn bench_mem_hash(c: &mut Criterion) {
+    let hashes: Vec<_> = (0..ITERS).map(|_| Hash::from_bytes(rand::random())).collect();
+
+    c.bench_function("HeavyHash", |b| {
         b.iter(|| {
-            for matrix in &matrices {
-                black_box(matrix.compute_rank());
+            for (nonce, &hash) in hashes.iter().enumerate() {
+                black_box(mem_hash(hash, 1_700_000_000_000, nonce as u64));
             }
         });
     });
+}
 
-    c.bench_function("HeavyHash", |b| {
+fn bench_pow(c: &mut Criterion) {
+    let state = State::new(&header(Hash::from_bytes([42; 32]), 1_700_000_000_000));
+    let nonces = (0..NONCES).collect::<Vec<_>>();
+    let headers: Vec<_> = (0..NONCES)
+        .map(|nonce| {
+            let mut header = header(Hash::from_bytes(rand::random()), 1_700_000_000_000 + nonce);
+            header.nonce = nonce;
+            header
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("PoW");
+    group.throughput(Throughput::Elements(NONCES));
+    group.bench_function(BenchmarkId::new("calculate_pow", NONCES), |b| {
         b.iter(|| {
-            for (matrix, &hash) in matrices.iter().zip(hashes.iter()) {
-                black_box(matrix.mem_hash(hash));
+            for &nonce in &nonces {
+                black_box(state.calculate_pow(nonce));
             }
         });
     });
+    group.bench_function(BenchmarkId::new("calculate_pow_batch", NONCES), |b| {
+        b.iter(|| black_box(state.calculate_pow_batch(&nonces)));
+    });
+    group.bench_function(BenchmarkId::new("check_headers_pow_batch", NONCES), |b| {
+        b.iter(|| black_box(check_headers_pow_batch(&headers)));
+    });
+    group.finish();
 }
 
-criterion_group!(benches, bench_pow);
+criterion_group!(benches, bench_mem_hash, bench_pow);
 criterion_main!(benches);
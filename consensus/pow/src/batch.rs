@@ -0,0 +1,562 @@
+//!
+//! Batched proof of work, computing the hashes of several nonces side by side.
+//!
+//! Every hash involved in the proof of work is a BLAKE3 hash of at most 64 bytes, except for the initial
+//! `PRE_POW_HASH || TIME || 32 zero byte padding || NONCE` hash whose first block is the same for all the nonces
+//! of a header and is thus compressed once per header. Each hash then boils down to a single BLAKE3 compression,
+//! which is computed over one nonce per lane of AVX2 or SSE4.1 vectors, selected at runtime. The lanes do not need
+//! to share their header, so that many nonces of a header being mined and the nonces of many headers being verified
+//! are batched alike.
+//!
+//! The S-box and the number of rounds of `MemHash` are derived from the PoW hash, which includes the nonce, so
+//! they are computed per lane as well. Lanes needing fewer rounds keep their result while the others proceed.
+//!
+//! The scalar path of [`crate::mem_hash`] remains the reference, the batched path being bit-identical to it.
+//!
+
+// Lane loops index several arrays with the same lane index
+#![allow(clippy::needless_range_loop)]
+
+#[cfg(target_arch = "x86")]
+use std::arch::x86::{__m128i, __m256i};
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::{__m128i, __m256i};
+use std::{array, mem::size_of};
+use vecno_hashes::Hash;
+use vecno_math::Uint256;
+
+type Lanes<const N: usize> = [u32; N];
+
+const IV: [u32; 8] = [0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19];
+
+/// Message word order of each round, the successive applications of the BLAKE3 message permutation
+const MSG_SCHEDULE: [[usize; 16]; 7] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8],
+    [3, 4, 10, 12, 13, 2, 7, 14, 6, 5, 9, 0, 11, 15, 8, 1],
+    [10, 7, 12, 9, 14, 3, 13, 15, 4, 0, 11, 2, 5, 8, 1, 6],
+    [12, 13, 9, 11, 15, 10, 14, 8, 7, 2, 5, 3, 0, 1, 6, 4],
+    [9, 14, 11, 5, 8, 12, 15, 1, 13, 3, 0, 10, 2, 6, 4, 7],
+    [11, 15, 5, 0, 1, 9, 8, 6, 14, 10, 2, 12, 3, 4, 7, 13],
+];
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const ROOT: u32 = 1 << 3;
+
+/// Size in bytes of the round counter hashed by the mixing loop of `MemHash`, which hashes it as a `usize`
+const ROUND_SIZE: u32 = size_of::<usize>() as u32;
+
+/// State of a header shared by all of its nonces
+#[derive(Clone, Copy)]
+pub(crate) struct BatchState {
+    /// Chaining value of the first block of `PRE_POW_HASH || TIME || 32 zero byte padding || NONCE`
+    prefix: [u32; 8],
+    timestamp: u64,
+}
+
+impl BatchState {
+    pub(crate) fn new(pre_pow_hash: Hash, timestamp: u64) -> Self {
+        let words = hash_words(&pre_pow_hash);
+        let mut block = [[0u32; 1]; 16];
+        for i in 0..8 {
+            block[i] = [words[i]];
+        }
+        block[8] = [timestamp as u32];
+        block[9] = [(timestamp >> 32) as u32];
+        let prefix = compress::<1, u32>(&IV.map(|word| [word]), &block, 64, CHUNK_START).map(|[word]| word);
+        Self { prefix, timestamp }
+    }
+}
+
+/// Computes the proof of work of each header state with its nonce, using the widest lanes supported by the CPU
+pub(crate) fn calculate_pow_batch(items: &[(BatchState, u64)]) -> Vec<Uint256> {
+    let mut pows = Vec::with_capacity(items.len());
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx2") {
+            // Safety: the CPU supports AVX2
+            unsafe { calculate_pow_batch_avx2(items, &mut pows) };
+            return pows;
+        }
+        if is_x86_feature_detected!("sse4.1") {
+            // Safety: the CPU supports SSE4.1
+            unsafe { calculate_pow_batch_sse41(items, &mut pows) };
+            return pows;
+        }
+    }
+    calculate_pow_batch_lanes::<1, u32>(items, &mut pows);
+    pows
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn calculate_pow_batch_avx2(items: &[(BatchState, u64)], pows: &mut Vec<Uint256>) {
+    calculate_pow_batch_lanes::<8, __m256i>(items, pows)
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse4.1")]
+unsafe fn calculate_pow_batch_sse41(items: &[(BatchState, u64)], pows: &mut Vec<Uint256>) {
+    calculate_pow_batch_lanes::<4, __m128i>(items, pows)
+}
+
+#[inline(always)]
+fn calculate_pow_batch_lanes<const N: usize, W: Word<N>>(items: &[(BatchState, u64)], pows: &mut Vec<Uint256>) {
+    for chunk in items.chunks(N) {
+        // The lanes of an incomplete chunk are filled with its last item and their result dropped
+        let mut lanes = [chunk[chunk.len() - 1]; N];
+        lanes[..chunk.len()].copy_from_slice(chunk);
+        pows.extend_from_slice(&calculate_pow_lanes::<N, W>(&lanes)[..chunk.len()]);
+    }
+}
+
+/// Lane-wise version of `State::calculate_pow`, each step mirroring its scalar counterpart in `MemHash`
+#[inline(always)]
+fn calculate_pow_lanes<const N: usize, W: Word<N>>(items: &[(BatchState, u64); N]) -> [Uint256; N] {
+    let zero = [0u32; N];
+    let prefix: [Lanes<N>; 8] = array::from_fn(|i| array::from_fn(|l| items[l].0.prefix[i]));
+    let timestamp_low: Lanes<N> = array::from_fn(|l| items[l].0.timestamp as u32);
+    let timestamp_high: Lanes<N> = array::from_fn(|l| (items[l].0.timestamp >> 32) as u32);
+    let nonce_low: Lanes<N> = array::from_fn(|l| items[l].1 as u32);
+    let nonce_high: Lanes<N> = array::from_fn(|l| (items[l].1 >> 32) as u32);
+
+    // Last block of the PoW hash: the end of the zero padding followed by the nonce
+    let mut block = [zero; 16];
+    block[2] = nonce_low;
+    block[3] = nonce_high;
+    let input = compress::<N, W>(&prefix, &block, 16, CHUNK_END | ROOT);
+
+    // S-box, made of the first 2 bytes of each hash of a chain of hashes of the input
+    let mut sbox = [[0u8; 64]; N];
+    let mut seed = hash_words_lanes::<N, W>(&input);
+    for i in 0..32 {
+        for l in 0..N {
+            sbox[l][i * 2..i * 2 + 2].copy_from_slice(&seed[0][l].to_le_bytes()[..2]);
+        }
+        if i < 31 {
+            seed = hash_words_lanes::<N, W>(&seed);
+        }
+    }
+
+    // Number of rounds, which differs among the lanes
+    let mut block = [zero; 16];
+    block[..8].copy_from_slice(&input);
+    block[8] = timestamp_low;
+    block[9] = timestamp_high;
+    let rounds_hash = hash_block::<N, W>(&block, 40)[0];
+    let rounds: Lanes<N> = array::from_fn(|l| rounds_hash[l] % 8 + 16);
+    let max_rounds = rounds.iter().copied().max().unwrap_or_default();
+
+    // Both hashing loops, the lanes with fewer rounds keeping their result once done
+    let mut result = input;
+    for k in 0..max_rounds * 2 {
+        let hashed = bit_manipulations(hash_words_lanes::<N, W>(&result));
+        for i in 0..8 {
+            for l in 0..N {
+                if k < rounds[l] * 2 {
+                    result[i][l] = hashed[i][l];
+                }
+            }
+        }
+    }
+
+    // Mixing loop
+    for round in 0..max_rounds as usize {
+        for i in 0..8 {
+            let mut block = [zero; 16];
+            block[0] = result[i];
+            block[1] = [round as u32; N];
+            let nonce_offset = 1 + ROUND_SIZE as usize / 4;
+            block[nonce_offset] = nonce_low;
+            block[nonce_offset + 1] = nonce_high;
+            let state = hash_block::<N, W>(&block, 4 + ROUND_SIZE + 8)[0];
+
+            for l in 0..N {
+                if round as u32 >= rounds[l] {
+                    continue;
+                }
+                let operand = result[(i + 1) % 8][l];
+                let mut v = state[l] ^ result[0][l] ^ result[i][l];
+                v = match v & 0x3 {
+                    0 => v.wrapping_add(operand),
+                    1 => v.wrapping_sub(operand),
+                    2 => v.rotate_left(operand & 0x1F),
+                    _ => v ^ operand,
+                };
+                let idx_base = (v % 64) as usize;
+                v = u32::from_le_bytes(v.to_le_bytes().map(|byte| sbox[l][(idx_base + byte as usize) % 64]));
+                result[i][l] = v;
+            }
+        }
+    }
+
+    let output = hash_words_lanes::<N, W>(&bit_manipulations(result));
+    array::from_fn(|l| {
+        let mut bytes = [0u8; 32];
+        for i in 0..8 {
+            bytes[i * 4..i * 4 + 4].copy_from_slice(&output[i][l].to_le_bytes());
+        }
+        Uint256::from_le_bytes(bytes)
+    })
+}
+
+fn hash_words(hash: &Hash) -> [u32; 8] {
+    let bytes = hash.as_bytes();
+    array::from_fn(|i| u32::from_le_bytes(bytes[i * 4..i * 4 + 4].try_into().unwrap()))
+}
+
+/// XORs the odd bytes into the even bytes preceding them, as `MemHash::bit_manipulations` does on the byte
+/// representation of the words
+#[inline(always)]
+fn bit_manipulations<const N: usize>(mut words: [Lanes<N>; 8]) -> [Lanes<N>; 8] {
+    for i in 0..8 {
+        for l in 0..N {
+            words[i][l] ^= (words[i][l] >> 8) & 0x00FF00FF;
+        }
+    }
+    words
+}
+
+/// BLAKE3 hash of 32 bytes given as words
+#[inline(always)]
+fn hash_words_lanes<const N: usize, W: Word<N>>(words: &[Lanes<N>; 8]) -> [Lanes<N>; 8] {
+    let mut block = [[0u32; N]; 16];
+    block[..8].copy_from_slice(words);
+    hash_block::<N, W>(&block, 32)
+}
+
+/// BLAKE3 hash of a message of `len` bytes fitting in a single block
+#[inline(always)]
+fn hash_block<const N: usize, W: Word<N>>(block: &[Lanes<N>; 16], len: u32) -> [Lanes<N>; 8] {
+    compress::<N, W>(&IV.map(|word| [word; N]), block, len, CHUNK_START | CHUNK_END | ROOT)
+}
+
+/// BLAKE3 compression of the first block of a chunk, returning the chaining value, which is the hash when `flags`
+/// contains `ROOT`
+#[inline(always)]
+fn compress<const N: usize, W: Word<N>>(cv: &[Lanes<N>; 8], block: &[Lanes<N>; 16], block_len: u32, flags: u32) -> [Lanes<N>; 8] {
+    let m: [W; 16] = array::from_fn(|i| W::load(&block[i]));
+    let mut state = [
+        W::load(&cv[0]),
+        W::load(&cv[1]),
+        W::load(&cv[2]),
+        W::load(&cv[3]),
+        W::load(&cv[4]),
+        W::load(&cv[5]),
+        W::load(&cv[6]),
+        W::load(&cv[7]),
+        W::splat(IV[0]),
+        W::splat(IV[1]),
+        W::splat(IV[2]),
+        W::splat(IV[3]),
+        // The block counter is 0 for the first chunk
+        W::splat(0),
+        W::splat(0),
+        W::splat(block_len),
+        W::splat(flags),
+    ];
+    // Unrolled so that the message words of each round are known at compile time
+    round(&mut state, &m, &MSG_SCHEDULE[0]);
+    round(&mut state, &m, &MSG_SCHEDULE[1]);
+    round(&mut state, &m, &MSG_SCHEDULE[2]);
+    round(&mut state, &m, &MSG_SCHEDULE[3]);
+    round(&mut state, &m, &MSG_SCHEDULE[4]);
+    round(&mut state, &m, &MSG_SCHEDULE[5]);
+    round(&mut state, &m, &MSG_SCHEDULE[6]);
+    array::from_fn(|i| state[i].xor(state[i + 8]).store())
+}
+
+#[inline(always)]
+fn round<const N: usize, W: Word<N>>(state: &mut [W; 16], m: &[W; 16], schedule: &[usize; 16]) {
+    // Columns
+    g(state, 0, 4, 8, 12, m[schedule[0]], m[schedule[1]]);
+    g(state, 1, 5, 9, 13, m[schedule[2]], m[schedule[3]]);
+    g(state, 2, 6, 10, 14, m[schedule[4]], m[schedule[5]]);
+    g(state, 3, 7, 11, 15, m[schedule[6]], m[schedule[7]]);
+    // Diagonals
+    g(state, 0, 5, 10, 15, m[schedule[8]], m[schedule[9]]);
+    g(state, 1, 6, 11, 12, m[schedule[10]], m[schedule[11]]);
+    g(state, 2, 7, 8, 13, m[schedule[12]], m[schedule[13]]);
+    g(state, 3, 4, 9, 14, m[schedule[14]], m[schedule[15]]);
+}
+
+#[inline(always)]
+fn g<const N: usize, W: Word<N>>(state: &mut [W; 16], a: usize, b: usize, c: usize, d: usize, mx: W, my: W) {
+    state[a] = state[a].add(state[b]).add(mx);
+    state[d] = state[d].xor(state[a]).rotate_right_16();
+    state[c] = state[c].add(state[d]);
+    state[b] = state[b].xor(state[c]).rotate_right_12();
+    state[a] = state[a].add(state[b]).add(my);
+    state[d] = state[d].xor(state[a]).rotate_right_8();
+    state[c] = state[c].add(state[d]);
+    state[b] = state[b].xor(state[c]).rotate_right_7();
+}
+
+/// `N` lanes of 32 bits words, on which the compression operates
+///
+/// The SIMD implementations must only be used from functions enabling their target feature.
+trait Word<const N: usize>: Copy {
+    fn load(lanes: &Lanes<N>) -> Self;
+    fn store(self) -> Lanes<N>;
+    fn splat(word: u32) -> Self;
+    fn add(self, other: Self) -> Self;
+    fn xor(self, other: Self) -> Self;
+    fn rotate_right_16(self) -> Self;
+    fn rotate_right_12(self) -> Self;
+    fn rotate_right_8(self) -> Self;
+    fn rotate_right_7(self) -> Self;
+}
+
+impl Word<1> for u32 {
+    #[inline(always)]
+    fn load(lanes: &Lanes<1>) -> Self {
+        lanes[0]
+    }
+
+    #[inline(always)]
+    fn store(self) -> Lanes<1> {
+        [self]
+    }
+
+    #[inline(always)]
+    fn splat(word: u32) -> Self {
+        word
+    }
+
+    #[inline(always)]
+    fn add(self, other: Self) -> Self {
+        self.wrapping_add(other)
+    }
+
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    #[inline(always)]
+    fn rotate_right_16(self) -> Self {
+        self.rotate_right(16)
+    }
+
+    #[inline(always)]
+    fn rotate_right_12(self) -> Self {
+        self.rotate_right(12)
+    }
+
+    #[inline(always)]
+    fn rotate_right_8(self) -> Self {
+        self.rotate_right(8)
+    }
+
+    #[inline(always)]
+    fn rotate_right_7(self) -> Self {
+        self.rotate_right(7)
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    use super::{Lanes, Word};
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    impl Word<4> for __m128i {
+        #[inline(always)]
+        fn load(lanes: &Lanes<4>) -> Self {
+            unsafe { _mm_loadu_si128(lanes.as_ptr() as *const __m128i) }
+        }
+
+        #[inline(always)]
+        fn store(self) -> Lanes<4> {
+            let mut lanes = [0u32; 4];
+            unsafe { _mm_storeu_si128(lanes.as_mut_ptr() as *mut __m128i, self) };
+            lanes
+        }
+
+        #[inline(always)]
+        fn splat(word: u32) -> Self {
+            unsafe { _mm_set1_epi32(word as i32) }
+        }
+
+        #[inline(always)]
+        fn add(self, other: Self) -> Self {
+            unsafe { _mm_add_epi32(self, other) }
+        }
+
+        #[inline(always)]
+        fn xor(self, other: Self) -> Self {
+            unsafe { _mm_xor_si128(self, other) }
+        }
+
+        #[inline(always)]
+        fn rotate_right_16(self) -> Self {
+            unsafe { _mm_shuffle_epi8(self, _mm_set_epi8(13, 12, 15, 14, 9, 8, 11, 10, 5, 4, 7, 6, 1, 0, 3, 2)) }
+        }
+
+        #[inline(always)]
+        fn rotate_right_12(self) -> Self {
+            unsafe { _mm_or_si128(_mm_srli_epi32(self, 12), _mm_slli_epi32(self, 20)) }
+        }
+
+        #[inline(always)]
+        fn rotate_right_8(self) -> Self {
+            unsafe { _mm_shuffle_epi8(self, _mm_set_epi8(12, 15, 14, 13, 8, 11, 10, 9, 4, 7, 6, 5, 0, 3, 2, 1)) }
+        }
+
+        #[inline(always)]
+        fn rotate_right_7(self) -> Self {
+            unsafe { _mm_or_si128(_mm_srli_epi32(self, 7), _mm_slli_epi32(self, 25)) }
+        }
+    }
+
+    impl Word<8> for __m256i {
+        #[inline(always)]
+        fn load(lanes: &Lanes<8>) -> Self {
+            unsafe { _mm256_loadu_si256(lanes.as_ptr() as *const __m256i) }
+        }
+
+        #[inline(always)]
+        fn store(self) -> Lanes<8> {
+            let mut lanes = [0u32; 8];
+            unsafe { _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, self) };
+            lanes
+        }
+
+        #[inline(always)]
+        fn splat(word: u32) -> Self {
+            unsafe { _mm256_set1_epi32(word as i32) }
+        }
+
+        #[inline(always)]
+        fn add(self, other: Self) -> Self {
+            unsafe { _mm256_add_epi32(self, other) }
+        }
+
+        #[inline(always)]
+        fn xor(self, other: Self) -> Self {
+            unsafe { _mm256_xor_si256(self, other) }
+        }
+
+        #[inline(always)]
+        fn rotate_right_16(self) -> Self {
+            unsafe {
+                _mm256_shuffle_epi8(
+                    self,
+                    _mm256_set_epi8(
+                        13, 12, 15, 14, 9, 8, 11, 10, 5, 4, 7, 6, 1, 0, 3, 2, 13, 12, 15, 14, 9, 8, 11, 10, 5, 4, 7, 6, 1, 0, 3, 2,
+                    ),
+                )
+            }
+        }
+
+        #[inline(always)]
+        fn rotate_right_12(self) -> Self {
+            unsafe { _mm256_or_si256(_mm256_srli_epi32(self, 12), _mm256_slli_epi32(self, 20)) }
+        }
+
+        #[inline(always)]
+        fn rotate_right_8(self) -> Self {
+            unsafe {
+                _mm256_shuffle_epi8(
+                    self,
+                    _mm256_set_epi8(
+                        12, 15, 14, 13, 8, 11, 10, 9, 4, 7, 6, 5, 0, 3, 2, 1, 12, 15, 14, 13, 8, 11, 10, 9, 4, 7, 6, 5, 0, 3, 2, 1,
+                    ),
+                )
+            }
+        }
+
+        #[inline(always)]
+        fn rotate_right_7(self) -> Self {
+            unsafe { _mm256_or_si256(_mm256_srli_epi32(self, 7), _mm256_slli_epi32(self, 25)) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::State;
+    use proptest::prelude::*;
+    use std::str::FromStr;
+    use vecno_consensus_core::header::Header;
+
+    /// Compares with the known BLAKE3 hash of "abc"
+    #[test]
+    fn test_compress() {
+        let mut block = [[0u32; 1]; 16];
+        block[0] = [u32::from_le_bytes(*b"abc\0")];
+        let hash = hash_block::<1, u32>(&block, 3).map(|[word]| word);
+        let expected = Hash::from_str("6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85").unwrap();
+        assert_eq!(hash, hash_words(&expected));
+    }
+
+    fn state(hash_merkle_root: [u8; 32], timestamp: u64) -> State {
+        let mut header = Header::from_precomputed_hash(Hash::default(), vec![]);
+        header.hash_merkle_root = Hash::from_bytes(hash_merkle_root);
+        header.timestamp = timestamp;
+        header.bits = 0x1e7fffff;
+        State::new(&header)
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn test_batch_matches_scalar(
+            hash_merkle_root in any::<[u8; 32]>(),
+            timestamp in any::<u64>(),
+            nonces in prop::collection::vec(any::<u64>(), 0..20),
+        ) {
+            let state = state(hash_merkle_root, timestamp);
+            let expected = nonces.iter().map(|&nonce| state.calculate_pow(nonce)).collect::<Vec<_>>();
+            prop_assert_eq!(&state.calculate_pow_batch(&nonces), &expected);
+
+            let batch = BatchState::new(state.pre_pow_hash, state.timestamp);
+            let items = nonces.iter().map(|&nonce| (batch, nonce)).collect::<Vec<_>>();
+            check_all_paths(&items, &expected)?;
+        }
+
+        #[test]
+        fn test_multi_header_batch_matches_scalar(
+            headers in prop::collection::vec((any::<[u8; 32]>(), any::<u64>(), any::<u64>()), 0..20),
+        ) {
+            let states = headers.iter().map(|&(hash_merkle_root, timestamp, _)| state(hash_merkle_root, timestamp)).collect::<Vec<_>>();
+            let expected = states.iter().zip(&headers).map(|(state, &(_, _, nonce))| state.calculate_pow(nonce)).collect::<Vec<_>>();
+
+            let items = states
+                .iter()
+                .zip(&headers)
+                .map(|(state, &(_, _, nonce))| (BatchState::new(state.pre_pow_hash, state.timestamp), nonce))
+                .collect::<Vec<_>>();
+            check_all_paths(&items, &expected)?;
+        }
+    }
+
+    /// Every path must match the scalar one, whichever is picked for the CPU
+    fn check_all_paths(items: &[(BatchState, u64)], expected: &[Uint256]) -> Result<(), TestCaseError> {
+        prop_assert_eq!(&calculate_pow_batch(items), expected);
+        let mut pows = Vec::new();
+        calculate_pow_batch_lanes::<1, u32>(items, &mut pows);
+        prop_assert_eq!(&pows, expected);
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("sse4.1") {
+                pows.clear();
+                // Safety: the CPU supports SSE4.1
+                unsafe { calculate_pow_batch_sse41(items, &mut pows) };
+                prop_assert_eq!(&pows, expected);
+            }
+            if is_x86_feature_detected!("avx2") {
+                pows.clear();
+                // Safety: the CPU supports AVX2
+                unsafe { calculate_pow_batch_avx2(items, &mut pows) };
+                prop_assert_eq!(&pows, expected);
+            }
+        }
+        Ok(())
+    }
+}
@@ -48,7 +48,7 @@ impl PoW {
         // PRE_POW_HASH || TIME || 32 zero byte padding || NONCE
         let hasher = PowHash::new(pre_pow_hash, timestamp.unwrap_or(header.timestamp));
 
-        Ok(Self { inner: crate::State { target, hasher, timestamp: timestamp.unwrap_or(header.timestamp)}, pre_pow_hash })
+        Ok(Self { inner: crate::State { target, hasher, pre_pow_hash, timestamp: timestamp.unwrap_or(header.timestamp)}, pre_pow_hash })
     }
 
     /// The target based on the provided bits.
@@ -91,7 +91,7 @@ impl PoW {
         // Initialize the hasher using pre_pow_hash and timestamp
         let hasher = PowHash::new(pre_pow_hash, timestamp);
 
-        Ok(PoW { inner: crate::State { target, hasher, timestamp }, pre_pow_hash })
+        Ok(PoW { inner: crate::State { target, hasher, pre_pow_hash, timestamp }, pre_pow_hash })
     }
 }
 
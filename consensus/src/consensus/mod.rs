@@ -24,6 +24,7 @@ use crate::{
             relations::RelationsStoreReader,
             statuses::StatusesStoreReader,
             tips::TipsStoreReader,
+            utxo_diffs::UtxoDiffsStoreReader,
             utxo_set::{UtxoSetStore, UtxoSetStoreReader},
             DB,
         },
@@ -67,7 +68,8 @@ use vecno_consensus_core::{
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
-    BlockHashSet, BlueWorkType, ChainPath, HashMapCustomHasher,
+    utxo::utxo_diff::UtxoDiff,
+    BlockHashSet, BlockLevel, BlueWorkType, ChainPath, HashMapCustomHasher,
 };
 use vecno_consensus_notify::root::ConsensusNotificationRoot;
 
@@ -77,10 +79,14 @@ use crossbeam_channel::{
 use itertools::Itertools;
 use vecno_consensusmanager::{SessionLock, SessionReadGuard};
 
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rayon::{
+    iter::{IntoParallelRefIterator, ParallelIterator},
+    slice::ParallelSlice,
+};
 use vecno_database::prelude::StoreResultExtensions;
 use vecno_hashes::Hash;
 use vecno_muhash::MuHash;
+use vecno_pow::{calc_level_from_pow, check_headers_pow_batch};
 use vecno_txscript::caches::TxScriptCacheCounters;
 
 use std::{
@@ -103,6 +109,9 @@ use crate::model::stores::selected_chain::SelectedChainStoreReader;
 
 use std::cmp;
 
+/// Number of headers whose PoW is verified side by side by each rayon task, a multiple of the widest SIMD lanes
+const HEADERS_POW_BATCH_SIZE: usize = 64;
+
 pub struct Consensus {
     // DB
     db: Arc<DB>,
@@ -380,6 +389,69 @@ impl Consensus {
         }
     }
 
+    /// Returns the range of selected chain indices retained by the selected chain store, i.e. from the pruning point up to the sink.
+    ///
+    /// Expects the pruning lock to be held by the caller for the whole chain walk
+    fn retained_chain_index_range(&self, sc_read: &impl SelectedChainStoreReader) -> ConsensusResult<(u64, u64)> {
+        let high_index = sc_read.get_tip().unwrap_option().ok_or(ConsensusError::General("the selected chain store has no tip"))?.0;
+        // The pruning point is always expected in the selected chain store. However if due to some reason this is not
+        // the case, we prefer not crashing but rather restrict the search to the sink (hence set low index to high index)
+        let low_index = sc_read.get_by_hash(self.pruning_point()).unwrap_option().unwrap_or(high_index);
+        Ok((low_index, high_index))
+    }
+
+    /// Returns the hash of the selected chain block at `index`
+    fn get_chain_block_hash_by_index(&self, sc_read: &impl SelectedChainStoreReader, index: u64) -> ConsensusResult<Hash> {
+        sc_read.get_by_index(index).unwrap_option().ok_or(ConsensusError::SelectedChainIndexNotFound(index))
+    }
+
+    /// Binary searches the selected chain indices in `[low_index, high_index]` for the highest index whose block score,
+    /// as returned by `score_of`, does not exceed `score`. Returns `None` if the score at `low_index` already exceeds `score`.
+    ///
+    /// Relies on DAA and blue scores being strictly increasing along the selected chain.
+    fn find_highest_chain_index_with_score_at_most(
+        &self,
+        sc_read: &impl SelectedChainStoreReader,
+        (low_index, high_index): (u64, u64),
+        score: u64,
+        score_of: impl Fn(Hash) -> ConsensusResult<u64>,
+    ) -> ConsensusResult<Option<u64>> {
+        let score_at = |index: u64| score_of(self.get_chain_block_hash_by_index(sc_read, index)?);
+        if score_at(low_index)? > score {
+            return Ok(None);
+        }
+        let (mut low, mut high) = (low_index, high_index);
+        while low < high {
+            // Round up so that `low` always advances
+            let mid = low + (high - low).div_ceil(2);
+            if score_at(mid)? <= score {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        Ok(Some(low))
+    }
+
+    /// Returns the header of `hash`, failing if it is missing
+    fn get_chain_block_header(&self, hash: Hash) -> ConsensusResult<Arc<Header>> {
+        self.headers_store.get_header(hash).unwrap_option().ok_or(ConsensusError::HeaderNotFound(hash))
+    }
+
+    /// Verifies the PoW of `headers` in parallel batches, returning the block level of each header passing the check. Headers
+    /// failing it, or all of them if PoW is skipped, get no level and are left to the PoW check of the header processor.
+    fn verify_headers_pow(&self, headers: &[Arc<Header>]) -> Vec<Option<BlockLevel>> {
+        if self.config.skip_proof_of_work {
+            return vec![None; headers.len()];
+        }
+        let max_block_level = self.config.max_block_level;
+        headers
+            .par_chunks(HEADERS_POW_BATCH_SIZE)
+            .flat_map_iter(|batch| check_headers_pow_batch(batch.iter().map(|header| header.as_ref())))
+            .map(|(passed, pow)| passed.then(|| calc_level_from_pow(pow, max_block_level)))
+            .collect()
+    }
+
     fn estimate_network_hashes_per_second_impl(&self, ghostdag_data: &GhostdagData, window_size: usize) -> ConsensusResult<u64> {
         let window = match self.services.window_manager.block_window(ghostdag_data, WindowType::VaryingWindow(window_size)) {
             Ok(w) => w,
@@ -411,10 +483,23 @@ impl ConsensusApi for Consensus {
     }
 
     fn validate_and_insert_block(&self, block: Block) -> BlockValidationFutures {
-        let (block_task, virtual_state_task) = self.validate_and_insert_block_impl(BlockTask::Ordinary { block });
+        let (block_task, virtual_state_task) = self.validate_and_insert_block_impl(BlockTask::Ordinary { block, block_level: None });
         BlockValidationFutures { block_task: Box::pin(block_task), virtual_state_task: Box::pin(virtual_state_task) }
     }
 
+    fn validate_and_insert_header_chunk(&self, headers: Vec<Arc<Header>>) -> Vec<BlockValidationFutures> {
+        let block_levels = self.verify_headers_pow(&headers);
+        headers
+            .into_iter()
+            .zip(block_levels)
+            .map(|(header, block_level)| {
+                let block = Block::from_header_arc(header);
+                let (block_task, virtual_state_task) = self.validate_and_insert_block_impl(BlockTask::Ordinary { block, block_level });
+                BlockValidationFutures { block_task: Box::pin(block_task), virtual_state_task: Box::pin(virtual_state_task) }
+            })
+            .collect()
+    }
+
     fn validate_and_insert_trusted_block(&self, tb: TrustedBlock) -> BlockValidationFutures {
         let (block_task, virtual_state_task) = self.validate_and_insert_block_impl(BlockTask::Trusted { block: tb.block });
         BlockValidationFutures { block_task: Box::pin(block_task), virtual_state_task: Box::pin(virtual_state_task) }
@@ -446,9 +531,12 @@ impl ConsensusApi for Consensus {
         self.services.mass_calculator.calc_tx_compute_mass(transaction)
     }
 
-    fn calculate_transaction_storage_mass(&self, _transaction: &MutableTransaction) -> Option<u64> {
-        // self.services.mass_calculator.calc_tx_storage_mass(&transaction.as_verifiable())
-        unimplemented!("unsupported at the API level until KIP9 is finalized")
+    fn calculate_transaction_storage_mass(&self, transaction: &MutableTransaction) -> Option<u64> {
+        // The storage mass is only defined for a transaction with all its UTXO entries populated
+        if !transaction.is_verifiable() {
+            return None;
+        }
+        self.services.mass_calculator.calc_tx_storage_mass(&transaction.as_verifiable())
     }
 
     fn get_stats(&self) -> ConsensusStats {
@@ -943,10 +1031,60 @@ impl ConsensusApi for Consensus {
             .collect::<ConsensusResult<Vec<_>>>()
     }
 
+    fn get_chain_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        self.utxo_diffs_store.get(hash).unwrap_option().ok_or(ConsensusError::MissingData(hash))
+    }
+
     fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
         self.is_chain_ancestor_of(hash, self.get_sink())
     }
 
+    fn get_chain_block_by_daa_score(&self, daa_score: u64) -> ConsensusResult<Arc<Header>> {
+        // We need consistency between the pruning point, selected chain and header store reads
+        let _guard = self.pruning_lock.blocking_read();
+        let sc_read = self.storage.selected_chain_store.read();
+        let index_range = self.retained_chain_index_range(sc_read.deref())?;
+        let sink = self.get_chain_block_hash_by_index(sc_read.deref(), index_range.1)?;
+        if daa_score > self.get_chain_block_header(sink)?.daa_score {
+            return Err(ConsensusError::DaaScoreNotInSelectedChain(daa_score));
+        }
+        let index = self
+            .find_highest_chain_index_with_score_at_most(sc_read.deref(), index_range, daa_score, |hash| {
+                self.headers_store.get_daa_score(hash).unwrap_option().ok_or(ConsensusError::HeaderNotFound(hash))
+            })?
+            .ok_or(ConsensusError::DaaScoreNotInSelectedChain(daa_score))?;
+        self.get_chain_block_header(self.get_chain_block_hash_by_index(sc_read.deref(), index)?)
+    }
+
+    fn get_chain_blocks_in_blue_score_range(
+        &self,
+        low_blue_score: u64,
+        high_blue_score: u64,
+        limit: usize,
+    ) -> ConsensusResult<Vec<Arc<Header>>> {
+        // We need consistency between the pruning point, selected chain and header store reads
+        let _guard = self.pruning_lock.blocking_read();
+        let sc_read = self.storage.selected_chain_store.read();
+        let index_range = self.retained_chain_index_range(sc_read.deref())?;
+        // Start right after the highest chain block with a blue score below `low_blue_score`, if any
+        let start_index = match low_blue_score.checked_sub(1) {
+            Some(score) => self.find_highest_chain_index_with_score_at_most(sc_read.deref(), index_range, score, |hash| {
+                self.headers_store.get_blue_score(hash).unwrap_option().ok_or(ConsensusError::HeaderNotFound(hash))
+            })?,
+            None => None,
+        }
+        .map_or(index_range.0, |index| index + 1);
+        let mut headers = vec![];
+        for index in (start_index..=index_range.1).take(limit) {
+            let header = self.get_chain_block_header(self.get_chain_block_hash_by_index(sc_read.deref(), index)?)?;
+            if header.blue_score > high_blue_score {
+                break;
+            }
+            headers.push(header);
+        }
+        Ok(headers)
+    }
+
     fn get_missing_block_body_hashes(&self, high: Hash) -> ConsensusResult<Vec<Hash>> {
         let _guard = self.pruning_lock.blocking_read();
         self.validate_block_exists(high)?;
@@ -5,7 +5,7 @@ use std::collections::{
     HashMap, VecDeque,
 };
 use tokio::sync::oneshot;
-use vecno_consensus_core::{block::Block, blockstatus::BlockStatus};
+use vecno_consensus_core::{block::Block, blockstatus::BlockStatus, BlockLevel};
 use vecno_hashes::Hash;
 
 pub type BlockResultSender = oneshot::Sender<BlockProcessResult<BlockStatus>>;
@@ -41,8 +41,9 @@ impl VirtualStateProcessingMessage {
 }
 
 pub enum BlockTask {
-    /// Ordinary block processing task, requiring full validation. The block might be header-only
-    Ordinary { block: Block },
+    /// Ordinary block processing task, requiring full validation. The block might be header-only.
+    /// `block_level` is set when the header PoW was already verified, in which case it is not checked again
+    Ordinary { block: Block, block_level: Option<BlockLevel> },
 
     /// Trusted block processing task, only requiring partial validation.
     /// Trusted blocks arrive as part of the pruning proof; the block might be header-only.
@@ -52,7 +53,7 @@ pub enum BlockTask {
 impl BlockTask {
     pub fn block(&self) -> &Block {
         match self {
-            BlockTask::Ordinary { block } => block,
+            BlockTask::Ordinary { block, .. } => block,
             BlockTask::Trusted { block } => block,
         }
     }
@@ -12,14 +12,22 @@ use vecno_database::prelude::StoreResultExtensions;
 use vecno_pow::calc_level_from_pow;
 
 impl HeaderProcessor {
-    /// Validates the header in isolation including pow check against header declared bits.
+    /// Validates the header in isolation including pow check against header declared bits, unless the pow was
+    /// already verified and its block level passed as `verified_block_level`.
     /// Returns the block level as computed from pow state or a rule error if such was encountered
-    pub(super) fn validate_header_in_isolation(&self, header: &Header) -> BlockProcessResult<BlockLevel> {
+    pub(super) fn validate_header_in_isolation(
+        &self,
+        header: &Header,
+        verified_block_level: Option<BlockLevel>,
+    ) -> BlockProcessResult<BlockLevel> {
         self.check_header_version(header)?;
         self.check_block_timestamp_in_isolation(header)?;
         self.check_parents_limit(header)?;
         Self::check_parents_not_origin(header)?;
-        self.check_pow_and_calc_block_level(header)
+        match verified_block_level {
+            Some(block_level) => Ok(block_level),
+            None => self.check_pow_and_calc_block_level(header),
+        }
     }
 
     pub(super) fn validate_parent_relations(&self, header: &Header) -> BlockProcessResult<()> {
@@ -108,3 +116,48 @@ impl HeaderProcessor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{config::ConfigBuilder, consensus::test_consensus::TestConsensus, errors::RuleError, params::SIMNET_PARAMS};
+    use std::sync::Arc;
+    use vecno_consensus_core::{api::ConsensusApi, header::Header};
+    use vecno_core::assert_match;
+
+    /// Sets the nonce of `header` to the first one whose PoW check outcome is `passes`
+    fn with_nonce(mut header: Header, passes: bool) -> Arc<Header> {
+        let state = vecno_pow::State::new(&header);
+        header.nonce = (0..).find(|&nonce| state.check_pow(nonce).0 == passes).unwrap();
+        Arc::new(header)
+    }
+
+    #[tokio::test]
+    async fn header_chunk_pow_test() {
+        let config = ConfigBuilder::new(SIMNET_PARAMS).edit_consensus_params(|p| p.skip_proof_of_work = false).build();
+        let consensus = TestConsensus::new(&config);
+        let wait_handles = consensus.init();
+
+        let valid = with_nonce(consensus.build_header_with_parents(1.into(), vec![config.genesis.hash]), true);
+        let invalid = with_nonce(consensus.build_header_with_parents(2.into(), vec![config.genesis.hash]), false);
+        let mut futures = consensus.validate_and_insert_header_chunk(vec![valid, invalid]).into_iter();
+        futures.next().unwrap().virtual_state_task.await.unwrap();
+        assert_match!(futures.next().unwrap().virtual_state_task.await, Err(RuleError::InvalidPoW));
+
+        consensus.shutdown(wait_handles);
+    }
+
+    #[tokio::test]
+    async fn header_chunk_skipped_pow_test() {
+        // Simnet skips PoW by default
+        let config = ConfigBuilder::new(SIMNET_PARAMS).build();
+        let consensus = TestConsensus::new(&config);
+        let wait_handles = consensus.init();
+
+        let header = with_nonce(consensus.build_header_with_parents(1.into(), vec![config.genesis.hash]), false);
+        for futures in consensus.validate_and_insert_header_chunk(vec![header]) {
+            futures.virtual_state_task.await.unwrap();
+        }
+
+        consensus.shutdown(wait_handles);
+    }
+}
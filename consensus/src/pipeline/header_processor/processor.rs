@@ -275,8 +275,8 @@ impl HeaderProcessor {
 
         // Validate the header depending on task type
         match task {
-            BlockTask::Ordinary { .. } => {
-                let ctx = self.validate_header(header)?;
+            BlockTask::Ordinary { block_level, .. } => {
+                let ctx = self.validate_header(header, *block_level)?;
                 self.commit_header(ctx, header);
             }
             BlockTask::Trusted { .. } => {
@@ -292,9 +292,13 @@ impl HeaderProcessor {
         Ok(StatusHeaderOnly)
     }
 
-    /// Runs full ordinary header validation
-    fn validate_header(&self, header: &Arc<Header>) -> BlockProcessResult<HeaderProcessingContext> {
-        let block_level = self.validate_header_in_isolation(header)?;
+    /// Runs full ordinary header validation. `verified_block_level` is the block level of a header whose PoW was already verified
+    fn validate_header(
+        &self,
+        header: &Arc<Header>,
+        verified_block_level: Option<BlockLevel>,
+    ) -> BlockProcessResult<HeaderProcessingContext> {
+        let block_level = self.validate_header_in_isolation(header, verified_block_level)?;
         self.validate_parent_relations(header)?;
         let mut ctx = self.build_processing_context(header, block_level);
         self.ghostdag(&mut ctx);
@@ -308,7 +312,7 @@ impl HeaderProcessor {
 
     // Runs partial header validation for trusted blocks (currently validates only header-in-isolation and computes GHOSTDAG).
     fn validate_trusted_header(&self, header: &Arc<Header>) -> BlockProcessResult<HeaderProcessingContext> {
-        let block_level = self.validate_header_in_isolation(header)?;
+        let block_level = self.validate_header_in_isolation(header, None)?;
         let mut ctx = self.build_processing_context(header, block_level);
         self.ghostdag(&mut ctx);
         Ok(ctx)
@@ -112,6 +112,42 @@ pub mod test_helpers {
         let output = TransactionOutput::new(tx_to_spend.outputs[0].value - fee, script_public_key);
         Transaction::new(TX_VERSION, vec![input], vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![])
     }
+
+    /// Creates a transaction that spends the outputs of specified indexes (if they exist) of every provided transaction and returns an optional change.
+    /// Assumes that the outputs being spent have opTrueScript as their scriptPublicKey.
+    ///
+    /// If some change is provided, creates two outputs, first one with the value of the spent outputs minus `change`
+    /// and `fee` (in sompi) and second one of `change` amount.
+    ///
+    /// If no change is provided, creates only one output with the value of the spent outputs minus `fee` (in sompi)
+    pub fn create_transaction_with_change<'a>(
+        txs_to_spend: impl Iterator<Item = &'a Transaction>,
+        output_indexes: Vec<usize>,
+        change: Option<u64>,
+        fee: u64,
+    ) -> Transaction {
+        let (script_public_key, redeem_script) = op_true_script();
+        let signature_script = pay_to_script_hash_signature_script(redeem_script, vec![]).expect("the script is canonical");
+        let mut inputs_value: u64 = 0;
+        let mut inputs = vec![];
+        for tx_to_spend in txs_to_spend {
+            for i in output_indexes.iter().copied() {
+                if i < tx_to_spend.outputs.len() {
+                    let previous_outpoint = TransactionOutpoint::new(tx_to_spend.id(), i as u32);
+                    inputs.push(TransactionInput::new(previous_outpoint, signature_script.clone(), MAX_TX_IN_SEQUENCE_NUM, 1));
+                    inputs_value += tx_to_spend.outputs[i].value;
+                }
+            }
+        }
+        let outputs = match change {
+            Some(change) => vec![
+                TransactionOutput::new(inputs_value - fee - change, script_public_key.clone()),
+                TransactionOutput::new(change, script_public_key),
+            ],
+            None => vec![TransactionOutput::new(inputs_value - fee, script_public_key.clone())],
+        };
+        Transaction::new(TX_VERSION, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![])
+    }
 }
 
 #[cfg(test)]
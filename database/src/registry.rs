@@ -52,11 +52,16 @@ pub enum DatabaseStorePrefixes {
     // ---- Components ----
     Addresses = 128,
     BannedAddresses = 129,
+    OnionAddresses = 130,
 
     // ---- Indexes ----
     UtxoIndex = 192,
     UtxoIndexTips = 193,
     CirculatingSupply = 194,
+    TxIndex = 195,
+    TxIndexSink = 196,
+    AddressIndex = 197,
+    AddressIndexSink = 198,
 
     // ---- Separator ----
     /// Reserved as a separator
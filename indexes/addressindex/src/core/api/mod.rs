@@ -0,0 +1,88 @@
+use parking_lot::RwLock;
+use std::{fmt::Debug, sync::Arc};
+use vecno_consensus_core::{acceptance_data::AcceptanceData, tx::ScriptPublicKeys};
+use vecno_consensusmanager::spawn_blocking;
+use vecno_database::prelude::StoreResult;
+use vecno_hashes::Hash;
+
+use crate::{
+    errors::AddressIndexResult,
+    model::{AddressHistoryCursor, AddressHistoryPage},
+};
+
+///Addressindex API targeted at retrieval calls.
+pub trait AddressIndexApi: Send + Sync + Debug {
+    /// Retrieve a page of at most `limit` entries of the merged history of the queried [ScriptPublicKeys],
+    /// starting right after `cursor`, or at the beginning of the history if no cursor is supplied.
+    ///
+    /// The entries of a single transaction are never split between pages, so a page may exceed `limit`.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_history(
+        &self,
+        script_public_keys: ScriptPublicKeys,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage>;
+
+    /// Retrieve the last chain block processed by the addressindex (used for testing purposes).
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_addressindex_sink(&self) -> StoreResult<Hash>;
+
+    /// Checks if the addressindex's db is synced with consensus.
+    ///
+    /// Note:
+    /// 1) Use a read lock when accessing this method
+    /// 2) due to potential sync-gaps is_synced is unreliable while consensus is actively resolving virtual states.
+    fn is_synced(&self) -> AddressIndexResult<bool>;
+
+    /// Update the addressindex with the given virtual chain changes.
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressIndexResult<()>;
+
+    /// Resync the addressindex from the consensus db
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn resync(&mut self) -> AddressIndexResult<()>;
+}
+
+/// Async proxy for the address history index
+#[derive(Debug, Clone)]
+pub struct AddressIndexProxy {
+    inner: Arc<RwLock<dyn AddressIndexApi>>,
+}
+
+impl AddressIndexProxy {
+    pub fn new(inner: Arc<RwLock<dyn AddressIndexApi>>) -> Self {
+        Self { inner }
+    }
+
+    pub async fn get_history(
+        self,
+        script_public_keys: ScriptPublicKeys,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        spawn_blocking(move || self.inner.read().get_history(script_public_keys, cursor, limit)).await.unwrap()
+    }
+
+    pub async fn update(
+        self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressIndexResult<()> {
+        spawn_blocking(move || {
+            self.inner.write().update(removed_chain_block_hashes, added_chain_block_hashes, added_chain_blocks_acceptance_data)
+        })
+        .await
+        .unwrap()
+    }
+}
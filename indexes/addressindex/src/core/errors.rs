@@ -0,0 +1,22 @@
+use std::io;
+use thiserror::Error;
+
+use crate::IDENT;
+use vecno_consensus_core::errors::consensus::ConsensusError;
+use vecno_database::prelude::StoreError;
+
+/// Errors originating from the [`AddressIndex`](crate::AddressIndex).
+#[derive(Error, Debug)]
+pub enum AddressIndexError {
+    #[error("[{IDENT}]: {0}")]
+    StoreAccessError(#[from] StoreError),
+
+    #[error("[{IDENT}]: {0}")]
+    ConsensusError(#[from] ConsensusError),
+
+    #[error("[{IDENT}]: {0}")]
+    DBResetError(#[from] io::Error),
+}
+
+/// Results originating from the [`AddressIndex`](crate::AddressIndex).
+pub type AddressIndexResult<T> = Result<T, AddressIndexError>;
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use vecno_consensus_core::tx::{ScriptPublicKey, TransactionId};
+use vecno_hashes::Hash;
+use vecno_utils::mem_size::MemSizeEstimator;
+
+/// A transaction accepted by the virtual selected chain which spent from or paid to a script public key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AddressHistoryEntry {
+    pub transaction_id: TransactionId,
+    /// The chain block whose mergeset accepted the transaction
+    pub accepting_block_hash: Hash,
+    /// The DAA score of the accepting block
+    pub accepting_block_daa_score: u64,
+    /// The sum of the transaction outputs paying to the script public key
+    pub received_amount: u64,
+    /// The sum of the outputs previously paid to the script public key which the transaction spends
+    pub spent_amount: u64,
+}
+
+impl AddressHistoryEntry {
+    pub fn cursor(&self) -> AddressHistoryCursor {
+        AddressHistoryCursor::new(self.accepting_block_daa_score, self.transaction_id)
+    }
+}
+
+/// The part of an [`AddressHistoryEntry`] which is not encoded in its store key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactAddressHistoryEntry {
+    pub accepting_block_hash: Hash,
+    pub received_amount: u64,
+    pub spent_amount: u64,
+}
+
+impl MemSizeEstimator for CompactAddressHistoryEntry {}
+
+/// A position within the history of a script public key.
+///
+/// The history is ordered by accepting block DAA score, then by transaction id.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AddressHistoryCursor {
+    pub accepting_block_daa_score: u64,
+    pub transaction_id: TransactionId,
+}
+
+impl AddressHistoryCursor {
+    pub fn new(accepting_block_daa_score: u64, transaction_id: TransactionId) -> Self {
+        Self { accepting_block_daa_score, transaction_id }
+    }
+}
+
+/// [`AddressHistoryEntry`]s paired with the script public key they relate to.
+pub type AddressHistoryEntries = Vec<(ScriptPublicKey, AddressHistoryEntry)>;
+
+/// A page of the merged history of a set of script public keys.
+#[derive(Clone, Debug, Default)]
+pub struct AddressHistoryPage {
+    pub entries: AddressHistoryEntries,
+    /// The cursor to resume from, `None` if the history was exhausted
+    pub next_cursor: Option<AddressHistoryCursor>,
+}
@@ -0,0 +1,344 @@
+use crate::{
+    api::AddressIndexApi,
+    errors::{AddressIndexError, AddressIndexResult},
+    model::{AddressHistoryCursor, AddressHistoryEntries, AddressHistoryEntry, AddressHistoryPage},
+    stores::store_manager::Store,
+    IDENT,
+};
+use parking_lot::RwLock;
+use rocksdb::WriteBatch;
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
+use vecno_consensus_core::{
+    acceptance_data::AcceptanceData,
+    api::ConsensusApi,
+    errors::consensus::ConsensusResult,
+    tx::{ScriptPublicKey, ScriptPublicKeys, Transaction, TransactionOutpoint, TransactionOutput},
+};
+use vecno_consensusmanager::ConsensusManager;
+use vecno_core::trace;
+use vecno_database::prelude::{StoreResult, DB};
+use vecno_hashes::Hash;
+use vecno_index_chain::{ChainIndex, ChainIndexConsensusResetHandler};
+
+/// AddressIndex indexes [`AddressHistoryEntry`]s by [`ScriptPublicKey`], for all transactions accepted by the virtual selected chain
+/// which spent from or paid to the script public key, and commits them to its own store.
+/// Note: The AddressIndex struct by itself is not thread save, only correct usage of the supplied RwLock via `new` makes it so.
+/// please follow guidelines found in the comments under `addressindex::core::api::AddressIndexApi` for proper thread safety.
+pub struct AddressIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+}
+
+impl AddressIndex {
+    /// Creates a new [`AddressIndex`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> AddressIndexResult<Arc<RwLock<Self>>> {
+        let mut addressindex = Self { consensus_manager: consensus_manager.clone(), store: Store::new(db) };
+        if !addressindex.is_synced()? {
+            addressindex.resync()?;
+        }
+        let addressindex = Arc::new(RwLock::new(addressindex));
+        consensus_manager
+            .register_consensus_reset_handler(Arc::new(ChainIndexConsensusResetHandler::new(Arc::downgrade(&addressindex))));
+        Ok(addressindex)
+    }
+
+    /// Collects the history entries of all the transactions accepted by the mergeset of `accepting_block_hash`.
+    ///
+    /// The outputs spent by the accepted transactions are resolved from the UTXO diff of the accepting block, or, if they
+    /// were both created and spent within the mergeset, from the accepted transactions themselves.
+    fn collect_entries(
+        consensus: &dyn ConsensusApi,
+        accepting_block_hash: Hash,
+        acceptance_data: &AcceptanceData,
+    ) -> ConsensusResult<AddressHistoryEntries> {
+        let accepting_block_daa_score = consensus.get_header(accepting_block_hash)?.daa_score;
+        let utxo_diff = consensus.get_chain_block_utxo_diff(accepting_block_hash)?;
+
+        let mut blocks = Vec::with_capacity(acceptance_data.len());
+        for mergeset_block_data in acceptance_data.iter().filter(|data| !data.accepted_transactions.is_empty()) {
+            blocks.push((mergeset_block_data, consensus.get_block(mergeset_block_data.block_hash)?));
+        }
+        let accepted_transactions = blocks
+            .iter()
+            .flat_map(|(mergeset_block_data, block)| {
+                mergeset_block_data.accepted_transactions.iter().map(move |tx| &block.transactions[tx.index_within_block as usize])
+            })
+            .collect::<Vec<&Transaction>>();
+        let mergeset_outputs = accepted_transactions
+            .iter()
+            .copied()
+            .flat_map(|tx| {
+                tx.outputs.iter().enumerate().map(move |(index, output)| (TransactionOutpoint::new(tx.id(), index as u32), output))
+            })
+            .collect::<HashMap<TransactionOutpoint, &TransactionOutput>>();
+
+        let mut entries = AddressHistoryEntries::new();
+        for tx in accepted_transactions {
+            // (received, spent) amounts by script public key
+            let mut amounts = HashMap::<&ScriptPublicKey, (u64, u64)>::new();
+            for output in tx.outputs.iter() {
+                amounts.entry(&output.script_public_key).or_default().0 += output.value;
+            }
+            for input in tx.inputs.iter() {
+                let spent = match utxo_diff.remove.get(&input.previous_outpoint) {
+                    Some(entry) => Some((&entry.script_public_key, entry.amount)),
+                    None => mergeset_outputs.get(&input.previous_outpoint).map(|output| (&output.script_public_key, output.value)),
+                };
+                if let Some((script_public_key, amount)) = spent {
+                    amounts.entry(script_public_key).or_default().1 += amount;
+                }
+            }
+            entries.extend(amounts.into_iter().map(|(script_public_key, (received_amount, spent_amount))| {
+                (
+                    script_public_key.clone(),
+                    AddressHistoryEntry {
+                        transaction_id: tx.id(),
+                        accepting_block_hash,
+                        accepting_block_daa_score,
+                        received_amount,
+                        spent_amount,
+                    },
+                )
+            }));
+        }
+        Ok(entries)
+    }
+}
+
+impl ChainIndex for AddressIndex {
+    type Error = AddressIndexError;
+
+    const IDENT: &'static str = IDENT;
+
+    fn consensus_manager(&self) -> &Arc<ConsensusManager> {
+        &self.consensus_manager
+    }
+
+    fn index_chain_block(
+        &mut self,
+        batch: &mut WriteBatch,
+        consensus: &dyn ConsensusApi,
+        accepting_block_hash: Hash,
+        acceptance_data: &AcceptanceData,
+    ) -> AddressIndexResult<()> {
+        let entries = Self::collect_entries(consensus, accepting_block_hash, acceptance_data)?;
+        Ok(self.store.add_entries(batch, &entries)?)
+    }
+
+    fn unindex_chain_block(
+        &mut self,
+        batch: &mut WriteBatch,
+        consensus: &dyn ConsensusApi,
+        accepting_block_hash: Hash,
+        acceptance_data: &AcceptanceData,
+    ) -> AddressIndexResult<()> {
+        let entries = Self::collect_entries(consensus, accepting_block_hash, acceptance_data)?;
+        Ok(self.store.remove_entries(batch, &entries)?)
+    }
+
+    fn get_sink(&self) -> StoreResult<Hash> {
+        self.store.get_sink()
+    }
+
+    fn set_sink(&mut self, batch: &mut WriteBatch, sink: Hash) -> StoreResult<()> {
+        self.store.set_sink(batch, sink)
+    }
+
+    fn write_batch(&mut self, batch: WriteBatch) -> StoreResult<()> {
+        self.store.write_batch(batch)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.store.delete_all()
+    }
+}
+
+impl AddressIndexApi for AddressIndex {
+    /// Retrieve a page of the merged history of the queried [ScriptPublicKeys] from the addressindex db.
+    fn get_history(
+        &self,
+        script_public_keys: ScriptPublicKeys,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        trace!("[{0}] retrieving the history of {1} script public keys", IDENT, script_public_keys.len());
+
+        let limit = limit.max(1);
+        let mut entries = AddressHistoryEntries::new();
+        for script_public_key in script_public_keys.into_iter() {
+            // One extra entry per script public key reveals whether the history continues past this page
+            let history = self.store.get_history(&script_public_key, cursor, limit + 1)?;
+            entries.extend(history.into_iter().map(|entry| (script_public_key.clone(), entry)));
+        }
+        entries.sort_by_key(|(_, entry)| entry.cursor());
+
+        // Extend the page up to the last entry of its final transaction, so a cursor never splits a transaction
+        let mut page_len = entries.len().min(limit);
+        if let Some(last_cursor) = page_len.checked_sub(1).map(|i| entries[i].1.cursor()) {
+            while page_len < entries.len() && entries[page_len].1.cursor() == last_cursor {
+                page_len += 1;
+            }
+        }
+        let has_more = page_len < entries.len();
+        entries.truncate(page_len);
+        let next_cursor = if has_more { entries.last().map(|(_, entry)| entry.cursor()) } else { None };
+
+        Ok(AddressHistoryPage { entries, next_cursor })
+    }
+
+    /// Retrieve the stored sink of the addressindex.
+    fn get_addressindex_sink(&self) -> StoreResult<Hash> {
+        trace!("[{0}] retrieving sink", IDENT);
+
+        self.store.get_sink()
+    }
+
+    /// Updates the [AddressIndex] via the virtual chain changes supplied, see [`ChainIndex::update_chain`].
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressIndexResult<()> {
+        self.update_chain(removed_chain_block_hashes, added_chain_block_hashes, added_chain_blocks_acceptance_data)
+    }
+
+    /// Checks to see if the [AddressIndex] is sync'd, see [`ChainIndex::is_chain_synced`].
+    fn is_synced(&self) -> AddressIndexResult<bool> {
+        self.is_chain_synced()
+    }
+
+    /// Brings the addressindex up to date with the virtual selected chain of the consensus database, see [`ChainIndex::resync_chain`].
+    fn resync(&mut self) -> AddressIndexResult<()> {
+        self.resync_chain()
+    }
+}
+
+impl Debug for AddressIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AddressIndex").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{api::AddressIndexApi, model::AddressHistoryCursor, AddressIndex};
+    use std::sync::Arc;
+    use vecno_consensus::{config::ConfigBuilder, consensus::test_consensus::TestConsensus, params::MAINNET_PARAMS};
+    use vecno_consensus_core::{
+        api::ConsensusApi,
+        coinbase::MinerData,
+        tx::{ScriptPublicKey, ScriptPublicKeys, TransactionId},
+    };
+    use vecno_consensusmanager::ConsensusManager;
+    use vecno_database::create_temp_db;
+    use vecno_database::prelude::ConnBuilder;
+    use vecno_hashes::Hash;
+
+    async fn add_chain(tc: &TestConsensus, parent: Hash, hashes: impl Iterator<Item = u64>, script_public_key: &ScriptPublicKey) {
+        let mut parent = parent;
+        for i in hashes {
+            let miner_data = MinerData::new(script_public_key.clone(), vec![]);
+            let block = tc.build_utxo_valid_block_with_parents(i.into(), vec![parent], miner_data, vec![]);
+            tc.validate_and_insert_block(block.to_immutable()).virtual_state_task.await.unwrap();
+            parent = i.into();
+        }
+    }
+
+    /// Returns the ids of the coinbase transactions of `hashes` paying to `script_public_key`, along with the amount they pay.
+    fn coinbases_paying_to(
+        tc: &TestConsensus,
+        hashes: impl Iterator<Item = u64>,
+        script_public_key: &ScriptPublicKey,
+    ) -> Vec<(TransactionId, u64)> {
+        hashes
+            .filter_map(|i| {
+                let coinbase = tc.get_block(i.into()).unwrap().transactions[0].clone();
+                let amount: u64 = coinbase
+                    .outputs
+                    .iter()
+                    .filter(|output| output.script_public_key == *script_public_key)
+                    .map(|output| output.value)
+                    .sum();
+                (amount > 0).then_some((coinbase.id(), amount))
+            })
+            .collect()
+    }
+
+    fn script_public_keys(script_public_key: &ScriptPublicKey) -> ScriptPublicKeys {
+        ScriptPublicKeys::from_iter([script_public_key.clone()])
+    }
+
+    #[tokio::test]
+    async fn test_addressindex() {
+        vecno_core::log::try_init_logger("INFO");
+
+        let config = ConfigBuilder::new(MAINNET_PARAMS)
+            .skip_proof_of_work()
+            .edit_consensus_params(|p| {
+                p.min_difficulty_window_len = p.legacy_difficulty_window_size;
+            })
+            .build();
+        let tc = Arc::new(TestConsensus::new(&config));
+        let wait_handles = tc.init();
+        let (_addressindex_db_lifetime, addressindex_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+        let addressindex = AddressIndex::new(consensus_manager, addressindex_db).unwrap();
+
+        let main_script_public_key = ScriptPublicKey::from_vec(0, vec![1]);
+        let side_script_public_key = ScriptPublicKey::from_vec(0, vec![2]);
+
+        // A fresh index is synced at the genesis.
+        assert!(addressindex.read().is_synced().unwrap());
+        assert_eq!(addressindex.read().get_addressindex_sink().unwrap(), config.genesis.hash);
+
+        // Build a chain 1 <- 2 <- ... <- 5 and catch up.
+        add_chain(&tc, config.genesis.hash, 1..6, &main_script_public_key).await;
+        addressindex.write().resync().unwrap();
+        assert!(addressindex.read().is_synced().unwrap());
+
+        // The coinbase of chain block `i` is accepted by chain block `i + 1`.
+        let expected = coinbases_paying_to(&tc, 1..5, &main_script_public_key);
+        assert!(!expected.is_empty());
+        let page = addressindex.read().get_history(script_public_keys(&main_script_public_key), None, 1000).unwrap();
+        assert!(page.next_cursor.is_none());
+        assert_eq!(page.entries.len(), expected.len());
+        for (script_public_key, entry) in page.entries.iter() {
+            assert_eq!(*script_public_key, main_script_public_key);
+            assert!(expected.contains(&(entry.transaction_id, entry.received_amount)));
+            assert_eq!(entry.spent_amount, 0);
+            assert_eq!(entry.accepting_block_daa_score, tc.get_header(entry.accepting_block_hash).unwrap().daa_score);
+        }
+        assert!(page.entries.windows(2).all(|w| w[0].1.cursor() < w[1].1.cursor()));
+
+        // Paging through the history one entry at a time yields the same entries.
+        let mut cursor: Option<AddressHistoryCursor> = None;
+        let mut paged = vec![];
+        loop {
+            let page = addressindex.read().get_history(script_public_keys(&main_script_public_key), cursor, 1).unwrap();
+            assert!(page.entries.len() <= 1);
+            paged.extend(page.entries);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(paged, page.entries);
+
+        // Reorg to a longer chain 6 <- 7 <- ... <- 12 and catch up.
+        add_chain(&tc, config.genesis.hash, 6..13, &side_script_public_key).await;
+        addressindex.write().resync().unwrap();
+        assert!(addressindex.read().is_synced().unwrap());
+        assert_eq!(addressindex.read().get_addressindex_sink().unwrap(), 12.into());
+
+        // The history of the removed chain is unindexed, the one of the new chain is indexed.
+        let page = addressindex.read().get_history(script_public_keys(&main_script_public_key), None, 100).unwrap();
+        assert!(page.entries.is_empty());
+        let expected = coinbases_paying_to(&tc, 6..12, &side_script_public_key);
+        let page = addressindex.read().get_history(script_public_keys(&side_script_public_key), None, 100).unwrap();
+        assert_eq!(page.entries.len(), expected.len());
+
+        drop(addressindex);
+        tc.shutdown(wait_handles);
+    }
+}
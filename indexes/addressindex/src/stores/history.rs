@@ -0,0 +1,203 @@
+use crate::core::model::{AddressHistoryCursor, AddressHistoryEntries, AddressHistoryEntry, CompactAddressHistoryEntry};
+
+use rocksdb::WriteBatch;
+use std::sync::Arc;
+use vecno_consensus_core::tx::{ScriptPublicKey, ScriptPublicKeyVersion, TransactionId};
+use vecno_database::prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreResult, DB};
+use vecno_database::registry::DatabaseStorePrefixes;
+use vecno_hashes::HASH_SIZE;
+
+pub const VERSION_TYPE_SIZE: usize = size_of::<ScriptPublicKeyVersion>(); // Const since we need to re-use this a few times.
+
+/// [`ScriptPublicKeyBucket`].
+/// Consists of 2 bytes of little endian [ScriptPublicKeyVersion] bytes, followed by 8 bytes of little endian script size and the script itself.
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+struct ScriptPublicKeyBucket(Vec<u8>);
+
+impl From<&ScriptPublicKey> for ScriptPublicKeyBucket {
+    fn from(script_public_key: &ScriptPublicKey) -> Self {
+        let mut bytes: Vec<u8> = Vec::with_capacity(VERSION_TYPE_SIZE + size_of::<u64>() + script_public_key.script().len());
+        bytes.extend_from_slice(&script_public_key.version().to_le_bytes());
+        bytes.extend_from_slice(&(script_public_key.script().len() as u64).to_le_bytes());
+        bytes.extend_from_slice(script_public_key.script());
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for ScriptPublicKeyBucket {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+// Keys:
+
+/// Size of the [AddressHistoryCursorKey] in bytes.
+pub const ADDRESS_HISTORY_CURSOR_KEY_SIZE: usize = size_of::<u64>() + HASH_SIZE;
+
+/// [AddressHistoryCursor] key which orders the [CompactAddressHistoryEntry]s within a [ScriptPublicKeyBucket].
+/// Consists of 8 bytes of big endian accepting block DAA score, followed by 32 bytes of [TransactionId]
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+struct AddressHistoryCursorKey([u8; ADDRESS_HISTORY_CURSOR_KEY_SIZE]);
+
+impl From<AddressHistoryCursorKey> for AddressHistoryCursor {
+    fn from(key: AddressHistoryCursorKey) -> Self {
+        let accepting_block_daa_score = u64::from_be_bytes(key.0[..size_of::<u64>()].try_into().expect("expected daa score size"));
+        let transaction_id = TransactionId::from_slice(&key.0[size_of::<u64>()..]);
+        Self::new(accepting_block_daa_score, transaction_id)
+    }
+}
+
+impl From<&AddressHistoryCursor> for AddressHistoryCursorKey {
+    fn from(cursor: &AddressHistoryCursor) -> Self {
+        let mut bytes = [0; ADDRESS_HISTORY_CURSOR_KEY_SIZE];
+        // Big endian, so the byte order of the keys matches the order of the DAA scores
+        bytes[..size_of::<u64>()].copy_from_slice(&cursor.accepting_block_daa_score.to_be_bytes());
+        bytes[size_of::<u64>()..].copy_from_slice(&cursor.transaction_id.as_bytes());
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for AddressHistoryCursorKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Full [CompactAddressHistoryEntry] access key.
+/// Consists of variable amount of bytes of [ScriptPublicKeyBucket], and 40 bytes of [AddressHistoryCursorKey]
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+struct AddressHistoryFullAccessKey(Arc<Vec<u8>>);
+
+impl AddressHistoryFullAccessKey {
+    /// Creates a new [AddressHistoryFullAccessKey] from a [ScriptPublicKeyBucket] and [AddressHistoryCursorKey].
+    pub fn new(script_public_key_bucket: &ScriptPublicKeyBucket, cursor_key: AddressHistoryCursorKey) -> Self {
+        let mut bytes = Vec::with_capacity(script_public_key_bucket.as_ref().len() + ADDRESS_HISTORY_CURSOR_KEY_SIZE);
+        bytes.extend_from_slice(script_public_key_bucket.as_ref());
+        bytes.extend_from_slice(cursor_key.as_ref());
+        Self(Arc::new(bytes))
+    }
+}
+
+impl AsRef<[u8]> for AddressHistoryFullAccessKey {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+// Traits:
+
+pub trait AddressHistoryStoreReader {
+    /// Get at most `limit` [AddressHistoryEntry]s of a [ScriptPublicKey], in history order, starting right after `cursor`.
+    fn get_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<Vec<AddressHistoryEntry>>;
+}
+
+pub trait AddressHistoryStore: AddressHistoryStoreReader {
+    /// add [AddressHistoryEntries] into the [AddressHistoryStore] via `batch`, overriding existing entries.
+    fn add_entries(&mut self, batch: &mut WriteBatch, entries: &AddressHistoryEntries) -> StoreResult<()>;
+
+    /// remove [AddressHistoryEntries] from the [AddressHistoryStore] via `batch`.
+    fn remove_entries(&mut self, batch: &mut WriteBatch, entries: &AddressHistoryEntries) -> StoreResult<()>;
+
+    /// removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+// Implementations:
+
+#[derive(Clone)]
+pub struct DbAddressHistoryStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<AddressHistoryFullAccessKey, CompactAddressHistoryEntry>,
+}
+
+impl DbAddressHistoryStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AddressIndex.into()) }
+    }
+}
+
+impl AddressHistoryStoreReader for DbAddressHistoryStore {
+    fn get_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<Vec<AddressHistoryEntry>> {
+        let script_public_key_bucket = ScriptPublicKeyBucket::from(script_public_key);
+        let cursor_key = cursor.as_ref().map(AddressHistoryCursorKey::from);
+        let seek_from = cursor_key.map(|cursor_key| AddressHistoryFullAccessKey::new(&script_public_key_bucket, cursor_key));
+
+        // The seek lands on the cursor itself if the script public key is part of the cursor transaction, so we take one extra entry
+        // and filter the cursor out, rather than skipping the first entry.
+        Ok(self
+            .access
+            .seek_iterator(Some(script_public_key_bucket.as_ref()), seek_from, limit.saturating_add(1), false)
+            .map(|res| {
+                let (key, entry) = res.unwrap();
+                (AddressHistoryCursorKey(<[u8; ADDRESS_HISTORY_CURSOR_KEY_SIZE]>::try_from(&key[..]).unwrap()), entry)
+            })
+            .filter(|(key, _)| Some(*key) != cursor_key)
+            .take(limit)
+            .map(|(key, entry)| {
+                let cursor = AddressHistoryCursor::from(key);
+                AddressHistoryEntry {
+                    transaction_id: cursor.transaction_id,
+                    accepting_block_hash: entry.accepting_block_hash,
+                    accepting_block_daa_score: cursor.accepting_block_daa_score,
+                    received_amount: entry.received_amount,
+                    spent_amount: entry.spent_amount,
+                }
+            })
+            .collect())
+    }
+}
+
+impl AddressHistoryStore for DbAddressHistoryStore {
+    fn add_entries(&mut self, batch: &mut WriteBatch, entries: &AddressHistoryEntries) -> StoreResult<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let mut writer = BatchDbWriter::new(batch);
+
+        let mut to_add = entries.iter().map(|(script_public_key, entry)| {
+            (
+                AddressHistoryFullAccessKey::new(&ScriptPublicKeyBucket::from(script_public_key), (&entry.cursor()).into()),
+                CompactAddressHistoryEntry {
+                    accepting_block_hash: entry.accepting_block_hash,
+                    received_amount: entry.received_amount,
+                    spent_amount: entry.spent_amount,
+                },
+            )
+        });
+
+        self.access.write_many(&mut writer, &mut to_add)?;
+        Ok(())
+    }
+
+    fn remove_entries(&mut self, batch: &mut WriteBatch, entries: &AddressHistoryEntries) -> StoreResult<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let mut writer = BatchDbWriter::new(batch);
+
+        let mut to_remove = entries.iter().map(|(script_public_key, entry)| {
+            AddressHistoryFullAccessKey::new(&ScriptPublicKeyBucket::from(script_public_key), (&entry.cursor()).into())
+        });
+
+        self.access.delete_many(&mut writer, &mut to_remove)?;
+        Ok(())
+    }
+
+    /// Removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use rocksdb::WriteBatch;
+use vecno_consensus_core::tx::ScriptPublicKey;
+use vecno_core::trace;
+use vecno_database::{
+    prelude::{CachePolicy, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use vecno_hashes::Hash;
+use vecno_index_chain::sink::{DbIndexSinkStore, IndexSinkStore, IndexSinkStoreReader};
+
+use crate::{
+    model::{AddressHistoryCursor, AddressHistoryEntries, AddressHistoryEntry},
+    stores::history::{AddressHistoryStore, AddressHistoryStoreReader, DbAddressHistoryStore},
+    IDENT,
+};
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    addressindex_sink_store: DbIndexSinkStore,
+    address_history_store: DbAddressHistoryStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            addressindex_sink_store: DbIndexSinkStore::new(db.clone(), DatabaseStorePrefixes::AddressIndexSink),
+            address_history_store: DbAddressHistoryStore::new(db.clone(), CachePolicy::Empty),
+            db,
+        }
+    }
+
+    pub fn get_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        cursor: Option<AddressHistoryCursor>,
+        limit: usize,
+    ) -> StoreResult<Vec<AddressHistoryEntry>> {
+        self.address_history_store.get_history(script_public_key, cursor, limit)
+    }
+
+    pub fn remove_entries(&mut self, batch: &mut WriteBatch, to_remove: &AddressHistoryEntries) -> StoreResult<()> {
+        self.address_history_store.remove_entries(batch, to_remove)
+    }
+
+    pub fn add_entries(&mut self, batch: &mut WriteBatch, to_add: &AddressHistoryEntries) -> StoreResult<()> {
+        self.address_history_store.add_entries(batch, to_add)
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.addressindex_sink_store.get()
+    }
+
+    pub fn set_sink(&mut self, batch: &mut WriteBatch, sink: Hash) -> StoreResult<()> {
+        self.addressindex_sink_store.set(batch, sink)
+    }
+
+    /// Commits the writes staged in `batch` to the addressindex database at once.
+    pub fn write_batch(&self, batch: WriteBatch) -> StoreResult<()> {
+        Ok(self.db.write(batch)?)
+    }
+
+    /// Resets the addressindex database:
+    pub fn delete_all(&mut self) -> StoreResult<()> {
+        trace!("[{0}] attempting to clear addressindex database...", IDENT);
+
+        // Clear all
+        self.addressindex_sink_store.remove()?;
+        self.address_history_store.delete_all()?;
+
+        trace!("[{0}] clearing addressindex database - success!", IDENT);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+use parking_lot::RwLock;
+use rocksdb::WriteBatch;
+use std::{
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+use vecno_consensus_core::{acceptance_data::AcceptanceData, api::ConsensusApi, errors::consensus::ConsensusError};
+use vecno_consensusmanager::{ConsensusManager, ConsensusResetHandler};
+use vecno_core::{info, trace};
+use vecno_database::prelude::{StoreError, StoreResult};
+use vecno_hashes::Hash;
+
+const RESYNC_CHUNK_SIZE: usize = 1024; // Amount of chain blocks processed at once while resyncing.
+
+/// An index of the transactions accepted by the virtual selected chain, committing the sink it is synced with.
+///
+/// Implementors index and unindex the transactions accepted by a single chain block, while the provided methods
+/// follow the virtual chain changes, catch up after a restart and rebuild the index from the consensus source.
+pub trait ChainIndex {
+    type Error: From<StoreError> + From<ConsensusError> + Debug;
+
+    /// Name of the index, used in logs.
+    const IDENT: &'static str;
+
+    fn consensus_manager(&self) -> &Arc<ConsensusManager>;
+
+    /// Indexes the transactions accepted by the added chain block `accepting_block_hash` via `batch`.
+    fn index_chain_block(
+        &mut self,
+        batch: &mut WriteBatch,
+        consensus: &dyn ConsensusApi,
+        accepting_block_hash: Hash,
+        acceptance_data: &AcceptanceData,
+    ) -> Result<(), Self::Error>;
+
+    /// Unindexes the transactions accepted by the removed chain block `accepting_block_hash` via `batch`.
+    fn unindex_chain_block(
+        &mut self,
+        batch: &mut WriteBatch,
+        consensus: &dyn ConsensusApi,
+        accepting_block_hash: Hash,
+        acceptance_data: &AcceptanceData,
+    ) -> Result<(), Self::Error>;
+
+    fn get_sink(&self) -> StoreResult<Hash>;
+
+    fn set_sink(&mut self, batch: &mut WriteBatch, sink: Hash) -> StoreResult<()>;
+
+    /// Commits the writes staged in `batch` to the index database at once.
+    fn write_batch(&mut self, batch: WriteBatch) -> StoreResult<()>;
+
+    /// Resets the index database.
+    fn delete_all(&mut self) -> StoreResult<()>;
+
+    /// Applies a virtual chain change to the index, committing it in a single write batch:
+    /// 1) Unindexes the transactions accepted by the removed chain blocks.
+    /// 2) Indexes the transactions accepted by the added chain blocks.
+    /// 3) Commits the new sink.
+    ///
+    /// If `try_reset_on_err` is set, the index database is reset when the change fails to apply.
+    fn apply_chain_changes(
+        &mut self,
+        consensus: &dyn ConsensusApi,
+        removed_chain_block_hashes: &[Hash],
+        added_chain_block_hashes: &[Hash],
+        added_chain_blocks_acceptance_data: &[Arc<AcceptanceData>],
+        try_reset_on_err: bool,
+    ) -> Result<(), Self::Error> {
+        let mut batch = WriteBatch::default();
+        let res = match self.batch_chain_changes(
+            &mut batch,
+            consensus,
+            removed_chain_block_hashes,
+            added_chain_block_hashes,
+            added_chain_blocks_acceptance_data,
+        ) {
+            Ok(()) => self.write_batch(batch).map_err(Into::into),
+            Err(err) => Err(err),
+        };
+        if try_reset_on_err && res.is_err() {
+            self.delete_all()?;
+        }
+        res
+    }
+
+    /// Stages the writes of a virtual chain change in `batch`, see [`ChainIndex::apply_chain_changes`].
+    fn batch_chain_changes(
+        &mut self,
+        batch: &mut WriteBatch,
+        consensus: &dyn ConsensusApi,
+        removed_chain_block_hashes: &[Hash],
+        added_chain_block_hashes: &[Hash],
+        added_chain_blocks_acceptance_data: &[Arc<AcceptanceData>],
+    ) -> Result<(), Self::Error> {
+        for removed_block_hash in removed_chain_block_hashes.iter().copied() {
+            // The acceptance data of a removed chain block is only missing if the block was pruned in the meantime,
+            // in which case its transactions can no longer be reorged out and are kept.
+            let Ok(acceptance_data) = consensus.get_block_acceptance_data(removed_block_hash) else {
+                continue;
+            };
+            self.unindex_chain_block(batch, consensus, removed_block_hash, &acceptance_data)?;
+        }
+
+        for (accepting_block_hash, acceptance_data) in added_chain_block_hashes.iter().copied().zip(added_chain_blocks_acceptance_data)
+        {
+            self.index_chain_block(batch, consensus, accepting_block_hash, acceptance_data)?;
+        }
+
+        // The new sink is the last added chain block, or, if the chain only shrank, the selected parent of the last removed block.
+        let new_sink = match (added_chain_block_hashes.last(), removed_chain_block_hashes.last()) {
+            (Some(added), _) => Some(*added),
+            (None, Some(removed)) => Some(consensus.get_ghostdag_data(*removed)?.selected_parent),
+            (None, None) => None,
+        };
+        if let Some(new_sink) = new_sink {
+            self.set_sink(batch, new_sink)?;
+        }
+
+        Ok(())
+    }
+
+    /// Updates the index via the virtual chain changes supplied.
+    ///
+    /// If the acceptance data of the added chain blocks is not supplied it is fetched from consensus.
+    fn update_chain(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> Result<(), Self::Error> {
+        trace!("[{0}] updating...", Self::IDENT);
+        trace!("[{0}] adding {1} chain blocks", Self::IDENT, added_chain_block_hashes.len());
+        trace!("[{0}] removing {1} chain blocks", Self::IDENT, removed_chain_block_hashes.len());
+
+        let consensus = self.consensus_manager().consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let added_chain_blocks_acceptance_data = if added_chain_blocks_acceptance_data.len() == added_chain_block_hashes.len() {
+            added_chain_blocks_acceptance_data
+        } else {
+            Arc::new(session.get_blocks_acceptance_data(&added_chain_block_hashes, None)?)
+        };
+
+        self.apply_chain_changes(
+            &*session,
+            &removed_chain_block_hashes,
+            &added_chain_block_hashes,
+            &added_chain_blocks_acceptance_data,
+            false,
+        )
+    }
+
+    /// Checks to see if the index is sync'd. This is done via comparing the index committed sink with the one of the consensus database.
+    ///
+    /// **Note:** Due to sync gaps between the index and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_chain_synced(&self) -> Result<bool, Self::Error> {
+        trace!("[{0}] checking sync status...", Self::IDENT);
+
+        let consensus = self.consensus_manager().consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        match self.get_sink() {
+            Ok(index_sink) => {
+                let res = index_sink == session.get_sink();
+                trace!("[{0}] sync status is {1}", Self::IDENT, res);
+                Ok(res)
+            }
+            Err(StoreError::KeyNotFound(_)) => {
+                //Means index sink database is empty i.e. not sync'd.
+                trace!("[{0}] sync status is {1}", Self::IDENT, false);
+                Ok(false)
+            }
+            Err(other_store_errors) => Err(other_store_errors.into()),
+        }
+    }
+
+    /// Brings the index up to date with the virtual selected chain of the consensus database.
+    ///
+    /// If the stored sink is still known to consensus the chain changes since it are applied, reverting
+    /// any reorged chain blocks. Otherwise the index is deleted and rebuilt from the acceptance data of
+    /// the chain starting at the consensus source.
+    ///
+    /// **Notes:**
+    /// 1) Transactions accepted below the pruning point are kept, as long as the index is not rebuilt.
+    /// 2) resyncing while consensus notifies of virtual chain changes, may result in a corrupted db.
+    fn resync_chain(&mut self) -> Result<(), Self::Error> {
+        let consensus = self.consensus_manager().consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let chain_path = match self.get_sink() {
+            Ok(sink) => session.get_virtual_chain_from_block(sink, Some(RESYNC_CHUNK_SIZE)).ok(),
+            Err(StoreError::KeyNotFound(_)) => None,
+            Err(err) => return Err(err.into()),
+        };
+        let mut chain_path = match chain_path {
+            Some(chain_path) => {
+                info!("Catching up the {}...", Self::IDENT);
+                chain_path
+            }
+            None => {
+                info!("Resyncing the {}...", Self::IDENT);
+                self.delete_all()?;
+                let source = session.get_source();
+                let mut batch = WriteBatch::default();
+                self.set_sink(&mut batch, source)?;
+                self.write_batch(batch)?;
+                session.get_virtual_chain_from_block(source, Some(RESYNC_CHUNK_SIZE))?
+            }
+        };
+
+        loop {
+            trace!(
+                "[{0}] resyncing with {1} added and {2} removed chain blocks from consensus db",
+                Self::IDENT,
+                chain_path.added.len(),
+                chain_path.removed.len()
+            );
+            let acceptance_data = session.get_blocks_acceptance_data(&chain_path.added, None)?;
+            self.apply_chain_changes(&*session, &chain_path.removed, &chain_path.added, &acceptance_data, true)?;
+
+            if chain_path.added.len() < RESYNC_CHUNK_SIZE {
+                break;
+            }
+            let low = *chain_path.added.last().expect("expected a last chain block");
+            chain_path = session.get_virtual_chain_from_block(low, Some(RESYNC_CHUNK_SIZE))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Resyncs a [`ChainIndex`] once the consensus it follows is replaced.
+pub struct ChainIndexConsensusResetHandler<T: ChainIndex> {
+    index: Weak<RwLock<T>>,
+}
+
+impl<T: ChainIndex> ChainIndexConsensusResetHandler<T> {
+    pub fn new(index: Weak<RwLock<T>>) -> Self {
+        Self { index }
+    }
+}
+
+impl<T: ChainIndex + Send + Sync> ConsensusResetHandler for ChainIndexConsensusResetHandler<T> {
+    fn handle_consensus_reset(&self) {
+        if let Some(index) = self.index.upgrade() {
+            index.write().resync_chain().unwrap();
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod index;
+pub mod sink;
+
+pub use crate::index::{ChainIndex, ChainIndexConsensusResetHandler};
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use rocksdb::WriteBatch;
+use vecno_database::{
+    prelude::{BatchDbWriter, CachedDbItem, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use vecno_hashes::Hash;
+
+/// Reader API for `IndexSinkStore`.
+pub trait IndexSinkStoreReader {
+    fn get(&self) -> StoreResult<Hash>;
+}
+
+pub trait IndexSinkStore: IndexSinkStoreReader {
+    fn set(&mut self, batch: &mut WriteBatch, sink: Hash) -> StoreResult<()>;
+    fn remove(&mut self) -> Result<(), StoreError>;
+}
+
+/// A DB + cache implementation of `IndexSinkStore` trait, storing the sink of an index under `prefix`
+#[derive(Clone)]
+pub struct DbIndexSinkStore {
+    db: Arc<DB>,
+    access: CachedDbItem<Hash>,
+}
+
+impl DbIndexSinkStore {
+    pub fn new(db: Arc<DB>, prefix: DatabaseStorePrefixes) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbItem::new(db.clone(), prefix.into()) }
+    }
+}
+
+impl IndexSinkStoreReader for DbIndexSinkStore {
+    fn get(&self) -> StoreResult<Hash> {
+        self.access.read()
+    }
+}
+
+impl IndexSinkStore for DbIndexSinkStore {
+    fn set(&mut self, batch: &mut WriteBatch, sink: Hash) -> Result<(), StoreError> {
+        self.access.write(BatchDbWriter::new(batch), &sink)
+    }
+
+    fn remove(&mut self) -> Result<(), StoreError> {
+        self.access.remove(DirectDbWriter::new(&self.db))
+    }
+}
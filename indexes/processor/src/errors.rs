@@ -1,5 +1,7 @@
 use thiserror::Error;
+use vecno_addressindex::errors::AddressIndexError;
 use vecno_notify::events::EventType;
+use vecno_txindex::errors::TxIndexError;
 use vecno_utxoindex::errors::UtxoIndexError;
 
 #[derive(Error, Debug)]
@@ -7,6 +9,12 @@ pub enum IndexError {
     #[error("{0}")]
     UtxoIndexError(#[from] UtxoIndexError),
 
+    #[error("{0}")]
+    TxIndexError(#[from] TxIndexError),
+
+    #[error("{0}")]
+    AddressIndexError(#[from] AddressIndexError),
+
     #[error("event type {0:?} is not supported")]
     NotSupported(EventType),
 }
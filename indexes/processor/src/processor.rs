@@ -7,6 +7,7 @@ use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc,
 };
+use vecno_addressindex::api::AddressIndexProxy;
 use vecno_consensus_notify::{notification as consensus_notification, notification::Notification as ConsensusNotification};
 use vecno_core::{debug, trace};
 use vecno_index_core::notification::{Notification, PruningPointUtxoSetOverrideNotification, UtxosChangedNotification};
@@ -17,11 +18,13 @@ use vecno_notify::{
     notification::Notification as NotificationTrait,
     notifier::DynNotify,
 };
+use vecno_txindex::api::TxIndexProxy;
 use vecno_utils::triggers::SingleTrigger;
 use vecno_utxoindex::api::UtxoIndexProxy;
 
 /// Processor processes incoming consensus UtxosChanged and PruningPointUtxoSetOverride
-/// notifications submitting them to a UtxoIndex.
+/// notifications submitting them to a UtxoIndex, and VirtualChainChanged notifications
+/// submitting them to a TxIndex and an AddressIndex.
 ///
 /// It also acts as a [`Collector`], converting the incoming consensus notifications
 /// into their pending local versions and relaying them to a local notifier.
@@ -30,6 +33,12 @@ pub struct Processor {
     /// An optional UTXO indexer
     utxoindex: Option<UtxoIndexProxy>,
 
+    /// An optional transaction indexer
+    txindex: Option<TxIndexProxy>,
+
+    /// An optional address history indexer
+    addressindex: Option<AddressIndexProxy>,
+
     recv_channel: CollectorNotificationReceiver<ConsensusNotification>,
 
     /// Has this collector been started?
@@ -39,9 +48,16 @@ pub struct Processor {
 }
 
 impl Processor {
-    pub fn new(utxoindex: Option<UtxoIndexProxy>, recv_channel: CollectorNotificationReceiver<ConsensusNotification>) -> Self {
+    pub fn new(
+        utxoindex: Option<UtxoIndexProxy>,
+        txindex: Option<TxIndexProxy>,
+        addressindex: Option<AddressIndexProxy>,
+        recv_channel: CollectorNotificationReceiver<ConsensusNotification>,
+    ) -> Self {
         Self {
             utxoindex,
+            txindex,
+            addressindex,
             recv_channel,
             collect_shutdown: Arc::new(SingleTrigger::new()),
             is_started: Arc::new(AtomicBool::new(false)),
@@ -58,12 +74,13 @@ impl Processor {
 
             while let Ok(notification) = self.recv_channel.recv().await {
                 match self.process_notification(notification).await {
-                    Ok(notification) => match notifier.notify(notification) {
+                    Ok(Some(notification)) => match notifier.notify(notification) {
                         Ok(_) => (),
                         Err(err) => {
                             trace!("[Index processor] notification sender error: {err:?}");
                         }
                     },
+                    Ok(None) => (),
                     Err(err) => {
                         trace!("[Index processor] error while processing a consensus notification: {err:?}");
                     }
@@ -76,13 +93,18 @@ impl Processor {
         });
     }
 
-    async fn process_notification(self: &Arc<Self>, notification: ConsensusNotification) -> IndexResult<Notification> {
+    /// Processes a consensus notification, returning the local notification to relay, if any.
+    async fn process_notification(self: &Arc<Self>, notification: ConsensusNotification) -> IndexResult<Option<Notification>> {
         match notification {
             ConsensusNotification::UtxosChanged(utxos_changed) => {
-                Ok(Notification::UtxosChanged(self.process_utxos_changed(utxos_changed).await?))
+                Ok(Some(Notification::UtxosChanged(self.process_utxos_changed(utxos_changed).await?)))
             }
             ConsensusNotification::PruningPointUtxoSetOverride(_) => {
-                Ok(Notification::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification {}))
+                Ok(Some(Notification::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification {})))
+            }
+            ConsensusNotification::VirtualChainChanged(virtual_chain_changed) => {
+                self.process_virtual_chain_changed(virtual_chain_changed).await?;
+                Ok(None)
             }
             _ => Err(IndexError::NotSupported(notification.event_type())),
         }
@@ -106,6 +128,40 @@ impl Processor {
         Err(IndexError::NotSupported(EventType::UtxosChanged))
     }
 
+    async fn process_virtual_chain_changed(
+        self: &Arc<Self>,
+        notification: consensus_notification::VirtualChainChangedNotification,
+    ) -> IndexResult<()> {
+        trace!("[{IDENT}]: processing {:?}", notification);
+        if self.txindex.is_none() && self.addressindex.is_none() {
+            return Err(IndexError::NotSupported(EventType::VirtualChainChanged));
+        }
+        debug!(
+            "IDXPRC, Indexing the transactions of {} added and {} removed chain blocks",
+            notification.added_chain_block_hashes.len(),
+            notification.removed_chain_block_hashes.len()
+        );
+        if let Some(txindex) = self.txindex.clone() {
+            txindex
+                .update(
+                    notification.removed_chain_block_hashes.clone(),
+                    notification.added_chain_block_hashes.clone(),
+                    notification.added_chain_blocks_acceptance_data.clone(),
+                )
+                .await?;
+        }
+        if let Some(addressindex) = self.addressindex.clone() {
+            addressindex
+                .update(
+                    notification.removed_chain_block_hashes,
+                    notification.added_chain_block_hashes,
+                    notification.added_chain_blocks_acceptance_data,
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
     async fn join_collecting_task(&self) -> Result<()> {
         trace!("[Index processor] joining");
         self.collect_shutdown.listener.clone().await;
@@ -131,6 +187,7 @@ mod tests {
     use async_channel::{unbounded, Receiver, Sender};
     use rand::{rngs::SmallRng, SeedableRng};
     use std::sync::Arc;
+    use vecno_addressindex::AddressIndex;
     use vecno_consensus::{config::Config, consensus::test_consensus::TestConsensus, params::DEVNET_PARAMS, test_helpers::*};
     use vecno_consensus_core::utxo::{utxo_collection::UtxoCollection, utxo_diff::UtxoDiff};
     use vecno_consensusmanager::ConsensusManager;
@@ -138,6 +195,7 @@ mod tests {
     use vecno_database::prelude::ConnBuilder;
     use vecno_database::utils::DbLifetime;
     use vecno_notify::notifier::test_helpers::NotifyMock;
+    use vecno_txindex::TxIndex;
     use vecno_utxoindex::UtxoIndex;
 
     // TODO: rewrite with Simnet, when possible.
@@ -149,22 +207,36 @@ mod tests {
         processor_receiver: Receiver<Notification>,
         test_consensus: TestConsensus,
         utxoindex_db_lifetime: DbLifetime,
+        txindex_db_lifetime: DbLifetime,
+        addressindex_db_lifetime: DbLifetime,
     }
 
     impl NotifyPipeline {
         fn new() -> Self {
             let (consensus_sender, consensus_receiver) = unbounded();
             let (utxoindex_db_lifetime, utxoindex_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let (txindex_db_lifetime, txindex_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let (addressindex_db_lifetime, addressindex_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
             let config = Arc::new(Config::new(DEVNET_PARAMS));
             let tc = TestConsensus::new(&config);
             tc.init();
             let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
-            let utxoindex = Some(UtxoIndexProxy::new(UtxoIndex::new(consensus_manager, utxoindex_db).unwrap()));
-            let processor = Arc::new(Processor::new(utxoindex, consensus_receiver));
+            let utxoindex = Some(UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap()));
+            let txindex = Some(TxIndexProxy::new(TxIndex::new(consensus_manager.clone(), txindex_db).unwrap()));
+            let addressindex = Some(AddressIndexProxy::new(AddressIndex::new(consensus_manager, addressindex_db).unwrap()));
+            let processor = Arc::new(Processor::new(utxoindex, txindex, addressindex, consensus_receiver));
             let (processor_sender, processor_receiver) = unbounded();
             let notifier = Arc::new(NotifyMock::new(processor_sender));
             processor.clone().start(notifier);
-            Self { test_consensus: tc, consensus_sender, processor, processor_receiver, utxoindex_db_lifetime }
+            Self {
+                test_consensus: tc,
+                consensus_sender,
+                processor,
+                processor_receiver,
+                utxoindex_db_lifetime,
+                txindex_db_lifetime,
+                addressindex_db_lifetime,
+            }
         }
     }
 
@@ -247,4 +319,28 @@ mod tests {
         pipeline.consensus_sender.close();
         pipeline.processor.clone().join().await.expect("stopping the processor must succeed");
     }
+
+    #[tokio::test]
+    async fn test_virtual_chain_changed_notification() {
+        let pipeline = NotifyPipeline::new();
+
+        // A virtual chain change is consumed by the txindex and the addressindex and not relayed.
+        let test_notification =
+            consensus_notification::VirtualChainChangedNotification::new(Arc::new(vec![]), Arc::new(vec![]), Arc::new(vec![]));
+        pipeline.consensus_sender.send(ConsensusNotification::VirtualChainChanged(test_notification)).await.expect("expected send");
+        pipeline
+            .consensus_sender
+            .send(ConsensusNotification::PruningPointUtxoSetOverride(
+                consensus_notification::PruningPointUtxoSetOverrideNotification {},
+            ))
+            .await
+            .expect("expected send");
+        match pipeline.processor_receiver.recv().await.expect("expected recv") {
+            Notification::PruningPointUtxoSetOverride(_) => (),
+            unexpected_notification => panic!("Unexpected notification: {unexpected_notification:?}"),
+        }
+        assert!(pipeline.processor_receiver.is_empty(), "the notification receiver should be empty");
+        pipeline.consensus_sender.close();
+        pipeline.processor.clone().join().await.expect("stopping the processor must succeed");
+    }
 }
@@ -0,0 +1,73 @@
+use parking_lot::RwLock;
+use std::{fmt::Debug, sync::Arc};
+use vecno_consensus_core::{acceptance_data::AcceptanceData, tx::TransactionId};
+use vecno_consensusmanager::spawn_blocking;
+use vecno_database::prelude::StoreResult;
+use vecno_hashes::Hash;
+
+use crate::{errors::TxIndexResult, model::TxIndexEntry};
+
+///Txindex API targeted at retrieval calls.
+pub trait TxIndexApi: Send + Sync + Debug {
+    /// Retrieve the entries of the given transaction ids from the txindex db,
+    /// yielding `None` for transactions which are not indexed.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_transaction_entries(&self, transaction_ids: Vec<TransactionId>) -> StoreResult<Vec<Option<TxIndexEntry>>>;
+
+    /// Retrieve the last chain block processed by the txindex (used for testing purposes).
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_txindex_sink(&self) -> StoreResult<Hash>;
+
+    /// Checks if the txindex's db is synced with consensus.
+    ///
+    /// Note:
+    /// 1) Use a read lock when accessing this method
+    /// 2) due to potential sync-gaps is_synced is unreliable while consensus is actively resolving virtual states.
+    fn is_synced(&self) -> TxIndexResult<bool>;
+
+    /// Update the txindex with the given virtual chain changes.
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()>;
+
+    /// Resync the txindex from the consensus db
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn resync(&mut self) -> TxIndexResult<()>;
+}
+
+/// Async proxy for the transaction index
+#[derive(Debug, Clone)]
+pub struct TxIndexProxy {
+    inner: Arc<RwLock<dyn TxIndexApi>>,
+}
+
+impl TxIndexProxy {
+    pub fn new(inner: Arc<RwLock<dyn TxIndexApi>>) -> Self {
+        Self { inner }
+    }
+
+    pub async fn get_transaction_entries(self, transaction_ids: Vec<TransactionId>) -> StoreResult<Vec<Option<TxIndexEntry>>> {
+        spawn_blocking(move || self.inner.read().get_transaction_entries(transaction_ids)).await.unwrap()
+    }
+
+    pub async fn update(
+        self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()> {
+        spawn_blocking(move || {
+            self.inner.write().update(removed_chain_block_hashes, added_chain_block_hashes, added_chain_blocks_acceptance_data)
+        })
+        .await
+        .unwrap()
+    }
+}
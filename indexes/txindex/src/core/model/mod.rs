@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use vecno_consensus_core::tx::TransactionId;
+use vecno_hashes::Hash;
+use vecno_utils::mem_size::MemSizeEstimator;
+
+/// The location of an accepted transaction within the DAG.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxIndexEntry {
+    /// The chain block whose mergeset accepted the transaction
+    pub accepting_block_hash: Hash,
+    /// The DAA score of the accepting block
+    pub accepting_block_daa_score: u64,
+    /// The (merged) block containing the transaction
+    pub including_block_hash: Hash,
+    /// The position of the transaction within the including block
+    pub index_within_block: u32,
+}
+
+impl MemSizeEstimator for TxIndexEntry {}
+
+/// A [`TxIndexEntry`] keyed by the id of the transaction it locates.
+pub type TxIndexEntries = Vec<(TransactionId, TxIndexEntry)>;
@@ -0,0 +1,243 @@
+use crate::{
+    api::TxIndexApi,
+    errors::{TxIndexError, TxIndexResult},
+    model::{TxIndexEntries, TxIndexEntry},
+    stores::store_manager::Store,
+    IDENT,
+};
+use parking_lot::RwLock;
+use rocksdb::WriteBatch;
+use std::{fmt::Debug, sync::Arc};
+use vecno_consensus_core::{acceptance_data::AcceptanceData, api::ConsensusApi, tx::TransactionId};
+use vecno_consensusmanager::ConsensusManager;
+use vecno_core::trace;
+use vecno_database::prelude::{StoreResult, DB};
+use vecno_hashes::Hash;
+use vecno_index_chain::{ChainIndex, ChainIndexConsensusResetHandler};
+
+/// TxIndex indexes [`TxIndexEntry`] by [`TransactionId`], for all transactions accepted by the virtual selected chain,
+/// and commits them to its own store.
+/// Note: The TxIndex struct by itself is not thread save, only correct usage of the supplied RwLock via `new` makes it so.
+/// please follow guidelines found in the comments under `txindex::core::api::TxIndexApi` for proper thread safety.
+pub struct TxIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+}
+
+impl TxIndex {
+    /// Creates a new [`TxIndex`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> TxIndexResult<Arc<RwLock<Self>>> {
+        let mut txindex = Self { consensus_manager: consensus_manager.clone(), store: Store::new(db) };
+        if !txindex.is_synced()? {
+            txindex.resync()?;
+        }
+        let txindex = Arc::new(RwLock::new(txindex));
+        consensus_manager.register_consensus_reset_handler(Arc::new(ChainIndexConsensusResetHandler::new(Arc::downgrade(&txindex))));
+        Ok(txindex)
+    }
+}
+
+impl ChainIndex for TxIndex {
+    type Error = TxIndexError;
+
+    const IDENT: &'static str = IDENT;
+
+    fn consensus_manager(&self) -> &Arc<ConsensusManager> {
+        &self.consensus_manager
+    }
+
+    fn index_chain_block(
+        &mut self,
+        batch: &mut WriteBatch,
+        consensus: &dyn ConsensusApi,
+        accepting_block_hash: Hash,
+        acceptance_data: &AcceptanceData,
+    ) -> TxIndexResult<()> {
+        let accepting_block_daa_score = consensus.get_header(accepting_block_hash)?.daa_score;
+        let entries: TxIndexEntries = acceptance_data
+            .iter()
+            .flat_map(|mergeset_block_data| {
+                mergeset_block_data.accepted_transactions.iter().map(move |tx| {
+                    (
+                        tx.transaction_id,
+                        TxIndexEntry {
+                            accepting_block_hash,
+                            accepting_block_daa_score,
+                            including_block_hash: mergeset_block_data.block_hash,
+                            index_within_block: tx.index_within_block,
+                        },
+                    )
+                })
+            })
+            .collect();
+        Ok(self.store.add_entries(batch, &entries)?)
+    }
+
+    fn unindex_chain_block(
+        &mut self,
+        batch: &mut WriteBatch,
+        _consensus: &dyn ConsensusApi,
+        accepting_block_hash: Hash,
+        acceptance_data: &AcceptanceData,
+    ) -> TxIndexResult<()> {
+        let transaction_ids = acceptance_data
+            .iter()
+            .flat_map(|mergeset_block_data| mergeset_block_data.accepted_transactions.iter().map(|tx| tx.transaction_id))
+            .collect::<Vec<_>>();
+        Ok(self.store.remove_entries(batch, accepting_block_hash, &transaction_ids)?)
+    }
+
+    fn get_sink(&self) -> StoreResult<Hash> {
+        self.store.get_sink()
+    }
+
+    fn set_sink(&mut self, batch: &mut WriteBatch, sink: Hash) -> StoreResult<()> {
+        self.store.set_sink(batch, sink)
+    }
+
+    fn write_batch(&mut self, batch: WriteBatch) -> StoreResult<()> {
+        self.store.write_batch(batch)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.store.delete_all()
+    }
+}
+
+impl TxIndexApi for TxIndex {
+    /// Retrieve the entries of the given transaction ids from the txindex db.
+    fn get_transaction_entries(&self, transaction_ids: Vec<TransactionId>) -> StoreResult<Vec<Option<TxIndexEntry>>> {
+        trace!("[{0}] retrieving {1} transaction entries", IDENT, transaction_ids.len());
+
+        transaction_ids.into_iter().map(|transaction_id| self.store.get_entry(transaction_id)).collect()
+    }
+
+    /// Retrieve the stored sink of the txindex.
+    fn get_txindex_sink(&self) -> StoreResult<Hash> {
+        trace!("[{0}] retrieving sink", IDENT);
+
+        self.store.get_sink()
+    }
+
+    /// Updates the [TxIndex] via the virtual chain changes supplied, see [`ChainIndex::update_chain`].
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()> {
+        self.update_chain(removed_chain_block_hashes, added_chain_block_hashes, added_chain_blocks_acceptance_data)
+    }
+
+    /// Checks to see if the [TxIndex] is sync'd, see [`ChainIndex::is_chain_synced`].
+    fn is_synced(&self) -> TxIndexResult<bool> {
+        self.is_chain_synced()
+    }
+
+    /// Brings the txindex up to date with the virtual selected chain of the consensus database, see [`ChainIndex::resync_chain`].
+    fn resync(&mut self) -> TxIndexResult<()> {
+        self.resync_chain()
+    }
+}
+
+impl Debug for TxIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxIndex").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{api::TxIndexApi, TxIndex};
+    use std::sync::Arc;
+    use vecno_consensus::{config::ConfigBuilder, consensus::test_consensus::TestConsensus, params::MAINNET_PARAMS};
+    use vecno_consensus_core::{
+        api::ConsensusApi,
+        coinbase::MinerData,
+        tx::{ScriptPublicKey, TransactionId},
+    };
+    use vecno_consensusmanager::ConsensusManager;
+    use vecno_database::create_temp_db;
+    use vecno_database::prelude::ConnBuilder;
+    use vecno_hashes::Hash;
+
+    async fn add_chain(tc: &TestConsensus, parent: Hash, hashes: impl Iterator<Item = u64>, extra_data: &[u8]) {
+        let mut parent = parent;
+        for i in hashes {
+            let miner_data = MinerData::new(ScriptPublicKey::from_vec(0, vec![]), extra_data.to_vec());
+            let block = tc.build_utxo_valid_block_with_parents(i.into(), vec![parent], miner_data, vec![]);
+            tc.validate_and_insert_block(block.to_immutable()).virtual_state_task.await.unwrap();
+            parent = i.into();
+        }
+    }
+
+    fn coinbase_id(tc: &TestConsensus, hash: Hash) -> TransactionId {
+        tc.get_block(hash).unwrap().transactions[0].id()
+    }
+
+    #[tokio::test]
+    async fn test_txindex() {
+        vecno_core::log::try_init_logger("INFO");
+
+        let config = ConfigBuilder::new(MAINNET_PARAMS)
+            .skip_proof_of_work()
+            .edit_consensus_params(|p| {
+                p.min_difficulty_window_len = p.legacy_difficulty_window_size;
+            })
+            .build();
+        let tc = Arc::new(TestConsensus::new(&config));
+        let wait_handles = tc.init();
+        let (_txindex_db_lifetime, txindex_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+        let txindex = TxIndex::new(consensus_manager, txindex_db).unwrap();
+
+        // A fresh index is synced at the genesis.
+        assert!(txindex.read().is_synced().unwrap());
+        assert_eq!(txindex.read().get_txindex_sink().unwrap(), config.genesis.hash);
+
+        // Build a chain 1 <- 2 <- ... <- 5 and catch up.
+        add_chain(&tc, config.genesis.hash, 1..6, b"main").await;
+        assert!(!txindex.read().is_synced().unwrap());
+        txindex.write().resync().unwrap();
+        assert!(txindex.read().is_synced().unwrap());
+        assert_eq!(txindex.read().get_txindex_sink().unwrap(), 5.into());
+
+        // The coinbase of chain block `i` is accepted by chain block `i + 1`.
+        let main_coinbase_ids = (1..5).map(|i| coinbase_id(&tc, i.into())).collect::<Vec<_>>();
+        let entries = txindex.read().get_transaction_entries(main_coinbase_ids.clone()).unwrap();
+        for (i, entry) in (1..5u64).zip(entries) {
+            let entry = entry.expect("expected the coinbase to be indexed");
+            assert_eq!(entry.including_block_hash, i.into());
+            assert_eq!(entry.accepting_block_hash, (i + 1).into());
+            assert_eq!(entry.accepting_block_daa_score, tc.get_header((i + 1).into()).unwrap().daa_score);
+            assert_eq!(entry.index_within_block, 0);
+        }
+
+        // Reorg to a longer chain 6 <- 7 <- ... <- 12 and catch up.
+        add_chain(&tc, config.genesis.hash, 6..13, b"side").await;
+        txindex.write().resync().unwrap();
+        assert!(txindex.read().is_synced().unwrap());
+        assert_eq!(txindex.read().get_txindex_sink().unwrap(), 12.into());
+
+        // Transactions of the removed chain are no longer indexed, those of the new one are.
+        assert!(txindex.read().get_transaction_entries(main_coinbase_ids).unwrap().into_iter().all(|entry| entry.is_none()));
+        let side_coinbase_ids = (6..12).map(|i| coinbase_id(&tc, i.into())).collect::<Vec<_>>();
+        let side_entries = txindex.read().get_transaction_entries(side_coinbase_ids.clone()).unwrap();
+        for (i, entry) in (6..12u64).zip(side_entries.iter()) {
+            let entry = entry.as_ref().expect("expected the coinbase to be indexed");
+            assert_eq!(entry.including_block_hash, i.into());
+            assert_eq!(entry.accepting_block_hash, (i + 1).into());
+        }
+
+        // A full rebuild from a reset store yields the same state.
+        txindex.write().store.delete_all().unwrap();
+        assert!(txindex.read().get_transaction_entries(side_coinbase_ids.clone()).unwrap().into_iter().all(|entry| entry.is_none()));
+        txindex.write().resync().unwrap();
+        assert!(txindex.read().is_synced().unwrap());
+        assert_eq!(txindex.read().get_txindex_sink().unwrap(), 12.into());
+        assert_eq!(txindex.read().get_transaction_entries(side_coinbase_ids).unwrap(), side_entries);
+
+        drop(txindex);
+        tc.shutdown(wait_handles);
+    }
+}
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use crate::core::model::{TxIndexEntries, TxIndexEntry};
+use rocksdb::WriteBatch;
+use vecno_consensus_core::tx::TransactionId;
+use vecno_database::prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreError, StoreResult, DB};
+use vecno_database::registry::DatabaseStorePrefixes;
+
+// Traits:
+
+pub trait TxIndexEntriesStoreReader {
+    /// Get the [TxIndexEntry] of a transaction, returning `None` if the transaction is not indexed.
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>>;
+}
+
+pub trait TxIndexEntriesStore: TxIndexEntriesStoreReader {
+    /// add [TxIndexEntries] into the [TxIndexEntriesStore] via `batch`, overriding existing entries.
+    fn add_entries(&mut self, batch: &mut WriteBatch, entries: &TxIndexEntries) -> StoreResult<()>;
+
+    /// remove the entries of the given transaction ids from the [TxIndexEntriesStore] via `batch`.
+    fn remove_entries(&mut self, batch: &mut WriteBatch, transaction_ids: &[TransactionId]) -> StoreResult<()>;
+
+    /// removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+// Implementations:
+
+#[derive(Clone)]
+pub struct DbTxIndexEntriesStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<TransactionId, TxIndexEntry>,
+}
+
+impl DbTxIndexEntriesStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndex.into()) }
+    }
+}
+
+impl TxIndexEntriesStoreReader for DbTxIndexEntriesStore {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        match self.access.read(transaction_id) {
+            Ok(entry) => Ok(Some(entry)),
+            Err(StoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl TxIndexEntriesStore for DbTxIndexEntriesStore {
+    fn add_entries(&mut self, batch: &mut WriteBatch, entries: &TxIndexEntries) -> StoreResult<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let mut writer = BatchDbWriter::new(batch);
+        let mut to_add = entries.iter().map(|(transaction_id, entry)| (*transaction_id, *entry));
+
+        self.access.write_many(&mut writer, &mut to_add)?;
+        Ok(())
+    }
+
+    fn remove_entries(&mut self, batch: &mut WriteBatch, transaction_ids: &[TransactionId]) -> StoreResult<()> {
+        if transaction_ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut writer = BatchDbWriter::new(batch);
+        let mut to_remove = transaction_ids.iter().copied();
+
+        self.access.delete_many(&mut writer, &mut to_remove)?;
+        Ok(())
+    }
+
+    /// Removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use rocksdb::WriteBatch;
+use vecno_consensus_core::tx::TransactionId;
+use vecno_core::trace;
+use vecno_database::{
+    prelude::{CachePolicy, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use vecno_hashes::Hash;
+use vecno_index_chain::sink::{DbIndexSinkStore, IndexSinkStore, IndexSinkStoreReader};
+
+use crate::{
+    model::{TxIndexEntries, TxIndexEntry},
+    stores::entries::{DbTxIndexEntriesStore, TxIndexEntriesStore, TxIndexEntriesStoreReader},
+    IDENT,
+};
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    txindex_sink_store: DbIndexSinkStore,
+    txindex_entries_store: DbTxIndexEntriesStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            txindex_sink_store: DbIndexSinkStore::new(db.clone(), DatabaseStorePrefixes::TxIndexSink),
+            txindex_entries_store: DbTxIndexEntriesStore::new(db.clone(), CachePolicy::Empty),
+            db,
+        }
+    }
+
+    pub fn get_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        self.txindex_entries_store.get(transaction_id)
+    }
+
+    /// Removes the entries of `transaction_ids` which are still accepted by `accepting_block_hash` via `batch`.
+    ///
+    /// Entries which were meanwhile re-accepted by another chain block are left untouched.
+    pub fn remove_entries(
+        &mut self,
+        batch: &mut WriteBatch,
+        accepting_block_hash: Hash,
+        transaction_ids: &[TransactionId],
+    ) -> StoreResult<()> {
+        let mut to_remove = Vec::with_capacity(transaction_ids.len());
+        for transaction_id in transaction_ids.iter().copied() {
+            if let Some(entry) = self.txindex_entries_store.get(transaction_id)? {
+                if entry.accepting_block_hash == accepting_block_hash {
+                    to_remove.push(transaction_id);
+                }
+            }
+        }
+        self.txindex_entries_store.remove_entries(batch, &to_remove)
+    }
+
+    pub fn add_entries(&mut self, batch: &mut WriteBatch, to_add: &TxIndexEntries) -> StoreResult<()> {
+        self.txindex_entries_store.add_entries(batch, to_add)
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.txindex_sink_store.get()
+    }
+
+    pub fn set_sink(&mut self, batch: &mut WriteBatch, sink: Hash) -> StoreResult<()> {
+        self.txindex_sink_store.set(batch, sink)
+    }
+
+    /// Commits the writes staged in `batch` to the txindex database at once.
+    pub fn write_batch(&self, batch: WriteBatch) -> StoreResult<()> {
+        Ok(self.db.write(batch)?)
+    }
+
+    /// Resets the txindex database:
+    pub fn delete_all(&mut self) -> StoreResult<()> {
+        trace!("[{0}] attempting to clear txindex database...", IDENT);
+
+        // Clear all
+        self.txindex_sink_store.remove()?;
+        self.txindex_entries_store.delete_all()?;
+
+        trace!("[{0}] clearing txindex database - success!", IDENT);
+
+        Ok(())
+    }
+}
@@ -495,7 +495,8 @@ impl TryFrom<GetMetricsResponse> for MetricsData {
             process_metrics,
             storage_metrics,
             custom_metrics: _,
-        } = response; //rpc.get_metrics(true, true, true, true, true, false).await?;
+            stratum_metrics: _,
+        } = response; //rpc.get_metrics(true, true, true, true, true, false, false).await?;
 
         let consensus_metrics = consensus_metrics.ok_or(Error::MissingData("Consensus Metrics"))?;
         let connection_metrics = connection_metrics.ok_or(Error::MissingData("Connection Metrics"))?;
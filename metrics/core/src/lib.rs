@@ -121,6 +121,6 @@ impl Metrics {
     }
 
     async fn sample_metrics(self: &Arc<Self>, rpc: Arc<dyn RpcApi>) -> Result<MetricsData> {
-        MetricsData::try_from(rpc.get_metrics(true, true, true, true, true, false).await?)
+        MetricsData::try_from(rpc.get_metrics(true, true, true, true, true, false, false).await?)
     }
 }
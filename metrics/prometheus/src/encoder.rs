@@ -0,0 +1,133 @@
+use std::fmt::Write;
+
+/// Type of an OpenMetrics metric family
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FamilyType {
+    Counter,
+    Gauge,
+}
+
+impl FamilyType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            FamilyType::Counter => "counter",
+            FamilyType::Gauge => "gauge",
+        }
+    }
+}
+
+/// Writer of the OpenMetrics text exposition format.
+///
+/// Families must be declared with [`OpenMetricsEncoder::family`] before their samples get written.
+/// Counter samples get the mandatory `_total` suffix appended to the family name.
+#[derive(Default)]
+pub struct OpenMetricsEncoder {
+    buffer: String,
+    family: Option<(String, FamilyType)>,
+}
+
+impl OpenMetricsEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn family(&mut self, name: &str, family_type: FamilyType, help: &str) -> &mut Self {
+        writeln!(self.buffer, "# TYPE {} {}", name, family_type.as_str()).unwrap();
+        writeln!(self.buffer, "# HELP {} {}", name, escape(help)).unwrap();
+        self.family = Some((name.to_owned(), family_type));
+        self
+    }
+
+    pub fn sample(&mut self, labels: &[(&str, &str)], value: f64) -> &mut Self {
+        let (name, family_type) = self.family.as_ref().expect("a family is declared before its samples");
+        self.buffer.push_str(name);
+        if *family_type == FamilyType::Counter {
+            self.buffer.push_str("_total");
+        }
+        if !labels.is_empty() {
+            let labels = labels.iter().map(|(key, value)| format!("{}=\"{}\"", key, escape(value))).collect::<Vec<_>>();
+            write!(self.buffer, "{{{}}}", labels.join(",")).unwrap();
+        }
+        writeln!(self.buffer, " {}", format_value(value)).unwrap();
+        self
+    }
+
+    /// Terminates the exposition and returns it
+    pub fn finish(mut self) -> String {
+        self.buffer.push_str("# EOF\n");
+        self.buffer
+    }
+}
+
+/// Converts a CamelCase identifier into a snake_case metric name component
+pub fn snake_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() + 8);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                result.push('_');
+            }
+            result.push(c.to_ascii_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Escapes label values and help texts
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '"' => result.push_str("\\\""),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_owned()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_owned()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encoding() {
+        let mut encoder = OpenMetricsEncoder::new();
+        encoder.family("vecno_rpc_requests", FamilyType::Counter, "Number of RPC requests").sample(&[("method", "GetInfo")], 3.0);
+        encoder.family("vecno_node_active_peers", FamilyType::Gauge, "Active \"peers\"\n").sample(&[], 8.0);
+        encoder.family("vecno_network_difficulty", FamilyType::Gauge, "Difficulty").sample(&[("quoted", "a\"b")], f64::NAN);
+        let expected = "\
+# TYPE vecno_rpc_requests counter
+# HELP vecno_rpc_requests Number of RPC requests
+vecno_rpc_requests_total{method=\"GetInfo\"} 3
+# TYPE vecno_node_active_peers gauge
+# HELP vecno_node_active_peers Active \\\"peers\\\"\\n
+vecno_node_active_peers 8
+# TYPE vecno_network_difficulty gauge
+# HELP vecno_network_difficulty Difficulty
+vecno_network_difficulty{quoted=\"a\\\"b\"} NaN
+# EOF
+";
+        assert_eq!(encoder.finish(), expected);
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("NodeCpuUsage"), "node_cpu_usage");
+        assert_eq!(snake_case("NodeP2pBytesTx"), "node_p2p_bytes_tx");
+        assert_eq!(snake_case("GetInfo"), "get_info");
+    }
+}
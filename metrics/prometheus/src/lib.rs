@@ -0,0 +1,269 @@
+//!
+//! Exporter serving the node metrics over HTTP in the OpenMetrics text format, so nodes can be scraped by Prometheus
+//! and compatible tooling.
+//!
+
+use crate::encoder::{snake_case, FamilyType, OpenMetricsEncoder};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    select,
+    time::timeout,
+};
+use vecno_consensus_core::api::counters::ProcessingCounters;
+use vecno_core::{
+    debug, info,
+    task::{
+        service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace, warn,
+};
+use vecno_metrics_core::{Metric, MetricsData, MetricsSnapshot};
+use vecno_mining::model::tx_query::TransactionQuery;
+use vecno_p2p_flows::flow_context::FlowContext;
+use vecno_rpc_core::api::rpc::RpcApi;
+use vecno_rpc_service::{counters::RpcOpCounters, service::RpcCoreService};
+use vecno_utils::{networking::NetAddress, triggers::SingleTrigger};
+
+pub mod encoder;
+
+pub const SERVICE_NAME: &str = "prometheus-exporter";
+
+/// Interval between two samplings of the node metrics, also used as time base of the per-second metrics
+const SAMPLING_INTERVAL: Duration = Duration::from_secs(1);
+
+const METRICS_PATH: &str = "/metrics";
+const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+const MAX_REQUEST_HEAD_SIZE: usize = 8 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct PrometheusExporter {
+    listen_address: NetAddress,
+    rpc_core_service: Arc<RpcCoreService>,
+    flow_context: Arc<FlowContext>,
+    processing_counters: Arc<ProcessingCounters>,
+    rpc_op_counters: Arc<RpcOpCounters>,
+    tick_service: Arc<TickService>,
+
+    /// Latest snapshot of the node metrics, available once two samples have been collected
+    snapshot: Mutex<Option<MetricsSnapshot>>,
+    shutdown: SingleTrigger,
+}
+
+impl PrometheusExporter {
+    pub fn new(
+        listen_address: NetAddress,
+        rpc_core_service: Arc<RpcCoreService>,
+        flow_context: Arc<FlowContext>,
+        processing_counters: Arc<ProcessingCounters>,
+        rpc_op_counters: Arc<RpcOpCounters>,
+        tick_service: Arc<TickService>,
+    ) -> Self {
+        Self {
+            listen_address,
+            rpc_core_service,
+            flow_context,
+            processing_counters,
+            rpc_op_counters,
+            tick_service,
+            snapshot: Mutex::new(None),
+            shutdown: SingleTrigger::default(),
+        }
+    }
+
+    /// Renders all the exported metrics in the OpenMetrics text format
+    pub fn render(&self) -> String {
+        let mut encoder = OpenMetricsEncoder::new();
+
+        if let Some(snapshot) = self.snapshot.lock().unwrap().clone() {
+            for metric in Metric::into_iter() {
+                let name = format!("vecno_{}", snake_case(metric.as_str()));
+                encoder.family(&name, FamilyType::Gauge, metric.title().0).sample(&[], snapshot.get(&metric));
+            }
+        }
+
+        let counters = self.processing_counters.snapshot();
+        for (name, help, value) in [
+            ("blocks_submitted", "Number of blocks submitted to consensus", counters.blocks_submitted),
+            ("headers_processed", "Number of processed headers", counters.header_counts),
+            ("dependencies_processed", "Number of processed header dependencies", counters.dep_counts),
+            ("mergeset_blocks_processed", "Number of processed mergeset blocks", counters.mergeset_counts),
+            ("bodies_processed", "Number of processed block bodies", counters.body_counts),
+            ("transactions_processed", "Number of processed transactions", counters.txs_counts),
+            ("chain_blocks_processed", "Number of processed chain blocks", counters.chain_block_counts),
+            ("chain_blocks_disqualified", "Number of disqualified chain blocks", counters.chain_disqualified_counts),
+            ("mass_processed", "Processed transaction mass", counters.mass_counts),
+        ] {
+            encoder.family(&format!("vecno_consensus_{}", name), FamilyType::Counter, help).sample(&[], value as f64);
+        }
+
+        let mining_manager = self.flow_context.mining_manager();
+        encoder
+            .family("vecno_mempool_transactions", FamilyType::Gauge, "Number of transactions in the mempool")
+            .sample(&[("pool", "ready")], mining_manager.transaction_count_sample(TransactionQuery::TransactionsOnly) as f64)
+            .sample(&[("pool", "orphan")], mining_manager.transaction_count_sample(TransactionQuery::OrphansOnly) as f64);
+
+        let peers = self.flow_context.hub().active_peers();
+        let outbound = peers.iter().filter(|peer| peer.is_outbound()).count();
+        encoder
+            .family("vecno_p2p_peers", FamilyType::Gauge, "Number of connected peers")
+            .sample(&[("direction", "inbound")], (peers.len() - outbound) as f64)
+            .sample(&[("direction", "outbound")], outbound as f64);
+
+        let compact_blocks = self.flow_context.compact_block_counters().snapshot();
+        encoder
+            .family("vecno_p2p_compact_blocks", FamilyType::Counter, "Number of relay blocks received as compact blocks per outcome")
+            .sample(&[("outcome", "reconstructed")], compact_blocks.reconstructed_counts as f64)
+            .sample(&[("outcome", "completed")], compact_blocks.completed_counts as f64)
+            .sample(&[("outcome", "fallback")], compact_blocks.fallback_counts as f64);
+        encoder
+            .family("vecno_p2p_compact_block_transactions", FamilyType::Counter, "Number of compact block transactions per source")
+            .sample(&[("source", "mempool")], compact_blocks.mempool_tx_counts as f64)
+            .sample(&[("source", "peer")], compact_blocks.requested_tx_counts as f64);
+        encoder
+            .family(
+                "vecno_p2p_compact_block_hit_rate",
+                FamilyType::Gauge,
+                "Ratio of the compact block transactions found in the mempool",
+            )
+            .sample(&[], compact_blocks.mempool_hit_rate());
+        encoder
+            .family(
+                "vecno_p2p_compact_block_reconstruction_rate",
+                FamilyType::Gauge,
+                "Ratio of the compact blocks rebuilt without requesting transactions from the peer",
+            )
+            .sample(&[], compact_blocks.reconstruction_rate());
+
+        encoder.family("vecno_rpc_requests", FamilyType::Counter, "Number of RPC requests per method");
+        for (op, count) in self.rpc_op_counters.snapshot() {
+            encoder.sample(&[("method", op)], count as f64);
+        }
+
+        encoder.finish()
+    }
+
+    async fn sampler(self: Arc<Self>) {
+        let mut last_data: Option<MetricsData> = None;
+        while let TickReason::Wakeup = self.tick_service.tick(SAMPLING_INTERVAL).await {
+            let data = match self.rpc_core_service.get_metrics(true, true, true, true, true, false, false).await {
+                Ok(response) => match MetricsData::try_from(response) {
+                    Ok(data) => data,
+                    Err(err) => {
+                        trace!("[{}] error converting the node metrics: {}", SERVICE_NAME, err);
+                        continue;
+                    }
+                },
+                Err(err) => {
+                    trace!("[{}] error sampling the node metrics: {}", SERVICE_NAME, err);
+                    continue;
+                }
+            };
+            if let Some(last_data) = last_data.replace(data.clone()) {
+                self.snapshot.lock().unwrap().replace(MetricsSnapshot::from((&last_data, &data)));
+            }
+        }
+        trace!("[{}] sampler exiting", SERVICE_NAME);
+    }
+
+    async fn serve(self: Arc<Self>, listener: TcpListener) {
+        let shutdown = self.shutdown.listener.clone();
+        loop {
+            select! {
+                _ = shutdown.clone() => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, peer)) => {
+                        let exporter = self.clone();
+                        tokio::spawn(async move {
+                            if let Err(err) = exporter.handle_connection(stream).await {
+                                debug!("[{}] error serving {}: {}", SERVICE_NAME, peer, err);
+                            }
+                        });
+                    }
+                    Err(err) => {
+                        warn!("[{}] error accepting a connection: {}", SERVICE_NAME, err);
+                    }
+                },
+            }
+        }
+        trace!("[{}] listener exiting", SERVICE_NAME);
+    }
+
+    async fn handle_connection(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        let head = timeout(REQUEST_TIMEOUT, read_request_head(&mut stream))
+            .await
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+
+        let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+        let (status, content_type, body) = match (request_line.next(), request_line.next()) {
+            (Some("GET"), Some(target)) if target.split('?').next() == Some(METRICS_PATH) => ("200 OK", CONTENT_TYPE, self.render()),
+            (Some("GET"), Some(_)) => ("404 Not Found", "text/plain; charset=utf-8", "Not Found\n".to_owned()),
+            _ => ("405 Method Not Allowed", "text/plain; charset=utf-8", "Method Not Allowed\n".to_owned()),
+        };
+
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            content_type,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await
+    }
+}
+
+/// Reads the request line and headers of an HTTP request. The request body, if any, is ignored.
+async fn read_request_head(stream: &mut TcpStream) -> std::io::Result<String> {
+    let mut head = Vec::with_capacity(1024);
+    let mut buffer = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        if head.len() >= MAX_REQUEST_HEAD_SIZE {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "request head too large"));
+        }
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+impl AsyncService for PrometheusExporter {
+    fn ident(self: Arc<Self>) -> &'static str {
+        SERVICE_NAME
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", SERVICE_NAME);
+        Box::pin(async move {
+            let listener = TcpListener::bind(self.listen_address.to_string()).await.map_err(|err| {
+                AsyncServiceError::Service(format!("{} failed to listen on {}: {}", SERVICE_NAME, self.listen_address, err))
+            })?;
+            info!("Prometheus exporter listening on http://{}{}", self.listen_address, METRICS_PATH);
+
+            let sampler = tokio::spawn(self.clone().sampler());
+            self.clone().serve(listener).await;
+            sampler.await.map_err(|err| AsyncServiceError::Service(err.to_string()))?;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", SERVICE_NAME);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", SERVICE_NAME);
+            Ok(())
+        })
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{block_template::BuilderError, mempool::RuleError};
 use thiserror::Error;
+use vecno_consensus_core::tx::TransactionId;
 
 #[derive(Error, Debug, Clone)]
 pub enum MiningManagerError {
@@ -10,6 +11,18 @@ pub enum MiningManagerError {
     /// A mempool rule error
     #[error(transparent)]
     MempoolError(#[from] RuleError),
+
+    /// A transaction package rejected as a whole because of the listed transactions
+    #[error("transaction package rejected: {}", display_rejections(.0))]
+    PackageRejected(Vec<(TransactionId, RuleError)>),
+
+    /// A block template policy with out of range settings
+    #[error("invalid block template policy: the prioritised mass share must be between 0 and 1")]
+    InvalidTemplatePolicy,
+}
+
+fn display_rejections(rejections: &[(TransactionId, RuleError)]) -> String {
+    rejections.iter().map(|(transaction_id, err)| format!("transaction {}: {}", transaction_id, err)).collect::<Vec<_>>().join("; ")
 }
 
 pub type MiningManagerResult<T> = std::result::Result<T, MiningManagerError>;
@@ -41,6 +41,10 @@ pub enum RuleError {
     #[error("transaction {0} is not standard: {1}")]
     RejectNonStandard(TransactionId, String),
 
+    /// An error emitted by the mempool admission policy
+    #[error("transaction {0} is rejected by the admission policy: {1}")]
+    RejectPolicy(TransactionId, PolicyError),
+
     #[error("one of the transaction inputs spends an immature UTXO: {0}")]
     RejectImmatureSpend(TxRuleError),
 
@@ -81,6 +85,27 @@ pub enum RuleError {
 
     #[error("Rejected tx {0} from mempool due to incomputable storage mass")]
     RejectStorageMassIncomputable(TransactionId),
+
+    #[error("transaction package is empty")]
+    RejectEmptyPackage,
+
+    #[error("transaction package has {0} transactions which is more than the allowed max amount of {1}")]
+    RejectPackageTooLarge(usize, usize),
+
+    #[error("transaction {0} appears more than once in the package")]
+    RejectPackageDuplicate(TransactionId),
+
+    #[error("transaction {0} spends an output of a transaction following it in the package")]
+    RejectPackageNotSorted(TransactionId),
+
+    #[error("output {0} is spent twice in the package, the second time by transaction {1}")]
+    RejectPackageDoubleSpend(TransactionOutpoint, TransactionId),
+
+    #[error("transaction package has {0} fees which is under the required amount of {1}")]
+    RejectPackageInsufficientFee(u64, u64),
+
+    #[error("output {0} already spent by transaction {1} in the Dandelion++ stem phase")]
+    RejectDoubleSpendInStempool(TransactionOutpoint, TransactionId),
 }
 
 impl From<NonStandardError> for RuleError {
@@ -152,3 +177,21 @@ impl NonStandardError {
 }
 
 pub type NonStandardResult<T> = std::result::Result<T, NonStandardError>;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    #[error("transaction output #{0} pays to a blocked script public key")]
+    RejectBlockedOutput(usize),
+
+    #[error("transaction input #{0} spends a UTXO of a blocked script public key")]
+    RejectBlockedInput(usize),
+
+    #[error("transaction payload of {0} bytes is larger than the max allowed size of {1} bytes")]
+    RejectPayloadSize(usize, usize),
+
+    /// A rejection by a custom admission policy
+    #[error("{0}")]
+    Reject(String),
+}
+
+pub type PolicyResult<T> = std::result::Result<T, PolicyError>;
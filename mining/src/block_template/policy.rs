@@ -1,3 +1,7 @@
+use serde::Deserialize;
+use std::collections::HashSet;
+use vecno_consensus_core::tx::TransactionId;
+
 /// Policy houses the policy (configuration parameters) which is used to control
 /// the generation of block templates. See the documentation for
 /// NewBlockTemplate for more details on how each of these parameters are used.
@@ -12,3 +16,76 @@ impl Policy {
         Self { max_block_mass }
     }
 }
+
+/// The order in which ready transactions are considered for inclusion in a block template
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TemplateSelectionMode {
+    /// Transactions are sampled at random with a probability growing with their feerate
+    #[default]
+    Weighted,
+    /// Transactions are taken strictly by descending feerate, making the selection deterministic
+    HighestFeerate,
+}
+
+/// Rules applied to the selection of the transactions of a block template, either set for the whole node through
+/// the `[template-policy]` table of the config file or provided along with a block template request
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TemplatePolicy {
+    pub selection_mode: TemplateSelectionMode,
+    /// Share of the block mass, between 0 and 1, filled first with prioritised transactions by descending feerate.
+    /// Transactions are prioritised either by being listed in [`Self::prioritised_transaction_ids`] or by having
+    /// been given a positive fee delta.
+    pub prioritised_mass_share: f64,
+    pub prioritised_transaction_ids: HashSet<TransactionId>,
+    /// Transactions which are never included in the template
+    pub excluded_transaction_ids: HashSet<TransactionId>,
+}
+
+impl TemplatePolicy {
+    /// Returns true if the policy selects transactions the same way as a node with no template policy
+    pub fn is_default(&self) -> bool {
+        self.selection_mode == TemplateSelectionMode::Weighted
+            && self.reserved_mass_share() == 0.0
+            && self.excluded_transaction_ids.is_empty()
+    }
+
+    /// Returns true if the policy is well formed
+    pub fn is_valid(&self) -> bool {
+        (0.0..=1.0).contains(&self.prioritised_mass_share)
+    }
+
+    /// Returns the share of the block mass reserved for prioritised transactions, clamped to a valid range
+    pub(crate) fn reserved_mass_share(&self) -> f64 {
+        if self.prioritised_mass_share.is_nan() {
+            0.0
+        } else {
+            self.prioritised_mass_share.clamp(0.0, 1.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_policy_deserialization() {
+        let policy: TemplatePolicy = toml::from_str(
+            r#"
+            selection-mode = "highest-feerate"
+            prioritised-mass-share = 0.25
+            excluded-transaction-ids = ["0000000000000000000000000000000000000000000000000000000000000001"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(policy.selection_mode, TemplateSelectionMode::HighestFeerate);
+        assert_eq!(policy.reserved_mass_share(), 0.25);
+        assert!(policy.prioritised_transaction_ids.is_empty());
+        assert_eq!(policy.excluded_transaction_ids.len(), 1);
+        assert!(policy.is_valid() && !policy.is_default());
+        assert!(TemplatePolicy::default().is_default());
+        assert!(!TemplatePolicy { prioritised_mass_share: 1.5, ..Default::default() }.is_valid());
+    }
+}
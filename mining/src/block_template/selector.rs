@@ -211,10 +211,15 @@ impl RebalancingWeightedTransactionSelector {
     /// calc_tx_value calculates a value to be used in transaction selection.
     /// The higher the number the more likely it is that the transaction will be
     /// included in the block.
+    ///
+    /// The value is based on the package feerate so that a low feerate transaction
+    /// gets selected along with the descendants paying for it (child-pays-for-parent).
+    /// The descendants themselves are only selectable in a subsequent block since
+    /// consensus forbids chained transactions within a block.
     fn calc_tx_value(&self, transaction: &CandidateTransaction) -> f64 {
         let mass_limit = self.policy.max_block_mass as f64;
-        let mass = transaction.calculated_mass as f64;
-        let fee = transaction.calculated_fee as f64;
+        let mass = transaction.package_mass as f64;
+        let fee = transaction.package_fee as f64;
         if transaction.tx.subnetwork_id.is_builtin_or_native() {
             fee / mass / mass_limit
         } else {
@@ -334,6 +339,6 @@ mod tests {
         let calculated_mass = transaction_estimated_serialized_size(&tx);
         let calculated_fee = DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE;
 
-        CandidateTransaction { tx, calculated_fee, calculated_mass }
+        CandidateTransaction { tx, calculated_fee, calculated_mass, package_fee: calculated_fee, package_mass: calculated_mass }
     }
 }
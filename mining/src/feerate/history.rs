@@ -0,0 +1,227 @@
+//! Historical feerate tracking.
+//!
+//! Unlike [`FeerateEstimator`](super::FeerateEstimator) which models the current mempool frontier, the history records,
+//! per accepted block, the feerates of the included transactions which were known to the mempool along with the time
+//! they spent in it. Estimations are then derived from observed confirmation times rather than from a model.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use std::collections::VecDeque;
+
+/// Inclusion times, in seconds, for which historical estimations are provided
+pub const TARGET_SECONDS: [u64; 5] = [1, 10, 60, 600, 3600];
+
+/// Fractions of the observed transactions which must have met the inclusion time for an estimation to hold
+pub const CONFIDENCE_LEVELS: [f64; 3] = [0.5, 0.8, 0.95];
+
+/// Minimum number of samples at or above a feerate for that feerate to be considered by an estimation
+pub const MIN_SAMPLES: usize = 10;
+
+/// Default duration, in milliseconds, during which block records are kept
+pub const DEFAULT_RETENTION: u64 = 6 * 60 * 60 * 1000;
+
+/// Maximum number of samples kept overall, bounding the size of the store at high transaction rates
+pub const MAX_SAMPLES: usize = 200_000;
+
+/// A transaction included in a block, along with the time it spent in the mempool
+#[derive(Clone, Copy, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct FeerateSample {
+    /// Fee/mass of the transaction in `sompi/gram` units
+    pub feerate: f64,
+    /// Time in milliseconds between the insertion of the transaction into the mempool and its acceptance
+    pub latency: u64,
+}
+
+impl FeerateSample {
+    pub fn new(feerate: f64, latency: u64) -> Self {
+        Self { feerate, latency }
+    }
+}
+
+/// The samples of the transactions of an accepted block
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct BlockFeerateRecord {
+    /// Unix time in milliseconds at which the block transactions were accepted by the mempool
+    pub timestamp: u64,
+    pub daa_score: u64,
+    pub samples: Vec<FeerateSample>,
+}
+
+impl BlockFeerateRecord {
+    pub fn new(timestamp: u64, daa_score: u64, samples: Vec<FeerateSample>) -> Self {
+        Self { timestamp, daa_score, samples }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct HistoricalFeerateBucket {
+    /// The lowest feerate at which at least `confidence` of the observed transactions were included within `target_seconds`
+    pub feerate: f64,
+    pub target_seconds: f64,
+    pub confidence: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct HistoricalFeeEstimate {
+    /// Duration in seconds covered by the records the estimation is based on
+    pub window_seconds: u64,
+    pub block_count: u64,
+    pub sample_count: u64,
+    /// Buckets ordered by target time then by confidence, a (target, confidence) pair being omitted if no feerate
+    /// observed in the window meets it
+    pub buckets: Vec<HistoricalFeerateBucket>,
+}
+
+/// A rolling store of [`BlockFeerateRecord`]s
+pub struct FeerateHistory {
+    records: VecDeque<BlockFeerateRecord>,
+    sample_count: usize,
+    retention: u64,
+}
+
+impl FeerateHistory {
+    pub fn new(retention: u64) -> Self {
+        Self { records: VecDeque::new(), sample_count: 0, retention }
+    }
+
+    /// Adds `record` and drops the records which fell out of the retention period as of its timestamp
+    pub fn add(&mut self, record: BlockFeerateRecord) {
+        if record.samples.is_empty() {
+            return;
+        }
+        let now = record.timestamp;
+        self.sample_count += record.samples.len();
+        self.records.push_back(record);
+        self.prune(now);
+    }
+
+    fn prune(&mut self, now: u64) {
+        while let Some(oldest) = self.records.front() {
+            if oldest.timestamp + self.retention >= now && self.sample_count <= MAX_SAMPLES {
+                break;
+            }
+            self.sample_count -= oldest.samples.len();
+            self.records.pop_front();
+        }
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &BlockFeerateRecord> {
+        self.records.iter()
+    }
+
+    /// Replaces the content of the store with `records`, keeping only those still within the retention period at `now`
+    pub fn restore(&mut self, mut records: Vec<BlockFeerateRecord>, now: u64) {
+        records.sort_by_key(|record| record.timestamp);
+        self.records = records.into_iter().filter(|record| !record.samples.is_empty()).collect();
+        self.sample_count = self.records.iter().map(|record| record.samples.len()).sum();
+        self.prune(now);
+    }
+
+    /// Estimates from the records of the last `window` milliseconds preceding `now` the lowest feerates at which
+    /// transactions got included within each of [`TARGET_SECONDS`] for each of the [`CONFIDENCE_LEVELS`].
+    ///
+    /// A `window` of 0 covers the whole store.
+    pub fn estimate(&self, window: u64, now: u64) -> HistoricalFeeEstimate {
+        let since = if window == 0 { 0 } else { now.saturating_sub(window) };
+        let records = self.records.iter().filter(|record| record.timestamp >= since).collect::<Vec<_>>();
+        let mut samples = records.iter().flat_map(|record| record.samples.iter().copied()).collect::<Vec<_>>();
+        // Highest feerates first, so that each prefix holds the samples at or above a feerate
+        samples.sort_unstable_by(|a, b| b.feerate.total_cmp(&a.feerate));
+
+        let mut buckets = Vec::with_capacity(TARGET_SECONDS.len() * CONFIDENCE_LEVELS.len());
+        for target_seconds in TARGET_SECONDS {
+            let target = target_seconds * 1000;
+            for confidence in CONFIDENCE_LEVELS {
+                let mut within_target = 0;
+                let mut feerate = None;
+                for (i, sample) in samples.iter().enumerate() {
+                    if sample.latency <= target {
+                        within_target += 1;
+                    }
+                    let count = i + 1;
+                    // Samples of equal feerate are judged together
+                    let is_last_of_feerate = !samples.get(count).is_some_and(|next| next.feerate == sample.feerate);
+                    if count >= MIN_SAMPLES && is_last_of_feerate && within_target as f64 >= confidence * count as f64 {
+                        feerate = Some(sample.feerate);
+                    }
+                }
+                if let Some(feerate) = feerate {
+                    buckets.push(HistoricalFeerateBucket { feerate, target_seconds: target_seconds as f64, confidence });
+                }
+            }
+        }
+
+        let window_seconds = match (records.first(), records.last()) {
+            (Some(first), Some(last)) if window == 0 => (last.timestamp - first.timestamp) / 1000,
+            (Some(_), Some(_)) => window / 1000,
+            _ => 0,
+        };
+        HistoricalFeeEstimate { window_seconds, block_count: records.len() as u64, sample_count: samples.len() as u64, buckets }
+    }
+}
+
+impl Default for FeerateHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_RETENTION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feerate_history_retention() {
+        let mut history = FeerateHistory::new(10_000);
+        history.add(BlockFeerateRecord::new(1_000, 1, vec![FeerateSample::new(1.0, 100)]));
+        history.add(BlockFeerateRecord::new(2_000, 2, vec![]));
+        history.add(BlockFeerateRecord::new(5_000, 3, vec![FeerateSample::new(2.0, 100); 2]));
+        assert_eq!(history.records().count(), 2);
+        history.add(BlockFeerateRecord::new(12_000, 4, vec![FeerateSample::new(3.0, 100)]));
+        assert_eq!(history.records().map(|record| record.daa_score).collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(history.sample_count, 3);
+
+        let records = history.records().cloned().collect();
+        let mut restored = FeerateHistory::new(10_000);
+        restored.restore(records, 16_000);
+        assert_eq!(restored.records().map(|record| record.daa_score).collect::<Vec<_>>(), vec![4]);
+        assert_eq!(restored.sample_count, 1);
+    }
+
+    #[test]
+    fn test_feerate_history_estimate() {
+        let now = 100_000_000;
+        let mut history = FeerateHistory::default();
+        // Very low feerate transactions included instantly, out of the estimation window
+        history.add(BlockFeerateRecord::new(now - 20_000, 1, vec![FeerateSample::new(0.5, 0); 40]));
+        // High feerates get included within a second, lower ones within a minute, and the lowest ones within an hour
+        let samples = (0..20)
+            .map(|_| FeerateSample::new(10.0, 500))
+            .chain((0..20).map(|_| FeerateSample::new(2.0, 30_000)))
+            .chain((0..20).map(|_| FeerateSample::new(1.0, 1_800_000)))
+            .collect();
+        history.add(BlockFeerateRecord::new(now - 1_000, 2, samples));
+
+        let estimate = history.estimate(10_000, now);
+        assert_eq!((estimate.window_seconds, estimate.block_count, estimate.sample_count), (10, 1, 60));
+        let bucket = |target_seconds: f64, confidence: f64| {
+            estimate.buckets.iter().find(|b| b.target_seconds == target_seconds && b.confidence == confidence).map(|b| b.feerate)
+        };
+        assert_eq!(bucket(1.0, 0.5), Some(2.0));
+        assert_eq!(bucket(1.0, 0.8), Some(10.0));
+        assert_eq!(bucket(60.0, 0.95), Some(2.0));
+        assert_eq!(bucket(3600.0, 0.95), Some(1.0));
+
+        // The whole store, where the instant inclusion of the very low feerate transactions drags some estimations down
+        let estimate = history.estimate(0, now);
+        assert_eq!((estimate.window_seconds, estimate.block_count, estimate.sample_count), (19, 2, 100));
+        let bucket = |target_seconds: f64, confidence: f64| {
+            estimate.buckets.iter().find(|b| b.target_seconds == target_seconds && b.confidence == confidence).map(|b| b.feerate)
+        };
+        assert_eq!(bucket(1.0, 0.5), Some(0.5));
+        assert_eq!(bucket(1.0, 0.95), Some(10.0));
+        assert_eq!(bucket(60.0, 0.95), Some(2.0));
+        assert_eq!(bucket(3600.0, 0.95), Some(0.5));
+
+        assert!(FeerateHistory::default().estimate(0, now).buckets.is_empty());
+    }
+}
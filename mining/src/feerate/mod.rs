@@ -5,6 +5,8 @@ use crate::block_template::selector::ALPHA;
 use itertools::Itertools;
 use std::fmt::Display;
 
+pub mod history;
+
 /// A type representing fee/mass of a transaction in `sompi/gram` units.
 /// Given a feerate value recommendation, calculate the required fee by
 /// taking the transaction mass and multiplying it by feerate: `fee = feerate * mass(tx)`
@@ -77,7 +79,8 @@ impl FeerateEstimatorArgs {
 
 #[derive(Debug, Clone)]
 pub struct FeerateEstimator {
-    /// The total probability weight of current mempool ready transactions, i.e., `Σ_{tx in mempool}(tx.fee/tx.mass)^alpha`.
+    /// The total probability weight of current mempool ready transactions, i.e., `Σ_{tx in mempool}(tx.package_fee/tx.package_mass)^alpha`,
+    /// where the package of a ready transaction accounts for the descendants paying for it (see [`FeerateTransactionKey::package_fee`]).
     /// Note that some estimators might consider a reduced weight which excludes outliers. See [`Frontier::build_feerate_estimator`]
     total_weight: f64,
 
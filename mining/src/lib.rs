@@ -10,10 +10,14 @@ pub(crate) mod cache;
 pub mod errors;
 pub mod feerate;
 pub mod manager;
+mod manager_proptests;
 mod manager_tests;
 pub mod mempool;
 pub mod model;
 pub mod monitor;
+pub mod persistence;
+
+pub use block_template::policy::{TemplatePolicy, TemplateSelectionMode};
 
 // Exposed for benchmarks
 pub use block_template::{policy::Policy, selector::RebalancingWeightedTransactionSelector};
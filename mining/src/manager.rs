@@ -2,10 +2,16 @@ use crate::{
     block_template::{builder::BlockTemplateBuilder, errors::BuilderError},
     cache::BlockTemplateCache,
     errors::MiningManagerResult,
-    feerate::{FeeEstimateVerbose, FeerateEstimations, FeerateEstimatorArgs},
+    feerate::{
+        history::{BlockFeerateRecord, HistoricalFeeEstimate},
+        FeeEstimateVerbose, FeerateEstimations, FeerateEstimatorArgs,
+    },
     mempool::{
         config::Config,
-        model::tx::{MempoolTransaction, TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
+        model::tx::{
+            MempoolTransaction, TransactionPostValidation, TransactionPostValidationTest, TransactionPreValidation, TxRemovalReason,
+        },
+        policy::AdmissionPolicy,
         populate_entries_and_try_validate::{
             populate_mempool_transactions_in_parallel, validate_mempool_transaction, validate_mempool_transactions_in_parallel,
         },
@@ -13,29 +19,37 @@ use crate::{
         Mempool,
     },
     model::{
+        entries_query::{EntriesPage, EntriesQuery},
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
+        persisted_tx::PersistedTransaction,
         topological_sort::IntoIterTopologically,
+        tx_acceptance::TransactionAcceptance,
         tx_insert::TransactionInsertion,
         tx_query::TransactionQuery,
     },
-    MempoolCountersSnapshot, MiningCounters, P2pTxCountSample,
+    MempoolCountersSnapshot, MiningCounters, P2pTxCountSample, TemplatePolicy,
 };
 use itertools::Itertools;
 use parking_lot::RwLock;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use tokio::sync::mpsc::UnboundedSender;
 use vecno_consensus_core::{
     api::{
         args::{TransactionValidationArgs, TransactionValidationBatchArgs},
         ConsensusApi,
     },
-    block::{BlockTemplate, TemplateBuildMode, TemplateTransactionSelector},
+    block::{BlockTemplate, TemplateBuildMode},
     coinbase::MinerData,
     errors::{block::RuleError as BlockRuleError, tx::TxRuleError},
     tx::{MutableTransaction, Transaction, TransactionId, TransactionOutput},
 };
+use vecno_consensus_notify::root::ConsensusNotificationRoot;
 use vecno_consensusmanager::{spawn_blocking, ConsensusProxy};
-use vecno_core::{debug, error, info, time::Stopwatch, warn};
+use vecno_core::{
+    debug, error, info,
+    time::{unix_now, Stopwatch},
+    warn,
+};
 use vecno_mining_errors::{manager::MiningManagerError, mempool::RuleError};
 
 pub struct MiningManager {
@@ -43,6 +57,8 @@ pub struct MiningManager {
     block_template_cache: BlockTemplateCache,
     mempool: RwLock<Mempool>,
     counters: Arc<MiningCounters>,
+    /// Policy used to select the transactions of block templates unless a request provides its own
+    template_policy: TemplatePolicy,
 }
 
 impl MiningManager {
@@ -74,7 +90,25 @@ impl MiningManager {
         let config = Arc::new(config);
         let mempool = RwLock::new(Mempool::new(config.clone(), counters.clone()));
         let block_template_cache = BlockTemplateCache::new(cache_lifetime);
-        Self { config, block_template_cache, mempool, counters }
+        Self { config, block_template_cache, mempool, counters, template_policy: Default::default() }
+    }
+
+    /// Consults `admission_policy` when admitting transactions into the mempool, on top of the standardness rules
+    pub fn with_admission_policy(mut self, admission_policy: Arc<dyn AdmissionPolicy>) -> Self {
+        self.mempool.get_mut().set_admission_policy(admission_policy);
+        self
+    }
+
+    /// Sends the notifications of transactions removed from the mempool without being accepted to `notification_root`
+    pub fn with_notification_root(mut self, notification_root: Arc<ConsensusNotificationRoot>) -> Self {
+        self.mempool.get_mut().set_notification_root(notification_root);
+        self
+    }
+
+    /// Selects the transactions of block templates according to `template_policy` unless a request provides its own
+    pub fn with_template_policy(mut self, template_policy: TemplatePolicy) -> Self {
+        self.template_policy = template_policy;
+        self
     }
 
     pub fn get_block_template(&self, consensus: &dyn ConsensusApi, miner_data: &MinerData) -> MiningManagerResult<BlockTemplate> {
@@ -97,6 +131,30 @@ impl MiningManager {
             return Ok(block_template);
         }
 
+        let block_template = self.build_block_template(consensus, miner_data, &self.template_policy)?;
+        Ok(cache_lock.set_immutable_cached_template(block_template).as_ref().clone())
+    }
+
+    /// Builds a block template whose transactions are selected according to `template_policy` rather than to the
+    /// node policy. Such templates are neither read from nor stored in the block template cache.
+    pub fn get_block_template_with_policy(
+        &self,
+        consensus: &dyn ConsensusApi,
+        miner_data: &MinerData,
+        template_policy: &TemplatePolicy,
+    ) -> MiningManagerResult<BlockTemplate> {
+        if !template_policy.is_valid() {
+            return Err(MiningManagerError::InvalidTemplatePolicy);
+        }
+        self.build_block_template(consensus, miner_data, template_policy)
+    }
+
+    fn build_block_template(
+        &self,
+        consensus: &dyn ConsensusApi,
+        miner_data: &MinerData,
+        template_policy: &TemplatePolicy,
+    ) -> MiningManagerResult<BlockTemplate> {
         // Rust rewrite:
         // We avoid passing a mempool ref to blockTemplateBuilder by calling
         // mempool.BlockCandidateTransactions and mempool.RemoveTransactions here.
@@ -107,7 +165,7 @@ impl MiningManager {
         loop {
             attempts += 1;
 
-            let selector = self.build_selector();
+            let selector = self.mempool.read().build_selector(template_policy);
             let block_template_builder = BlockTemplateBuilder::new();
             let build_mode = if attempts < self.config.maximum_build_block_template_attempts {
                 TemplateBuildMode::Standard
@@ -116,7 +174,6 @@ impl MiningManager {
             };
             match block_template_builder.build_block_template(consensus, miner_data, selector, build_mode) {
                 Ok(block_template) => {
-                    let block_template = cache_lock.set_immutable_cached_template(block_template);
                     match attempts {
                         1 => {
                             debug!(
@@ -141,7 +198,7 @@ impl MiningManager {
                             );
                         }
                     }
-                    return Ok(block_template.as_ref().clone());
+                    return Ok(block_template);
                 }
                 Err(BuilderError::ConsensusError(BlockRuleError::InvalidTransactionsInNewBlock(invalid_transactions))) => {
                     let mut missing_outpoint: usize = 0;
@@ -198,8 +255,19 @@ impl MiningManager {
     }
 
     /// Dynamically builds a transaction selector based on the specific state of the ready transactions frontier
-    pub(crate) fn build_selector(&self) -> Box<dyn TemplateTransactionSelector> {
-        self.mempool.read().build_selector()
+    #[cfg(test)]
+    pub(crate) fn build_selector(&self) -> Box<dyn vecno_consensus_core::block::TemplateTransactionSelector> {
+        self.mempool.read().build_selector(&self.template_policy)
+    }
+
+    /// Adds `fee_delta`, which may be negative, to the fee `transaction_id` is ranked by when selecting the transactions
+    /// of block templates, and returns the resulting overall delta. The delta is kept until the transaction gets
+    /// accepted, even if it is not in the mempool yet.
+    pub fn prioritise_transaction(&self, transaction_id: TransactionId, fee_delta: i64) -> i64 {
+        let fee_delta = self.mempool.write().prioritise_transaction(transaction_id, fee_delta);
+        // Cached templates were built with the previous delta
+        self.block_template_cache.clear();
+        fee_delta
     }
 
     /// Returns realtime feerate estimations based on internal mempool state
@@ -209,6 +277,12 @@ impl MiningManager {
         estimator.calc_estimations(self.config.minimum_feerate())
     }
 
+    /// Returns feerate estimations based on the feerates and waiting times of the mempool transactions accepted
+    /// during the last `window_seconds`, or during the whole recorded history if `window_seconds` is 0
+    pub(crate) fn get_historical_fee_estimate(&self, window_seconds: u64) -> HistoricalFeeEstimate {
+        self.mempool.read().build_historical_fee_estimate(window_seconds.saturating_mul(1000), unix_now())
+    }
+
     /// Returns realtime feerate estimations based on internal mempool state with additional verbose data
     pub(crate) fn get_realtime_feerate_estimations_verbose(
         &self,
@@ -325,6 +399,65 @@ impl MiningManager {
         }
     }
 
+    /// Tests whether each of `transactions` would be accepted into the mempool by [`Self::validate_and_insert_transaction`],
+    /// running the same consensus, standardness, admission policy, RBF and orphan validations but without inserting anything
+    /// into the mempool.
+    ///
+    /// Each transaction is tested independently against the current mempool, so a transaction spending an output of another
+    /// one of `transactions` is reported as an orphan.
+    pub fn test_mempool_accept(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<Transaction>,
+        orphan: Orphan,
+        rbf_policy: RbfPolicy,
+    ) -> Vec<TransactionAcceptance> {
+        transactions
+            .into_iter()
+            .map(|transaction| {
+                self.test_mutable_transaction_acceptance(consensus, MutableTransaction::from_tx(transaction), orphan, rbf_policy)
+            })
+            .collect()
+    }
+
+    fn test_mutable_transaction_acceptance(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transaction: MutableTransaction,
+        orphan: Orphan,
+        rbf_policy: RbfPolicy,
+    ) -> TransactionAcceptance {
+        let mut acceptance =
+            TransactionAcceptance::new(transaction.id(), consensus.calculate_transaction_compute_mass(&transaction.tx));
+
+        // read lock on mempool
+        let pre_validation = self.mempool.read().pre_validate_and_populate_transaction(consensus, transaction, rbf_policy);
+        let TransactionPreValidation { mut transaction, feerate_threshold } = match pre_validation {
+            Ok(pre_validation) => pre_validation,
+            Err(err) => {
+                acceptance.error = Some(err);
+                return acceptance;
+            }
+        };
+        let args = TransactionValidationArgs::new(feerate_threshold);
+        // no lock on mempool
+        let validation_result = validate_mempool_transaction(consensus, &mut transaction, &args);
+        if transaction.is_fully_populated() {
+            acceptance.storage_mass = consensus.calculate_transaction_storage_mass(&transaction);
+            acceptance.fee = transaction.calculated_fee;
+            acceptance.feerate = transaction.calculated_feerate();
+        }
+        // read lock on mempool
+        match self.mempool.read().post_validate_transaction(validation_result, &transaction, orphan, rbf_policy) {
+            Ok(TransactionPostValidationTest { is_orphan, replaced }) => {
+                acceptance.is_orphan = is_orphan;
+                acceptance.replaced_transaction_id = replaced;
+            }
+            Err(err) => acceptance.error = Some(err),
+        }
+        acceptance
+    }
+
     fn validate_and_insert_unorphaned_transactions(
         &self,
         consensus: &dyn ConsensusApi,
@@ -508,6 +641,54 @@ impl MiningManager {
         insert_results
     }
 
+    /// Validates an ordered package of transactions and adds either all of them or none to the set of
+    /// known transactions that have not yet been added to any block.
+    ///
+    /// The package is judged by its aggregate fee so that a child can pay for a parent falling below the minimum
+    /// relay fee. Package transactions already known to the mempool or accepted by consensus are skipped. Orphans
+    /// and replace by fee are forbidden.
+    ///
+    /// On rejection of some package transactions, the error details the rule broken by each of them.
+    ///
+    /// On success, returns the newly accepted package transactions followed by the transactions that were
+    /// unorphaned following their insertion. The returned transactions are references of objects owned by the mempool.
+    pub fn validate_and_insert_transaction_package(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<Transaction>,
+        priority: Priority,
+    ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
+        let transactions = transactions.into_iter().map(MutableTransaction::from_tx).collect_vec();
+
+        // read lock on mempool
+        let mut transactions = self.mempool.read().pre_validate_and_populate_package(consensus, transactions)?;
+
+        // no lock on mempool
+        let args = TransactionValidationBatchArgs::new();
+        let validation_results = validate_mempool_transactions_in_parallel(consensus, &mut transactions, &args);
+        let rejections = transactions
+            .iter()
+            .zip(validation_results)
+            .filter_map(|(transaction, validation_result)| validation_result.err().map(|err| (transaction.id(), err)))
+            .collect_vec();
+        if !rejections.is_empty() {
+            return Err(MiningManagerError::PackageRejected(rejections));
+        }
+
+        // write lock on mempool
+        let mut mempool = self.mempool.write();
+        let mut accepted_transactions = mempool.post_validate_and_insert_package(consensus, transactions, priority)?;
+        let unorphaned_transactions = accepted_transactions
+            .iter()
+            .flat_map(|transaction| mempool.get_unorphaned_transactions_after_accepted_transaction(transaction))
+            .collect_vec();
+        drop(mempool);
+
+        self.counters.increase_tx_counts(accepted_transactions.len() as u64, priority);
+        accepted_transactions.extend(self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions));
+        Ok(accepted_transactions)
+    }
+
     fn next_transaction_chunk_upper_bound(&self, transactions: &[MutableTransaction], lower_bound: usize) -> Option<usize> {
         if lower_bound >= transactions.len() {
             return None;
@@ -561,6 +742,26 @@ impl MiningManager {
         (transactions, orphans)
     }
 
+    /// Returns the mempool transactions whose id is matched by `filter`, without cloning the other ones.
+    ///
+    /// Note: orphans are included if the query asks for them.
+    pub fn get_matching_transactions(
+        &self,
+        query: TransactionQuery,
+        filter: impl Fn(&TransactionId) -> bool,
+    ) -> Vec<Arc<Transaction>> {
+        // read lock on mempool
+        self.mempool.read().get_matching_transactions(query, filter)
+    }
+
+    /// Returns a page of the mempool entries matching `entries_query`, along with totals over all the matching entries.
+    ///
+    /// Filtering and sorting are done under a single read lock on the mempool, so only the returned page gets cloned.
+    pub fn get_mempool_entries(&self, query: TransactionQuery, entries_query: &EntriesQuery) -> EntriesPage {
+        // read lock on mempool
+        self.mempool.read().get_entries_page(query, entries_query)
+    }
+
     /// get_transactions_by_addresses returns the sending and receiving transactions for
     /// a set of addresses.
     ///
@@ -614,7 +815,7 @@ impl MiningManager {
         for chunk in &expired_low_priority_transactions.iter().chunks(24) {
             let mut mempool = self.mempool.write();
             chunk.into_iter().for_each(|tx| {
-                if let Err(err) = mempool.remove_transaction(tx, true, TxRemovalReason::Muted, "") {
+                if let Err(err) = mempool.remove_transaction(tx, true, TxRemovalReason::Expired, "") {
                     warn!("Failed to remove transaction {} from mempool: {}", tx, err);
                 }
             });
@@ -832,10 +1033,94 @@ impl MiningManager {
         self.mempool.read().unknown_transactions(transactions)
     }
 
+    /// Returns all the transactions of the mempool, orphans included, along with the context of their
+    /// insertion, so that they can be restored with [`Self::restore_transactions`] after a node restart.
+    pub fn get_persisted_transactions(&self) -> Vec<PersistedTransaction> {
+        self.mempool.read().get_persisted_transactions()
+    }
+
+    /// Re-validates and re-inserts transactions persisted before a node restart, dropping those which
+    /// expired or became invalid in the meantime.
+    ///
+    /// Returns the number of restored transactions.
+    pub fn restore_transactions(&self, consensus: &dyn ConsensusApi, transactions: Vec<PersistedTransaction>) -> usize {
+        let virtual_daa_score = consensus.get_virtual_daa_score();
+        let (transactions, expired): (Vec<_>, Vec<_>) =
+            transactions.into_iter().partition(|transaction| !transaction.is_expired(&self.config, virtual_daa_score));
+        for transaction in expired.iter() {
+            debug!("Dropping persisted transaction {} since it expired", transaction.transaction.id());
+        }
+        let added_at_daa_scores: HashMap<TransactionId, u64> =
+            transactions.iter().map(|transaction| (transaction.transaction.id(), transaction.added_at_daa_score)).collect();
+
+        // Transactions are inserted by groups sharing the same insertion arguments, so a transaction may get inserted
+        // before its parents, in which case it lands in the orphan pool until they get inserted too.
+        // A mandatory RBF already took place before the restart, so it is relaxed into an optional one.
+        for priority in [Priority::High, Priority::Low] {
+            for rbf_policy in [RbfPolicy::Forbidden, RbfPolicy::Allowed] {
+                let group = transactions
+                    .iter()
+                    .filter(|transaction| transaction.priority == priority && transaction.rbf_policy.for_insert() == rbf_policy)
+                    .map(|transaction| transaction.transaction.clone())
+                    .collect_vec();
+                if group.is_empty() {
+                    continue;
+                }
+                let results = self.validate_and_insert_transaction_batch(consensus, group, priority, Orphan::Allowed, rbf_policy);
+                for err in results.into_iter().filter_map(Result::err) {
+                    debug!("Dropping persisted transaction: {}", err);
+                }
+            }
+        }
+        // Restored orphans are not part of the batch results, so the restored transactions are counted in the mempool
+        let restored = self.mempool.write().restore_added_at_daa_scores(&added_at_daa_scores);
+        let invalid = transactions.len() - restored;
+
+        if !expired.is_empty() || invalid > 0 {
+            info!("Dropped {} expired and {} invalid persisted transactions", expired.len(), invalid);
+        }
+        restored
+    }
+
+    /// Returns the records of the feerate history, so that they can be restored with [`Self::restore_feerate_history`]
+    /// after a node restart.
+    pub fn get_feerate_history_records(&self) -> Vec<BlockFeerateRecord> {
+        self.mempool.read().get_feerate_history_records()
+    }
+
+    /// Restores the records of the feerate history persisted before a node restart, dropping the outdated ones
+    pub fn restore_feerate_history(&self, records: Vec<BlockFeerateRecord>) {
+        self.mempool.write().restore_feerate_history(records, unix_now());
+    }
+
+    /// Checks the internal consistency of the mempool, returning a description of the first violated invariant if any.
+    ///
+    /// The check traverses the whole mempool while holding its lock so it is meant for tests and debugging.
+    pub fn check_invariants(&self) -> Result<(), String> {
+        self.mempool.read().check_invariants()
+    }
+
     #[cfg(test)]
     pub(crate) fn get_estimated_size(&self) -> usize {
         self.mempool.read().get_estimated_size()
     }
+
+    /// Inserts package transactions already populated and validated, skipping the checks of the package structure
+    #[cfg(test)]
+    pub(crate) fn post_validate_and_insert_package(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<MutableTransaction>,
+        priority: Priority,
+    ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
+        self.mempool.write().post_validate_and_insert_package(consensus, transactions, priority)
+    }
+
+    /// Returns the feerate a ready transaction is ranked by in the frontier, accounting for the descendants paying for it
+    #[cfg(test)]
+    pub(crate) fn get_ready_transaction_feerate(&self, transaction_id: &TransactionId) -> Option<f64> {
+        self.mempool.read().get_ready_transaction_feerate(transaction_id)
+    }
 }
 
 /// Async proxy for the mining manager
@@ -853,11 +1138,31 @@ impl MiningManagerProxy {
         consensus.clone().spawn_blocking(move |c| self.inner.get_block_template(c, &miner_data)).await
     }
 
+    /// Builds a block template selecting transactions according to `template_policy`, bypassing the template cache
+    pub async fn get_block_template_with_policy(
+        self,
+        consensus: &ConsensusProxy,
+        miner_data: MinerData,
+        template_policy: TemplatePolicy,
+    ) -> MiningManagerResult<BlockTemplate> {
+        consensus.clone().spawn_blocking(move |c| self.inner.get_block_template_with_policy(c, &miner_data, &template_policy)).await
+    }
+
+    /// Adds `fee_delta` to the fee `transaction_id` is ranked by for block templates, returning the resulting delta
+    pub async fn prioritise_transaction(self, transaction_id: TransactionId, fee_delta: i64) -> i64 {
+        spawn_blocking(move || self.inner.prioritise_transaction(transaction_id, fee_delta)).await.unwrap()
+    }
+
     /// Returns realtime feerate estimations based on internal mempool state
     pub async fn get_realtime_feerate_estimations(self) -> FeerateEstimations {
         spawn_blocking(move || self.inner.get_realtime_feerate_estimations()).await.unwrap()
     }
 
+    /// Returns feerate estimations based on the mempool transactions accepted during the last `window_seconds`
+    pub async fn get_historical_fee_estimate(self, window_seconds: u64) -> HistoricalFeeEstimate {
+        spawn_blocking(move || self.inner.get_historical_fee_estimate(window_seconds)).await.unwrap()
+    }
+
     /// Returns realtime feerate estimations based on internal mempool state with additional verbose data
     pub async fn get_realtime_feerate_estimations_verbose(
         self,
@@ -910,6 +1215,32 @@ impl MiningManagerProxy {
             .await
     }
 
+    /// Tests whether each of the transactions would be accepted into the mempool, without inserting nor relaying any.
+    ///
+    /// See [`MiningManager::test_mempool_accept`].
+    pub async fn test_mempool_accept(
+        self,
+        consensus: &ConsensusProxy,
+        transactions: Vec<Transaction>,
+        orphan: Orphan,
+        rbf_policy: RbfPolicy,
+    ) -> Vec<TransactionAcceptance> {
+        consensus.clone().spawn_blocking(move |c| self.inner.test_mempool_accept(c, transactions, orphan, rbf_policy)).await
+    }
+
+    /// Validates an ordered package of transactions and adds either all of them or none to the set of
+    /// known transactions that have not yet been added to any block.
+    ///
+    /// See [`MiningManager::validate_and_insert_transaction_package`].
+    pub async fn validate_and_insert_transaction_package(
+        self,
+        consensus: &ConsensusProxy,
+        transactions: Vec<Transaction>,
+        priority: Priority,
+    ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
+        consensus.clone().spawn_blocking(move |c| self.inner.validate_and_insert_transaction_package(c, transactions, priority)).await
+    }
+
     pub async fn handle_new_block_transactions(
         self,
         consensus: &ConsensusProxy,
@@ -954,6 +1285,19 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.get_all_transactions(query)).await.unwrap()
     }
 
+    /// Returns the mempool transactions whose id is matched by `filter`, without cloning the other ones.
+    pub async fn get_matching_transactions<F>(self, query: TransactionQuery, filter: F) -> Vec<Arc<Transaction>>
+    where
+        F: Fn(&TransactionId) -> bool + Send + 'static,
+    {
+        spawn_blocking(move || self.inner.get_matching_transactions(query, filter)).await.unwrap()
+    }
+
+    /// Returns a page of the mempool entries matching `entries_query`, along with totals over all the matching entries.
+    pub async fn get_mempool_entries(self, query: TransactionQuery, entries_query: EntriesQuery) -> EntriesPage {
+        spawn_blocking(move || self.inner.get_mempool_entries(query, &entries_query)).await.unwrap()
+    }
+
     /// get_transactions_by_addresses returns the sending and receiving transactions for
     /// a set of addresses.
     ///
@@ -990,6 +1334,25 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.unknown_transactions(transactions)).await.unwrap()
     }
 
+    /// Returns all the transactions of the mempool, orphans included, along with the context of their insertion.
+    pub async fn get_persisted_transactions(self) -> Vec<PersistedTransaction> {
+        spawn_blocking(move || self.inner.get_persisted_transactions()).await.unwrap()
+    }
+
+    /// Re-validates and re-inserts transactions persisted before a node restart, returning the number of restored transactions.
+    pub async fn restore_transactions(self, consensus: &ConsensusProxy, transactions: Vec<PersistedTransaction>) -> usize {
+        consensus.clone().spawn_blocking(move |c| self.inner.restore_transactions(c, transactions)).await
+    }
+
+    pub async fn get_feerate_history_records(self) -> Vec<BlockFeerateRecord> {
+        spawn_blocking(move || self.inner.get_feerate_history_records()).await.unwrap()
+    }
+
+    /// Restores the records of the feerate history persisted before a node restart
+    pub async fn restore_feerate_history(self, records: Vec<BlockFeerateRecord>) {
+        spawn_blocking(move || self.inner.restore_feerate_history(records)).await.unwrap()
+    }
+
     pub fn snapshot(&self) -> MempoolCountersSnapshot {
         self.inner.counters.snapshot()
     }
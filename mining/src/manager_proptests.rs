@@ -0,0 +1,286 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        manager::MiningManager,
+        mempool::{
+            config::Config,
+            tx::{Orphan, Priority, RbfPolicy},
+        },
+        testutils::consensus_mock::ConsensusMock,
+        MiningCounters, TemplatePolicy, TemplateSelectionMode,
+    };
+    use itertools::Itertools;
+    use proptest::prelude::*;
+    use std::sync::Arc;
+    use vecno_consensus_core::{
+        coinbase::MinerData,
+        constants::{MAX_TX_IN_SEQUENCE_NUM, SOMPI_PER_VECNO, TX_VERSION},
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{MutableTransaction, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput},
+    };
+    use vecno_txscript::{pay_to_script_hash_signature_script, test_helpers::op_true_script};
+
+    const TARGET_TIME_PER_BLOCK: u64 = 1_000;
+    const MAX_BLOCK_MASS: u64 = 500_000;
+    const FUNDING_TRANSACTION_COUNT: usize = 4;
+
+    /// A step of a mempool scenario. Indexes refer to the outputs and transactions built so far, modulo their count.
+    #[derive(Clone, Debug)]
+    enum Step {
+        /// Builds a transaction spending some known outputs and submits it, unless it is withheld
+        Insert { inputs: Vec<usize>, output_count: usize, fee: u64, priority: Priority, withheld: bool },
+        /// Submits a withheld transaction, unorphaning the submitted transactions spending its outputs
+        Release { index: usize },
+        /// Submits a transaction spending the same outputs as a built transaction, with another fee
+        Replace { index: usize, fee: u64, rbf_policy: RbfPolicy },
+        /// Changes the fee a built transaction is ranked by
+        Prioritise { index: usize, fee_delta: i64 },
+        /// Accepts a block made of at most `count` transactions of the block template, along with a transaction unknown
+        /// to the mempool double spending a built transaction if `conflict` is set
+        AcceptBlock { count: usize, conflict: Option<usize> },
+        /// Reverts the last accepted block and accepts a competing block double spending one of its transactions
+        Reorg,
+        /// Advances the virtual DAA score and expires the low priority transactions
+        Expire { daa_score_increase: u64 },
+    }
+
+    fn step() -> impl Strategy<Value = Step> {
+        let priority = prop_oneof![Just(Priority::Low), Just(Priority::High)];
+        let rbf_policy = prop_oneof![Just(RbfPolicy::Forbidden), Just(RbfPolicy::Allowed), Just(RbfPolicy::Mandatory)];
+        let insert =
+            (prop::collection::vec(any::<usize>(), 1..=2), 1..=2usize, 0..20_000u64, priority, prop::bool::weighted(0.2)).prop_map(
+                |(inputs, output_count, fee, priority, withheld)| Step::Insert { inputs, output_count, fee, priority, withheld },
+            );
+        let replace =
+            (any::<usize>(), 0..40_000u64, rbf_policy).prop_map(|(index, fee, rbf_policy)| Step::Replace { index, fee, rbf_policy });
+        let accept_block = (0..8usize, prop::option::weighted(0.3, any::<usize>()))
+            .prop_map(|(count, conflict)| Step::AcceptBlock { count, conflict });
+        prop_oneof![
+            8 => insert,
+            2 => any::<usize>().prop_map(|index| Step::Release { index }),
+            2 => replace,
+            1 => (any::<usize>(), -20_000..20_000i64).prop_map(|(index, fee_delta)| Step::Prioritise { index, fee_delta }),
+            3 => accept_block,
+            1 => Just(Step::Reorg),
+            1 => (1..6u64).prop_map(|daa_score_increase| Step::Expire { daa_score_increase }),
+        ]
+    }
+
+    struct BuiltTransaction {
+        transaction: Transaction,
+        inputs: Vec<(TransactionOutpoint, u64)>,
+        priority: Priority,
+    }
+
+    /// Drives a mining manager backed by a [`ConsensusMock`] through scenario steps
+    struct Harness {
+        consensus: Arc<ConsensusMock>,
+        mining_manager: MiningManager,
+        /// Outputs of the funding and built transactions along with their amount
+        outputs: Vec<(TransactionOutpoint, u64)>,
+        built: Vec<BuiltTransaction>,
+        /// Indexes of the built transactions not submitted yet
+        withheld: Vec<usize>,
+        /// Transactions of the accepted blocks which were not reverted yet, coinbase excluded
+        blocks: Vec<Vec<Transaction>>,
+        daa_score: u64,
+    }
+
+    impl Harness {
+        fn new() -> Self {
+            let consensus = Arc::new(ConsensusMock::new());
+            // Keep the pools small and the expiration intervals short so that evictions and expirations do occur
+            let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+            config.maximum_transaction_count = 16;
+            config.maximum_orphan_transaction_count = 4;
+            config.transaction_expire_interval_daa_score = 8;
+            config.transaction_expire_scan_interval_daa_score = 0;
+            config.transaction_expire_scan_interval_milliseconds = 0;
+            config.orphan_expire_interval_daa_score = 4;
+            config.orphan_expire_scan_interval_daa_score = 0;
+            config.accepted_transaction_expire_interval_daa_score = 16;
+            config.accepted_transaction_expire_scan_interval_daa_score = 0;
+            config.accepted_transaction_expire_scan_interval_milliseconds = 0;
+            let mining_manager = MiningManager::with_config(config, None, Arc::new(MiningCounters::default()));
+
+            let (script_public_key, _) = op_true_script();
+            let mut outputs = vec![];
+            for i in 0..FUNDING_TRANSACTION_COUNT {
+                // Make the funding amounts always different so that funding txs have different ids
+                let amounts = [1_000 * SOMPI_PER_VECNO + i as u64, 2_500 * SOMPI_PER_VECNO];
+                let funding_outputs =
+                    amounts.iter().map(|&amount| TransactionOutput::new(amount, script_public_key.clone())).collect();
+                let funding_tx = Transaction::new(TX_VERSION, vec![], funding_outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+                outputs.extend(
+                    amounts.iter().enumerate().map(|(j, &amount)| (TransactionOutpoint::new(funding_tx.id(), j as u32), amount)),
+                );
+                consensus.add_transaction(funding_tx, 1);
+            }
+            consensus.set_virtual_daa_score(1);
+
+            Self { consensus, mining_manager, outputs, built: vec![], withheld: vec![], blocks: vec![], daa_score: 1 }
+        }
+
+        /// Builds a transaction spending `inputs` into `output_count` outputs and paying at least `fee`, and registers its
+        /// outputs as known
+        fn build(&mut self, inputs: Vec<(TransactionOutpoint, u64)>, output_count: usize, fee: u64, priority: Priority) -> usize {
+            let (script_public_key, redeem_script) = op_true_script();
+            let signature_script = pay_to_script_hash_signature_script(redeem_script, vec![]).expect("the redeem script is canonical");
+            let input_amount = inputs.iter().map(|(_, amount)| amount).sum::<u64>();
+            let output_amount = input_amount.saturating_sub(fee) / output_count as u64;
+            let transaction = Transaction::new(
+                TX_VERSION,
+                inputs
+                    .iter()
+                    .map(|(outpoint, _)| TransactionInput::new(*outpoint, signature_script.clone(), MAX_TX_IN_SEQUENCE_NUM, 1))
+                    .collect(),
+                (0..output_count).map(|_| TransactionOutput::new(output_amount, script_public_key.clone())).collect(),
+                0,
+                SUBNETWORK_ID_NATIVE,
+                0,
+                vec![],
+            );
+            self.outputs.extend((0..output_count).map(|i| (TransactionOutpoint::new(transaction.id(), i as u32), output_amount)));
+            self.built.push(BuiltTransaction { transaction, inputs, priority });
+            self.built.len() - 1
+        }
+
+        fn submit(&self, index: usize, rbf_policy: RbfPolicy) {
+            let built = &self.built[index];
+            // Rejections are part of the scenarios, only the resulting state of the mempool matters
+            let _ = self.mining_manager.validate_and_insert_transaction(
+                self.consensus.as_ref(),
+                built.transaction.clone(),
+                built.priority,
+                Orphan::Allowed,
+                rbf_policy,
+            );
+        }
+
+        /// Builds a transaction spending the first input of a built transaction with a fee no other built transaction pays
+        fn build_conflict(&mut self, index: usize) -> Transaction {
+            let input = self.built[index].inputs[0];
+            let fee = 1_000_000 + self.built.len() as u64;
+            let conflict = self.build(vec![input], 1, fee, Priority::Low);
+            self.built[conflict].transaction.clone()
+        }
+
+        fn accept_block(&mut self, transactions: Vec<Transaction>) {
+            self.daa_score += 1;
+            for transaction in transactions.iter() {
+                self.consensus.add_transaction(transaction.clone(), self.daa_score);
+            }
+            self.consensus.set_virtual_daa_score(self.daa_score);
+            let coinbase = Transaction::new(TX_VERSION, vec![], vec![], 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+            let block_transactions = [coinbase].into_iter().chain(transactions.iter().cloned()).collect_vec();
+            let result =
+                self.mining_manager.handle_new_block_transactions(self.consensus.as_ref(), self.daa_score, &block_transactions);
+            assert!(result.is_ok(), "handling the transactions of a new block should succeed: {:?}", result.err());
+            self.blocks.push(transactions);
+        }
+
+        fn apply(&mut self, step: Step) {
+            match step {
+                Step::Insert { inputs, output_count, fee, priority, withheld } => {
+                    let inputs = inputs
+                        .into_iter()
+                        .map(|i| self.outputs[i % self.outputs.len()])
+                        .unique_by(|(outpoint, _)| *outpoint)
+                        .collect();
+                    let index = self.build(inputs, output_count, fee, priority);
+                    if withheld {
+                        self.withheld.push(index);
+                    } else {
+                        self.submit(index, RbfPolicy::Forbidden);
+                    }
+                }
+                Step::Release { index } => {
+                    if !self.withheld.is_empty() {
+                        let index = self.withheld.swap_remove(index % self.withheld.len());
+                        self.submit(index, RbfPolicy::Forbidden);
+                    }
+                }
+                Step::Replace { index, fee, rbf_policy } => {
+                    if !self.built.is_empty() {
+                        let target = &self.built[index % self.built.len()];
+                        let (inputs, output_count, priority) =
+                            (target.inputs.clone(), target.transaction.outputs.len(), target.priority);
+                        let replacement = self.build(inputs, output_count, fee, priority);
+                        self.submit(replacement, rbf_policy);
+                    }
+                }
+                Step::Prioritise { index, fee_delta } => {
+                    if !self.built.is_empty() {
+                        let transaction_id = self.built[index % self.built.len()].transaction.id();
+                        self.mining_manager.prioritise_transaction(transaction_id, fee_delta);
+                    }
+                }
+                Step::AcceptBlock { count, conflict } => {
+                    let (script_public_key, _) = op_true_script();
+                    let miner_data = MinerData::new(script_public_key, vec![]);
+                    let template_policy =
+                        TemplatePolicy { selection_mode: TemplateSelectionMode::HighestFeerate, ..Default::default() };
+                    let template = self
+                        .mining_manager
+                        .get_block_template_with_policy(self.consensus.as_ref(), &miner_data, &template_policy)
+                        .expect("the mempool should always be able to build a block template");
+                    // Transactions reorged out of their block may have been spent by mempool transactions which are no
+                    // longer valid, so only the transactions consensus can finance make it into the block
+                    let mut transactions = template.block.transactions[1..]
+                        .iter()
+                        .filter(|tx| self.consensus.can_finance_transaction(&MutableTransaction::from_tx((*tx).clone())))
+                        .take(count)
+                        .cloned()
+                        .collect_vec();
+                    if let Some(index) = conflict.filter(|_| !self.built.is_empty()) {
+                        let conflict = self.build_conflict(index % self.built.len());
+                        let spent_by_block = transactions
+                            .iter()
+                            .flat_map(|tx| tx.inputs.iter())
+                            .any(|input| input.previous_outpoint == conflict.inputs[0].previous_outpoint);
+                        if !spent_by_block && self.consensus.can_finance_transaction(&MutableTransaction::from_tx(conflict.clone())) {
+                            transactions.push(conflict);
+                        }
+                    }
+                    self.accept_block(transactions);
+                }
+                Step::Reorg => {
+                    if let Some(reverted) = self.blocks.pop() {
+                        // The mempool is not notified of the reverted transactions, as is the case of a node
+                        for transaction in reverted.iter().rev() {
+                            self.consensus.remove_transaction(&transaction.id());
+                        }
+                        let mut transactions = vec![];
+                        if let Some(index) =
+                            reverted.first().and_then(|tx| self.built.iter().position(|x| x.transaction.id() == tx.id()))
+                        {
+                            transactions.push(self.build_conflict(index));
+                        }
+                        self.accept_block(transactions);
+                    }
+                }
+                Step::Expire { daa_score_increase } => {
+                    self.daa_score += daa_score_increase;
+                    self.consensus.set_virtual_daa_score(self.daa_score);
+                    self.mining_manager.expire_low_priority_transactions(self.consensus.as_ref());
+                }
+            }
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        /// Drives the mempool through random scenarios of insertions, replacements, block acceptances, reorgs and
+        /// expirations, checking its invariants after every step
+        #[test]
+        fn test_mempool_invariants(steps in prop::collection::vec(step(), 1..64)) {
+            let mut harness = Harness::new();
+            for (i, step) in steps.into_iter().enumerate() {
+                let description = format!("{step:?}");
+                harness.apply(step);
+                prop_assert_eq!(harness.mining_manager.check_invariants(), Ok(()), "after step {}: {}", i, description);
+            }
+        }
+    }
+}
@@ -3,26 +3,33 @@ mod tests {
     use crate::{
         block_template::builder::BlockTemplateBuilder,
         errors::{MiningManagerError, MiningManagerResult},
+        feerate::history::{CONFIDENCE_LEVELS, TARGET_SECONDS},
         manager::MiningManager,
         mempool::{
             config::{Config, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
             errors::RuleError,
             model::frontier::selectors::TakeAllSelector,
+            policy::{AdmissionPolicyConfig, ConfigurableAdmissionPolicy},
             tx::{Orphan, Priority, RbfPolicy},
         },
-        model::{tx_insert::TransactionInsertion, tx_query::TransactionQuery},
+        model::{
+            entries_query::{EntriesPage, EntriesQuery, EntriesSortKey},
+            persisted_tx::PersistedTransaction,
+            tx_insert::TransactionInsertion,
+            tx_query::TransactionQuery,
+        },
         testutils::consensus_mock::ConsensusMock,
-        MiningCounters,
+        MiningCounters, TemplatePolicy, TemplateSelectionMode,
     };
     use itertools::Itertools;
-    use std::{iter::once, sync::Arc};
+    use std::{collections::HashSet, iter::once, sync::Arc};
     use tokio::sync::mpsc::{error::TryRecvError, unbounded_channel};
     use vecno_addresses::{Address, Prefix, Version};
     use vecno_consensus_core::{
         api::ConsensusApi,
         block::TemplateBuildMode,
         coinbase::MinerData,
-        constants::{MAX_TX_IN_SEQUENCE_NUM, SOMPI_PER_VECNO, TX_VERSION},
+        constants::{MAX_TX_IN_SEQUENCE_NUM, SOMPI_PER_VECNO, TX_VERSION, UNACCEPTED_DAA_SCORE},
         errors::tx::TxRuleError,
         mass::transaction_estimated_serialized_size,
         subnets::SUBNETWORK_ID_NATIVE,
@@ -31,8 +38,13 @@ mod tests {
             TransactionOutput, UtxoEntry,
         },
     };
+    use vecno_consensus_notify::{
+        notification::{MempoolRemovalReason, Notification},
+        root::ConsensusNotificationRoot,
+    };
     use vecno_hashes::Hash;
-    use vecno_mining_errors::mempool::RuleResult;
+    use vecno_mining_errors::mempool::{PolicyError, RuleResult};
+    use vecno_notify::{scope::MempoolTransactionRemovedScope, subscriber::SubscriptionManager};
     use vecno_txscript::{
         pay_to_address_script, pay_to_script_hash_signature_script,
         test_helpers::{create_transaction, create_transaction_with_change, op_true_script},
@@ -636,6 +648,95 @@ mod tests {
         );
     }
 
+    /// test_mempool_transaction_removed_notifications verifies that removals of transactions from the mempool are notified,
+    /// except when the transaction is accepted by consensus.
+    #[tokio::test]
+    async fn test_mempool_transaction_removed_notifications() {
+        let (notification_sender, notification_receiver) = async_channel::unbounded();
+        let notification_root = Arc::new(ConsensusNotificationRoot::new(notification_sender));
+        notification_root.start_notify(0, MempoolTransactionRemovedScope::default().into()).await.unwrap();
+
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager =
+            MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters).with_notification_root(notification_root);
+
+        let funding_txs = create_and_add_funding_transactions(&consensus, 2);
+        let fee = DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE;
+        let accepted_transaction = create_funded_transaction(select_transactions(&funding_txs, &[0]), vec![0], None, fee);
+        let double_spent_transaction = create_funded_transaction(select_transactions(&funding_txs, &[1]), vec![0], None, fee);
+        for transaction in [&accepted_transaction, &double_spent_transaction] {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                Priority::Low,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok());
+        }
+
+        // Spends the same outpoint as the double spent transaction with a different fee
+        let double_spend_transaction_in_the_block =
+            create_funded_transaction(select_transactions(&funding_txs, &[1]), vec![0], None, 2 * fee);
+        let block_transactions = build_block_transactions([&accepted_transaction, &double_spend_transaction_in_the_block].into_iter());
+        let result = mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &block_transactions);
+        assert!(result.is_ok());
+
+        // Only the double spent transaction is notified, the accepted one is not
+        match notification_receiver.try_recv() {
+            Ok(Notification::MempoolTransactionRemoved(notification)) => {
+                assert_eq!(double_spent_transaction.id(), notification.transaction_id);
+                assert_eq!(MempoolRemovalReason::DoubleSpend, notification.reason);
+                assert!(notification.script_public_keys.contains(&double_spent_transaction.outputs[0].script_public_key));
+            }
+            other => panic!("expected a mempool transaction removed notification, got {:?}", other),
+        }
+        assert!(notification_receiver.try_recv().is_err(), "no other removal should be notified");
+    }
+
+    /// test_admission_policy verifies that the mempool consults its admission policy when admitting a transaction.
+    #[test]
+    fn test_admission_policy() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let funding_txs = create_and_add_funding_transactions(&consensus, 1);
+        let transaction =
+            create_funded_transaction(select_transactions(&funding_txs, &[0]), vec![0], None, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+
+        let policy_configs = vec![
+            (
+                "blocked script public key",
+                AdmissionPolicyConfig { blocked_script_public_keys: vec![op_true_script().0], ..Default::default() },
+                Err(RuleError::RejectPolicy(transaction.id(), PolicyError::RejectBlockedOutput(0))),
+            ),
+            (
+                "high minimum feerate",
+                AdmissionPolicyConfig { minimum_feerate: Some(1_000.0), ..Default::default() },
+                Err(RuleError::RejectNonStandard(transaction.id(), String::new())),
+            ),
+            ("permissive", AdmissionPolicyConfig { max_payload_size: Some(0), ..Default::default() }, Ok(())),
+        ];
+
+        for (name, policy_config, expected) in policy_configs {
+            let counters = Arc::new(MiningCounters::default());
+            let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters)
+                .with_admission_policy(Arc::new(ConfigurableAdmissionPolicy::new(policy_config)));
+            let result = into_mempool_result(mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                Priority::Low,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            ));
+            match (result, expected) {
+                (Err(RuleError::RejectNonStandard(id, _)), Err(RuleError::RejectNonStandard(expected_id, _))) => {
+                    assert_eq!(expected_id, id, "{name}")
+                }
+                (result, expected) => assert_eq!(expected, result, "{name}"),
+            }
+        }
+    }
+
     /// test_orphan_transactions verifies that a transaction could be a part of a new block template only if it's not an orphan.
     #[test]
     fn test_orphan_transactions() {
@@ -1174,6 +1275,602 @@ mod tests {
         assert!(validate_and_insert_mutable_transaction(&mining_manager, consensus.as_ref(), too_big_tx.clone()).is_err());
     }
 
+    /// test_child_pays_for_parent verifies that a ready transaction is ranked by the feerate of the package
+    /// formed with its descendants when they pay a higher feerate than the transaction alone.
+    #[test]
+    fn test_child_pays_for_parent() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        let funding_tx = create_transaction_without_input(vec![500 * SOMPI_PER_VECNO]);
+        consensus.add_transaction(funding_tx.clone(), 1);
+        let parent_tx = create_transaction(&funding_tx, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let child_tx = create_transaction(&parent_tx, 100 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let grandchild_tx = create_transaction(&child_tx, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let fee_and_mass = |id: TransactionId| {
+            let tx = mining_manager.get_transaction(&id, TransactionQuery::TransactionsOnly).unwrap();
+            (tx.calculated_fee.unwrap(), tx.tx.mass())
+        };
+
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            once(&parent_tx),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        let (parent_fee, parent_mass) = fee_and_mass(parent_tx.id());
+        let parent_feerate = parent_fee as f64 / parent_mass as f64;
+        assert_eq!(mining_manager.get_ready_transaction_feerate(&parent_tx.id()), Some(parent_feerate));
+
+        // The child pays for its parent
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            once(&child_tx),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        let (child_fee, child_mass) = fee_and_mass(child_tx.id());
+        let package_feerate = (parent_fee + child_fee) as f64 / (parent_mass + child_mass) as f64;
+        assert!(package_feerate > parent_feerate);
+        assert_eq!(mining_manager.get_ready_transaction_feerate(&parent_tx.id()), Some(package_feerate));
+        assert_eq!(mining_manager.get_ready_transaction_feerate(&child_tx.id()), None, "the child is not ready");
+
+        // A low paying grandchild does not lower the package feerate
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            once(&grandchild_tx),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        assert_eq!(mining_manager.get_ready_transaction_feerate(&parent_tx.id()), Some(package_feerate));
+
+        // Once the parent is in a block, the child is ranked by its own package
+        consensus.add_transaction(parent_tx.clone(), 2);
+        let result = mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &build_block_transactions(once(&parent_tx)));
+        assert!(result.is_ok(), "mining manager should handle new block transactions successfully but returns {result:?}");
+        assert_eq!(mining_manager.get_ready_transaction_feerate(&parent_tx.id()), None);
+        assert_eq!(mining_manager.get_ready_transaction_feerate(&child_tx.id()), Some(child_fee as f64 / child_mass as f64));
+    }
+
+    /// test_restore_transactions verifies that persisted transactions are restored with their insertion context
+    /// and that the ones which became invalid in the meantime are dropped.
+    #[test]
+    fn test_restore_transactions() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        const TX_PAIRS_COUNT: usize = 3;
+        let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, TX_PAIRS_COUNT);
+        let orphan_tx = create_transaction(&create_transaction_without_input(vec![500 * SOMPI_PER_VECNO]), 1000);
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            parent_txs.iter(),
+            Priority::High,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            child_txs.iter().chain(once(&orphan_tx)),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Allowed,
+        );
+
+        // Simulate a node restart through a serialization round trip
+        let persisted = mining_manager.get_persisted_transactions();
+        assert_eq!(persisted.len(), 2 * TX_PAIRS_COUNT + 1);
+        let persisted: Vec<PersistedTransaction> = borsh::from_slice(&borsh::to_vec(&persisted).unwrap()).unwrap();
+
+        // The first child became invalid while the node was down
+        consensus.set_status(child_txs[0].id(), Err(TxRuleError::TxHasGas));
+
+        let counters = Arc::new(MiningCounters::default());
+        let restored_mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let restored = restored_mining_manager.restore_transactions(consensus.as_ref(), persisted.clone());
+        assert_eq!(restored, 2 * TX_PAIRS_COUNT, "all but the invalid transaction should be restored");
+
+        let restored_persisted = restored_mining_manager.get_persisted_transactions();
+        assert!(
+            restored_persisted.iter().all(|x| x.transaction.id() != child_txs[0].id()),
+            "the invalid transaction should be dropped"
+        );
+        for expected in persisted.iter().filter(|x| x.transaction.id() != child_txs[0].id()) {
+            let restored = restored_persisted
+                .iter()
+                .find(|x| x.transaction.id() == expected.transaction.id())
+                .expect("the valid transactions should be restored");
+            assert_eq!(restored.priority, expected.priority);
+            assert_eq!(restored.rbf_policy, expected.rbf_policy);
+            assert_eq!(restored.added_at_daa_score, expected.added_at_daa_score);
+            assert_eq!(restored.is_orphan, expected.is_orphan);
+        }
+    }
+
+    /// test_transaction_package verifies that a transaction package is judged by its aggregate fee and that it is
+    /// either inserted as a whole or rejected as a whole, with the errors of the offending transactions.
+    #[test]
+    fn test_transaction_package() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        let funding_tx = create_transaction_without_input(vec![500 * SOMPI_PER_VECNO]);
+        consensus.add_transaction(funding_tx.clone(), 1);
+        let parent_tx = create_transaction(&funding_tx, 0);
+        let child_tx = create_transaction(&parent_tx, 100 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let low_fee_child_tx = create_transaction(&parent_tx, 1);
+        let invalid_child_tx = create_transaction(&parent_tx, 200 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        consensus.set_status(invalid_child_tx.id(), Err(TxRuleError::TxHasGas));
+
+        // Alone, the parent does not pay the minimum relay fee
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            parent_tx.clone(),
+            Priority::High,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        assert!(
+            matches!(into_mempool_result(result), Err(RuleError::RejectNonStandard(..))),
+            "a transaction paying no fee should be rejected"
+        );
+
+        // Malformed packages are rejected before any validation
+        let result = mining_manager.validate_and_insert_transaction_package(consensus.as_ref(), vec![], Priority::High);
+        assert_eq!(into_mempool_result(result), Err(RuleError::RejectEmptyPackage));
+        let result = mining_manager.validate_and_insert_transaction_package(
+            consensus.as_ref(),
+            vec![child_tx.clone(), parent_tx.clone()],
+            Priority::High,
+        );
+        assert_eq!(into_mempool_result(result), Err(RuleError::RejectPackageNotSorted(child_tx.id())));
+        let result = mining_manager.validate_and_insert_transaction_package(
+            consensus.as_ref(),
+            vec![parent_tx.clone(), child_tx.clone(), low_fee_child_tx.clone()],
+            Priority::High,
+        );
+        assert_eq!(
+            into_mempool_result(result),
+            Err(RuleError::RejectPackageDoubleSpend(low_fee_child_tx.inputs[0].previous_outpoint, low_fee_child_tx.id()))
+        );
+
+        // A package not paying enough fees as a whole is rejected
+        let result = mining_manager.validate_and_insert_transaction_package(
+            consensus.as_ref(),
+            vec![parent_tx.clone(), low_fee_child_tx.clone()],
+            Priority::High,
+        );
+        assert!(matches!(into_mempool_result(result), Err(RuleError::RejectPackageInsufficientFee(..))));
+        assert_transaction_count(&mining_manager, 0, "no transaction of a rejected package should be in the mempool");
+
+        // A package with an invalid transaction is rejected as a whole, reporting the invalid transaction only
+        let result = mining_manager.validate_and_insert_transaction_package(
+            consensus.as_ref(),
+            vec![parent_tx.clone(), invalid_child_tx.clone()],
+            Priority::High,
+        );
+        match result {
+            Err(MiningManagerError::PackageRejected(rejections)) => {
+                assert_eq!(rejections, vec![(invalid_child_tx.id(), RuleError::RejectTxRule(TxRuleError::TxHasGas))]);
+            }
+            _ => panic!("the package should be rejected because of its invalid child but returned {result:?}"),
+        }
+        assert_transaction_count(&mining_manager, 0, "no transaction of a rejected package should be in the mempool");
+
+        // The child pays for its parent
+        let result = mining_manager.validate_and_insert_transaction_package(
+            consensus.as_ref(),
+            vec![parent_tx.clone(), child_tx.clone()],
+            Priority::High,
+        );
+        let accepted = result.expect("the package should be accepted");
+        assert_eq!(accepted.iter().map(|tx| tx.id()).collect_vec(), vec![parent_tx.id(), child_tx.id()]);
+        assert_transaction_count(&mining_manager, 2, "the whole package should be in the mempool");
+
+        // Submitting the package again is a no-op
+        let result = mining_manager.validate_and_insert_transaction_package(
+            consensus.as_ref(),
+            vec![parent_tx.clone(), child_tx.clone()],
+            Priority::High,
+        );
+        assert!(result.expect("a known package should be accepted").is_empty());
+        assert_transaction_count(&mining_manager, 2, "the mempool should be unchanged");
+    }
+
+    /// test_transaction_package_insertion_failure verifies that a package failing to be inserted partway, once some of
+    /// its transactions are in the pool already, leaves the mempool unchanged.
+    #[test]
+    fn test_transaction_package_insertion_failure() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        let funding_tx = create_transaction_without_input(vec![500 * SOMPI_PER_VECNO]);
+        consensus.add_transaction(funding_tx.clone(), 1);
+        let parent_tx = create_transaction(&funding_tx, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let child_tx = create_transaction(&parent_tx, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let conflicting_child_tx = create_transaction(&parent_tx, 2 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+
+        // The child is first received as an orphan missing its parent
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            child_tx.clone(),
+            Priority::High,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "the child should be accepted as an orphan");
+        assert!(mining_manager.has_transaction(&child_tx.id(), TransactionQuery::OrphansOnly));
+
+        // Populate the package transactions the way package pre-validation does, the children spending the package parent
+        let populate = |transaction: &Transaction| {
+            let mut mutable_tx = MutableTransaction::from_tx(transaction.clone());
+            mutable_tx.calculated_compute_mass = Some(consensus.calculate_transaction_compute_mass(transaction));
+            for (i, input) in transaction.inputs.iter().enumerate() {
+                if input.previous_outpoint.transaction_id == parent_tx.id() {
+                    let output = &parent_tx.outputs[input.previous_outpoint.index as usize];
+                    mutable_tx.entries[i] =
+                        Some(UtxoEntry::new(output.value, output.script_public_key.clone(), UNACCEPTED_DAA_SCORE, false));
+                }
+            }
+            consensus.validate_mempool_transaction(&mut mutable_tx, &Default::default()).expect("the transaction is valid");
+            mutable_tx
+        };
+
+        // The two children double spend each other, which only shows up when inserting the second one since the package
+        // structure checks are skipped
+        let result = mining_manager.post_validate_and_insert_package(
+            consensus.as_ref(),
+            vec![populate(&parent_tx), populate(&child_tx), populate(&conflicting_child_tx)],
+            Priority::High,
+        );
+        assert_eq!(
+            into_mempool_result(result),
+            Err(RuleError::RejectDoubleSpendInMempool(conflicting_child_tx.inputs[0].previous_outpoint, child_tx.id()))
+        );
+        assert_transaction_count(&mining_manager, 0, "no transaction of a package failing to be inserted should be in the mempool");
+        assert!(
+            mining_manager.has_transaction(&child_tx.id(), TransactionQuery::OrphansOnly),
+            "the orphan should be kept when the package fails to be inserted"
+        );
+        assert_eq!(mining_manager.get_estimated_size(), 0, "the mempool size should be restored");
+        mining_manager.check_invariants().expect("the mempool invariants should hold");
+
+        // Without the conflicting child, the package gets in and unorphans the child
+        let result = mining_manager.post_validate_and_insert_package(
+            consensus.as_ref(),
+            vec![populate(&parent_tx), populate(&child_tx)],
+            Priority::High,
+        );
+        assert!(result.is_ok(), "the package should be inserted");
+        assert_transaction_count(&mining_manager, 2, "the whole package should be in the mempool");
+        assert!(!mining_manager.has_transaction(&child_tx.id(), TransactionQuery::OrphansOnly));
+        mining_manager.check_invariants().expect("the mempool invariants should hold");
+    }
+
+    /// test_mempool_accept verifies that testing transactions for acceptance reports the outcome of their validation
+    /// without modifying the mempool.
+    #[test]
+    fn test_mempool_accept() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        let funding_tx = create_transaction_without_input(vec![500 * SOMPI_PER_VECNO]);
+        consensus.add_transaction(funding_tx.clone(), 1);
+        let parent_tx = create_transaction(&funding_tx, 10 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let child_tx = create_transaction(&parent_tx, 10 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let replacement_tx = create_transaction(&funding_tx, 20 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+
+        // Transactions are tested independently so the child of a tested transaction is an orphan
+        let acceptances = mining_manager.test_mempool_accept(
+            consensus.as_ref(),
+            vec![parent_tx.clone(), child_tx.clone()],
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
+        assert_eq!(acceptances.len(), 2);
+        assert!(acceptances[0].is_accepted(), "the parent should be accepted but got {:?}", acceptances[0].error);
+        assert!(!acceptances[0].is_orphan);
+        assert_eq!(acceptances[0].transaction_id, parent_tx.id());
+        assert_eq!(acceptances[0].fee, Some(10 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE));
+        assert_eq!(acceptances[0].compute_mass, transaction_estimated_serialized_size(&parent_tx));
+        assert!(acceptances[0].storage_mass.is_some());
+        assert!(acceptances[0].feerate.is_some_and(|feerate| feerate > 0.0));
+        assert!(acceptances[1].is_accepted(), "the child should be accepted but got {:?}", acceptances[1].error);
+        assert!(acceptances[1].is_orphan);
+        assert_eq!(acceptances[1].fee, None);
+        assert_transaction_count(&mining_manager, 0, "tested transactions should not be inserted into the mempool");
+
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            parent_tx.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "the parent should be inserted into the mempool");
+
+        let acceptances = mining_manager.test_mempool_accept(
+            consensus.as_ref(),
+            vec![parent_tx.clone(), child_tx.clone(), replacement_tx.clone()],
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
+        assert_eq!(acceptances[0].error, Some(RuleError::RejectDuplicate(parent_tx.id())));
+        assert!(acceptances[1].is_accepted() && !acceptances[1].is_orphan, "the child of a mempool transaction is not an orphan");
+        assert_eq!(
+            acceptances[2].error,
+            Some(RuleError::RejectDoubleSpendInMempool(replacement_tx.inputs[0].previous_outpoint, parent_tx.id()))
+        );
+
+        let acceptances =
+            mining_manager.test_mempool_accept(consensus.as_ref(), vec![replacement_tx.clone()], Orphan::Allowed, RbfPolicy::Allowed);
+        assert!(acceptances[0].is_accepted(), "the replacement should be accepted but got {:?}", acceptances[0].error);
+        assert_eq!(acceptances[0].replaced_transaction_id, Some(parent_tx.id()));
+        assert!(
+            mining_manager.has_transaction(&parent_tx.id(), TransactionQuery::TransactionsOnly),
+            "a transaction replaced in a test should stay in the mempool"
+        );
+        assert_transaction_count(&mining_manager, 1, "tested transactions should not be inserted into the mempool");
+    }
+
+    /// test_feerate_history verifies that the mempool transactions accepted in a block feed the historical fee estimations
+    /// and that the history survives a restoration
+    #[test]
+    fn test_feerate_history() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters.clone());
+
+        const TX_COUNT: usize = 10;
+        let funding_txs = create_and_add_funding_transactions(&consensus, TX_COUNT + 1);
+        let transactions = (0..=TX_COUNT)
+            .map(|i| {
+                create_funded_transaction(
+                    select_transactions(&funding_txs, &[i]),
+                    vec![0],
+                    None,
+                    DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
+                )
+            })
+            .collect::<Vec<_>>();
+        // The last transaction is never submitted so its fee is unknown to the mempool
+        for transaction in transactions.iter().take(TX_COUNT) {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                Priority::Low,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "the insertion of a new valid transaction in the mempool failed");
+        }
+        assert_eq!(mining_manager.get_historical_fee_estimate(0).sample_count, 0);
+
+        let block_transactions = build_block_transactions(transactions.iter());
+        mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &block_transactions).unwrap();
+
+        let estimate = mining_manager.get_historical_fee_estimate(0);
+        assert_eq!(estimate.block_count, 1);
+        assert_eq!(estimate.sample_count, TX_COUNT as u64);
+        // All the transactions were accepted right after their insertion, meeting all the targets
+        assert_eq!(estimate.buckets.len(), TARGET_SECONDS.len() * CONFIDENCE_LEVELS.len());
+        assert!(estimate.buckets.iter().all(|bucket| bucket.feerate > 0.0));
+
+        let restored_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        restored_manager.restore_feerate_history(mining_manager.get_feerate_history_records());
+        let restored_estimate = restored_manager.get_historical_fee_estimate(0);
+        assert_eq!(restored_estimate.sample_count, estimate.sample_count);
+        assert_eq!(restored_estimate.buckets.len(), estimate.buckets.len());
+    }
+
+    /// test_template_policies verifies the deterministic selection of block template transactions, the exclusion of
+    /// transactions, the block mass reserved for prioritised transactions and the effect of fee deltas
+    #[test]
+    fn test_template_policies() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+
+        const TX_COUNT: usize = 5;
+        let funding_txs = create_and_add_funding_transactions(&consensus, TX_COUNT);
+        // The feerate grows with the transaction index
+        let txs = (0..TX_COUNT)
+            .map(|i| {
+                let fee = (i as u64 + 1) * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE;
+                create_funded_transaction(select_transactions(&funding_txs, &[i]), vec![0], None, fee)
+            })
+            .collect_vec();
+        let ids = |indexes: &[usize]| indexes.iter().map(|&i| txs[i].id()).collect_vec();
+
+        // Blocks have room for two transactions
+        let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        config.maximum_mass_per_block = transaction_estimated_serialized_size(&txs[0]) * 5 / 2;
+        let node_policy = TemplatePolicy { selection_mode: TemplateSelectionMode::HighestFeerate, ..Default::default() };
+        let mining_manager = MiningManager::with_config(config, None, counters).with_template_policy(node_policy);
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            txs.iter(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+
+        let miner_data = get_miner_data(Prefix::Testnet);
+        let template_ids = |policy: Option<&TemplatePolicy>| {
+            let template = match policy {
+                Some(policy) => mining_manager.get_block_template_with_policy(consensus.as_ref(), &miner_data, policy),
+                None => mining_manager.get_block_template(consensus.as_ref(), &miner_data),
+            };
+            template.unwrap().block.transactions[1..].iter().map(|tx| tx.id()).collect_vec()
+        };
+
+        // The node policy applies when the request provides none
+        assert_eq!(template_ids(None), ids(&[4, 3]));
+
+        let mut policy = TemplatePolicy { selection_mode: TemplateSelectionMode::HighestFeerate, ..Default::default() };
+        policy.excluded_transaction_ids.insert(txs[4].id());
+        assert_eq!(template_ids(Some(&policy)), ids(&[3, 2]));
+
+        // Half of the block is reserved to prioritised transactions, which get in despite their low feerate
+        policy.prioritised_mass_share = 0.5;
+        policy.prioritised_transaction_ids.insert(txs[0].id());
+        assert_eq!(template_ids(Some(&policy)), ids(&[0, 3]));
+
+        // A positive fee delta prioritises a transaction too, the reserved mass going to the highest feerate ones
+        assert_eq!(mining_manager.prioritise_transaction(txs[1].id(), 1), 1);
+        assert_eq!(template_ids(Some(&policy)), ids(&[1, 3]));
+
+        // The reserved mass applies to the weighted selection as well
+        policy.selection_mode = TemplateSelectionMode::Weighted;
+        let selected = template_ids(Some(&policy));
+        assert_eq!(selected.len(), 2);
+        assert_eq!(selected[0], txs[1].id());
+        assert!(!selected.contains(&txs[4].id()));
+
+        // A negative fee delta demotes a transaction, deltas adding up
+        let fee = 4 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE as i64;
+        assert_eq!(mining_manager.prioritise_transaction(txs[3].id(), -fee / 2), -fee / 2);
+        assert_eq!(mining_manager.prioritise_transaction(txs[3].id(), -fee / 2), -fee);
+        assert_eq!(mining_manager.get_ready_transaction_feerate(&txs[3].id()), Some(0.0));
+        assert_eq!(template_ids(None), ids(&[4, 2]));
+
+        // The delta is dropped once the transaction is accepted
+        let result = mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &build_block_transactions(once(&txs[1])));
+        assert!(result.is_ok(), "mining manager should handle new block transactions successfully but returns {result:?}");
+        assert_eq!(mining_manager.prioritise_transaction(txs[1].id(), 0), 0);
+
+        policy.prioritised_mass_share = 1.5;
+        assert!(matches!(
+            mining_manager.get_block_template_with_policy(consensus.as_ref(), &miner_data, &policy),
+            Err(MiningManagerError::InvalidTemplatePolicy)
+        ));
+    }
+
+    /// test_mempool_entries_query verifies the filtering, sorting and paging of the mempool entries, along with the totals
+    /// computed over all the matching entries
+    #[test]
+    fn test_mempool_entries_query() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        const TX_COUNT: usize = 4;
+        let funding_txs = create_and_add_funding_transactions(&consensus, TX_COUNT);
+        // The feerate grows with the transaction index
+        let txs = (0..TX_COUNT)
+            .map(|i| {
+                let fee = (i as u64 + 1) * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE;
+                create_funded_transaction(select_transactions(&funding_txs, &[i]), vec![0], None, fee)
+            })
+            .collect_vec();
+        let ids = |indexes: &[usize]| indexes.iter().map(|&i| txs[i].id()).collect_vec();
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            txs.iter(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        let orphan = create_transaction(&create_transaction_without_input(vec![500 * SOMPI_PER_VECNO]), 1000);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            orphan.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "the insertion of an orphan transaction in the mempool failed");
+
+        let page_ids = |page: &EntriesPage| page.transactions.iter().map(|mtx| mtx.id()).collect_vec();
+        let mut query = EntriesQuery { sort_key: EntriesSortKey::Feerate, descending: true, limit: Some(2), ..Default::default() };
+        let page = mining_manager.get_mempool_entries(TransactionQuery::TransactionsOnly, &query);
+        assert_eq!(page_ids(&page), ids(&[3, 2]));
+        assert_eq!(page.total_count, TX_COUNT);
+        assert_eq!(page.total_fees, 10 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let (pool_transactions, _) = mining_manager.get_all_transactions(TransactionQuery::TransactionsOnly);
+        assert_eq!(page.total_mass, pool_transactions.iter().map(|mtx| mtx.tx.mass()).sum::<u64>());
+
+        query.offset = 2;
+        let page = mining_manager.get_mempool_entries(TransactionQuery::TransactionsOnly, &query);
+        assert_eq!(page_ids(&page), ids(&[1, 0]));
+        query.offset = TX_COUNT;
+        assert!(mining_manager.get_mempool_entries(TransactionQuery::TransactionsOnly, &query).transactions.is_empty());
+
+        // The totals only account for the entries matching the filters
+        query =
+            EntriesQuery { min_feerate: mining_manager.get_ready_transaction_feerate(&txs[2].id()).unwrap(), ..Default::default() };
+        let page = mining_manager.get_mempool_entries(TransactionQuery::All, &query);
+        assert_eq!(page_ids(&page), ids(&[2, 3]).into_iter().sorted().collect_vec());
+        assert_eq!(page.total_fees, 7 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+
+        query = EntriesQuery { payload_only: true, ..Default::default() };
+        assert_eq!(mining_manager.get_mempool_entries(TransactionQuery::All, &query).total_count, 0);
+
+        let page = mining_manager.get_mempool_entries(TransactionQuery::OrphansOnly, &EntriesQuery::default());
+        assert_eq!(page_ids(&page), vec![orphan.id()]);
+        assert_eq!(page.total_fees, 0);
+    }
+
+    /// test_matching_transactions verifies that only the transactions of the queried pools matched by the filter are returned
+    #[test]
+    fn test_matching_transactions() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        const TX_COUNT: usize = 4;
+        let funding_txs = create_and_add_funding_transactions(&consensus, TX_COUNT);
+        let txs = (0..TX_COUNT)
+            .map(|i| {
+                create_funded_transaction(
+                    select_transactions(&funding_txs, &[i]),
+                    vec![0],
+                    None,
+                    DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
+                )
+            })
+            .collect_vec();
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            txs.iter(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        let orphan = create_transaction(&create_transaction_without_input(vec![500 * SOMPI_PER_VECNO]), 1000);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            orphan.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "the insertion of an orphan transaction in the mempool failed");
+
+        let wanted: HashSet<_> = [txs[1].id(), txs[3].id(), orphan.id()].into_iter().collect();
+        let matching_ids = |query| {
+            mining_manager.get_matching_transactions(query, |id| wanted.contains(id)).iter().map(|tx| tx.id()).sorted().collect_vec()
+        };
+        assert_eq!(matching_ids(TransactionQuery::TransactionsOnly), [txs[1].id(), txs[3].id()].into_iter().sorted().collect_vec());
+        assert_eq!(matching_ids(TransactionQuery::OrphansOnly), vec![orphan.id()]);
+        assert_eq!(matching_ids(TransactionQuery::All).len(), 3);
+    }
+
     fn validate_and_insert_mutable_transaction(
         mining_manager: &MiningManager,
         consensus: &dyn ConsensusApi,
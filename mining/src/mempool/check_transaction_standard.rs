@@ -92,7 +92,9 @@ impl Mempool {
                 return Err(NonStandardError::RejectScriptPublicKeyVersion(transaction_id, i));
             }
 
-            if ScriptClass::from_script(&output.script_public_key) == ScriptClass::NonStandard {
+            if ScriptClass::from_script(&output.script_public_key) == ScriptClass::NonStandard
+                && !self.admission_policy.allows_non_standard_script(&output.script_public_key)
+            {
                 return Err(NonStandardError::RejectOutputScriptClass(transaction_id, i));
             }
 
@@ -167,8 +169,9 @@ impl Mempool {
     /// context of this function is one whose referenced public key script is of a
     /// standard form and, for pay-to-script-hash, does not have more than
     /// maxStandardP2SHSigOps signature operations.
-    /// In addition, makes sure that the transaction's fee is above the minimum for acceptance
-    /// into the mempool and relay.
+    ///
+    /// Note that the transaction's fee is checked separately by [`Self::check_transaction_fee`]
+    /// since the fee of a package transaction is judged along with the rest of its package.
     pub(crate) fn check_transaction_standard_in_context(&self, transaction: &MutableTransaction) -> NonStandardResult<()> {
         let transaction_id = transaction.id();
         let contextual_mass = transaction.tx.mass();
@@ -183,7 +186,9 @@ impl Mempool {
             let entry = transaction.entries[i].as_ref().unwrap();
             match ScriptClass::from_script(&entry.script_public_key) {
                 ScriptClass::NonStandard => {
-                    return Err(NonStandardError::RejectInputScriptClass(transaction_id, i));
+                    if !self.admission_policy.allows_non_standard_script(&entry.script_public_key) {
+                        return Err(NonStandardError::RejectInputScriptClass(transaction_id, i));
+                    }
                 }
                 ScriptClass::PubKey => {}
                 ScriptClass::PubKeyECDSA => {}
@@ -197,20 +202,25 @@ impl Mempool {
                     }
                 }
             }
-
-            // TODO: For now, until wallets adapt, we don't require fee as function of full contextual_mass (but the fee/mass ratio will affect tx selection to block template)
-            let minimum_fee = self.minimum_required_transaction_relay_fee(transaction.calculated_compute_mass.unwrap());
-            if transaction.calculated_fee.unwrap() < minimum_fee {
-                return Err(NonStandardError::RejectInsufficientFee(transaction_id, transaction.calculated_fee.unwrap(), minimum_fee));
-            }
         }
 
         Ok(())
     }
 
+    /// check_transaction_fee makes sure that the transaction's fee is above the minimum for
+    /// acceptance into the mempool and relay.
+    pub(crate) fn check_transaction_fee(&self, transaction: &MutableTransaction) -> NonStandardResult<()> {
+        // TODO: For now, until wallets adapt, we don't require fee as function of full contextual_mass (but the fee/mass ratio will affect tx selection to block template)
+        let minimum_fee = self.minimum_required_transaction_relay_fee(transaction.calculated_compute_mass.unwrap());
+        if transaction.calculated_fee.unwrap() < minimum_fee {
+            return Err(NonStandardError::RejectInsufficientFee(transaction.id(), transaction.calculated_fee.unwrap(), minimum_fee));
+        }
+        Ok(())
+    }
+
     /// minimum_required_transaction_relay_fee returns the minimum transaction fee required
     /// for a transaction with the passed mass to be accepted into the mempool and relayed.
-    fn minimum_required_transaction_relay_fee(&self, mass: u64) -> u64 {
+    pub(crate) fn minimum_required_transaction_relay_fee(&self, mass: u64) -> u64 {
         // Calculate the minimum fee for a transaction to be allowed into the
         // mempool and relayed by scaling the base fee. MinimumRelayTransactionFee is in
         // sompi/kg so multiply by mass (which is in grams) and divide by 1000 to get
@@ -221,6 +231,11 @@ impl Mempool {
             minimum_fee = self.config.minimum_relay_transaction_fee;
         }
 
+        // The admission policy may require a higher feerate, typically during periods of spam
+        if let Some(minimum_feerate) = self.admission_policy.minimum_feerate() {
+            minimum_fee = minimum_fee.max((mass as f64 * minimum_feerate).ceil() as u64);
+        }
+
         // Set the minimum fee to the maximum possible value if the calculated
         // fee is not in the valid range for monetary amounts.
         minimum_fee = minimum_fee.min(MAX_SOMPI);
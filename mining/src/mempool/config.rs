@@ -14,6 +14,10 @@ pub(crate) const DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS: u64 = 10;
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_MASS: u64 = 100_000;
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT: u64 = 500;
 
+/// DEFAULT_MAXIMUM_PACKAGE_TRANSACTION_COUNT specifies the maximum number of transactions a package submitted
+/// or relayed as a whole may contain.
+pub(crate) const DEFAULT_MAXIMUM_PACKAGE_TRANSACTION_COUNT: usize = 25;
+
 /// DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE specifies the minimum transaction fee for a transaction to be accepted to
 /// the mempool and relayed. It is specified in sompi per 1kg (or 1000 grams) of transaction mass.
 pub(crate) const DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE: u64 = 1000;
@@ -40,6 +44,7 @@ pub struct Config {
     pub orphan_expire_scan_interval_daa_score: u64,
     pub maximum_orphan_transaction_mass: u64,
     pub maximum_orphan_transaction_count: u64,
+    pub maximum_package_transaction_count: usize,
     pub accept_non_standard: bool,
     pub maximum_mass_per_block: u64,
     pub minimum_relay_transaction_fee: u64,
@@ -64,6 +69,7 @@ impl Config {
         orphan_expire_scan_interval_daa_score: u64,
         maximum_orphan_transaction_mass: u64,
         maximum_orphan_transaction_count: u64,
+        maximum_package_transaction_count: usize,
         accept_non_standard: bool,
         maximum_mass_per_block: u64,
         minimum_relay_transaction_fee: u64,
@@ -85,6 +91,7 @@ impl Config {
             orphan_expire_scan_interval_daa_score,
             maximum_orphan_transaction_mass,
             maximum_orphan_transaction_count,
+            maximum_package_transaction_count,
             accept_non_standard,
             maximum_mass_per_block,
             minimum_relay_transaction_fee,
@@ -114,6 +121,7 @@ impl Config {
             orphan_expire_scan_interval_daa_score: DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS * 1000 / target_milliseconds_per_block,
             maximum_orphan_transaction_mass: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_MASS,
             maximum_orphan_transaction_count: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT,
+            maximum_package_transaction_count: DEFAULT_MAXIMUM_PACKAGE_TRANSACTION_COUNT,
             accept_non_standard: relay_non_std_transactions,
             maximum_mass_per_block: max_block_mass,
             minimum_relay_transaction_fee: DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
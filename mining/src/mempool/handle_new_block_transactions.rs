@@ -1,17 +1,20 @@
-use crate::mempool::{
-    errors::RuleResult,
-    model::{
-        pool::Pool,
-        tx::{MempoolTransaction, TxRemovalReason},
+use crate::{
+    feerate::history::{BlockFeerateRecord, FeerateSample},
+    mempool::{
+        errors::RuleResult,
+        model::{
+            pool::Pool,
+            tx::{MempoolTransaction, TxRemovalReason},
+        },
+        Mempool,
     },
-    Mempool,
 };
 use std::{collections::HashSet, sync::atomic::Ordering};
 use vecno_consensus_core::{
     api::ConsensusApi,
     tx::{Transaction, TransactionId},
 };
-use vecno_core::time::Stopwatch;
+use vecno_core::time::{unix_now, Stopwatch};
 
 impl Mempool {
     pub(crate) fn handle_new_block_transactions(
@@ -24,8 +27,14 @@ impl Mempool {
         let mut tx_accepted_counts = 0;
         let mut input_counts = 0;
         let mut output_counts = 0;
+        let now = unix_now();
+        let mut feerate_samples = vec![];
         for transaction in block_transactions[1..].iter() {
             let transaction_id = transaction.id();
+            // Only the transactions known to the mempool have a known fee and time spent waiting for a block
+            if let Some(transaction) = self.transaction_pool.get(&transaction_id) {
+                feerate_samples.push(FeerateSample::new(transaction.fee_rate(), now.saturating_sub(transaction.added_at_time)));
+            }
             // Rust rewrite: This behavior does differ from golang implementation.
             // If the transaction got accepted via a peer but is still an orphan here, do not remove
             // its redeemers in the orphan pool. We give those a chance to be unorphaned and included
@@ -35,6 +44,7 @@ impl Mempool {
             }
             self.remove_double_spends(transaction)?;
             self.orphan_pool.remove_orphan(&transaction_id, false, TxRemovalReason::Accepted, "")?;
+            self.transaction_pool.remove_fee_delta(&transaction_id);
             if self.accepted_transactions.add(transaction_id, block_daa_score) {
                 tx_accepted_counts += 1;
                 input_counts += transaction.inputs.len();
@@ -42,6 +52,7 @@ impl Mempool {
             }
             unorphaned_transactions.extend(self.get_unorphaned_transactions_after_accepted_transaction(transaction));
         }
+        self.feerate_history.add(BlockFeerateRecord::new(now, block_daa_score, feerate_samples));
         self.counters.block_tx_counts.fetch_add(block_transactions.len() as u64 - 1, Ordering::Relaxed);
         self.counters.tx_accepted_counts.fetch_add(tx_accepted_counts, Ordering::Relaxed);
         self.counters.input_counts.fetch_add(input_counts as u64, Ordering::Relaxed);
@@ -55,7 +66,9 @@ impl Mempool {
     }
 
     pub(crate) fn expire_orphan_low_priority_transactions(&mut self, consensus: &dyn ConsensusApi) -> RuleResult<()> {
-        self.orphan_pool.expire_low_priority_transactions(consensus.get_virtual_daa_score())
+        let expired_orphans = self.orphan_pool.expire_low_priority_transactions(consensus.get_virtual_daa_score())?;
+        self.notify_removed_transactions(&expired_orphans, TxRemovalReason::Expired);
+        Ok(())
     }
 
     pub(crate) fn expire_accepted_transactions(&mut self, consensus: &dyn ConsensusApi) {
@@ -1,32 +1,52 @@
 use crate::{
-    feerate::{FeerateEstimator, FeerateEstimatorArgs},
+    feerate::{
+        history::{FeerateHistory, HistoricalFeeEstimate},
+        FeerateEstimator, FeerateEstimatorArgs,
+    },
     model::{
+        entries_query::{EntriesPage, EntriesQuery, EntryProperties},
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
         tx_query::TransactionQuery,
     },
-    MiningCounters,
+    MiningCounters, TemplatePolicy,
 };
 
 use self::{
     config::Config,
-    model::{accepted_transactions::AcceptedTransactions, orphan_pool::OrphanPool, pool::Pool, transactions_pool::TransactionsPool},
+    model::{
+        accepted_transactions::AcceptedTransactions,
+        orphan_pool::OrphanPool,
+        pool::Pool,
+        transactions_pool::TransactionsPool,
+        tx::{MempoolTransaction, TxRemovalReason},
+    },
+    policy::{AdmissionPolicy, DefaultAdmissionPolicy},
     tx::Priority,
 };
+use itertools::Itertools;
 use std::sync::Arc;
 use vecno_consensus_core::{
     block::TemplateTransactionSelector,
-    tx::{MutableTransaction, TransactionId},
+    tx::{MutableTransaction, Transaction, TransactionId},
 };
-use vecno_core::time::Stopwatch;
+use vecno_consensus_notify::{
+    notification::{MempoolTransactionRemovedNotification, Notification},
+    root::ConsensusNotificationRoot,
+};
+use vecno_core::{time::Stopwatch, warn};
+use vecno_notify::{events::EventType, notifier::Notify};
 
 pub(crate) mod check_transaction_standard;
 pub mod config;
 pub mod errors;
 pub(crate) mod handle_new_block_transactions;
 pub(crate) mod model;
+pub(crate) mod persistence;
+pub mod policy;
 pub(crate) mod populate_entries_and_try_validate;
 pub(crate) mod remove_transaction;
 pub(crate) mod replace_by_fee;
+pub(crate) mod validate_and_insert_package;
 pub(crate) mod validate_and_insert_transaction;
 
 /// Mempool contains transactions intended to be inserted into a block and mined.
@@ -50,7 +70,12 @@ pub(crate) struct Mempool {
     transaction_pool: TransactionsPool,
     orphan_pool: OrphanPool,
     accepted_transactions: AcceptedTransactions,
+    /// Feerates and waiting times of the mempool transactions accepted in recent blocks
+    feerate_history: FeerateHistory,
     counters: Arc<MiningCounters>,
+    admission_policy: Arc<dyn AdmissionPolicy>,
+    /// Root receiving the notifications of transactions removed from the mempool without being accepted
+    notification_root: Option<Arc<ConsensusNotificationRoot>>,
 }
 
 impl Mempool {
@@ -58,7 +83,60 @@ impl Mempool {
         let transaction_pool = TransactionsPool::new(config.clone());
         let orphan_pool = OrphanPool::new(config.clone());
         let accepted_transactions = AcceptedTransactions::new(config.clone());
-        Self { config, transaction_pool, orphan_pool, accepted_transactions, counters }
+        let admission_policy = Arc::new(DefaultAdmissionPolicy);
+        Self {
+            config,
+            transaction_pool,
+            orphan_pool,
+            accepted_transactions,
+            feerate_history: FeerateHistory::default(),
+            counters,
+            admission_policy,
+            notification_root: None,
+        }
+    }
+
+    pub(crate) fn set_admission_policy(&mut self, admission_policy: Arc<dyn AdmissionPolicy>) {
+        self.admission_policy = admission_policy;
+    }
+
+    pub(crate) fn set_notification_root(&mut self, notification_root: Arc<ConsensusNotificationRoot>) {
+        self.notification_root = Some(notification_root);
+    }
+
+    /// Notifies the removal of `transactions` if `reason` is worth notifying
+    pub(crate) fn notify_removed_transactions<'a>(
+        &self,
+        transactions: impl IntoIterator<Item = &'a MempoolTransaction>,
+        reason: TxRemovalReason,
+    ) {
+        let (Some(notification_root), Some(reason)) = (self.notification_root.as_ref(), reason.notification_reason()) else {
+            return;
+        };
+        if !notification_root.has_subscription(EventType::MempoolTransactionRemoved) {
+            return;
+        }
+        for transaction in transactions {
+            // The transaction is related to the addresses it pays to as well as to the addresses of the UTXOs it spends
+            let script_public_keys = transaction
+                .mtx
+                .tx
+                .outputs
+                .iter()
+                .map(|output| &output.script_public_key)
+                .chain(transaction.mtx.entries.iter().flatten().map(|entry| &entry.script_public_key))
+                .unique()
+                .cloned()
+                .collect_vec();
+            let notification = Notification::MempoolTransactionRemoved(MempoolTransactionRemovedNotification::new(
+                transaction.id(),
+                reason,
+                Arc::new(script_public_keys),
+            ));
+            if let Err(err) = notification_root.notify(notification) {
+                warn!("Failed to notify the removal of transaction {} from mempool: {}", transaction.id(), err);
+            }
+        }
     }
 
     pub(crate) fn get_transaction(&self, transaction_id: &TransactionId, query: TransactionQuery) -> Option<MutableTransaction> {
@@ -83,6 +161,46 @@ impl Mempool {
         (transactions, orphans)
     }
 
+    /// Returns the page of the entries of the queried pools matching `entries_query`
+    pub(crate) fn get_entries_page(&self, query: TransactionQuery, entries_query: &EntriesQuery) -> EntriesPage {
+        let pools =
+            [(query.include_transaction_pool(), self.transaction_pool.all()), (query.include_orphan_pool(), self.orphan_pool.all())];
+        let mut entries = pools
+            .into_iter()
+            .filter_map(|(included, pool)| included.then_some(pool))
+            .flat_map(|pool| pool.values())
+            .map(|transaction| (EntryProperties::new(&transaction.mtx, transaction.added_at_time), transaction))
+            .filter(|(properties, _)| entries_query.matches(properties))
+            .collect_vec();
+        let total_mass = entries.iter().map(|(properties, _)| properties.mass).sum();
+        let total_fees = entries.iter().map(|(properties, _)| properties.fee).sum();
+        entries.sort_unstable_by(|(a, _), (b, _)| entries_query.compare(a, b));
+        let transactions = entries
+            .iter()
+            .skip(entries_query.offset)
+            .take(entries_query.limit.unwrap_or(usize::MAX))
+            .map(|(_, transaction)| transaction.mtx.clone())
+            .collect();
+        EntriesPage { transactions, total_count: entries.len(), total_mass, total_fees }
+    }
+
+    /// Returns the transactions of the queried pools whose id is matched by `filter`
+    pub(crate) fn get_matching_transactions(
+        &self,
+        query: TransactionQuery,
+        filter: impl Fn(&TransactionId) -> bool,
+    ) -> Vec<Arc<Transaction>> {
+        let pools =
+            [(query.include_transaction_pool(), self.transaction_pool.all()), (query.include_orphan_pool(), self.orphan_pool.all())];
+        pools
+            .into_iter()
+            .filter_map(|(included, pool)| included.then_some(pool))
+            .flat_map(|pool| pool.iter())
+            .filter(|(id, _)| filter(id))
+            .map(|(_, transaction)| transaction.mtx.tx.clone())
+            .collect()
+    }
+
     pub(crate) fn get_all_transaction_ids(&self, query: TransactionQuery) -> (Vec<TransactionId>, Vec<TransactionId>) {
         let transactions = if query.include_transaction_pool() { self.transaction_pool.get_all_transaction_ids() } else { vec![] };
         let orphans = if query.include_orphan_pool() { self.orphan_pool.get_all_transaction_ids() } else { vec![] };
@@ -124,9 +242,14 @@ impl Mempool {
     }
 
     /// Dynamically builds a transaction selector based on the specific state of the ready transactions frontier
-    pub(crate) fn build_selector(&self) -> Box<dyn TemplateTransactionSelector> {
+    pub(crate) fn build_selector(&self, template_policy: &TemplatePolicy) -> Box<dyn TemplateTransactionSelector> {
         let _sw = Stopwatch::<10>::with_threshold("build_selector op");
-        self.transaction_pool.build_selector()
+        self.transaction_pool.build_selector(template_policy)
+    }
+
+    /// Adds `fee_delta` to the fee `transaction_id` is ranked by for block templates, returning the resulting delta
+    pub(crate) fn prioritise_transaction(&mut self, transaction_id: TransactionId, fee_delta: i64) -> i64 {
+        self.transaction_pool.prioritise_transaction(transaction_id, fee_delta)
     }
 
     /// Builds a feerate estimator based on internal state of the ready transactions frontier
@@ -134,6 +257,11 @@ impl Mempool {
         self.transaction_pool.build_feerate_estimator(args)
     }
 
+    /// Builds a feerate estimation from the transactions accepted during the last `window` milliseconds preceding `now`
+    pub(crate) fn build_historical_fee_estimate(&self, window: u64, now: u64) -> HistoricalFeeEstimate {
+        self.feerate_history.estimate(window, now)
+    }
+
     pub(crate) fn all_transaction_ids_with_priority(&self, priority: Priority) -> Vec<TransactionId> {
         let _sw = Stopwatch::<15>::with_threshold("all_transaction_ids_with_priority op");
         self.transaction_pool.all_transaction_ids_with_priority(priority)
@@ -158,14 +286,37 @@ impl Mempool {
         self.accepted_transactions.unaccepted(&mut not_in_pools_txs)
     }
 
+    /// Checks the internal consistency of the transactions and orphan pools and that no transaction is held by both
+    /// pools nor is held while being accepted
+    pub(crate) fn check_invariants(&self) -> Result<(), String> {
+        self.transaction_pool.check_invariants()?;
+        self.orphan_pool.check_invariants()?;
+        if let Some(id) = self.orphan_pool.all().keys().find(|id| self.transaction_pool.has(id)) {
+            return Err(format!("transaction {id} is both in the transactions pool and the orphan pool"));
+        }
+        if let Some(id) =
+            self.transaction_pool.all().keys().chain(self.orphan_pool.all().keys()).find(|id| self.accepted_transactions.has(id))
+        {
+            return Err(format!("accepted transaction {id} is still in the mempool"));
+        }
+        Ok(())
+    }
+
     #[cfg(test)]
     pub(crate) fn get_estimated_size(&self) -> usize {
         self.transaction_pool.get_estimated_size()
     }
+
+    #[cfg(test)]
+    pub(crate) fn get_ready_transaction_feerate(&self, transaction_id: &TransactionId) -> Option<f64> {
+        self.transaction_pool.get_ready_transaction_feerate(transaction_id)
+    }
 }
 
 pub mod tx {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    use borsh::{BorshDeserialize, BorshSerialize};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
     pub enum Priority {
         Low,
         High,
@@ -178,7 +329,7 @@ pub mod tx {
     }
 
     /// Replace by Fee (RBF) policy
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
     pub enum RbfPolicy {
         /// ### RBF is forbidden
         ///
@@ -1,15 +1,22 @@
 use crate::{
     feerate::{FeerateEstimator, FeerateEstimatorArgs},
     model::candidate_tx::CandidateTransaction,
-    Policy, RebalancingWeightedTransactionSelector,
+    Policy, RebalancingWeightedTransactionSelector, TemplatePolicy, TemplateSelectionMode,
 };
 
 use feerate_key::FeerateTransactionKey;
 use rand::{distributions::Uniform, prelude::Distribution, Rng};
 use search_tree::SearchTree;
 use selectors::{SequenceSelector, SequenceSelectorInput, TakeAllSelector};
-use std::{collections::HashSet, iter::FusedIterator, sync::Arc};
-use vecno_consensus_core::{block::TemplateTransactionSelector, tx::Transaction};
+use std::{
+    collections::{HashMap, HashSet},
+    iter::FusedIterator,
+    sync::Arc,
+};
+use vecno_consensus_core::{
+    block::TemplateTransactionSelector,
+    tx::{Transaction, TransactionId},
+};
 use vecno_core::trace;
 
 pub(crate) mod feerate_key;
@@ -38,6 +45,9 @@ pub struct Frontier {
     /// Frontier transactions sorted by feerate order and searchable for weight sampling
     search_tree: SearchTree,
 
+    /// The current key of every frontier transaction, required for re-keying a transaction whose package feerate changed
+    keys: HashMap<TransactionId, FeerateTransactionKey>,
+
     /// Total masses: Σ_{tx in frontier} tx.mass
     total_mass: u64,
 
@@ -47,7 +57,12 @@ pub struct Frontier {
 
 impl Default for Frontier {
     fn default() -> Self {
-        Self { search_tree: Default::default(), total_mass: Default::default(), average_transaction_mass: INITIAL_AVG_MASS }
+        Self {
+            search_tree: Default::default(),
+            keys: Default::default(),
+            total_mass: Default::default(),
+            average_transaction_mass: INITIAL_AVG_MASS,
+        }
     }
 }
 
@@ -70,7 +85,11 @@ impl Frontier {
 
     pub fn insert(&mut self, key: FeerateTransactionKey) -> bool {
         let mass = key.mass;
-        if self.search_tree.insert(key) {
+        if self.keys.contains_key(&key.tx.id()) {
+            return false;
+        }
+        if self.search_tree.insert(key.clone()) {
+            self.keys.insert(key.tx.id(), key);
             self.total_mass += mass;
             // A decaying average formula. Denote ɛ = 1 - AVG_MASS_DECAY_FACTOR. A transaction inserted N slots ago has
             // ɛ * (1 - ɛ)^N weight within the updated average. This gives some weight to the full mempool history while
@@ -86,6 +105,7 @@ impl Frontier {
     pub fn remove(&mut self, key: &FeerateTransactionKey) -> bool {
         let mass = key.mass;
         if self.search_tree.remove(key) {
+            self.keys.remove(&key.tx.id());
             self.total_mass -= mass;
             true
         } else {
@@ -93,6 +113,25 @@ impl Frontier {
         }
     }
 
+    /// Returns the current key of a frontier transaction
+    pub fn get(&self, transaction_id: &TransactionId) -> Option<&FeerateTransactionKey> {
+        self.keys.get(transaction_id)
+    }
+
+    /// Replaces the key of a frontier transaction, typically following a change of its package feerate.
+    /// Returns false if the transaction is not in the frontier.
+    pub fn update(&mut self, key: FeerateTransactionKey) -> bool {
+        let Some(current) = self.keys.get_mut(&key.tx.id()) else {
+            return false;
+        };
+        // The transaction itself, and thus its mass, is unchanged so the total mass and average mass stay as is
+        debug_assert_eq!(current.mass, key.mass);
+        self.search_tree.remove(current);
+        self.search_tree.insert(key.clone());
+        *current = key;
+        true
+    }
+
     /// Samples the frontier in-place based on the provided policy and returns a SequenceSelector.
     ///
     /// This sampling algorithm should be used when frontier total mass is high enough compared to
@@ -203,6 +242,81 @@ impl Frontier {
         }
     }
 
+    /// Builds a transaction selector following `template_policy`, where `prioritised_ids` are the transactions
+    /// prioritised in addition to those listed by the policy.
+    ///
+    /// The prioritised transactions are taken first, by descending feerate, up to the share of the block mass reserved
+    /// for them. The remaining transactions follow either by descending feerate or in a feerate-weighted random order,
+    /// depending on the selection mode. Excluded transactions are skipped altogether.
+    pub fn build_policy_selector(
+        &self,
+        policy: &Policy,
+        template_policy: &TemplatePolicy,
+        prioritised_ids: impl Iterator<Item = TransactionId>,
+    ) -> Box<dyn TemplateTransactionSelector> {
+        let is_excluded = |id: &TransactionId| template_policy.excluded_transaction_ids.contains(id);
+        let mut sequence = SequenceSelectorInput::default();
+        let mut selected = HashSet::new();
+
+        let reserved_mass = (policy.max_block_mass as f64 * template_policy.reserved_mass_share()) as u64;
+        let mut selected_mass: u64 = 0;
+        if reserved_mass > 0 {
+            let mut prioritised = template_policy
+                .prioritised_transaction_ids
+                .iter()
+                .copied()
+                .chain(prioritised_ids)
+                .filter(|id| !is_excluded(id))
+                .filter_map(|id| self.keys.get(&id))
+                .collect::<Vec<_>>();
+            prioritised.sort_unstable_by(|a, b| b.cmp(a));
+            for key in prioritised {
+                if selected_mass + key.mass > reserved_mass || !selected.insert(key.tx.id()) {
+                    continue;
+                }
+                selected_mass += key.mass;
+                sequence.push(key.tx.clone(), key.mass);
+            }
+        }
+
+        let is_remaining = |id: &TransactionId| !is_excluded(id) && !selected.contains(id);
+        match template_policy.selection_mode {
+            TemplateSelectionMode::HighestFeerate => {
+                // Take slightly more than the hard limit in order to allow the SequenceSelector to compensate for consensus rejections
+                let desired_mass = (policy.max_block_mass as f64 * MASS_LIMIT_FACTOR) as u64;
+                for key in self.search_tree.descending_iter().filter(|key| is_remaining(&key.tx.id())) {
+                    if selected_mass > desired_mass {
+                        break;
+                    }
+                    selected_mass += key.mass;
+                    sequence.push(key.tx.clone(), key.mass);
+                }
+            }
+            TemplateSelectionMode::Weighted => {
+                let mut rng = rand::thread_rng();
+                if self.total_mass > policy.max_block_mass * COLLISION_FACTOR {
+                    for item in self.sample_inplace(&mut rng, policy, &mut 0).iter().filter(|item| is_remaining(&item.tx.id())) {
+                        sequence.push(item.tx.clone(), item.mass);
+                    }
+                } else {
+                    // Weighted random permutation: ordering by `ln(u)/weight` with `u` uniform in (0, 1] amounts to repeatedly
+                    // sampling without replacement with probabilities proportional to the weights
+                    let mut keys = self
+                        .search_tree
+                        .ascending_iter()
+                        .filter(|key| is_remaining(&key.tx.id()))
+                        .map(|key| ((1.0 - rng.gen::<f64>()).ln() / key.weight(), key))
+                        .collect::<Vec<_>>();
+                    keys.sort_unstable_by(|(a, _), (b, _)| b.total_cmp(a));
+                    for (_, key) in keys {
+                        sequence.push(key.tx.clone(), key.mass);
+                    }
+                }
+            }
+        }
+        Box::new(SequenceSelector::new(sequence, policy.clone()))
+    }
+
     /// Exposed for benchmarking purposes
     pub fn build_selector_sample_inplace(&self, _collisions: &mut u64) -> Box<dyn TemplateTransactionSelector> {
         let mut rng = rand::thread_rng();
@@ -268,6 +382,35 @@ impl Frontier {
     pub fn ascending_iter(&self) -> impl DoubleEndedIterator<Item = &Arc<Transaction>> + ExactSizeIterator + FusedIterator {
         self.search_tree.ascending_iter().map(|key| &key.tx)
     }
+
+    /// Returns an iterator to the keys of the frontier in increasing feerate order
+    pub fn ascending_key_iter(&self) -> impl DoubleEndedIterator<Item = &FeerateTransactionKey> + ExactSizeIterator + FusedIterator {
+        self.search_tree.ascending_iter()
+    }
+
+    /// Checks that the search tree, the key index and the total mass agree with each other
+    pub(crate) fn check_invariants(&self) -> Result<(), String> {
+        if self.keys.len() != self.search_tree.len() {
+            return Err(format!("frontier indexes {} keys but its search tree holds {}", self.keys.len(), self.search_tree.len()));
+        }
+        let mut total_mass = 0;
+        for key in self.search_tree.ascending_iter() {
+            match self.keys.get(&key.tx.id()) {
+                Some(indexed)
+                    if indexed.fee == key.fee
+                        && indexed.mass == key.mass
+                        && indexed.package_fee == key.package_fee
+                        && indexed.package_mass == key.package_mass => {}
+                Some(_) => return Err(format!("frontier key of transaction {} differs from its indexed key", key.tx.id())),
+                None => return Err(format!("frontier transaction {} is missing from the key index", key.tx.id())),
+            }
+            total_mass += key.mass;
+        }
+        if total_mass != self.total_mass {
+            return Err(format!("frontier total mass is {} while its transactions weigh {}", self.total_mass, total_mass));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
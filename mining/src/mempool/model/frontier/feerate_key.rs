@@ -6,6 +6,12 @@ use vecno_consensus_core::tx::Transaction;
 pub struct FeerateTransactionKey {
     pub fee: u64,
     pub mass: u64,
+    /// Fee of the best paying package rooted at this transaction, that is, this transaction along with one of its
+    /// mempool descendants and all the mempool ancestors of the latter (child-pays-for-parent). Equals `fee` if no
+    /// such package pays a higher feerate than the transaction alone.
+    pub package_fee: u64,
+    /// Mass of the package whose fee is `package_fee`
+    pub package_mass: u64,
     weight: f64,
     pub tx: Arc<Transaction>,
 }
@@ -20,14 +26,19 @@ impl PartialEq for FeerateTransactionKey {
 
 impl FeerateTransactionKey {
     pub fn new(fee: u64, mass: u64, tx: Arc<Transaction>) -> Self {
+        Self::with_package(fee, mass, fee, mass, tx)
+    }
+
+    pub fn with_package(fee: u64, mass: u64, package_fee: u64, package_mass: u64, tx: Arc<Transaction>) -> Self {
         // NOTE: any change to the way this weight is calculated (such as scaling by some factor)
         // requires a reversed update to total_weight in `Frontier::build_feerate_estimator`. This
         // is because the math methods in FeeEstimator assume this specific weight function.
-        Self { fee, mass, weight: (fee as f64 / mass as f64).powi(ALPHA), tx }
+        Self { fee, mass, package_fee, package_mass, weight: (package_fee as f64 / package_mass as f64).powi(ALPHA), tx }
     }
 
+    /// Returns the feerate the transaction is ranked by, which is the feerate of its best paying package
     pub fn feerate(&self) -> f64 {
-        self.fee as f64 / self.mass as f64
+        self.package_fee as f64 / self.package_mass as f64
     }
 
     pub fn weight(&self) -> f64 {
@@ -59,14 +70,14 @@ impl Ord for FeerateTransactionKey {
         }
 
         // If feerates (and thus weights) are equal, prefer the higher fee in absolute value
-        match self.fee.cmp(&other.fee) {
+        match self.package_fee.cmp(&other.package_fee) {
             core::cmp::Ordering::Equal => {}
             ord => return ord,
         }
 
         //
-        // At this point we don't compare the mass fields since if both feerate
-        // and fee are equal, mass must be equal as well
+        // At this point we don't compare the mass fields since if both package feerate
+        // and package fee are equal, package mass must be equal as well
         //
 
         // Finally, we compare transaction ids in order to allow multiple transactions with
@@ -105,4 +116,14 @@ pub(crate) mod tests {
     pub(crate) fn build_feerate_key(fee: u64, mass: u64, id: u64) -> FeerateTransactionKey {
         FeerateTransactionKey::new(fee, mass, generate_unique_tx(id))
     }
+
+    #[test]
+    fn test_package_ordering() {
+        // A low feerate parent whose child pays for it ranks at the feerate of the package
+        let parent = FeerateTransactionKey::with_package(1_000, 2_000, 101_000, 4_000, generate_unique_tx(1));
+        let other = build_feerate_key(40_000, 2_000, 2);
+        assert_eq!(parent.feerate(), 101_000.0 / 4_000.0);
+        assert!(parent > other);
+        assert!(build_feerate_key(1_000, 2_000, 1) < other);
+    }
 }
@@ -6,7 +6,7 @@ use crate::mempool::{
         pool::{Pool, TransactionsEdges},
         tx::{MempoolTransaction, TxRemovalReason},
     },
-    tx::Priority,
+    tx::{Priority, RbfPolicy},
 };
 use std::sync::Arc;
 use vecno_consensus_core::{
@@ -59,11 +59,17 @@ impl OrphanPool {
         self.outpoint_owner_id.get(outpoint).and_then(|id| self.all_orphans.get_mut(id))
     }
 
+    /// Overrides the DAA score at which the orphan was added, returning false if it is not in the pool
+    pub(crate) fn set_added_at_daa_score(&mut self, transaction_id: &TransactionId, added_at_daa_score: u64) -> bool {
+        self.all_orphans.get_mut(transaction_id).map(|tx| tx.added_at_daa_score = added_at_daa_score).is_some()
+    }
+
     pub(crate) fn try_add_orphan(
         &mut self,
         virtual_daa_score: u64,
         transaction: MutableTransaction,
         priority: Priority,
+        rbf_policy: RbfPolicy,
     ) -> RuleResult<()> {
         // Rust rewrite: original name is maybeAddOrphan
         if self.config.maximum_orphan_transaction_count == 0 {
@@ -75,7 +81,24 @@ impl OrphanPool {
         self.check_orphan_double_spend(&transaction)?;
         // Make sure there is room in the pool for the new transaction
         self.limit_orphan_pool_size(1)?;
-        self.add_orphan(virtual_daa_score, transaction, priority)?;
+        self.add_orphan(virtual_daa_score, transaction, priority, rbf_policy)?;
+        Ok(())
+    }
+
+    /// Checks that `transaction` would be added to the orphan pool by [`Self::try_add_orphan`], without adding it
+    pub(crate) fn check_orphan(&self, transaction: &MutableTransaction) -> RuleResult<()> {
+        if self.config.maximum_orphan_transaction_count == 0 {
+            return Ok(());
+        }
+        self.check_orphan_duplicate(transaction)?;
+        self.check_orphan_mass(transaction)?;
+        self.check_orphan_double_spend(transaction)?;
+        // Room can be made in a full pool unless it is filled with high priority transactions
+        if self.all_orphans.len() >= self.config.maximum_orphan_transaction_count as usize
+            && self.get_random_low_priority_orphan().is_none()
+        {
+            return Err(RuleError::RejectOrphanPoolIsFull(self.all_orphans.len(), self.config.maximum_orphan_transaction_count));
+        }
         Ok(())
     }
 
@@ -126,9 +149,15 @@ impl OrphanPool {
         Ok(())
     }
 
-    fn add_orphan(&mut self, virtual_daa_score: u64, transaction: MutableTransaction, priority: Priority) -> RuleResult<()> {
+    fn add_orphan(
+        &mut self,
+        virtual_daa_score: u64,
+        transaction: MutableTransaction,
+        priority: Priority,
+        rbf_policy: RbfPolicy,
+    ) -> RuleResult<()> {
         let id = transaction.id();
-        let transaction = MempoolTransaction::new(transaction, priority, virtual_daa_score);
+        let transaction = MempoolTransaction::new(transaction, priority, rbf_policy, virtual_daa_score);
         // Add all entries in outpoint_owner_id
         for input in transaction.mtx.tx.inputs.iter() {
             self.outpoint_owner_id.insert(input.previous_outpoint, id);
@@ -264,9 +293,10 @@ impl OrphanPool {
         &mut self.chained_orphans
     }
 
-    pub(crate) fn expire_low_priority_transactions(&mut self, virtual_daa_score: u64) -> RuleResult<()> {
+    /// Removes the expired low priority orphans and returns them
+    pub(crate) fn expire_low_priority_transactions(&mut self, virtual_daa_score: u64) -> RuleResult<Vec<MempoolTransaction>> {
         if virtual_daa_score < self.last_expire_scan + self.config.orphan_expire_scan_interval_daa_score {
-            return Ok(());
+            return Ok(vec![]);
         }
 
         // Never expire high priority transactions
@@ -285,11 +315,51 @@ impl OrphanPool {
             })
             .collect();
 
+        let mut expired_transactions = Vec::with_capacity(expired_low_priority_transactions.len());
         for transaction_id in expired_low_priority_transactions.iter() {
-            self.remove_orphan(transaction_id, false, TxRemovalReason::Expired, "")?;
+            expired_transactions.extend(self.remove_orphan(transaction_id, false, TxRemovalReason::Expired, "")?);
         }
 
         self.last_expire_scan = virtual_daa_score;
+        Ok(expired_transactions)
+    }
+
+    /// Checks the consistency of the outpoint index and chained relations of the pool with its orphans
+    pub(crate) fn check_invariants(&self) -> Result<(), String> {
+        let mut spent_outpoint_count = 0;
+        for (id, orphan) in self.all_orphans.iter() {
+            for input in orphan.mtx.tx.inputs.iter() {
+                if self.outpoint_owner_id.get(&input.previous_outpoint) != Some(id) {
+                    return Err(format!("outpoint {} spent by orphan {} is not owned by it", input.previous_outpoint, id));
+                }
+                spent_outpoint_count += 1;
+            }
+            if let Some(parent_id) = self
+                .get_parent_transaction_ids_in_pool(&orphan.mtx)
+                .iter()
+                .find(|parent_id| !self.chained_orphans.get(parent_id).is_some_and(|x| x.contains(id)))
+            {
+                return Err(format!("orphan {id} is not chained to its parent orphan {parent_id}"));
+            }
+        }
+        if spent_outpoint_count != self.outpoint_owner_id.len() {
+            return Err(format!(
+                "{} outpoints are owned while the orphans spend {}",
+                self.outpoint_owner_id.len(),
+                spent_outpoint_count
+            ));
+        }
+        for (id, chains) in self.chained_orphans.iter() {
+            for chain_id in chains.iter() {
+                let is_chained = self
+                    .all_orphans
+                    .get(chain_id)
+                    .is_some_and(|chained| chained.mtx.tx.inputs.iter().any(|input| input.previous_outpoint.transaction_id == *id));
+                if !self.all_orphans.contains_key(id) || !is_chained {
+                    return Err(format!("orphan {chain_id} chained to {id} does not spend any of its outputs"));
+                }
+            }
+        }
         Ok(())
     }
 }
@@ -9,13 +9,13 @@ use crate::{
             tx::{DoubleSpend, MempoolTransaction},
             utxo_set::MempoolUtxoSet,
         },
-        tx::Priority,
+        tx::{Priority, RbfPolicy},
     },
     model::{topological_index::TopologicalIndex, TransactionIdSet},
-    Policy,
+    Policy, TemplatePolicy,
 };
 use std::{
-    collections::{hash_map::Keys, hash_set::Iter},
+    collections::{hash_map::Keys, hash_set::Iter, HashMap, VecDeque},
     iter::once,
     sync::Arc,
 };
@@ -25,7 +25,12 @@ use vecno_consensus_core::{
 };
 use vecno_core::{debug, time::unix_now, trace};
 
-use super::frontier::Frontier;
+use super::frontier::{feerate_key::FeerateTransactionKey, Frontier};
+
+/// Maximum number of transactions of a package, that is of a transaction along with all its mempool ancestors, or of
+/// the mempool descendants of a ready transaction, considered when computing package feerates. This bounds the cost
+/// of keeping the frontier keys up to date in the presence of deeply chained transactions.
+const MAX_PACKAGE_SIZE: usize = 25;
 
 /// Pool of transactions to be included in a block template
 ///
@@ -77,6 +82,10 @@ pub(crate) struct TransactionsPool {
 
     /// Store of UTXOs
     utxo_set: MempoolUtxoSet,
+
+    /// Adjustments of the fee transactions are ranked by in the frontier, set by the operator. Deltas of transactions
+    /// not in the pool are kept so that they apply once the transaction enters it.
+    fee_deltas: HashMap<TransactionId, i64>,
 }
 
 impl TransactionsPool {
@@ -91,6 +100,7 @@ impl TransactionsPool {
             last_expire_scan_time: unix_now(),
             utxo_set: MempoolUtxoSet::new(),
             estimated_size: 0,
+            fee_deltas: HashMap::new(),
         }
     }
 
@@ -100,9 +110,10 @@ impl TransactionsPool {
         transaction: MutableTransaction,
         virtual_daa_score: u64,
         priority: Priority,
+        rbf_policy: RbfPolicy,
         transaction_size: usize,
     ) -> RuleResult<&MempoolTransaction> {
-        let transaction = MempoolTransaction::new(transaction, priority, virtual_daa_score);
+        let transaction = MempoolTransaction::new(transaction, priority, rbf_policy, virtual_daa_score);
         let id = transaction.id();
         self.add_mempool_transaction(transaction, transaction_size)?;
         Ok(self.get(&id).unwrap())
@@ -120,9 +131,10 @@ impl TransactionsPool {
         // The transactions chained to the added transaction cannot be stored
         // here yet since, by definition, they would have been orphans.
         let parents = self.get_parent_transaction_ids_in_pool(&transaction.mtx);
+        let is_ready = parents.is_empty();
         self.parent_transactions.insert(id, parents.clone());
-        if parents.is_empty() {
-            self.ready_transactions.insert((&transaction).into());
+        if is_ready {
+            self.ready_transactions.insert(self.build_key(&transaction));
         }
         for parent_id in parents {
             let entry = self.chained_transactions.entry(parent_id).or_default();
@@ -132,12 +144,23 @@ impl TransactionsPool {
         self.utxo_set.add_transaction(&transaction.mtx);
         self.estimated_size += transaction_size;
         self.all_transactions.insert(id, transaction);
+
+        // The new transaction may raise the package feerate of its ready ancestors
+        if !is_ready {
+            let ready_ancestors = self.get_ready_ancestor_ids(&id);
+            self.update_package_keys(ready_ancestors);
+        }
         trace!("Added transaction {}", id);
         Ok(())
     }
 
     /// Fully removes the transaction from all relational sets, as well as from the UTXO set
     pub(crate) fn remove_transaction(&mut self, transaction_id: &TransactionId) -> RuleResult<MempoolTransaction> {
+        // Collect the transactions whose packages include the removed transaction before the relations are lost
+        let mut package_members = self.get_ancestor_ids_in_pool(transaction_id).unwrap_or_default();
+        package_members.extend(self.get_descendant_ids_in_pool(transaction_id));
+        package_members.remove(transaction_id);
+
         // Remove all bijective parent/chained relations
         if let Some(parents) = self.parent_transactions.get(transaction_id) {
             for parent in parents.iter() {
@@ -146,24 +169,35 @@ impl TransactionsPool {
                 }
             }
         }
+        let mut newly_ready = vec![];
         if let Some(chains) = self.chained_transactions.get(transaction_id) {
             for chain in chains.iter() {
                 if let Some(parents) = self.parent_transactions.get_mut(chain) {
                     parents.remove(transaction_id);
                     if parents.is_empty() {
-                        let tx = self.all_transactions.get(chain).unwrap();
-                        self.ready_transactions.insert(tx.into());
+                        newly_ready.push(*chain);
                     }
                 }
             }
         }
+        for id in newly_ready {
+            let key = self.build_key(self.all_transactions.get(&id).unwrap());
+            self.ready_transactions.insert(key);
+        }
         self.parent_transactions.remove(transaction_id);
         self.chained_transactions.remove(transaction_id);
 
         // Remove the transaction itself
         let removed_tx = self.all_transactions.remove(transaction_id).ok_or(RuleError::RejectMissingTransaction(*transaction_id))?;
 
-        self.ready_transactions.remove(&(&removed_tx).into());
+        if let Some(key) = self.ready_transactions.get(transaction_id).cloned() {
+            self.ready_transactions.remove(&key);
+        }
+
+        // Recompute the package feerates no longer accounting for the removed transaction
+        let affected_ready_transactions =
+            package_members.iter().flat_map(|member| self.get_ready_ancestor_ids(member)).collect::<TransactionIdSet>();
+        self.update_package_keys(affected_ready_transactions);
 
         // TODO: consider using `self.parent_transactions.get(transaction_id)`
         // The tradeoff to consider is whether it might be possible that a parent tx exists in the pool
@@ -182,6 +216,155 @@ impl TransactionsPool {
         Ok(removed_tx)
     }
 
+    /// Returns the ids of the in-pool ancestors of `transaction_id`, the transaction itself included, or `None` if they
+    /// exceed [`MAX_PACKAGE_SIZE`]
+    fn get_ancestor_ids_in_pool(&self, transaction_id: &TransactionId) -> Option<TransactionIdSet> {
+        let mut ancestors = TransactionIdSet::from_iter(once(*transaction_id));
+        let mut queue = VecDeque::from_iter(once(*transaction_id));
+        while let Some(id) = queue.pop_front() {
+            for parent_id in self.parent_transactions.get(&id).into_iter().flatten() {
+                if ancestors.insert(*parent_id) {
+                    if ancestors.len() > MAX_PACKAGE_SIZE {
+                        return None;
+                    }
+                    queue.push_back(*parent_id);
+                }
+            }
+        }
+        Some(ancestors)
+    }
+
+    /// Returns the ids of at most [`MAX_PACKAGE_SIZE`] in-pool descendants of `transaction_id`, closest first
+    fn get_descendant_ids_in_pool(&self, transaction_id: &TransactionId) -> Vec<TransactionId> {
+        let mut visited = TransactionIdSet::new();
+        let mut descendants = vec![];
+        let mut queue = VecDeque::from_iter(once(*transaction_id));
+        while let Some(id) = queue.pop_front() {
+            for chain_id in self.chained_transactions.get(&id).into_iter().flatten() {
+                if visited.insert(*chain_id) {
+                    if descendants.len() == MAX_PACKAGE_SIZE {
+                        return descendants;
+                    }
+                    descendants.push(*chain_id);
+                    queue.push_back(*chain_id);
+                }
+            }
+        }
+        descendants
+    }
+
+    /// Returns the ids of the ready transactions among the in-pool ancestors of `transaction_id`, the transaction itself included
+    fn get_ready_ancestor_ids(&self, transaction_id: &TransactionId) -> Vec<TransactionId> {
+        self.get_ancestor_ids_in_pool(transaction_id)
+            .into_iter()
+            .flatten()
+            .filter(|id| self.parent_transactions.get(id).is_some_and(|parents| parents.is_empty()))
+            .collect()
+    }
+
+    /// Returns the fee and mass of the package made of `transaction_id` and all its in-pool ancestors, or `None` if the
+    /// package exceeds [`MAX_PACKAGE_SIZE`]
+    fn get_ancestor_package(&self, transaction_id: &TransactionId) -> Option<(u64, u64)> {
+        let ancestors = self.get_ancestor_ids_in_pool(transaction_id)?;
+        Some(
+            ancestors
+                .iter()
+                .filter_map(|id| self.all_transactions.get(id))
+                .fold((0, 0), |(fee, mass), tx| (fee + self.effective_fee(tx), mass + tx.mtx.tx.mass())),
+        )
+    }
+
+    /// Returns the fee of `transaction` adjusted by its fee delta, if any
+    fn effective_fee(&self, transaction: &MempoolTransaction) -> u64 {
+        let fee = transaction.mtx.calculated_fee.expect("fee is expected to be populated");
+        match self.fee_deltas.get(&transaction.id()) {
+            Some(&delta) => fee.saturating_add_signed(delta),
+            None => fee,
+        }
+    }
+
+    /// Builds the frontier key of `transaction` alone, ranked by its effective fee
+    fn build_key(&self, transaction: &MempoolTransaction) -> FeerateTransactionKey {
+        let key: FeerateTransactionKey = transaction.into();
+        FeerateTransactionKey::new(self.effective_fee(transaction), key.mass, key.tx)
+    }
+
+    /// Builds the frontier key of the ready transaction `transaction_id`, ranked by the highest feerate among the
+    /// packages made of one of its descendants, or itself, along with all the ancestors of the latter.
+    ///
+    /// Note that consensus rejects blocks with chained transactions, so the package as a whole is not included in a
+    /// single block. The ready transaction gets selected at the package feerate and its descendants follow in the
+    /// next blocks, each becoming ready once its parents are accepted.
+    fn build_package_key(&self, transaction_id: &TransactionId) -> FeerateTransactionKey {
+        let key = self.build_key(self.all_transactions.get(transaction_id).expect("the transaction is in the pool"));
+        let (package_fee, package_mass) = self
+            .get_descendant_ids_in_pool(transaction_id)
+            .iter()
+            .filter_map(|redeemer_id| self.get_ancestor_package(redeemer_id))
+            // Compare feerates with exact integer arithmetic
+            .fold((key.fee, key.mass), |(best_fee, best_mass), (fee, mass)| {
+                if fee as u128 * best_mass as u128 > best_fee as u128 * mass as u128 {
+                    (fee, mass)
+                } else {
+                    (best_fee, best_mass)
+                }
+            });
+        FeerateTransactionKey::with_package(key.fee, key.mass, package_fee, package_mass, key.tx)
+    }
+
+    /// Refreshes the frontier keys of the given ready transactions so that they reflect their current package feerate
+    fn update_package_keys(&mut self, ready_transaction_ids: impl IntoIterator<Item = TransactionId>) {
+        for id in ready_transaction_ids {
+            if !self.all_transactions.contains_key(&id) {
+                continue;
+            }
+            let key = self.build_package_key(&id);
+            match self.ready_transactions.get(&id) {
+                Some(current)
+                    if current.fee == key.fee
+                        && current.package_fee == key.package_fee
+                        && current.package_mass == key.package_mass => {}
+                Some(_) => {
+                    self.ready_transactions.update(key);
+                }
+                None => {
+                    self.ready_transactions.insert(key);
+                }
+            }
+        }
+    }
+
+    /// Adds `fee_delta` to the fee delta of `transaction_id`, changing the fee it is ranked by in the frontier whether
+    /// it is in the pool already or enters it later, and returns the resulting delta
+    pub(crate) fn prioritise_transaction(&mut self, transaction_id: TransactionId, fee_delta: i64) -> i64 {
+        let total = self.fee_deltas.get(&transaction_id).copied().unwrap_or_default().saturating_add(fee_delta);
+        if total == 0 {
+            self.fee_deltas.remove(&transaction_id);
+        } else {
+            self.fee_deltas.insert(transaction_id, total);
+        }
+        if self.all_transactions.contains_key(&transaction_id) {
+            // The delta changes the packages of the transaction and of all its descendants, which in turn rank every
+            // ready ancestor of theirs, including ready transactions that are not ancestors of `transaction_id`
+            let affected_ready_transactions = once(transaction_id)
+                .chain(self.get_descendant_ids_in_pool(&transaction_id))
+                .flat_map(|member| self.get_ready_ancestor_ids(&member))
+                .collect::<TransactionIdSet>();
+            self.update_package_keys(affected_ready_transactions);
+        }
+        total
+    }
+
+    /// Drops the fee delta of `transaction_id`, typically once the transaction got accepted
+    pub(crate) fn remove_fee_delta(&mut self, transaction_id: &TransactionId) {
+        self.fee_deltas.remove(transaction_id);
+    }
+
+    /// Overrides the DAA score at which the transaction was added, returning false if it is not in the pool
+    pub(crate) fn set_added_at_daa_score(&mut self, transaction_id: &TransactionId, added_at_daa_score: u64) -> bool {
+        self.all_transactions.get_mut(transaction_id).map(|tx| tx.added_at_daa_score = added_at_daa_score).is_some()
+    }
+
     pub(crate) fn update_revalidated_transaction(&mut self, transaction: MutableTransaction) -> bool {
         if let Some(tx) = self.all_transactions.get_mut(&transaction.id()) {
             // Make sure to update the overall estimated size since the updated transaction might have a different size
@@ -203,8 +386,14 @@ impl TransactionsPool {
     }
 
     /// Dynamically builds a transaction selector based on the specific state of the ready transactions frontier
-    pub(crate) fn build_selector(&self) -> Box<dyn TemplateTransactionSelector> {
-        self.ready_transactions.build_selector(&Policy::new(self.config.maximum_mass_per_block))
+    pub(crate) fn build_selector(&self, template_policy: &TemplatePolicy) -> Box<dyn TemplateTransactionSelector> {
+        let policy = Policy::new(self.config.maximum_mass_per_block);
+        if template_policy.is_default() {
+            self.ready_transactions.build_selector(&policy)
+        } else {
+            let prioritised_ids = self.fee_deltas.iter().filter(|(_, delta)| **delta > 0).map(|(id, _)| *id);
+            self.ready_transactions.build_policy_selector(&policy, template_policy, prioritised_ids)
+        }
     }
 
     /// Builds a feerate estimator based on internal state of the ready transactions frontier
@@ -223,42 +412,58 @@ impl TransactionsPool {
         &self,
         transaction: &MutableTransaction,
         transaction_size: usize,
+    ) -> RuleResult<Vec<TransactionId>> {
+        self.limit_package_transaction_count(&[transaction], transaction.calculated_feerate().unwrap(), transaction_size)
+    }
+
+    /// Returns the exceeding low-priority transactions having the lowest fee rates in order
+    /// to make room for all the `transactions` of a package, judged by their aggregate `feerate_threshold`
+    /// and occupying `package_size` bytes. The returned transactions are guaranteed to be unchained
+    /// (no successor in mempool) and to not be parent of any of `transactions`.
+    ///
+    /// An error is returned if the mempool is filled with high priority transactions, or
+    /// there are not enough lower feerate transactions that can be removed to accommodate `transactions`
+    pub(crate) fn limit_package_transaction_count(
+        &self,
+        transactions: &[&MutableTransaction],
+        feerate_threshold: f64,
+        package_size: usize,
     ) -> RuleResult<Vec<TransactionId>> {
         // No eviction needed -- return
-        if self.len() < self.config.maximum_transaction_count
-            && self.estimated_size + transaction_size <= self.config.mempool_size_limit
+        if self.len() + transactions.len() <= self.config.maximum_transaction_count
+            && self.estimated_size + package_size <= self.config.mempool_size_limit
         {
             return Ok(Default::default());
         }
 
         // Returns a vector of transactions to be removed (the caller has to actually remove)
-        let feerate_threshold = transaction.calculated_feerate().unwrap();
-        let mut txs_to_remove = Vec::with_capacity(1); // Normally we expect a single removal
+        let mut txs_to_remove = Vec::with_capacity(transactions.len()); // Normally we expect a single removal per transaction
         let mut selection_overall_size = 0;
-        for tx in self
+        for (key, tx) in self
             .ready_transactions
-            .ascending_iter()
-            .map(|tx| self.all_transactions.get(&tx.id()).unwrap())
-            .filter(|mtx| mtx.priority == Priority::Low)
+            .ascending_key_iter()
+            .map(|key| (key, self.all_transactions.get(&key.tx.id()).unwrap()))
+            .filter(|(_, mtx)| mtx.priority == Priority::Low)
         {
             // TODO (optimization): inline the `has_parent_in_set` check within the redeemer traversal and exit early if possible
             let redeemers = self.get_redeemer_ids_in_pool(&tx.id()).into_iter().chain(once(tx.id())).collect::<TransactionIdSet>();
-            if transaction.has_parent_in_set(&redeemers) {
+            if transactions.iter().any(|transaction| transaction.has_parent_in_set(&redeemers)) {
                 continue;
             }
 
-            // We are iterating ready txs by ascending feerate so the pending tx has lower feerate than all remaining txs
-            if tx.fee_rate() > feerate_threshold {
+            // We are iterating ready txs by ascending package feerate so the pending tx has lower feerate than all remaining txs.
+            // A ready tx is compared by its package feerate so that it is not evicted while a descendant pays for it.
+            if key.feerate() > feerate_threshold {
                 let err = RuleError::RejectMempoolIsFull;
-                debug!("Transaction {} with feerate {} has been rejected: {}", transaction.id(), feerate_threshold, err);
+                debug!("Transaction {} with feerate {} has been rejected: {}", transactions[0].id(), feerate_threshold, err);
                 return Err(err);
             }
 
             txs_to_remove.push(tx.id());
             selection_overall_size += tx.mtx.mempool_estimated_bytes();
 
-            if self.len() + 1 - txs_to_remove.len() <= self.config.maximum_transaction_count
-                && self.estimated_size + transaction_size - selection_overall_size <= self.config.mempool_size_limit
+            if self.len() + transactions.len() - txs_to_remove.len() <= self.config.maximum_transaction_count
+                && self.estimated_size + package_size - selection_overall_size <= self.config.mempool_size_limit
             {
                 return Ok(txs_to_remove);
             }
@@ -269,9 +474,9 @@ impl TransactionsPool {
             "Mempool is filled with high-priority/ancestor txs (count: {}, bytes: {}). Transaction {} with feerate {} and size {} has been rejected: {}",
             self.len(),
             self.estimated_size,
-            transaction.id(),
+            transactions[0].id(),
             feerate_threshold,
-            transaction_size,
+            package_size,
             RuleError::RejectMempoolIsFull
         );
         Err(RuleError::RejectMempoolIsFull)
@@ -281,6 +486,71 @@ impl TransactionsPool {
         self.estimated_size
     }
 
+    /// Checks the consistency of the relations, frontier, estimated size and UTXO set of the pool with its transactions.
+    ///
+    /// The package part of the frontier keys is only checked for transactions whose packages fit within
+    /// [`MAX_PACKAGE_SIZE`], beyond which it is maintained on a best effort basis.
+    pub(crate) fn check_invariants(&self) -> Result<(), String> {
+        for (id, transaction) in self.all_transactions.iter() {
+            let parents = self.parent_transactions.get(id).ok_or_else(|| format!("transaction {id} has no parent relation"))?;
+            let parents_in_pool = self.get_parent_transaction_ids_in_pool(&transaction.mtx);
+            if let Some(parent_id) = parents.iter().find(|parent_id| !parents_in_pool.contains(parent_id)) {
+                return Err(format!("transaction {id} has parent {parent_id} which is not an in-pool parent"));
+            }
+            if let Some(parent_id) =
+                parents.iter().find(|parent_id| !self.chained_transactions.get(parent_id).is_some_and(|x| x.contains(id)))
+            {
+                return Err(format!("transaction {id} is not chained to its parent {parent_id}"));
+            }
+
+            match self.ready_transactions.get(id) {
+                Some(_) if !parents.is_empty() => return Err(format!("transaction {id} is in the frontier while having parents")),
+                None if parents.is_empty() => return Err(format!("transaction {id} has no parents but is not in the frontier")),
+                Some(key) => {
+                    if key.fee != self.effective_fee(transaction) || key.mass != transaction.mtx.tx.mass() {
+                        return Err(format!("frontier key of transaction {id} does not match its fee and mass"));
+                    }
+                    let descendants = self.get_descendant_ids_in_pool(id);
+                    if descendants.len() < MAX_PACKAGE_SIZE && descendants.iter().all(|x| self.get_ancestor_ids_in_pool(x).is_some()) {
+                        let package_key = self.build_package_key(id);
+                        if key.package_fee != package_key.package_fee || key.package_mass != package_key.package_mass {
+                            return Err(format!("frontier key of transaction {id} does not match its package"));
+                        }
+                    }
+                }
+                None => {}
+            }
+        }
+        if let Some(id) = self.parent_transactions.keys().find(|id| !self.all_transactions.contains_key(id)) {
+            return Err(format!("parent relation of transaction {id} outlived the transaction"));
+        }
+        for (id, chains) in self.chained_transactions.iter() {
+            if !self.all_transactions.contains_key(id) {
+                return Err(format!("chained relation of transaction {id} outlived the transaction"));
+            }
+            if let Some(chain_id) =
+                chains.iter().find(|chain_id| !self.parent_transactions.get(chain_id).is_some_and(|x| x.contains(id)))
+            {
+                return Err(format!("transaction {chain_id} chained to {id} does not have it as a parent"));
+            }
+        }
+        if let Some(key) = self.ready_transactions.ascending_key_iter().find(|key| !self.all_transactions.contains_key(&key.tx.id())) {
+            return Err(format!("frontier transaction {} is not in the pool", key.tx.id()));
+        }
+        self.ready_transactions.check_invariants()?;
+
+        let estimated_size = self.all_transactions.values().map(|x| x.mtx.mempool_estimated_bytes()).sum::<usize>();
+        if estimated_size != self.estimated_size {
+            return Err(format!("estimated size is {} while the transactions occupy {}", self.estimated_size, estimated_size));
+        }
+        self.utxo_set.check_invariants(&self.all_transactions)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn get_ready_transaction_feerate(&self, transaction_id: &TransactionId) -> Option<f64> {
+        self.ready_transactions.get(transaction_id).map(|key| key.feerate())
+    }
+
     pub(crate) fn all_transaction_ids_with_priority(&self, priority: Priority) -> Vec<TransactionId> {
         self.all().values().filter_map(|x| if x.priority == priority { Some(x.id()) } else { None }).collect()
     }
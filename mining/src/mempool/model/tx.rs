@@ -4,18 +4,23 @@ use std::{
     sync::Arc,
 };
 use vecno_consensus_core::tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint};
+use vecno_consensus_notify::notification::MempoolRemovalReason;
+use vecno_core::time::unix_now;
 use vecno_mining_errors::mempool::RuleError;
 
 pub(crate) struct MempoolTransaction {
     pub(crate) mtx: MutableTransaction,
     pub(crate) priority: Priority,
+    pub(crate) rbf_policy: RbfPolicy,
     pub(crate) added_at_daa_score: u64,
+    /// Unix time in milliseconds at which the transaction was inserted into the pool
+    pub(crate) added_at_time: u64,
 }
 
 impl MempoolTransaction {
-    pub(crate) fn new(mtx: MutableTransaction, priority: Priority, added_at_daa_score: u64) -> Self {
+    pub(crate) fn new(mtx: MutableTransaction, priority: Priority, rbf_policy: RbfPolicy, added_at_daa_score: u64) -> Self {
         assert_eq!(mtx.tx.inputs.len(), mtx.entries.len());
-        Self { mtx, priority, added_at_daa_score }
+        Self { mtx, priority, rbf_policy, added_at_daa_score, added_at_time: unix_now() }
     }
 
     pub(crate) fn id(&self) -> TransactionId {
@@ -30,7 +35,6 @@ impl MempoolTransaction {
 }
 
 impl RbfPolicy {
-    #[cfg(test)]
     /// Returns an alternate policy accepting a transaction insertion in case the policy requires a replacement
     pub(crate) fn for_insert(&self) -> RbfPolicy {
         match self {
@@ -74,7 +78,14 @@ pub(crate) struct TransactionPostValidation {
     pub accepted: Option<Arc<Transaction>>,
 }
 
-#[derive(PartialEq, Eq)]
+/// Outcome of the post validation of a transaction tested for acceptance but not inserted into the mempool
+#[derive(Default)]
+pub(crate) struct TransactionPostValidationTest {
+    pub is_orphan: bool,
+    pub replaced: Option<TransactionId>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub(crate) enum TxRemovalReason {
     Muted,
     Accepted,
@@ -105,6 +116,20 @@ impl TxRemovalReason {
     pub(crate) fn verbose(&self) -> bool {
         !matches!(self, TxRemovalReason::Muted)
     }
+
+    /// Returns the reason reported to the subscribers of mempool removal notifications or `None`
+    /// if the removal is not worth notifying, typically because the transaction made it into the DAG
+    pub(crate) fn notification_reason(&self) -> Option<MempoolRemovalReason> {
+        match self {
+            TxRemovalReason::Muted | TxRemovalReason::Accepted | TxRemovalReason::Unorphaned => None,
+            TxRemovalReason::MakingRoom => Some(MempoolRemovalReason::Evicted),
+            TxRemovalReason::Expired => Some(MempoolRemovalReason::Expired),
+            TxRemovalReason::DoubleSpend => Some(MempoolRemovalReason::DoubleSpend),
+            TxRemovalReason::InvalidInBlockTemplate => Some(MempoolRemovalReason::InvalidInBlockTemplate),
+            TxRemovalReason::RevalidationWithMissingOutpoints => Some(MempoolRemovalReason::MissingOutpoints),
+            TxRemovalReason::ReplacedByFee => Some(MempoolRemovalReason::ReplacedByFee),
+        }
+    }
 }
 
 impl Display for TxRemovalReason {
@@ -3,7 +3,10 @@ use std::collections::HashSet;
 use crate::{
     mempool::{
         errors::RuleResult,
-        model::{map::OutpointIndex, tx::DoubleSpend},
+        model::{
+            map::{MempoolTransactionCollection, OutpointIndex},
+            tx::DoubleSpend,
+        },
     },
     model::TransactionIdSet,
 };
@@ -25,15 +28,12 @@ impl MempoolUtxoSet {
 
     pub(crate) fn add_transaction(&mut self, transaction: &MutableTransaction) {
         let transaction_id = transaction.id();
-        let mut outpoint = TransactionOutpoint::new(transaction_id, 0);
-
-        for (i, input) in transaction.tx.inputs.iter().enumerate() {
-            outpoint.index = i as u32;
 
+        for input in transaction.tx.inputs.iter() {
             // Delete the output this input spends, in case it was created by mempool.
             // If the outpoint doesn't exist in self.pool_unspent_outputs - this means
             // it was created in the DAG (a.k.a. in consensus).
-            self.pool_unspent_outputs.remove(&outpoint);
+            self.pool_unspent_outputs.remove(&input.previous_outpoint);
 
             self.outpoint_owner_id.insert(input.previous_outpoint, transaction_id);
         }
@@ -66,6 +66,52 @@ impl MempoolUtxoSet {
         }
     }
 
+    /// Checks that the set holds exactly the outpoints spent by `transactions` and the outputs they create which are
+    /// not spent by one another
+    pub(crate) fn check_invariants(&self, transactions: &MempoolTransactionCollection) -> Result<(), String> {
+        let mut spent_outpoint_count = 0;
+        for (id, transaction) in transactions.iter() {
+            for input in transaction.mtx.tx.inputs.iter() {
+                match self.outpoint_owner_id.get(&input.previous_outpoint) {
+                    Some(owner_id) if owner_id == id => {}
+                    Some(owner_id) => {
+                        return Err(format!("outpoint {} spent by {} is owned by {}", input.previous_outpoint, id, owner_id))
+                    }
+                    None => return Err(format!("outpoint {} spent by {} has no owner", input.previous_outpoint, id)),
+                }
+                spent_outpoint_count += 1;
+            }
+        }
+        if spent_outpoint_count != self.outpoint_owner_id.len() {
+            return Err(format!(
+                "{} outpoints are owned while the transactions spend {}",
+                self.outpoint_owner_id.len(),
+                spent_outpoint_count
+            ));
+        }
+
+        let mut unspent_output_count = 0;
+        for (id, transaction) in transactions.iter() {
+            for i in 0..transaction.mtx.tx.outputs.len() {
+                let outpoint = TransactionOutpoint::new(*id, i as u32);
+                match (self.outpoint_owner_id.contains_key(&outpoint), self.pool_unspent_outputs.contains_key(&outpoint)) {
+                    (true, true) => return Err(format!("spent output {} is listed as unspent", outpoint)),
+                    (false, false) => return Err(format!("unspent output {} is not listed as such", outpoint)),
+                    (true, false) => {}
+                    (false, true) => unspent_output_count += 1,
+                }
+            }
+        }
+        if unspent_output_count != self.pool_unspent_outputs.len() {
+            return Err(format!(
+                "{} outputs are listed as unspent while the transactions leave {}",
+                self.pool_unspent_outputs.len(),
+                unspent_output_count
+            ));
+        }
+        Ok(())
+    }
+
     pub(crate) fn get_outpoint_owner_id(&self, outpoint: &TransactionOutpoint) -> Option<&TransactionId> {
         self.outpoint_owner_id.get(outpoint)
     }
@@ -0,0 +1,53 @@
+use crate::{
+    feerate::history::BlockFeerateRecord,
+    mempool::{
+        model::{pool::Pool, tx::MempoolTransaction},
+        Mempool,
+    },
+    model::persisted_tx::PersistedTransaction,
+};
+use std::collections::HashMap;
+use vecno_consensus_core::tx::TransactionId;
+
+impl Mempool {
+    /// Returns the transactions of both the transaction pool and the orphan pool along with the context of their insertion
+    pub(crate) fn get_persisted_transactions(&self) -> Vec<PersistedTransaction> {
+        let persist = |transaction: &MempoolTransaction, is_orphan: bool| {
+            PersistedTransaction::new(
+                (*transaction.mtx.tx).clone(),
+                transaction.priority,
+                transaction.rbf_policy,
+                transaction.added_at_daa_score,
+                is_orphan,
+            )
+        };
+        self.transaction_pool
+            .all()
+            .values()
+            .map(|transaction| persist(transaction, false))
+            .chain(self.orphan_pool.all().values().map(|transaction| persist(transaction, true)))
+            .collect()
+    }
+
+    /// Sets back the DAA score at which restored transactions were originally added, so that they expire
+    /// as if the node had not been restarted.
+    ///
+    /// Returns the number of transactions found in either pool.
+    pub(crate) fn restore_added_at_daa_scores(&mut self, added_at_daa_scores: &HashMap<TransactionId, u64>) -> usize {
+        added_at_daa_scores
+            .iter()
+            .filter(|(transaction_id, added_at_daa_score)| {
+                self.transaction_pool.set_added_at_daa_score(transaction_id, **added_at_daa_score)
+                    || self.orphan_pool.set_added_at_daa_score(transaction_id, **added_at_daa_score)
+            })
+            .count()
+    }
+
+    pub(crate) fn get_feerate_history_records(&self) -> Vec<BlockFeerateRecord> {
+        self.feerate_history.records().cloned().collect()
+    }
+
+    pub(crate) fn restore_feerate_history(&mut self, records: Vec<BlockFeerateRecord>, now: u64) {
+        self.feerate_history.restore(records, now);
+    }
+}
@@ -0,0 +1,198 @@
+use serde::Deserialize;
+use std::collections::HashSet;
+use vecno_addresses::Address;
+use vecno_consensus_core::tx::{MutableTransaction, ScriptPublicKey};
+use vecno_mining_errors::mempool::{PolicyError, PolicyResult};
+use vecno_txscript::pay_to_address_script;
+
+/// A node-specific policy consulted by the mempool when admitting a transaction.
+///
+/// The policy is applied on top of the standardness rules of the mempool and regardless of
+/// [`accept_non_standard`](super::config::Config::accept_non_standard), with the exception of
+/// [`Self::minimum_feerate`] which only raises the minimum relay fee when the latter is checked.
+pub trait AdmissionPolicy: Send + Sync {
+    /// Checks `transaction` before its UTXO entries are populated
+    fn check_transaction_in_isolation(&self, _transaction: &MutableTransaction) -> PolicyResult<()> {
+        Ok(())
+    }
+
+    /// Checks `transaction` once all its UTXO entries are populated, its fee excepted
+    fn check_transaction_in_context(&self, _transaction: &MutableTransaction) -> PolicyResult<()> {
+        Ok(())
+    }
+
+    /// Returns the minimum feerate, in sompi/gram of compute mass, a transaction must pay to be accepted
+    fn minimum_feerate(&self) -> Option<f64> {
+        None
+    }
+
+    /// Returns whether `script_public_key` is accepted despite its script class being non-standard
+    fn allows_non_standard_script(&self, _script_public_key: &ScriptPublicKey) -> bool {
+        false
+    }
+}
+
+/// The policy of a mempool with no configured policy, adding no rule to the standardness ones
+#[derive(Default)]
+pub struct DefaultAdmissionPolicy;
+
+impl AdmissionPolicy for DefaultAdmissionPolicy {}
+
+/// Settings of a [`ConfigurableAdmissionPolicy`], read from the `[mempool-policy]` table of the node config file
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct AdmissionPolicyConfig {
+    /// Transactions paying to or spending from any of these addresses are rejected
+    pub blocked_addresses: Vec<Address>,
+    /// Transactions paying to or spending from any of these script public keys are rejected
+    pub blocked_script_public_keys: Vec<ScriptPublicKey>,
+    /// Maximum size in bytes of a transaction payload
+    pub max_payload_size: Option<usize>,
+    /// Minimum feerate in sompi/gram of compute mass a transaction must pay
+    pub minimum_feerate: Option<f64>,
+    /// Non-standard script public keys with the version of one of these and starting with its script are accepted
+    pub non_standard_script_prefixes: Vec<ScriptPublicKey>,
+}
+
+impl AdmissionPolicyConfig {
+    /// Returns true if the config defines no rule at all
+    pub fn is_empty(&self) -> bool {
+        self.blocked_addresses.is_empty()
+            && self.blocked_script_public_keys.is_empty()
+            && self.max_payload_size.is_none()
+            && self.minimum_feerate.is_none()
+            && self.non_standard_script_prefixes.is_empty()
+    }
+}
+
+/// The built-in admission policy, defined by an [`AdmissionPolicyConfig`]
+pub struct ConfigurableAdmissionPolicy {
+    blocked_script_public_keys: HashSet<ScriptPublicKey>,
+    max_payload_size: Option<usize>,
+    minimum_feerate: Option<f64>,
+    non_standard_script_prefixes: Vec<ScriptPublicKey>,
+}
+
+impl ConfigurableAdmissionPolicy {
+    pub fn new(config: AdmissionPolicyConfig) -> Self {
+        let blocked_script_public_keys = config
+            .blocked_addresses
+            .iter()
+            .map(pay_to_address_script)
+            .chain(config.blocked_script_public_keys)
+            .collect::<HashSet<_>>();
+        Self {
+            blocked_script_public_keys,
+            max_payload_size: config.max_payload_size,
+            minimum_feerate: config.minimum_feerate,
+            non_standard_script_prefixes: config.non_standard_script_prefixes,
+        }
+    }
+}
+
+impl AdmissionPolicy for ConfigurableAdmissionPolicy {
+    fn check_transaction_in_isolation(&self, transaction: &MutableTransaction) -> PolicyResult<()> {
+        if let Some(max_payload_size) = self.max_payload_size {
+            if transaction.tx.payload.len() > max_payload_size {
+                return Err(PolicyError::RejectPayloadSize(transaction.tx.payload.len(), max_payload_size));
+            }
+        }
+        if let Some(i) =
+            transaction.tx.outputs.iter().position(|output| self.blocked_script_public_keys.contains(&output.script_public_key))
+        {
+            return Err(PolicyError::RejectBlockedOutput(i));
+        }
+        Ok(())
+    }
+
+    fn check_transaction_in_context(&self, transaction: &MutableTransaction) -> PolicyResult<()> {
+        if let Some(i) = transaction
+            .entries
+            .iter()
+            .position(|entry| entry.as_ref().is_some_and(|entry| self.blocked_script_public_keys.contains(&entry.script_public_key)))
+        {
+            return Err(PolicyError::RejectBlockedInput(i));
+        }
+        Ok(())
+    }
+
+    fn minimum_feerate(&self) -> Option<f64> {
+        self.minimum_feerate
+    }
+
+    fn allows_non_standard_script(&self, script_public_key: &ScriptPublicKey) -> bool {
+        self.non_standard_script_prefixes
+            .iter()
+            .any(|prefix| prefix.version() == script_public_key.version() && script_public_key.script().starts_with(prefix.script()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{str::FromStr, sync::Arc};
+    use vecno_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry},
+    };
+
+    fn spk(hex: &str) -> ScriptPublicKey {
+        ScriptPublicKey::from_str(hex).unwrap()
+    }
+
+    fn transaction(input_spk: ScriptPublicKey, output_spk: ScriptPublicKey, payload: Vec<u8>) -> MutableTransaction {
+        let input = TransactionInput::new(TransactionOutpoint::new(1.into(), 0), vec![], 0, 0);
+        let output = TransactionOutput::new(1_000, output_spk);
+        let tx = Transaction::new(0, vec![input], vec![output], 0, SUBNETWORK_ID_NATIVE, 0, payload);
+        MutableTransaction::with_entries(Arc::new(tx), vec![UtxoEntry::new(2_000, input_spk, 0, false)])
+    }
+
+    #[test]
+    fn test_configurable_admission_policy() {
+        let blocked = spk("000020aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaac");
+        let allowed = spk("000020bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbac");
+        let policy = ConfigurableAdmissionPolicy::new(AdmissionPolicyConfig {
+            blocked_script_public_keys: vec![blocked.clone()],
+            max_payload_size: Some(4),
+            non_standard_script_prefixes: vec![spk("0000ab")],
+            ..Default::default()
+        });
+
+        let tx = transaction(allowed.clone(), allowed.clone(), vec![0; 4]);
+        assert_eq!(policy.check_transaction_in_isolation(&tx), Ok(()));
+        assert_eq!(policy.check_transaction_in_context(&tx), Ok(()));
+
+        let tx = transaction(allowed.clone(), allowed.clone(), vec![0; 5]);
+        assert_eq!(policy.check_transaction_in_isolation(&tx), Err(PolicyError::RejectPayloadSize(5, 4)));
+
+        let tx = transaction(allowed.clone(), blocked.clone(), vec![]);
+        assert_eq!(policy.check_transaction_in_isolation(&tx), Err(PolicyError::RejectBlockedOutput(0)));
+
+        let tx = transaction(blocked, allowed.clone(), vec![]);
+        assert_eq!(policy.check_transaction_in_isolation(&tx), Ok(()));
+        assert_eq!(policy.check_transaction_in_context(&tx), Err(PolicyError::RejectBlockedInput(0)));
+
+        assert!(policy.allows_non_standard_script(&spk("0000abcdef")));
+        assert!(!policy.allows_non_standard_script(&spk("0001abcdef")));
+        assert!(!policy.allows_non_standard_script(&allowed));
+    }
+
+    #[test]
+    fn test_admission_policy_config_deserialization() {
+        let config: AdmissionPolicyConfig = toml::from_str(
+            r#"
+            blocked-script-public-keys = ["000020aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaac"]
+            max-payload-size = 0
+            minimum-feerate = 2.5
+            non-standard-script-prefixes = ["0000ab"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.blocked_script_public_keys.len(), 1);
+        assert_eq!(config.max_payload_size, Some(0));
+        assert_eq!(config.minimum_feerate, Some(2.5));
+        assert_eq!(config.non_standard_script_prefixes, vec![spk("0000ab")]);
+        assert!(!config.is_empty());
+        assert!(AdmissionPolicyConfig::default().is_empty());
+    }
+}
@@ -16,7 +16,9 @@ impl Mempool {
         extra_info: &str,
     ) -> RuleResult<()> {
         if self.orphan_pool.has(transaction_id) {
-            return self.orphan_pool.remove_orphan(transaction_id, true, reason, extra_info).map(|_| ());
+            let removed_orphans = self.orphan_pool.remove_orphan(transaction_id, true, reason, extra_info)?;
+            self.notify_removed_transactions(&removed_orphans, reason);
+            return Ok(());
         }
 
         if !self.transaction_pool.has(transaction_id) {
@@ -29,18 +31,22 @@ impl Mempool {
             removed_transactions.extend(self.transaction_pool.get_redeemer_ids_in_pool(transaction_id));
         }
 
-        let mut removed_orphans: Vec<TransactionId> = vec![];
+        let mut removed = Vec::with_capacity(removed_transactions.len());
+        let mut removed_orphans = vec![];
         for tx_id in removed_transactions.iter() {
             // Remove the tx from the transaction pool and the UTXO set (handled within the pool)
             let tx = self.transaction_pool.remove_transaction(tx_id)?;
             // Update/remove descendent orphan txs (depending on `remove_redeemers`)
             let txs = self.orphan_pool.update_orphans_after_transaction_removed(&tx, remove_redeemers)?;
-            removed_orphans.extend(txs.into_iter().map(|x| x.id()));
+            removed_orphans.extend(txs);
+            removed.push(tx);
         }
-        removed_transactions.extend(removed_orphans);
+        removed_transactions.extend(removed_orphans.iter().map(|x| x.id()));
+        self.notify_removed_transactions(removed.iter().chain(removed_orphans.iter()), reason);
 
         match reason {
-            TxRemovalReason::Muted => {}
+            // Expired transactions are logged in bulk by the caller
+            TxRemovalReason::Muted | TxRemovalReason::Expired => {}
             TxRemovalReason::DoubleSpend => match removed_transactions.len() {
                 0 => {}
                 1 => debug!("Removed transaction ({}) {}{}", reason, removed_transactions[0], extra_info),
@@ -55,46 +55,30 @@ impl Mempool {
         }
     }
 
-    /// Executes replace by fee (RBF) for an incoming transaction and a policy.
+    /// Validates replace by fee (RBF) for an incoming transaction and a policy, without executing it.
     ///
     /// See [`RbfPolicy`] variants for details of each policy process and success conditions.
     ///
-    /// On success, `transaction` is guaranteed to embed no double spend with the mempool.
-    ///
-    /// On success with the [`RbfPolicy::Mandatory`] policy, some removed transaction is always returned.
-    pub(super) fn execute_replace_by_fee(
-        &mut self,
+    /// On success, returns the double spends whose owners must be removed from the mempool before inserting
+    /// `transaction`, the owner of the first one being the replaced transaction.
+    pub(super) fn validate_replace_by_fee(
+        &self,
         transaction: &MutableTransaction,
         rbf_policy: RbfPolicy,
-    ) -> RuleResult<Option<Arc<Transaction>>> {
+    ) -> RuleResult<Vec<DoubleSpend>> {
         match rbf_policy {
             RbfPolicy::Forbidden => {
                 self.transaction_pool.check_double_spends(transaction)?;
-                Ok(None)
+                Ok(vec![])
             }
 
             RbfPolicy::Allowed => {
                 let double_spends = self.transaction_pool.get_double_spend_transaction_ids(transaction);
-                match double_spends.is_empty() {
-                    true => Ok(None),
-                    false => {
-                        let removed = self.validate_double_spending_transaction(transaction, &double_spends[0])?.mtx.tx.clone();
-                        for double_spend in double_spends.iter().skip(1) {
-                            // Validate the feerate threshold is passed for all double spends
-                            self.validate_double_spending_transaction(transaction, double_spend)?;
-                        }
-                        // We apply consequences such as removal only after we fully validate against all double spends
-                        for double_spend in double_spends {
-                            self.remove_transaction(
-                                &double_spend.owner_id,
-                                true,
-                                TxRemovalReason::ReplacedByFee,
-                                format!("by {}", transaction.id()).as_str(),
-                            )?;
-                        }
-                        Ok(Some(removed))
-                    }
+                for double_spend in double_spends.iter() {
+                    // Validate the feerate threshold is passed for all double spends
+                    self.validate_double_spending_transaction(transaction, double_spend)?;
                 }
+                Ok(double_spends)
             }
 
             RbfPolicy::Mandatory => {
@@ -102,14 +86,8 @@ impl Mempool {
                 match double_spends.len() {
                     0 => Err(RuleError::RejectRbfNoDoubleSpend),
                     1 => {
-                        let removed = self.validate_double_spending_transaction(transaction, &double_spends[0])?.mtx.tx.clone();
-                        self.remove_transaction(
-                            &double_spends[0].owner_id,
-                            true,
-                            TxRemovalReason::ReplacedByFee,
-                            format!("by {}", transaction.id()).as_str(),
-                        )?;
-                        Ok(Some(removed))
+                        self.validate_double_spending_transaction(transaction, &double_spends[0])?;
+                        Ok(double_spends)
                     }
                     _ => Err(RuleError::RejectRbfTooManyDoubleSpendingTransactions),
                 }
@@ -117,6 +95,35 @@ impl Mempool {
         }
     }
 
+    /// Executes replace by fee (RBF) for an incoming transaction and a policy.
+    ///
+    /// See [`RbfPolicy`] variants for details of each policy process and success conditions.
+    ///
+    /// On success, `transaction` is guaranteed to embed no double spend with the mempool.
+    ///
+    /// On success with the [`RbfPolicy::Mandatory`] policy, some removed transaction is always returned.
+    pub(super) fn execute_replace_by_fee(
+        &mut self,
+        transaction: &MutableTransaction,
+        rbf_policy: RbfPolicy,
+    ) -> RuleResult<Option<Arc<Transaction>>> {
+        let double_spends = self.validate_replace_by_fee(transaction, rbf_policy)?;
+        let removed = match double_spends.first() {
+            Some(double_spend) => Some(self.transaction_pool.get_double_spend_owner(double_spend)?.mtx.tx.clone()),
+            None => None,
+        };
+        // We apply consequences such as removal only after we fully validate against all double spends
+        for double_spend in double_spends {
+            self.remove_transaction(
+                &double_spend.owner_id,
+                true,
+                TxRemovalReason::ReplacedByFee,
+                format!("by {}", transaction.id()).as_str(),
+            )?;
+        }
+        Ok(removed)
+    }
+
     fn get_double_spend_feerate(&self, double_spend: &DoubleSpend) -> RuleResult<f64> {
         let owner = self.transaction_pool.get_double_spend_owner(double_spend)?;
         match owner.mtx.calculated_feerate() {
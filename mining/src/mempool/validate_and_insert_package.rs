@@ -0,0 +1,232 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{atomic::Ordering, Arc},
+};
+
+use crate::{
+    errors::{MiningManagerError, MiningManagerResult},
+    mempool::{
+        errors::{RuleError, RuleResult},
+        model::{pool::Pool, tx::TxRemovalReason},
+        tx::{Priority, RbfPolicy},
+        Mempool,
+    },
+};
+use itertools::Itertools;
+use vecno_consensus_core::{
+    api::ConsensusApi,
+    constants::UNACCEPTED_DAA_SCORE,
+    tx::{MutableTransaction, Transaction, TransactionId, UtxoEntry},
+};
+use vecno_core::debug;
+
+/// A transaction package is an ordered list of transactions where each transaction only spends
+/// outputs of transactions preceding it in the package, of mempool transactions or of the UTXO set.
+///
+/// A package is validated and inserted as a whole: either all its transactions enter the mempool
+/// or none does. The minimum relay fee is required from the package as a whole rather than from
+/// every transaction, so that a child paying enough fees lets a low-fee parent in.
+///
+/// Package transactions are never orphans and never replace mempool transactions by fee.
+impl Mempool {
+    pub(crate) fn pre_validate_and_populate_package(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<MutableTransaction>,
+    ) -> MiningManagerResult<Vec<MutableTransaction>> {
+        self.check_package_structure(&transactions)?;
+
+        // Transactions already known are dropped from the package, the remaining ones then spend their outputs as usual
+        let mut package_transactions: HashMap<TransactionId, Arc<Transaction>> = HashMap::with_capacity(transactions.len());
+        let mut rejections = vec![];
+        let transactions = transactions
+            .into_iter()
+            .filter(|transaction| !self.is_known_package_transaction(&transaction.id()))
+            .map(|mut transaction| {
+                if let Err(err) =
+                    self.pre_validate_and_populate_package_transaction(consensus, &mut transaction, &package_transactions)
+                {
+                    rejections.push((transaction.id(), err));
+                }
+                package_transactions.insert(transaction.id(), transaction.tx.clone());
+                transaction
+            })
+            .collect_vec();
+
+        if !rejections.is_empty() {
+            return Err(MiningManagerError::PackageRejected(rejections));
+        }
+        Ok(transactions)
+    }
+
+    pub(crate) fn post_validate_and_insert_package(
+        &mut self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<MutableTransaction>,
+        priority: Priority,
+    ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
+        // Since the execution of the manager public functions is not atomic, some transactions may have been
+        // inserted or accepted concurrently. Those are dropped like in pre-validation.
+        let transactions =
+            transactions.into_iter().filter(|transaction| !self.is_known_package_transaction(&transaction.id())).collect_vec();
+        if transactions.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let rejections = transactions
+            .iter()
+            .filter_map(|transaction| {
+                self.transaction_pool
+                    .check_double_spends(transaction)
+                    .and_then(|_| self.validate_transaction_in_context_ignoring_fee(transaction))
+                    .err()
+                    .map(|err| (transaction.id(), err))
+            })
+            .collect_vec();
+        if !rejections.is_empty() {
+            return Err(MiningManagerError::PackageRejected(rejections));
+        }
+
+        // The package is judged by its aggregate fee
+        let package_fee: u64 = transactions.iter().map(|transaction| transaction.calculated_fee.unwrap()).sum();
+        let package_compute_mass: u64 = transactions.iter().map(|transaction| transaction.calculated_compute_mass.unwrap()).sum();
+        let package_mass: u64 = transactions.iter().map(|transaction| transaction.tx.mass()).sum();
+        if !self.config.accept_non_standard {
+            let minimum_fee = self.minimum_required_transaction_relay_fee(package_compute_mass);
+            if package_fee < minimum_fee {
+                return Err(RuleError::RejectPackageInsufficientFee(package_fee, minimum_fee).into());
+            }
+        }
+
+        // Run all the remaining fallible checks before the pool gets modified: select the transactions to evict in order
+        // to make room for the whole package and look up the package transactions received before as orphans missing
+        // their package parents
+        let transaction_sizes = transactions.iter().map(|transaction| transaction.mempool_estimated_bytes()).collect_vec();
+        let package_size = transaction_sizes.iter().sum::<usize>();
+        let package_feerate = package_fee as f64 / package_mass as f64;
+        let txs_to_remove = self.transaction_pool.limit_package_transaction_count(
+            &transactions.iter().collect_vec(),
+            package_feerate,
+            package_size,
+        )?;
+        let unorphaned_ids =
+            transactions.iter().map(|transaction| transaction.id()).filter(|id| self.orphan_pool.has(id)).collect_vec();
+        let package_id = transactions[0].id();
+
+        // Add the transactions in package order so that parents always precede their children. This is the only step
+        // which may fail once the pool is being modified, in which case it is undone.
+        let virtual_daa_score = consensus.get_virtual_daa_score();
+        let accepted_transactions =
+            self.insert_package_transactions(transactions.into_iter().zip(transaction_sizes), virtual_daa_score, priority)?;
+
+        // The package is in, the unorphaned transactions and the evicted ones can go
+        for transaction_id in unorphaned_ids.iter() {
+            self.orphan_pool.remove_orphan(transaction_id, false, TxRemovalReason::Unorphaned, " by package")?;
+        }
+        if !txs_to_remove.is_empty() {
+            let transaction_pool_len_before = self.transaction_pool.len();
+            let extra_info = format!(" for package of {}", package_id);
+            for x in txs_to_remove.iter() {
+                self.remove_transaction(x, true, TxRemovalReason::MakingRoom, extra_info.as_str())?;
+                // See `post_validate_and_insert_transaction` for why we might already have sufficient space
+                if self.transaction_pool.len() <= self.config.maximum_transaction_count
+                    && self.transaction_pool.get_estimated_size() <= self.config.mempool_size_limit
+                {
+                    break;
+                }
+            }
+            self.counters
+                .tx_evicted_counts
+                .fetch_add(transaction_pool_len_before.saturating_sub(self.transaction_pool.len()) as u64, Ordering::Relaxed);
+        }
+        debug!("Transaction package of {} transactions accepted, with {} fees", accepted_transactions.len(), package_fee);
+        Ok(accepted_transactions)
+    }
+
+    /// Adds the package transactions to the transaction pool in order. If one of them cannot be added, the ones added
+    /// before it are removed again so that the pool is left unchanged.
+    fn insert_package_transactions(
+        &mut self,
+        transactions: impl IntoIterator<Item = (MutableTransaction, usize)>,
+        virtual_daa_score: u64,
+        priority: Priority,
+    ) -> RuleResult<Vec<Arc<Transaction>>> {
+        let mut accepted_transactions: Vec<Arc<Transaction>> = vec![];
+        for (transaction, transaction_size) in transactions {
+            // Package transactions conflicting with each other only show up once the preceding ones are in the pool
+            let result = self.transaction_pool.check_double_spends(&transaction).and_then(|_| {
+                self.transaction_pool
+                    .add_transaction(transaction, virtual_daa_score, priority, RbfPolicy::Forbidden, transaction_size)
+                    .map(|accepted_transaction| accepted_transaction.mtx.tx.clone())
+            });
+            match result {
+                Ok(accepted_transaction) => accepted_transactions.push(accepted_transaction),
+                Err(err) => {
+                    for accepted_transaction in accepted_transactions.iter().rev() {
+                        self.transaction_pool
+                            .remove_transaction(&accepted_transaction.id())
+                            .expect("a package transaction added to the pool is removable");
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        Ok(accepted_transactions)
+    }
+
+    /// Checks that the package is not empty, not too large, has no duplicate, is topologically sorted
+    /// and does not spend the same outpoint twice
+    fn check_package_structure(&self, transactions: &[MutableTransaction]) -> RuleResult<()> {
+        if transactions.is_empty() {
+            return Err(RuleError::RejectEmptyPackage);
+        }
+        if transactions.len() > self.config.maximum_package_transaction_count {
+            return Err(RuleError::RejectPackageTooLarge(transactions.len(), self.config.maximum_package_transaction_count));
+        }
+
+        let mut positions = HashMap::with_capacity(transactions.len());
+        for (position, transaction) in transactions.iter().enumerate() {
+            if positions.insert(transaction.id(), position).is_some() {
+                return Err(RuleError::RejectPackageDuplicate(transaction.id()));
+            }
+        }
+
+        let mut spent_outpoints = HashSet::new();
+        for (position, transaction) in transactions.iter().enumerate() {
+            for input in transaction.tx.inputs.iter() {
+                if positions.get(&input.previous_outpoint.transaction_id).is_some_and(|&parent_position| parent_position > position) {
+                    return Err(RuleError::RejectPackageNotSorted(transaction.id()));
+                }
+                if !spent_outpoints.insert(input.previous_outpoint) {
+                    return Err(RuleError::RejectPackageDoubleSpend(input.previous_outpoint, transaction.id()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn pre_validate_and_populate_package_transaction(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transaction: &mut MutableTransaction,
+        package_transactions: &HashMap<TransactionId, Arc<Transaction>>,
+    ) -> RuleResult<()> {
+        transaction.calculated_compute_mass = Some(consensus.calculate_transaction_compute_mass(&transaction.tx));
+        self.validate_transaction_in_isolation(transaction)?;
+        self.transaction_pool.check_double_spends(transaction)?;
+        self.populate_mempool_entries(transaction);
+        for (i, input) in transaction.tx.inputs.iter().enumerate() {
+            if let Some(parent) = package_transactions.get(&input.previous_outpoint.transaction_id) {
+                let output = parent.outputs.get(input.previous_outpoint.index as usize).ok_or(RuleError::RejectMissingOutpoint)?;
+                transaction.entries[i] =
+                    Some(UtxoEntry::new(output.value, output.script_public_key.clone(), UNACCEPTED_DAA_SCORE, false));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if the transaction is either in the transactions pool or already accepted by consensus
+    fn is_known_package_transaction(&self, transaction_id: &TransactionId) -> bool {
+        self.transaction_pool.has(transaction_id) || self.accepted_transactions.has(transaction_id)
+    }
+}
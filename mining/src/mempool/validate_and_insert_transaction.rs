@@ -4,7 +4,9 @@ use crate::mempool::{
     errors::{RuleError, RuleResult},
     model::{
         pool::Pool,
-        tx::{MempoolTransaction, TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
+        tx::{
+            MempoolTransaction, TransactionPostValidation, TransactionPostValidationTest, TransactionPreValidation, TxRemovalReason,
+        },
     },
     tx::{Orphan, Priority, RbfPolicy},
     Mempool,
@@ -61,7 +63,7 @@ impl Mempool {
                     return Err(RuleError::RejectDisallowedOrphan(transaction_id));
                 }
                 let _ = self.get_replace_by_fee_constraint(&transaction, rbf_policy)?;
-                self.orphan_pool.try_add_orphan(consensus.get_virtual_daa_score(), transaction, priority)?;
+                self.orphan_pool.try_add_orphan(consensus.get_virtual_daa_score(), transaction, priority, rbf_policy)?;
                 return Ok(TransactionPostValidation::default());
             }
             Err(err) => {
@@ -122,15 +124,60 @@ impl Mempool {
         // Add the transaction to the mempool as a MempoolTransaction and return a clone of the embedded Arc<Transaction>
         let accepted_transaction = self
             .transaction_pool
-            .add_transaction(transaction, consensus.get_virtual_daa_score(), priority, transaction_size)?
+            .add_transaction(transaction, consensus.get_virtual_daa_score(), priority, rbf_policy, transaction_size)?
             .mtx
             .tx
             .clone();
         Ok(TransactionPostValidation { removed: removed_transaction, accepted: Some(accepted_transaction) })
     }
 
+    /// Performs the validations of [`Self::post_validate_and_insert_transaction`] without modifying the mempool.
+    ///
+    /// On success, reports whether the transaction would be inserted as an orphan and which transaction,
+    /// if any, it would replace by fee.
+    pub(crate) fn post_validate_transaction(
+        &self,
+        validation_result: RuleResult<()>,
+        transaction: &MutableTransaction,
+        orphan: Orphan,
+        rbf_policy: RbfPolicy,
+    ) -> RuleResult<TransactionPostValidationTest> {
+        let transaction_id = transaction.id();
+        if self.transaction_pool.has(&transaction_id) {
+            return Err(RuleError::RejectDuplicate(transaction_id));
+        }
+
+        self.validate_transaction_unacceptance(transaction)?;
+
+        match validation_result {
+            Ok(_) => {}
+            Err(RuleError::RejectMissingOutpoint) => {
+                if orphan == Orphan::Forbidden {
+                    return Err(RuleError::RejectDisallowedOrphan(transaction_id));
+                }
+                let _ = self.get_replace_by_fee_constraint(transaction, rbf_policy)?;
+                self.orphan_pool.check_orphan(transaction)?;
+                return Ok(TransactionPostValidationTest { is_orphan: true, replaced: None });
+            }
+            Err(err) => {
+                return Err(err);
+            }
+        }
+
+        self.validate_transaction_in_context(transaction)?;
+        let double_spends = self.validate_replace_by_fee(transaction, rbf_policy)?;
+
+        // Replaced transactions are not accounted for when checking if there is room in the pool
+        let _ = self.transaction_pool.limit_transaction_count(transaction, transaction.mempool_estimated_bytes())?;
+
+        Ok(TransactionPostValidationTest {
+            is_orphan: false,
+            replaced: double_spends.first().map(|double_spend| double_spend.owner_id),
+        })
+    }
+
     /// Validates that the transaction wasn't already accepted into the DAG
-    fn validate_transaction_unacceptance(&self, transaction: &MutableTransaction) -> RuleResult<()> {
+    pub(crate) fn validate_transaction_unacceptance(&self, transaction: &MutableTransaction) -> RuleResult<()> {
         // Reject if the transaction is registered as an accepted transaction
         let transaction_id = transaction.id();
         match self.accepted_transactions.has(&transaction_id) {
@@ -139,7 +186,7 @@ impl Mempool {
         }
     }
 
-    fn validate_transaction_in_isolation(&self, transaction: &MutableTransaction) -> RuleResult<()> {
+    pub(crate) fn validate_transaction_in_isolation(&self, transaction: &MutableTransaction) -> RuleResult<()> {
         let transaction_id = transaction.id();
         if self.transaction_pool.has(&transaction_id) {
             return Err(RuleError::RejectDuplicate(transaction_id));
@@ -148,10 +195,22 @@ impl Mempool {
         if !self.config.accept_non_standard {
             self.check_transaction_standard_in_isolation(transaction)?;
         }
+        self.admission_policy
+            .check_transaction_in_isolation(transaction)
+            .map_err(|err| RuleError::RejectPolicy(transaction_id, err))?;
         Ok(())
     }
 
     fn validate_transaction_in_context(&self, transaction: &MutableTransaction) -> RuleResult<()> {
+        self.validate_transaction_in_context_ignoring_fee(transaction)?;
+        if !self.config.accept_non_standard {
+            self.check_transaction_fee(transaction)?;
+        }
+        Ok(())
+    }
+
+    /// Performs the mempool in-context validations of `transaction` except for the minimum fee check
+    pub(crate) fn validate_transaction_in_context_ignoring_fee(&self, transaction: &MutableTransaction) -> RuleResult<()> {
         // TEMP: apply parts of go-vecnod mempool dust prevention patch
         let has_coinbase_input = transaction.entries.iter().any(|e| e.as_ref().unwrap().is_coinbase);
         let num_extra_outs = transaction.tx.outputs.len() as i64 - transaction.tx.inputs.len() as i64;
@@ -166,6 +225,9 @@ impl Mempool {
         if !self.config.accept_non_standard {
             self.check_transaction_standard_in_context(transaction)?;
         }
+        self.admission_policy
+            .check_transaction_in_context(transaction)
+            .map_err(|err| RuleError::RejectPolicy(transaction.id(), err))?;
         Ok(())
     }
 
@@ -12,10 +12,20 @@ pub struct CandidateTransaction {
     pub calculated_fee: u64,
     /// Populated mass
     pub calculated_mass: u64,
+    /// Fee of the best paying package rooted at the transaction, see [`FeerateTransactionKey::package_fee`]
+    pub package_fee: u64,
+    /// Mass of the best paying package rooted at the transaction
+    pub package_mass: u64,
 }
 
 impl CandidateTransaction {
     pub fn from_key(key: FeerateTransactionKey) -> Self {
-        Self { tx: key.tx, calculated_fee: key.fee, calculated_mass: key.mass }
+        Self {
+            tx: key.tx,
+            calculated_fee: key.fee,
+            calculated_mass: key.mass,
+            package_fee: key.package_fee,
+            package_mass: key.package_mass,
+        }
     }
 }
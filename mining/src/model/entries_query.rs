@@ -0,0 +1,86 @@
+use std::cmp::Ordering;
+use vecno_consensus_core::tx::{MutableTransaction, TransactionId};
+
+/// Key the mempool entries returned by an [`EntriesQuery`] are sorted by. Ties are broken by transaction id so that
+/// the order is stable from one page to the next.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EntriesSortKey {
+    #[default]
+    TransactionId,
+    Feerate,
+    ArrivalTime,
+    Mass,
+}
+
+/// Filters, order and page of a mempool entries query
+#[derive(Clone, Debug, Default)]
+pub struct EntriesQuery {
+    pub sort_key: EntriesSortKey,
+    pub descending: bool,
+    /// Entries paying a lower feerate are skipped, orphans included since their fee is unknown
+    pub min_feerate: f64,
+    /// Only the entries carrying a payload are returned
+    pub payload_only: bool,
+    /// Number of matching entries skipped before the returned ones
+    pub offset: usize,
+    /// Maximum number of returned entries, all the remaining ones being returned if `None`
+    pub limit: Option<usize>,
+}
+
+/// An entry of the mempool as considered by an [`EntriesQuery`]
+pub(crate) struct EntryProperties {
+    pub(crate) id: TransactionId,
+    pub(crate) has_payload: bool,
+    pub(crate) feerate: f64,
+    pub(crate) mass: u64,
+    pub(crate) fee: u64,
+    pub(crate) added_at_time: u64,
+}
+
+impl EntryProperties {
+    pub(crate) fn new(transaction: &MutableTransaction, added_at_time: u64) -> Self {
+        // Orphans are not fully validated so they have no contextual mass nor fee yet
+        let mass = match transaction.tx.mass() {
+            0 => transaction.calculated_compute_mass.unwrap_or_default(),
+            mass => mass,
+        };
+        Self {
+            id: transaction.id(),
+            has_payload: !transaction.tx.payload.is_empty(),
+            feerate: transaction.calculated_feerate().unwrap_or_default(),
+            mass,
+            fee: transaction.calculated_fee.unwrap_or_default(),
+            added_at_time,
+        }
+    }
+}
+
+impl EntriesQuery {
+    pub(crate) fn matches(&self, entry: &EntryProperties) -> bool {
+        (self.min_feerate <= 0.0 || entry.feerate >= self.min_feerate) && (!self.payload_only || entry.has_payload)
+    }
+
+    pub(crate) fn compare(&self, a: &EntryProperties, b: &EntryProperties) -> Ordering {
+        let ordering = match self.sort_key {
+            EntriesSortKey::TransactionId => Ordering::Equal,
+            EntriesSortKey::Feerate => a.feerate.total_cmp(&b.feerate),
+            EntriesSortKey::ArrivalTime => a.added_at_time.cmp(&b.added_at_time),
+            EntriesSortKey::Mass => a.mass.cmp(&b.mass),
+        }
+        .then_with(|| a.id.cmp(&b.id));
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+/// A page of the mempool entries matching an [`EntriesQuery`], along with totals over all the matching entries
+#[derive(Default)]
+pub struct EntriesPage {
+    pub transactions: Vec<MutableTransaction>,
+    pub total_count: usize,
+    pub total_mass: u64,
+    pub total_fees: u64,
+}
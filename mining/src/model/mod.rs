@@ -2,9 +2,12 @@ use std::collections::HashSet;
 use vecno_consensus_core::tx::TransactionId;
 
 pub mod candidate_tx;
+pub mod entries_query;
 pub mod owner_txs;
+pub mod persisted_tx;
 pub mod topological_index;
 pub mod topological_sort;
+pub mod tx_acceptance;
 pub mod tx_insert;
 pub mod tx_query;
 
@@ -0,0 +1,30 @@
+use crate::mempool::{
+    config::Config,
+    tx::{Priority, RbfPolicy},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use vecno_consensus_core::tx::Transaction;
+
+/// A mempool transaction along with the context of its insertion, persisted across node restarts
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct PersistedTransaction {
+    pub transaction: Transaction,
+    pub priority: Priority,
+    pub rbf_policy: RbfPolicy,
+    pub added_at_daa_score: u64,
+    pub is_orphan: bool,
+}
+
+impl PersistedTransaction {
+    pub fn new(transaction: Transaction, priority: Priority, rbf_policy: RbfPolicy, added_at_daa_score: u64, is_orphan: bool) -> Self {
+        Self { transaction, priority, rbf_policy, added_at_daa_score, is_orphan }
+    }
+
+    /// Returns whether the transaction would have been expired by the mempool at `virtual_daa_score`
+    pub fn is_expired(&self, config: &Config, virtual_daa_score: u64) -> bool {
+        // Never expire high priority transactions
+        let expire_interval_daa_score =
+            if self.is_orphan { config.orphan_expire_interval_daa_score } else { config.transaction_expire_interval_daa_score };
+        self.priority == Priority::Low && virtual_daa_score > self.added_at_daa_score + expire_interval_daa_score
+    }
+}
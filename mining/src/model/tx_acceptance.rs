@@ -0,0 +1,39 @@
+use crate::mempool::errors::RuleError;
+use vecno_consensus_core::tx::TransactionId;
+
+/// Outcome of a transaction tested for acceptance into the mempool without being inserted
+#[derive(Debug)]
+pub struct TransactionAcceptance {
+    pub transaction_id: TransactionId,
+    pub compute_mass: u64,
+    /// Known only if all the UTXO entries spent by the transaction are found
+    pub storage_mass: Option<u64>,
+    /// Known only if all the UTXO entries spent by the transaction are found
+    pub fee: Option<u64>,
+    /// Fee per gram of contextual mass, the max of the compute and storage masses
+    pub feerate: Option<f64>,
+    /// The transaction would be inserted into the orphan pool
+    pub is_orphan: bool,
+    /// The mempool transaction the transaction would replace by fee
+    pub replaced_transaction_id: Option<TransactionId>,
+    pub error: Option<RuleError>,
+}
+
+impl TransactionAcceptance {
+    pub fn new(transaction_id: TransactionId, compute_mass: u64) -> Self {
+        Self {
+            transaction_id,
+            compute_mass,
+            storage_mass: None,
+            fee: None,
+            feerate: None,
+            is_orphan: false,
+            replaced_transaction_id: None,
+            error: None,
+        }
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self.error.is_none()
+    }
+}
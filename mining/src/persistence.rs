@@ -0,0 +1,213 @@
+//!
+//! Persistence of the mempool across node restarts: its transactions are dumped to a file on shutdown and
+//! re-validated on startup, so that pending transactions are not lost and need not be resubmitted.
+//!
+//! The feerate history is persisted the same way into a file of its own, so that historical fee estimations
+//! remain available right after a restart. Both files are consumed when restored.
+//!
+
+use crate::{feerate::history::BlockFeerateRecord, manager::MiningManagerProxy, model::persisted_tx::PersistedTransaction};
+use borsh::{BorshDeserialize, BorshSerialize};
+use std::{
+    fs,
+    io::{self, BufReader, BufWriter},
+    path::PathBuf,
+    sync::Arc,
+};
+use vecno_consensusmanager::ConsensusManager;
+use vecno_core::{
+    info,
+    task::service::{AsyncService, AsyncServiceFuture},
+    trace, warn,
+};
+
+const SERVICE_NAME: &str = "mempool-persistence";
+const FEERATE_HISTORY_SERVICE_NAME: &str = "feerate-history-persistence";
+
+/// Version of the file format, bumped on any change of [`PersistedTransaction`]
+const VERSION: u8 = 1;
+
+/// Version of the feerate history file format, bumped on any change of [`BlockFeerateRecord`]
+const FEERATE_HISTORY_VERSION: u8 = 1;
+
+/// File holding a list of borsh-serialized items preceded by the version of their format
+struct VersionedFile {
+    path: PathBuf,
+    version: u8,
+}
+
+impl VersionedFile {
+    fn new(path: PathBuf, version: u8) -> Self {
+        Self { path, version }
+    }
+
+    /// Loads the items and removes the file, so that a node crashing later on does not restore outdated items.
+    /// Returns `None` if there is no file.
+    fn take<T: BorshDeserialize>(&self) -> Option<io::Result<Vec<T>>> {
+        if !self.path.exists() {
+            return None;
+        }
+        let items = self.load();
+        if let Err(err) = fs::remove_file(&self.path) {
+            warn!("Failed to remove the persistence file {}: {}", self.path.display(), err);
+        }
+        Some(items)
+    }
+
+    fn load<T: BorshDeserialize>(&self) -> io::Result<Vec<T>> {
+        let mut reader = BufReader::new(fs::File::open(&self.path)?);
+        let version = u8::deserialize_reader(&mut reader)?;
+        if version != self.version {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unsupported version {}", version)));
+        }
+        Vec::deserialize_reader(&mut reader)
+    }
+
+    fn save<T: BorshSerialize>(&self, items: &[T]) -> io::Result<()> {
+        // Write to a temporary file first so that a crash in the middle leaves no truncated file behind
+        let tmp_path = self.path.with_extension("tmp");
+        let mut writer = BufWriter::new(fs::File::create(&tmp_path)?);
+        self.version.serialize(&mut writer)?;
+        items.serialize(&mut writer)?;
+        writer.into_inner().map_err(|err| err.into_error())?.sync_all()?;
+        fs::rename(&tmp_path, &self.path)
+    }
+}
+
+pub struct MempoolPersistence {
+    mining_manager: MiningManagerProxy,
+    consensus_manager: Arc<ConsensusManager>,
+    file: VersionedFile,
+}
+
+impl MempoolPersistence {
+    pub fn new(mining_manager: MiningManagerProxy, consensus_manager: Arc<ConsensusManager>, path: PathBuf) -> Self {
+        Self { mining_manager, consensus_manager, file: VersionedFile::new(path, VERSION) }
+    }
+
+    async fn restore(&self) {
+        match self.file.take::<PersistedTransaction>() {
+            None => {}
+            Some(Ok(mut transactions)) => {
+                // Recompute the cached transaction ids rather than trusting the file
+                transactions.iter_mut().for_each(|transaction| transaction.transaction.finalize());
+                let count = transactions.len();
+                let session = self.consensus_manager.consensus().unguarded_session();
+                let restored = self.mining_manager.clone().restore_transactions(&session, transactions).await;
+                info!("Restored {} out of {} persisted mempool transactions", restored, count);
+            }
+            Some(Err(err)) => warn!("Failed to load the persisted mempool from {}: {}", self.file.path.display(), err),
+        }
+    }
+}
+
+impl AsyncService for MempoolPersistence {
+    fn ident(self: Arc<Self>) -> &'static str {
+        SERVICE_NAME
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", SERVICE_NAME);
+        Box::pin(async move {
+            self.restore().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", SERVICE_NAME);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            let transactions = self.mining_manager.clone().get_persisted_transactions().await;
+            let count = transactions.len();
+            let service = self.clone();
+            match tokio::task::spawn_blocking(move || service.file.save(&transactions)).await.unwrap() {
+                Ok(()) => info!("Persisted {} mempool transactions to {}", count, self.file.path.display()),
+                Err(err) => warn!("Failed to persist the mempool to {}: {}", self.file.path.display(), err),
+            }
+            trace!("{} stopped", SERVICE_NAME);
+            Ok(())
+        })
+    }
+}
+
+pub struct FeerateHistoryPersistence {
+    mining_manager: MiningManagerProxy,
+    file: VersionedFile,
+}
+
+impl FeerateHistoryPersistence {
+    pub fn new(mining_manager: MiningManagerProxy, path: PathBuf) -> Self {
+        Self { mining_manager, file: VersionedFile::new(path, FEERATE_HISTORY_VERSION) }
+    }
+
+    async fn restore(&self) {
+        match self.file.take::<BlockFeerateRecord>() {
+            None => {}
+            Some(Ok(records)) => {
+                let count = records.len();
+                self.mining_manager.clone().restore_feerate_history(records).await;
+                info!("Restored {} persisted feerate history records", count);
+            }
+            Some(Err(err)) => warn!("Failed to load the persisted feerate history from {}: {}", self.file.path.display(), err),
+        }
+    }
+}
+
+impl AsyncService for FeerateHistoryPersistence {
+    fn ident(self: Arc<Self>) -> &'static str {
+        FEERATE_HISTORY_SERVICE_NAME
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", FEERATE_HISTORY_SERVICE_NAME);
+        Box::pin(async move {
+            self.restore().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", FEERATE_HISTORY_SERVICE_NAME);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            let records = self.mining_manager.clone().get_feerate_history_records().await;
+            let count = records.len();
+            let service = self.clone();
+            match tokio::task::spawn_blocking(move || service.file.save(&records)).await.unwrap() {
+                Ok(()) => info!("Persisted {} feerate history records to {}", count, self.file.path.display()),
+                Err(err) => warn!("Failed to persist the feerate history to {}: {}", self.file.path.display(), err),
+            }
+            trace!("{} stopped", FEERATE_HISTORY_SERVICE_NAME);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versioned_file() {
+        let path = std::env::temp_dir().join(format!("vecno-versioned-file-{}.bin", std::process::id()));
+        let file = VersionedFile::new(path.clone(), 2);
+        assert!(file.take::<u64>().is_none());
+
+        file.save(&[1u64, 2, 3]).unwrap();
+        assert!(!path.with_extension("tmp").exists());
+        assert_eq!(file.take::<u64>().unwrap().unwrap(), vec![1, 2, 3]);
+        // The file is consumed when restored
+        assert!(!path.exists());
+        assert!(file.take::<u64>().is_none());
+
+        // Files of another version are rejected, and consumed all the same
+        file.save(&[1u64]).unwrap();
+        assert_eq!(VersionedFile::new(path.clone(), 1).take::<u64>().unwrap().unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert!(!path.exists());
+    }
+}
@@ -22,12 +22,21 @@ use vecno_core::time::unix_now;
 use vecno_hashes::{Hash, ZERO_HASH};
 
 use parking_lot::RwLock;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 pub(crate) struct ConsensusMock {
     transactions: RwLock<HashMap<TransactionId, Arc<Transaction>>>,
     statuses: RwLock<HashMap<TransactionId, TxResult<()>>>,
     utxos: RwLock<UtxoCollection>,
+    /// UTXOs spent by the added transactions, allowing to revert them
+    spent_utxos: RwLock<HashMap<TransactionId, Vec<(TransactionOutpoint, UtxoEntry)>>>,
+    virtual_daa_score: AtomicU64,
 }
 
 impl ConsensusMock {
@@ -36,6 +45,8 @@ impl ConsensusMock {
             transactions: RwLock::new(HashMap::default()),
             statuses: RwLock::new(HashMap::default()),
             utxos: RwLock::new(HashMap::default()),
+            spent_utxos: RwLock::new(HashMap::default()),
+            virtual_daa_score: AtomicU64::new(0),
         }
     }
 
@@ -49,9 +60,13 @@ impl ConsensusMock {
         let mut utxos = self.utxos.write();
 
         // Remove the spent UTXOs
-        transaction.tx.inputs.iter().for_each(|x| {
-            utxos.remove(&x.previous_outpoint);
-        });
+        let spent = transaction
+            .tx
+            .inputs
+            .iter()
+            .filter_map(|x| utxos.remove(&x.previous_outpoint).map(|entry| (x.previous_outpoint, entry)))
+            .collect();
+        self.spent_utxos.write().insert(transaction.id(), spent);
         // Create the new UTXOs
         transaction.tx.outputs.iter().enumerate().for_each(|(i, x)| {
             utxos.insert(
@@ -63,6 +78,23 @@ impl ConsensusMock {
         transactions.insert(transaction.id(), transaction.tx);
     }
 
+    /// Reverts the effect of a transaction previously added by [`Self::add_transaction`] on the UTXO set, as if the
+    /// block including it got reorged out of the virtual chain
+    pub(crate) fn remove_transaction(&self, transaction_id: &TransactionId) {
+        let Some(transaction) = self.transactions.write().remove(transaction_id) else {
+            return;
+        };
+        let mut utxos = self.utxos.write();
+        (0..transaction.outputs.len()).for_each(|i| {
+            utxos.remove(&TransactionOutpoint::new(*transaction_id, i as u32));
+        });
+        utxos.extend(self.spent_utxos.write().remove(transaction_id).unwrap_or_default());
+    }
+
+    pub(crate) fn set_virtual_daa_score(&self, virtual_daa_score: u64) {
+        self.virtual_daa_score.store(virtual_daa_score, Ordering::Relaxed);
+    }
+
     pub(crate) fn can_finance_transaction(&self, transaction: &MutableTransaction) -> bool {
         let utxos = self.utxos.read();
         for outpoint in transaction.missing_outpoints() {
@@ -169,7 +201,7 @@ impl ConsensusApi for ConsensusMock {
     }
 
     fn get_virtual_daa_score(&self) -> u64 {
-        0
+        self.virtual_daa_score.load(Ordering::Relaxed)
     }
 
     fn get_virtual_state_approx_id(&self) -> VirtualStateApproxId {
@@ -51,10 +51,18 @@ event_type_enum! {
         VirtualDaaScoreChanged,
         PruningPointUtxoSetOverride,
         NewBlockTemplate,
+        MempoolTransactionRemoved,
     }
 }
 
-pub const EVENT_COUNT: usize = 9;
+pub const EVENT_COUNT: usize = 10;
+
+impl EventType {
+    /// Returns true if the subscriptions to this event type can be restricted to a set of addresses
+    pub fn is_address_filtered(&self) -> bool {
+        matches!(self, EventType::UtxosChanged | EventType::MempoolTransactionRemoved)
+    }
+}
 
 impl FromStr for EventType {
     type Err = Error;
@@ -70,6 +78,7 @@ impl FromStr for EventType {
             "virtual-daa-score-changed" => Ok(EventType::VirtualDaaScoreChanged),
             "pruning-point-utxo-set-override" => Ok(EventType::PruningPointUtxoSetOverride),
             "new-block-template" => Ok(EventType::NewBlockTemplate),
+            "mempool-transaction-removed" => Ok(EventType::MempoolTransactionRemoved),
             _ => Err(Error::InvalidEventType(s.to_string())),
         }
     }
@@ -4,7 +4,7 @@ use super::{
     events::EventType,
     subscription::{
         single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
-        Single,
+        Single, Subscription,
     },
 };
 use std::fmt::{Debug, Display};
@@ -22,6 +22,18 @@ pub trait Notification: Clone + Debug + Display + Send + Sync + 'static {
     fn apply_utxos_changed_subscription(&self, subscription: &UtxosChangedSubscription, context: &SubscriptionContext)
         -> Option<Self>;
 
+    /// Applies a subscription filtering the notification by addresses.
+    ///
+    /// The default implementation only checks that the subscription is active and must be overridden
+    /// by notification types actually carrying addresses.
+    fn apply_mempool_transaction_removed_subscription(
+        &self,
+        subscription: &UtxosChangedSubscription,
+        _context: &SubscriptionContext,
+    ) -> Option<Self> {
+        subscription.active().then(|| self.clone())
+    }
+
     fn apply_subscription(&self, subscription: &dyn Single, context: &SubscriptionContext) -> Option<Self> {
         match subscription.event_type() {
             EventType::VirtualChainChanged => self.apply_virtual_chain_changed_subscription(
@@ -30,6 +42,10 @@ pub trait Notification: Clone + Debug + Display + Send + Sync + 'static {
             ),
             EventType::UtxosChanged => self
                 .apply_utxos_changed_subscription(subscription.as_any().downcast_ref::<UtxosChangedSubscription>().unwrap(), context),
+            EventType::MempoolTransactionRemoved => self.apply_mempool_transaction_removed_subscription(
+                subscription.as_any().downcast_ref::<UtxosChangedSubscription>().unwrap(),
+                context,
+            ),
             _ => self.apply_overall_subscription(subscription.as_any().downcast_ref::<OverallSubscription>().unwrap(), context),
         }
     }
@@ -3,6 +3,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use derive_more::Display;
 use serde::{Deserialize, Serialize};
 use vecno_addresses::Address;
+use vecno_hashes::Hash;
 use workflow_serializer::prelude::*;
 
 macro_rules! scope_enum {
@@ -45,6 +46,7 @@ pub enum Scope {
     VirtualDaaScoreChanged,
     PruningPointUtxoSetOverride,
     NewBlockTemplate,
+    MempoolTransactionRemoved,
 }
 }
 
@@ -52,6 +54,25 @@ impl Scope {
     pub fn event_type(&self) -> EventType {
         self.into()
     }
+
+    /// Builds the scope of an event type filtered by addresses (see [`EventType::is_address_filtered`]).
+    /// An empty address set stands for all addresses.
+    pub fn with_addresses(event_type: EventType, addresses: Vec<Address>) -> Self {
+        match event_type {
+            EventType::UtxosChanged => UtxosChangedScope::new(addresses).into(),
+            EventType::MempoolTransactionRemoved => MempoolTransactionRemovedScope::new(addresses).into(),
+            _ => panic!("{} is not filtered by addresses", event_type),
+        }
+    }
+
+    /// Returns the addresses of a scope filtered by addresses, `None` for any other scope
+    pub fn into_addresses(self) -> Option<Vec<Address>> {
+        match self {
+            Scope::UtxosChanged(scope) => Some(scope.addresses),
+            Scope::MempoolTransactionRemoved(scope) => Some(scope.addresses),
+            _ => None,
+        }
+    }
 }
 
 impl Serializer for Scope {
@@ -89,33 +110,53 @@ impl Deserializer for BlockAddedScope {
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct VirtualChainChangedScope {
     pub include_accepted_transaction_ids: bool,
+    /// Last chain block known by the subscriber. If set, the chain changes since this block are replayed
+    /// before the live notifications.
+    ///
+    /// Notifiers ignore this field, the replay being handled by the RPC server the subscriber is connected to.
+    #[serde(default)]
+    pub start_hash: Option<Hash>,
 }
 
 impl VirtualChainChangedScope {
     pub fn new(include_accepted_transaction_ids: bool) -> Self {
-        Self { include_accepted_transaction_ids }
+        Self { include_accepted_transaction_ids, start_hash: None }
+    }
+
+    pub fn with_start_hash(include_accepted_transaction_ids: bool, start_hash: Hash) -> Self {
+        Self { include_accepted_transaction_ids, start_hash: Some(start_hash) }
     }
 }
 
 impl std::fmt::Display for VirtualChainChangedScope {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "VirtualChainChangedScope{}", if self.include_accepted_transaction_ids { " with accepted transactions" } else { "" })
+        write!(
+            f,
+            "VirtualChainChangedScope{}",
+            if self.include_accepted_transaction_ids { " with accepted transactions" } else { "" }
+        )?;
+        if let Some(start_hash) = self.start_hash {
+            write!(f, " from {}", start_hash)?;
+        }
+        Ok(())
     }
 }
 
 impl Serializer for VirtualChainChangedScope {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(bool, &self.include_accepted_transaction_ids, writer)?;
+        store!(Option<Hash>, &self.start_hash, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for VirtualChainChangedScope {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let include_accepted_transaction_ids = load!(bool, reader)?;
-        Ok(Self { include_accepted_transaction_ids })
+        let start_hash = if version > 1 { load!(Option<Hash>, reader)? } else { None };
+        Ok(Self { include_accepted_transaction_ids, start_hash })
     }
 }
 
@@ -266,3 +307,49 @@ impl Deserializer for NewBlockTemplateScope {
         Ok(Self {})
     }
 }
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct MempoolTransactionRemovedScope {
+    pub addresses: Vec<Address>,
+}
+
+impl std::fmt::Display for MempoolTransactionRemovedScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let addresses = match self.addresses.len() {
+            0 => "all".to_string(),
+            1 => format!("{}", self.addresses[0]),
+            n => format!("{} addresses", n),
+        };
+        write!(f, "MempoolTransactionRemovedScope ({})", addresses)
+    }
+}
+
+impl PartialEq for MempoolTransactionRemovedScope {
+    fn eq(&self, other: &Self) -> bool {
+        self.addresses.len() == other.addresses.len() && self.addresses.iter().all(|x| other.addresses.contains(x))
+    }
+}
+
+impl Eq for MempoolTransactionRemovedScope {}
+
+impl MempoolTransactionRemovedScope {
+    pub fn new(addresses: Vec<Address>) -> Self {
+        Self { addresses }
+    }
+}
+
+impl Serializer for MempoolTransactionRemovedScope {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<Address>, &self.addresses, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for MempoolTransactionRemovedScope {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let addresses = load!(Vec<Address>, reader)?;
+        Ok(Self { addresses })
+    }
+}
@@ -18,6 +18,12 @@ impl ArrayBuilder {
                     listener_id,
                     utxos_changed_capacity.unwrap_or_default(),
                 )),
+                EventType::MempoolTransactionRemoved => Arc::new(single::UtxosChangedSubscription::with_event_type(
+                    event_type,
+                    single::UtxosChangedState::None,
+                    listener_id,
+                    0,
+                )),
                 _ => Arc::new(single::OverallSubscription::new(event_type, false)),
             };
             subscription
@@ -32,6 +38,7 @@ impl ArrayBuilder {
                 EventType::UtxosChanged => {
                     Box::new(compounded::UtxosChangedSubscription::with_capacity(utxos_changed_capacity.unwrap_or_default()))
                 }
+                EventType::MempoolTransactionRemoved => Box::new(compounded::UtxosChangedSubscription::with_event_type(event_type, 0)),
                 _ => Box::new(compounded::OverallSubscription::new(event_type)),
             };
             subscription
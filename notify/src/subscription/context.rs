@@ -1,5 +1,6 @@
 use crate::{
     address::tracker::Tracker,
+    events::EventType,
     listener::ListenerId,
     subscription::{
         single::{UtxosChangedState, UtxosChangedSubscription},
@@ -15,6 +16,7 @@ use vecno_addresses::Address;
 pub struct SubscriptionContextInner {
     pub address_tracker: Tracker,
     pub utxos_changed_subscription_to_all: DynSubscription,
+    pub mempool_transaction_removed_subscription_to_all: DynSubscription,
 }
 
 impl SubscriptionContextInner {
@@ -25,18 +27,32 @@ impl SubscriptionContextInner {
     }
 
     pub fn with_options(max_addresses: Option<usize>) -> Self {
-        let address_tracker = Tracker::new(max_addresses);
-        let utxos_changed_subscription_all =
-            Arc::new(UtxosChangedSubscription::new(UtxosChangedState::All, Self::CONTEXT_LISTENER_ID));
-        Self { address_tracker, utxos_changed_subscription_to_all: utxos_changed_subscription_all }
+        Self::with_address_tracker(Tracker::new(max_addresses))
     }
 
     #[cfg(test)]
     pub fn with_addresses(addresses: &[Address]) -> Self {
-        let address_tracker = Tracker::with_addresses(addresses);
-        let utxos_changed_subscription_all =
-            Arc::new(UtxosChangedSubscription::new(UtxosChangedState::All, Self::CONTEXT_LISTENER_ID));
-        Self { address_tracker, utxos_changed_subscription_to_all: utxos_changed_subscription_all }
+        Self::with_address_tracker(Tracker::with_addresses(addresses))
+    }
+
+    fn with_address_tracker(address_tracker: Tracker) -> Self {
+        let subscription_to_all = |event_type| -> DynSubscription {
+            Arc::new(UtxosChangedSubscription::with_event_type(event_type, UtxosChangedState::All, Self::CONTEXT_LISTENER_ID, 0))
+        };
+        Self {
+            address_tracker,
+            utxos_changed_subscription_to_all: subscription_to_all(EventType::UtxosChanged),
+            mempool_transaction_removed_subscription_to_all: subscription_to_all(EventType::MempoolTransactionRemoved),
+        }
+    }
+
+    /// Returns the subscription to all addresses shared by all listeners of an event type filtered by addresses
+    pub fn subscription_to_all(&self, event_type: EventType) -> &DynSubscription {
+        match event_type {
+            EventType::UtxosChanged => &self.utxos_changed_subscription_to_all,
+            EventType::MempoolTransactionRemoved => &self.mempool_transaction_removed_subscription_to_all,
+            _ => panic!("{} is not filtered by addresses", event_type),
+        }
     }
 }
 
@@ -3,7 +3,7 @@ use crate::{
     error::Result,
     events::EventType,
     listener::ListenerId,
-    scope::{Scope, UtxosChangedScope, VirtualChainChangedScope},
+    scope::{Scope, VirtualChainChangedScope},
     subscription::{
         context::SubscriptionContext, BroadcastingSingle, Command, DynSubscription, Mutation, MutationOutcome, MutationPolicies,
         Single, Subscription, UtxosChangedMutationPolicy,
@@ -182,9 +182,9 @@ enum UtxosChangedMutation {
     All,
 }
 
-impl From<(Command, &UtxosChangedScope)> for UtxosChangedMutation {
-    fn from((command, scope): (Command, &UtxosChangedScope)) -> Self {
-        match (command, scope.addresses.is_empty()) {
+impl From<(Command, &[Address])> for UtxosChangedMutation {
+    fn from((command, addresses): (Command, &[Address])) -> Self {
+        match (command, addresses.is_empty()) {
             (Command::Stop, true) => Self::None,
             (Command::Stop, false) => Self::Remove,
             (Command::Start, false) => Self::Add,
@@ -306,8 +306,14 @@ impl Display for UtxosChangedSubscriptionData {
     }
 }
 
+/// Subscription with a scope restricted to a set of addresses.
+///
+/// To be used by all event types filtered by addresses (see [`EventType::is_address_filtered`]).
 #[derive(Debug)]
 pub struct UtxosChangedSubscription {
+    /// Event type of the subscription
+    event_type: EventType,
+
     /// Mutable inner data
     data: RwLock<UtxosChangedSubscriptionData>,
 
@@ -323,8 +329,13 @@ impl UtxosChangedSubscription {
     }
 
     pub fn with_capacity(state: UtxosChangedState, listener_id: ListenerId, capacity: usize) -> Self {
+        Self::with_event_type(EventType::UtxosChanged, state, listener_id, capacity)
+    }
+
+    pub fn with_event_type(event_type: EventType, state: UtxosChangedState, listener_id: ListenerId, capacity: usize) -> Self {
+        assert!(event_type.is_address_filtered());
         let data = RwLock::new(UtxosChangedSubscriptionData::with_capacity(state, capacity));
-        let subscription = Self { data, listener_id };
+        let subscription = Self { event_type, data, listener_id };
         trace!(
             "UtxosChangedSubscription: {} in total (new {})",
             UTXOS_CHANGED_SUBSCRIPTIONS.fetch_add(1, Ordering::SeqCst) + 1,
@@ -361,11 +372,15 @@ impl UtxosChangedSubscription {
     pub fn to_all(&self) -> bool {
         matches!(self.data().state, UtxosChangedState::All)
     }
+
+    fn scope_with(&self, addresses: Vec<Address>) -> Scope {
+        Scope::with_addresses(self.event_type, addresses)
+    }
 }
 
 impl Clone for UtxosChangedSubscription {
     fn clone(&self) -> Self {
-        let subscription = Self { data: RwLock::new(self.data().clone()), listener_id: self.listener_id };
+        let subscription = Self { event_type: self.event_type, data: RwLock::new(self.data().clone()), listener_id: self.listener_id };
         trace!(
             "UtxosChangedSubscription: {} in total (clone {})",
             UTXOS_CHANGED_SUBSCRIPTIONS.fetch_add(1, Ordering::SeqCst) + 1,
@@ -392,9 +407,9 @@ impl Drop for UtxosChangedSubscription {
 }
 
 impl PartialEq for UtxosChangedSubscription {
-    /// Equality is specifically bound to the listener ID
+    /// Equality is specifically bound to the event type and the listener ID
     fn eq(&self, other: &Self) -> bool {
-        self.listener_id == other.listener_id
+        self.event_type == other.event_type && self.listener_id == other.listener_id
     }
 }
 impl Eq for UtxosChangedSubscription {}
@@ -415,10 +430,10 @@ impl Single for UtxosChangedSubscription {
         context: &SubscriptionContext,
     ) -> Result<MutationOutcome> {
         assert_eq!(self.event_type(), mutation.event_type());
-        let outcome = if let Scope::UtxosChanged(scope) = mutation.scope {
+        let outcome = if let Some(addresses) = mutation.scope.into_addresses() {
             let mut data = self.data_mut();
             let state = data.state;
-            let mutation_type = UtxosChangedMutation::from((mutation.command, &scope));
+            let mutation_type = UtxosChangedMutation::from((mutation.command, addresses.as_slice()));
             match (state, mutation_type) {
                 (UtxosChangedState::None, UtxosChangedMutation::None | UtxosChangedMutation::Remove) => {
                     // State None + Mutations None or Remove(R) => No change
@@ -426,14 +441,14 @@ impl Single for UtxosChangedSubscription {
                 }
                 (UtxosChangedState::None, UtxosChangedMutation::Add) => {
                     // State None + Mutation Add(A) => Mutated new state Selected(A)
-                    let addresses = data.register(scope.addresses, context)?;
+                    let addresses = data.register(addresses, context)?;
                     data.update_state(UtxosChangedState::Selected);
                     let mutations = match policies.utxo_changed {
                         UtxosChangedMutationPolicy::AddressSet => {
-                            vec![Mutation::new(mutation.command, UtxosChangedScope::new(addresses).into())]
+                            vec![Mutation::new(mutation.command, self.scope_with(addresses))]
                         }
                         UtxosChangedMutationPolicy::Wildcard => {
-                            vec![Mutation::new(mutation.command, UtxosChangedScope::default().into())]
+                            vec![Mutation::new(mutation.command, self.scope_with(vec![]))]
                         }
                     };
                     MutationOutcome::with_mutated(current.clone(), mutations)
@@ -441,7 +456,7 @@ impl Single for UtxosChangedSubscription {
                 (UtxosChangedState::None, UtxosChangedMutation::All) => {
                     // State None + Mutation All => Mutated new state All
                     data.update_state(UtxosChangedState::All);
-                    let mutations = vec![Mutation::new(mutation.command, UtxosChangedScope::default().into())];
+                    let mutations = vec![Mutation::new(mutation.command, self.scope_with(vec![]))];
                     MutationOutcome::with_mutated(current.clone(), mutations)
                 }
                 (UtxosChangedState::Selected, UtxosChangedMutation::None) => {
@@ -451,22 +466,22 @@ impl Single for UtxosChangedSubscription {
                     assert!(!removed.is_empty(), "state Selected implies a non empty address set");
                     let mutations = match policies.utxo_changed {
                         UtxosChangedMutationPolicy::AddressSet => {
-                            vec![Mutation::new(Command::Stop, UtxosChangedScope::new(removed).into())]
+                            vec![Mutation::new(Command::Stop, self.scope_with(removed))]
                         }
                         UtxosChangedMutationPolicy::Wildcard => {
-                            vec![Mutation::new(Command::Stop, UtxosChangedScope::default().into())]
+                            vec![Mutation::new(Command::Stop, self.scope_with(vec![]))]
                         }
                     };
                     MutationOutcome::with_mutated(current.clone(), mutations)
                 }
                 (UtxosChangedState::Selected, UtxosChangedMutation::Remove) => {
                     // State Selected(S) + Mutation Remove(R) => Mutated state Selected(S – R) or mutated new state None or no change
-                    let removed = data.unregister(scope.addresses, context);
+                    let removed = data.unregister(addresses, context);
                     match (removed.is_empty(), data.indexes.is_empty()) {
                         (false, false) => {
                             let mutations = match policies.utxo_changed {
                                 UtxosChangedMutationPolicy::AddressSet => {
-                                    vec![Mutation::new(Command::Stop, UtxosChangedScope::new(removed).into())]
+                                    vec![Mutation::new(Command::Stop, self.scope_with(removed))]
                                 }
                                 UtxosChangedMutationPolicy::Wildcard => vec![],
                             };
@@ -476,10 +491,10 @@ impl Single for UtxosChangedSubscription {
                             data.update_state(UtxosChangedState::None);
                             let mutations = match policies.utxo_changed {
                                 UtxosChangedMutationPolicy::AddressSet => {
-                                    vec![Mutation::new(Command::Stop, UtxosChangedScope::new(removed).into())]
+                                    vec![Mutation::new(Command::Stop, self.scope_with(removed))]
                                 }
                                 UtxosChangedMutationPolicy::Wildcard => {
-                                    vec![Mutation::new(Command::Stop, UtxosChangedScope::default().into())]
+                                    vec![Mutation::new(Command::Stop, self.scope_with(vec![]))]
                                 }
                             };
                             MutationOutcome::with_mutated(current.clone(), mutations)
@@ -489,12 +504,12 @@ impl Single for UtxosChangedSubscription {
                 }
                 (UtxosChangedState::Selected, UtxosChangedMutation::Add) => {
                     // State Selected(S) + Mutation Add(A) => Mutated state Selected(A ∪ S)
-                    let added = data.register(scope.addresses, context)?;
+                    let added = data.register(addresses, context)?;
                     match added.is_empty() {
                         false => {
                             let mutations = match policies.utxo_changed {
                                 UtxosChangedMutationPolicy::AddressSet => {
-                                    vec![Mutation::new(Command::Start, UtxosChangedScope::new(added).into())]
+                                    vec![Mutation::new(Command::Start, self.scope_with(added))]
                                 }
                                 UtxosChangedMutationPolicy::Wildcard => vec![],
                             };
@@ -510,8 +525,8 @@ impl Single for UtxosChangedSubscription {
                     data.update_state(UtxosChangedState::All);
                     let mutations = match policies.utxo_changed {
                         UtxosChangedMutationPolicy::AddressSet => vec![
-                            Mutation::new(Command::Stop, UtxosChangedScope::new(removed).into()),
-                            Mutation::new(Command::Start, UtxosChangedScope::default().into()),
+                            Mutation::new(Command::Stop, self.scope_with(removed)),
+                            Mutation::new(Command::Start, self.scope_with(vec![])),
                         ],
                         UtxosChangedMutationPolicy::Wildcard => vec![],
                     };
@@ -520,7 +535,7 @@ impl Single for UtxosChangedSubscription {
                 (UtxosChangedState::All, UtxosChangedMutation::None) => {
                     // State All + Mutation None => Mutated new state None
                     data.update_state(UtxosChangedState::None);
-                    let mutations = vec![Mutation::new(Command::Stop, UtxosChangedScope::default().into())];
+                    let mutations = vec![Mutation::new(Command::Stop, self.scope_with(vec![]))];
                     MutationOutcome::with_mutated(current.clone(), mutations)
                 }
                 (UtxosChangedState::All, UtxosChangedMutation::Remove) => {
@@ -529,12 +544,12 @@ impl Single for UtxosChangedSubscription {
                 }
                 (UtxosChangedState::All, UtxosChangedMutation::Add) => {
                     // State All + Mutation Add(A) => Mutated new state Selectee(A)
-                    let added = data.register(scope.addresses, context)?;
+                    let added = data.register(addresses, context)?;
                     data.update_state(UtxosChangedState::Selected);
                     let mutations = match policies.utxo_changed {
                         UtxosChangedMutationPolicy::AddressSet => vec![
-                            Mutation::new(Command::Start, UtxosChangedScope::new(added).into()),
-                            Mutation::new(Command::Stop, UtxosChangedScope::default().into()),
+                            Mutation::new(Command::Start, self.scope_with(added)),
+                            Mutation::new(Command::Stop, self.scope_with(vec![])),
                         ],
                         UtxosChangedMutationPolicy::Wildcard => vec![],
                     };
@@ -554,7 +569,7 @@ impl Single for UtxosChangedSubscription {
 
 impl Subscription for UtxosChangedSubscription {
     fn event_type(&self) -> EventType {
-        EventType::UtxosChanged
+        self.event_type
     }
 
     fn active(&self) -> bool {
@@ -563,17 +578,17 @@ impl Subscription for UtxosChangedSubscription {
 
     fn scope(&self, context: &SubscriptionContext) -> Scope {
         // TODO: consider using a provided prefix
-        UtxosChangedScope::new(self.data().to_addresses(Prefix::Mainnet, context)).into()
+        self.scope_with(self.data().to_addresses(Prefix::Mainnet, context))
     }
 }
 
 impl BroadcastingSingle for DynSubscription {
     fn broadcasting(self, context: &SubscriptionContext) -> DynSubscription {
         match self.event_type() {
-            EventType::UtxosChanged => {
+            event_type if event_type.is_address_filtered() => {
                 let utxos_changed_subscription = self.as_any().downcast_ref::<UtxosChangedSubscription>().unwrap();
                 match utxos_changed_subscription.to_all() {
-                    true => context.utxos_changed_subscription_to_all.clone(),
+                    true => context.subscription_to_all(event_type).clone(),
                     false => self,
                 }
             }
@@ -586,7 +601,10 @@ impl BroadcastingSingle for DynSubscription {
 mod tests {
     use super::super::*;
     use super::*;
-    use crate::{address::test_helpers::get_3_addresses, scope::BlockAddedScope};
+    use crate::{
+        address::test_helpers::get_3_addresses,
+        scope::{BlockAddedScope, UtxosChangedScope},
+    };
     use std::collections::hash_map::DefaultHasher;
 
     #[test]
@@ -820,7 +838,7 @@ mod tests {
             Arc::new(VirtualChainChangedSubscription { active, include_accepted_transaction_ids })
         }
         fn m(command: Command, include_accepted_transaction_ids: bool) -> Mutation {
-            Mutation { command, scope: Scope::VirtualChainChanged(VirtualChainChangedScope { include_accepted_transaction_ids }) }
+            Mutation { command, scope: Scope::VirtualChainChanged(VirtualChainChangedScope::new(include_accepted_transaction_ids)) }
         }
 
         // Subscriptions
@@ -0,0 +1,322 @@
+//!
+//! Dandelion++ transaction relay, where a transaction first travels along a stem of single peers before being
+//! diffused to the whole network, so that its diffusion does not start from the node it originates from.
+//!
+
+use parking_lot::Mutex;
+use rand::{seq::SliceRandom, thread_rng, Rng};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    time::Duration,
+};
+use vecno_consensus_core::tx::{Transaction, TransactionId, TransactionOutpoint};
+use vecno_core::time::unix_now;
+use vecno_p2p_lib::PeerKey;
+
+/// Maximum number of transactions received from peers held in the stem phase, further transactions being diffused right
+/// away. Local transactions are held up to the same number on their own, so that peers cannot deprive them of the stem phase.
+pub const MAX_STEM_TRANSACTIONS: usize = 10_000;
+
+/// Maximum number of transactions received from a single peer held in the stem phase
+pub const MAX_STEM_TRANSACTIONS_PER_PEER: usize = 100;
+
+#[derive(Clone, Debug)]
+pub struct DandelionConfig {
+    /// Relay the transactions submitted locally through a stem phase before diffusing them
+    pub enabled: bool,
+    /// Duration over which the stem peer and the stem or fluff role of the node are kept
+    pub epoch_duration: Duration,
+    /// Time a transaction is held in the stem phase before being diffused by the node itself if it was not seen in
+    /// the mempool by then. A random delay of up to half the embargo is added for each transaction.
+    pub embargo_duration: Duration,
+    /// Probability for the node to diffuse the stem transactions it receives during an epoch rather than relaying them
+    pub fluff_probability: f64,
+}
+
+impl Default for DandelionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            epoch_duration: Duration::from_secs(10 * 60),
+            embargo_duration: Duration::from_secs(30),
+            fluff_probability: 0.1,
+        }
+    }
+}
+
+impl DandelionConfig {
+    pub fn is_valid(&self) -> bool {
+        !self.epoch_duration.is_zero() && !self.embargo_duration.is_zero() && (0.0..=1.0).contains(&self.fluff_probability)
+    }
+}
+
+struct Epoch {
+    ends_at: u64,
+    /// Whether the stem transactions received from peers are diffused during this epoch
+    is_fluff: bool,
+    stem_peer: Option<PeerKey>,
+}
+
+struct StemTransaction {
+    transaction: Transaction,
+    /// Peer the transaction was received from, `None` for local transactions
+    origin: Option<PeerKey>,
+    embargo_deadline: u64,
+}
+
+#[derive(Default)]
+struct Stempool {
+    transactions: HashMap<TransactionId, StemTransaction>,
+    /// Held transaction spending each outpoint, so that no conflicting transactions are relayed
+    spenders: HashMap<TransactionOutpoint, TransactionId>,
+    /// Number of held transactions by origin
+    origin_counts: HashMap<Option<PeerKey>, usize>,
+}
+
+impl Stempool {
+    fn origin_count(&self, origin: &Option<PeerKey>) -> usize {
+        self.origin_counts.get(origin).copied().unwrap_or_default()
+    }
+
+    fn insert(&mut self, transaction_id: TransactionId, entry: StemTransaction) {
+        self.spenders.extend(entry.transaction.inputs.iter().map(|input| (input.previous_outpoint, transaction_id)));
+        *self.origin_counts.entry(entry.origin).or_default() += 1;
+        self.transactions.insert(transaction_id, entry);
+    }
+
+    fn remove(&mut self, transaction_id: &TransactionId) -> Option<StemTransaction> {
+        let entry = self.transactions.remove(transaction_id)?;
+        entry.transaction.inputs.iter().for_each(|input| {
+            self.spenders.remove(&input.previous_outpoint);
+        });
+        if let Entry::Occupied(mut count) = self.origin_counts.entry(entry.origin) {
+            *count.get_mut() -= 1;
+            if *count.get() == 0 {
+                count.remove();
+            }
+        }
+        Some(entry)
+    }
+}
+
+/// Outcome of holding a transaction in the stem phase
+#[derive(Debug, PartialEq, Eq)]
+pub enum Embargo {
+    /// The transaction is held until its embargo expires
+    Held,
+    /// There is no room for the transaction, which is to be diffused right away
+    Full,
+    /// The transaction spends an outpoint already spent by a held transaction
+    Conflict(TransactionOutpoint, TransactionId),
+}
+
+/// Routes the transactions in the stem phase and holds them until they are seen diffused or their embargo expires
+pub struct DandelionRelay {
+    config: DandelionConfig,
+    epoch: Mutex<Epoch>,
+    stempool: Mutex<Stempool>,
+}
+
+impl DandelionRelay {
+    pub fn new(config: DandelionConfig) -> Self {
+        Self { config, epoch: Mutex::new(Epoch { ends_at: 0, is_fluff: false, stem_peer: None }), stempool: Default::default() }
+    }
+
+    pub fn config(&self) -> &DandelionConfig {
+        &self.config
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Returns the peer a transaction is to be relayed to in the stem phase, `None` meaning that it is to be diffused.
+    /// Local transactions are always stemmed when a stem peer is available, while the ones received from peers are
+    /// diffused during fluff epochs. `candidates` are the outbound peers accepting stem transactions.
+    pub fn stem_peer(&self, candidates: &[PeerKey], is_local: bool) -> Option<PeerKey> {
+        self.stem_peer_at(candidates, is_local, unix_now())
+    }
+
+    /// Holds `transaction`, received from `origin` or submitted locally if `None`, in the stem phase until its embargo expires
+    pub fn embargo(&self, transaction: Transaction, origin: Option<PeerKey>) -> Embargo {
+        self.embargo_at(transaction, origin, unix_now())
+    }
+
+    /// Indicates whether the transaction is currently held in the stem phase
+    pub fn contains(&self, transaction_id: &TransactionId) -> bool {
+        self.stempool.lock().transactions.contains_key(transaction_id)
+    }
+
+    /// Removes the transactions whose embargo expired and returns them, each along with whether it was submitted locally
+    pub fn take_expired(&self) -> Vec<(Transaction, bool)> {
+        self.take_expired_at(unix_now())
+    }
+
+    fn stem_peer_at(&self, candidates: &[PeerKey], is_local: bool, now: u64) -> Option<PeerKey> {
+        let mut epoch = self.epoch.lock();
+        if now >= epoch.ends_at {
+            let mut rng = thread_rng();
+            *epoch = Epoch {
+                ends_at: now + self.config.epoch_duration.as_millis() as u64,
+                is_fluff: rng.gen_bool(self.config.fluff_probability),
+                stem_peer: candidates.choose(&mut rng).copied(),
+            };
+        } else if !epoch.stem_peer.is_some_and(|peer| candidates.contains(&peer)) {
+            // The stem peer disconnected, so another one is picked for the rest of the epoch
+            epoch.stem_peer = candidates.choose(&mut thread_rng()).copied();
+        }
+        if epoch.is_fluff && !is_local {
+            return None;
+        }
+        epoch.stem_peer
+    }
+
+    fn embargo_at(&self, transaction: Transaction, origin: Option<PeerKey>, now: u64) -> Embargo {
+        let mut stempool = self.stempool.lock();
+        let transaction_id = transaction.id();
+        if stempool.transactions.contains_key(&transaction_id) {
+            return Embargo::Held;
+        }
+        if let Some((outpoint, spender)) = transaction
+            .inputs
+            .iter()
+            .find_map(|input| stempool.spenders.get(&input.previous_outpoint).map(|spender| (input.previous_outpoint, *spender)))
+        {
+            return Embargo::Conflict(outpoint, spender);
+        }
+        let is_full = match origin {
+            None => stempool.origin_count(&None) >= MAX_STEM_TRANSACTIONS,
+            Some(_) => {
+                stempool.origin_count(&origin) >= MAX_STEM_TRANSACTIONS_PER_PEER
+                    || stempool.transactions.len() - stempool.origin_count(&None) >= MAX_STEM_TRANSACTIONS
+            }
+        };
+        if is_full {
+            return Embargo::Full;
+        }
+        let embargo = self.config.embargo_duration.as_millis() as u64;
+        let embargo_deadline = now + embargo + thread_rng().gen_range(0..=embargo / 2);
+        stempool.insert(transaction_id, StemTransaction { transaction, origin, embargo_deadline });
+        Embargo::Held
+    }
+
+    fn take_expired_at(&self, now: u64) -> Vec<(Transaction, bool)> {
+        let mut stempool = self.stempool.lock();
+        let expired =
+            stempool.transactions.iter().filter_map(|(id, entry)| (entry.embargo_deadline <= now).then_some(*id)).collect::<Vec<_>>();
+        expired.into_iter().filter_map(|id| stempool.remove(&id)).map(|entry| (entry.transaction, entry.origin.is_none())).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use uuid::Uuid;
+    use vecno_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{TransactionInput, TransactionOutpoint},
+    };
+    use vecno_hashes::Hash;
+    use vecno_utils::networking::IpAddress;
+
+    fn peer_key() -> PeerKey {
+        PeerKey::new(Uuid::new_v4().into(), IpAddress::from_str("1.2.3.4").unwrap())
+    }
+
+    fn transaction(index: u64) -> Transaction {
+        let inputs = vec![TransactionInput::new(TransactionOutpoint::new(Hash::from_u64_word(index), 0), vec![], 0, 1)];
+        Transaction::new(0, inputs, vec![], 0, SUBNETWORK_ID_NATIVE, 0, vec![])
+    }
+
+    #[test]
+    fn test_stem_routing() {
+        let relay = DandelionRelay::new(DandelionConfig { fluff_probability: 0.0, ..Default::default() });
+        let epoch = relay.config().epoch_duration.as_millis() as u64;
+        assert_eq!(relay.stem_peer_at(&[], true, 0), None);
+
+        // The stem peer is kept for the whole epoch
+        let peers = (0..8).map(|_| peer_key()).collect::<Vec<_>>();
+        let stem_peer = relay.stem_peer_at(&peers, true, 0).unwrap();
+        assert!(peers.contains(&stem_peer));
+        assert_eq!(relay.stem_peer_at(&peers, false, 1), Some(stem_peer));
+
+        // A new stem peer is picked once the current one disconnects
+        let remaining = peers.iter().copied().filter(|&peer| peer != stem_peer).collect::<Vec<_>>();
+        let new_stem_peer = relay.stem_peer_at(&remaining, false, 2).unwrap();
+        assert_ne!(new_stem_peer, stem_peer);
+        assert!(relay.stem_peer_at(&remaining, false, epoch).is_some());
+
+        // During fluff epochs only local transactions are stemmed
+        let relay = DandelionRelay::new(DandelionConfig { fluff_probability: 1.0, ..Default::default() });
+        assert!(relay.stem_peer_at(&peers, true, 0).is_some());
+        assert_eq!(relay.stem_peer_at(&peers, false, 0), None);
+    }
+
+    #[test]
+    fn test_embargo() {
+        let relay = DandelionRelay::new(DandelionConfig::default());
+        let embargo = relay.config().embargo_duration.as_millis() as u64;
+        let peer = peer_key();
+        assert_eq!(relay.embargo_at(transaction(1), None, 0), Embargo::Held);
+        assert_eq!(relay.embargo_at(transaction(2), Some(peer), 0), Embargo::Held);
+        assert_eq!(relay.embargo_at(transaction(1), Some(peer), embargo), Embargo::Held);
+        assert!(relay.contains(&transaction(1).id()));
+        assert!(!relay.contains(&transaction(3).id()));
+
+        assert!(relay.take_expired_at(embargo - 1).is_empty());
+        let mut expired = relay.take_expired_at(embargo + embargo / 2);
+        expired.sort_by_key(|(_, is_local)| !is_local);
+        assert_eq!(
+            expired.iter().map(|(tx, is_local)| (tx.id(), *is_local)).collect::<Vec<_>>(),
+            vec![(transaction(1).id(), true), (transaction(2).id(), false)]
+        );
+        assert!(!relay.contains(&transaction(1).id()));
+    }
+
+    #[test]
+    fn test_conflicting_transactions() {
+        let relay = DandelionRelay::new(DandelionConfig::default());
+        let embargo = relay.config().embargo_duration.as_millis() as u64;
+        let (peer, other_peer) = (peer_key(), peer_key());
+        let double_spend = Transaction::new(0, transaction(1).inputs, vec![], 1, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        let outpoint = double_spend.inputs[0].previous_outpoint;
+
+        assert_eq!(relay.embargo_at(transaction(1), Some(peer), 0), Embargo::Held);
+        assert_eq!(relay.embargo_at(double_spend.clone(), Some(other_peer), 0), Embargo::Conflict(outpoint, transaction(1).id()));
+        assert_eq!(relay.embargo_at(double_spend.clone(), None, 0), Embargo::Conflict(outpoint, transaction(1).id()));
+        assert!(!relay.contains(&double_spend.id()));
+
+        // The outpoint is released along with its spender
+        assert_eq!(relay.take_expired_at(2 * embargo).len(), 1);
+        assert_eq!(relay.embargo_at(double_spend.clone(), Some(other_peer), 0), Embargo::Held);
+    }
+
+    #[test]
+    fn test_full_stempool() {
+        let relay = DandelionRelay::new(DandelionConfig::default());
+        let embargo = relay.config().embargo_duration.as_millis() as u64;
+
+        // Each peer holds a limited share of the stempool
+        let peer = peer_key();
+        for index in 0..MAX_STEM_TRANSACTIONS_PER_PEER as u64 {
+            assert_eq!(relay.embargo_at(transaction(index), Some(peer), 0), Embargo::Held);
+        }
+        assert_eq!(relay.embargo_at(transaction(0), Some(peer), 0), Embargo::Held);
+        assert_eq!(relay.embargo_at(transaction(MAX_STEM_TRANSACTIONS as u64), Some(peer), 0), Embargo::Full);
+        assert_eq!(relay.take_expired_at(2 * embargo).len(), MAX_STEM_TRANSACTIONS_PER_PEER);
+        assert_eq!(relay.embargo_at(transaction(MAX_STEM_TRANSACTIONS as u64), Some(peer), 0), Embargo::Held);
+        relay.take_expired_at(2 * embargo);
+
+        // Peers fill the stempool up to its capacity, which leaves room for local transactions
+        let mut peer = peer;
+        for index in 0..MAX_STEM_TRANSACTIONS as u64 {
+            if index % MAX_STEM_TRANSACTIONS_PER_PEER as u64 == 0 {
+                peer = peer_key();
+            }
+            assert_eq!(relay.embargo_at(transaction(index), Some(peer), 0), Embargo::Held);
+        }
+        assert_eq!(relay.embargo_at(transaction(MAX_STEM_TRANSACTIONS as u64), Some(peer_key()), 0), Embargo::Full);
+        assert_eq!(relay.embargo_at(transaction(MAX_STEM_TRANSACTIONS as u64), None, 0), Embargo::Held);
+    }
+}
@@ -1,10 +1,16 @@
 use crate::flowcontext::{
+    compact_blocks::CompactBlockCounters,
     orphans::{OrphanBlocksPool, OrphanOutput},
     process_queue::ProcessQueue,
     transactions::TransactionsSpread,
 };
-use crate::{v5, v6};
+use crate::{
+    dandelion::{DandelionConfig, DandelionRelay, Embargo},
+    misbehavior::{misbehavior_weight, MisbehaviorConfig, MisbehaviorTracker},
+    v5, v6,
+};
 use async_trait::async_trait;
+use duration_string::DurationString;
 use futures::future::join_all;
 use parking_lot::{Mutex, RwLock};
 use std::collections::HashMap;
@@ -39,19 +45,26 @@ use vecno_consensus_notify::{
 use vecno_consensusmanager::{BlockProcessingBatch, ConsensusInstance, ConsensusManager, ConsensusProxy};
 use vecno_core::{
     debug, info,
-    task::tick::TickService,
+    task::tick::{TickReason, TickService},
     vecnod_env::{name, version},
 };
 use vecno_core::{time::unix_now, warn};
 use vecno_hashes::Hash;
-use vecno_mining::mempool::tx::{Orphan, Priority};
+use vecno_mining::mempool::{
+    errors::RuleError as MempoolRuleError,
+    tx::{Orphan, Priority},
+};
+use vecno_mining::{errors::MiningManagerError, model::tx_query::TransactionQuery};
 use vecno_mining::{manager::MiningManagerProxy, mempool::tx::RbfPolicy};
 use vecno_notify::notifier::Notify;
 use vecno_p2p_lib::{
     common::ProtocolError,
-    convert::model::version::Version,
+    convert::model::version::{
+        Version, SERVICE_FLAG_COMPACT_BLOCKS, SERVICE_FLAG_DANDELION, SERVICE_FLAG_ENCRYPTED_TRANSPORT, SERVICE_FLAG_ONION_ADDRESSES,
+        SERVICE_FLAG_PACKAGE_RELAY,
+    },
     make_message,
-    pb::{vecnod_message::Payload, InvRelayBlockMessage},
+    pb::{vecnod_message::Payload, InvRelayBlockMessage, StemTransactionMessage, TransactionPackageMessage},
     ConnectionInitializer, Hub, PeerKey, PeerProperties, Router, VecnodHandshake,
 };
 use vecno_utils::iter::IterExtensions;
@@ -69,6 +82,9 @@ const MAX_ORPHANS_UPPER_BOUND: usize = 1024;
 /// The min time to wait before allowing another parallel request
 const REQUEST_SCOPE_WAIT_TIME: Duration = Duration::from_secs(1);
 
+/// The interval at which the embargoes of the Dandelion++ stem transactions are checked
+const DANDELION_EMBARGO_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Represents a block event to be logged
 #[derive(Debug, PartialEq)]
 pub enum BlockLogEvent {
@@ -221,6 +237,9 @@ pub struct FlowContextInner {
     ibd_metadata: Arc<RwLock<Option<IbdMetadata>>>,
     pub address_manager: Arc<Mutex<AddressManager>>,
     connection_manager: RwLock<Option<Arc<ConnectionManager>>>,
+    misbehavior_tracker: MisbehaviorTracker,
+    compact_block_counters: CompactBlockCounters,
+    dandelion: DandelionRelay,
     mining_manager: MiningManagerProxy,
     pub(crate) tick_service: Arc<TickService>,
     notification_root: Arc<ConsensusNotificationRoot>,
@@ -303,6 +322,8 @@ impl FlowContext {
         mining_manager: MiningManagerProxy,
         tick_service: Arc<TickService>,
         notification_root: Arc<ConsensusNotificationRoot>,
+        misbehavior_config: MisbehaviorConfig,
+        dandelion_config: DandelionConfig,
     ) -> Self {
         let hub = Hub::new();
 
@@ -324,6 +345,9 @@ impl FlowContext {
                 hub,
                 address_manager,
                 connection_manager: Default::default(),
+                misbehavior_tracker: MisbehaviorTracker::new(misbehavior_config),
+                compact_block_counters: Default::default(),
+                dandelion: DandelionRelay::new(dandelion_config),
                 mining_manager,
                 tick_service,
                 notification_root,
@@ -351,6 +375,10 @@ impl FlowContext {
         if let Some(logger) = self.block_event_logger.as_ref() {
             logger.start();
         }
+        if self.dandelion.is_enabled() {
+            let context = self.clone();
+            tokio::spawn(async move { context.fluff_embargoed_transactions().await });
+        }
     }
 
     pub fn set_connection_manager(&self, connection_manager: Arc<ConnectionManager>) {
@@ -365,6 +393,33 @@ impl FlowContext {
         self.connection_manager.read().clone()
     }
 
+    pub fn misbehavior_tracker(&self) -> &MisbehaviorTracker {
+        &self.misbehavior_tracker
+    }
+
+    pub fn compact_block_counters(&self) -> &CompactBlockCounters {
+        &self.compact_block_counters
+    }
+
+    pub fn dandelion(&self) -> &DandelionRelay {
+        &self.dandelion
+    }
+
+    /// Adds the misbehavior score of `err` to the peer of `router`, banning its IP once the ban threshold is reached
+    pub async fn report_misbehavior(&self, router: &Router, err: &ProtocolError) {
+        let weight = misbehavior_weight(err);
+        if weight == 0 {
+            return;
+        }
+        let ip = router.net_address().ip();
+        if let Some(duration) = self.misbehavior_tracker.report(ip.into(), weight) {
+            if let Some(connection_manager) = self.connection_manager() {
+                warn!("Banning peer {} for {} after misbehaving: {}", router, DurationString::from(duration), err);
+                connection_manager.ban(ip, duration).await;
+            }
+        }
+    }
+
     pub fn consensus(&self) -> ConsensusInstance {
         self.consensus_manager.consensus()
     }
@@ -612,12 +667,21 @@ impl FlowContext {
     /// Transactions submitted through rpc are considered high priority. This definition does not affect the tx selection algorithm
     /// but only changes how we manage the lifetime of the tx. A high-priority tx does not expire and is repeatedly rebroadcasted to
     /// peers
+    ///
+    /// With Dandelion++ enabled, the transaction is only validated and sent to the stem peer, entering the local mempool once it is
+    /// diffused back by the network or when its embargo expires. Orphans are diffused right away.
     pub async fn submit_rpc_transaction(
         &self,
         consensus: &ConsensusProxy,
         transaction: Transaction,
         orphan: Orphan,
     ) -> Result<(), ProtocolError> {
+        if let Some(stem_peer) = self.dandelion_stem_peer(true) {
+            let is_orphan = self.test_stem_transaction(consensus, &transaction, orphan).await?;
+            if !is_orphan && self.stem_transaction(stem_peer, transaction.clone(), None).await? {
+                return Ok(());
+            }
+        }
         let transaction_insertion = self
             .mining_manager()
             .clone()
@@ -660,6 +724,156 @@ impl FlowContext {
         ))
     }
 
+    /// Adds the rpc-submitted transaction package to the mempool and propagates it to peers.
+    ///
+    /// The package transactions are announced to all peers as regular transactions and the package itself is
+    /// relayed to peers supporting package relay, so that low-fee parents reach them along with their children.
+    pub async fn submit_rpc_transaction_package(
+        &self,
+        consensus: &ConsensusProxy,
+        transactions: Vec<Transaction>,
+    ) -> Result<(), ProtocolError> {
+        let accepted_transactions =
+            self.mining_manager().clone().validate_and_insert_transaction_package(consensus, transactions, Priority::High).await?;
+        self.broadcast_transactions(
+            accepted_transactions.iter().map(|x| x.id()),
+            false, // RPC transactions are considered high priority, so we don't want to throttle them
+        )
+        .await;
+        self.relay_transaction_package(&accepted_transactions, None).await;
+        Ok(())
+    }
+
+    /// Sends the transaction package to all peers supporting package relay, except for the peer it was received from.
+    pub async fn relay_transaction_package(&self, transactions: &[Arc<Transaction>], origin: Option<PeerKey>) {
+        if transactions.is_empty() {
+            return;
+        }
+        let msg = make_message!(
+            Payload::TransactionPackage,
+            TransactionPackageMessage { transactions: transactions.iter().map(|x| (&**x).into()).collect() }
+        );
+        self.hub
+            .broadcast_to_matching_peers(msg, |router| Some(router.key()) != origin && router.properties().supports_package_relay())
+            .await;
+    }
+
+    /// Relays a transaction received from `origin` in the stem phase of Dandelion++, either to the stem peer of the epoch or,
+    /// during fluff epochs, by adding it to the mempool and diffusing it.
+    pub async fn relay_stem_transaction(
+        &self,
+        consensus: &ConsensusProxy,
+        transaction: Transaction,
+        origin: PeerKey,
+    ) -> Result<(), ProtocolError> {
+        let transaction_id = transaction.id();
+        if self.dandelion.contains(&transaction_id)
+            || self.mining_manager().clone().has_transaction(transaction_id, TransactionQuery::All).await
+        {
+            return Ok(());
+        }
+        if let Some(stem_peer) = self.dandelion_stem_peer(false) {
+            self.test_stem_transaction(consensus, &transaction, Orphan::Forbidden).await?;
+            if self.stem_transaction(stem_peer, transaction.clone(), Some(origin)).await? {
+                return Ok(());
+            }
+        }
+        let transaction_insertion = self
+            .mining_manager()
+            .clone()
+            .validate_and_insert_transaction(consensus, transaction, Priority::Low, Orphan::Forbidden, RbfPolicy::Allowed)
+            .await?;
+        self.broadcast_transactions(transaction_insertion.accepted.iter().map(|x| x.id()), false).await;
+        Ok(())
+    }
+
+    /// Returns the outbound peer a transaction is to be relayed to in the stem phase, `None` meaning that it is to be
+    /// diffused, either because Dandelion++ is disabled, no peer accepts stem transactions or the epoch is a fluff one.
+    fn dandelion_stem_peer(&self, is_local: bool) -> Option<PeerKey> {
+        if !self.dandelion.is_enabled() {
+            return None;
+        }
+        let candidates = self
+            .hub
+            .active_peers()
+            .into_iter()
+            .filter(|peer| peer.is_outbound() && peer.properties().supports_dandelion())
+            .map(|peer| peer.key())
+            .collect::<Vec<_>>();
+        self.dandelion.stem_peer(&candidates, is_local)
+    }
+
+    /// Validates the transaction against the mempool without adding it and returns whether it is an orphan
+    async fn test_stem_transaction(
+        &self,
+        consensus: &ConsensusProxy,
+        transaction: &Transaction,
+        orphan: Orphan,
+    ) -> Result<bool, ProtocolError> {
+        let acceptance = self
+            .mining_manager()
+            .clone()
+            .test_mempool_accept(consensus, vec![transaction.clone()], orphan, RbfPolicy::Forbidden)
+            .await
+            .pop()
+            .expect("a single transaction was tested");
+        match acceptance.error {
+            Some(err) => Err(MiningManagerError::MempoolError(err).into()),
+            None => Ok(acceptance.is_orphan),
+        }
+    }
+
+    /// Holds the transaction under embargo and sends it to `stem_peer`. Returns false if the transaction cannot be held,
+    /// in which case it is to be diffused right away, and an error if it conflicts with a held transaction.
+    async fn stem_transaction(
+        &self,
+        stem_peer: PeerKey,
+        transaction: Transaction,
+        origin: Option<PeerKey>,
+    ) -> Result<bool, ProtocolError> {
+        let msg = make_message!(Payload::StemTransaction, StemTransactionMessage { transaction: Some((&transaction).into()) });
+        match self.dandelion.embargo(transaction, origin) {
+            Embargo::Held => {}
+            Embargo::Full => return Ok(false),
+            Embargo::Conflict(outpoint, spender) => {
+                return Err(MiningManagerError::MempoolError(MempoolRuleError::RejectDoubleSpendInStempool(outpoint, spender)).into())
+            }
+        }
+        // A transaction which fails to reach the stem peer is diffused once its embargo expires
+        let _ = self.hub.send(stem_peer, msg).await;
+        Ok(true)
+    }
+
+    /// Diffuses the stem transactions whose embargo expired before they were seen in the mempool, until the node shuts down
+    async fn fluff_embargoed_transactions(self) {
+        while let TickReason::Wakeup = self.tick_service.tick(DANDELION_EMBARGO_CHECK_INTERVAL).await {
+            let expired = self.dandelion.take_expired();
+            if expired.is_empty() {
+                continue;
+            }
+            let session = self.consensus().unguarded_session();
+            for (transaction, is_local) in expired {
+                let transaction_id = transaction.id();
+                if self.mining_manager().clone().has_transaction(transaction_id, TransactionQuery::All).await {
+                    continue;
+                }
+                debug!("Embargo of stem transaction {} expired, diffusing it", transaction_id);
+                let priority = if is_local { Priority::High } else { Priority::Low };
+                match self
+                    .mining_manager()
+                    .clone()
+                    .validate_and_insert_transaction(&session, transaction, priority, Orphan::Allowed, RbfPolicy::Forbidden)
+                    .await
+                {
+                    Ok(transaction_insertion) => {
+                        self.broadcast_transactions(transaction_insertion.accepted.iter().map(|x| x.id()), false).await
+                    }
+                    Err(err) => debug!("Embargoed transaction {} was rejected: {}", transaction_id, err),
+                }
+            }
+        }
+    }
+
     /// Returns true if the time has come for running the task cleaning mempool transactions.
     async fn should_run_mempool_scanning_task(&self) -> bool {
         self.transactions_spread.write().await.should_run_mempool_scanning_task()
@@ -691,6 +905,23 @@ impl FlowContext {
 #[async_trait]
 impl ConnectionInitializer for FlowContext {
     async fn initialize_connection(&self, router: Arc<Router>) -> Result<(), ProtocolError> {
+        let res = self.handshake_and_register_flows(router.clone()).await;
+        if let Err(err) = &res {
+            // Peers failing the handshake are scored like peers failing a flow
+            self.report_misbehavior(&router, err).await;
+        }
+        res
+    }
+}
+
+impl FlowContext {
+    async fn handshake_and_register_flows(&self, router: Arc<Router>) -> Result<(), ProtocolError> {
+        if let Some(connection_manager) = self.connection_manager() {
+            if connection_manager.is_banned(&router.net_address()).await {
+                return Err(ProtocolError::Other("peer is banned"));
+            }
+        }
+
         // Build the handshake object and subscribe to handshake messages
         let mut handshake = VecnodHandshake::new(&router);
 
@@ -699,13 +930,20 @@ impl ConnectionInitializer for FlowContext {
 
         let network_name = self.config.network_name();
 
-        let local_address = self.address_manager.lock().best_local_address();
+        // An onion service is only advertised through the addresses messages of the peers able to decode it
+        let local_address = self.address_manager.lock().best_local_address().filter(|address| !address.is_onion());
 
         // Build the local version message
         // Subnets are not currently supported
         let mut self_version_message = Version::new(local_address, self.node_id, network_name.clone(), None, PROTOCOL_VERSION);
         self_version_message.add_user_agent(name(), version(), &self.config.user_agent_comments);
-        // TODO: get number of live services
+        self_version_message.services |= SERVICE_FLAG_PACKAGE_RELAY | SERVICE_FLAG_COMPACT_BLOCKS | SERVICE_FLAG_ONION_ADDRESSES;
+        if self.config.p2p_encryption {
+            self_version_message.services |= SERVICE_FLAG_ENCRYPTED_TRANSPORT;
+        }
+        if self.dandelion.is_enabled() {
+            self_version_message.services |= SERVICE_FLAG_DANDELION;
+        }
         // TODO: disable_relay_tx from config/cmd
 
         // Perform the handshake
@@ -728,6 +966,11 @@ impl ConnectionInitializer for FlowContext {
             return Err(ProtocolError::WrongNetwork(network_name, peer_version.network));
         }
 
+        // A peer seemingly not supporting encryption might as well have had its version message stripped of the service
+        if self.config.p2p_require_encryption && !router.is_encrypted() {
+            return Err(ProtocolError::Other("peer does not support the required encrypted transport"));
+        }
+
         debug!("protocol versions - self: {}, peer: {}", PROTOCOL_VERSION, peer_version.protocol_version);
 
         // Register all flows according to version
@@ -740,6 +983,7 @@ impl ConnectionInitializer for FlowContext {
         // Build and register the peer properties
         let peer_properties = Arc::new(PeerProperties {
             user_agent: peer_version.user_agent.to_owned(),
+            services: peer_version.services,
             advertised_protocol_version: peer_version.protocol_version,
             protocol_version: applied_protocol_version,
             disable_relay_tx: peer_version.disable_relay_tx,
@@ -751,11 +995,16 @@ impl ConnectionInitializer for FlowContext {
         // Send and receive the ready signal
         handshake.exchange_ready_messages().await?;
 
-        info!("Registering p2p flows for peer {} for protocol version {}", router, applied_protocol_version);
+        info!(
+            "Registering p2p flows for peer {} for protocol version {}{}",
+            router,
+            applied_protocol_version,
+            if router.is_encrypted() { " over an encrypted connection" } else { "" }
+        );
 
         // Launch all flows. Note we launch only after the ready signal was exchanged
         for flow in flows {
-            flow.launch();
+            flow.launch(self.clone());
         }
 
         if router.is_outbound() || peer_version.address.is_some() {
@@ -1,3 +1,4 @@
+use crate::flow_context::FlowContext;
 use std::sync::Arc;
 use vecno_core::warn;
 use vecno_p2p_lib::{common::ProtocolError, Router};
@@ -16,12 +17,13 @@ where
 
     async fn start(&mut self) -> Result<(), ProtocolError>;
 
-    fn launch(mut self: Box<Self>) {
+    fn launch(mut self: Box<Self>, ctx: FlowContext) {
         tokio::spawn(async move {
             let res = self.start().await;
             if let Err(err) = res {
                 if let Some(router) = self.router() {
                     router.try_sending_reject_message(&err).await;
+                    ctx.report_misbehavior(&router, &err).await;
                     if router.close().await || !err.is_connection_closed_error() {
                         warn!("{} flow error: {}, disconnecting from peer {}.", self.name(), err, router);
                     }
@@ -0,0 +1,75 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counters of the compact block relay, accumulated over all peers
+#[derive(Default)]
+pub struct CompactBlockCounters {
+    /// Compact blocks rebuilt from the mempool and the prefilled transactions alone
+    pub reconstructed_counts: AtomicU64,
+    /// Compact blocks completed with transactions requested from the peer
+    pub completed_counts: AtomicU64,
+    /// Compact blocks which could not be rebuilt and were requested again as full blocks
+    pub fallback_counts: AtomicU64,
+    /// Transactions of compact blocks found in the mempool
+    pub mempool_tx_counts: AtomicU64,
+    /// Transactions of compact blocks missing from the mempool and requested from the peer
+    pub requested_tx_counts: AtomicU64,
+}
+
+impl CompactBlockCounters {
+    pub fn snapshot(&self) -> CompactBlockCountersSnapshot {
+        CompactBlockCountersSnapshot {
+            reconstructed_counts: self.reconstructed_counts.load(Ordering::Relaxed),
+            completed_counts: self.completed_counts.load(Ordering::Relaxed),
+            fallback_counts: self.fallback_counts.load(Ordering::Relaxed),
+            mempool_tx_counts: self.mempool_tx_counts.load(Ordering::Relaxed),
+            requested_tx_counts: self.requested_tx_counts.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CompactBlockCountersSnapshot {
+    pub reconstructed_counts: u64,
+    pub completed_counts: u64,
+    pub fallback_counts: u64,
+    pub mempool_tx_counts: u64,
+    pub requested_tx_counts: u64,
+}
+
+impl CompactBlockCountersSnapshot {
+    /// Ratio of the compact block transactions found in the mempool, 1 if no transaction was looked up yet
+    pub fn mempool_hit_rate(&self) -> f64 {
+        match self.mempool_tx_counts + self.requested_tx_counts {
+            0 => 1.0,
+            total => self.mempool_tx_counts as f64 / total as f64,
+        }
+    }
+
+    /// Ratio of the compact blocks rebuilt without any request to the peer, 1 if no compact block was received yet
+    pub fn reconstruction_rate(&self) -> f64 {
+        match self.reconstructed_counts + self.completed_counts + self.fallback_counts {
+            0 => 1.0,
+            total => self.reconstructed_counts as f64 / total as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_block_rates() {
+        let counters = CompactBlockCounters::default();
+        assert_eq!(counters.snapshot().mempool_hit_rate(), 1.0);
+        assert_eq!(counters.snapshot().reconstruction_rate(), 1.0);
+
+        counters.reconstructed_counts.fetch_add(3, Ordering::Relaxed);
+        counters.fallback_counts.fetch_add(1, Ordering::Relaxed);
+        counters.mempool_tx_counts.fetch_add(90, Ordering::Relaxed);
+        counters.requested_tx_counts.fetch_add(10, Ordering::Relaxed);
+        let snapshot = counters.snapshot();
+        assert_eq!(snapshot.mempool_hit_rate(), 0.9);
+        assert_eq!(snapshot.reconstruction_rate(), 0.75);
+    }
+}
@@ -1,6 +1,9 @@
+pub mod dandelion;
 pub mod flow_context;
 pub mod flow_trait;
 pub mod flowcontext;
+pub mod misbehavior;
 pub mod service;
+pub mod snapshot;
 pub mod v5;
 pub mod v6;
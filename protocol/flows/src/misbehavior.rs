@@ -0,0 +1,207 @@
+use parking_lot::Mutex;
+use std::{collections::HashMap, time::Duration};
+use vecno_consensus_core::errors::{block::RuleError, tx::TxRuleError};
+use vecno_core::time::unix_now;
+use vecno_p2p_lib::common::ProtocolError;
+use vecno_utils::networking::IpAddress;
+
+/// Bans get doubled as a peer keeps misbehaving, up to this many times the base ban duration
+pub const MAX_BAN_DURATION_FACTOR: u32 = 8;
+
+/// Scores and ban records decayed below this are forgotten
+const NEGLIGIBLE_RECORD: f64 = 0.01;
+
+/// Returns the misbehavior score a peer gets for a flow failing with `err`, 0 meaning that the peer is not to blame
+pub fn misbehavior_weight(err: &ProtocolError) -> u32 {
+    match err {
+        ProtocolError::RuleError(err) => rule_error_weight(err),
+        ProtocolError::PruningImportError(_) => 50,
+        ProtocolError::ConversionError(_) | ProtocolError::IdentityError(_) => 50,
+        ProtocolError::MisbehavingPeer(_) => 25,
+        ProtocolError::UnexpectedMessage(..) | ProtocolError::NoRouteForMessageType(_) => 20,
+        ProtocolError::IncomingRouteCapacityReached(..) | ProtocolError::Timeout(_) => 10,
+        ProtocolError::WrongNetwork(..) | ProtocolError::VersionMismatch(..) => 10,
+        _ => 0,
+    }
+}
+
+fn rule_error_weight(err: &RuleError) -> u32 {
+    match err {
+        // Data no honest peer can produce whatever its view of the DAG
+        RuleError::InvalidPoW
+        | RuleError::BadMerkleRoot(..)
+        | RuleError::BadUTXOCommitment(..)
+        | RuleError::BadAcceptedIDMerkleRoot(..) => 100,
+        RuleError::TxInIsolationValidationFailed(_, err) | RuleError::TxInContextFailed(_, err)
+            if matches!(err, TxRuleError::SignatureInvalid(_) | TxRuleError::SignatureEmpty(_)) =>
+        {
+            100
+        }
+        // Errors depending on the local state or clock of the node, which an honest peer might not share
+        RuleError::MissingParents(_)
+        | RuleError::TimeTooFarIntoTheFuture(..)
+        | RuleError::PrunedBlock
+        | RuleError::PruningViolation(_)
+        | RuleError::InsufficientDaaWindowSize(_)
+        | RuleError::InvalidTransactionsInNewBlock(_) => 0,
+        RuleError::KnownInvalid | RuleError::TxInContextFailed(..) | RuleError::InvalidTransactionsInUtxoContext(..) => 10,
+        _ => 50,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MisbehaviorConfig {
+    /// Score at which a peer gets banned
+    pub ban_threshold: u32,
+    /// Duration of the first ban of a peer, doubled on each later ban up to [`MAX_BAN_DURATION_FACTOR`] times.
+    /// The past bans of a peer are forgotten at the pace of one per longest ban duration.
+    pub ban_duration: Duration,
+    /// Time for the score of a peer to halve
+    pub score_half_life: Duration,
+}
+
+impl Default for MisbehaviorConfig {
+    fn default() -> Self {
+        Self { ban_threshold: 100, ban_duration: Duration::from_secs(3 * 60 * 60), score_half_life: Duration::from_secs(60 * 60) }
+    }
+}
+
+impl MisbehaviorConfig {
+    pub fn is_valid(&self) -> bool {
+        self.ban_threshold > 0 && !self.ban_duration.is_zero() && !self.score_half_life.is_zero()
+    }
+
+    /// Duration of the bans of the peers which kept misbehaving, also applying to the bans requested through RPC
+    pub fn max_ban_duration(&self) -> Duration {
+        self.ban_duration * MAX_BAN_DURATION_FACTOR
+    }
+}
+
+struct MisbehaviorRecord {
+    score: f64,
+    /// Decayed count of the past bans
+    bans: f64,
+    updated_at: u64,
+}
+
+/// Keeps the misbehavior scores of the peers by IP, so that they survive reconnections
+pub struct MisbehaviorTracker {
+    config: MisbehaviorConfig,
+    records: Mutex<HashMap<IpAddress, MisbehaviorRecord>>,
+}
+
+impl MisbehaviorTracker {
+    pub fn new(config: MisbehaviorConfig) -> Self {
+        Self { config, records: Default::default() }
+    }
+
+    pub fn config(&self) -> &MisbehaviorConfig {
+        &self.config
+    }
+
+    /// Adds `weight` to the score of `ip` and returns the duration `ip` must be banned for if the ban threshold is reached
+    pub fn report(&self, ip: IpAddress, weight: u32) -> Option<Duration> {
+        self.report_at(ip, weight, unix_now())
+    }
+
+    /// Returns the current score of `ip`
+    pub fn score(&self, ip: IpAddress) -> u32 {
+        self.score_at(ip, unix_now())
+    }
+
+    fn report_at(&self, ip: IpAddress, weight: u32, now: u64) -> Option<Duration> {
+        let mut records = self.records.lock();
+        records.retain(|_, record| {
+            self.decay(record, now);
+            record.score >= NEGLIGIBLE_RECORD || record.bans >= NEGLIGIBLE_RECORD
+        });
+        let record = records.entry(ip).or_insert(MisbehaviorRecord { score: 0.0, bans: 0.0, updated_at: now });
+        record.score += weight as f64;
+        if record.score < self.config.ban_threshold as f64 {
+            return None;
+        }
+        let factor = 2u32.saturating_pow(record.bans.round() as u32).min(MAX_BAN_DURATION_FACTOR);
+        record.score = 0.0;
+        record.bans += 1.0;
+        Some(self.config.ban_duration * factor)
+    }
+
+    fn score_at(&self, ip: IpAddress, now: u64) -> u32 {
+        let mut records = self.records.lock();
+        records.get_mut(&ip).map_or(0, |record| {
+            self.decay(record, now);
+            record.score.round() as u32
+        })
+    }
+
+    fn decay(&self, record: &mut MisbehaviorRecord, now: u64) {
+        let elapsed = now.saturating_sub(record.updated_at) as f64;
+        record.score *= 0.5f64.powf(elapsed / self.config.score_half_life.as_millis() as f64);
+        record.bans = (record.bans - elapsed / self.config.max_ban_duration().as_millis() as f64).max(0.0);
+        record.updated_at = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use vecno_txscript_errors::TxScriptError;
+
+    const HOUR: u64 = 60 * 60 * 1000;
+
+    #[test]
+    fn test_misbehavior_weights() {
+        assert_eq!(misbehavior_weight(&ProtocolError::RuleError(RuleError::InvalidPoW)), 100);
+        assert_eq!(misbehavior_weight(&ProtocolError::RuleError(RuleError::BadMerkleRoot(1.into(), 2.into()))), 100);
+        assert_eq!(misbehavior_weight(&ProtocolError::RuleError(RuleError::NoTransactions)), 50);
+        assert_eq!(misbehavior_weight(&ProtocolError::RuleError(RuleError::KnownInvalid)), 10);
+        assert_eq!(misbehavior_weight(&ProtocolError::RuleError(RuleError::MissingParents(vec![1.into()]))), 0);
+        assert_eq!(misbehavior_weight(&ProtocolError::RuleError(RuleError::TimeTooFarIntoTheFuture(2, 1))), 0);
+        let tx_error = |err| ProtocolError::RuleError(RuleError::TxInContextFailed(1.into(), err));
+        assert_eq!(misbehavior_weight(&tx_error(TxRuleError::SignatureEmpty(TxScriptError::EvalFalse))), 100);
+        assert_eq!(misbehavior_weight(&tx_error(TxRuleError::SequenceLockConditionsAreNotMet)), 10);
+        assert_eq!(misbehavior_weight(&ProtocolError::WrongNetwork("vecno-mainnet".to_string(), "vecno-testnet".to_string())), 10);
+        assert_eq!(misbehavior_weight(&ProtocolError::UnexpectedMessage("Verack", None)), 20);
+        assert_eq!(misbehavior_weight(&ProtocolError::Timeout(Duration::from_secs(1))), 10);
+        assert_eq!(misbehavior_weight(&ProtocolError::ConnectionClosed), 0);
+        assert_eq!(misbehavior_weight(&ProtocolError::Rejected("reason".to_string())), 0);
+    }
+
+    #[test]
+    fn test_graduated_bans() {
+        let config = MisbehaviorConfig::default();
+        let tracker = MisbehaviorTracker::new(config.clone());
+        let ip = IpAddress::from_str("1.2.3.4").unwrap();
+
+        // Bans double with each one
+        assert_eq!(tracker.report_at(ip, 60, 0), None);
+        assert_eq!(tracker.score_at(ip, 0), 60);
+        assert_eq!(tracker.report_at(ip, 60, 0), Some(config.ban_duration));
+        assert_eq!(tracker.score_at(ip, 0), 0);
+        assert_eq!(tracker.report_at(ip, 100, 0), Some(config.ban_duration * 2));
+        assert_eq!(tracker.report_at(ip, 100, 0), Some(config.ban_duration * 4));
+        assert_eq!(tracker.report_at(ip, 100, 0), Some(config.ban_duration * 8));
+        assert_eq!(tracker.report_at(ip, 100, 0), Some(config.max_ban_duration()));
+
+        // The ban record of a peer is forgotten as time passes
+        let later = 5 * config.max_ban_duration().as_millis() as u64;
+        assert_eq!(tracker.report_at(ip, 100, later), Some(config.ban_duration));
+    }
+
+    #[test]
+    fn test_score_decay() {
+        let tracker = MisbehaviorTracker::new(MisbehaviorConfig::default());
+        let ip = IpAddress::from_str("1.2.3.4").unwrap();
+        let other_ip = IpAddress::from_str("::1").unwrap();
+
+        assert_eq!(tracker.report_at(ip, 80, 0), None);
+        assert_eq!(tracker.score_at(ip, HOUR), 40);
+        assert_eq!(tracker.report_at(ip, 50, HOUR), None);
+        assert_eq!(tracker.score_at(other_ip, HOUR), 0);
+
+        // Negligible records are dropped
+        assert_eq!(tracker.report_at(other_ip, 10, 24 * HOUR), None);
+        assert_eq!(tracker.records.lock().len(), 1);
+    }
+}
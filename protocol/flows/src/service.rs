@@ -6,7 +6,7 @@ use vecno_core::{
     task::service::{AsyncService, AsyncServiceFuture},
     trace,
 };
-use vecno_p2p_lib::Adaptor;
+use vecno_p2p_lib::{Adaptor, ProxyConfig};
 use vecno_utils::triggers::SingleTrigger;
 use vecno_utils_tower::counters::TowerConnectionCounters;
 
@@ -65,9 +65,17 @@ impl AsyncService for P2pService {
         // Prepare a shutdown signal receiver
         let shutdown_signal = self.shutdown.listener.clone();
 
-        let p2p_adaptor =
-            Adaptor::bidirectional(self.listen, self.flow_context.hub().clone(), self.flow_context.clone(), self.counters.clone())
-                .unwrap();
+        let config = &self.flow_context.config;
+        let proxy_config =
+            ProxyConfig { proxy: config.p2p_proxy.map(Into::into), onion_proxy: config.p2p_onion_proxy.map(Into::into) };
+        let p2p_adaptor = Adaptor::bidirectional(
+            self.listen,
+            self.flow_context.hub().clone(),
+            self.flow_context.clone(),
+            self.counters.clone(),
+            proxy_config,
+        )
+        .unwrap();
         let connection_manager = ConnectionManager::new(
             p2p_adaptor.clone(),
             self.outbound_target,
@@ -83,7 +91,7 @@ impl AsyncService for P2pService {
         // Launch the service and wait for a shutdown signal
         Box::pin(async move {
             for peer_address in self.connect_peers.iter().cloned().chain(self.add_peers.iter().cloned()) {
-                connection_manager.add_connection_request(peer_address.into(), true).await;
+                connection_manager.add_connection_request(peer_address, true).await;
             }
 
             // Keep the P2P server running until a service shutdown signal is received
@@ -0,0 +1,646 @@
+//!
+//! Node snapshots, bootstrapping a node from a file rather than from the pruning point data sent by an IBD peer.
+//!
+//! A snapshot holds the pruning proof, the past pruning points, the trusted data of the pruning point anticone, the
+//! headers from the pruning point up to the headers selected tip and the pruning point UTXO set. These are stored as
+//! the very p2p messages a syncer sends during IBD, so that an import goes through the validations of an IBD with
+//! headers proof, ending with the verification of the UTXO set against the pruning point UTXO commitment.
+//!
+//! File layout: magic, format version (u32 LE), network id (u8 length followed by the bytes), a sequence of protobuf
+//! encoded messages each prefixed with its length (u32 LE), and the SHA-256 checksum of all the preceding bytes.
+//!
+
+use futures::future::try_join_all;
+use log::{debug, info};
+use prost::Message;
+use sha2::{Digest, Sha256};
+use std::{
+    cmp::max,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+    sync::Arc,
+};
+use thiserror::Error;
+use vecno_consensus_core::{
+    block::Block,
+    config::Config,
+    errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError},
+    header::Header,
+    pruning::{PruningPointProof, PruningPointsList, PruningProofMetadata},
+};
+use vecno_consensusmanager::{spawn_blocking, ConsensusInstance, ConsensusManager, ConsensusProxy};
+use vecno_hashes::Hash;
+use vecno_muhash::MuHash;
+use vecno_p2p_lib::{
+    common::ProtocolError,
+    convert::{
+        error::ConversionError,
+        model::trusted::{TrustedDataEntry, TrustedDataPackage},
+    },
+    make_message,
+    pb::{
+        self, vecnod_message::Payload, BlockHeadersMessage, BlockWithTrustedDataV4Message, DoneBlocksWithTrustedDataMessage,
+        DoneHeadersMessage, DonePruningPointUtxoSetChunksMessage, PruningPointProofMessage, PruningPointUtxoSetChunkMessage,
+        PruningPointsMessage, TrustedDataMessage, VecnodMessage,
+    },
+};
+
+const MAGIC: &[u8; 8] = b"VECNOSNP";
+const VERSION: u32 = 1;
+
+/// Number of UTXOs per chunk, matching the chunks sent to IBD peers
+const UTXO_CHUNK_SIZE: usize = 1000;
+
+/// Number of headers per chunk, matching the chunks sent to IBD peers
+const HEADERS_CHUNK_SIZE: usize = 1 << 10;
+
+/// Upper bound of the size of a message, protecting the reader from a corrupted length
+const MAX_MESSAGE_SIZE: usize = 1 << 30;
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("snapshot I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("malformed snapshot message: {0}")]
+    Decode(#[from] prost::DecodeError),
+
+    #[error("{0}")]
+    ConversionError(#[from] ConversionError),
+
+    #[error("{0}")]
+    RuleError(#[from] RuleError),
+
+    #[error("{0}")]
+    PruningImportError(#[from] PruningImportError),
+
+    #[error("{0}")]
+    ConsensusError(#[from] ConsensusError),
+
+    #[error("{0}")]
+    ProtocolError(#[from] ProtocolError),
+
+    #[error("not a snapshot file")]
+    InvalidMagic,
+
+    #[error("unsupported snapshot version {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("snapshot of network {0} cannot be imported by a node of network {1}")]
+    WrongNetwork(String, String),
+
+    #[error("snapshot message of {0} bytes exceeds the maximum size")]
+    MessageTooLarge(usize),
+
+    #[error("expected snapshot message {0}")]
+    UnexpectedMessage(&'static str),
+
+    #[error("snapshot checksum mismatch")]
+    ChecksumMismatch,
+
+    #[error("{0}")]
+    Other(&'static str),
+}
+
+pub type SnapshotResult<T> = Result<T, SnapshotError>;
+
+/// Reads the next message of the snapshot, expecting it to be of type `$pattern`
+macro_rules! read_message {
+    ($reader:expr, $pattern:path) => {
+        match $reader.read_payload()? {
+            $pattern(msg) => msg,
+            _ => return Err(SnapshotError::UnexpectedMessage(stringify!($pattern))),
+        }
+    };
+}
+
+struct SnapshotWriter<W: Write> {
+    inner: W,
+    hasher: Sha256,
+}
+
+impl<W: Write> SnapshotWriter<W> {
+    fn new(inner: W, network: &str) -> SnapshotResult<Self> {
+        let mut writer = Self { inner, hasher: Sha256::new() };
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&[network.len() as u8])?;
+        writer.write_all(network.as_bytes())?;
+        Ok(writer)
+    }
+
+    fn write_all(&mut self, bytes: &[u8]) -> SnapshotResult<()> {
+        self.hasher.update(bytes);
+        self.inner.write_all(bytes)?;
+        Ok(())
+    }
+
+    fn write_message(&mut self, msg: VecnodMessage) -> SnapshotResult<()> {
+        let bytes = msg.encode_to_vec();
+        self.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        self.write_all(&bytes)
+    }
+
+    /// Appends the checksum, completing the snapshot
+    fn finish(mut self) -> SnapshotResult<W> {
+        let checksum = self.hasher.finalize();
+        self.inner.write_all(&checksum)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+struct SnapshotReader<R: Read> {
+    inner: R,
+    hasher: Sha256,
+}
+
+impl<R: Read> SnapshotReader<R> {
+    fn new(inner: R, network: &str) -> SnapshotResult<Self> {
+        let mut reader = Self { inner, hasher: Sha256::new() };
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(SnapshotError::InvalidMagic);
+        }
+        let mut version = [0u8; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let mut len = [0u8; 1];
+        reader.read_exact(&mut len)?;
+        let mut snapshot_network = vec![0u8; len[0] as usize];
+        reader.read_exact(&mut snapshot_network)?;
+        let snapshot_network = String::from_utf8_lossy(&snapshot_network);
+        if snapshot_network != network {
+            return Err(SnapshotError::WrongNetwork(snapshot_network.into_owned(), network.to_owned()));
+        }
+        Ok(reader)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> SnapshotResult<()> {
+        self.inner.read_exact(buf)?;
+        self.hasher.update(&*buf);
+        Ok(())
+    }
+
+    fn read_payload(&mut self) -> SnapshotResult<Payload> {
+        let mut len = [0u8; 4];
+        self.read_exact(&mut len)?;
+        let len = u32::from_le_bytes(len) as usize;
+        if len > MAX_MESSAGE_SIZE {
+            return Err(SnapshotError::MessageTooLarge(len));
+        }
+        let mut bytes = vec![0u8; len];
+        self.read_exact(&mut bytes)?;
+        VecnodMessage::decode(bytes.as_slice())?.payload.ok_or(SnapshotError::UnexpectedMessage("with a payload"))
+    }
+
+    /// Verifies the checksum of all the bytes read so far, which must be followed by the end of the file
+    fn finish(mut self) -> SnapshotResult<()> {
+        let expected = self.hasher.finalize();
+        let mut checksum = [0u8; 32];
+        self.inner.read_exact(&mut checksum)?;
+        if checksum[..] != expected[..] {
+            return Err(SnapshotError::ChecksumMismatch);
+        }
+        if self.inner.read(&mut [0u8; 1])? != 0 {
+            return Err(SnapshotError::Other("unexpected data following the snapshot checksum"));
+        }
+        Ok(())
+    }
+}
+
+/// Writes a snapshot of the current pruning point of `consensus` to `path`, returning the pruning point
+pub async fn export_snapshot(consensus: &ConsensusInstance, config: &Config, path: &Path) -> SnapshotResult<Hash> {
+    // The session is held throughout the export so that the pruning point cannot move in the meantime
+    let session = consensus.session().await;
+    let pruning_point = session.async_pruning_point().await;
+    if pruning_point == config.genesis.hash {
+        return Err(SnapshotError::Other("the pruning point is the genesis block, the node has nothing to export yet"));
+    }
+    info!("Exporting a snapshot of pruning point {} to {}", pruning_point, path.display());
+
+    let mut writer = SnapshotWriter::new(BufWriter::new(File::create(path)?), &config.net.to_string())?;
+
+    let proof = session.async_get_pruning_point_proof().await;
+    writer.write_message(make_message!(
+        Payload::PruningPointProof,
+        PruningPointProofMessage { headers: proof.iter().map(|headers| headers.into()).collect() }
+    ))?;
+
+    let pp_headers = session.async_pruning_point_headers().await;
+    writer.write_message(make_message!(
+        Payload::PruningPoints,
+        PruningPointsMessage { headers: pp_headers.into_iter().map(|header| <pb::BlockHeader>::from(&*header)).collect() }
+    ))?;
+
+    let trusted_data = session.async_get_pruning_point_anticone_and_trusted_data().await?;
+    writer.write_message(make_message!(
+        Payload::TrustedData,
+        TrustedDataMessage {
+            daa_window: trusted_data.daa_window_blocks.iter().map(|daa_block| daa_block.into()).collect(),
+            ghostdag_data: trusted_data.ghostdag_blocks.iter().map(|gd| gd.into()).collect()
+        }
+    ))?;
+    for &hash in trusted_data.anticone.iter() {
+        let block = session.async_get_block(hash).await?;
+        writer.write_message(make_message!(
+            Payload::BlockWithTrustedDataV4,
+            BlockWithTrustedDataV4Message { block: Some((&block).into()), ..Default::default() }
+        ))?;
+    }
+    writer.write_message(make_message!(Payload::DoneBlocksWithTrustedData, DoneBlocksWithTrustedDataMessage {}))?;
+
+    // Internal consensus logic requires that `max_blocks > mergeset_size_limit`
+    let max_blocks = max(HEADERS_CHUNK_SIZE, config.mergeset_size_limit as usize + 1);
+    let headers_selected_tip = session.async_get_headers_selected_tip().await;
+    let mut low = pruning_point;
+    while low != headers_selected_tip {
+        let hashes = session.async_get_hashes_between(low, headers_selected_tip, max_blocks).await?.0;
+        low = *hashes.last().expect("low and high are valid and different");
+        let mut block_headers = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            block_headers.push(<pb::BlockHeader>::from(&*session.async_get_header(hash).await?));
+        }
+        writer.write_message(make_message!(Payload::BlockHeaders, BlockHeadersMessage { block_headers }))?;
+    }
+    writer.write_message(make_message!(Payload::DoneHeaders, DoneHeadersMessage {}))?;
+
+    let mut from_outpoint = None;
+    let mut utxo_count = 0;
+    loop {
+        let utxos =
+            session.async_get_pruning_point_utxos(pruning_point, from_outpoint, UTXO_CHUNK_SIZE, from_outpoint.is_some()).await?;
+        utxo_count += utxos.len();
+        writer.write_message(make_message!(
+            Payload::PruningPointUtxoSetChunk,
+            PruningPointUtxoSetChunkMessage {
+                outpoint_and_utxo_entry_pairs: utxos.iter().map(|(outpoint, entry)| (outpoint, entry).into()).collect()
+            }
+        ))?;
+        // This indicates that there are no more entries to query
+        if utxos.len() < UTXO_CHUNK_SIZE {
+            break;
+        }
+        // Mark the beginning of the next chunk
+        from_outpoint = Some(utxos.last().expect("not empty by prev condition").0);
+    }
+    writer.write_message(make_message!(Payload::DonePruningPointUtxoSetChunks, DonePruningPointUtxoSetChunksMessage {}))?;
+    writer.finish()?;
+
+    info!(
+        "Exported a snapshot of pruning point {} with {} anticone blocks and {} UTXOs",
+        pruning_point,
+        trusted_data.anticone.len(),
+        utxo_count
+    );
+    Ok(pruning_point)
+}
+
+/// Imports the snapshot at `path` into a staging consensus, which replaces the current consensus once the snapshot
+/// is fully validated. Returns the imported pruning point.
+pub async fn import_snapshot(consensus_manager: &Arc<ConsensusManager>, config: &Config, path: &Path) -> SnapshotResult<Hash> {
+    info!("Importing the snapshot {}", path.display());
+    let mut reader = SnapshotReader::new(BufReader::new(File::open(path)?), &config.net.to_string())?;
+
+    let staging = consensus_manager.new_staging_consensus();
+    let staging_session = staging.session().await;
+    let result = async {
+        let pruning_point = import_into_staging(&consensus_manager.consensus(), &staging_session, config, &mut reader).await?;
+        // Nothing gets committed unless the whole file matches its checksum
+        reader.finish()?;
+        SnapshotResult::Ok(pruning_point)
+    }
+    .await;
+    drop(staging_session);
+
+    match result {
+        Ok(pruning_point) => {
+            spawn_blocking(|| staging.commit()).await.unwrap();
+            info!("Imported the snapshot of pruning point {}. Committed staging consensus.", pruning_point);
+            Ok(pruning_point)
+        }
+        Err(err) => {
+            spawn_blocking(|| staging.cancel()).await.unwrap();
+            Err(err)
+        }
+    }
+}
+
+/// Mirrors the steps of an IBD with headers proof, the messages being read from the snapshot instead of a peer
+async fn import_into_staging<R: Read>(
+    consensus: &ConsensusInstance,
+    staging: &ConsensusProxy,
+    config: &Config,
+    reader: &mut SnapshotReader<R>,
+) -> SnapshotResult<Hash> {
+    let proof: PruningPointProof = read_message!(reader, Payload::PruningPointProof).try_into()?;
+    debug!("read proof with overall {} headers", proof.iter().map(|l| l.len()).sum::<usize>());
+    let pruning_points: PruningPointsList = read_message!(reader, Payload::PruningPoints).try_into()?;
+    let pkg: TrustedDataPackage = read_message!(reader, Payload::TrustedData).try_into()?;
+    debug!("read trusted data with {} daa entries and {} ghostdag entries", pkg.daa_window.len(), pkg.ghostdag_window.len());
+    let mut entries: Vec<TrustedDataEntry> = Vec::new();
+    loop {
+        match reader.read_payload()? {
+            Payload::BlockWithTrustedDataV4(msg) => entries.push(msg.try_into()?),
+            Payload::DoneBlocksWithTrustedData(_) => break,
+            _ => return Err(SnapshotError::UnexpectedMessage("BlockWithTrustedDataV4 or DoneBlocksWithTrustedData")),
+        }
+    }
+
+    let Some(proof_pruning_point) = proof.first().and_then(|level| level.last()).map(|header| header.hash) else {
+        return Err(SnapshotError::Other("the snapshot proof is empty"));
+    };
+    if proof_pruning_point == config.genesis.hash {
+        return Err(SnapshotError::Other("the snapshot pruning point is the genesis block"));
+    }
+    if pruning_points.is_empty() || pruning_points.last().unwrap().hash != proof_pruning_point {
+        return Err(SnapshotError::Other("the proof pruning point is not equal to the last pruning point in the list"));
+    }
+    if pruning_points.first().unwrap().hash != config.genesis.hash {
+        return Err(SnapshotError::Other("the first pruning point in the list is expected to be genesis"));
+    }
+    if entries.first().map(|entry| entry.block.hash()) != Some(proof_pruning_point) {
+        return Err(SnapshotError::Other("the proof pruning point is not equal to the expected trusted entry"));
+    }
+
+    // The headers selected tip of the snapshot stands for the relay block of IBD when validating the proof,
+    // so the headers are read beforehand and only converted once submitted
+    let mut header_chunks = Vec::new();
+    loop {
+        match reader.read_payload()? {
+            Payload::BlockHeaders(msg) => header_chunks.push(msg),
+            Payload::DoneHeaders(_) => break,
+            _ => return Err(SnapshotError::UnexpectedMessage("BlockHeaders or DoneHeaders")),
+        }
+    }
+    let tip_blue_work = match header_chunks.last().and_then(|msg| msg.block_headers.last()) {
+        Some(header) => Header::try_from(header.clone())?.blue_work,
+        None => entries[0].block.header.blue_work,
+    };
+
+    // The proof and the pruning points are validated in the context of current consensus
+    let current = consensus.session().await;
+    let proof_metadata = PruningProofMetadata::new(tip_blue_work);
+    let proof = current.clone().spawn_blocking(move |c| c.validate_pruning_proof(&proof, &proof_metadata).map(|()| proof)).await?;
+    if proof_pruning_point == current.async_pruning_point().await {
+        return Err(SnapshotError::Other("the snapshot pruning point is the same as the current pruning point"));
+    }
+    if current.async_are_pruning_points_violating_finality(pruning_points.clone()).await {
+        return Err(SnapshotError::Other("the snapshot pruning points are violating finality"));
+    }
+    drop(current);
+
+    let trusted_set = pkg.build_trusted_subdag(entries)?;
+    let trusted_set = staging
+        .clone()
+        .spawn_blocking(move |c| {
+            c.apply_pruning_proof(proof, &trusted_set)?;
+            c.import_pruning_points(pruning_points);
+            SnapshotResult::Ok(trusted_set)
+        })
+        .await?;
+    info!("Processing {} trusted blocks", trusted_set.len());
+    for tb in trusted_set {
+        staging.validate_and_insert_trusted_block(tb).virtual_state_task.await?;
+    }
+
+    info!("Processing {} block headers", header_chunks.iter().map(|msg| msg.block_headers.len()).sum::<usize>());
+    for msg in header_chunks {
+        let chunk: Vec<Arc<Header>> = msg.try_into()?;
+        let jobs =
+            chunk.into_iter().map(|header| staging.validate_and_insert_block(Block::from_header_arc(header)).virtual_state_task);
+        try_join_all(jobs).await?;
+    }
+    staging.async_validate_pruning_points().await?;
+    validate_staging_timestamps(&consensus.session().await, staging).await?;
+
+    let mut multiset = MuHash::new();
+    let mut utxo_count = 0;
+    loop {
+        match reader.read_payload()? {
+            Payload::PruningPointUtxoSetChunk(msg) => {
+                let chunk: Vec<_> = msg.try_into()?;
+                utxo_count += chunk.len();
+                multiset = staging
+                    .clone()
+                    .spawn_blocking(move |c| {
+                        c.append_imported_pruning_point_utxos(&chunk, &mut multiset);
+                        multiset
+                    })
+                    .await;
+            }
+            Payload::DonePruningPointUtxoSetChunks(_) => break,
+            _ => return Err(SnapshotError::UnexpectedMessage("PruningPointUtxoSetChunk or DonePruningPointUtxoSetChunks")),
+        }
+    }
+    info!("Verifying {} UTXOs against the commitment of pruning point {}", utxo_count, proof_pruning_point);
+    staging.clone().spawn_blocking(move |c| c.import_pruning_point_utxo_set(proof_pruning_point, multiset)).await?;
+
+    Ok(proof_pruning_point)
+}
+
+/// Rejects snapshots which are not significantly ahead of current consensus, as IBD does
+async fn validate_staging_timestamps(consensus: &ConsensusProxy, staging: &ConsensusProxy) -> SnapshotResult<()> {
+    let staging_hst = staging.async_get_header(staging.async_get_headers_selected_tip().await).await?;
+    let current_hst = consensus.async_get_header(consensus.async_get_headers_selected_tip().await).await?;
+    if staging_hst.timestamp < current_hst.timestamp || staging_hst.timestamp - current_hst.timestamp < 600_000 {
+        return Err(SnapshotError::Other("the snapshot is not ahead of the current selected tip by at least 10 minutes"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_channel::{unbounded, Receiver};
+    use std::time::{Duration, Instant};
+    use vecno_consensus::{consensus::factory::Factory as ConsensusFactory, pipeline::ProcessingCounters};
+    use vecno_consensus_core::{
+        block::{TemplateBuildMode, TemplateTransactionSelector},
+        coinbase::MinerData,
+        config::{params::SIMNET_PARAMS, ConfigBuilder},
+        muhash::MuHashExtensions,
+        tx::{ScriptPublicKey, Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
+    };
+    use vecno_consensus_notify::{notification::Notification, root::ConsensusNotificationRoot};
+    use vecno_core::core::Core;
+    use vecno_database::{prelude::ConnBuilder, utils::get_vecno_tempdir};
+    use vecno_txscript::caches::TxScriptCacheCounters;
+
+    fn write_snapshot(network: &str) -> Vec<u8> {
+        let mut writer = SnapshotWriter::new(Vec::new(), network).unwrap();
+        writer.write_message(make_message!(Payload::DoneHeaders, DoneHeadersMessage {})).unwrap();
+        writer.write_message(make_message!(Payload::DonePruningPointUtxoSetChunks, DonePruningPointUtxoSetChunksMessage {})).unwrap();
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let bytes = write_snapshot("vecno-simnet");
+        let mut reader = SnapshotReader::new(bytes.as_slice(), "vecno-simnet").unwrap();
+        assert!(matches!(reader.read_payload().unwrap(), Payload::DoneHeaders(_)));
+        assert!(matches!(reader.read_payload().unwrap(), Payload::DonePruningPointUtxoSetChunks(_)));
+        reader.finish().unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_corruption() {
+        let bytes = write_snapshot("vecno-simnet");
+        assert!(matches!(SnapshotReader::new(bytes.as_slice(), "vecno-mainnet"), Err(SnapshotError::WrongNetwork(_, _))));
+        assert!(matches!(SnapshotReader::new(&bytes[1..], "vecno-simnet"), Err(SnapshotError::InvalidMagic)));
+
+        // Flip a bit of the last message
+        let mut corrupted = bytes.clone();
+        let index = corrupted.len() - 33;
+        corrupted[index] ^= 1;
+        let mut reader = SnapshotReader::new(corrupted.as_slice(), "vecno-simnet").unwrap();
+        reader.read_payload().unwrap();
+        let _ = reader.read_payload();
+        assert!(matches!(reader.finish(), Err(SnapshotError::ChecksumMismatch)));
+
+        let mut trailing = bytes;
+        trailing.push(0);
+        let mut reader = SnapshotReader::new(trailing.as_slice(), "vecno-simnet").unwrap();
+        reader.read_payload().unwrap();
+        reader.read_payload().unwrap();
+        assert!(matches!(reader.finish(), Err(SnapshotError::Other(_))));
+    }
+
+    struct EmptyTxSelector;
+
+    impl TemplateTransactionSelector for EmptyTxSelector {
+        fn select_transactions(&mut self) -> Vec<Transaction> {
+            vec![]
+        }
+
+        fn reject_selection(&mut self, _tx_id: TransactionId) {}
+
+        fn is_successful(&self) -> bool {
+            true
+        }
+    }
+
+    /// A consensus manager over a database of its own, set up as the daemon does
+    struct TestNode {
+        consensus_manager: Arc<ConsensusManager>,
+        _notification_recv: Receiver<Notification>,
+        _db_tempdir: tempfile::TempDir,
+    }
+
+    impl TestNode {
+        fn new(config: &Config) -> Self {
+            let db_tempdir = get_vecno_tempdir();
+            let meta_db = ConnBuilder::default().with_db_path(db_tempdir.path().join("meta")).with_files_limit(5).build().unwrap();
+            let (notification_send, notification_recv) = unbounded();
+            let consensus_factory = Arc::new(ConsensusFactory::new(
+                meta_db,
+                config,
+                db_tempdir.path().join("consensus"),
+                4,
+                Arc::new(ConsensusNotificationRoot::new(notification_send)),
+                Arc::new(ProcessingCounters::default()),
+                Arc::new(TxScriptCacheCounters::default()),
+                200,
+            ));
+            let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
+            Self { consensus_manager, _notification_recv: notification_recv, _db_tempdir: db_tempdir }
+        }
+    }
+
+    async fn pruning_point_utxos(session: &ConsensusProxy, pruning_point: Hash) -> Vec<(TransactionOutpoint, UtxoEntry)> {
+        session.async_get_pruning_point_utxos(pruning_point, None, usize::MAX, false).await.unwrap()
+    }
+
+    fn muhash(utxos: &[(TransactionOutpoint, UtxoEntry)]) -> Hash {
+        let mut multiset = MuHash::new();
+        utxos.iter().for_each(|(outpoint, entry)| multiset.add_utxo(outpoint, entry));
+        multiset.finalize()
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_export_import() {
+        // Small windows and depths so that the pruning point moves after a short chain
+        let config = ConfigBuilder::new(SIMNET_PARAMS)
+            .skip_proof_of_work()
+            .edit_consensus_params(|p| {
+                p.ghostdag_k = 10;
+                p.mergeset_size_limit = 20;
+                p.merge_depth = 10;
+                p.finality_depth = 20;
+                p.pruning_depth = 60;
+                p.sampled_difficulty_window_size = 10;
+                p.min_difficulty_window_len = 10;
+                p.difficulty_sample_rate = 1;
+                p.past_median_time_sampled_window_size = 11;
+                p.past_median_time_sample_rate = 1;
+            })
+            .build();
+
+        let source = TestNode::new(&config);
+        let source_core = Arc::new(Core::new());
+        source_core.bind(source.consensus_manager.clone());
+        let source_joins = source_core.start();
+
+        // Mine a chain spanning more than 10 minutes, as required from a snapshot ahead of an empty node
+        let session = source.consensus_manager.consensus().session().await;
+        let miner_data = MinerData::new(ScriptPublicKey::from_vec(0, vec![0x51]), vec![]);
+        let mut timestamp = config.genesis.timestamp;
+        for _ in 0..120 {
+            timestamp += 10_000;
+            let miner_data = miner_data.clone();
+            let mut block = session
+                .clone()
+                .spawn_blocking(move |c| c.build_block_template(miner_data, Box::new(EmptyTxSelector), TemplateBuildMode::Standard))
+                .await
+                .unwrap()
+                .block;
+            block.header.timestamp = timestamp;
+            block.header.finalize();
+            session.validate_and_insert_block(block.to_immutable()).virtual_state_task.await.unwrap();
+        }
+
+        // The pruning point UTXO set is advanced by the pruning processor after the pruning point moves
+        let start = Instant::now();
+        let (pruning_point, utxos) = loop {
+            let pruning_point = session.async_pruning_point().await;
+            if pruning_point != config.genesis.hash {
+                let utxos = pruning_point_utxos(&session, pruning_point).await;
+                if muhash(&utxos) == session.async_get_header(pruning_point).await.unwrap().utxo_commitment {
+                    break (pruning_point, utxos);
+                }
+            }
+            assert!(start.elapsed() < Duration::from_secs(30), "the pruning point UTXO set was not updated in time");
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        };
+        assert!(!utxos.is_empty());
+        drop(session);
+
+        let snapshot_tempdir = get_vecno_tempdir();
+        let path = snapshot_tempdir.path().join("snapshot.bin");
+        assert_eq!(export_snapshot(&source.consensus_manager.consensus(), &config, &path).await.unwrap(), pruning_point);
+
+        // The target imports before starting, as the daemon does
+        let target = TestNode::new(&config);
+        assert_eq!(import_snapshot(&target.consensus_manager, &config, &path).await.unwrap(), pruning_point);
+        let target_core = Arc::new(Core::new());
+        target_core.bind(target.consensus_manager.clone());
+        let target_joins = target_core.start();
+
+        let session = target.consensus_manager.consensus().session().await;
+        assert_eq!(session.async_pruning_point().await, pruning_point);
+        let imported_utxos = pruning_point_utxos(&session, pruning_point).await;
+        assert_eq!(imported_utxos, utxos);
+        assert_eq!(muhash(&imported_utxos), muhash(&utxos));
+        drop(session);
+
+        target_core.shutdown();
+        target_core.join(target_joins);
+        source_core.shutdown();
+        source_core.join(source_joins);
+    }
+}
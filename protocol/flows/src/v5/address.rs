@@ -9,7 +9,6 @@ use vecno_p2p_lib::{
     pb::{vecnod_message::Payload, AddressesMessage, RequestAddressesMessage},
     IncomingRoute, Router,
 };
-use vecno_utils::networking::IpAddress;
 
 /// The maximum number of addresses that are sent in a single vecno Addresses message.
 const MAX_ADDRESSES_SEND: usize = 1000;
@@ -49,13 +48,13 @@ impl ReceiveAddressesFlow {
             .await?;
 
         let msg = dequeue_with_timeout!(self.incoming_route, Payload::Addresses)?;
-        let address_list: Vec<(IpAddress, u16)> = msg.try_into()?;
+        let address_list: Vec<NetAddress> = msg.try_into()?;
         if address_list.len() > MAX_ADDRESSES_RECEIVE {
             return Err(ProtocolError::OtherOwned(format!("address count {} exceeded {}", address_list.len(), MAX_ADDRESSES_RECEIVE)));
         }
         let mut amgr_lock = self.ctx.address_manager.lock();
-        for (ip, port) in address_list {
-            amgr_lock.add_address(NetAddress::new(ip, port))
+        for address in address_list {
+            amgr_lock.add_address(address)
         }
 
         Ok(())
@@ -87,10 +86,23 @@ impl SendAddressesFlow {
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
         loop {
             dequeue!(self.incoming_route, Payload::RequestAddresses)?;
-            let addresses = self.ctx.address_manager.lock().iterate_addresses().collect_vec();
-            let address_list = addresses
-                .choose_multiple(&mut rand::thread_rng(), MAX_ADDRESSES_SEND)
-                .map(|addr| (addr.ip, addr.port).into())
+            // Onion addresses are only sent to the peers able to decode them
+            let supports_onion = self.router.properties().supports_onion_addresses();
+            let (addresses, local_onion) = {
+                let mut address_manager = self.ctx.address_manager.lock();
+                let addresses = address_manager.iterate_addresses().filter(|addr| supports_onion || !addr.is_onion()).collect_vec();
+                let local_onion = address_manager.best_local_address().filter(|addr| supports_onion && addr.is_onion());
+                (addresses, local_onion)
+            };
+            // The onion service of the node is advertised along with the known addresses since version messages cannot carry it
+            let address_list = local_onion
+                .into_iter()
+                .chain(
+                    addresses
+                        .choose_multiple(&mut rand::thread_rng(), MAX_ADDRESSES_SEND - usize::from(local_onion.is_some()))
+                        .copied(),
+                )
+                .map(|addr| addr.into())
                 .collect();
             self.router.enqueue(make_message!(Payload::Addresses, AddressesMessage { address_list })).await?;
         }
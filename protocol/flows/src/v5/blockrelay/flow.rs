@@ -3,15 +3,26 @@ use crate::{
     flow_trait::Flow,
     flowcontext::orphans::OrphanOutput,
 };
-use std::{collections::VecDeque, sync::Arc};
-use vecno_consensus_core::{api::BlockValidationFutures, block::Block, blockstatus::BlockStatus, errors::block::RuleError};
+use std::{
+    collections::VecDeque,
+    sync::{atomic::Ordering, Arc},
+};
+use vecno_consensus_core::{
+    api::BlockValidationFutures, block::Block, blockstatus::BlockStatus, errors::block::RuleError, merkle::calc_hash_merkle_root,
+    tx::Transaction,
+};
 use vecno_consensusmanager::{BlockProcessingBatch, ConsensusProxy};
 use vecno_core::debug;
 use vecno_hashes::Hash;
+use vecno_mining::model::tx_query::TransactionQuery;
 use vecno_p2p_lib::{
     common::ProtocolError,
+    convert::model::compact_block::{CompactBlock, PartialBlock},
     dequeue, dequeue_with_timeout, make_message, make_request,
-    pb::{vecnod_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestRelayBlocksMessage},
+    pb::{
+        vecnod_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestBlockTransactionsMessage,
+        RequestRelayBlocksMessage,
+    },
     IncomingRoute, Router, SharedIncomingRoute,
 };
 use vecno_utils::channel::{JobSender, JobTrySendError as TrySendError};
@@ -228,10 +239,22 @@ impl HandleRelayInvsFlow {
         let Some(request_scope) = self.ctx.try_adding_block_request(requested_hash) else {
             return Ok(None);
         };
+        if self.router.properties().supports_compact_blocks() {
+            if let Some(block) = self.request_compact_block(requested_hash, request_id).await? {
+                return Ok(Some((block, request_scope)));
+            }
+            debug!("Relay block {} could not be rebuilt from its compact block, requesting the full block", requested_hash);
+            self.ctx.compact_block_counters().fallback_counts.fetch_add(1, Ordering::Relaxed);
+        }
+        let block = self.request_full_block(requested_hash, request_id).await?;
+        Ok(Some((block, request_scope)))
+    }
+
+    async fn request_full_block(&mut self, requested_hash: Hash, request_id: u32) -> Result<Block, ProtocolError> {
         self.router
             .enqueue(make_request!(
                 Payload::RequestRelayBlocks,
-                RequestRelayBlocksMessage { hashes: vec![requested_hash.into()] },
+                RequestRelayBlocksMessage { hashes: vec![requested_hash.into()], compact: false },
                 request_id
             ))
             .await?;
@@ -240,8 +263,88 @@ impl HandleRelayInvsFlow {
         if block.hash() != requested_hash {
             Err(ProtocolError::OtherOwned(format!("requested block hash {} but got block {}", requested_hash, block.hash())))
         } else {
-            Ok(Some((block, request_scope)))
+            Ok(block)
+        }
+    }
+
+    /// Requests the block as a compact block and rebuilds it from the mempool, asking the peer for the transactions
+    /// missing from it. Returns `None` if the rebuilt block does not match its header, in which case the block should
+    /// be requested in full.
+    async fn request_compact_block(&mut self, requested_hash: Hash, request_id: u32) -> Result<Option<Block>, ProtocolError> {
+        self.router
+            .enqueue(make_request!(
+                Payload::RequestRelayBlocks,
+                RequestRelayBlocksMessage { hashes: vec![requested_hash.into()], compact: true },
+                request_id
+            ))
+            .await?;
+        let msg = dequeue_with_timeout!(self.msg_route, Payload::CompactBlock)?;
+        let compact_block: CompactBlock = msg.try_into()?;
+        if compact_block.hash() != requested_hash {
+            return Err(ProtocolError::OtherOwned(format!(
+                "requested block hash {} but got compact block {}",
+                requested_hash,
+                compact_block.hash()
+            )));
+        }
+
+        // Orphans are left out since their mass is not known yet, so they would not match the block transactions
+        let filter = compact_block.short_id_filter();
+        let candidates = self
+            .ctx
+            .mining_manager()
+            .clone()
+            .get_matching_transactions(TransactionQuery::TransactionsOnly, move |id| filter.matches(id))
+            .await;
+        let partial_block = PartialBlock::new(compact_block, candidates)?;
+        let missing_indexes = partial_block.missing_indexes();
+        let counters = self.ctx.compact_block_counters();
+        counters.mempool_tx_counts.fetch_add(partial_block.found_count() as u64, Ordering::Relaxed);
+        counters.requested_tx_counts.fetch_add(missing_indexes.len() as u64, Ordering::Relaxed);
+
+        let missing_transactions = if missing_indexes.is_empty() {
+            vec![]
+        } else {
+            self.request_block_transactions(requested_hash, missing_indexes, request_id).await?
+        };
+        let requested_count = missing_transactions.len();
+        let block = partial_block.fill(missing_transactions)?;
+
+        // Mempool transactions are matched by short ids only, so a collision may have slipped in
+        let storage_mass_activated = self.ctx.config.storage_mass_activation.is_active(block.header.daa_score);
+        if calc_hash_merkle_root(block.transactions.iter(), storage_mass_activated) != block.header.hash_merkle_root {
+            return Ok(None);
+        }
+        if requested_count == 0 {
+            counters.reconstructed_counts.fetch_add(1, Ordering::Relaxed);
+        } else {
+            counters.completed_counts.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(Some(block))
+    }
+
+    async fn request_block_transactions(
+        &mut self,
+        block_hash: Hash,
+        indexes: Vec<u32>,
+        request_id: u32,
+    ) -> Result<Vec<Transaction>, ProtocolError> {
+        self.router
+            .enqueue(make_request!(
+                Payload::RequestBlockTransactions,
+                RequestBlockTransactionsMessage { block_hash: Some(block_hash.into()), indexes },
+                request_id
+            ))
+            .await?;
+        let msg = dequeue_with_timeout!(self.msg_route, Payload::BlockTransactions)?;
+        let (received_hash, transactions): (Hash, Vec<Transaction>) = msg.try_into()?;
+        if received_hash != block_hash {
+            return Err(ProtocolError::OtherOwned(format!(
+                "requested transactions of block {} but got transactions of block {}",
+                block_hash, received_hash
+            )));
         }
+        Ok(transactions)
     }
 
     /// Process the orphan block. Returns `Some(BlockProcessingBatch)` if the block has no missing roots, where
@@ -1,10 +1,13 @@
 use crate::{flow_context::FlowContext, flow_trait::Flow};
 use std::sync::Arc;
+use vecno_consensusmanager::ConsensusProxy;
 use vecno_core::debug;
+use vecno_hashes::Hash;
 use vecno_p2p_lib::{
     common::ProtocolError,
-    dequeue_with_request_id, make_message, make_response,
-    pb::{vecnod_message::Payload, InvRelayBlockMessage},
+    convert::model::compact_block::CompactBlock,
+    make_message, make_response,
+    pb::{vecnod_message::Payload, BlockTransactionsMessage, InvRelayBlockMessage, RequestRelayBlocksMessage},
     IncomingRoute, Router,
 };
 
@@ -36,19 +39,71 @@ impl HandleRelayBlockRequests {
         // Note: in go-vecnod this was done via a dedicated one-time flow.
         self.send_sink().await?;
         loop {
-            let (msg, request_id) = dequeue_with_request_id!(self.incoming_route, Payload::RequestRelayBlocks)?;
-            let hashes: Vec<_> = msg.try_into()?;
-
+            let Some(msg) = self.incoming_route.recv().await else {
+                return Err(ProtocolError::ConnectionClosed);
+            };
+            let request_id = msg.request_id;
             let session = self.ctx.consensus().unguarded_session();
-
-            for hash in hashes {
-                let block = session.async_get_block(hash).await?;
-                self.router.enqueue(make_response!(Payload::Block, (&block).into(), request_id)).await?;
-                debug!("relayed block with hash {} to peer {}", hash, self.router);
+            match msg.payload {
+                Some(Payload::RequestRelayBlocks(payload)) => self.send_blocks(&session, payload, request_id).await?,
+                Some(Payload::RequestBlockTransactions(payload)) => {
+                    let (hash, indexes) = payload.try_into()?;
+                    self.send_block_transactions(&session, hash, indexes, request_id).await?
+                }
+                _ => {
+                    return Err(ProtocolError::UnexpectedMessage(
+                        stringify!(Payload::RequestRelayBlocks | Payload::RequestBlockTransactions),
+                        msg.payload.as_ref().map(|v| v.into()),
+                    ))
+                }
             }
         }
     }
 
+    async fn send_blocks(
+        &mut self,
+        session: &ConsensusProxy,
+        msg: RequestRelayBlocksMessage,
+        request_id: u32,
+    ) -> Result<(), ProtocolError> {
+        let compact = msg.compact;
+        let hashes: Vec<_> = msg.try_into()?;
+        for hash in hashes {
+            let block = session.async_get_block(hash).await?;
+            let msg = if compact {
+                make_response!(Payload::CompactBlock, (&CompactBlock::new(&block, rand::random())).into(), request_id)
+            } else {
+                make_response!(Payload::Block, (&block).into(), request_id)
+            };
+            self.router.enqueue(msg).await?;
+            debug!("relayed {}block with hash {} to peer {}", if compact { "compact " } else { "" }, hash, self.router);
+        }
+        Ok(())
+    }
+
+    async fn send_block_transactions(
+        &mut self,
+        session: &ConsensusProxy,
+        hash: Hash,
+        indexes: Vec<u32>,
+        request_id: u32,
+    ) -> Result<(), ProtocolError> {
+        let block = session.async_get_block(hash).await?;
+        let transactions = indexes
+            .into_iter()
+            .map(|index| block.transactions.get(index as usize).map(|tx| tx.into()))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| ProtocolError::OtherOwned(format!("requested transactions out of the range of block {}", hash)))?;
+        self.router
+            .enqueue(make_response!(
+                Payload::BlockTransactions,
+                BlockTransactionsMessage { block_hash: Some(hash.into()), transactions },
+                request_id
+            ))
+            .await?;
+        Ok(())
+    }
+
     async fn send_sink(&mut self) -> Result<(), ProtocolError> {
         let sink = self.ctx.consensus().unguarded_session().async_get_sink().await;
         if sink == self.ctx.config.genesis.hash {
@@ -5,7 +5,7 @@ use crate::{
         Flow,
     },
 };
-use futures::future::{join_all, select, try_join_all, Either};
+use futures::future::{join, join_all, select, try_join_all, Either};
 use std::{
     sync::Arc,
     time::{Duration, Instant},
@@ -386,21 +386,18 @@ impl IbdFlow {
                 let last_header = chunk.last().expect("chunk is never empty");
                 (last_header.daa_score, last_header.timestamp)
             };
-            let mut prev_jobs: Vec<BlockValidationFuture> =
-                chunk.into_iter().map(|h| consensus.validate_and_insert_block(Block::from_header_arc(h)).virtual_state_task).collect();
+            let mut prev_jobs = Self::submit_headers(consensus, chunk).await;
 
             while let Some(chunk) = chunk_stream.next().await? {
                 let (current_daa_score, current_timestamp) = {
                     let last_header = chunk.last().expect("chunk is never empty");
                     (last_header.daa_score, last_header.timestamp)
                 };
-                let current_jobs = chunk
-                    .into_iter()
-                    .map(|h| consensus.validate_and_insert_block(Block::from_header_arc(h)).virtual_state_task)
-                    .collect();
                 let prev_chunk_len = prev_jobs.len();
-                // Join the previous chunk so that we always concurrently process a chunk and receive another
-                try_join_all(prev_jobs).await?;
+                // Verify the PoW of the chunk and submit it while joining the previous chunk, so that we always
+                // concurrently process a chunk and receive another
+                let (current_jobs, prev_result) = join(Self::submit_headers(consensus, chunk), try_join_all(prev_jobs)).await;
+                prev_result?;
                 // Log the progress
                 progress_reporter.report(prev_chunk_len, prev_daa_score, prev_timestamp);
                 prev_daa_score = current_daa_score;
@@ -418,6 +415,18 @@ impl IbdFlow {
         Ok(())
     }
 
+    /// Submits a chunk of headers to consensus, which verifies their PoW in parallel on a blocking thread before queuing
+    /// them for processing
+    async fn submit_headers(consensus: &ConsensusProxy, chunk: HeadersChunk) -> Vec<BlockValidationFuture> {
+        consensus
+            .clone()
+            .spawn_blocking(move |c| c.validate_and_insert_header_chunk(chunk))
+            .await
+            .into_iter()
+            .map(|futures| futures.virtual_state_task)
+            .collect()
+    }
+
     async fn sync_missing_relay_past_headers(
         &mut self,
         consensus: &ConsensusProxy,
@@ -445,8 +454,7 @@ impl IbdFlow {
 
         let msg = dequeue_with_timeout!(self.incoming_route, Payload::BlockHeaders)?;
         let chunk: HeadersChunk = msg.try_into()?;
-        let jobs: Vec<BlockValidationFuture> =
-            chunk.into_iter().map(|h| consensus.validate_and_insert_block(Block::from_header_arc(h)).virtual_state_task).collect();
+        let jobs = Self::submit_headers(consensus, chunk).await;
         try_join_all(jobs).await?;
         dequeue_with_timeout!(self.incoming_route, Payload::DoneHeaders)?;
 
@@ -11,7 +11,11 @@ use self::{
     request_pp_proof::RequestPruningPointProofFlow,
     request_pruning_point_and_anticone::PruningPointAndItsAnticoneRequestsFlow,
     request_pruning_point_utxo_set::RequestPruningPointUtxoSetFlow,
-    txrelay::flow::{RelayTransactionsFlow, RequestTransactionsFlow},
+    txrelay::{
+        flow::{RelayTransactionsFlow, RequestTransactionsFlow},
+        package::HandleTransactionPackagesFlow,
+        stem::HandleStemTransactionsFlow,
+    },
 };
 use crate::{flow_context::FlowContext, flow_trait::Flow};
 
@@ -65,13 +69,18 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
             SharedIncomingRoute::new(
                 router.subscribe_with_capacity(vec![VecnodMessagePayloadType::InvRelayBlock], ctx.block_invs_channel_size()),
             ),
-            router.subscribe(vec![VecnodMessagePayloadType::Block, VecnodMessagePayloadType::BlockLocator]),
+            router.subscribe(vec![
+                VecnodMessagePayloadType::Block,
+                VecnodMessagePayloadType::BlockLocator,
+                VecnodMessagePayloadType::CompactBlock,
+                VecnodMessagePayloadType::BlockTransactions,
+            ]),
             ibd_sender,
         )),
         Box::new(HandleRelayBlockRequests::new(
             ctx.clone(),
             router.clone(),
-            router.subscribe(vec![VecnodMessagePayloadType::RequestRelayBlocks]),
+            router.subscribe(vec![VecnodMessagePayloadType::RequestRelayBlocks, VecnodMessagePayloadType::RequestBlockTransactions]),
         )),
         Box::new(ReceivePingsFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![VecnodMessagePayloadType::Ping]))),
         Box::new(SendPingsFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![VecnodMessagePayloadType::Pong]))),
@@ -131,6 +140,16 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
             router.clone(),
             router.subscribe(vec![VecnodMessagePayloadType::RequestTransactions]),
         )),
+        Box::new(HandleTransactionPackagesFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![VecnodMessagePayloadType::TransactionPackage]),
+        )),
+        Box::new(HandleStemTransactionsFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![VecnodMessagePayloadType::StemTransaction]),
+        )),
         Box::new(ReceiveAddressesFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![VecnodMessagePayloadType::Addresses]))),
         Box::new(SendAddressesFlow::new(
             ctx.clone(),
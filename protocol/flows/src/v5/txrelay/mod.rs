@@ -1 +1,3 @@
 pub mod flow;
+pub mod package;
+pub mod stem;
@@ -0,0 +1,70 @@
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+use std::sync::Arc;
+use vecno_consensus_core::tx::Transaction;
+use vecno_core::debug;
+use vecno_mining::{
+    errors::MiningManagerError,
+    mempool::{errors::RuleError, tx::Priority},
+};
+use vecno_p2p_lib::{common::ProtocolError, dequeue, pb::vecnod_message::Payload, IncomingRoute, Router};
+
+/// Flow listening to TransactionPackage messages, adds the packages to the mempool and propagates
+/// them to the rest of the network.
+pub struct HandleTransactionPackagesFlow {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+}
+
+#[async_trait::async_trait]
+impl Flow for HandleTransactionPackagesFlow {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl HandleTransactionPackagesFlow {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let transactions: Vec<Transaction> = dequeue!(self.incoming_route, Payload::TransactionPackage)?.try_into()?;
+
+            let session = self.ctx.consensus().unguarded_session();
+
+            // Transaction relay is disabled if the node is out of sync and thus not mining
+            if !session.async_is_nearly_synced().await {
+                continue;
+            }
+
+            match self
+                .ctx
+                .mining_manager()
+                .clone()
+                .validate_and_insert_transaction_package(&session, transactions, Priority::Low)
+                .await
+            {
+                Ok(accepted_transactions) => {
+                    self.ctx.broadcast_transactions(accepted_transactions.iter().map(|x| x.id()), false).await;
+                    self.ctx.relay_transaction_package(&accepted_transactions, Some(self.router.key())).await;
+                }
+                Err(MiningManagerError::PackageRejected(rejections)) => {
+                    if let Some((transaction_id, _)) = rejections.iter().find(|(_, err)| matches!(err, RuleError::RejectInvalid(_))) {
+                        return Err(ProtocolError::MisbehavingPeer(format!(
+                            "rejected invalid transaction {} in package",
+                            transaction_id
+                        )));
+                    }
+                    debug!("Transaction package from {} rejected: {}", self.router, MiningManagerError::PackageRejected(rejections));
+                }
+                Err(err) => debug!("Transaction package from {} rejected: {}", self.router, err),
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+use std::sync::Arc;
+use vecno_consensus_core::tx::Transaction;
+use vecno_core::debug;
+use vecno_p2p_lib::{common::ProtocolError, dequeue, pb::vecnod_message::Payload, IncomingRoute, Router};
+
+/// Flow listening to StemTransaction messages, relays the transactions in the stem phase of Dandelion++
+/// or diffuses them to the rest of the network during fluff epochs.
+pub struct HandleStemTransactionsFlow {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+}
+
+#[async_trait::async_trait]
+impl Flow for HandleStemTransactionsFlow {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl HandleStemTransactionsFlow {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let transaction: Transaction = dequeue!(self.incoming_route, Payload::StemTransaction)?.try_into()?;
+            let transaction_id = transaction.id();
+
+            let session = self.ctx.consensus().unguarded_session();
+
+            // Transaction relay is disabled if the node is out of sync and thus not mining
+            if !session.async_is_nearly_synced().await {
+                continue;
+            }
+
+            match self.ctx.relay_stem_transaction(&session, transaction, self.router.key()).await {
+                Ok(()) => {}
+                Err(ProtocolError::MiningManagerError(err)) => {
+                    debug!("Stem transaction {} from {} rejected: {}", transaction_id, self.router, err)
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
@@ -10,7 +10,11 @@ use crate::v5::{
     request_ibd_chain_block_locator::RequestIbdChainBlockLocatorFlow,
     request_pp_proof::RequestPruningPointProofFlow,
     request_pruning_point_utxo_set::RequestPruningPointUtxoSetFlow,
-    txrelay::flow::{RelayTransactionsFlow, RequestTransactionsFlow},
+    txrelay::{
+        flow::{RelayTransactionsFlow, RequestTransactionsFlow},
+        package::HandleTransactionPackagesFlow,
+        stem::HandleStemTransactionsFlow,
+    },
 };
 use crate::{flow_context::FlowContext, flow_trait::Flow};
 
@@ -52,7 +56,7 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
         Box::new(HandleRelayBlockRequests::new(
             ctx.clone(),
             router.clone(),
-            router.subscribe(vec![VecnodMessagePayloadType::RequestRelayBlocks]),
+            router.subscribe(vec![VecnodMessagePayloadType::RequestRelayBlocks, VecnodMessagePayloadType::RequestBlockTransactions]),
         )),
         Box::new(ReceivePingsFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![VecnodMessagePayloadType::Ping]))),
         Box::new(SendPingsFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![VecnodMessagePayloadType::Pong]))),
@@ -112,6 +116,16 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
             router.clone(),
             router.subscribe(vec![VecnodMessagePayloadType::RequestTransactions]),
         )),
+        Box::new(HandleTransactionPackagesFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![VecnodMessagePayloadType::TransactionPackage]),
+        )),
+        Box::new(HandleStemTransactionsFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![VecnodMessagePayloadType::StemTransaction]),
+        )),
         Box::new(ReceiveAddressesFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![VecnodMessagePayloadType::Addresses]))),
         Box::new(SendAddressesFlow::new(
             ctx.clone(),
@@ -7,8 +7,9 @@ async fn main() {
     // [-] - init logger
     vecno_core::log::init_logger(None, "debug");
     // [0] - init p2p-adaptor
-    let initializer = Arc::new(EchoFlowInitializer::new());
-    let adaptor = vecno_p2p_lib::Adaptor::client_only(vecno_p2p_lib::Hub::new(), initializer, Default::default());
+    // Pass `--encrypt` to offer peers an encrypted connection
+    let initializer = Arc::new(EchoFlowInitializer::with_encryption(std::env::args().any(|arg| arg == "--encrypt")));
+    let adaptor = vecno_p2p_lib::Adaptor::client_only(vecno_p2p_lib::Hub::new(), initializer, Default::default(), Default::default());
     // [1] - connect 128 peers + flows
     let ip_port = String::from("[::1]:50051");
     for i in 0..1 {
@@ -9,8 +9,11 @@ async fn main() {
     vecno_core::log::init_logger(None, "debug");
     // [0] - init p2p-adaptor - server side
     let ip_port = NetAddress::from_str("[::1]:50051").unwrap();
-    let initializer = Arc::new(EchoFlowInitializer::new());
-    let adaptor = vecno_p2p_lib::Adaptor::bidirectional(ip_port, vecno_p2p_lib::Hub::new(), initializer, Default::default()).unwrap();
+    // Pass `--encrypt` to offer peers an encrypted connection
+    let initializer = Arc::new(EchoFlowInitializer::with_encryption(std::env::args().any(|arg| arg == "--encrypt")));
+    let adaptor =
+        vecno_p2p_lib::Adaptor::bidirectional(ip_port, vecno_p2p_lib::Hub::new(), initializer, Default::default(), Default::default())
+            .unwrap();
     // [1] - connect to a few peers
     let ip_port = String::from("[::1]:7111");
     for i in 0..1 {
@@ -1,5 +1,6 @@
-use super::{error::ConversionError, option::TryIntoOptionEx};
+use super::{error::ConversionError, model::compact_block::CompactBlock, option::TryIntoOptionEx};
 use crate::pb as protowire;
+use std::sync::Arc;
 use vecno_consensus_core::{block::Block, tx::Transaction};
 
 // ----------------------------------------------------------------------------
@@ -12,6 +13,21 @@ impl From<&Block> for protowire::BlockMessage {
     }
 }
 
+impl From<&CompactBlock> for protowire::CompactBlockMessage {
+    fn from(block: &CompactBlock) -> Self {
+        Self {
+            header: Some(block.header.as_ref().into()),
+            short_id_nonce: block.short_id_nonce,
+            short_ids: block.short_ids.clone(),
+            prefilled_transactions: block
+                .prefilled_transactions
+                .iter()
+                .map(|(index, tx)| protowire::PrefilledTransactionMessage { index: *index, transaction: Some(tx.into()) })
+                .collect(),
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // protowire to consensus_core
 // ----------------------------------------------------------------------------
@@ -26,3 +42,20 @@ impl TryFrom<protowire::BlockMessage> for Block {
         ))
     }
 }
+
+impl TryFrom<protowire::CompactBlockMessage> for CompactBlock {
+    type Error = ConversionError;
+
+    fn try_from(block: protowire::CompactBlockMessage) -> Result<Self, Self::Error> {
+        Ok(Self {
+            header: Arc::new(block.header.try_into_ex()?),
+            short_id_nonce: block.short_id_nonce,
+            short_ids: block.short_ids,
+            prefilled_transactions: block
+                .prefilled_transactions
+                .into_iter()
+                .map(|prefilled| Ok((prefilled.index, prefilled.transaction.try_into_ex()?)))
+                .collect::<Result<Vec<(u32, Transaction)>, Self::Error>>()?,
+        })
+    }
+}
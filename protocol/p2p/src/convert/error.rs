@@ -12,6 +12,9 @@ pub enum ConversionError {
     #[error("IP has illegal length {0}")]
     IllegalIPLength(usize),
 
+    #[error("Onion address has illegal length {0}")]
+    IllegalOnionLength(usize),
+
     #[error("Bytes size mismatch error {0}")]
     ArrayBytesSizeError(#[from] std::array::TryFromSliceError),
 
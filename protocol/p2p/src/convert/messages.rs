@@ -10,10 +10,10 @@ use crate::pb as protowire;
 use vecno_consensus_core::{
     header::Header,
     pruning::{PruningPointProof, PruningPointsList},
-    tx::{TransactionId, TransactionOutpoint, UtxoEntry},
+    tx::{Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
 };
 use vecno_hashes::Hash;
-use vecno_utils::networking::{IpAddress, PeerId};
+use vecno_utils::networking::{NetAddress, PeerId};
 
 use std::sync::Arc;
 
@@ -33,6 +33,7 @@ impl From<Version> for protowire::VersionMessage {
             disable_relay_tx: item.disable_relay_tx,
             subnetwork_id: item.subnetwork_id.map(|x| x.into()),
             network: item.network.clone(),
+            encryption_key: vec![],
         }
     }
 }
@@ -175,7 +176,7 @@ impl TryFrom<protowire::BlockLocatorMessage> for Vec<Hash> {
     }
 }
 
-impl TryFrom<protowire::AddressesMessage> for Vec<(IpAddress, u16)> {
+impl TryFrom<protowire::AddressesMessage> for Vec<NetAddress> {
     type Error = ConversionError;
 
     fn try_from(msg: protowire::AddressesMessage) -> Result<Self, Self::Error> {
@@ -199,6 +200,22 @@ impl TryFrom<protowire::InvTransactionsMessage> for Vec<TransactionId> {
     }
 }
 
+impl TryFrom<protowire::TransactionPackageMessage> for Vec<Transaction> {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::TransactionPackageMessage) -> Result<Self, Self::Error> {
+        msg.transactions.into_iter().map(|v| v.try_into()).collect()
+    }
+}
+
+impl TryFrom<protowire::StemTransactionMessage> for Transaction {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::StemTransactionMessage) -> Result<Self, Self::Error> {
+        msg.transaction.try_into_ex()
+    }
+}
+
 impl TryFrom<protowire::TransactionNotFoundMessage> for TransactionId {
     type Error = ConversionError;
 
@@ -220,3 +237,17 @@ impl TryFrom<protowire::RequestAntipastMessage> for (Hash, Hash) {
         Ok((msg.block_hash.try_into_ex()?, msg.context_hash.try_into_ex()?))
     }
 }
+
+impl TryFrom<protowire::RequestBlockTransactionsMessage> for (Hash, Vec<u32>) {
+    type Error = ConversionError;
+    fn try_from(msg: protowire::RequestBlockTransactionsMessage) -> Result<Self, Self::Error> {
+        Ok((msg.block_hash.try_into_ex()?, msg.indexes))
+    }
+}
+
+impl TryFrom<protowire::BlockTransactionsMessage> for (Hash, Vec<Transaction>) {
+    type Error = ConversionError;
+    fn try_from(msg: protowire::BlockTransactionsMessage) -> Result<Self, Self::Error> {
+        Ok((msg.block_hash.try_into_ex()?, msg.transactions.into_iter().map(|v| v.try_into()).collect::<Result<_, _>>()?))
+    }
+}
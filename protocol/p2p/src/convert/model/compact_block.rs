@@ -0,0 +1,224 @@
+//!
+//! Model structures of the compact block relay, where a relay block is sent as its header along with short ids of
+//! its transactions, so that the receiving peer rebuilds it from the transactions found in its own mempool.
+//!
+
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    sync::Arc,
+};
+use vecno_consensus_core::{
+    block::Block,
+    header::Header,
+    tx::{Transaction, TransactionId},
+};
+use vecno_hashes::Hash;
+use xxhash_rust::xxh3::xxh3_64_with_seed;
+
+use crate::common::ProtocolError;
+
+/// Mask truncating the short transaction ids to 48 bits
+const SHORT_ID_MASK: u64 = (1 << 48) - 1;
+
+/// A transaction id hashed with a per-block seed and truncated to 48 bits
+pub type ShortTransactionId = u64;
+
+/// Returns the short id of `id` under `seed`
+pub fn short_transaction_id(seed: u64, id: &TransactionId) -> ShortTransactionId {
+    xxh3_64_with_seed(&id.as_bytes(), seed) & SHORT_ID_MASK
+}
+
+/// The seed of the short ids depends on the block hash and on a nonce chosen by the sender, so that transactions
+/// with colliding short ids cannot be crafted in advance
+fn short_id_seed(block_hash: Hash, nonce: u64) -> u64 {
+    block_hash.to_le_u64()[0] ^ nonce
+}
+
+pub struct CompactBlock {
+    pub header: Arc<Header>,
+    pub short_id_nonce: u64,
+    /// Short ids of the transactions which are not prefilled, in block order
+    pub short_ids: Vec<ShortTransactionId>,
+    /// Transactions sent as is along with their index in the block, by increasing index
+    pub prefilled_transactions: Vec<(u32, Transaction)>,
+}
+
+impl CompactBlock {
+    /// Builds the compact form of `block`. Only the coinbase transaction is prefilled since it is never found in mempools.
+    pub fn new(block: &Block, short_id_nonce: u64) -> Self {
+        let seed = short_id_seed(block.hash(), short_id_nonce);
+        let prefilled_transactions = block.transactions.first().map(|tx| (0, tx.clone())).into_iter().collect();
+        let short_ids = block.transactions.iter().skip(1).map(|tx| short_transaction_id(seed, &tx.id())).collect();
+        Self { header: block.header.clone(), short_id_nonce, short_ids, prefilled_transactions }
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.header.hash
+    }
+
+    pub fn transaction_count(&self) -> usize {
+        self.short_ids.len() + self.prefilled_transactions.len()
+    }
+
+    /// Returns a filter matching the ids of the transactions which may belong to this block
+    pub fn short_id_filter(&self) -> ShortIdFilter {
+        ShortIdFilter {
+            seed: short_id_seed(self.hash(), self.short_id_nonce),
+            short_ids: self.short_ids.iter().copied().collect::<HashSet<_>>().into(),
+        }
+    }
+}
+
+/// Matches transaction ids against the short ids of a compact block
+#[derive(Clone)]
+pub struct ShortIdFilter {
+    seed: u64,
+    short_ids: Arc<HashSet<ShortTransactionId>>,
+}
+
+impl ShortIdFilter {
+    pub fn matches(&self, id: &TransactionId) -> bool {
+        self.short_ids.contains(&short_transaction_id(self.seed, id))
+    }
+}
+
+/// A block being rebuilt from a compact block, holding the transactions found so far
+pub struct PartialBlock {
+    header: Arc<Header>,
+    transactions: Vec<Option<Transaction>>,
+    found_count: usize,
+}
+
+impl PartialBlock {
+    /// Places the prefilled transactions of `compact_block` and the `candidates` matching its short ids.
+    ///
+    /// Candidates sharing their short id with another candidate are ignored, so the transactions at their
+    /// index end up missing and get requested from the peer.
+    pub fn new(compact_block: CompactBlock, candidates: impl IntoIterator<Item = Arc<Transaction>>) -> Result<Self, ProtocolError> {
+        let transaction_count = compact_block.transaction_count();
+        if transaction_count == 0 {
+            return Err(ProtocolError::Other("compact block has no transactions"));
+        }
+        let seed = short_id_seed(compact_block.hash(), compact_block.short_id_nonce);
+        let mut matches: HashMap<ShortTransactionId, Option<Arc<Transaction>>> = HashMap::new();
+        for tx in candidates {
+            match matches.entry(short_transaction_id(seed, &tx.id())) {
+                Entry::Occupied(mut entry) => {
+                    entry.insert(None);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(Some(tx));
+                }
+            }
+        }
+
+        let prefilled_count = compact_block.prefilled_transactions.len();
+        let mut short_ids = compact_block.short_ids.into_iter();
+        let lookup = |short_id: ShortTransactionId| matches.get(&short_id).cloned().flatten().map(|tx| (*tx).clone());
+        let mut transactions = Vec::with_capacity(transaction_count);
+        for (index, prefilled) in compact_block.prefilled_transactions {
+            let index = index as usize;
+            if index < transactions.len() || index >= transaction_count {
+                return Err(ProtocolError::Other("compact block has misplaced prefilled transactions"));
+            }
+            transactions.extend(short_ids.by_ref().take(index - transactions.len()).map(lookup));
+            transactions.push(Some(prefilled));
+        }
+        transactions.extend(short_ids.map(lookup));
+        let found_count = transactions.iter().flatten().count() - prefilled_count;
+        Ok(Self { header: compact_block.header, transactions, found_count })
+    }
+
+    /// Number of transactions found among the candidates, prefilled transactions excluded
+    pub fn found_count(&self) -> usize {
+        self.found_count
+    }
+
+    /// Indexes of the transactions still missing, in increasing order
+    pub fn missing_indexes(&self) -> Vec<u32> {
+        self.transactions.iter().enumerate().filter_map(|(index, tx)| tx.is_none().then_some(index as u32)).collect()
+    }
+
+    /// Completes the block with `missing`, the transactions at the missing indexes in increasing order of index
+    pub fn fill(self, missing: Vec<Transaction>) -> Result<Block, ProtocolError> {
+        if missing.len() != self.transactions.len() - self.transactions.iter().flatten().count() {
+            return Err(ProtocolError::Other("unexpected number of missing block transactions"));
+        }
+        let mut missing = missing.into_iter();
+        let transactions = self.transactions.into_iter().map(|tx| tx.or_else(|| missing.next()).unwrap()).collect();
+        Ok(Block::from_arcs(self.header, Arc::new(transactions)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vecno_consensus_core::{
+        subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE},
+        tx::{TransactionInput, TransactionOutpoint},
+    };
+
+    fn transaction(index: u32) -> Transaction {
+        let subnetwork_id = if index == 0 { SUBNETWORK_ID_COINBASE } else { SUBNETWORK_ID_NATIVE };
+        let inputs = if index == 0 {
+            vec![]
+        } else {
+            vec![TransactionInput::new(TransactionOutpoint::new(Hash::from_u64_word(index as u64), 0), vec![], 0, 1)]
+        };
+        Transaction::new(0, inputs, vec![], 0, subnetwork_id, 0, vec![])
+    }
+
+    fn block(transaction_count: u32) -> Block {
+        let transactions = (0..transaction_count).map(transaction).collect();
+        Block::new(Header::from_precomputed_hash(Hash::from_u64_word(1), vec![]), transactions)
+    }
+
+    #[test]
+    fn test_short_ids() {
+        let block = block(10);
+        let compact_block = CompactBlock::new(&block, 7);
+        assert_eq!(compact_block.transaction_count(), 10);
+        assert_eq!(compact_block.prefilled_transactions.len(), 1);
+        assert!(compact_block.short_ids.iter().all(|&short_id| short_id <= SHORT_ID_MASK));
+
+        let filter = compact_block.short_id_filter();
+        assert!(block.transactions.iter().skip(1).all(|tx| filter.matches(&tx.id())));
+        assert!(!filter.matches(&transaction(10).id()));
+
+        // Short ids depend on the nonce
+        assert_ne!(CompactBlock::new(&block, 8).short_ids, compact_block.short_ids);
+    }
+
+    #[test]
+    fn test_block_reconstruction() {
+        let block = block(10);
+        let mempool =
+            block.transactions.iter().skip(1).take(6).cloned().chain((10..20).map(transaction)).map(Arc::new).collect::<Vec<_>>();
+
+        let partial_block = PartialBlock::new(CompactBlock::new(&block, 7), mempool).unwrap();
+        assert_eq!(partial_block.found_count(), 6);
+        assert_eq!(partial_block.missing_indexes(), vec![7, 8, 9]);
+        assert!(PartialBlock::new(CompactBlock::new(&block, 7), vec![]).unwrap().fill(vec![]).is_err());
+
+        let missing = block.transactions[7..].to_vec();
+        let rebuilt = partial_block.fill(missing).unwrap();
+        assert_eq!(rebuilt.hash(), block.hash());
+        assert_eq!(*rebuilt.transactions, *block.transactions);
+    }
+
+    #[test]
+    fn test_invalid_compact_blocks() {
+        let block = block(4);
+        let mut compact_block = CompactBlock::new(&block, 7);
+        compact_block.prefilled_transactions[0].0 = 4;
+        assert!(PartialBlock::new(compact_block, vec![]).is_err());
+
+        let mut compact_block = CompactBlock::new(&block, 7);
+        compact_block.prefilled_transactions.push((0, transaction(0)));
+        assert!(PartialBlock::new(compact_block, vec![]).is_err());
+
+        let empty_compact_block =
+            CompactBlock { header: block.header.clone(), short_id_nonce: 0, short_ids: vec![], prefilled_transactions: vec![] };
+        assert!(PartialBlock::new(empty_compact_block, vec![]).is_err());
+    }
+}
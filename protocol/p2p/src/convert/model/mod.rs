@@ -1,2 +1,3 @@
+pub mod compact_block;
 pub mod trusted;
 pub mod version;
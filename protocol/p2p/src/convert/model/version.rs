@@ -8,10 +8,25 @@ use vecno_utils::networking::{NetAddress, PeerId};
 /// Maximum allowed length for the user agent field in a version message `VersionMessage`.
 pub const MAX_USER_AGENT_LEN: usize = 256;
 
+/// Service flag advertised in the version message by nodes accepting `TransactionPackage` messages
+pub const SERVICE_FLAG_PACKAGE_RELAY: u64 = 1 << 0;
+
+/// Service flag advertised in the version message by nodes offering to encrypt the connection, see [`crate::VecnodHandshake`]
+pub const SERVICE_FLAG_ENCRYPTED_TRANSPORT: u64 = 1 << 1;
+
+/// Service flag advertised in the version message by nodes able to relay blocks as `CompactBlock` messages
+pub const SERVICE_FLAG_COMPACT_BLOCKS: u64 = 1 << 2;
+
+/// Service flag advertised in the version message by nodes relaying `StemTransaction` messages of the Dandelion++ stem phase
+pub const SERVICE_FLAG_DANDELION: u64 = 1 << 3;
+
+/// Service flag advertised in the version message by nodes able to decode the onion addresses of `Addresses` messages
+pub const SERVICE_FLAG_ONION_ADDRESSES: u64 = 1 << 4;
+
 pub struct Version {
     pub protocol_version: u32,
     pub network: String,
-    pub services: u64, // TODO
+    pub services: u64,
     pub timestamp: u64,
     pub address: Option<NetAddress>,
     pub id: PeerId,
@@ -4,7 +4,7 @@ use super::error::ConversionError;
 use crate::pb as protowire;
 
 use itertools::Itertools;
-use vecno_utils::networking::{IpAddress, NetAddress};
+use vecno_utils::networking::{IpAddress, NetAddress, OnionAddress};
 
 // ----------------------------------------------------------------------------
 // consensus_core to protowire
@@ -20,13 +20,17 @@ impl From<(IpAddress, u16)> for protowire::NetAddress {
                 IpAddr::V6(ip) => ip.octets().to_vec(),
             },
             port: port as u32,
+            onion: vec![],
         }
     }
 }
 
 impl From<NetAddress> for protowire::NetAddress {
     fn from(item: NetAddress) -> Self {
-        (item.ip, item.port).into()
+        match item.onion {
+            Some(onion) => Self { timestamp: 0, ip: vec![], port: item.port as u32, onion: onion.public_key().to_vec() },
+            None => (item.ip, item.port).into(),
+        }
     }
 }
 
@@ -60,6 +64,11 @@ impl TryFrom<protowire::NetAddress> for NetAddress {
     type Error = ConversionError;
 
     fn try_from(item: protowire::NetAddress) -> Result<Self, Self::Error> {
+        if !item.onion.is_empty() {
+            let public_key: [u8; 32] =
+                item.onion.as_slice().try_into().map_err(|_| ConversionError::IllegalOnionLength(item.onion.len()))?;
+            return Ok(NetAddress::new_onion(OnionAddress::new(public_key), item.port.try_into()?));
+        }
         let (ip, port) = item.try_into()?;
         Ok(NetAddress::new(ip, port))
     }
@@ -67,7 +76,7 @@ impl TryFrom<protowire::NetAddress> for NetAddress {
 
 #[cfg(test)]
 mod tests {
-    use vecno_utils::networking::IpAddress;
+    use vecno_utils::networking::{IpAddress, NetAddress};
 
     use crate::pb;
     use std::{
@@ -77,14 +86,30 @@ mod tests {
 
     #[test]
     fn test_netaddress() {
-        let net_addr_ipv4 = pb::NetAddress { timestamp: 0, ip: hex::decode("6a0a8af0").unwrap(), port: 123 };
+        let net_addr_ipv4 = pb::NetAddress { timestamp: 0, ip: hex::decode("6a0a8af0").unwrap(), port: 123, onion: vec![] };
         let ipv4 = Ipv4Addr::from_str("106.10.138.240").unwrap().into();
         assert_eq!(<(IpAddress, u16)>::try_from(net_addr_ipv4.clone()).unwrap(), (ipv4, 123u16));
         assert_eq!(pb::NetAddress::from((ipv4, 123u16)), net_addr_ipv4);
 
-        let net_addr_ipv6 = pb::NetAddress { timestamp: 0, ip: hex::decode("20010db885a3000000008a2e03707334").unwrap(), port: 456 };
+        let net_addr_ipv6 =
+            pb::NetAddress { timestamp: 0, ip: hex::decode("20010db885a3000000008a2e03707334").unwrap(), port: 456, onion: vec![] };
         let ipv6 = Ipv6Addr::from_str("2001:0db8:85a3:0000:0000:8a2e:0370:7334").unwrap().into();
         assert_eq!(<(IpAddress, u16)>::try_from(net_addr_ipv6.clone()).unwrap(), (ipv6, 456u16));
         assert_eq!(pb::NetAddress::from((ipv6, 456u16)), net_addr_ipv6);
     }
+
+    #[test]
+    fn test_onion_netaddress() {
+        let onion = NetAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:7111").unwrap();
+        let net_addr_onion = pb::NetAddress::from(onion);
+        assert!(net_addr_onion.ip.is_empty());
+        assert_eq!(net_addr_onion.onion, onion.onion.unwrap().public_key().to_vec());
+        assert_eq!(NetAddress::try_from(net_addr_onion.clone()).unwrap(), onion);
+
+        let ipv4 = NetAddress::from_str("106.10.138.240:123").unwrap();
+        assert_eq!(NetAddress::try_from(pb::NetAddress::from(ipv4)).unwrap(), ipv4);
+
+        let truncated = pb::NetAddress { onion: net_addr_onion.onion[..31].to_vec(), ..net_addr_onion };
+        assert!(NetAddress::try_from(truncated).is_err());
+    }
 }
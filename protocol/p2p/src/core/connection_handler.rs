@@ -1,12 +1,14 @@
 use crate::common::ProtocolError;
 use crate::core::hub::HubEvent;
+use crate::core::proxy::{ProxyConfig, Socks5Connector};
 use crate::pb::{
     p2p_client::P2pClient as ProtoP2pClient, p2p_server::P2p as ProtoP2p, p2p_server::P2pServer as ProtoP2pServer, VecnodMessage,
 };
 use crate::{ConnectionInitializer, Router};
 use futures::FutureExt;
-use std::net::ToSocketAddrs;
+use std::net::{SocketAddr, ToSocketAddrs};
 use std::pin::Pin;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
@@ -28,6 +30,9 @@ pub enum ConnectionError {
     #[error("missing socket address")]
     NoAddress,
 
+    #[error("host name {0} cannot be resolved without leaking the query outside of the proxy")]
+    ProxiedHostName(String),
+
     #[error("{0}")]
     IoError(#[from] std::io::Error),
 
@@ -51,6 +56,7 @@ pub struct ConnectionHandler {
     hub_sender: MpscSender<HubEvent>,
     initializer: Arc<dyn ConnectionInitializer>,
     counters: Arc<TowerConnectionCounters>,
+    proxy_config: ProxyConfig,
 }
 
 impl ConnectionHandler {
@@ -58,8 +64,9 @@ impl ConnectionHandler {
         hub_sender: MpscSender<HubEvent>,
         initializer: Arc<dyn ConnectionInitializer>,
         counters: Arc<TowerConnectionCounters>,
+        proxy_config: ProxyConfig,
     ) -> Self {
-        Self { hub_sender, initializer, counters }
+        Self { hub_sender, initializer, counters, proxy_config }
     }
 
     /// Launches a P2P server listener loop
@@ -95,17 +102,32 @@ impl ConnectionHandler {
 
     /// Connect to a new peer
     pub(crate) async fn connect(&self, peer_address: String) -> Result<Arc<Router>, ConnectionError> {
-        let Some(socket_address) = peer_address.to_socket_addrs()?.next() else {
-            return Err(ConnectionError::NoAddress);
+        // Host names other than onion ones are resolved locally, which a proxy forbids
+        let target = match NetAddress::from_str(&peer_address) {
+            Ok(target) => target,
+            Err(_) if self.proxy_config.proxy.is_some() => return Err(ConnectionError::ProxiedHostName(peer_address)),
+            Err(_) => match peer_address.to_socket_addrs()?.next() {
+                Some(socket_address) => socket_address.into(),
+                None => return Err(ConnectionError::NoAddress),
+            },
         };
-        let peer_address = format!("http://{}", peer_address); // Add scheme prefix as required by Tonic
+        // Onion peers are identified by the virtual IP of their service
+        let socket_address = SocketAddr::from(target);
+        let proxy = self.proxy_config.proxy_for(&target)?;
+        let peer_address = format!("http://{}", socket_address); // Add scheme prefix as required by Tonic
 
-        let channel = tonic::transport::Endpoint::new(peer_address)?
+        let endpoint = tonic::transport::Endpoint::new(peer_address)?
             .timeout(Duration::from_millis(Self::communication_timeout()))
-            .connect_timeout(Duration::from_millis(Self::connect_timeout()))
-            .tcp_keepalive(Some(Duration::from_millis(Self::keep_alive())))
-            .connect()
-            .await?;
+            .tcp_keepalive(Some(Duration::from_millis(Self::keep_alive())));
+        let channel = match proxy {
+            Some(proxy) => {
+                endpoint
+                    .connect_timeout(Duration::from_millis(Self::proxy_connect_timeout()))
+                    .connect_with_connector(Socks5Connector::new(proxy, target))
+                    .await?
+            }
+            None => endpoint.connect_timeout(Duration::from_millis(Self::connect_timeout())).connect().await?,
+        };
 
         let channel = ServiceBuilder::new()
             .layer(MapResponseBodyLayer::new(move |body| CountBytesBody::new(body, self.counters.bytes_rx.clone())))
@@ -192,6 +214,11 @@ impl ConnectionHandler {
     fn connect_timeout() -> u64 {
         1_000
     }
+
+    /// Connections through a proxy are given more time, since circuits may have to be built by the proxy
+    fn proxy_connect_timeout() -> u64 {
+        10_000
+    }
 }
 
 #[tonic::async_trait]
@@ -0,0 +1,235 @@
+use crate::{
+    common::ProtocolError,
+    make_message,
+    pb::{vecnod_message::Payload, EncryptedMessage, VecnodMessage},
+};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use prost::Message;
+use secp256k1::{
+    ellswift::{ElligatorSwift, ElligatorSwiftParty},
+    SecretKey, SECP256K1,
+};
+use sha2::{Digest, Sha256};
+use std::fmt::Debug;
+
+/// Size of the ElligatorSwift encoded ephemeral public keys carried by version messages
+pub const ENCRYPTION_KEY_SIZE: usize = 64;
+
+/// Domain separating the session keys from any other use of the shared secret
+const KEY_DERIVATION_DOMAIN: &[u8] = b"vecno-p2p-encryption-v1";
+
+/// An ephemeral key pair, used for the key exchange of a single connection
+pub struct EphemeralKey {
+    secret_key: SecretKey,
+    encoding: ElligatorSwift,
+}
+
+impl EphemeralKey {
+    pub fn generate() -> Self {
+        let secret_key = SecretKey::new(&mut rand::thread_rng());
+        let encoding = ElligatorSwift::from_seckey(SECP256K1, secret_key, Some(rand::random()));
+        Self { secret_key, encoding }
+    }
+
+    /// The encoded public key, indistinguishable from random bytes
+    pub fn public_key(&self) -> Vec<u8> {
+        self.encoding.to_array().to_vec()
+    }
+
+    /// Derives the ciphers of the session from the public key of the peer. The initiator of the key exchange
+    /// is the outbound side of the connection.
+    ///
+    /// The session keys are bound to the services and keys advertised by both version messages, so that a session
+    /// fails authentication if any of them was tampered with on the way.
+    pub fn derive_session(
+        self,
+        self_services: u64,
+        peer_services: u64,
+        peer_public_key: &[u8],
+        is_initiator: bool,
+    ) -> Result<SessionCiphers, ProtocolError> {
+        let peer_public_key: [u8; ENCRYPTION_KEY_SIZE] =
+            peer_public_key.try_into().map_err(|_| ProtocolError::Other("invalid encryption key length"))?;
+        let peer_encoding = ElligatorSwift::from_array(peer_public_key);
+        let (initiator_encoding, responder_encoding, party) = if is_initiator {
+            (self.encoding, peer_encoding, ElligatorSwiftParty::A)
+        } else {
+            (peer_encoding, self.encoding, ElligatorSwiftParty::B)
+        };
+        let (initiator_services, responder_services) =
+            if is_initiator { (self_services, peer_services) } else { (peer_services, self_services) };
+        let shared_secret = ElligatorSwift::shared_secret(initiator_encoding, responder_encoding, self.secret_key, party, None);
+
+        let mut transcript = Vec::with_capacity(2 * (size_of::<u64>() + ENCRYPTION_KEY_SIZE));
+        transcript.extend(initiator_services.to_le_bytes());
+        transcript.extend(initiator_encoding.to_array());
+        transcript.extend(responder_services.to_le_bytes());
+        transcript.extend(responder_encoding.to_array());
+
+        let initiator_cipher = MessageCipher::new(derive_key(shared_secret.as_secret_bytes(), &transcript, b"initiator"));
+        let responder_cipher = MessageCipher::new(derive_key(shared_secret.as_secret_bytes(), &transcript, b"responder"));
+        Ok(if is_initiator {
+            SessionCiphers { outgoing: initiator_cipher, incoming: responder_cipher }
+        } else {
+            SessionCiphers { outgoing: responder_cipher, incoming: initiator_cipher }
+        })
+    }
+}
+
+fn derive_key(shared_secret: &[u8; 32], transcript: &[u8], direction: &[u8]) -> Key {
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_DERIVATION_DOMAIN);
+    hasher.update(direction);
+    hasher.update(transcript);
+    hasher.update(shared_secret);
+    Key::clone_from_slice(&hasher.finalize())
+}
+
+/// The ciphers of both directions of an encrypted connection
+pub struct SessionCiphers {
+    pub outgoing: MessageCipher,
+    pub incoming: MessageCipher,
+}
+
+/// Seals or opens the messages of one direction of a connection. Messages are numbered by the nonce,
+/// so that any dropped, replayed or reordered message fails authentication.
+pub struct MessageCipher {
+    aead: ChaCha20Poly1305,
+    counter: u64,
+}
+
+impl Debug for MessageCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageCipher").field("counter", &self.counter).finish_non_exhaustive()
+    }
+}
+
+impl MessageCipher {
+    fn new(key: Key) -> Self {
+        Self { aead: ChaCha20Poly1305::new(&key), counter: 0 }
+    }
+
+    /// Indicates whether no message was sealed or opened by this cipher yet
+    pub fn is_unused(&self) -> bool {
+        self.counter == 0
+    }
+
+    fn next_nonce(&mut self) -> Result<Nonce, ProtocolError> {
+        let mut nonce = Nonce::default();
+        nonce[4..].copy_from_slice(&self.counter.to_le_bytes());
+        self.counter = self.counter.checked_add(1).ok_or(ProtocolError::Other("encrypted session exhausted its nonces"))?;
+        Ok(nonce)
+    }
+
+    /// Wraps `msg` into an encrypted message
+    pub fn seal(&mut self, msg: &VecnodMessage) -> Result<VecnodMessage, ProtocolError> {
+        let nonce = self.next_nonce()?;
+        let ciphertext = self
+            .aead
+            .encrypt(&nonce, msg.encode_to_vec().as_slice())
+            .map_err(|_| ProtocolError::Other("message encryption failed"))?;
+        Ok(make_message!(Payload::Encrypted, EncryptedMessage { ciphertext }))
+    }
+
+    /// Unwraps the message held by an encrypted message, failing if it was not sealed by the peer cipher or out of order
+    pub fn open(&mut self, msg: &EncryptedMessage) -> Result<VecnodMessage, ProtocolError> {
+        let nonce = self.next_nonce()?;
+        let plaintext = self
+            .aead
+            .decrypt(&nonce, msg.ciphertext.as_slice())
+            .map_err(|_| ProtocolError::Other("encrypted message failed authentication"))?;
+        VecnodMessage::decode(plaintext.as_slice()).map_err(|_| ProtocolError::Other("encrypted message holds an invalid message"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb::PingMessage;
+
+    fn encrypted_payload(msg: VecnodMessage) -> EncryptedMessage {
+        let Some(Payload::Encrypted(encrypted)) = msg.payload else { panic!("expected an encrypted message") };
+        encrypted
+    }
+
+    const SERVICES: u64 = 0b11;
+
+    /// Derives the sessions of both peers, `received_services` being the services the responder sees the initiator advertise
+    fn session_pair_with_services(received_services: u64) -> (SessionCiphers, SessionCiphers) {
+        let initiator_key = EphemeralKey::generate();
+        let responder_key = EphemeralKey::generate();
+        let initiator_public_key = initiator_key.public_key();
+        let responder_public_key = responder_key.public_key();
+        (
+            initiator_key.derive_session(SERVICES, SERVICES, &responder_public_key, true).unwrap(),
+            responder_key.derive_session(SERVICES, received_services, &initiator_public_key, false).unwrap(),
+        )
+    }
+
+    fn session_pair() -> (SessionCiphers, SessionCiphers) {
+        session_pair_with_services(SERVICES)
+    }
+
+    #[test]
+    fn test_session_ciphers() {
+        assert_eq!(EphemeralKey::generate().public_key().len(), ENCRYPTION_KEY_SIZE);
+
+        let (mut initiator, mut responder) = session_pair();
+        let ping = make_message!(Payload::Ping, PingMessage { nonce: 7 }, 3, 4);
+        assert!(responder.incoming.is_unused());
+        for _ in 0..3 {
+            let sealed = initiator.outgoing.seal(&ping).unwrap();
+            assert_eq!(responder.incoming.open(&encrypted_payload(sealed)).unwrap(), ping);
+            let sealed = responder.outgoing.seal(&ping).unwrap();
+            assert_eq!(initiator.incoming.open(&encrypted_payload(sealed)).unwrap(), ping);
+        }
+
+        assert!(!responder.incoming.is_unused());
+
+        // Messages sealed for the other direction are rejected
+        let sealed = initiator.outgoing.seal(&ping).unwrap();
+        assert!(initiator.incoming.open(&encrypted_payload(sealed)).is_err());
+    }
+
+    #[test]
+    fn test_unauthentic_messages() {
+        let ping = make_message!(Payload::Ping, PingMessage { nonce: 7 });
+
+        // Tampered message
+        let (mut initiator, mut responder) = session_pair();
+        let mut tampered = encrypted_payload(initiator.outgoing.seal(&ping).unwrap());
+        tampered.ciphertext[0] ^= 1;
+        assert!(responder.incoming.open(&tampered).is_err());
+
+        // Replayed message
+        let (mut initiator, mut responder) = session_pair();
+        let sealed = encrypted_payload(initiator.outgoing.seal(&ping).unwrap());
+        assert!(responder.incoming.open(&sealed).is_ok());
+        assert!(responder.incoming.open(&sealed).is_err());
+
+        // Dropped message
+        let (mut initiator, mut responder) = session_pair();
+        let _dropped = initiator.outgoing.seal(&ping).unwrap();
+        let sealed = encrypted_payload(initiator.outgoing.seal(&ping).unwrap());
+        assert!(responder.incoming.open(&sealed).is_err());
+
+        // Message of another session
+        let (mut initiator, _) = session_pair();
+        let (_, mut responder) = session_pair();
+        let sealed = encrypted_payload(initiator.outgoing.seal(&ping).unwrap());
+        assert!(responder.incoming.open(&sealed).is_err());
+
+        // Session of peers whose version messages were tampered with
+        let (mut initiator, mut responder) = session_pair_with_services(SERVICES & !1);
+        let sealed = encrypted_payload(initiator.outgoing.seal(&ping).unwrap());
+        assert!(responder.incoming.open(&sealed).is_err());
+    }
+
+    #[test]
+    fn test_invalid_peer_key() {
+        assert!(EphemeralKey::generate().derive_session(SERVICES, SERVICES, &[0u8; 33], true).is_err());
+    }
+}
@@ -142,6 +142,17 @@ impl Hub {
         }
     }
 
+    /// Broadcast a message to all peers matching `predicate`
+    pub async fn broadcast_to_matching_peers<F>(&self, msg: VecnodMessage, predicate: F)
+    where
+        F: Fn(&Router) -> bool,
+    {
+        let peers = self.peers.read().values().filter(|router| predicate(router)).cloned().collect::<Vec<_>>();
+        for router in peers {
+            let _ = router.enqueue(msg.clone()).await;
+        }
+    }
+
     /// Broadcast a vector of messages to all peers
     pub async fn broadcast_many(&self, msgs: Vec<VecnodMessage>) {
         if msgs.is_empty() {
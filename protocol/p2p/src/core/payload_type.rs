@@ -46,6 +46,12 @@ pub enum VecnodMessagePayloadType {
     IbdChainBlockLocator,
     RequestAntipast,
     RequestNextPruningPointAndItsAnticoneBlocks,
+    TransactionPackage,
+    Encrypted,
+    CompactBlock,
+    RequestBlockTransactions,
+    BlockTransactions,
+    StemTransaction,
 }
 
 impl From<&VecnodMessagePayload> for VecnodMessagePayloadType {
@@ -100,6 +106,12 @@ impl From<&VecnodMessagePayload> for VecnodMessagePayloadType {
             VecnodMessagePayload::RequestNextPruningPointAndItsAnticoneBlocks(_) => {
                 VecnodMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks
             }
+            VecnodMessagePayload::TransactionPackage(_) => VecnodMessagePayloadType::TransactionPackage,
+            VecnodMessagePayload::Encrypted(_) => VecnodMessagePayloadType::Encrypted,
+            VecnodMessagePayload::CompactBlock(_) => VecnodMessagePayloadType::CompactBlock,
+            VecnodMessagePayload::RequestBlockTransactions(_) => VecnodMessagePayloadType::RequestBlockTransactions,
+            VecnodMessagePayload::BlockTransactions(_) => VecnodMessagePayloadType::BlockTransactions,
+            VecnodMessagePayload::StemTransaction(_) => VecnodMessagePayloadType::StemTransaction,
         }
     }
 }
@@ -1,3 +1,6 @@
+use crate::convert::model::version::{
+    SERVICE_FLAG_COMPACT_BLOCKS, SERVICE_FLAG_DANDELION, SERVICE_FLAG_ONION_ADDRESSES, SERVICE_FLAG_PACKAGE_RELAY,
+};
 use std::{fmt::Display, net::SocketAddr, sync::Arc, time::Instant};
 use vecno_consensus_core::subnets::SubnetworkId;
 use vecno_utils::networking::{IpAddress, PeerId};
@@ -5,7 +8,7 @@ use vecno_utils::networking::{IpAddress, PeerId};
 #[derive(Debug, Clone, Default)]
 pub struct PeerProperties {
     pub user_agent: String,
-    // TODO: add services
+    pub services: u64,
     pub advertised_protocol_version: u32,
     pub protocol_version: u32,
     pub disable_relay_tx: bool,
@@ -13,6 +16,28 @@ pub struct PeerProperties {
     pub time_offset: i64,
 }
 
+impl PeerProperties {
+    /// Indicates whether the peer accepts transaction packages
+    pub fn supports_package_relay(&self) -> bool {
+        self.services & SERVICE_FLAG_PACKAGE_RELAY != 0
+    }
+
+    /// Indicates whether the peer is able to send relay blocks as compact blocks
+    pub fn supports_compact_blocks(&self) -> bool {
+        self.services & SERVICE_FLAG_COMPACT_BLOCKS != 0
+    }
+
+    /// Indicates whether the peer accepts transactions in the stem phase of Dandelion++ relay
+    pub fn supports_dandelion(&self) -> bool {
+        self.services & SERVICE_FLAG_DANDELION != 0
+    }
+
+    /// Indicates whether the peer is able to receive onion addresses
+    pub fn supports_onion_addresses(&self) -> bool {
+        self.services & SERVICE_FLAG_ONION_ADDRESSES != 0
+    }
+}
+
 #[derive(Debug)]
 pub struct Peer {
     identity: PeerId,
@@ -21,6 +46,7 @@ pub struct Peer {
     connection_started: Instant,
     properties: Arc<PeerProperties>,
     last_ping_duration: u64,
+    is_encrypted: bool,
 }
 
 impl Peer {
@@ -31,8 +57,9 @@ impl Peer {
         connection_started: Instant,
         properties: Arc<PeerProperties>,
         last_ping_duration: u64,
+        is_encrypted: bool,
     ) -> Self {
-        Self { identity, net_address, is_outbound, connection_started, properties, last_ping_duration }
+        Self { identity, net_address, is_outbound, connection_started, properties, last_ping_duration, is_encrypted }
     }
 
     /// Internal identity of this peer
@@ -65,6 +92,11 @@ impl Peer {
     pub fn last_ping_duration(&self) -> u64 {
         self.last_ping_duration
     }
+
+    /// Indicates whether the messages exchanged with this peer are encrypted
+    pub fn is_encrypted(&self) -> bool {
+        self.is_encrypted
+    }
 }
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
@@ -0,0 +1,259 @@
+//!
+//! SOCKS5 client (RFC 1928) through which the outbound connections are made when a proxy is configured,
+//! typically the SOCKS port of a Tor daemon, which is also the only way to reach onion services.
+//!
+
+use hyper_util::rt::TokioIo;
+use std::{
+    future::Future,
+    io,
+    net::{IpAddr, SocketAddr},
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use tonic::transport::Uri;
+use vecno_utils::networking::NetAddress;
+
+const SOCKS_VERSION: u8 = 5;
+const NO_AUTHENTICATION: u8 = 0;
+const CONNECT_COMMAND: u8 = 1;
+const SUCCEEDED_REPLY: u8 = 0;
+
+const IPV4_ADDRESS_TYPE: u8 = 1;
+const DOMAIN_ADDRESS_TYPE: u8 = 3;
+const IPV6_ADDRESS_TYPE: u8 = 4;
+
+/// SOCKS5 proxies the outbound connections are made through
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProxyConfig {
+    /// Proxy of the connections to IP addresses, which are made directly if undefined
+    pub proxy: Option<SocketAddr>,
+    /// Proxy of the connections to onion services, which are unreachable if undefined
+    pub onion_proxy: Option<SocketAddr>,
+}
+
+impl ProxyConfig {
+    /// Returns the proxy `address` is to be connected through, `None` meaning a direct connection
+    pub fn proxy_for(&self, address: &NetAddress) -> io::Result<Option<SocketAddr>> {
+        if address.is_onion() {
+            match self.onion_proxy {
+                Some(proxy) => Ok(Some(proxy)),
+                None => Err(io::Error::other(format!("onion service {address} is unreachable without an onion proxy"))),
+            }
+        } else {
+            Ok(self.proxy)
+        }
+    }
+}
+
+/// Opens a connection to `target` through the SOCKS5 proxy at `proxy`. Onion services are passed to the proxy
+/// as domain names for it to resolve them, so that they never get resolved locally.
+pub async fn socks5_connect(proxy: SocketAddr, target: NetAddress) -> io::Result<TcpStream> {
+    let mut stream = TcpStream::connect(proxy).await?;
+    stream.set_nodelay(true)?;
+
+    // Greeting, offering no authentication only
+    stream.write_all(&[SOCKS_VERSION, 1, NO_AUTHENTICATION]).await?;
+    let mut method = [0u8; 2];
+    stream.read_exact(&mut method).await?;
+    if method != [SOCKS_VERSION, NO_AUTHENTICATION] {
+        return Err(io::Error::other("the SOCKS5 proxy requires an unsupported authentication method"));
+    }
+
+    let mut request = vec![SOCKS_VERSION, CONNECT_COMMAND, 0];
+    match (target.onion, target.ip.0) {
+        (Some(onion), _) => {
+            let domain = onion.to_string();
+            request.extend([DOMAIN_ADDRESS_TYPE, domain.len() as u8]);
+            request.extend(domain.as_bytes());
+        }
+        (None, IpAddr::V4(ip)) => {
+            request.push(IPV4_ADDRESS_TYPE);
+            request.extend(ip.octets());
+        }
+        (None, IpAddr::V6(ip)) => {
+            request.push(IPV6_ADDRESS_TYPE);
+            request.extend(ip.octets());
+        }
+    }
+    request.extend(target.port.to_be_bytes());
+    stream.write_all(&request).await?;
+
+    // The reply holds the address bound by the proxy, which is of no use here
+    let mut reply = [0u8; 4];
+    stream.read_exact(&mut reply).await?;
+    if reply[0] != SOCKS_VERSION {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid SOCKS5 reply"));
+    }
+    if reply[1] != SUCCEEDED_REPLY {
+        return Err(io::Error::other(format!("the SOCKS5 proxy failed connecting to {target} with reply code {}", reply[1])));
+    }
+    let bound_address_len = match reply[3] {
+        IPV4_ADDRESS_TYPE => 4,
+        IPV6_ADDRESS_TYPE => 16,
+        DOMAIN_ADDRESS_TYPE => stream.read_u8().await? as usize,
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid SOCKS5 reply address type")),
+    };
+    let mut bound_address = vec![0u8; bound_address_len + size_of::<u16>()];
+    stream.read_exact(&mut bound_address).await?;
+
+    Ok(stream)
+}
+
+/// Connector of tonic endpoints opening the connection to `target` through a SOCKS5 proxy, regardless of the endpoint URI
+#[derive(Clone)]
+pub(crate) struct Socks5Connector {
+    proxy: SocketAddr,
+    target: NetAddress,
+}
+
+impl Socks5Connector {
+    pub(crate) fn new(proxy: SocketAddr, target: NetAddress) -> Self {
+        Self { proxy, target }
+    }
+}
+
+impl tower::Service<Uri> for Socks5Connector {
+    type Response = TokioIo<TcpStream>;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = io::Result<Self::Response>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _uri: Uri) -> Self::Future {
+        let (proxy, target) = (self.proxy, self.target);
+        Box::pin(async move { socks5_connect(proxy, target).await.map(TokioIo::new) })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use std::{str::FromStr, sync::Arc};
+    use tokio::{io::copy_bidirectional, net::TcpListener};
+
+    /// Spawns a minimal SOCKS5 server relaying the connections to their target, onion services being relayed to
+    /// `onion_service`. Returns the address of the server along with the targets requested so far.
+    pub(crate) async fn spawn_socks5_stand_in(onion_service: Option<SocketAddr>) -> (SocketAddr, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let _ = relay(stream, onion_service, recorded).await;
+                });
+            }
+        });
+        (address, requests)
+    }
+
+    async fn relay(mut stream: TcpStream, onion_service: Option<SocketAddr>, recorded: Arc<Mutex<Vec<String>>>) -> io::Result<()> {
+        let mut greeting = [0u8; 2];
+        stream.read_exact(&mut greeting).await?;
+        let mut methods = vec![0u8; greeting[1] as usize];
+        stream.read_exact(&mut methods).await?;
+        stream.write_all(&[SOCKS_VERSION, NO_AUTHENTICATION]).await?;
+
+        let mut request = [0u8; 4];
+        stream.read_exact(&mut request).await?;
+        let host = match request[3] {
+            IPV4_ADDRESS_TYPE => {
+                let mut octets = [0u8; 4];
+                stream.read_exact(&mut octets).await?;
+                IpAddr::from(octets).to_string()
+            }
+            IPV6_ADDRESS_TYPE => {
+                let mut octets = [0u8; 16];
+                stream.read_exact(&mut octets).await?;
+                format!("[{}]", IpAddr::from(octets))
+            }
+            _ => {
+                let mut domain = vec![0u8; stream.read_u8().await? as usize];
+                stream.read_exact(&mut domain).await?;
+                String::from_utf8(domain).unwrap()
+            }
+        };
+        let target = format!("{}:{}", host, stream.read_u16().await?);
+        recorded.lock().push(target.clone());
+
+        let connection = match request[3] {
+            DOMAIN_ADDRESS_TYPE => match onion_service {
+                Some(onion_service) => TcpStream::connect(onion_service).await,
+                None => Err(io::ErrorKind::NotFound.into()),
+            },
+            _ => TcpStream::connect(target).await,
+        };
+        let Ok(mut connection) = connection else {
+            // Host unreachable
+            return stream.write_all(&[SOCKS_VERSION, 4, 0, IPV4_ADDRESS_TYPE, 0, 0, 0, 0, 0, 0]).await;
+        };
+        stream.write_all(&[SOCKS_VERSION, SUCCEEDED_REPLY, 0, IPV4_ADDRESS_TYPE, 127, 0, 0, 1, 0, 0]).await?;
+        copy_bidirectional(&mut stream, &mut connection).await.map(drop)
+    }
+
+    async fn spawn_echo_server() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let (mut reader, mut writer) = stream.split();
+                    let _ = tokio::io::copy(&mut reader, &mut writer).await;
+                });
+            }
+        });
+        address
+    }
+
+    async fn assert_echo(mut stream: TcpStream) {
+        stream.write_all(b"vecno").await.unwrap();
+        let mut echo = [0u8; 5];
+        stream.read_exact(&mut echo).await.unwrap();
+        assert_eq!(&echo, b"vecno");
+    }
+
+    #[tokio::test]
+    async fn test_socks5_connect() {
+        let echo_server = spawn_echo_server().await;
+        let (proxy, requests) = spawn_socks5_stand_in(Some(echo_server)).await;
+
+        // IP targets
+        assert_echo(socks5_connect(proxy, echo_server.into()).await.unwrap()).await;
+
+        // Onion services are requested by domain name
+        let onion = NetAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:7111").unwrap();
+        assert_echo(socks5_connect(proxy, onion).await.unwrap()).await;
+
+        // Failures reported by the proxy
+        let (failing_proxy, _) = spawn_socks5_stand_in(None).await;
+        assert!(socks5_connect(failing_proxy, onion).await.is_err());
+
+        assert_eq!(*requests.lock(), vec![echo_server.to_string(), onion.to_string()]);
+    }
+
+    #[test]
+    fn test_proxy_selection() {
+        let proxy = SocketAddr::from_str("127.0.0.1:9050").unwrap();
+        let onion_proxy = SocketAddr::from_str("127.0.0.1:9150").unwrap();
+        let ip = NetAddress::from_str("1.2.3.4:7111").unwrap();
+        let onion = NetAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:7111").unwrap();
+
+        let config = ProxyConfig::default();
+        assert_eq!(config.proxy_for(&ip).unwrap(), None);
+        assert!(config.proxy_for(&onion).is_err());
+
+        let config = ProxyConfig { proxy: Some(proxy), onion_proxy: Some(onion_proxy) };
+        assert_eq!(config.proxy_for(&ip).unwrap(), Some(proxy));
+        assert_eq!(config.proxy_for(&onion).unwrap(), Some(onion_proxy));
+    }
+}
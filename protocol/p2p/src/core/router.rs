@@ -19,6 +19,7 @@ use tonic::Streaming;
 use vecno_core::{debug, error, info, trace, warn};
 use vecno_utils::networking::PeerId;
 
+use super::encryption::{MessageCipher, SessionCiphers};
 use super::peer::{PeerKey, PeerProperties};
 
 pub struct IncomingRoute {
@@ -102,6 +103,9 @@ struct RouterMutableState {
 
     /// Duration of the last ping to this peer
     last_ping_duration: u64,
+
+    /// Cipher of the outgoing messages, set aside until the peer is ready to decrypt them
+    pending_outgoing_cipher: Option<MessageCipher>,
 }
 
 impl RouterMutableState {
@@ -134,6 +138,12 @@ pub struct Router {
     /// The outgoing route for sending messages to this peer
     outgoing_route: MpscSender<VecnodMessage>,
 
+    /// Cipher encrypting the outgoing messages if the connection is encrypted
+    outgoing_cipher: Mutex<Option<MessageCipher>>,
+
+    /// Cipher decrypting the incoming messages if the connection is encrypted
+    incoming_cipher: Mutex<Option<MessageCipher>>,
+
     /// A channel sender for internal event management. Used to send information from each router to a central hub object
     hub_sender: MpscSender<HubEvent>,
 
@@ -162,6 +172,7 @@ impl From<&Router> for Peer {
             router.connection_started,
             router.properties(),
             router.last_ping_duration(),
+            router.is_encrypted(),
         )
     }
 }
@@ -191,6 +202,8 @@ impl Router {
             routing_map_by_type: RwLock::new(HashMap::new()),
             routing_map_by_id: RwLock::new(HashMap::new()),
             outgoing_route,
+            outgoing_cipher: Mutex::new(None),
+            incoming_cipher: Mutex::new(None),
             hub_sender,
             mutable_state: Mutex::new(RouterMutableState::new(Some(start_sender), Some(shutdown_sender))),
         });
@@ -294,6 +307,42 @@ impl Router {
         self.mutable_state.lock().last_ping_duration
     }
 
+    /// Indicates whether the messages exchanged with this peer are encrypted
+    pub fn is_encrypted(&self) -> bool {
+        self.outgoing_cipher.lock().is_some()
+    }
+
+    /// Sets the ciphers of an encrypted session. Incoming messages are decrypted right away, whereas outgoing
+    /// messages get encrypted only once [`Router::start_outgoing_encryption`] is called
+    pub(crate) fn set_session_ciphers(&self, ciphers: SessionCiphers) {
+        *self.incoming_cipher.lock() = Some(ciphers.incoming);
+        self.mutable_state.lock().pending_outgoing_cipher = Some(ciphers.outgoing);
+    }
+
+    /// Starts encrypting the outgoing messages if session ciphers were set. Must be called only once the peer
+    /// is known to have set its own session ciphers.
+    pub(crate) fn start_outgoing_encryption(&self) {
+        if let Some(cipher) = self.mutable_state.lock().pending_outgoing_cipher.take() {
+            *self.outgoing_cipher.lock() = Some(cipher);
+        }
+    }
+
+    /// Unwraps an incoming encrypted message, making sure the peer does not mix plaintext messages into an encrypted connection
+    fn open_incoming(&self, msg: VecnodMessage) -> Result<VecnodMessage, ProtocolError> {
+        let mut incoming_cipher = self.incoming_cipher.lock();
+        match (&msg.payload, incoming_cipher.as_mut()) {
+            (Some(VecnodMessagePayload::Encrypted(encrypted)), Some(cipher)) => cipher.open(encrypted),
+            (Some(VecnodMessagePayload::Encrypted(_)), None) => {
+                Err(ProtocolError::Other("received an encrypted message over a plaintext connection"))
+            }
+            // The peer sends its verack in plaintext, possibly after we already set the session ciphers but always
+            // before its first encrypted message
+            (Some(VecnodMessagePayload::Verack(_)), Some(cipher)) if cipher.is_unused() => Ok(msg),
+            (_, None) => Ok(msg),
+            (_, Some(_)) => Err(ProtocolError::Other("received a plaintext message over an encrypted connection")),
+        }
+    }
+
     pub fn incoming_flow_baseline_channel_size() -> usize {
         256
     }
@@ -363,6 +412,7 @@ impl Router {
 
     /// Routes a message coming from the network to the corresponding registered flow
     pub fn route_to_flow(&self, msg: VecnodMessage) -> Result<(), ProtocolError> {
+        let msg = self.open_incoming(msg)?;
         if msg.payload.is_none() {
             debug!("P2P, Route to flow got empty payload, peer: {}", self);
             return Err(ProtocolError::Other("received vecnod p2p message with empty payload"));
@@ -402,6 +452,12 @@ impl Router {
     /// Enqueues a locally-originated message to be sent to the network peer
     pub async fn enqueue(&self, msg: VecnodMessage) -> Result<(), ProtocolError> {
         assert!(msg.payload.is_some(), "Vecnod P2P message should always have a value");
+        // Messages are sealed and queued under the same lock so that they reach the peer in the order of their nonces
+        let mut outgoing_cipher = self.outgoing_cipher.lock();
+        let msg = match outgoing_cipher.as_mut() {
+            Some(cipher) => cipher.seal(&msg)?,
+            None => msg,
+        };
         match self.outgoing_route.try_send(msg) {
             Ok(_) => Ok(()),
             Err(TrySendError::Closed(_)) => Err(ProtocolError::ConnectionClosed),
@@ -1,5 +1,6 @@
 use crate::{
     common::ProtocolError,
+    convert::model::version::SERVICE_FLAG_ENCRYPTED_TRANSPORT,
     core::adaptor::ConnectionInitializer,
     handshake::VecnodHandshake,
     pb::{self, VersionMessage},
@@ -64,6 +65,11 @@ impl EchoFlow {
             VecnodMessagePayloadType::IbdChainBlockLocator,
             VecnodMessagePayloadType::RequestAntipast,
             VecnodMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks,
+            VecnodMessagePayloadType::TransactionPackage,
+            VecnodMessagePayloadType::CompactBlock,
+            VecnodMessagePayloadType::RequestBlockTransactions,
+            VecnodMessagePayloadType::BlockTransactions,
+            VecnodMessagePayloadType::StemTransaction,
         ]);
         let mut echo_flow = EchoFlow { router, receiver };
         debug!("EchoFlow, start app-layer receiving loop");
@@ -88,12 +94,15 @@ impl EchoFlow {
 
 /// An example initializer, performing handshake and registering a simple echo flow
 #[derive(Default)]
-pub struct EchoFlowInitializer {}
+pub struct EchoFlowInitializer {
+    /// Whether to offer peers to encrypt the connection
+    offer_encryption: bool,
+}
 
-fn build_dummy_version_message() -> VersionMessage {
+fn build_dummy_version_message(services: u64) -> VersionMessage {
     pb::VersionMessage {
         protocol_version: 1,
-        services: 0,
+        services,
         timestamp: unix_now() as i64,
         address: None,
         id: Vec::from(Uuid::new_v4().as_bytes()),
@@ -101,12 +110,17 @@ fn build_dummy_version_message() -> VersionMessage {
         disable_relay_tx: false,
         subnetwork_id: None,
         network: "vecno-mainnet".to_string(),
+        encryption_key: vec![],
     }
 }
 
 impl EchoFlowInitializer {
     pub fn new() -> Self {
-        EchoFlowInitializer {}
+        EchoFlowInitializer { offer_encryption: false }
+    }
+
+    pub fn with_encryption(offer_encryption: bool) -> Self {
+        EchoFlowInitializer { offer_encryption }
     }
 }
 
@@ -124,7 +138,8 @@ impl ConnectionInitializer for EchoFlowInitializer {
         router.start();
 
         // Build the local version message
-        let self_version_message = build_dummy_version_message();
+        let services = if self.offer_encryption { SERVICE_FLAG_ENCRYPTED_TRANSPORT } else { 0 };
+        let self_version_message = build_dummy_version_message(services);
 
         // Perform the handshake
         let peer_version_message = handshake.handshake(self_version_message).await?;
@@ -145,10 +160,13 @@ impl ConnectionInitializer for EchoFlowInitializer {
 
 #[cfg(test)]
 mod tests {
-    use std::{str::FromStr, time::Duration};
+    use std::{net::SocketAddr, str::FromStr, time::Duration};
 
     use super::*;
-    use crate::{Adaptor, Hub};
+    use crate::{
+        core::proxy::tests::spawn_socks5_stand_in, make_message, pb::vecnod_message::Payload, Adaptor, ConnectionError, Hub,
+        ProxyConfig,
+    };
     use vecno_core::debug;
     use vecno_utils::networking::NetAddress;
 
@@ -157,10 +175,14 @@ mod tests {
         vecno_core::log::try_init_logger("debug");
 
         let address1 = NetAddress::from_str("[::1]:50053").unwrap();
-        let adaptor1 = Adaptor::bidirectional(address1, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default()).unwrap();
+        let adaptor1 =
+            Adaptor::bidirectional(address1, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default(), Default::default())
+                .unwrap();
 
         let address2 = NetAddress::from_str("[::1]:50054").unwrap();
-        let adaptor2 = Adaptor::bidirectional(address2, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default()).unwrap();
+        let adaptor2 =
+            Adaptor::bidirectional(address2, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default(), Default::default())
+                .unwrap();
 
         // Initiate the connection from `adaptor1` (outbound) to `adaptor2` (inbound)
         let peer2_id = adaptor1
@@ -201,4 +223,95 @@ mod tests {
         drop(adaptor2);
         tokio::time::sleep(std::time::Duration::from_secs(2)).await;
     }
+
+    #[tokio::test]
+    async fn test_encrypted_handshake() {
+        vecno_core::log::try_init_logger("debug");
+
+        // The connection is encrypted only if both peers offer it, and falls back to plaintext otherwise
+        for (port, offer_encryption, expected_encryption) in [(50055, true, true), (50057, false, false)] {
+            let address1 = NetAddress::from_str(&format!("[::1]:{}", port)).unwrap();
+            let initializer1 = Arc::new(EchoFlowInitializer::with_encryption(offer_encryption));
+            let adaptor1 = Adaptor::bidirectional(address1, Hub::new(), initializer1, Default::default(), Default::default()).unwrap();
+
+            let address2 = NetAddress::from_str(&format!("[::1]:{}", port + 1)).unwrap();
+            let initializer2 = Arc::new(EchoFlowInitializer::with_encryption(true));
+            let adaptor2 = Adaptor::bidirectional(address2, Hub::new(), initializer2, Default::default(), Default::default()).unwrap();
+
+            let peer2_id = adaptor1
+                .connect_peer_with_retries(format!("[::1]:{}", port + 1), 16, Duration::from_secs(1))
+                .await
+                .expect("peer connection failed");
+
+            // Wait for handshake completion
+            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+            // Start an endless ping exchange between the echo flows, which must not break the connection
+            adaptor1.broadcast(make_message!(Payload::Ping, pb::PingMessage { nonce: 1 })).await;
+            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+            let adaptor1_peers = adaptor1.active_peers();
+            let adaptor2_peers = adaptor2.active_peers();
+            assert_eq!(adaptor1_peers.len(), 1, "handshake failed -- outbound peer is missing");
+            assert_eq!(adaptor2_peers.len(), 1, "handshake failed -- inbound peer is missing");
+            assert_eq!(adaptor1_peers[0].is_encrypted(), expected_encryption);
+            assert_eq!(adaptor2_peers[0].is_encrypted(), expected_encryption);
+
+            adaptor1.terminate(peer2_id).await;
+            adaptor1.close().await;
+            adaptor2.close().await;
+            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_proxied_handshake() {
+        vecno_core::log::try_init_logger("debug");
+
+        let onion = NetAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:50060").unwrap();
+        let address2 = NetAddress::from_str("[::1]:50060").unwrap();
+        let adaptor2 =
+            Adaptor::bidirectional(address2, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default(), Default::default())
+                .unwrap();
+
+        // Onion services are unreachable without an onion proxy
+        let address1 = NetAddress::from_str("[::1]:50059").unwrap();
+        let (proxy, requests) = spawn_socks5_stand_in(Some(address2.into())).await;
+        let proxy_config = ProxyConfig { proxy: Some(proxy), onion_proxy: None };
+        let adaptor1 =
+            Adaptor::bidirectional(address1, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default(), proxy_config)
+                .unwrap();
+        assert!(adaptor1.connect_peer(onion.to_string()).await.is_err());
+        // Host names are not resolved outside of the proxy
+        assert!(matches!(adaptor1.connect_peer(String::from("localhost:50060")).await, Err(ConnectionError::ProxiedHostName(_))));
+
+        // Connections to IP addresses go through the proxy as well
+        let peer2_id = adaptor1.connect_peer(String::from("[::1]:50060")).await.expect("proxied peer connection failed");
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        assert_eq!(adaptor1.active_peers().len(), 1, "handshake failed -- outbound peer is missing");
+        assert_eq!(adaptor2.active_peers().len(), 1, "handshake failed -- inbound peer is missing");
+        adaptor1.terminate(peer2_id).await;
+        adaptor1.close().await;
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+        // Onion peers are identified by the virtual IP of their service
+        let address1 = NetAddress::from_str("[::1]:50061").unwrap();
+        let proxy_config = ProxyConfig { proxy: None, onion_proxy: Some(proxy) };
+        let adaptor1 =
+            Adaptor::bidirectional(address1, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default(), proxy_config)
+                .unwrap();
+        let peer2_id = adaptor1.connect_peer(onion.to_string()).await.expect("onion peer connection failed");
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        let adaptor1_peers = adaptor1.active_peers();
+        assert_eq!(adaptor1_peers.len(), 1, "handshake failed -- outbound peer is missing");
+        assert_eq!(adaptor1_peers[0].net_address(), SocketAddr::from(onion));
+        assert_eq!(adaptor2.active_peers().len(), 1, "handshake failed -- inbound peer is missing");
+
+        assert_eq!(*requests.lock(), vec![address2.to_string(), onion.to_string()]);
+
+        adaptor1.terminate(peer2_id).await;
+        adaptor1.close().await;
+        adaptor2.close().await;
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    }
 }
@@ -1,11 +1,19 @@
 use std::time::Duration;
 
+use crate::convert::model::version::SERVICE_FLAG_ENCRYPTED_TRANSPORT;
+use crate::core::encryption::EphemeralKey;
 use crate::pb::{vecnod_message::Payload, ReadyMessage, VerackMessage, VersionMessage};
 use crate::{common::ProtocolError, dequeue_with_timeout, make_message};
 use crate::{IncomingRoute, Router, VecnodMessagePayloadType};
 use vecno_core::debug;
 
-/// Implements the Vecno peer-to-peer handshake protocol
+/// Implements the Vecno peer-to-peer handshake protocol.
+///
+/// When both peers advertise [`SERVICE_FLAG_ENCRYPTED_TRANSPORT`], their version messages carry ephemeral public keys
+/// from which the session keys are derived, and all the messages following the verack of each peer are encrypted.
+/// The session keys are bound to the services and keys of both version messages. Stripping the service from a version
+/// message still downgrades the connection to plaintext, which nodes requiring encryption detect by rejecting such peers.
+/// Peers not advertising the service keep exchanging plaintext messages.
 pub struct VecnodHandshake<'a> {
     router: &'a Router,
     version_receiver: IncomingRoute,
@@ -24,12 +32,29 @@ impl<'a> VecnodHandshake<'a> {
         }
     }
 
-    async fn receive_version_flow(router: &Router, version_receiver: &mut IncomingRoute) -> Result<VersionMessage, ProtocolError> {
+    async fn receive_version_flow(
+        router: &Router,
+        version_receiver: &mut IncomingRoute,
+        ephemeral_key: Option<EphemeralKey>,
+        self_services: u64,
+    ) -> Result<VersionMessage, ProtocolError> {
         debug!("starting receive version flow");
 
         let version_message = dequeue_with_timeout!(version_receiver, Payload::Version, Duration::from_secs(4))?;
         debug!("accepted version message: {version_message:?}");
 
+        // The session ciphers must be set before sending the verack, since the peer encrypts the messages following it
+        if let Some(ephemeral_key) = ephemeral_key {
+            if version_message.services & SERVICE_FLAG_ENCRYPTED_TRANSPORT != 0 {
+                router.set_session_ciphers(ephemeral_key.derive_session(
+                    self_services,
+                    version_message.services,
+                    &version_message.encryption_key,
+                    router.is_outbound(),
+                )?);
+            }
+        }
+
         let verack_message = make_message!(Payload::Verack, VerackMessage {});
         router.enqueue(verack_message).await?;
 
@@ -68,13 +93,26 @@ impl<'a> VecnodHandshake<'a> {
     }
 
     /// Performs the handshake with the peer, essentially exchanging version messages
-    pub async fn handshake(&mut self, self_version_message: VersionMessage) -> Result<VersionMessage, ProtocolError> {
+    pub async fn handshake(&mut self, mut self_version_message: VersionMessage) -> Result<VersionMessage, ProtocolError> {
+        // Offer to encrypt the connection if the encrypted transport service is advertised
+        let ephemeral_key = (self_version_message.services & SERVICE_FLAG_ENCRYPTED_TRANSPORT != 0).then(|| {
+            let ephemeral_key = EphemeralKey::generate();
+            self_version_message.encryption_key = ephemeral_key.public_key();
+            ephemeral_key
+        });
+
         // Run both send and receive flows concurrently -- this is critical in order to avoid a handshake deadlock
+        let self_services = self_version_message.services;
         let (send_res, recv_res) = tokio::join!(
             Self::send_version_flow(self.router, &mut self.verack_receiver, self_version_message),
-            Self::receive_version_flow(self.router, &mut self.version_receiver)
+            Self::receive_version_flow(self.router, &mut self.version_receiver, ephemeral_key, self_services)
         );
         send_res?;
-        recv_res
+        let peer_version_message = recv_res?;
+
+        // The peer sent its verack only after setting its own session ciphers, so it can now decrypt our messages
+        self.router.start_outgoing_encryption();
+
+        Ok(peer_version_message)
     }
 }
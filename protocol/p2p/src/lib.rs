@@ -15,5 +15,6 @@ pub use crate::core::connection_handler::ConnectionError;
 pub use crate::core::hub::Hub;
 pub use crate::core::payload_type::VecnodMessagePayloadType;
 pub use crate::core::peer::{Peer, PeerKey, PeerProperties};
+pub use crate::core::proxy::ProxyConfig;
 pub use crate::core::router::{IncomingRoute, Router, SharedIncomingRoute, BLANK_ROUTE_ID};
 pub use handshake::VecnodHandshake;
@@ -48,6 +48,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "MempoolTransactionRemoved notification: transaction {} ({})", "_0.transaction_id", "_0.reason")]
+    MempoolTransactionRemoved(MempoolTransactionRemovedNotification),
 }
 }
 
@@ -64,6 +67,7 @@ impl Notification {
             Notification::VirtualDaaScoreChanged(v) => to_value(&v),
             Notification::SinkBlueScoreChanged(v) => to_value(&v),
             Notification::VirtualChainChanged(v) => to_value(&v),
+            Notification::MempoolTransactionRemoved(v) => to_value(&v),
         }
     }
 }
@@ -112,6 +116,20 @@ impl NotificationTrait for Notification {
         }
     }
 
+    fn apply_mempool_transaction_removed_subscription(
+        &self,
+        subscription: &UtxosChangedSubscription,
+        context: &SubscriptionContext,
+    ) -> Option<Self> {
+        match subscription.active() {
+            true => {
+                let Self::MempoolTransactionRemoved(notification) = self else { return None };
+                notification.apply_mempool_transaction_removed_subscription(subscription, context).map(Self::MempoolTransactionRemoved)
+            }
+            false => None,
+        }
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
@@ -157,6 +175,10 @@ impl Serializer for Notification {
                 store!(u16, &8, writer)?;
                 serialize!(NewBlockTemplateNotification, notification, writer)?;
             }
+            Notification::MempoolTransactionRemoved(notification) => {
+                store!(u16, &9, writer)?;
+                serialize!(MempoolTransactionRemovedNotification, notification, writer)?;
+            }
         }
         Ok(())
     }
@@ -202,6 +224,10 @@ impl Deserializer for Notification {
                 let notification = deserialize!(NewBlockTemplateNotification, reader)?;
                 Ok(Notification::NewBlockTemplate(notification))
             }
+            9 => {
+                let notification = deserialize!(MempoolTransactionRemovedNotification, reader)?;
+                Ok(Notification::MempoolTransactionRemoved(notification))
+            }
             _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid variant")),
         }
     }
@@ -40,6 +40,7 @@ pub enum RpcApiOps {
     NotifyVirtualDaaScoreChanged = 16,
     NotifyVirtualChainChanged = 17,
     NotifySinkBlueScoreChanged = 18,
+    NotifyMempoolTransactionRemoved = 19,
 
     // Notification ops required by wRPC
 
@@ -54,6 +55,7 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification = 66,
     PruningPointUtxoSetOverrideNotification = 67,
     NewBlockTemplateNotification = 68,
+    MempoolTransactionRemovedNotification = 69,
 
     // RPC methods
     /// Ping the node to check if connection is alive
@@ -136,6 +138,26 @@ pub enum RpcApiOps {
     GetFeeEstimateExperimental = 148,
     /// Block color determination by iterating DAG.
     GetCurrentBlockColor = 149,
+    /// Get the location of an accepted transaction from the transaction index
+    GetTransaction = 150,
+    /// Get the locations of a list of accepted transactions from the transaction index
+    GetTransactionsByIds = 151,
+    /// Get a page of the transaction history of a list of addresses from the address history index
+    GetTransactionsByAddresses = 152,
+    /// Get the selected chain block at a DAA score
+    GetChainBlockByDaaScore = 153,
+    /// Get the selected chain blocks within a blue score range
+    GetChainBlocksInBlueScoreRange = 154,
+    /// Extracts an ordered package of transactions out of the request message and attempts to add all of them to the mempool, judged by their aggregate fee
+    SubmitTransactionPackage = 155,
+    /// Tests whether transactions would be accepted to the mempool, without adding nor relaying them
+    TestMempoolAccept = 156,
+    /// Fee estimation based on the observed confirmation times of recent transactions
+    GetFeeEstimateHistorical = 157,
+    /// Adjusts the fee a transaction is ranked by when selecting block template transactions
+    PrioritiseTransaction = 158,
+    /// Get the banned IP addresses along with the expiry of their ban
+    ListBanned = 159,
 }
 
 impl RpcApiOps {
@@ -151,6 +173,7 @@ impl RpcApiOps {
                 | RpcApiOps::NotifyFinalityConflictResolved
                 | RpcApiOps::NotifySinkBlueScoreChanged
                 | RpcApiOps::NotifyVirtualDaaScoreChanged
+                | RpcApiOps::NotifyMempoolTransactionRemoved
                 | RpcApiOps::Subscribe
                 | RpcApiOps::Unsubscribe
         )
@@ -177,6 +200,7 @@ impl From<EventType> for RpcApiOps {
             EventType::VirtualDaaScoreChanged => RpcApiOps::VirtualDaaScoreChangedNotification,
             EventType::PruningPointUtxoSetOverride => RpcApiOps::PruningPointUtxoSetOverrideNotification,
             EventType::NewBlockTemplate => RpcApiOps::NewBlockTemplateNotification,
+            EventType::MempoolTransactionRemoved => RpcApiOps::MempoolTransactionRemovedNotification,
         }
     }
 }
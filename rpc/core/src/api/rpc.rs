@@ -61,6 +61,7 @@ pub trait RpcApi: Sync + Send + AnySync {
         consensus_metrics: bool,
         storage_metrics: bool,
         custom_metrics: bool,
+        stratum_metrics: bool,
     ) -> RpcResult<GetMetricsResponse> {
         self.get_metrics_call(
             None,
@@ -71,6 +72,7 @@ pub trait RpcApi: Sync + Send + AnySync {
                 consensus_metrics,
                 storage_metrics,
                 custom_metrics,
+                stratum_metrics,
             },
         )
         .await
@@ -226,6 +228,43 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: SubmitTransactionReplacementRequest,
     ) -> RpcResult<SubmitTransactionReplacementResponse>;
 
+    /// Submits an ordered package of transactions to the mempool, where each transaction may spend outputs of the
+    /// transactions preceding it. Either all the transactions are added to the mempool or none is, the package being
+    /// judged by its aggregate fee.
+    ///
+    /// Returns the IDs of the package transactions on acceptance, or the rejected transactions along with the reason
+    /// of their rejection.
+    async fn submit_transaction_package(&self, transactions: Vec<RpcTransaction>) -> RpcResult<SubmitTransactionPackageResponse> {
+        self.submit_transaction_package_call(None, SubmitTransactionPackageRequest { transactions }).await
+    }
+    async fn submit_transaction_package_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: SubmitTransactionPackageRequest,
+    ) -> RpcResult<SubmitTransactionPackageResponse>;
+
+    /// Tests whether each of the transactions would be accepted to the mempool by [`Self::submit_transaction`], running
+    /// the full validation but without adding them to the mempool nor relaying them. Each transaction is tested
+    /// independently against the current mempool.
+    ///
+    /// Returns the outcome of the validation of each transaction, along with its computed masses, fee and feerate.
+    async fn test_mempool_accept(
+        &self,
+        transactions: Vec<RpcTransaction>,
+        allow_orphan: bool,
+        replace_by_fee: bool,
+    ) -> RpcResult<Vec<RpcTransactionAcceptance>> {
+        Ok(self
+            .test_mempool_accept_call(None, TestMempoolAcceptRequest::new(transactions, allow_orphan, replace_by_fee))
+            .await?
+            .acceptances)
+    }
+    async fn test_mempool_accept_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: TestMempoolAcceptRequest,
+    ) -> RpcResult<TestMempoolAcceptResponse>;
+
     /// Requests information about a specific block.
     async fn get_block(&self, hash: RpcHash, include_transactions: bool) -> RpcResult<RpcBlock> {
         Ok(self.get_block_call(None, GetBlockRequest::new(hash, include_transactions)).await?.block)
@@ -379,6 +418,16 @@ pub trait RpcApi: Sync + Send + AnySync {
     }
     async fn unban_call(&self, connection: Option<&DynRpcConnection>, request: UnbanRequest) -> RpcResult<UnbanResponse>;
 
+    /// Requests the banned IP addresses along with the expiry of their ban.
+    async fn list_banned(&self) -> RpcResult<Vec<RpcBannedAddress>> {
+        Ok(self.list_banned_call(None, ListBannedRequest {}).await?.banned_addresses)
+    }
+    async fn list_banned_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: ListBannedRequest,
+    ) -> RpcResult<ListBannedResponse>;
+
     /// Returns info about the node.
     async fn get_info(&self) -> RpcResult<GetInfoResponse> {
         self.get_info_call(None, GetInfoRequest {}).await
@@ -459,6 +508,29 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetFeeEstimateExperimentalRequest,
     ) -> RpcResult<GetFeeEstimateExperimentalResponse>;
 
+    /// Estimates the feerates required for inclusion within several target times, based on the feerates and
+    /// mempool waiting times of the transactions accepted during the last `window_seconds`, or during the whole
+    /// history kept by the node if `window_seconds` is 0.
+    async fn get_fee_estimate_historical(&self, window_seconds: u64) -> RpcResult<RpcHistoricalFeeEstimate> {
+        Ok(self.get_fee_estimate_historical_call(None, GetFeeEstimateHistoricalRequest { window_seconds }).await?.estimate)
+    }
+    async fn get_fee_estimate_historical_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetFeeEstimateHistoricalRequest,
+    ) -> RpcResult<GetFeeEstimateHistoricalResponse>;
+
+    /// Adds `fee_delta` sompi, which may be negative, to the fee `transaction_id` is ranked by when selecting the
+    /// transactions of block templates, and returns the resulting overall delta of the transaction.
+    async fn prioritise_transaction(&self, transaction_id: RpcTransactionId, fee_delta: i64) -> RpcResult<i64> {
+        Ok(self.prioritise_transaction_call(None, PrioritiseTransactionRequest::new(transaction_id, fee_delta)).await?.fee_delta)
+    }
+    async fn prioritise_transaction_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: PrioritiseTransactionRequest,
+    ) -> RpcResult<PrioritiseTransactionResponse>;
+
     ///
     async fn get_current_block_color(&self, hash: RpcHash) -> RpcResult<GetCurrentBlockColorResponse> {
         Ok(self.get_current_block_color_call(None, GetCurrentBlockColorRequest { hash }).await?)
@@ -469,6 +541,77 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetCurrentBlockColorRequest,
     ) -> RpcResult<GetCurrentBlockColorResponse>;
 
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Transaction index API
+
+    /// Requests the location of an accepted transaction, optionally including the transaction itself.
+    async fn get_transaction(&self, transaction_id: RpcTransactionId, include_transaction: bool) -> RpcResult<RpcIndexedTransaction> {
+        Ok(self.get_transaction_call(None, GetTransactionRequest { transaction_id, include_transaction }).await?.transaction)
+    }
+    async fn get_transaction_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse>;
+
+    /// Requests the locations of several accepted transactions, omitting those which are not indexed.
+    async fn get_transactions_by_ids(
+        &self,
+        transaction_ids: Vec<RpcTransactionId>,
+        include_transactions: bool,
+    ) -> RpcResult<Vec<RpcIndexedTransaction>> {
+        Ok(self
+            .get_transactions_by_ids_call(None, GetTransactionsByIdsRequest { transaction_ids, include_transactions })
+            .await?
+            .transactions)
+    }
+    async fn get_transactions_by_ids_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetTransactionsByIdsRequest,
+    ) -> RpcResult<GetTransactionsByIdsResponse>;
+
+    /// Requests a page of the history of `addresses`, starting right after `cursor`.
+    async fn get_transactions_by_addresses(
+        &self,
+        addresses: Vec<RpcAddress>,
+        cursor: Option<RpcAddressHistoryCursor>,
+        limit: u32,
+    ) -> RpcResult<GetTransactionsByAddressesResponse> {
+        self.get_transactions_by_addresses_call(None, GetTransactionsByAddressesRequest { addresses, cursor, limit }).await
+    }
+    async fn get_transactions_by_addresses_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetTransactionsByAddressesRequest,
+    ) -> RpcResult<GetTransactionsByAddressesResponse>;
+
+    /// Requests the header of the selected chain block with the highest DAA score not exceeding `daa_score`.
+    async fn get_chain_block_by_daa_score(&self, daa_score: u64) -> RpcResult<RpcHeader> {
+        Ok(self.get_chain_block_by_daa_score_call(None, GetChainBlockByDaaScoreRequest { daa_score }).await?.header)
+    }
+    async fn get_chain_block_by_daa_score_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetChainBlockByDaaScoreRequest,
+    ) -> RpcResult<GetChainBlockByDaaScoreResponse>;
+
+    /// Requests the headers of the selected chain blocks with a blue score within `[low_blue_score, high_blue_score]`.
+    /// The response carries the blue score to resume from if the range was truncated.
+    async fn get_chain_blocks_in_blue_score_range(
+        &self,
+        low_blue_score: u64,
+        high_blue_score: u64,
+    ) -> RpcResult<GetChainBlocksInBlueScoreRangeResponse> {
+        self.get_chain_blocks_in_blue_score_range_call(None, GetChainBlocksInBlueScoreRangeRequest { low_blue_score, high_blue_score })
+            .await
+    }
+    async fn get_chain_blocks_in_blue_score_range_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetChainBlocksInBlueScoreRangeRequest,
+    ) -> RpcResult<GetChainBlocksInBlueScoreRangeResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -2,8 +2,9 @@
 
 use crate::{
     convert::utxo::utxo_set_into_rpc, BlockAddedNotification, FinalityConflictNotification, FinalityConflictResolvedNotification,
-    NewBlockTemplateNotification, Notification, PruningPointUtxoSetOverrideNotification, RpcAcceptedTransactionIds,
-    SinkBlueScoreChangedNotification, UtxosChangedNotification, VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
+    MempoolTransactionRemovedNotification, NewBlockTemplateNotification, Notification, PruningPointUtxoSetOverrideNotification,
+    RpcAcceptedTransactionIds, RpcMempoolRemovalReason, SinkBlueScoreChangedNotification, UtxosChangedNotification,
+    VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
 };
 use std::sync::Arc;
 use vecno_consensus_notify::notification as consensus_notify;
@@ -31,6 +32,7 @@ impl From<&consensus_notify::Notification> for Notification {
             consensus_notify::Notification::VirtualDaaScoreChanged(msg) => Notification::VirtualDaaScoreChanged(msg.into()),
             consensus_notify::Notification::PruningPointUtxoSetOverride(msg) => Notification::PruningPointUtxoSetOverride(msg.into()),
             consensus_notify::Notification::NewBlockTemplate(msg) => Notification::NewBlockTemplate(msg.into()),
+            consensus_notify::Notification::MempoolTransactionRemoved(msg) => Notification::MempoolTransactionRemoved(msg.into()),
         }
     }
 }
@@ -112,6 +114,27 @@ impl From<&consensus_notify::NewBlockTemplateNotification> for NewBlockTemplateN
     }
 }
 
+impl From<consensus_notify::MempoolRemovalReason> for RpcMempoolRemovalReason {
+    fn from(item: consensus_notify::MempoolRemovalReason) -> Self {
+        match item {
+            consensus_notify::MempoolRemovalReason::Evicted => RpcMempoolRemovalReason::Evicted,
+            consensus_notify::MempoolRemovalReason::Expired => RpcMempoolRemovalReason::Expired,
+            consensus_notify::MempoolRemovalReason::DoubleSpend => RpcMempoolRemovalReason::DoubleSpend,
+            consensus_notify::MempoolRemovalReason::ReplacedByFee => RpcMempoolRemovalReason::ReplacedByFee,
+            consensus_notify::MempoolRemovalReason::InvalidInBlockTemplate => RpcMempoolRemovalReason::InvalidInBlockTemplate,
+            consensus_notify::MempoolRemovalReason::MissingOutpoints => RpcMempoolRemovalReason::MissingOutpoints,
+        }
+    }
+}
+
+impl From<&consensus_notify::MempoolTransactionRemovedNotification> for MempoolTransactionRemovedNotification {
+    // Addresses are left empty because no address prefix is available.
+    // Use vecno_rpc_service::converter::consensus::ConsensusConverter instead.
+    fn from(item: &consensus_notify::MempoolTransactionRemovedNotification) -> Self {
+        Self { transaction_id: item.transaction_id, reason: item.reason.into(), addresses: Default::default() }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // index to rpc_core
 // ----------------------------------------------------------------------------
@@ -1,9 +1,9 @@
 //! Conversion of Notification Scope related types
 
 use crate::{
-    NotifyBlockAddedRequest, NotifyFinalityConflictRequest, NotifyNewBlockTemplateRequest, NotifyPruningPointUtxoSetOverrideRequest,
-    NotifySinkBlueScoreChangedRequest, NotifyUtxosChangedRequest, NotifyVirtualChainChangedRequest,
-    NotifyVirtualDaaScoreChangedRequest,
+    NotifyBlockAddedRequest, NotifyFinalityConflictRequest, NotifyMempoolTransactionRemovedRequest, NotifyNewBlockTemplateRequest,
+    NotifyPruningPointUtxoSetOverrideRequest, NotifySinkBlueScoreChangedRequest, NotifyUtxosChangedRequest,
+    NotifyVirtualChainChangedRequest, NotifyVirtualDaaScoreChangedRequest,
 };
 use vecno_notify::scope::*;
 
@@ -47,7 +47,7 @@ macro_rules! from {
 
 from!(BlockAdded);
 from!(item: VirtualChainChanged, {
-    Self::new(item.include_accepted_transaction_ids)
+    Self { include_accepted_transaction_ids: item.include_accepted_transaction_ids, start_hash: item.start_hash }
 });
 from!(FinalityConflict);
 impl From<&NotifyFinalityConflictRequest> for FinalityConflictResolvedScope {
@@ -62,3 +62,6 @@ from!(SinkBlueScoreChanged);
 from!(VirtualDaaScoreChanged);
 from!(PruningPointUtxoSetOverride);
 from!(NewBlockTemplate);
+from!(item: MempoolTransactionRemoved, {
+    Self::new(item.addresses.clone())
+});
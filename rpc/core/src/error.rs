@@ -5,7 +5,7 @@
 use std::{net::AddrParseError, num::TryFromIntError};
 use thiserror::Error;
 use vecno_consensus_core::{subnets::SubnetworkConversionError, tx::TransactionId};
-use vecno_utils::networking::IpAddress;
+use vecno_utils::networking::{IpAddress, NetAddressParseError};
 use workflow_core::channel::ChannelError;
 
 use crate::{api::ctl::RpcState, RpcHash, RpcTransactionId, SubmitBlockRejectReason};
@@ -30,6 +30,9 @@ pub enum RpcError {
     #[error("Ip address parsing error {0}")]
     ParseIpAddressError(#[from] AddrParseError),
 
+    #[error("Network address parsing error {0}")]
+    ParseNetAddressError(#[from] NetAddressParseError),
+
     #[error("Wrong rpc api version format")]
     RpcApiVersionFormatError,
 
@@ -51,18 +54,30 @@ pub enum RpcError {
     #[error("Rejected transaction {0}: {1}")]
     RejectedTransaction(RpcTransactionId, String),
 
+    #[error("Rejected transaction package: {0}")]
+    RejectedTransactionPackage(String),
+
     #[error("Block {0} is invalid. No verbose data can be built.")]
     InvalidBlock(RpcHash),
 
     #[error("If includeTransactions is set, then includeBlockVerboseData must be set as well.")]
     InvalidGetBlocksRequest,
 
+    #[error("Low blue score {0} is higher than high blue score {1}.")]
+    InvalidBlueScoreRange(u64, u64),
+
     #[error("Transaction {0} not found")]
     TransactionNotFound(TransactionId),
 
     #[error("Method unavailable. Run the node with the --utxoindex argument.")]
     NoUtxoIndex,
 
+    #[error("Method unavailable. Run the node with the --txindex argument.")]
+    NoTxIndex,
+
+    #[error("Method unavailable. Run the node with the --addressindex argument.")]
+    NoAddressIndex,
+
     #[error("Method unavailable. No connection manager is currently available.")]
     NoConnectionManager,
 
@@ -1,4 +1,4 @@
-use crate::{RpcTransactionOutpoint, RpcUtxoEntry};
+use crate::{RpcHash, RpcTransactionId, RpcTransactionOutpoint, RpcUtxoEntry};
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
@@ -58,3 +58,70 @@ impl Deserializer for RpcBalancesByAddressesEntry {
         Ok(Self { address, balance })
     }
 }
+
+/// Represents a position within the history of a set of addresses, as used by the `GetTransactionsByAddresses` RPC.
+///
+/// The history is ordered by accepting block DAA score, then by transaction id.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAddressHistoryCursor {
+    pub accepting_block_daa_score: u64,
+    pub transaction_id: RpcTransactionId,
+}
+
+impl Serializer for RpcAddressHistoryCursor {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?; // version
+        store!(u64, &self.accepting_block_daa_score, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)
+    }
+}
+
+impl Deserializer for RpcAddressHistoryCursor {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version: u8 = load!(u8, reader)?;
+        let accepting_block_daa_score = load!(u64, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        Ok(Self { accepting_block_daa_score, transaction_id })
+    }
+}
+
+/// Represents a transaction which spent from or paid to an address, returned by the `GetTransactionsByAddresses` RPC.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAddressTransaction {
+    pub address: RpcAddress,
+    pub transaction_id: RpcTransactionId,
+    /// The chain block whose mergeset accepted the transaction
+    pub accepting_block_hash: RpcHash,
+    pub accepting_block_daa_score: u64,
+    /// The sum of the transaction outputs paying to `address`
+    pub received_amount: u64,
+    /// The sum of the outputs previously paid to `address` which the transaction spends
+    pub spent_amount: u64,
+}
+
+impl Serializer for RpcAddressTransaction {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?; // version
+        store!(RpcAddress, &self.address, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(RpcHash, &self.accepting_block_hash, writer)?;
+        store!(u64, &self.accepting_block_daa_score, writer)?;
+        store!(u64, &self.received_amount, writer)?;
+        store!(u64, &self.spent_amount, writer)
+    }
+}
+
+impl Deserializer for RpcAddressTransaction {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version: u8 = load!(u8, reader)?;
+        let address = load!(RpcAddress, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let accepting_block_hash = load!(RpcHash, reader)?;
+        let accepting_block_daa_score = load!(u64, reader)?;
+        let received_amount = load!(u64, reader)?;
+        let spent_amount = load!(u64, reader)?;
+        Ok(Self { address, transaction_id, accepting_block_hash, accepting_block_daa_score, received_amount, spent_amount })
+    }
+}
@@ -1,5 +1,6 @@
-use super::RpcRawHeader;
+use super::{RpcRawHeader, RpcTransactionId};
 use crate::prelude::{RpcHash, RpcHeader, RpcTransaction};
+use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
@@ -124,6 +125,54 @@ impl Deserializer for RpcBlockVerboseData {
     }
 }
 
+/// Order in which ready mempool transactions are considered for inclusion in a block template
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[borsh(use_discriminant = true)]
+pub enum RpcTemplateSelectionMode {
+    /// Feerate-weighted random selection
+    #[default]
+    Weighted = 0,
+    /// Strict descending feerate order
+    HighestFeerate = 1,
+}
+
+/// Transaction selection policy of a block template, overriding the one of the node
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RpcTemplatePolicy {
+    pub selection_mode: RpcTemplateSelectionMode,
+    /// Share of the block mass, between 0 and 1, filled first with the prioritised transactions, these being
+    /// the listed ones along with the ones given a positive fee delta by `PrioritiseTransaction`
+    pub prioritised_mass_share: f64,
+    pub prioritised_transaction_ids: Vec<RpcTransactionId>,
+    pub excluded_transaction_ids: Vec<RpcTransactionId>,
+}
+
+impl Serializer for RpcTemplatePolicy {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(RpcTemplateSelectionMode, &self.selection_mode, writer)?;
+        store!(f64, &self.prioritised_mass_share, writer)?;
+        store!(Vec<RpcTransactionId>, &self.prioritised_transaction_ids, writer)?;
+        store!(Vec<RpcTransactionId>, &self.excluded_transaction_ids, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcTemplatePolicy {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let selection_mode = load!(RpcTemplateSelectionMode, reader)?;
+        let prioritised_mass_share = load!(f64, reader)?;
+        let prioritised_transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+        let excluded_transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+
+        Ok(Self { selection_mode, prioritised_mass_share, prioritised_transaction_ids, excluded_transaction_ids })
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "wasm32-sdk")] {
         use wasm_bindgen::prelude::*;
@@ -173,6 +222,22 @@ cfg_if::cfg_if! {
             transactions: ITransaction[];
         }
 
+        /**
+         * Transaction selection policy of a block template, overriding the one of the node.
+         * 
+         * @category Node RPC
+         */
+        export interface ITemplatePolicy {
+            selectionMode?: "weighted" | "highestFeerate";
+            /**
+             * Share of the block mass, between 0 and 1, filled first with the prioritised transactions,
+             * these being the listed ones along with the ones given a positive fee delta.
+             */
+            prioritisedMassShare?: number;
+            prioritisedTransactionIds?: HexString[];
+            excludedTransactionIds?: HexString[];
+        }
+
         "#;
     }
 }
@@ -107,3 +107,55 @@ impl Deserializer for RpcFeeEstimateVerboseExperimentalData {
         })
     }
 }
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcHistoricalFeerateBucket {
+    /// The lowest fee/mass ratio at which at least `confidence` of the observed transactions were included within `target_seconds`
+    pub feerate: f64,
+
+    /// The inclusion time targeted by the bucket
+    pub target_seconds: f64,
+
+    /// The fraction of the observed transactions paying at least `feerate` which were included within `target_seconds`
+    pub confidence: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcHistoricalFeeEstimate {
+    /// The duration covered by the observed transactions
+    pub window_seconds: u64,
+
+    /// The number of accepted blocks holding observed transactions
+    pub block_count: u64,
+
+    /// The number of observed transactions, i.e., the transactions accepted in blocks after waiting in the mempool
+    pub sample_count: u64,
+
+    /// Buckets ordered by target time then by confidence. A (target time, confidence) pair is omitted if no observed
+    /// feerate meets it, so the vector is empty if too few transactions were observed.
+    pub buckets: Vec<RpcHistoricalFeerateBucket>,
+}
+
+impl Serializer for RpcHistoricalFeeEstimate {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.window_seconds, writer)?;
+        store!(u64, &self.block_count, writer)?;
+        store!(u64, &self.sample_count, writer)?;
+        store!(Vec<RpcHistoricalFeerateBucket>, &self.buckets, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcHistoricalFeeEstimate {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let window_seconds = load!(u64, reader)?;
+        let block_count = load!(u64, reader)?;
+        let sample_count = load!(u64, reader)?;
+        let buckets = load!(Vec<RpcHistoricalFeerateBucket>, reader)?;
+        Ok(Self { window_seconds, block_count, sample_count, buckets })
+    }
+}
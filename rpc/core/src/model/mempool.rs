@@ -1,6 +1,8 @@
 use super::RpcAddress;
 use super::RpcTransaction;
+use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
 use workflow_serializer::prelude::*;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -63,6 +65,51 @@ impl Deserializer for RpcMempoolEntryByAddress {
     }
 }
 
+/// Key the mempool entries returned by `GetMempoolEntries` are sorted by, ties being broken by transaction id
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[borsh(use_discriminant = true)]
+pub enum RpcMempoolEntrySortKey {
+    #[default]
+    TransactionId = 0,
+    Feerate = 1,
+    /// Time the transaction entered the mempool
+    ArrivalTime = 2,
+    Mass = 3,
+}
+
+/// Reason why a transaction was removed from the mempool without being accepted by consensus
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[borsh(use_discriminant = true)]
+pub enum RpcMempoolRemovalReason {
+    Evicted = 0,
+    Expired = 1,
+    DoubleSpend = 2,
+    ReplacedByFee = 3,
+    InvalidInBlockTemplate = 4,
+    MissingOutpoints = 5,
+}
+
+impl RpcMempoolRemovalReason {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RpcMempoolRemovalReason::Evicted => "evicted",
+            RpcMempoolRemovalReason::Expired => "expired",
+            RpcMempoolRemovalReason::DoubleSpend => "double spend",
+            RpcMempoolRemovalReason::ReplacedByFee => "replaced by fee",
+            RpcMempoolRemovalReason::InvalidInBlockTemplate => "invalid in block template",
+            RpcMempoolRemovalReason::MissingOutpoints => "missing outpoints",
+        }
+    }
+}
+
+impl Display for RpcMempoolRemovalReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "wasm32-sdk")] {
         use wasm_bindgen::prelude::*;
@@ -79,6 +126,20 @@ cfg_if::cfg_if! {
                 transaction : ITransaction;
                 isOrphan : boolean;
             }
+
+            /**
+             * Reason why a transaction was removed from the mempool.
+             * 
+             * @category Node RPC
+             */
+            export type MempoolRemovalReason = "evicted" | "expired" | "doubleSpend" | "replacedByFee" | "invalidInBlockTemplate" | "missingOutpoints";
+
+            /**
+             * Key the mempool entries are sorted by.
+             * 
+             * @category Node RPC
+             */
+            export type MempoolEntrySortKey = "transactionId" | "feerate" | "arrivalTime" | "mass";
         "#;
     }
 }
@@ -123,18 +123,27 @@ pub struct GetBlockTemplateRequest {
     pub pay_address: RpcAddress,
     // TODO: replace with hex serialization
     pub extra_data: RpcExtraData,
+    /// Transaction selection policy overriding the one of the node. Templates built with a policy bypass
+    /// the template cache of the node.
+    #[serde(default)]
+    pub template_policy: Option<RpcTemplatePolicy>,
 }
 impl GetBlockTemplateRequest {
     pub fn new(pay_address: RpcAddress, extra_data: RpcExtraData) -> Self {
-        Self { pay_address, extra_data }
+        Self { pay_address, extra_data, template_policy: None }
+    }
+
+    pub fn with_template_policy(self, template_policy: RpcTemplatePolicy) -> Self {
+        Self { template_policy: Some(template_policy), ..self }
     }
 }
 
 impl Serializer for GetBlockTemplateRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(RpcAddress, &self.pay_address, writer)?;
         store!(RpcExtraData, &self.extra_data, writer)?;
+        serialize!(Option<RpcTemplatePolicy>, &self.template_policy, writer)?;
 
         Ok(())
     }
@@ -142,11 +151,12 @@ impl Serializer for GetBlockTemplateRequest {
 
 impl Deserializer for GetBlockTemplateRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let pay_address = load!(RpcAddress, reader)?;
         let extra_data = load!(RpcExtraData, reader)?;
+        let template_policy = if version > 1 { deserialize!(Option<RpcTemplatePolicy>, reader)? } else { None };
 
-        Ok(Self { pay_address, extra_data })
+        Ok(Self { pay_address, extra_data, template_policy })
     }
 }
 
@@ -382,18 +392,27 @@ impl GetPeerAddressesResponse {
 
 impl Serializer for GetPeerAddressesResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(Vec<RpcPeerAddress>, &self.known_addresses, writer)?;
         store!(Vec<RpcIpAddress>, &self.banned_addresses, writer)?;
+        store!(Vec<Option<RpcOnionAddress>>, &self.known_addresses.iter().map(|address| address.onion).collect(), writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetPeerAddressesResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
-        let known_addresses = load!(Vec<RpcPeerAddress>, reader)?;
+        let version = load!(u16, reader)?;
+        let mut known_addresses = load!(Vec<RpcPeerAddress>, reader)?;
         let banned_addresses = load!(Vec<RpcIpAddress>, reader)?;
+        if version > 1 {
+            let onions = load!(Vec<Option<RpcOnionAddress>>, reader)?;
+            for (address, onion) in known_addresses.iter_mut().zip(onions) {
+                if let Some(onion) = onion {
+                    *address = RpcPeerAddress::new_onion(onion, address.port);
+                }
+            }
+        }
         Ok(Self { known_addresses, banned_addresses })
     }
 }
@@ -509,25 +528,75 @@ impl Deserializer for GetMempoolEntryResponse {
     }
 }
 
+/// Requests a page of the mempool entries.
+///
+/// Orphans only are requested by setting both `include_orphan_pool` and `filter_transaction_pool`.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetMempoolEntriesRequest {
     pub include_orphan_pool: bool,
     // TODO: replace with `include_transaction_pool`
     pub filter_transaction_pool: bool,
+    /// Number of matching entries skipped before the returned ones
+    #[serde(default)]
+    pub offset: u64,
+    /// Maximum number of returned entries, 0 meaning no limit
+    #[serde(default)]
+    pub limit: u64,
+    #[serde(default)]
+    pub sort_by: RpcMempoolEntrySortKey,
+    #[serde(default)]
+    pub sort_descending: bool,
+    /// Entries paying a lower feerate are skipped, orphans included since their fee is unknown
+    #[serde(default)]
+    pub min_feerate: f64,
+    /// Only the entries carrying a payload are returned
+    #[serde(default)]
+    pub payload_only: bool,
 }
 
 impl GetMempoolEntriesRequest {
     pub fn new(include_orphan_pool: bool, filter_transaction_pool: bool) -> Self {
-        Self { include_orphan_pool, filter_transaction_pool }
+        Self {
+            include_orphan_pool,
+            filter_transaction_pool,
+            offset: 0,
+            limit: 0,
+            sort_by: Default::default(),
+            sort_descending: false,
+            min_feerate: 0.0,
+            payload_only: false,
+        }
+    }
+
+    pub fn with_page(self, offset: u64, limit: u64) -> Self {
+        Self { offset, limit, ..self }
+    }
+
+    pub fn with_sort(self, sort_by: RpcMempoolEntrySortKey, sort_descending: bool) -> Self {
+        Self { sort_by, sort_descending, ..self }
+    }
+
+    pub fn with_min_feerate(self, min_feerate: f64) -> Self {
+        Self { min_feerate, ..self }
+    }
+
+    pub fn with_payload_only(self, payload_only: bool) -> Self {
+        Self { payload_only, ..self }
     }
 }
 
 impl Serializer for GetMempoolEntriesRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(bool, &self.include_orphan_pool, writer)?;
         store!(bool, &self.filter_transaction_pool, writer)?;
+        store!(u64, &self.offset, writer)?;
+        store!(u64, &self.limit, writer)?;
+        store!(RpcMempoolEntrySortKey, &self.sort_by, writer)?;
+        store!(bool, &self.sort_descending, writer)?;
+        store!(f64, &self.min_feerate, writer)?;
+        store!(bool, &self.payload_only, writer)?;
 
         Ok(())
     }
@@ -535,39 +604,65 @@ impl Serializer for GetMempoolEntriesRequest {
 
 impl Deserializer for GetMempoolEntriesRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let include_orphan_pool = load!(bool, reader)?;
         let filter_transaction_pool = load!(bool, reader)?;
+        let mut request = Self::new(include_orphan_pool, filter_transaction_pool);
+        if version > 1 {
+            request.offset = load!(u64, reader)?;
+            request.limit = load!(u64, reader)?;
+            request.sort_by = load!(RpcMempoolEntrySortKey, reader)?;
+            request.sort_descending = load!(bool, reader)?;
+            request.min_feerate = load!(f64, reader)?;
+            request.payload_only = load!(bool, reader)?;
+        }
 
-        Ok(Self { include_orphan_pool, filter_transaction_pool })
+        Ok(request)
     }
 }
 
+/// A page of the mempool entries, along with totals over all the entries matching the request filters
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetMempoolEntriesResponse {
     pub mempool_entries: Vec<RpcMempoolEntry>,
+    /// Number of entries matching the request filters
+    #[serde(default)]
+    pub total_count: u64,
+    #[serde(default)]
+    pub total_mass: u64,
+    /// Sum of the fees of the matching entries, orphans having no known fee
+    #[serde(default)]
+    pub total_fees: u64,
 }
 
 impl GetMempoolEntriesResponse {
-    pub fn new(mempool_entries: Vec<RpcMempoolEntry>) -> Self {
-        Self { mempool_entries }
+    pub fn new(mempool_entries: Vec<RpcMempoolEntry>, total_count: u64, total_mass: u64, total_fees: u64) -> Self {
+        Self { mempool_entries, total_count, total_mass, total_fees }
     }
 }
 
 impl Serializer for GetMempoolEntriesResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         serialize!(Vec<RpcMempoolEntry>, &self.mempool_entries, writer)?;
+        store!(u64, &self.total_count, writer)?;
+        store!(u64, &self.total_mass, writer)?;
+        store!(u64, &self.total_fees, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetMempoolEntriesResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let mempool_entries = deserialize!(Vec<RpcMempoolEntry>, reader)?;
-        Ok(Self { mempool_entries })
+        let (total_count, total_mass, total_fees) = if version > 1 {
+            (load!(u64, reader)?, load!(u64, reader)?, load!(u64, reader)?)
+        } else {
+            (mempool_entries.len() as u64, 0, mempool_entries.iter().map(|entry| entry.fee).sum())
+        };
+        Ok(Self { mempool_entries, total_count, total_mass, total_fees })
     }
 }
 
@@ -603,16 +698,35 @@ impl GetConnectedPeerInfoResponse {
 
 impl Serializer for GetConnectedPeerInfoResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &4, writer)?;
         store!(Vec<RpcPeerInfo>, &self.peer_info, writer)?;
+        store!(Vec<u32>, &self.peer_info.iter().map(|peer| peer.misbehavior_score).collect(), writer)?;
+        store!(Vec<bool>, &self.peer_info.iter().map(|peer| peer.is_encrypted).collect(), writer)?;
+        store!(Vec<Option<RpcOnionAddress>>, &self.peer_info.iter().map(|peer| peer.address.onion).collect(), writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetConnectedPeerInfoResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
-        let peer_info = load!(Vec<RpcPeerInfo>, reader)?;
+        let version = load!(u16, reader)?;
+        let mut peer_info = load!(Vec<RpcPeerInfo>, reader)?;
+        if version > 1 {
+            let misbehavior_scores = load!(Vec<u32>, reader)?;
+            peer_info.iter_mut().zip(misbehavior_scores).for_each(|(peer, score)| peer.misbehavior_score = score);
+        }
+        if version > 2 {
+            let encryption = load!(Vec<bool>, reader)?;
+            peer_info.iter_mut().zip(encryption).for_each(|(peer, is_encrypted)| peer.is_encrypted = is_encrypted);
+        }
+        if version > 3 {
+            let onions = load!(Vec<Option<RpcOnionAddress>>, reader)?;
+            for (peer, onion) in peer_info.iter_mut().zip(onions) {
+                if let Some(onion) = onion {
+                    peer.address = RpcPeerAddress::new_onion(onion, peer.address.port);
+                }
+            }
+        }
         Ok(Self { peer_info })
     }
 }
@@ -632,9 +746,10 @@ impl AddPeerRequest {
 
 impl Serializer for AddPeerRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(RpcContextualPeerAddress, &self.peer_address, writer)?;
         store!(bool, &self.is_permanent, writer)?;
+        store!(Option<RpcOnionAddress>, &self.peer_address.onion(), writer)?;
 
         Ok(())
     }
@@ -642,9 +757,14 @@ impl Serializer for AddPeerRequest {
 
 impl Deserializer for AddPeerRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
-        let peer_address = load!(RpcContextualPeerAddress, reader)?;
+        let version = load!(u16, reader)?;
+        let mut peer_address = load!(RpcContextualPeerAddress, reader)?;
         let is_permanent = load!(bool, reader)?;
+        if version > 1 {
+            if let Some(onion) = load!(Option<RpcOnionAddress>, reader)? {
+                peer_address = peer_address.with_onion(onion);
+            }
+        }
 
         Ok(Self { peer_address, is_permanent })
     }
@@ -794,6 +914,142 @@ impl Deserializer for SubmitTransactionReplacementResponse {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionPackageRequest {
+    pub transactions: Vec<RpcTransaction>,
+}
+
+impl SubmitTransactionPackageRequest {
+    pub fn new(transactions: Vec<RpcTransaction>) -> Self {
+        Self { transactions }
+    }
+}
+
+impl Serializer for SubmitTransactionPackageRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcTransaction>, &self.transactions, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for SubmitTransactionPackageRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transactions = deserialize!(Vec<RpcTransaction>, reader)?;
+
+        Ok(Self { transactions })
+    }
+}
+
+/// Either `transaction_ids` lists the IDs of all the package transactions when the package is accepted,
+/// or `rejections` lists the transactions causing the rejection of the package.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionPackageResponse {
+    pub transaction_ids: Vec<RpcTransactionId>,
+    pub rejections: Vec<RpcTransactionRejection>,
+}
+
+impl SubmitTransactionPackageResponse {
+    pub fn new(transaction_ids: Vec<RpcTransactionId>, rejections: Vec<RpcTransactionRejection>) -> Self {
+        Self { transaction_ids, rejections }
+    }
+}
+
+impl Serializer for SubmitTransactionPackageResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcTransactionId>, &self.transaction_ids, writer)?;
+        serialize!(Vec<RpcTransactionRejection>, &self.rejections, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for SubmitTransactionPackageResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+        let rejections = deserialize!(Vec<RpcTransactionRejection>, reader)?;
+
+        Ok(Self { transaction_ids, rejections })
+    }
+}
+
+/// Tests whether `transactions` would be accepted to the mempool, without adding them to it nor relaying them.
+///
+/// `allow_orphan` and `replace_by_fee` respectively test the transactions as if submitted with orphans allowed and as
+/// replacements of a double spending mempool transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestMempoolAcceptRequest {
+    pub transactions: Vec<RpcTransaction>,
+    pub allow_orphan: bool,
+    pub replace_by_fee: bool,
+}
+
+impl TestMempoolAcceptRequest {
+    pub fn new(transactions: Vec<RpcTransaction>, allow_orphan: bool, replace_by_fee: bool) -> Self {
+        Self { transactions, allow_orphan, replace_by_fee }
+    }
+}
+
+impl Serializer for TestMempoolAcceptRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcTransaction>, &self.transactions, writer)?;
+        store!(bool, &self.allow_orphan, writer)?;
+        store!(bool, &self.replace_by_fee, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for TestMempoolAcceptRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transactions = deserialize!(Vec<RpcTransaction>, reader)?;
+        let allow_orphan = load!(bool, reader)?;
+        let replace_by_fee = load!(bool, reader)?;
+
+        Ok(Self { transactions, allow_orphan, replace_by_fee })
+    }
+}
+
+/// The acceptance outcome of each of the tested transactions, in the order of the request
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestMempoolAcceptResponse {
+    pub acceptances: Vec<RpcTransactionAcceptance>,
+}
+
+impl TestMempoolAcceptResponse {
+    pub fn new(acceptances: Vec<RpcTransactionAcceptance>) -> Self {
+        Self { acceptances }
+    }
+}
+
+impl Serializer for TestMempoolAcceptResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcTransactionAcceptance>, &self.acceptances, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for TestMempoolAcceptResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let acceptances = deserialize!(Vec<RpcTransactionAcceptance>, reader)?;
+
+        Ok(Self { acceptances })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSubnetworkRequest {
@@ -1956,17 +2212,20 @@ pub struct GetMetricsRequest {
     pub consensus_metrics: bool,
     pub storage_metrics: bool,
     pub custom_metrics: bool,
+    #[serde(default)]
+    pub stratum_metrics: bool,
 }
 
 impl Serializer for GetMetricsRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(bool, &self.process_metrics, writer)?;
         store!(bool, &self.connection_metrics, writer)?;
         store!(bool, &self.bandwidth_metrics, writer)?;
         store!(bool, &self.consensus_metrics, writer)?;
         store!(bool, &self.storage_metrics, writer)?;
         store!(bool, &self.custom_metrics, writer)?;
+        store!(bool, &self.stratum_metrics, writer)?;
 
         Ok(())
     }
@@ -1974,15 +2233,24 @@ impl Serializer for GetMetricsRequest {
 
 impl Deserializer for GetMetricsRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let process_metrics = load!(bool, reader)?;
         let connection_metrics = load!(bool, reader)?;
         let bandwidth_metrics = load!(bool, reader)?;
         let consensus_metrics = load!(bool, reader)?;
         let storage_metrics = load!(bool, reader)?;
         let custom_metrics = load!(bool, reader)?;
+        let stratum_metrics = if version > 1 { load!(bool, reader)? } else { false };
 
-        Ok(Self { process_metrics, connection_metrics, bandwidth_metrics, consensus_metrics, storage_metrics, custom_metrics })
+        Ok(Self {
+            process_metrics,
+            connection_metrics,
+            bandwidth_metrics,
+            consensus_metrics,
+            storage_metrics,
+            custom_metrics,
+            stratum_metrics,
+        })
     }
 }
 
@@ -2257,6 +2525,96 @@ impl Deserializer for StorageMetrics {
     }
 }
 
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StratumWorkerMetrics {
+    /// Worker name given by the miner on authorization, usually `<address>.<worker>`
+    pub worker: String,
+    pub remote_address: String,
+    /// Current share difficulty assigned to the worker
+    pub difficulty: f64,
+    /// Hashrate estimated from the recently accepted shares, in hashes per second
+    pub hashrate: f64,
+    pub shares_accepted: u64,
+    pub shares_stale: u64,
+    pub shares_rejected: u64,
+    pub blocks_found: u64,
+    /// Unix timestamp in milliseconds of the connection of the worker
+    pub connected_since: u64,
+}
+
+impl Serializer for StratumWorkerMetrics {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.worker, writer)?;
+        store!(String, &self.remote_address, writer)?;
+        store!(f64, &self.difficulty, writer)?;
+        store!(f64, &self.hashrate, writer)?;
+        store!(u64, &self.shares_accepted, writer)?;
+        store!(u64, &self.shares_stale, writer)?;
+        store!(u64, &self.shares_rejected, writer)?;
+        store!(u64, &self.blocks_found, writer)?;
+        store!(u64, &self.connected_since, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for StratumWorkerMetrics {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let worker = load!(String, reader)?;
+        let remote_address = load!(String, reader)?;
+        let difficulty = load!(f64, reader)?;
+        let hashrate = load!(f64, reader)?;
+        let shares_accepted = load!(u64, reader)?;
+        let shares_stale = load!(u64, reader)?;
+        let shares_rejected = load!(u64, reader)?;
+        let blocks_found = load!(u64, reader)?;
+        let connected_since = load!(u64, reader)?;
+
+        Ok(Self {
+            worker,
+            remote_address,
+            difficulty,
+            hashrate,
+            shares_accepted,
+            shares_stale,
+            shares_rejected,
+            blocks_found,
+            connected_since,
+        })
+    }
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StratumMetrics {
+    /// Sum of the estimated hashrates of all the connected workers, in hashes per second
+    pub hashrate: f64,
+    pub workers: Vec<StratumWorkerMetrics>,
+}
+
+impl Serializer for StratumMetrics {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(f64, &self.hashrate, writer)?;
+        serialize!(Vec<StratumWorkerMetrics>, &self.workers, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for StratumMetrics {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let hashrate = load!(f64, reader)?;
+        let workers = deserialize!(Vec<StratumWorkerMetrics>, reader)?;
+
+        Ok(Self { hashrate, workers })
+    }
+}
+
 // TODO: Custom metrics dictionary
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum CustomMetricValue {
@@ -2290,6 +2648,8 @@ pub struct GetMetricsResponse {
     pub storage_metrics: Option<StorageMetrics>,
     // TODO: this is currently a placeholder
     pub custom_metrics: Option<HashMap<String, CustomMetricValue>>,
+    #[serde(default)]
+    pub stratum_metrics: Option<StratumMetrics>,
 }
 
 impl GetMetricsResponse {
@@ -2301,6 +2661,7 @@ impl GetMetricsResponse {
         consensus_metrics: Option<ConsensusMetrics>,
         storage_metrics: Option<StorageMetrics>,
         custom_metrics: Option<HashMap<String, CustomMetricValue>>,
+        stratum_metrics: Option<StratumMetrics>,
     ) -> Self {
         Self {
             process_metrics,
@@ -2310,13 +2671,14 @@ impl GetMetricsResponse {
             storage_metrics,
             server_time,
             custom_metrics,
+            stratum_metrics,
         }
     }
 }
 
 impl Serializer for GetMetricsResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(u64, &self.server_time, writer)?;
         serialize!(Option<ProcessMetrics>, &self.process_metrics, writer)?;
         serialize!(Option<ConnectionMetrics>, &self.connection_metrics, writer)?;
@@ -2324,6 +2686,7 @@ impl Serializer for GetMetricsResponse {
         serialize!(Option<ConsensusMetrics>, &self.consensus_metrics, writer)?;
         serialize!(Option<StorageMetrics>, &self.storage_metrics, writer)?;
         serialize!(Option<HashMap<String, CustomMetricValue>>, &self.custom_metrics, writer)?;
+        serialize!(Option<StratumMetrics>, &self.stratum_metrics, writer)?;
 
         Ok(())
     }
@@ -2331,7 +2694,7 @@ impl Serializer for GetMetricsResponse {
 
 impl Deserializer for GetMetricsResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let server_time = load!(u64, reader)?;
         let process_metrics = deserialize!(Option<ProcessMetrics>, reader)?;
         let connection_metrics = deserialize!(Option<ConnectionMetrics>, reader)?;
@@ -2339,6 +2702,7 @@ impl Deserializer for GetMetricsResponse {
         let consensus_metrics = deserialize!(Option<ConsensusMetrics>, reader)?;
         let storage_metrics = deserialize!(Option<StorageMetrics>, reader)?;
         let custom_metrics = deserialize!(Option<HashMap<String, CustomMetricValue>>, reader)?;
+        let stratum_metrics = if version > 1 { deserialize!(Option<StratumMetrics>, reader)? } else { None };
 
         Ok(Self {
             server_time,
@@ -2348,6 +2712,7 @@ impl Deserializer for GetMetricsResponse {
             consensus_metrics,
             storage_metrics,
             custom_metrics,
+            stratum_metrics,
         })
     }
 }
@@ -2618,170 +2983,677 @@ impl Deserializer for GetFeeEstimateExperimentalResponse {
     }
 }
 
+/// Requests the historical fee estimation over the last `window_seconds`, or over the whole kept history if 0
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct GetCurrentBlockColorRequest {
-    pub hash: RpcHash,
+pub struct GetFeeEstimateHistoricalRequest {
+    pub window_seconds: u64,
 }
 
-impl Serializer for GetCurrentBlockColorRequest {
+impl Serializer for GetFeeEstimateHistoricalRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
-        store!(RpcHash, &self.hash, writer)?;
-
+        store!(u64, &self.window_seconds, writer)?;
         Ok(())
     }
 }
 
-impl Deserializer for GetCurrentBlockColorRequest {
+impl Deserializer for GetFeeEstimateHistoricalRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
         let _version = load!(u16, reader)?;
-        let hash = load!(RpcHash, reader)?;
-
-        Ok(Self { hash })
+        let window_seconds = load!(u64, reader)?;
+        Ok(Self { window_seconds })
     }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct GetCurrentBlockColorResponse {
-    pub blue: bool,
+pub struct GetFeeEstimateHistoricalResponse {
+    pub estimate: RpcHistoricalFeeEstimate,
 }
 
-impl Serializer for GetCurrentBlockColorResponse {
+impl Serializer for GetFeeEstimateHistoricalResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
-        store!(bool, &self.blue, writer)?;
-
+        serialize!(RpcHistoricalFeeEstimate, &self.estimate, writer)?;
         Ok(())
     }
 }
 
-impl Deserializer for GetCurrentBlockColorResponse {
+impl Deserializer for GetFeeEstimateHistoricalResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
         let _version = load!(u16, reader)?;
-        let blue = load!(bool, reader)?;
-
-        Ok(Self { blue })
+        let estimate = deserialize!(RpcHistoricalFeeEstimate, reader)?;
+        Ok(Self { estimate })
     }
 }
 
-// ----------------------------------------------------------------------------
-// Subscriptions & notifications
-// ----------------------------------------------------------------------------
-
-// ~~~~~~~~~~~~~~~~~~~~~~
-// BlockAddedNotification
-
-/// NotifyBlockAddedRequest registers this connection for blockAdded notifications.
-///
-/// See: BlockAddedNotification
+/// PrioritiseTransactionRequest adjusts the fee a transaction is ranked by when selecting the transactions of
+/// block templates, without changing the fee actually paid. The transaction does not need to be in the mempool yet.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyBlockAddedRequest {
-    pub command: Command,
+pub struct PrioritiseTransactionRequest {
+    pub transaction_id: RpcTransactionId,
+    /// Amount in sompi added to the current fee delta of the transaction, possibly negative
+    pub fee_delta: i64,
 }
-impl NotifyBlockAddedRequest {
-    pub fn new(command: Command) -> Self {
-        Self { command }
+
+impl PrioritiseTransactionRequest {
+    pub fn new(transaction_id: RpcTransactionId, fee_delta: i64) -> Self {
+        Self { transaction_id, fee_delta }
     }
 }
 
-impl Serializer for NotifyBlockAddedRequest {
+impl Serializer for PrioritiseTransactionRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
-        store!(Command, &self.command, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(i64, &self.fee_delta, writer)?;
         Ok(())
     }
 }
 
-impl Deserializer for NotifyBlockAddedRequest {
+impl Deserializer for PrioritiseTransactionRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
         let _version = load!(u16, reader)?;
-        let command = load!(Command, reader)?;
-        Ok(Self { command })
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let fee_delta = load!(i64, reader)?;
+        Ok(Self { transaction_id, fee_delta })
     }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyBlockAddedResponse {}
+pub struct PrioritiseTransactionResponse {
+    /// The overall fee delta of the transaction following the request
+    pub fee_delta: i64,
+}
 
-impl Serializer for NotifyBlockAddedResponse {
+impl Serializer for PrioritiseTransactionResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
+        store!(i64, &self.fee_delta, writer)?;
         Ok(())
     }
 }
 
-impl Deserializer for NotifyBlockAddedResponse {
+impl Deserializer for PrioritiseTransactionResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
         let _version = load!(u16, reader)?;
-        Ok(Self {})
+        let fee_delta = load!(i64, reader)?;
+        Ok(Self { fee_delta })
     }
 }
 
-/// BlockAddedNotification is sent whenever a blocks has been added (NOT accepted)
-/// into the DAG.
-///
-/// See: NotifyBlockAddedRequest
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct BlockAddedNotification {
-    pub block: Arc<RpcBlock>,
-}
+pub struct ListBannedRequest {}
 
-impl Serializer for BlockAddedNotification {
+impl Serializer for ListBannedRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
-        serialize!(RpcBlock, &self.block, writer)?;
         Ok(())
     }
 }
 
-impl Deserializer for BlockAddedNotification {
+impl Deserializer for ListBannedRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
         let _version = load!(u16, reader)?;
-        let block = deserialize!(RpcBlock, reader)?;
-        Ok(Self { block: block.into() })
+        Ok(Self {})
     }
 }
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// VirtualChainChangedNotification
-
-// NotifyVirtualChainChangedRequest registers this connection for
-// virtualDaaScoreChanged notifications.
-//
-// See: VirtualChainChangedNotification
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyVirtualChainChangedRequest {
-    pub include_accepted_transaction_ids: bool,
-    pub command: Command,
+pub struct ListBannedResponse {
+    pub banned_addresses: Vec<RpcBannedAddress>,
 }
 
-impl NotifyVirtualChainChangedRequest {
-    pub fn new(include_accepted_transaction_ids: bool, command: Command) -> Self {
-        Self { include_accepted_transaction_ids, command }
+impl ListBannedResponse {
+    pub fn new(banned_addresses: Vec<RpcBannedAddress>) -> Self {
+        Self { banned_addresses }
     }
 }
 
-impl Serializer for NotifyVirtualChainChangedRequest {
+impl Serializer for ListBannedResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
-        store!(bool, &self.include_accepted_transaction_ids, writer)?;
-        store!(Command, &self.command, writer)?;
+        store!(Vec<RpcBannedAddress>, &self.banned_addresses, writer)?;
         Ok(())
     }
 }
 
-impl Deserializer for NotifyVirtualChainChangedRequest {
+impl Deserializer for ListBannedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let banned_addresses = load!(Vec<RpcBannedAddress>, reader)?;
+        Ok(Self { banned_addresses })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCurrentBlockColorRequest {
+    pub hash: RpcHash,
+}
+
+impl Serializer for GetCurrentBlockColorRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcHash, &self.hash, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetCurrentBlockColorRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let hash = load!(RpcHash, reader)?;
+
+        Ok(Self { hash })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCurrentBlockColorResponse {
+    pub blue: bool,
+}
+
+impl Serializer for GetCurrentBlockColorResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(bool, &self.blue, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetCurrentBlockColorResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let blue = load!(bool, reader)?;
+
+        Ok(Self { blue })
+    }
+}
+
+/// GetTransactionRequest requests the location of an accepted transaction from the transaction index.
+///
+/// Requires the node to run with the `--txindex` argument.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionRequest {
+    pub transaction_id: RpcTransactionId,
+    pub include_transaction: bool,
+}
+
+impl GetTransactionRequest {
+    pub fn new(transaction_id: RpcTransactionId, include_transaction: bool) -> Self {
+        Self { transaction_id, include_transaction }
+    }
+}
+
+impl Serializer for GetTransactionRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(bool, &self.include_transaction, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let include_transaction = load!(bool, reader)?;
+
+        Ok(Self { transaction_id, include_transaction })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionResponse {
+    pub transaction: RpcIndexedTransaction,
+}
+
+impl GetTransactionResponse {
+    pub fn new(transaction: RpcIndexedTransaction) -> Self {
+        Self { transaction }
+    }
+}
+
+impl Serializer for GetTransactionResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcIndexedTransaction, &self.transaction, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction = deserialize!(RpcIndexedTransaction, reader)?;
+
+        Ok(Self { transaction })
+    }
+}
+
+/// GetTransactionsByIdsRequest requests the locations of several accepted transactions from the transaction index.
+///
+/// Transactions which are not indexed are omitted from the response.
+/// Requires the node to run with the `--txindex` argument.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionsByIdsRequest {
+    pub transaction_ids: Vec<RpcTransactionId>,
+    pub include_transactions: bool,
+}
+
+impl GetTransactionsByIdsRequest {
+    pub fn new(transaction_ids: Vec<RpcTransactionId>, include_transactions: bool) -> Self {
+        Self { transaction_ids, include_transactions }
+    }
+}
+
+impl Serializer for GetTransactionsByIdsRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcTransactionId>, &self.transaction_ids, writer)?;
+        store!(bool, &self.include_transactions, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionsByIdsRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+        let include_transactions = load!(bool, reader)?;
+
+        Ok(Self { transaction_ids, include_transactions })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionsByIdsResponse {
+    pub transactions: Vec<RpcIndexedTransaction>,
+}
+
+impl GetTransactionsByIdsResponse {
+    pub fn new(transactions: Vec<RpcIndexedTransaction>) -> Self {
+        Self { transactions }
+    }
+}
+
+impl Serializer for GetTransactionsByIdsResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcIndexedTransaction>, &self.transactions, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionsByIdsResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transactions = deserialize!(Vec<RpcIndexedTransaction>, reader)?;
+
+        Ok(Self { transactions })
+    }
+}
+
+/// GetTransactionsByAddressesRequest requests a page of the history of a set of addresses from the address history index,
+/// that is, the transactions accepted by the virtual selected chain which spent from or paid to any of them.
+///
+/// The history is ordered by accepting block DAA score, then by transaction id. Pages start right after `cursor`,
+/// or at the beginning of the history if no cursor is supplied, and hold up to `limit` entries.
+/// Requires the node to run with the `--addressindex` argument.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionsByAddressesRequest {
+    pub addresses: Vec<RpcAddress>,
+    pub cursor: Option<RpcAddressHistoryCursor>,
+    pub limit: u32,
+}
+
+impl GetTransactionsByAddressesRequest {
+    pub fn new(addresses: Vec<RpcAddress>, cursor: Option<RpcAddressHistoryCursor>, limit: u32) -> Self {
+        Self { addresses, cursor, limit }
+    }
+}
+
+impl Serializer for GetTransactionsByAddressesRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcAddress>, &self.addresses, writer)?;
+        serialize!(Option<RpcAddressHistoryCursor>, &self.cursor, writer)?;
+        store!(u32, &self.limit, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionsByAddressesRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let addresses = load!(Vec<RpcAddress>, reader)?;
+        let cursor = deserialize!(Option<RpcAddressHistoryCursor>, reader)?;
+        let limit = load!(u32, reader)?;
+
+        Ok(Self { addresses, cursor, limit })
+    }
+}
+
+/// The entries of a single transaction are never split between pages, so a page may exceed the requested limit.
+/// `next_cursor` is set if the history continues past this page.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionsByAddressesResponse {
+    pub entries: Vec<RpcAddressTransaction>,
+    pub next_cursor: Option<RpcAddressHistoryCursor>,
+}
+
+impl GetTransactionsByAddressesResponse {
+    pub fn new(entries: Vec<RpcAddressTransaction>, next_cursor: Option<RpcAddressHistoryCursor>) -> Self {
+        Self { entries, next_cursor }
+    }
+}
+
+impl Serializer for GetTransactionsByAddressesResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcAddressTransaction>, &self.entries, writer)?;
+        serialize!(Option<RpcAddressHistoryCursor>, &self.next_cursor, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionsByAddressesResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let entries = deserialize!(Vec<RpcAddressTransaction>, reader)?;
+        let next_cursor = deserialize!(Option<RpcAddressHistoryCursor>, reader)?;
+
+        Ok(Self { entries, next_cursor })
+    }
+}
+
+/// GetChainBlockByDaaScoreRequest requests the selected chain block with the highest DAA score not exceeding `daa_score`,
+/// that is, the chain block whose mergeset covers `daa_score`.
+///
+/// Only the selected chain from the pruning point up to the sink is searched.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChainBlockByDaaScoreRequest {
+    pub daa_score: u64,
+}
+
+impl GetChainBlockByDaaScoreRequest {
+    pub fn new(daa_score: u64) -> Self {
+        Self { daa_score }
+    }
+}
+
+impl Serializer for GetChainBlockByDaaScoreRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.daa_score, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetChainBlockByDaaScoreRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let daa_score = load!(u64, reader)?;
+
+        Ok(Self { daa_score })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChainBlockByDaaScoreResponse {
+    pub header: RpcHeader,
+}
+
+impl GetChainBlockByDaaScoreResponse {
+    pub fn new(header: RpcHeader) -> Self {
+        Self { header }
+    }
+}
+
+impl Serializer for GetChainBlockByDaaScoreResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcHeader, &self.header, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetChainBlockByDaaScoreResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let header = deserialize!(RpcHeader, reader)?;
+
+        Ok(Self { header })
+    }
+}
+
+/// GetChainBlocksInBlueScoreRangeRequest requests the selected chain blocks with a blue score within
+/// `[low_blue_score, high_blue_score]`, ordered by blue score.
+///
+/// Only the selected chain from the pruning point up to the sink is searched. The response is bounded in size,
+/// so a range may need to be requested in several batches, resuming from the `next_low_blue_score` of the response.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChainBlocksInBlueScoreRangeRequest {
+    pub low_blue_score: u64,
+    pub high_blue_score: u64,
+}
+
+impl GetChainBlocksInBlueScoreRangeRequest {
+    pub fn new(low_blue_score: u64, high_blue_score: u64) -> Self {
+        Self { low_blue_score, high_blue_score }
+    }
+}
+
+impl Serializer for GetChainBlocksInBlueScoreRangeRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.low_blue_score, writer)?;
+        store!(u64, &self.high_blue_score, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetChainBlocksInBlueScoreRangeRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
         let _version = load!(u16, reader)?;
+        let low_blue_score = load!(u64, reader)?;
+        let high_blue_score = load!(u64, reader)?;
+
+        Ok(Self { low_blue_score, high_blue_score })
+    }
+}
+
+/// `next_low_blue_score` is set if the range was truncated, in which case the remaining headers are returned by
+/// requesting the range starting from it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChainBlocksInBlueScoreRangeResponse {
+    pub headers: Vec<RpcHeader>,
+    pub next_low_blue_score: Option<u64>,
+}
+
+impl GetChainBlocksInBlueScoreRangeResponse {
+    pub fn new(headers: Vec<RpcHeader>, next_low_blue_score: Option<u64>) -> Self {
+        Self { headers, next_low_blue_score }
+    }
+}
+
+impl Serializer for GetChainBlocksInBlueScoreRangeResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcHeader>, &self.headers, writer)?;
+        store!(Option<u64>, &self.next_low_blue_score, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetChainBlocksInBlueScoreRangeResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let headers = deserialize!(Vec<RpcHeader>, reader)?;
+        let next_low_blue_score = load!(Option<u64>, reader)?;
+
+        Ok(Self { headers, next_low_blue_score })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Subscriptions & notifications
+// ----------------------------------------------------------------------------
+
+// ~~~~~~~~~~~~~~~~~~~~~~
+// BlockAddedNotification
+
+/// NotifyBlockAddedRequest registers this connection for blockAdded notifications.
+///
+/// See: BlockAddedNotification
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyBlockAddedRequest {
+    pub command: Command,
+}
+impl NotifyBlockAddedRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+impl Serializer for NotifyBlockAddedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Command, &self.command, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyBlockAddedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let command = load!(Command, reader)?;
+        Ok(Self { command })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyBlockAddedResponse {}
+
+impl Serializer for NotifyBlockAddedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyBlockAddedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// BlockAddedNotification is sent whenever a blocks has been added (NOT accepted)
+/// into the DAG.
+///
+/// See: NotifyBlockAddedRequest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockAddedNotification {
+    pub block: Arc<RpcBlock>,
+}
+
+impl Serializer for BlockAddedNotification {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcBlock, &self.block, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for BlockAddedNotification {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let block = deserialize!(RpcBlock, reader)?;
+        Ok(Self { block: block.into() })
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// VirtualChainChangedNotification
+
+// NotifyVirtualChainChangedRequest registers this connection for
+// virtualDaaScoreChanged notifications.
+//
+// If `start_hash` is set, the chain changes since this chain block are
+// first replayed, followed by the live notifications. Every notification
+// then applies on top of the chain resulting from its predecessor.
+//
+// See: VirtualChainChangedNotification
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyVirtualChainChangedRequest {
+    pub include_accepted_transaction_ids: bool,
+    #[serde(default)]
+    pub start_hash: Option<RpcHash>,
+    pub command: Command,
+}
+
+impl NotifyVirtualChainChangedRequest {
+    pub fn new(include_accepted_transaction_ids: bool, command: Command) -> Self {
+        Self { include_accepted_transaction_ids, start_hash: None, command }
+    }
+
+    pub fn with_start_hash(include_accepted_transaction_ids: bool, start_hash: RpcHash, command: Command) -> Self {
+        Self { include_accepted_transaction_ids, start_hash: Some(start_hash), command }
+    }
+}
+
+impl Serializer for NotifyVirtualChainChangedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &2, writer)?;
+        store!(bool, &self.include_accepted_transaction_ids, writer)?;
+        store!(Command, &self.command, writer)?;
+        store!(Option<RpcHash>, &self.start_hash, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyVirtualChainChangedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let version = load!(u16, reader)?;
         let include_accepted_transaction_ids = load!(bool, reader)?;
         let command = load!(Command, reader)?;
-        Ok(Self { include_accepted_transaction_ids, command })
+        let start_hash = if version > 1 { load!(Option<RpcHash>, reader)? } else { None };
+        Ok(Self { include_accepted_transaction_ids, start_hash, command })
     }
 }
 
@@ -3398,6 +4270,121 @@ impl Deserializer for NewBlockTemplateNotification {
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// MempoolTransactionRemovedNotification
+
+/// NotifyMempoolTransactionRemovedRequest registers this connection for mempoolTransactionRemoved notifications
+/// for the given addresses. Depending on the provided `command`, notifications will start or stop for the
+/// provided `addresses`.
+///
+/// If `addresses` is empty, the notifications will start or stop for all addresses.
+///
+/// See: MempoolTransactionRemovedNotification
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyMempoolTransactionRemovedRequest {
+    pub addresses: Vec<RpcAddress>,
+    pub command: Command,
+}
+
+impl NotifyMempoolTransactionRemovedRequest {
+    pub fn new(addresses: Vec<RpcAddress>, command: Command) -> Self {
+        Self { addresses, command }
+    }
+}
+
+impl Serializer for NotifyMempoolTransactionRemovedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcAddress>, &self.addresses, writer)?;
+        store!(Command, &self.command, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyMempoolTransactionRemovedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let addresses = load!(Vec<RpcAddress>, reader)?;
+        let command = load!(Command, reader)?;
+        Ok(Self { addresses, command })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyMempoolTransactionRemovedResponse {}
+
+impl Serializer for NotifyMempoolTransactionRemovedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyMempoolTransactionRemovedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// MempoolTransactionRemovedNotification is sent whenever a transaction leaves the mempool without
+/// being accepted by consensus (eviction, expiration, double spend, replacement...).
+///
+/// `addresses` holds the addresses of the transaction outputs and spent UTXOs matching the subscription,
+/// or all of them for a subscription to all addresses.
+///
+/// See: NotifyMempoolTransactionRemovedRequest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolTransactionRemovedNotification {
+    pub transaction_id: RpcTransactionId,
+    pub reason: RpcMempoolRemovalReason,
+    pub addresses: Arc<Vec<RpcAddress>>,
+}
+
+impl MempoolTransactionRemovedNotification {
+    pub(crate) fn apply_mempool_transaction_removed_subscription(
+        &self,
+        subscription: &UtxosChangedSubscription,
+        context: &SubscriptionContext,
+    ) -> Option<Self> {
+        if subscription.to_all() {
+            Some(self.clone())
+        } else {
+            let subscription_data = subscription.data();
+            let addresses: Vec<RpcAddress> =
+                self.addresses.iter().filter(|address| subscription_data.contains_address(address, context)).cloned().collect();
+            if addresses.is_empty() {
+                None
+            } else {
+                Some(Self { transaction_id: self.transaction_id, reason: self.reason, addresses: Arc::new(addresses) })
+            }
+        }
+    }
+}
+
+impl Serializer for MempoolTransactionRemovedNotification {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(RpcMempoolRemovalReason, &self.reason, writer)?;
+        store!(Vec<RpcAddress>, &self.addresses, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for MempoolTransactionRemovedNotification {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let reason = load!(RpcMempoolRemovalReason, reader)?;
+        let addresses = load!(Vec<RpcAddress>, reader)?;
+        Ok(Self { transaction_id, reason, addresses: addresses.into() })
+    }
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
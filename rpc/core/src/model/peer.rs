@@ -1,11 +1,12 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
-use vecno_utils::networking::{ContextualNetAddress, IpAddress, NetAddress, PeerId};
+use vecno_utils::networking::{ContextualNetAddress, IpAddress, NetAddress, OnionAddress, PeerId};
 
 pub type RpcNodeId = PeerId;
 pub type RpcIpAddress = IpAddress;
 pub type RpcPeerAddress = NetAddress;
 pub type RpcContextualPeerAddress = ContextualNetAddress;
+pub type RpcOnionAddress = OnionAddress;
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct RpcPeerInfo {
@@ -20,4 +21,24 @@ pub struct RpcPeerInfo {
     pub advertised_protocol_version: u32,
     pub time_connected: u64, // NOTE: i64 in gRPC protowire
     pub is_ibd_peer: bool,
+
+    /// Decaying score of the protocol errors of the peer, which gets banned once it reaches the ban threshold.
+    /// Serialized apart from the other fields by `GetConnectedPeerInfoResponse` for compatibility.
+    #[serde(default)]
+    #[borsh(skip)]
+    pub misbehavior_score: u32,
+
+    /// Whether the p2p messages exchanged with the peer are encrypted.
+    /// Serialized apart from the other fields by `GetConnectedPeerInfoResponse` for compatibility.
+    #[serde(default)]
+    #[borsh(skip)]
+    pub is_encrypted: bool,
+}
+
+/// A banned IP address along with the unix time, in milliseconds, its ban expires at
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBannedAddress {
+    pub ip: RpcIpAddress,
+    pub banned_until: u64,
 }
@@ -303,10 +303,18 @@ mod mockery {
                 advertised_protocol_version: mock(),
                 time_connected: mock(),
                 is_ibd_peer: mock(),
+                misbehavior_score: mock(),
+                is_encrypted: mock(),
             }
         }
     }
 
+    impl Mock for RpcBannedAddress {
+        fn mock() -> Self {
+            RpcBannedAddress { ip: mock(), banned_until: mock() }
+        }
+    }
+
     impl Mock for RpcMempoolEntry {
         fn mock() -> Self {
             RpcMempoolEntry { fee: mock(), transaction: mock(), is_orphan: mock() }
@@ -418,6 +426,28 @@ mod mockery {
         }
     }
 
+    impl Mock for StratumWorkerMetrics {
+        fn mock() -> Self {
+            StratumWorkerMetrics {
+                worker: "vecno:qz0000.rig0".to_string(),
+                remote_address: "127.0.0.1:50000".to_string(),
+                difficulty: mock(),
+                hashrate: mock(),
+                shares_accepted: mock(),
+                shares_stale: mock(),
+                shares_rejected: mock(),
+                blocks_found: mock(),
+                connected_since: mock(),
+            }
+        }
+    }
+
+    impl Mock for StratumMetrics {
+        fn mock() -> Self {
+            StratumMetrics { hashrate: mock(), workers: mock() }
+        }
+    }
+
     // --------------------------------------------
     // implementations for all the rpc request
     // and response data structures.
@@ -440,7 +470,16 @@ mod mockery {
 
     impl Mock for GetBlockTemplateRequest {
         fn mock() -> Self {
-            GetBlockTemplateRequest { pay_address: mock(), extra_data: vec![4, 2] }
+            GetBlockTemplateRequest {
+                pay_address: mock(),
+                extra_data: vec![4, 2],
+                template_policy: Some(RpcTemplatePolicy {
+                    selection_mode: RpcTemplateSelectionMode::HighestFeerate,
+                    prioritised_mass_share: mock(),
+                    prioritised_transaction_ids: mock(),
+                    excluded_transaction_ids: mock(),
+                }),
+            }
         }
     }
 
@@ -560,7 +599,16 @@ mod mockery {
 
     impl Mock for GetMempoolEntriesRequest {
         fn mock() -> Self {
-            GetMempoolEntriesRequest { include_orphan_pool: true, filter_transaction_pool: false }
+            GetMempoolEntriesRequest {
+                include_orphan_pool: true,
+                filter_transaction_pool: false,
+                offset: mock(),
+                limit: mock(),
+                sort_by: RpcMempoolEntrySortKey::Feerate,
+                sort_descending: true,
+                min_feerate: mock(),
+                payload_only: true,
+            }
         }
     }
 
@@ -568,7 +616,7 @@ mod mockery {
 
     impl Mock for GetMempoolEntriesResponse {
         fn mock() -> Self {
-            GetMempoolEntriesResponse { mempool_entries: mock() }
+            GetMempoolEntriesResponse { mempool_entries: mock(), total_count: mock(), total_mass: mock(), total_fees: mock() }
         }
     }
 
@@ -590,6 +638,18 @@ mod mockery {
 
     test!(GetConnectedPeerInfoResponse);
 
+    #[test]
+    fn test_connected_onion_peer() {
+        let onion = "2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:7111".parse::<NetAddress>().unwrap();
+        let response = GetConnectedPeerInfoResponse { peer_info: vec![RpcPeerInfo { address: onion, ..mock() }, mock()] };
+
+        let mut buffer = Vec::new();
+        serialize!(GetConnectedPeerInfoResponse, &response, &mut buffer).unwrap();
+        let deserialized = deserialize!(GetConnectedPeerInfoResponse, &mut buffer.as_slice()).unwrap();
+        assert_eq!(deserialized.peer_info[0].address.onion, onion.onion);
+        assert_eq!(deserialized.peer_info[1].address.onion, None);
+    }
+
     impl Mock for AddPeerRequest {
         fn mock() -> Self {
             AddPeerRequest { peer_address: mock(), is_permanent: mock() }
@@ -622,6 +682,105 @@ mod mockery {
 
     test!(SubmitTransactionResponse);
 
+    impl Mock for SubmitTransactionPackageRequest {
+        fn mock() -> Self {
+            SubmitTransactionPackageRequest { transactions: mock() }
+        }
+    }
+
+    test!(SubmitTransactionPackageRequest);
+
+    impl Mock for RpcTransactionRejection {
+        fn mock() -> Self {
+            RpcTransactionRejection { transaction_id: mock(), reason: "transaction package rejected".to_string() }
+        }
+    }
+
+    impl Mock for SubmitTransactionPackageResponse {
+        fn mock() -> Self {
+            SubmitTransactionPackageResponse { transaction_ids: mock(), rejections: mock() }
+        }
+    }
+
+    test!(SubmitTransactionPackageResponse);
+
+    impl Mock for TestMempoolAcceptRequest {
+        fn mock() -> Self {
+            TestMempoolAcceptRequest { transactions: mock(), allow_orphan: true, replace_by_fee: false }
+        }
+    }
+
+    test!(TestMempoolAcceptRequest);
+
+    impl Mock for RpcTransactionAcceptance {
+        fn mock() -> Self {
+            RpcTransactionAcceptance {
+                transaction_id: mock(),
+                accepted: false,
+                is_orphan: false,
+                compute_mass: mock(),
+                storage_mass: mock(),
+                fee: mock(),
+                feerate: mock(),
+                replaced_transaction_id: mock(),
+                error: Some("transaction rejected".to_string()),
+            }
+        }
+    }
+
+    impl Mock for TestMempoolAcceptResponse {
+        fn mock() -> Self {
+            TestMempoolAcceptResponse { acceptances: mock() }
+        }
+    }
+
+    test!(TestMempoolAcceptResponse);
+
+    impl Mock for GetFeeEstimateHistoricalRequest {
+        fn mock() -> Self {
+            GetFeeEstimateHistoricalRequest { window_seconds: mock() }
+        }
+    }
+
+    test!(GetFeeEstimateHistoricalRequest);
+
+    impl Mock for RpcHistoricalFeerateBucket {
+        fn mock() -> Self {
+            RpcHistoricalFeerateBucket { feerate: mock(), target_seconds: mock(), confidence: mock() }
+        }
+    }
+
+    impl Mock for GetFeeEstimateHistoricalResponse {
+        fn mock() -> Self {
+            GetFeeEstimateHistoricalResponse {
+                estimate: RpcHistoricalFeeEstimate {
+                    window_seconds: mock(),
+                    block_count: mock(),
+                    sample_count: mock(),
+                    buckets: mock(),
+                },
+            }
+        }
+    }
+
+    test!(GetFeeEstimateHistoricalResponse);
+
+    impl Mock for PrioritiseTransactionRequest {
+        fn mock() -> Self {
+            PrioritiseTransactionRequest { transaction_id: mock(), fee_delta: -42 }
+        }
+    }
+
+    test!(PrioritiseTransactionRequest);
+
+    impl Mock for PrioritiseTransactionResponse {
+        fn mock() -> Self {
+            PrioritiseTransactionResponse { fee_delta: -42 }
+        }
+    }
+
+    test!(PrioritiseTransactionResponse);
+
     impl Mock for GetSubnetworkRequest {
         fn mock() -> Self {
             GetSubnetworkRequest { subnetwork_id: mock() }
@@ -873,6 +1032,22 @@ mod mockery {
 
     test!(UnbanResponse);
 
+    impl Mock for ListBannedRequest {
+        fn mock() -> Self {
+            ListBannedRequest {}
+        }
+    }
+
+    test!(ListBannedRequest);
+
+    impl Mock for ListBannedResponse {
+        fn mock() -> Self {
+            ListBannedResponse { banned_addresses: mock() }
+        }
+    }
+
+    test!(ListBannedResponse);
+
     impl Mock for EstimateNetworkHashesPerSecondRequest {
         fn mock() -> Self {
             EstimateNetworkHashesPerSecondRequest { window_size: mock(), start_hash: mock() }
@@ -986,6 +1161,7 @@ mod mockery {
                 consensus_metrics: true,
                 storage_metrics: true,
                 custom_metrics: false,
+                stratum_metrics: true,
             }
         }
     }
@@ -1002,6 +1178,7 @@ mod mockery {
                 consensus_metrics: mock(),
                 storage_metrics: mock(),
                 custom_metrics: None,
+                stratum_metrics: mock(),
             }
         }
     }
@@ -1064,6 +1241,118 @@ mod mockery {
 
     test!(GetDaaScoreTimestampEstimateResponse);
 
+    impl Mock for RpcIndexedTransaction {
+        fn mock() -> Self {
+            RpcIndexedTransaction {
+                transaction_id: mock(),
+                accepting_block_hash: mock(),
+                accepting_block_daa_score: mock(),
+                including_block_hash: mock(),
+                index_within_block: mock(),
+                transaction: mock(),
+            }
+        }
+    }
+
+    impl Mock for GetTransactionRequest {
+        fn mock() -> Self {
+            GetTransactionRequest { transaction_id: mock(), include_transaction: true }
+        }
+    }
+
+    test!(GetTransactionRequest);
+
+    impl Mock for GetTransactionResponse {
+        fn mock() -> Self {
+            GetTransactionResponse { transaction: mock() }
+        }
+    }
+
+    test!(GetTransactionResponse);
+
+    impl Mock for GetTransactionsByIdsRequest {
+        fn mock() -> Self {
+            GetTransactionsByIdsRequest { transaction_ids: mock(), include_transactions: true }
+        }
+    }
+
+    test!(GetTransactionsByIdsRequest);
+
+    impl Mock for GetTransactionsByIdsResponse {
+        fn mock() -> Self {
+            GetTransactionsByIdsResponse { transactions: mock() }
+        }
+    }
+
+    test!(GetTransactionsByIdsResponse);
+
+    impl Mock for RpcAddressHistoryCursor {
+        fn mock() -> Self {
+            RpcAddressHistoryCursor { accepting_block_daa_score: mock(), transaction_id: mock() }
+        }
+    }
+
+    impl Mock for GetTransactionsByAddressesRequest {
+        fn mock() -> Self {
+            GetTransactionsByAddressesRequest { addresses: mock(), cursor: mock(), limit: mock() }
+        }
+    }
+
+    test!(GetTransactionsByAddressesRequest);
+
+    impl Mock for RpcAddressTransaction {
+        fn mock() -> Self {
+            RpcAddressTransaction {
+                address: mock(),
+                transaction_id: mock(),
+                accepting_block_hash: mock(),
+                accepting_block_daa_score: mock(),
+                received_amount: mock(),
+                spent_amount: mock(),
+            }
+        }
+    }
+
+    impl Mock for GetTransactionsByAddressesResponse {
+        fn mock() -> Self {
+            GetTransactionsByAddressesResponse { entries: mock(), next_cursor: mock() }
+        }
+    }
+
+    test!(GetTransactionsByAddressesResponse);
+
+    impl Mock for GetChainBlockByDaaScoreRequest {
+        fn mock() -> Self {
+            GetChainBlockByDaaScoreRequest { daa_score: mock() }
+        }
+    }
+
+    test!(GetChainBlockByDaaScoreRequest);
+
+    impl Mock for GetChainBlockByDaaScoreResponse {
+        fn mock() -> Self {
+            GetChainBlockByDaaScoreResponse { header: mock() }
+        }
+    }
+
+    test!(GetChainBlockByDaaScoreResponse);
+
+    impl Mock for GetChainBlocksInBlueScoreRangeRequest {
+        fn mock() -> Self {
+            GetChainBlocksInBlueScoreRangeRequest { low_blue_score: mock(), high_blue_score: mock() }
+        }
+    }
+
+    test!(GetChainBlocksInBlueScoreRangeRequest);
+
+    impl Mock for GetChainBlocksInBlueScoreRangeResponse {
+        fn mock() -> Self {
+            GetChainBlocksInBlueScoreRangeResponse { headers: mock(), next_low_blue_score: mock() }
+        }
+    }
+
+    test!(GetChainBlocksInBlueScoreRangeResponse);
+
     impl Mock for NotifyBlockAddedRequest {
         fn mock() -> Self {
             NotifyBlockAddedRequest { command: Command::Start }
@@ -1090,7 +1379,7 @@ mod mockery {
 
     impl Mock for NotifyVirtualChainChangedRequest {
         fn mock() -> Self {
-            NotifyVirtualChainChangedRequest { command: Command::Start, include_accepted_transaction_ids: true }
+            NotifyVirtualChainChangedRequest { command: Command::Start, include_accepted_transaction_ids: true, start_hash: mock() }
         }
     }
 
@@ -1284,6 +1573,34 @@ mod mockery {
 
     test!(NewBlockTemplateNotification);
 
+    impl Mock for NotifyMempoolTransactionRemovedRequest {
+        fn mock() -> Self {
+            NotifyMempoolTransactionRemovedRequest { addresses: mock(), command: Command::Start }
+        }
+    }
+
+    test!(NotifyMempoolTransactionRemovedRequest);
+
+    impl Mock for NotifyMempoolTransactionRemovedResponse {
+        fn mock() -> Self {
+            NotifyMempoolTransactionRemovedResponse {}
+        }
+    }
+
+    test!(NotifyMempoolTransactionRemovedResponse);
+
+    impl Mock for MempoolTransactionRemovedNotification {
+        fn mock() -> Self {
+            MempoolTransactionRemovedNotification {
+                transaction_id: mock(),
+                reason: RpcMempoolRemovalReason::ReplacedByFee,
+                addresses: mock(),
+            }
+        }
+    }
+
+    test!(MempoolTransactionRemovedNotification);
+
     impl Mock for SubscribeResponse {
         fn mock() -> Self {
             SubscribeResponse::new(mock())
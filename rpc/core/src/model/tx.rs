@@ -398,3 +398,198 @@ pub struct RpcAcceptedTransactionIds {
     pub accepting_block_hash: RpcHash,
     pub accepted_transaction_ids: Vec<RpcTransactionId>,
 }
+
+/// Represents a transaction located by the transaction index
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcIndexedTransaction {
+    pub transaction_id: RpcTransactionId,
+    /// The chain block whose mergeset accepted the transaction
+    pub accepting_block_hash: RpcHash,
+    pub accepting_block_daa_score: u64,
+    /// The block containing the transaction
+    pub including_block_hash: RpcHash,
+    pub index_within_block: u32,
+    /// The transaction itself, if requested and the including block body is still available
+    pub transaction: Option<RpcTransaction>,
+}
+
+impl Serializer for RpcIndexedTransaction {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(RpcHash, &self.accepting_block_hash, writer)?;
+        store!(u64, &self.accepting_block_daa_score, writer)?;
+        store!(RpcHash, &self.including_block_hash, writer)?;
+        store!(u32, &self.index_within_block, writer)?;
+        serialize!(Option<RpcTransaction>, &self.transaction, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcIndexedTransaction {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let accepting_block_hash = load!(RpcHash, reader)?;
+        let accepting_block_daa_score = load!(u64, reader)?;
+        let including_block_hash = load!(RpcHash, reader)?;
+        let index_within_block = load!(u32, reader)?;
+        let transaction = deserialize!(Option<RpcTransaction>, reader)?;
+
+        Ok(Self {
+            transaction_id,
+            accepting_block_hash,
+            accepting_block_daa_score,
+            including_block_hash,
+            index_within_block,
+            transaction,
+        })
+    }
+}
+
+/// Represents a transaction rejected from a submitted transaction package, along with the reason of its rejection
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionRejection {
+    pub transaction_id: RpcTransactionId,
+    pub reason: String,
+}
+
+impl RpcTransactionRejection {
+    pub fn new(transaction_id: RpcTransactionId, reason: String) -> Self {
+        Self { transaction_id, reason }
+    }
+}
+
+impl Serializer for RpcTransactionRejection {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(String, &self.reason, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcTransactionRejection {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let reason = load!(String, reader)?;
+
+        Ok(Self { transaction_id, reason })
+    }
+}
+
+/// Represents the outcome of a transaction tested for acceptance to the mempool
+///
+/// The storage mass, fee and feerate are known only if all the UTXO entries spent by the transaction are found.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionAcceptance {
+    pub transaction_id: RpcTransactionId,
+    pub accepted: bool,
+    /// The transaction would be added to the orphan pool
+    pub is_orphan: bool,
+    pub compute_mass: u64,
+    pub storage_mass: Option<u64>,
+    pub fee: Option<u64>,
+    /// Fee per gram of contextual mass, the max of the compute and storage masses
+    pub feerate: Option<f64>,
+    /// The mempool transaction the transaction would replace by fee
+    pub replaced_transaction_id: Option<RpcTransactionId>,
+    /// The reason of the rejection of the transaction
+    pub error: Option<String>,
+}
+
+impl Serializer for RpcTransactionAcceptance {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(bool, &self.accepted, writer)?;
+        store!(bool, &self.is_orphan, writer)?;
+        store!(u64, &self.compute_mass, writer)?;
+        store!(Option<u64>, &self.storage_mass, writer)?;
+        store!(Option<u64>, &self.fee, writer)?;
+        store!(Option<f64>, &self.feerate, writer)?;
+        store!(Option<RpcTransactionId>, &self.replaced_transaction_id, writer)?;
+        store!(Option<String>, &self.error, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcTransactionAcceptance {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let accepted = load!(bool, reader)?;
+        let is_orphan = load!(bool, reader)?;
+        let compute_mass = load!(u64, reader)?;
+        let storage_mass = load!(Option<u64>, reader)?;
+        let fee = load!(Option<u64>, reader)?;
+        let feerate = load!(Option<f64>, reader)?;
+        let replaced_transaction_id = load!(Option<RpcTransactionId>, reader)?;
+        let error = load!(Option<String>, reader)?;
+
+        Ok(Self { transaction_id, accepted, is_orphan, compute_mass, storage_mass, fee, feerate, replaced_transaction_id, error })
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "wasm32-sdk")] {
+        use wasm_bindgen::prelude::*;
+
+        #[wasm_bindgen(typescript_custom_section)]
+        const TS_INDEXED_TRANSACTION: &'static str = r#"
+            /**
+             * Transaction located by the transaction index.
+             * 
+             * @category Node RPC
+             */
+            export interface IIndexedTransaction {
+                transactionId : HexString;
+                acceptingBlockHash : HexString;
+                acceptingBlockDaaScore : bigint;
+                includingBlockHash : HexString;
+                indexWithinBlock : number;
+                transaction? : ITransaction;
+            }
+        "#;
+
+        #[wasm_bindgen(typescript_custom_section)]
+        const TS_TRANSACTION_REJECTION: &'static str = r#"
+            /**
+             * Transaction rejected from a submitted transaction package.
+             * 
+             * @category Node RPC
+             */
+            export interface ITransactionRejection {
+                transactionId : HexString;
+                reason : string;
+            }
+        "#;
+
+        #[wasm_bindgen(typescript_custom_section)]
+        const TS_TRANSACTION_ACCEPTANCE: &'static str = r#"
+            /**
+             * Outcome of a transaction tested for acceptance to the mempool.
+             * 
+             * @category Node RPC
+             */
+            export interface ITransactionAcceptance {
+                transactionId : HexString;
+                accepted : boolean;
+                isOrphan : boolean;
+                computeMass : bigint;
+                storageMass? : bigint;
+                fee? : bigint;
+                feerate? : number;
+                replacedTransactionId? : HexString;
+                error? : string;
+            }
+        "#;
+    }
+}
@@ -819,6 +819,10 @@ declare! {
          * `extraData` can contain a user-supplied plain text or a byte array represented by `Uint8array`.
          */
         extraData? : string | Uint8Array;
+        /**
+         * Transaction selection policy overriding the one of the node.
+         */
+        templatePolicy? : ITemplatePolicy;
     }
     "#,
 }
@@ -834,9 +838,11 @@ try_from! ( args: IGetBlockTemplateRequest, GetBlockTemplateRequest, {
     } else {
         Default::default()
     };
+    let template_policy = args.try_get_value("templatePolicy")?.map(from_value).transpose()?;
     Ok(GetBlockTemplateRequest {
         pay_address,
         extra_data,
+        template_policy,
     })
 });
 
@@ -898,6 +904,225 @@ try_from! ( args: GetCurrentBlockColorResponse, IGetCurrentBlockColorResponse, {
 
 // ---
 
+declare! {
+    IGetTransactionRequest,
+    r#"
+    /**
+     * Requires the node to run with the `--txindex` argument.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionRequest {
+        transactionId : HexString;
+        includeTransaction : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionRequest, GetTransactionRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionResponse {
+        transaction : IIndexedTransaction;
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionResponse, IGetTransactionResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetTransactionsByIdsRequest,
+    r#"
+    /**
+     * Requires the node to run with the `--txindex` argument.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionsByIdsRequest {
+        transactionIds : HexString[];
+        includeTransactions : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionsByIdsRequest, GetTransactionsByIdsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionsByIdsResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionsByIdsResponse {
+        transactions : IIndexedTransaction[];
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionsByIdsResponse, IGetTransactionsByIdsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetTransactionsByAddressesRequest,
+    r#"
+    /**
+     * Requires the node to run with the `--addressindex` argument.
+     * 
+     * @category Node RPC
+     */
+    export interface IAddressHistoryCursor {
+        acceptingBlockDaaScore : bigint;
+        transactionId : HexString;
+    }
+    /**
+     * Requires the node to run with the `--addressindex` argument.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionsByAddressesRequest {
+        addresses : Address[] | string[];
+        cursor? : IAddressHistoryCursor;
+        limit : number;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionsByAddressesRequest, GetTransactionsByAddressesRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionsByAddressesResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IAddressTransaction {
+        address : Address;
+        transactionId : HexString;
+        acceptingBlockHash : HexString;
+        acceptingBlockDaaScore : bigint;
+        receivedAmount : bigint;
+        spentAmount : bigint;
+    }
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionsByAddressesResponse {
+        entries : IAddressTransaction[];
+        nextCursor? : IAddressHistoryCursor;
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionsByAddressesResponse, IGetTransactionsByAddressesResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetChainBlockByDaaScoreRequest,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetChainBlockByDaaScoreRequest {
+        daaScore : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: IGetChainBlockByDaaScoreRequest, GetChainBlockByDaaScoreRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetChainBlockByDaaScoreResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetChainBlockByDaaScoreResponse {
+        header : IHeader;
+    }
+    "#,
+}
+
+try_from! ( args: GetChainBlockByDaaScoreResponse, IGetChainBlockByDaaScoreResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetChainBlocksInBlueScoreRangeRequest,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetChainBlocksInBlueScoreRangeRequest {
+        lowBlueScore : bigint;
+        highBlueScore : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: IGetChainBlocksInBlueScoreRangeRequest, GetChainBlocksInBlueScoreRangeRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetChainBlocksInBlueScoreRangeResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetChainBlocksInBlueScoreRangeResponse {
+        headers : IHeader[];
+        nextLowBlueScore? : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: GetChainBlocksInBlueScoreRangeResponse, IGetChainBlocksInBlueScoreRangeResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IGetDaaScoreTimestampEstimateRequest,
     r#"
@@ -1016,13 +1241,21 @@ declare! {
     IGetMempoolEntriesRequest,
     r#"
     /**
-     * 
+     * Requests a page of the mempool entries. Orphans only are requested
+     * by setting both `includeOrphanPool` and `filterTransactionPool`.
      * 
      * @category Node RPC
      */
     export interface IGetMempoolEntriesRequest {
         includeOrphanPool? : boolean;
         filterTransactionPool? : boolean;
+        offset? : bigint;
+        /** Maximum number of returned entries, 0 meaning no limit */
+        limit? : bigint;
+        sortBy? : MempoolEntrySortKey;
+        sortDescending? : boolean;
+        minFeerate? : number;
+        payloadOnly? : boolean;
     }
     "#,
 }
@@ -1041,6 +1274,10 @@ declare! {
      */
     export interface IGetMempoolEntriesResponse {
         mempoolEntries : IMempoolEntry[];
+        /** Totals over all the entries matching the request filters */
+        totalCount : bigint;
+        totalMass : bigint;
+        totalFees : bigint;
     }
     "#,
 }
@@ -1426,6 +1663,118 @@ try_from! ( args: SubmitTransactionReplacementResponse, ISubmitTransactionReplac
 
 // ---
 
+declare! {
+    ISubmitTransactionPackageRequest,
+    r#"
+    /**
+     * Submit an ordered package of transactions to the node.
+     * 
+     * @category Node RPC
+     */
+    export interface ISubmitTransactionPackageRequest {
+        transactions : Transaction[],
+    }
+    "#,
+}
+
+try_from! ( args: ISubmitTransactionPackageRequest, SubmitTransactionPackageRequest, {
+    let transactions = args
+        .try_get_value("transactions")?
+        .ok_or_else(|| Error::MissingRpcFieldError("ISubmitTransactionPackageRequest".to_string(), "transactions".to_string()))?;
+
+    let transactions = js_sys::Array::from(&transactions)
+        .iter()
+        .map(|transaction| {
+            if let Ok(transaction) = Transaction::try_owned_from(&transaction) {
+                Ok(transaction.into())
+            } else {
+                let tx = Transaction::try_cast_from(&transaction)?;
+                Ok(tx.as_ref().into())
+            }
+        })
+        .collect::<Result<Vec<RpcTransaction>>>()?;
+    Ok(SubmitTransactionPackageRequest { transactions })
+});
+
+declare! {
+    ISubmitTransactionPackageResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface ISubmitTransactionPackageResponse {
+        transactionIds : HexString[];
+        rejections : ITransactionRejection[];
+    }
+    "#,
+}
+
+try_from! ( args: SubmitTransactionPackageResponse, ISubmitTransactionPackageResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    ITestMempoolAcceptRequest,
+    r#"
+    /**
+     * Test whether transactions would be accepted to the mempool,
+     * without adding them to it nor relaying them.
+     * 
+     * @category Node RPC
+     */
+    export interface ITestMempoolAcceptRequest {
+        transactions : Transaction[],
+        allowOrphan? : boolean,
+        replaceByFee? : boolean
+    }
+    "#,
+}
+
+try_from! ( args: ITestMempoolAcceptRequest, TestMempoolAcceptRequest, {
+    let transactions = args
+        .try_get_value("transactions")?
+        .ok_or_else(|| Error::MissingRpcFieldError("ITestMempoolAcceptRequest".to_string(), "transactions".to_string()))?;
+
+    let transactions = js_sys::Array::from(&transactions)
+        .iter()
+        .map(|transaction| {
+            if let Ok(transaction) = Transaction::try_owned_from(&transaction) {
+                Ok(transaction.into())
+            } else {
+                let tx = Transaction::try_cast_from(&transaction)?;
+                Ok(tx.as_ref().into())
+            }
+        })
+        .collect::<Result<Vec<RpcTransaction>>>()?;
+    let allow_orphan = args.try_get_bool("allowOrphan")?.unwrap_or(false);
+    let replace_by_fee = args.try_get_bool("replaceByFee")?.unwrap_or(false);
+    Ok(TestMempoolAcceptRequest { transactions, allow_orphan, replace_by_fee })
+});
+
+declare! {
+    ITestMempoolAcceptResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface ITestMempoolAcceptResponse {
+        acceptances : ITransactionAcceptance[];
+    }
+    "#,
+}
+
+try_from! ( args: TestMempoolAcceptResponse, ITestMempoolAcceptResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     ISubmitTransactionRequest,
     // "ISubmitTransactionRequest | Transaction",
@@ -1524,6 +1873,46 @@ try_from! ( args: UnbanResponse, IUnbanResponse, {
 
 // ---
 
+declare! {
+    IListBannedRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IListBannedRequest { }
+    "#,
+}
+
+try_from! ( args: IListBannedRequest, ListBannedRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IListBannedResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IListBannedResponse {
+        bannedAddresses : {
+            ip : string;
+            /**
+             * Unix time, in milliseconds, the ban expires at.
+             */
+            bannedUntil : bigint;
+        }[];
+    }
+    "#,
+}
+
+try_from! ( args: ListBannedResponse, IListBannedResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IFeerateBucket,
     r#"
@@ -1728,3 +2117,126 @@ try_from!( args: GetFeeEstimateExperimentalResponse, IGetFeeEstimateExperimental
 });
 
 // ---
+
+declare! {
+    IHistoricalFeeEstimate,
+    r#"
+    /**
+     * Feerate estimation based on the observed confirmation times of recent transactions.
+     * 
+     * @category Node RPC
+     */
+    export interface IHistoricalFeeEstimate {
+        windowSeconds : bigint;
+        blockCount : bigint;
+        sampleCount : bigint;
+        /**
+         * Buckets ordered by target time then by confidence, each providing the lowest feerate at which
+         * at least `confidence` of the observed transactions were included within `targetSeconds`.
+         */
+        buckets : IHistoricalFeerateBucket[];
+    }
+
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IHistoricalFeerateBucket {
+        feerate : number;
+        targetSeconds : number;
+        confidence : number;
+    }
+    "#,
+}
+
+try_from!( estimate: RpcHistoricalFeeEstimate, IHistoricalFeeEstimate, {
+    Ok(to_value(&estimate)?.into())
+});
+
+declare! {
+    IGetFeeEstimateHistoricalRequest,
+    r#"
+    /**
+     * Get historical fee estimate from the node.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetFeeEstimateHistoricalRequest {
+        /**
+         * Duration of the history to estimate from, the whole history kept by the node if 0 or omitted.
+         */
+        windowSeconds? : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: IGetFeeEstimateHistoricalRequest, GetFeeEstimateHistoricalRequest, {
+    let window_seconds = args.get_u64("windowSeconds").unwrap_or_default();
+    Ok(GetFeeEstimateHistoricalRequest { window_seconds })
+});
+
+declare! {
+    IGetFeeEstimateHistoricalResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetFeeEstimateHistoricalResponse {
+        estimate : IHistoricalFeeEstimate;
+    }
+    "#,
+}
+
+try_from!( args: GetFeeEstimateHistoricalResponse, IGetFeeEstimateHistoricalResponse, {
+    let estimate = IHistoricalFeeEstimate::try_from(args.estimate)?;
+    let response = IGetFeeEstimateHistoricalResponse::default();
+    response.set("estimate", &estimate)?;
+    Ok(response)
+});
+
+// ---
+
+declare! {
+    IPrioritiseTransactionRequest,
+    r#"
+    /**
+     * Adjust the fee a transaction is ranked by when selecting block template transactions.
+     * 
+     * @category Node RPC
+     */
+    export interface IPrioritiseTransactionRequest {
+        transactionId : HexString;
+        /**
+         * Amount in sompi, possibly negative, added to the current fee delta of the transaction.
+         */
+        feeDelta : bigint | number;
+    }
+    "#,
+}
+
+try_from! ( args: IPrioritiseTransactionRequest, PrioritiseTransactionRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IPrioritiseTransactionResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IPrioritiseTransactionResponse {
+        feeDelta : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: PrioritiseTransactionResponse, IPrioritiseTransactionResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
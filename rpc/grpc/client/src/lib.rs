@@ -276,6 +276,16 @@ impl RpcApi for GrpcClient {
     route!(get_fee_estimate_call, GetFeeEstimate);
     route!(get_fee_estimate_experimental_call, GetFeeEstimateExperimental);
     route!(get_current_block_color_call, GetCurrentBlockColor);
+    route!(get_transaction_call, GetTransaction);
+    route!(get_transactions_by_ids_call, GetTransactionsByIds);
+    route!(get_transactions_by_addresses_call, GetTransactionsByAddresses);
+    route!(get_chain_block_by_daa_score_call, GetChainBlockByDaaScore);
+    route!(get_chain_blocks_in_blue_score_range_call, GetChainBlocksInBlueScoreRange);
+    route!(submit_transaction_package_call, SubmitTransactionPackage);
+    route!(test_mempool_accept_call, TestMempoolAccept);
+    route!(get_fee_estimate_historical_call, GetFeeEstimateHistorical);
+    route!(prioritise_transaction_call, PrioritiseTransaction);
+    route!(list_banned_call, ListBanned);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
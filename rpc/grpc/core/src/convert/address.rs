@@ -1,6 +1,7 @@
 use crate::protowire;
 use crate::{from, try_from};
-use vecno_rpc_core::RpcError;
+use std::str::FromStr;
+use vecno_rpc_core::{RpcError, RpcHash};
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -10,6 +11,21 @@ from!(item: &vecno_rpc_core::RpcBalancesByAddressesEntry, protowire::RpcBalances
     Self { address: (&item.address).into(), balance: item.balance.unwrap_or_default(), error: None }
 });
 
+from!(item: &vecno_rpc_core::RpcAddressHistoryCursor, protowire::RpcAddressHistoryCursor, {
+    Self { accepting_block_daa_score: item.accepting_block_daa_score, transaction_id: item.transaction_id.to_string() }
+});
+
+from!(item: &vecno_rpc_core::RpcAddressTransaction, protowire::RpcAddressTransaction, {
+    Self {
+        address: (&item.address).into(),
+        transaction_id: item.transaction_id.to_string(),
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        accepting_block_daa_score: item.accepting_block_daa_score,
+        received_amount: item.received_amount,
+        spent_amount: item.spent_amount,
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -18,3 +34,18 @@ try_from!(item: &protowire::RpcBalancesByAddressesEntry, vecno_rpc_core::RpcBala
     let balance = if item.error.is_some() { None } else { Some(item.balance) };
     Self { address: item.address.as_str().try_into()?, balance }
 });
+
+try_from!(item: &protowire::RpcAddressHistoryCursor, vecno_rpc_core::RpcAddressHistoryCursor, {
+    Self { accepting_block_daa_score: item.accepting_block_daa_score, transaction_id: RpcHash::from_str(&item.transaction_id)? }
+});
+
+try_from!(item: &protowire::RpcAddressTransaction, vecno_rpc_core::RpcAddressTransaction, {
+    Self {
+        address: item.address.as_str().try_into()?,
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+        accepting_block_daa_score: item.accepting_block_daa_score,
+        received_amount: item.received_amount,
+        spent_amount: item.spent_amount,
+    }
+});
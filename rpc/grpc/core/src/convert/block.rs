@@ -1,7 +1,7 @@
 use crate::protowire;
 use crate::{from, try_from};
 use std::str::FromStr;
-use vecno_rpc_core::{RpcError, RpcHash};
+use vecno_rpc_core::{RpcError, RpcHash, RpcTemplateSelectionMode};
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -38,6 +38,22 @@ from!(item: &vecno_rpc_core::RpcBlockVerboseData, protowire::RpcBlockVerboseData
     }
 });
 
+from!(item: RpcTemplateSelectionMode, protowire::RpcTemplateSelectionMode, {
+    match item {
+        RpcTemplateSelectionMode::Weighted => protowire::RpcTemplateSelectionMode::Weighted,
+        RpcTemplateSelectionMode::HighestFeerate => protowire::RpcTemplateSelectionMode::HighestFeerate,
+    }
+});
+
+from!(item: &vecno_rpc_core::RpcTemplatePolicy, protowire::RpcTemplatePolicy, {
+    Self {
+        selection_mode: protowire::RpcTemplateSelectionMode::from(item.selection_mode) as i32,
+        prioritised_mass_share: item.prioritised_mass_share,
+        prioritised_transaction_ids: item.prioritised_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        excluded_transaction_ids: item.excluded_transaction_ids.iter().map(|x| x.to_string()).collect(),
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -95,3 +111,29 @@ try_from!(item: &protowire::RpcBlockVerboseData, vecno_rpc_core::RpcBlockVerbose
         is_chain_block: item.is_chain_block,
     }
 });
+
+from!(item: protowire::RpcTemplateSelectionMode, RpcTemplateSelectionMode, {
+    match item {
+        protowire::RpcTemplateSelectionMode::Weighted => RpcTemplateSelectionMode::Weighted,
+        protowire::RpcTemplateSelectionMode::HighestFeerate => RpcTemplateSelectionMode::HighestFeerate,
+    }
+});
+
+try_from!(item: &protowire::RpcTemplatePolicy, vecno_rpc_core::RpcTemplatePolicy, {
+    Self {
+        selection_mode: protowire::RpcTemplateSelectionMode::try_from(item.selection_mode)
+            .map_err(|_| RpcError::PrimitiveToEnumConversionError)?
+            .into(),
+        prioritised_mass_share: item.prioritised_mass_share,
+        prioritised_transaction_ids: item
+            .prioritised_transaction_ids
+            .iter()
+            .map(|x| RpcHash::from_str(x))
+            .collect::<Result<Vec<vecno_rpc_core::RpcHash>, faster_hex::Error>>()?,
+        excluded_transaction_ids: item
+            .excluded_transaction_ids
+            .iter()
+            .map(|x| RpcHash::from_str(x))
+            .collect::<Result<Vec<vecno_rpc_core::RpcHash>, faster_hex::Error>>()?,
+    }
+});
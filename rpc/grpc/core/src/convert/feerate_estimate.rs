@@ -32,6 +32,23 @@ from!(item: &vecno_rpc_core::RpcFeeEstimateVerboseExperimentalData, protowire::R
     }
 });
 
+from!(item: &vecno_rpc_core::RpcHistoricalFeerateBucket, protowire::RpcHistoricalFeerateBucket, {
+    Self {
+        feerate: item.feerate,
+        target_seconds: item.target_seconds,
+        confidence: item.confidence,
+    }
+});
+
+from!(item: &vecno_rpc_core::RpcHistoricalFeeEstimate, protowire::RpcHistoricalFeeEstimate, {
+    Self {
+        window_seconds: item.window_seconds,
+        block_count: item.block_count,
+        sample_count: item.sample_count,
+        buckets: item.buckets.iter().map(|b| b.into()).collect(),
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -64,3 +81,20 @@ try_from!(item: &protowire::RpcFeeEstimateVerboseExperimentalData, vecno_rpc_cor
         next_block_template_feerate_max: item.next_block_template_feerate_max,
     }
 });
+
+try_from!(item: &protowire::RpcHistoricalFeerateBucket, vecno_rpc_core::RpcHistoricalFeerateBucket, {
+    Self {
+        feerate: item.feerate,
+        target_seconds: item.target_seconds,
+        confidence: item.confidence,
+    }
+});
+
+try_from!(item: &protowire::RpcHistoricalFeeEstimate, vecno_rpc_core::RpcHistoricalFeeEstimate, {
+    Self {
+        window_seconds: item.window_seconds,
+        block_count: item.block_count,
+        sample_count: item.sample_count,
+        buckets: item.buckets.iter().map(|b| b.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
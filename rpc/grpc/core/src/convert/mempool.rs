@@ -1,6 +1,6 @@
 use crate::protowire;
 use crate::{from, try_from};
-use vecno_rpc_core::RpcError;
+use vecno_rpc_core::{RpcError, RpcMempoolEntrySortKey};
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -18,10 +18,28 @@ from!(item: &vecno_rpc_core::RpcMempoolEntryByAddress, protowire::RpcMempoolEntr
     }
 });
 
+from!(item: RpcMempoolEntrySortKey, protowire::RpcMempoolEntrySortKey, {
+    match item {
+        RpcMempoolEntrySortKey::TransactionId => protowire::RpcMempoolEntrySortKey::TransactionId,
+        RpcMempoolEntrySortKey::Feerate => protowire::RpcMempoolEntrySortKey::Feerate,
+        RpcMempoolEntrySortKey::ArrivalTime => protowire::RpcMempoolEntrySortKey::ArrivalTime,
+        RpcMempoolEntrySortKey::Mass => protowire::RpcMempoolEntrySortKey::Mass,
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
 
+from!(item: protowire::RpcMempoolEntrySortKey, RpcMempoolEntrySortKey, {
+    match item {
+        protowire::RpcMempoolEntrySortKey::TransactionId => RpcMempoolEntrySortKey::TransactionId,
+        protowire::RpcMempoolEntrySortKey::Feerate => RpcMempoolEntrySortKey::Feerate,
+        protowire::RpcMempoolEntrySortKey::ArrivalTime => RpcMempoolEntrySortKey::ArrivalTime,
+        protowire::RpcMempoolEntrySortKey::Mass => RpcMempoolEntrySortKey::Mass,
+    }
+});
+
 try_from!(item: &protowire::RpcMempoolEntry, vecno_rpc_core::RpcMempoolEntry, {
     Self::new(
         item.fee,
@@ -156,6 +156,7 @@ from!(item: &vecno_rpc_core::GetBlockTemplateRequest, protowire::GetBlockTemplat
     Self {
         pay_address: (&item.pay_address).into(),
         extra_data: String::from_utf8(item.extra_data.clone()).expect("extra data has to be valid UTF-8"),
+        template_policy: item.template_policy.as_ref().map(|x| x.into()),
     }
 });
 from!(item: RpcResult<&vecno_rpc_core::GetBlockTemplateResponse>, protowire::GetBlockTemplateResponseMessage, {
@@ -226,10 +227,25 @@ from!(item: RpcResult<&vecno_rpc_core::GetMempoolEntryResponse>, protowire::GetM
 });
 
 from!(item: &vecno_rpc_core::GetMempoolEntriesRequest, protowire::GetMempoolEntriesRequestMessage, {
-    Self { include_orphan_pool: item.include_orphan_pool, filter_transaction_pool: item.filter_transaction_pool }
+    Self {
+        include_orphan_pool: item.include_orphan_pool,
+        filter_transaction_pool: item.filter_transaction_pool,
+        offset: item.offset,
+        limit: item.limit,
+        sort_by: protowire::RpcMempoolEntrySortKey::from(item.sort_by) as i32,
+        sort_descending: item.sort_descending,
+        min_feerate: item.min_feerate,
+        payload_only: item.payload_only,
+    }
 });
 from!(item: RpcResult<&vecno_rpc_core::GetMempoolEntriesResponse>, protowire::GetMempoolEntriesResponseMessage, {
-    Self { entries: item.mempool_entries.iter().map(|x| x.into()).collect(), error: None }
+    Self {
+        entries: item.mempool_entries.iter().map(|x| x.into()).collect(),
+        total_count: item.total_count,
+        total_mass: item.total_mass,
+        total_fees: item.total_fees,
+        error: None,
+    }
 });
 
 from!(&vecno_rpc_core::GetConnectedPeerInfoRequest, protowire::GetConnectedPeerInfoRequestMessage);
@@ -256,6 +272,28 @@ from!(item: RpcResult<&vecno_rpc_core::SubmitTransactionReplacementResponse>, pr
     Self { transaction_id: item.transaction_id.to_string(), replaced_transaction: Some((&item.replaced_transaction).into()), error: None }
 });
 
+from!(item: &vecno_rpc_core::SubmitTransactionPackageRequest, protowire::SubmitTransactionPackageRequestMessage, {
+    Self { transactions: item.transactions.iter().map(|x| x.into()).collect() }
+});
+from!(item: RpcResult<&vecno_rpc_core::SubmitTransactionPackageResponse>, protowire::SubmitTransactionPackageResponseMessage, {
+    Self {
+        transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect(),
+        rejections: item.rejections.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
+});
+
+from!(item: &vecno_rpc_core::TestMempoolAcceptRequest, protowire::TestMempoolAcceptRequestMessage, {
+    Self {
+        transactions: item.transactions.iter().map(|x| x.into()).collect(),
+        allow_orphan: item.allow_orphan,
+        replace_by_fee: item.replace_by_fee,
+    }
+});
+from!(item: RpcResult<&vecno_rpc_core::TestMempoolAcceptResponse>, protowire::TestMempoolAcceptResponseMessage, {
+    Self { acceptances: item.acceptances.iter().map(|x| x.into()).collect(), error: None }
+});
+
 from!(item: &vecno_rpc_core::GetSubnetworkRequest, protowire::GetSubnetworkRequestMessage, {
     Self { subnetwork_id: item.subnetwork_id.to_string() }
 });
@@ -366,6 +404,11 @@ from!(_item: RpcResult<&vecno_rpc_core::BanResponse>, protowire::BanResponseMess
 from!(item: &vecno_rpc_core::UnbanRequest, protowire::UnbanRequestMessage, { Self { ip: item.ip.to_string() } });
 from!(_item: RpcResult<&vecno_rpc_core::UnbanResponse>, protowire::UnbanResponseMessage, { Self { error: None } });
 
+from!(&vecno_rpc_core::ListBannedRequest, protowire::ListBannedRequestMessage);
+from!(item: RpcResult<&vecno_rpc_core::ListBannedResponse>, protowire::ListBannedResponseMessage, {
+    Self { banned_addresses: item.banned_addresses.iter().map(|x| x.into()).collect(), error: None }
+});
+
 from!(item: &vecno_rpc_core::EstimateNetworkHashesPerSecondRequest, protowire::EstimateNetworkHashesPerSecondRequestMessage, {
     Self { window_size: item.window_size, start_hash: item.start_hash.map_or(Default::default(), |x| x.to_string()) }
 });
@@ -420,6 +463,19 @@ from!(item: RpcResult<&vecno_rpc_core::GetFeeEstimateExperimentalResponse>, prot
         error: None
     }
 });
+from!(item: &vecno_rpc_core::GetFeeEstimateHistoricalRequest, protowire::GetFeeEstimateHistoricalRequestMessage, {
+    Self { window_seconds: item.window_seconds }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetFeeEstimateHistoricalResponse>, protowire::GetFeeEstimateHistoricalResponseMessage, {
+    Self { estimate: Some((&item.estimate).into()), error: None }
+});
+
+from!(item: &vecno_rpc_core::PrioritiseTransactionRequest, protowire::PrioritiseTransactionRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string(), fee_delta: item.fee_delta }
+});
+from!(item: RpcResult<&vecno_rpc_core::PrioritiseTransactionResponse>, protowire::PrioritiseTransactionResponseMessage, {
+    Self { fee_delta: item.fee_delta, error: None }
+});
 
 from!(item: &vecno_rpc_core::GetCurrentBlockColorRequest, protowire::GetCurrentBlockColorRequestMessage, {
     Self {
@@ -430,6 +486,60 @@ from!(item: RpcResult<&vecno_rpc_core::GetCurrentBlockColorResponse>, protowire:
     Self { blue: item.blue, error: None }
 });
 
+// Transaction index API
+
+from!(item: &vecno_rpc_core::GetTransactionRequest, protowire::GetTransactionRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string(), include_transaction: item.include_transaction }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetTransactionResponse>, protowire::GetTransactionResponseMessage, {
+    Self { transaction: Some((&item.transaction).into()), error: None }
+});
+from!(item: &vecno_rpc_core::GetTransactionsByIdsRequest, protowire::GetTransactionsByIdsRequestMessage, {
+    Self {
+        transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect(),
+        include_transactions: item.include_transactions,
+    }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetTransactionsByIdsResponse>, protowire::GetTransactionsByIdsResponseMessage, {
+    Self { transactions: item.transactions.iter().map(|x| x.into()).collect(), error: None }
+});
+
+// Address index API
+
+from!(item: &vecno_rpc_core::GetTransactionsByAddressesRequest, protowire::GetTransactionsByAddressesRequestMessage, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.into()).collect(),
+        cursor: item.cursor.as_ref().map(|x| x.into()),
+        limit: item.limit,
+    }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetTransactionsByAddressesResponse>, protowire::GetTransactionsByAddressesResponseMessage, {
+    Self {
+        entries: item.entries.iter().map(|x| x.into()).collect(),
+        next_cursor: item.next_cursor.as_ref().map(|x| x.into()),
+        error: None,
+    }
+});
+
+// Selected chain index API
+
+from!(item: &vecno_rpc_core::GetChainBlockByDaaScoreRequest, protowire::GetChainBlockByDaaScoreRequestMessage, {
+    Self { daa_score: item.daa_score }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetChainBlockByDaaScoreResponse>, protowire::GetChainBlockByDaaScoreResponseMessage, {
+    Self { header: Some((&item.header).into()), error: None }
+});
+from!(item: &vecno_rpc_core::GetChainBlocksInBlueScoreRangeRequest, protowire::GetChainBlocksInBlueScoreRangeRequestMessage, {
+    Self { low_blue_score: item.low_blue_score, high_blue_score: item.high_blue_score }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetChainBlocksInBlueScoreRangeResponse>, protowire::GetChainBlocksInBlueScoreRangeResponseMessage, {
+    Self {
+        headers: item.headers.iter().map(|x| x.into()).collect(),
+        next_low_blue_score: item.next_low_blue_score.unwrap_or_default(),
+        error: None,
+    }
+});
+
 from!(&vecno_rpc_core::PingRequest, protowire::PingRequestMessage);
 from!(RpcResult<&vecno_rpc_core::PingResponse>, protowire::PingResponseMessage);
 
@@ -441,6 +551,7 @@ from!(item: &vecno_rpc_core::GetMetricsRequest, protowire::GetMetricsRequestMess
         consensus_metrics: item.consensus_metrics,
         storage_metrics: item.storage_metrics,
         custom_metrics: item.custom_metrics,
+        stratum_metrics: item.stratum_metrics,
     }
 });
 from!(item: RpcResult<&vecno_rpc_core::GetMetricsResponse>, protowire::GetMetricsResponseMessage, {
@@ -451,6 +562,7 @@ from!(item: RpcResult<&vecno_rpc_core::GetMetricsResponse>, protowire::GetMetric
         bandwidth_metrics: item.bandwidth_metrics.as_ref().map(|x| x.into()),
         consensus_metrics: item.consensus_metrics.as_ref().map(|x| x.into()),
         storage_metrics: item.storage_metrics.as_ref().map(|x| x.into()),
+        stratum_metrics: item.stratum_metrics.as_ref().map(|x| x.into()),
         // TODO
         // custom_metrics : None,
         error: None,
@@ -516,6 +628,11 @@ from!(item: &vecno_rpc_core::NotifyUtxosChangedRequest, protowire::StopNotifying
 from!(RpcResult<&vecno_rpc_core::NotifyUtxosChangedResponse>, protowire::NotifyUtxosChangedResponseMessage);
 from!(RpcResult<&vecno_rpc_core::NotifyUtxosChangedResponse>, protowire::StopNotifyingUtxosChangedResponseMessage);
 
+from!(item: &vecno_rpc_core::NotifyMempoolTransactionRemovedRequest, protowire::NotifyMempoolTransactionRemovedRequestMessage, {
+    Self { addresses: item.addresses.iter().map(|x| x.into()).collect(), command: item.command.into() }
+});
+from!(RpcResult<&vecno_rpc_core::NotifyMempoolTransactionRemovedResponse>, protowire::NotifyMempoolTransactionRemovedResponseMessage);
+
 from!(item: &vecno_rpc_core::NotifyPruningPointUtxoSetOverrideRequest, protowire::NotifyPruningPointUtxoSetOverrideRequestMessage, {
     Self { command: item.command.into() }
 });
@@ -540,7 +657,11 @@ from!(item: &vecno_rpc_core::NotifyVirtualDaaScoreChangedRequest, protowire::Not
 from!(RpcResult<&vecno_rpc_core::NotifyVirtualDaaScoreChangedResponse>, protowire::NotifyVirtualDaaScoreChangedResponseMessage);
 
 from!(item: &vecno_rpc_core::NotifyVirtualChainChangedRequest, protowire::NotifyVirtualChainChangedRequestMessage, {
-    Self { include_accepted_transaction_ids: item.include_accepted_transaction_ids, command: item.command.into() }
+    Self {
+        include_accepted_transaction_ids: item.include_accepted_transaction_ids,
+        start_hash: item.start_hash.map(|x| x.to_string()).unwrap_or_default(),
+        command: item.command.into(),
+    }
 });
 from!(RpcResult<&vecno_rpc_core::NotifyVirtualChainChangedResponse>, protowire::NotifyVirtualChainChangedResponseMessage);
 
@@ -598,7 +719,11 @@ impl TryFrom<&protowire::SubmitBlockResponseMessage> for vecno_rpc_core::SubmitB
 }
 
 try_from!(item: &protowire::GetBlockTemplateRequestMessage, vecno_rpc_core::GetBlockTemplateRequest, {
-    Self { pay_address: item.pay_address.clone().try_into()?, extra_data: RpcExtraData::from_iter(item.extra_data.bytes()) }
+    Self {
+        pay_address: item.pay_address.clone().try_into()?,
+        extra_data: RpcExtraData::from_iter(item.extra_data.bytes()),
+        template_policy: item.template_policy.as_ref().map(vecno_rpc_core::RpcTemplatePolicy::try_from).transpose()?,
+    }
 });
 try_from!(item: &protowire::GetBlockTemplateResponseMessage, RpcResult<vecno_rpc_core::GetBlockTemplateResponse>, {
     Self {
@@ -688,10 +813,26 @@ try_from!(item: &protowire::GetMempoolEntryResponseMessage, RpcResult<vecno_rpc_
 });
 
 try_from!(item: &protowire::GetMempoolEntriesRequestMessage, vecno_rpc_core::GetMempoolEntriesRequest, {
-    Self { include_orphan_pool: item.include_orphan_pool, filter_transaction_pool: item.filter_transaction_pool }
+    Self {
+        include_orphan_pool: item.include_orphan_pool,
+        filter_transaction_pool: item.filter_transaction_pool,
+        offset: item.offset,
+        limit: item.limit,
+        sort_by: protowire::RpcMempoolEntrySortKey::try_from(item.sort_by)
+            .map_err(|_| RpcError::PrimitiveToEnumConversionError)?
+            .into(),
+        sort_descending: item.sort_descending,
+        min_feerate: item.min_feerate,
+        payload_only: item.payload_only,
+    }
 });
 try_from!(item: &protowire::GetMempoolEntriesResponseMessage, RpcResult<vecno_rpc_core::GetMempoolEntriesResponse>, {
-    Self { mempool_entries: item.entries.iter().map(vecno_rpc_core::RpcMempoolEntry::try_from).collect::<Result<Vec<_>, _>>()? }
+    Self {
+        mempool_entries: item.entries.iter().map(vecno_rpc_core::RpcMempoolEntry::try_from).collect::<Result<Vec<_>, _>>()?,
+        total_count: item.total_count,
+        total_mass: item.total_mass,
+        total_fees: item.total_fees,
+    }
 });
 
 try_from!(&protowire::GetConnectedPeerInfoRequestMessage, vecno_rpc_core::GetConnectedPeerInfoRequest);
@@ -738,6 +879,27 @@ try_from!(item: &protowire::SubmitTransactionReplacementResponseMessage, RpcResu
     }
 });
 
+try_from!(item: &protowire::SubmitTransactionPackageRequestMessage, vecno_rpc_core::SubmitTransactionPackageRequest, {
+    Self { transactions: item.transactions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+try_from!(item: &protowire::SubmitTransactionPackageResponseMessage, RpcResult<vecno_rpc_core::SubmitTransactionPackageResponse>, {
+    Self {
+        transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        rejections: item.rejections.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::TestMempoolAcceptRequestMessage, vecno_rpc_core::TestMempoolAcceptRequest, {
+    Self {
+        transactions: item.transactions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        allow_orphan: item.allow_orphan,
+        replace_by_fee: item.replace_by_fee,
+    }
+});
+try_from!(item: &protowire::TestMempoolAcceptResponseMessage, RpcResult<vecno_rpc_core::TestMempoolAcceptResponse>, {
+    Self { acceptances: item.acceptances.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
 try_from!(item: &protowire::GetSubnetworkRequestMessage, vecno_rpc_core::GetSubnetworkRequest, {
     Self { subnetwork_id: vecno_rpc_core::RpcSubnetworkId::from_str(&item.subnetwork_id)? }
 });
@@ -843,6 +1005,11 @@ try_from!(&protowire::BanResponseMessage, RpcResult<vecno_rpc_core::BanResponse>
 try_from!(item: &protowire::UnbanRequestMessage, vecno_rpc_core::UnbanRequest, { Self { ip: RpcIpAddress::from_str(&item.ip)? } });
 try_from!(&protowire::UnbanResponseMessage, RpcResult<vecno_rpc_core::UnbanResponse>);
 
+try_from!(&protowire::ListBannedRequestMessage, vecno_rpc_core::ListBannedRequest);
+try_from!(item: &protowire::ListBannedResponseMessage, RpcResult<vecno_rpc_core::ListBannedResponse>, {
+    Self { banned_addresses: item.banned_addresses.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
 try_from!(item: &protowire::EstimateNetworkHashesPerSecondRequestMessage, vecno_rpc_core::EstimateNetworkHashesPerSecondRequest, {
     Self {
         window_size: item.window_size,
@@ -905,6 +1072,24 @@ try_from!(item: &protowire::GetFeeEstimateExperimentalResponseMessage, RpcResult
         verbose: item.verbose.as_ref().map(|x| x.try_into()).transpose()?
     }
 });
+try_from!(item: &protowire::GetFeeEstimateHistoricalRequestMessage, vecno_rpc_core::GetFeeEstimateHistoricalRequest, {
+    Self { window_seconds: item.window_seconds }
+});
+try_from!(item: &protowire::GetFeeEstimateHistoricalResponseMessage, RpcResult<vecno_rpc_core::GetFeeEstimateHistoricalResponse>, {
+    Self {
+        estimate: item.estimate
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetFeeEstimateHistoricalResponseMessage".to_string(), "estimate".to_string()))?
+            .try_into()?
+    }
+});
+
+try_from!(item: &protowire::PrioritiseTransactionRequestMessage, vecno_rpc_core::PrioritiseTransactionRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)?, fee_delta: item.fee_delta }
+});
+try_from!(item: &protowire::PrioritiseTransactionResponseMessage, RpcResult<vecno_rpc_core::PrioritiseTransactionResponse>, {
+    Self { fee_delta: item.fee_delta }
+});
 
 try_from!(item: &protowire::GetCurrentBlockColorRequestMessage, vecno_rpc_core::GetCurrentBlockColorRequest, {
     Self {
@@ -917,6 +1102,64 @@ try_from!(item: &protowire::GetCurrentBlockColorResponseMessage, RpcResult<vecno
     }
 });
 
+try_from!(item: &protowire::GetTransactionRequestMessage, vecno_rpc_core::GetTransactionRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)?, include_transaction: item.include_transaction }
+});
+try_from!(item: &protowire::GetTransactionResponseMessage, RpcResult<vecno_rpc_core::GetTransactionResponse>, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetTransactionResponseMessage".to_string(), "transaction".to_string()))?
+            .try_into()?,
+    }
+});
+try_from!(item: &protowire::GetTransactionsByIdsRequestMessage, vecno_rpc_core::GetTransactionsByIdsRequest, {
+    Self {
+        transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        include_transactions: item.include_transactions,
+    }
+});
+try_from!(item: &protowire::GetTransactionsByIdsResponseMessage, RpcResult<vecno_rpc_core::GetTransactionsByIdsResponse>, {
+    Self { transactions: item.transactions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::GetTransactionsByAddressesRequestMessage, vecno_rpc_core::GetTransactionsByAddressesRequest, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        cursor: item.cursor.as_ref().map(|x| x.try_into()).transpose()?,
+        limit: item.limit,
+    }
+});
+try_from!(item: &protowire::GetTransactionsByAddressesResponseMessage, RpcResult<vecno_rpc_core::GetTransactionsByAddressesResponse>, {
+    Self {
+        entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_cursor: item.next_cursor.as_ref().map(|x| x.try_into()).transpose()?,
+    }
+});
+
+try_from!(item: &protowire::GetChainBlockByDaaScoreRequestMessage, vecno_rpc_core::GetChainBlockByDaaScoreRequest, {
+    Self { daa_score: item.daa_score }
+});
+try_from!(item: &protowire::GetChainBlockByDaaScoreResponseMessage, RpcResult<vecno_rpc_core::GetChainBlockByDaaScoreResponse>, {
+    Self {
+        header: item
+            .header
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetChainBlockByDaaScoreResponseMessage".to_string(), "header".to_string()))?
+            .try_into()?,
+    }
+});
+try_from!(item: &protowire::GetChainBlocksInBlueScoreRangeRequestMessage, vecno_rpc_core::GetChainBlocksInBlueScoreRangeRequest, {
+    Self { low_blue_score: item.low_blue_score, high_blue_score: item.high_blue_score }
+});
+try_from!(item: &protowire::GetChainBlocksInBlueScoreRangeResponseMessage, RpcResult<vecno_rpc_core::GetChainBlocksInBlueScoreRangeResponse>, {
+    Self {
+        headers: item.headers.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_low_blue_score: (item.next_low_blue_score > 0).then_some(item.next_low_blue_score),
+    }
+});
+
 try_from!(&protowire::PingRequestMessage, vecno_rpc_core::PingRequest);
 try_from!(&protowire::PingResponseMessage, RpcResult<vecno_rpc_core::PingResponse>);
 
@@ -928,6 +1171,7 @@ try_from!(item: &protowire::GetMetricsRequestMessage, vecno_rpc_core::GetMetrics
         consensus_metrics: item.consensus_metrics,
         storage_metrics: item.storage_metrics,
         custom_metrics : item.custom_metrics,
+        stratum_metrics: item.stratum_metrics,
     }
 });
 try_from!(item: &protowire::GetMetricsResponseMessage, RpcResult<vecno_rpc_core::GetMetricsResponse>, {
@@ -940,6 +1184,7 @@ try_from!(item: &protowire::GetMetricsResponseMessage, RpcResult<vecno_rpc_core:
         storage_metrics: item.storage_metrics.as_ref().map(|x| x.try_into()).transpose()?,
         // TODO
         custom_metrics: None,
+        stratum_metrics: item.stratum_metrics.as_ref().map(|x| x.try_into()).transpose()?,
     }
 });
 
@@ -1002,6 +1247,17 @@ try_from!(item: &protowire::StopNotifyingUtxosChangedRequestMessage, vecno_rpc_c
 try_from!(&protowire::NotifyUtxosChangedResponseMessage, RpcResult<vecno_rpc_core::NotifyUtxosChangedResponse>);
 try_from!(&protowire::StopNotifyingUtxosChangedResponseMessage, RpcResult<vecno_rpc_core::NotifyUtxosChangedResponse>);
 
+try_from!(item: &protowire::NotifyMempoolTransactionRemovedRequestMessage, vecno_rpc_core::NotifyMempoolTransactionRemovedRequest, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        command: item.command.into(),
+    }
+});
+try_from!(
+    &protowire::NotifyMempoolTransactionRemovedResponseMessage,
+    RpcResult<vecno_rpc_core::NotifyMempoolTransactionRemovedResponse>
+);
+
 try_from!(
     item: &protowire::NotifyPruningPointUtxoSetOverrideRequestMessage,
     vecno_rpc_core::NotifyPruningPointUtxoSetOverrideRequest,
@@ -1032,7 +1288,11 @@ try_from!(item: &protowire::NotifyVirtualDaaScoreChangedRequestMessage, vecno_rp
 try_from!(&protowire::NotifyVirtualDaaScoreChangedResponseMessage, RpcResult<vecno_rpc_core::NotifyVirtualDaaScoreChangedResponse>);
 
 try_from!(item: &protowire::NotifyVirtualChainChangedRequestMessage, vecno_rpc_core::NotifyVirtualChainChangedRequest, {
-    Self { include_accepted_transaction_ids: item.include_accepted_transaction_ids, command: item.command.into() }
+    Self {
+        include_accepted_transaction_ids: item.include_accepted_transaction_ids,
+        start_hash: if item.start_hash.is_empty() { None } else { Some(RpcHash::from_str(&item.start_hash)?) },
+        command: item.command.into(),
+    }
 });
 try_from!(&protowire::NotifyVirtualChainChangedResponseMessage, RpcResult<vecno_rpc_core::NotifyVirtualChainChangedResponse>);
 
@@ -80,6 +80,27 @@ from!(item: &vecno_rpc_core::StorageMetrics, protowire::StorageMetrics, {
     }
 });
 
+from!(item: &vecno_rpc_core::StratumWorkerMetrics, protowire::StratumWorkerMetrics, {
+    Self {
+        worker: item.worker.clone(),
+        remote_address: item.remote_address.clone(),
+        difficulty: item.difficulty,
+        hashrate: item.hashrate,
+        shares_accepted: item.shares_accepted,
+        shares_stale: item.shares_stale,
+        shares_rejected: item.shares_rejected,
+        blocks_found: item.blocks_found,
+        connected_since: item.connected_since,
+    }
+});
+
+from!(item: &vecno_rpc_core::StratumMetrics, protowire::StratumMetrics, {
+    Self {
+        hashrate: item.hashrate,
+        workers: item.workers.iter().map(|x| x.into()).collect(),
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -153,3 +174,24 @@ try_from!(item: &protowire::StorageMetrics, vecno_rpc_core::StorageMetrics, {
         storage_size_bytes: item.storage_size_bytes,
     }
 });
+
+try_from!(item: &protowire::StratumWorkerMetrics, vecno_rpc_core::StratumWorkerMetrics, {
+    Self {
+        worker: item.worker.clone(),
+        remote_address: item.remote_address.clone(),
+        difficulty: item.difficulty,
+        hashrate: item.hashrate,
+        shares_accepted: item.shares_accepted,
+        shares_stale: item.shares_stale,
+        shares_rejected: item.shares_rejected,
+        blocks_found: item.blocks_found,
+        connected_since: item.connected_since,
+    }
+});
+
+try_from!(item: &protowire::StratumMetrics, vecno_rpc_core::StratumMetrics, {
+    Self {
+        hashrate: item.hashrate,
+        workers: item.workers.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
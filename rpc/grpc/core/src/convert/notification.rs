@@ -1,19 +1,20 @@
 use crate::protowire::{
-    vecnod_response::Payload, BlockAddedNotificationMessage, NewBlockTemplateNotificationMessage, RpcNotifyCommand, VecnodResponse,
-};
-use crate::protowire::{
-    FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
+    self, FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage,
+    MempoolTransactionRemovedNotificationMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
     NotifyPruningPointUtxoSetOverrideResponseMessage, NotifyUtxosChangedRequestMessage, NotifyUtxosChangedResponseMessage,
     PruningPointUtxoSetOverrideNotificationMessage, SinkBlueScoreChangedNotificationMessage,
     StopNotifyingPruningPointUtxoSetOverrideRequestMessage, StopNotifyingPruningPointUtxoSetOverrideResponseMessage,
     StopNotifyingUtxosChangedRequestMessage, StopNotifyingUtxosChangedResponseMessage, UtxosChangedNotificationMessage,
     VirtualChainChangedNotificationMessage, VirtualDaaScoreChangedNotificationMessage,
 };
+use crate::protowire::{
+    vecnod_response::Payload, BlockAddedNotificationMessage, NewBlockTemplateNotificationMessage, RpcNotifyCommand, VecnodResponse,
+};
 use crate::{from, try_from};
 use std::str::FromStr;
 use std::sync::Arc;
 use vecno_notify::subscription::Command;
-use vecno_rpc_core::{Notification, RpcError, RpcHash};
+use vecno_rpc_core::{Notification, RpcError, RpcHash, RpcMempoolRemovalReason};
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -34,6 +35,7 @@ from!(item: &vecno_rpc_core::Notification, Payload, {
         Notification::PruningPointUtxoSetOverride(ref notification) => {
             Payload::PruningPointUtxoSetOverrideNotification(notification.into())
         }
+        Notification::MempoolTransactionRemoved(ref notification) => Payload::MempoolTransactionRemovedNotification(notification.into()),
     }
 });
 
@@ -74,6 +76,25 @@ from!(item: &vecno_rpc_core::VirtualDaaScoreChangedNotification, VirtualDaaScore
 
 from!(&vecno_rpc_core::PruningPointUtxoSetOverrideNotification, PruningPointUtxoSetOverrideNotificationMessage);
 
+from!(item: RpcMempoolRemovalReason, protowire::RpcMempoolRemovalReason, {
+    match item {
+        RpcMempoolRemovalReason::Evicted => protowire::RpcMempoolRemovalReason::Evicted,
+        RpcMempoolRemovalReason::Expired => protowire::RpcMempoolRemovalReason::Expired,
+        RpcMempoolRemovalReason::DoubleSpend => protowire::RpcMempoolRemovalReason::DoubleSpend,
+        RpcMempoolRemovalReason::ReplacedByFee => protowire::RpcMempoolRemovalReason::ReplacedByFee,
+        RpcMempoolRemovalReason::InvalidInBlockTemplate => protowire::RpcMempoolRemovalReason::InvalidInBlockTemplate,
+        RpcMempoolRemovalReason::MissingOutpoints => protowire::RpcMempoolRemovalReason::MissingOutpoints,
+    }
+});
+
+from!(item: &vecno_rpc_core::MempoolTransactionRemovedNotification, MempoolTransactionRemovedNotificationMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        reason: protowire::RpcMempoolRemovalReason::from(item.reason) as i32,
+        addresses: item.addresses.iter().map(|x| x.into()).collect(),
+    }
+});
+
 from!(item: Command, RpcNotifyCommand, {
     match item {
         Command::Start => RpcNotifyCommand::NotifyStart,
@@ -117,6 +138,9 @@ try_from!(item: &Payload, vecno_rpc_core::Notification, {
         Payload::PruningPointUtxoSetOverrideNotification(ref notification) => {
             Notification::PruningPointUtxoSetOverride(notification.try_into()?)
         }
+        Payload::MempoolTransactionRemovedNotification(ref notification) => {
+            Notification::MempoolTransactionRemoved(notification.try_into()?)
+        }
         _ => Err(RpcError::UnsupportedFeature)?,
     }
 });
@@ -171,6 +195,27 @@ try_from!(item: &VirtualDaaScoreChangedNotificationMessage, vecno_rpc_core::Virt
 
 try_from!(&PruningPointUtxoSetOverrideNotificationMessage, vecno_rpc_core::PruningPointUtxoSetOverrideNotification);
 
+from!(item: protowire::RpcMempoolRemovalReason, RpcMempoolRemovalReason, {
+    match item {
+        protowire::RpcMempoolRemovalReason::Evicted => RpcMempoolRemovalReason::Evicted,
+        protowire::RpcMempoolRemovalReason::Expired => RpcMempoolRemovalReason::Expired,
+        protowire::RpcMempoolRemovalReason::DoubleSpend => RpcMempoolRemovalReason::DoubleSpend,
+        protowire::RpcMempoolRemovalReason::ReplacedByFee => RpcMempoolRemovalReason::ReplacedByFee,
+        protowire::RpcMempoolRemovalReason::InvalidInBlockTemplate => RpcMempoolRemovalReason::InvalidInBlockTemplate,
+        protowire::RpcMempoolRemovalReason::MissingOutpoints => RpcMempoolRemovalReason::MissingOutpoints,
+    }
+});
+
+try_from!(item: &MempoolTransactionRemovedNotificationMessage, vecno_rpc_core::MempoolTransactionRemovedNotification, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        reason: protowire::RpcMempoolRemovalReason::try_from(item.reason)
+            .map_err(|_| RpcError::PrimitiveToEnumConversionError)?
+            .into(),
+        addresses: Arc::new(item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?),
+    }
+});
+
 from!(item: RpcNotifyCommand, Command, {
     match item {
         RpcNotifyCommand::NotifyStart => Command::Start,
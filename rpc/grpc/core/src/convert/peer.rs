@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use crate::protowire;
 use crate::{from, try_from};
-use vecno_rpc_core::{RpcError, RpcNodeId, RpcPeerAddress};
+use vecno_rpc_core::{RpcError, RpcIpAddress, RpcNodeId, RpcPeerAddress};
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -19,9 +19,15 @@ from!(item: &vecno_rpc_core::RpcPeerInfo, protowire::GetConnectedPeerInfoMessage
         advertised_protocol_version: item.advertised_protocol_version,
         time_connected: item.time_connected as i64,
         is_ibd_peer: item.is_ibd_peer,
+        misbehavior_score: item.misbehavior_score,
+        is_encrypted: item.is_encrypted,
     }
 });
 
+from!(item: &vecno_rpc_core::RpcBannedAddress, protowire::RpcBannedAddress, {
+    Self { ip: item.ip.to_string(), banned_until: item.banned_until }
+});
+
 from!(item: &vecno_rpc_core::RpcPeerAddress, protowire::GetPeerAddressesKnownAddressMessage, { Self { addr: item.to_string() } });
 from!(item: &vecno_rpc_core::RpcIpAddress, protowire::GetPeerAddressesKnownAddressMessage, { Self { addr: item.to_string() } });
 
@@ -40,8 +46,14 @@ try_from!(item: &protowire::GetConnectedPeerInfoMessage, vecno_rpc_core::RpcPeer
         advertised_protocol_version: item.advertised_protocol_version,
         time_connected: item.time_connected as u64,
         is_ibd_peer: item.is_ibd_peer,
+        misbehavior_score: item.misbehavior_score,
+        is_encrypted: item.is_encrypted,
     }
 });
 
+try_from!(item: &protowire::RpcBannedAddress, vecno_rpc_core::RpcBannedAddress, {
+    Self { ip: RpcIpAddress::from_str(&item.ip)?, banned_until: item.banned_until }
+});
+
 try_from!(item: &protowire::GetPeerAddressesKnownAddressMessage, vecno_rpc_core::RpcPeerAddress, { Self::from_str(&item.addr)? });
 try_from!(item: &protowire::GetPeerAddressesKnownAddressMessage, vecno_rpc_core::RpcIpAddress, { Self::from_str(&item.addr)? });
@@ -82,6 +82,39 @@ from!(item: &vecno_rpc_core::RpcAcceptedTransactionIds, protowire::RpcAcceptedTr
     }
 });
 
+from!(item: &vecno_rpc_core::RpcIndexedTransaction, protowire::RpcIndexedTransaction, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        accepting_block_daa_score: item.accepting_block_daa_score,
+        including_block_hash: item.including_block_hash.to_string(),
+        index_within_block: item.index_within_block,
+        transaction: item.transaction.as_ref().map(|x| x.into()),
+    }
+});
+
+from!(item: &vecno_rpc_core::RpcTransactionRejection, protowire::RpcTransactionRejection, {
+    Self { transaction_id: item.transaction_id.to_string(), reason: item.reason.clone() }
+});
+
+from!(item: &vecno_rpc_core::RpcTransactionAcceptance, protowire::RpcTransactionAcceptance, {
+    let fee_data = match (item.storage_mass, item.fee, item.feerate) {
+        (Some(storage_mass), Some(fee), Some(feerate)) => {
+            Some(protowire::RpcTransactionAcceptanceFeeData { storage_mass, fee, feerate })
+        }
+        _ => None,
+    };
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        accepted: item.accepted,
+        is_orphan: item.is_orphan,
+        compute_mass: item.compute_mass,
+        fee_data,
+        replaced_transaction_id: item.replaced_transaction_id.map(|x| x.to_string()).unwrap_or_default(),
+        error: item.error.clone().unwrap_or_default(),
+    }
+});
+
 from!(item: &vecno_rpc_core::RpcUtxosByAddressesEntry, protowire::RpcUtxosByAddressesEntry, {
     Self {
         address: item.address.as_ref().map_or("".to_string(), |x| x.into()),
@@ -189,6 +222,37 @@ try_from!(item: &protowire::RpcAcceptedTransactionIds, vecno_rpc_core::RpcAccept
     }
 });
 
+try_from!(item: &protowire::RpcIndexedTransaction, vecno_rpc_core::RpcIndexedTransaction, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+        accepting_block_daa_score: item.accepting_block_daa_score,
+        including_block_hash: RpcHash::from_str(&item.including_block_hash)?,
+        index_within_block: item.index_within_block,
+        transaction: item.transaction.as_ref().map(vecno_rpc_core::RpcTransaction::try_from).transpose()?,
+    }
+});
+
+try_from!(item: &protowire::RpcTransactionRejection, vecno_rpc_core::RpcTransactionRejection, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)?, reason: item.reason.clone() }
+});
+
+try_from!(item: &protowire::RpcTransactionAcceptance, vecno_rpc_core::RpcTransactionAcceptance, {
+    let replaced_transaction_id =
+        if item.replaced_transaction_id.is_empty() { None } else { Some(RpcHash::from_str(&item.replaced_transaction_id)?) };
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        accepted: item.accepted,
+        is_orphan: item.is_orphan,
+        compute_mass: item.compute_mass,
+        storage_mass: item.fee_data.as_ref().map(|x| x.storage_mass),
+        fee: item.fee_data.as_ref().map(|x| x.fee),
+        feerate: item.fee_data.as_ref().map(|x| x.feerate),
+        replaced_transaction_id,
+        error: if item.error.is_empty() { None } else { Some(item.error.clone()) },
+    }
+});
+
 try_from!(item: &protowire::RpcUtxosByAddressesEntry, vecno_rpc_core::RpcUtxosByAddressesEntry, {
     let address = if item.address.is_empty() { None } else { Some(item.address.as_str().try_into()?) };
     Self {
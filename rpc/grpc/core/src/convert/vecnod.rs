@@ -63,6 +63,16 @@ pub mod vecnod_request_convert {
     impl_into_vecnod_request!(GetFeeEstimate);
     impl_into_vecnod_request!(GetFeeEstimateExperimental);
     impl_into_vecnod_request!(GetCurrentBlockColor);
+    impl_into_vecnod_request!(GetTransaction);
+    impl_into_vecnod_request!(GetTransactionsByIds);
+    impl_into_vecnod_request!(GetTransactionsByAddresses);
+    impl_into_vecnod_request!(GetChainBlockByDaaScore);
+    impl_into_vecnod_request!(GetChainBlocksInBlueScoreRange);
+    impl_into_vecnod_request!(SubmitTransactionPackage);
+    impl_into_vecnod_request!(TestMempoolAccept);
+    impl_into_vecnod_request!(GetFeeEstimateHistorical);
+    impl_into_vecnod_request!(PrioritiseTransaction);
+    impl_into_vecnod_request!(ListBanned);
 
     impl_into_vecnod_request!(NotifyBlockAdded);
     impl_into_vecnod_request!(NotifyNewBlockTemplate);
@@ -72,6 +82,7 @@ pub mod vecnod_request_convert {
     impl_into_vecnod_request!(NotifyVirtualDaaScoreChanged);
     impl_into_vecnod_request!(NotifyVirtualChainChanged);
     impl_into_vecnod_request!(NotifySinkBlueScoreChanged);
+    impl_into_vecnod_request!(NotifyMempoolTransactionRemoved);
 
     macro_rules! impl_into_vecnod_request {
         ($name:tt) => {
@@ -200,6 +211,16 @@ pub mod vecnod_response_convert {
     impl_into_vecnod_response!(GetFeeEstimate);
     impl_into_vecnod_response!(GetFeeEstimateExperimental);
     impl_into_vecnod_response!(GetCurrentBlockColor);
+    impl_into_vecnod_response!(GetTransaction);
+    impl_into_vecnod_response!(GetTransactionsByIds);
+    impl_into_vecnod_response!(GetTransactionsByAddresses);
+    impl_into_vecnod_response!(GetChainBlockByDaaScore);
+    impl_into_vecnod_response!(GetChainBlocksInBlueScoreRange);
+    impl_into_vecnod_response!(SubmitTransactionPackage);
+    impl_into_vecnod_response!(TestMempoolAccept);
+    impl_into_vecnod_response!(GetFeeEstimateHistorical);
+    impl_into_vecnod_response!(PrioritiseTransaction);
+    impl_into_vecnod_response!(ListBanned);
 
     impl_into_vecnod_notify_response!(NotifyBlockAdded);
     impl_into_vecnod_notify_response!(NotifyNewBlockTemplate);
@@ -209,6 +230,7 @@ pub mod vecnod_response_convert {
     impl_into_vecnod_notify_response!(NotifyVirtualDaaScoreChanged);
     impl_into_vecnod_notify_response!(NotifyVirtualChainChanged);
     impl_into_vecnod_notify_response!(NotifySinkBlueScoreChanged);
+    impl_into_vecnod_notify_response!(NotifyMempoolTransactionRemoved);
 
     impl_into_vecnod_notify_response!(NotifyUtxosChanged, StopNotifyingUtxosChanged);
     impl_into_vecnod_notify_response!(NotifyPruningPointUtxoSetOverride, StopNotifyingPruningPointUtxoSetOverride);
@@ -2,9 +2,9 @@ use vecno_notify::{scope::Scope, subscription::Command};
 
 use crate::protowire::{
     vecnod_request, vecnod_response, NotifyBlockAddedRequestMessage, NotifyFinalityConflictRequestMessage,
-    NotifyNewBlockTemplateRequestMessage, NotifyPruningPointUtxoSetOverrideRequestMessage, NotifySinkBlueScoreChangedRequestMessage,
-    NotifyUtxosChangedRequestMessage, NotifyVirtualChainChangedRequestMessage, NotifyVirtualDaaScoreChangedRequestMessage,
-    VecnodRequest, VecnodResponse,
+    NotifyMempoolTransactionRemovedRequestMessage, NotifyNewBlockTemplateRequestMessage,
+    NotifyPruningPointUtxoSetOverrideRequestMessage, NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage,
+    NotifyVirtualChainChangedRequestMessage, NotifyVirtualDaaScoreChangedRequestMessage, VecnodRequest, VecnodResponse,
 };
 
 impl VecnodRequest {
@@ -33,6 +33,7 @@ impl vecnod_request::Payload {
                 vecnod_request::Payload::NotifyVirtualChainChangedRequest(NotifyVirtualChainChangedRequestMessage {
                     command: command.into(),
                     include_accepted_transaction_ids: scope.include_accepted_transaction_ids,
+                    start_hash: scope.start_hash.map(|x| x.to_string()).unwrap_or_default(),
                 })
             }
             Scope::FinalityConflict(_) => {
@@ -64,6 +65,12 @@ impl vecnod_request::Payload {
                     command: command.into(),
                 })
             }
+            Scope::MempoolTransactionRemoved(ref scope) => {
+                vecnod_request::Payload::NotifyMempoolTransactionRemovedRequest(NotifyMempoolTransactionRemovedRequestMessage {
+                    addresses: scope.addresses.iter().map(|x| x.into()).collect::<Vec<String>>(),
+                    command: command.into(),
+                })
+            }
         }
     }
 
@@ -79,6 +86,7 @@ impl vecnod_request::Payload {
                 | Payload::NotifyVirtualDaaScoreChangedRequest(_)
                 | Payload::NotifyPruningPointUtxoSetOverrideRequest(_)
                 | Payload::NotifyNewBlockTemplateRequest(_)
+                | Payload::NotifyMempoolTransactionRemovedRequest(_)
                 | Payload::StopNotifyingUtxosChangedRequest(_)
                 | Payload::StopNotifyingPruningPointUtxoSetOverrideRequest(_)
         )
@@ -108,6 +116,7 @@ impl vecnod_response::Payload {
             Payload::VirtualDaaScoreChangedNotification(_) => true,
             Payload::PruningPointUtxoSetOverrideNotification(_) => true,
             Payload::NewBlockTemplateNotification(_) => true,
+            Payload::MempoolTransactionRemovedNotification(_) => true,
             _ => false,
         }
     }
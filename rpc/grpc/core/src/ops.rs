@@ -87,6 +87,16 @@ pub enum VecnodPayloadOps {
     GetFeeEstimate,
     GetFeeEstimateExperimental,
     GetCurrentBlockColor,
+    GetTransaction,
+    GetTransactionsByIds,
+    GetTransactionsByAddresses,
+    GetChainBlockByDaaScore,
+    GetChainBlocksInBlueScoreRange,
+    SubmitTransactionPackage,
+    TestMempoolAccept,
+    GetFeeEstimateHistorical,
+    PrioritiseTransaction,
+    ListBanned,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -97,6 +107,7 @@ pub enum VecnodPayloadOps {
     NotifyPruningPointUtxoSetOverride,
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
+    NotifyMempoolTransactionRemoved,
 
     // Legacy stop subscription commands
     StopNotifyingUtxosChanged,
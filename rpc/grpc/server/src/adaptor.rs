@@ -4,6 +4,7 @@ use tokio::sync::{mpsc::channel as mpsc_channel, oneshot::Sender as OneshotSende
 use vecno_core::debug;
 use vecno_notify::{notifier::Notifier, subscription::context::SubscriptionContext};
 use vecno_rpc_core::{api::rpc::DynRpcService, notify::connection::ChannelConnection, Notification, RpcResult};
+use vecno_rpc_service::counters::RpcOpCounters;
 use vecno_utils::networking::NetAddress;
 use vecno_utils_tower::counters::TowerConnectionCounters;
 
@@ -40,6 +41,7 @@ impl Adaptor {
         subscription_context: SubscriptionContext,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        op_counters: Arc<RpcOpCounters>,
     ) -> Arc<Self> {
         let (manager_sender, manager_receiver) = mpsc_channel(Self::manager_channel_size());
         let connection_handler = ConnectionHandler::new(
@@ -50,6 +52,7 @@ impl Adaptor {
             subscription_context,
             broadcasters,
             counters,
+            op_counters,
         );
         let server_termination = connection_handler.serve(serve_address);
         let adaptor = Arc::new(Adaptor::new(Some(server_termination), connection_handler, manager, serve_address));
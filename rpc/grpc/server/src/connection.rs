@@ -38,6 +38,7 @@ use vecno_notify::{
     notifier::Notifier,
 };
 use vecno_rpc_core::Notification;
+use vecno_rpc_service::replay::VirtualChainReplay;
 
 pub type IncomingRoute = MpmcReceiver<VecnodRequest>;
 pub type GrpcNotifier = Notifier<Notification, Connection>;
@@ -54,6 +55,11 @@ struct InnerMutableState {
     ///
     /// Registered when handling the first subscription to any notifications
     listener_id: Option<ListenerId>,
+
+    /// Resumable VirtualChainChanged subscription
+    ///
+    /// Set while the client is subscribed to virtual chain changes from a start hash
+    virtual_chain_replay: Option<VirtualChainReplay>,
 }
 
 impl InnerMutableState {
@@ -330,7 +336,16 @@ impl Connection {
         }
     }
 
+    /// Sets the resumable VirtualChainChanged subscription of the connection, ending the previous one if any
+    pub fn set_virtual_chain_replay(&self, replay: Option<VirtualChainReplay>) {
+        let previous = std::mem::replace(&mut self.inner.mutable_state.lock().virtual_chain_replay, replay);
+        if previous.is_some() {
+            debug!("GRPC, Connection {} virtual chain replay ended", self);
+        }
+    }
+
     fn unregister_listener(&self) {
+        self.set_virtual_chain_replay(None);
         let listener_id = self.inner.mutable_state.lock().listener_id.take();
         if let Some(listener_id) = listener_id {
             self.inner.server_context.notifier.unregister_listener(listener_id).expect("unregister listener");
@@ -42,6 +42,7 @@ use vecno_rpc_core::{
     notify::{channel::NotificationChannel, connection::ChannelConnection},
     Notification, RpcResult,
 };
+use vecno_rpc_service::counters::RpcOpCounters;
 use vecno_utils::networking::NetAddress;
 use vecno_utils_tower::{
     counters::TowerConnectionCounters,
@@ -54,11 +55,17 @@ pub struct ServerContext {
     pub core_service: DynRpcService,
     /// The notifier relaying RPC core notifications to connections
     pub notifier: Arc<Notifier<Notification, Connection>>,
+    /// The call counters of the RPC methods
+    pub op_counters: Arc<RpcOpCounters>,
 }
 
 impl ServerContext {
-    pub fn new(core_service: DynRpcService, notifier: Arc<Notifier<Notification, Connection>>) -> Self {
-        Self { core_service, notifier }
+    pub fn new(
+        core_service: DynRpcService,
+        notifier: Arc<Notifier<Notification, Connection>>,
+        op_counters: Arc<RpcOpCounters>,
+    ) -> Self {
+        Self { core_service, notifier, op_counters }
     }
 }
 
@@ -89,6 +96,7 @@ impl ConnectionHandler {
         subscription_context: SubscriptionContext,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        op_counters: Arc<RpcOpCounters>,
     ) -> Self {
         // This notifier UTXOs subscription granularity to rpc-core notifier
         let policies = MutationPolicies::new(UtxosChangedMutationPolicy::AddressSet);
@@ -114,7 +122,7 @@ impl ConnectionHandler {
             broadcasters,
             policies,
         ));
-        let server_context = ServerContext::new(core_service, notifier);
+        let server_context = ServerContext::new(core_service, notifier, op_counters);
         let interface = Arc::new(Factory::new_interface(server_context.clone(), network_bps));
         let running = Default::default();
 
@@ -12,10 +12,14 @@ use crate::{
     error::GrpcServerError,
 };
 use vecno_grpc_core::protowire::{vecnod_request::Payload, *};
-use vecno_grpc_core::{ops::VecnodPayloadOps, protowire::NotifyFinalityConflictResponseMessage};
-use vecno_notify::{scope::FinalityConflictResolvedScope, subscriber::SubscriptionManager};
-use vecno_rpc_core::{SubmitBlockRejectReason, SubmitBlockReport, SubmitBlockResponse};
+use vecno_grpc_core::{
+    ops::VecnodPayloadOps,
+    protowire::{NotifyFinalityConflictResponseMessage, NotifyVirtualChainChangedResponseMessage},
+};
+use vecno_notify::{scope::FinalityConflictResolvedScope, subscriber::SubscriptionManager, subscription::Command};
+use vecno_rpc_core::{RpcError, RpcResult, SubmitBlockRejectReason, SubmitBlockReport, SubmitBlockResponse};
 use vecno_rpc_macros::build_grpc_server_interface;
+use vecno_rpc_service::replay::VirtualChainReplay;
 
 pub struct Factory {}
 
@@ -81,6 +85,16 @@ impl Factory {
                 GetFeeEstimate,
                 GetFeeEstimateExperimental,
                 GetCurrentBlockColor,
+                GetTransaction,
+                GetTransactionsByIds,
+                GetTransactionsByAddresses,
+                GetChainBlockByDaaScore,
+                GetChainBlocksInBlueScoreRange,
+                SubmitTransactionPackage,
+                TestMempoolAccept,
+                GetFeeEstimateHistorical,
+                PrioritiseTransaction,
+                ListBanned,
                 NotifyBlockAdded,
                 NotifyNewBlockTemplate,
                 NotifyFinalityConflict,
@@ -89,6 +103,7 @@ impl Factory {
                 NotifyPruningPointUtxoSetOverride,
                 NotifyVirtualDaaScoreChanged,
                 NotifyVirtualChainChanged,
+                NotifyMempoolTransactionRemoved,
                 StopNotifyingUtxosChanged,
                 StopNotifyingPruningPointUtxoSetOverride,
             ]
@@ -135,6 +150,63 @@ impl Factory {
         });
         interface.replace_method(VecnodPayloadOps::NotifyFinalityConflict, method);
 
+        // Manually reimplementing the NotifyVirtualChainChangedRequest method so a subscription
+        // with a start hash gets served by a virtual chain replay instead of the notifier.
+        let method: VecnodMethod = Method::new(|server_ctx: ServerContext, connection: Connection, request: VecnodRequest| {
+            Box::pin(async move {
+                let mut response: VecnodResponse = match request.payload {
+                    Some(Payload::NotifyVirtualChainChangedRequest(ref request)) => {
+                        match vecno_rpc_core::NotifyVirtualChainChangedRequest::try_from(request) {
+                            Ok(request) => {
+                                let listener_id = connection.get_or_register_listener_id()?;
+                                let result: RpcResult<()> = match (request.command, request.start_hash) {
+                                    (Command::Start, Some(start_hash)) => {
+                                        match VirtualChainReplay::start(
+                                            server_ctx.core_service.clone(),
+                                            connection.clone(),
+                                            start_hash,
+                                            request.include_accepted_transaction_ids,
+                                        )
+                                        .await
+                                        {
+                                            Ok(replay) => {
+                                                // The replay supersedes any regular subscription of the connection
+                                                connection.set_virtual_chain_replay(Some(replay));
+                                                server_ctx
+                                                    .notifier
+                                                    .clone()
+                                                    .execute_subscribe_command(listener_id, request.into(), Command::Stop)
+                                                    .await
+                                                    .map_err(RpcError::from)
+                                            }
+                                            Err(err) => Err(err),
+                                        }
+                                    }
+                                    (command, _) => {
+                                        connection.set_virtual_chain_replay(None);
+                                        server_ctx
+                                            .notifier
+                                            .clone()
+                                            .execute_subscribe_command(listener_id, request.into(), command)
+                                            .await
+                                            .map_err(RpcError::from)
+                                    }
+                                };
+                                NotifyVirtualChainChangedResponseMessage::from(result).into()
+                            }
+                            Err(err) => NotifyVirtualChainChangedResponseMessage::from(err).into(),
+                        }
+                    }
+                    _ => {
+                        return Err(GrpcServerError::InvalidRequestPayload);
+                    }
+                };
+                response.id = request.id;
+                Ok(response)
+            })
+        });
+        interface.replace_method(VecnodPayloadOps::NotifyVirtualChainChanged, method);
+
         // Methods with special properties
         let network_bps = network_bps as usize;
         interface.set_method_properties(
@@ -35,6 +35,7 @@ impl RequestHandler {
 
     pub async fn handle_request(&self, request: VecnodRequest) -> GrpcServerResult<VecnodResponse> {
         let id = request.id;
+        self.server_ctx.op_counters.increment(self.rpc_op.as_str());
         let mut response = self.method.call(self.server_ctx.clone(), self.connection.clone(), request).await?;
         response.id = id;
         Ok(response)
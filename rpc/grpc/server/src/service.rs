@@ -7,7 +7,7 @@ use vecno_core::{
     task::service::{AsyncService, AsyncServiceFuture},
     trace, warn,
 };
-use vecno_rpc_service::service::RpcCoreService;
+use vecno_rpc_service::{counters::RpcOpCounters, service::RpcCoreService};
 use vecno_utils::{networking::NetAddress, triggers::SingleTrigger};
 use vecno_utils_tower::counters::TowerConnectionCounters;
 
@@ -20,6 +20,7 @@ pub struct GrpcService {
     started: SingleTrigger,
     shutdown: SingleTrigger,
     counters: Arc<TowerConnectionCounters>,
+    op_counters: Arc<RpcOpCounters>,
 }
 
 impl GrpcService {
@@ -32,6 +33,7 @@ impl GrpcService {
         rpc_max_clients: usize,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        op_counters: Arc<RpcOpCounters>,
     ) -> Self {
         Self {
             net_address: address,
@@ -42,6 +44,7 @@ impl GrpcService {
             started: Default::default(),
             shutdown: Default::default(),
             counters,
+            op_counters,
         }
     }
 
@@ -71,6 +74,7 @@ impl AsyncService for GrpcService {
             self.core_service.subscription_context(),
             self.broadcasters,
             self.counters.clone(),
+            self.op_counters.clone(),
         );
 
         // Signal the server was started
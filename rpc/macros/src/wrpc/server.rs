@@ -51,6 +51,7 @@ impl ToTokens for RpcTable {
             targets.push(quote! {
                 #rpc_api_ops::#handler => {
                     interface.method(#rpc_api_ops::#handler, method!(|server_ctx: #server_ctx_type, connection_ctx: #connection_ctx_type, request: Serializable<#request_type>| async move {
+                        server_ctx.op_counters().increment(#rpc_api_ops::#handler.as_str());
                         let verbose = server_ctx.verbose();
                         if verbose { workflow_log::log_info!("request: {:?}",request); }
                         // TODO: RPC-CONNECT
@@ -15,9 +15,9 @@ use vecno_math::Uint256;
 use vecno_mining::model::{owner_txs::OwnerTransactions, TransactionIdSet};
 use vecno_notify::converter::Converter;
 use vecno_rpc_core::{
-    BlockAddedNotification, Notification, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData, RpcHash, RpcMempoolEntry,
-    RpcMempoolEntryByAddress, RpcResult, RpcTransaction, RpcTransactionInput, RpcTransactionOutput, RpcTransactionOutputVerboseData,
-    RpcTransactionVerboseData,
+    BlockAddedNotification, MempoolTransactionRemovedNotification, Notification, RpcAcceptedTransactionIds, RpcBlock,
+    RpcBlockVerboseData, RpcHash, RpcMempoolEntry, RpcMempoolEntryByAddress, RpcResult, RpcTransaction, RpcTransactionInput,
+    RpcTransactionOutput, RpcTransactionOutputVerboseData, RpcTransactionVerboseData,
 };
 use vecno_txscript::{extract_script_pub_key_address, script_class::ScriptClass};
 
@@ -193,6 +193,18 @@ impl Converter for ConsensusConverter {
                 let block = Arc::new(self.get_block(&session, &msg.block, true, true).await.unwrap_or_else(|_| (&msg.block).into()));
                 Notification::BlockAdded(BlockAddedNotification { block })
             }
+            consensus_notify::Notification::MempoolTransactionRemoved(msg) => {
+                let addresses = msg
+                    .script_public_keys
+                    .iter()
+                    .filter_map(|script_public_key| extract_script_pub_key_address(script_public_key, self.config.prefix()).ok())
+                    .collect();
+                Notification::MempoolTransactionRemoved(MempoolTransactionRemovedNotification {
+                    transaction_id: msg.transaction_id,
+                    reason: msg.reason.into(),
+                    addresses: Arc::new(addresses),
+                })
+            }
             _ => (&incoming).into(),
         }
     }
@@ -0,0 +1,24 @@
+use vecno_mining::model::entries_query::{EntriesQuery, EntriesSortKey};
+use vecno_rpc_core::{GetMempoolEntriesRequest, RpcMempoolEntrySortKey};
+
+pub trait EntriesQueryConverter {
+    fn entries_query(&self) -> EntriesQuery;
+}
+
+impl EntriesQueryConverter for GetMempoolEntriesRequest {
+    fn entries_query(&self) -> EntriesQuery {
+        EntriesQuery {
+            sort_key: match self.sort_by {
+                RpcMempoolEntrySortKey::TransactionId => EntriesSortKey::TransactionId,
+                RpcMempoolEntrySortKey::Feerate => EntriesSortKey::Feerate,
+                RpcMempoolEntrySortKey::ArrivalTime => EntriesSortKey::ArrivalTime,
+                RpcMempoolEntrySortKey::Mass => EntriesSortKey::Mass,
+            },
+            descending: self.sort_descending,
+            min_feerate: self.min_feerate,
+            payload_only: self.payload_only,
+            offset: self.offset.try_into().unwrap_or(usize::MAX),
+            limit: (self.limit > 0).then(|| self.limit.try_into().unwrap_or(usize::MAX)),
+        }
+    }
+}
@@ -1,7 +1,11 @@
-use vecno_mining::feerate::{FeeEstimateVerbose, FeerateBucket, FeerateEstimations};
+use vecno_mining::feerate::{
+    history::{HistoricalFeeEstimate, HistoricalFeerateBucket},
+    FeeEstimateVerbose, FeerateBucket, FeerateEstimations,
+};
 use vecno_rpc_core::{
     message::GetFeeEstimateExperimentalResponse as RpcFeeEstimateVerboseResponse, RpcFeeEstimate,
-    RpcFeeEstimateVerboseExperimentalData as RpcFeeEstimateVerbose, RpcFeerateBucket,
+    RpcFeeEstimateVerboseExperimentalData as RpcFeeEstimateVerbose, RpcFeerateBucket, RpcHistoricalFeeEstimate,
+    RpcHistoricalFeerateBucket,
 };
 
 pub trait FeerateBucketConverter {
@@ -47,3 +51,28 @@ impl FeeEstimateVerboseConverter for FeeEstimateVerbose {
         }
     }
 }
+
+pub trait HistoricalFeerateBucketConverter {
+    fn into_rpc(self) -> RpcHistoricalFeerateBucket;
+}
+
+impl HistoricalFeerateBucketConverter for HistoricalFeerateBucket {
+    fn into_rpc(self) -> RpcHistoricalFeerateBucket {
+        RpcHistoricalFeerateBucket { feerate: self.feerate, target_seconds: self.target_seconds, confidence: self.confidence }
+    }
+}
+
+pub trait HistoricalFeeEstimateConverter {
+    fn into_rpc(self) -> RpcHistoricalFeeEstimate;
+}
+
+impl HistoricalFeeEstimateConverter for HistoricalFeeEstimate {
+    fn into_rpc(self) -> RpcHistoricalFeeEstimate {
+        RpcHistoricalFeeEstimate {
+            window_seconds: self.window_seconds,
+            block_count: self.block_count,
+            sample_count: self.sample_count,
+            buckets: self.buckets.into_iter().map(HistoricalFeerateBucketConverter::into_rpc).collect(),
+        }
+    }
+}
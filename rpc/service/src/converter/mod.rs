@@ -1,4 +1,7 @@
 pub mod consensus;
+pub mod entries_query;
 pub mod feerate_estimate;
 pub mod index;
 pub mod protocol;
+pub mod template_policy;
+pub mod tx_acceptance;
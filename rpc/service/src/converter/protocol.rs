@@ -25,6 +25,8 @@ impl ProtocolConverter {
             user_agent: properties.user_agent.clone(),
             advertised_protocol_version: properties.advertised_protocol_version,
             time_connected: peer.time_connected(),
+            misbehavior_score: self.flow_context.misbehavior_tracker().score(peer.net_address().ip().into()),
+            is_encrypted: peer.is_encrypted(),
         }
     }
 
@@ -0,0 +1,20 @@
+use vecno_mining::{TemplatePolicy, TemplateSelectionMode};
+use vecno_rpc_core::{RpcTemplatePolicy, RpcTemplateSelectionMode};
+
+pub trait TemplatePolicyConverter {
+    fn into_mining(self) -> TemplatePolicy;
+}
+
+impl TemplatePolicyConverter for RpcTemplatePolicy {
+    fn into_mining(self) -> TemplatePolicy {
+        TemplatePolicy {
+            selection_mode: match self.selection_mode {
+                RpcTemplateSelectionMode::Weighted => TemplateSelectionMode::Weighted,
+                RpcTemplateSelectionMode::HighestFeerate => TemplateSelectionMode::HighestFeerate,
+            },
+            prioritised_mass_share: self.prioritised_mass_share,
+            prioritised_transaction_ids: self.prioritised_transaction_ids.into_iter().collect(),
+            excluded_transaction_ids: self.excluded_transaction_ids.into_iter().collect(),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use vecno_mining::model::tx_acceptance::TransactionAcceptance;
+use vecno_rpc_core::RpcTransactionAcceptance;
+
+pub trait TransactionAcceptanceConverter {
+    fn into_rpc(self) -> RpcTransactionAcceptance;
+}
+
+impl TransactionAcceptanceConverter for TransactionAcceptance {
+    fn into_rpc(self) -> RpcTransactionAcceptance {
+        RpcTransactionAcceptance {
+            transaction_id: self.transaction_id,
+            accepted: self.is_accepted(),
+            is_orphan: self.is_orphan,
+            compute_mass: self.compute_mass,
+            storage_mass: self.storage_mass,
+            fee: self.fee,
+            feerate: self.feerate,
+            replaced_transaction_id: self.replaced_transaction_id,
+            error: self.error.map(|err| err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use parking_lot::RwLock;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// Number of calls of every RPC method, shared by all the RPC servers relaying calls to the RPC core service.
+///
+/// Methods are identified by their operation name, which is common to gRPC and wRPC.
+#[derive(Debug, Default)]
+pub struct RpcOpCounters {
+    counts: RwLock<HashMap<&'static str, Arc<AtomicU64>>>,
+}
+
+impl RpcOpCounters {
+    pub fn increment(&self, op: &'static str) {
+        if let Some(count) = self.counts.read().get(op) {
+            count.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        self.counts.write().entry(op).or_default().fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the call count of every method called at least once, sorted by method name
+    pub fn snapshot(&self) -> Vec<(&'static str, u64)> {
+        let mut snapshot = self.counts.read().iter().map(|(op, count)| (*op, count.load(Ordering::Relaxed))).collect::<Vec<_>>();
+        snapshot.sort_unstable_by_key(|(op, _)| *op);
+        snapshot
+    }
+}
@@ -1,3 +1,5 @@
 pub mod collector;
 pub mod converter;
+pub mod counters;
+pub mod replay;
 pub mod service;
@@ -0,0 +1,141 @@
+//! Resumable virtual chain subscriptions.
+//!
+//! A subscriber passing a `start_hash` in its [`VirtualChainChangedScope`] gets the chain changes since this chain block
+//! replayed before the live notifications. Both the replay and the live part are served by the same task, which computes
+//! every notification against the last chain block it sent, so each notification applies on top of its predecessor
+//! and no removed chain block can be missed, even if the chain reorgs while the replay is in progress.
+
+use std::fmt::Debug;
+use vecno_core::{debug, trace};
+use vecno_notify::{
+    connection::Connection,
+    listener::ListenerId,
+    scope::{Scope, VirtualChainChangedScope},
+};
+use vecno_rpc_core::{
+    api::rpc::DynRpcService,
+    notify::{
+        channel::NotificationChannel,
+        connection::{ChannelConnection, ChannelType},
+    },
+    GetVirtualChainFromBlockResponse, Notification, RpcHash, RpcResult, VirtualChainChangedNotification,
+};
+
+const VIRTUAL_CHAIN_REPLAY: &str = "virtual-chain-replay";
+
+/// Handle of a resumable VirtualChainChanged subscription of a connection.
+///
+/// Dropping the handle ends the subscription.
+pub struct VirtualChainReplay {
+    /// Listener receiving the live VirtualChainChanged notifications, used as catch-up triggers
+    listener_id: ListenerId,
+    /// Channel of the listener, closed to end the replay task, which then unregisters the listener
+    channel: NotificationChannel,
+}
+
+impl VirtualChainReplay {
+    /// Starts sending `connection` the virtual chain changes since `start_hash`, followed by the live changes.
+    ///
+    /// Fails if the virtual chain cannot be obtained from `start_hash`, typically because the block is unknown or pruned.
+    pub async fn start<C>(
+        service: DynRpcService,
+        connection: C,
+        start_hash: RpcHash,
+        include_accepted_transaction_ids: bool,
+    ) -> RpcResult<Self>
+    where
+        C: Connection<Notification = Notification>,
+    {
+        // Validate the start hash before registering anything
+        let first_batch = service.get_virtual_chain_from_block(start_hash, include_accepted_transaction_ids).await?;
+
+        // Subscribe to live chain changes before replaying, so that no change can slip between the replay and the live part
+        let channel = NotificationChannel::default();
+        let listener_id =
+            service.register_new_listener(ChannelConnection::new(VIRTUAL_CHAIN_REPLAY, channel.sender(), ChannelType::Closable));
+        if let Err(err) = service.start_notify(listener_id, Scope::VirtualChainChanged(VirtualChainChangedScope::new(false))).await {
+            let _ = service.unregister_listener(listener_id).await;
+            return Err(err);
+        }
+        debug!("[{}] replaying the virtual chain from {} to connection {}", VIRTUAL_CHAIN_REPLAY, start_hash, connection);
+
+        let task_channel = channel.clone();
+        tokio::spawn(async move {
+            Self::replay(&service, &connection, &task_channel, start_hash, first_batch, include_accepted_transaction_ids).await;
+            let _ = service.unregister_listener(listener_id).await;
+            trace!("[{}] replay to connection {} exited", VIRTUAL_CHAIN_REPLAY, connection);
+        });
+
+        Ok(Self { listener_id, channel })
+    }
+
+    /// Sends `connection` the chain changes since `start_hash`, then keeps it up to date until `channel` gets closed
+    async fn replay<C>(
+        service: &DynRpcService,
+        connection: &C,
+        channel: &NotificationChannel,
+        start_hash: RpcHash,
+        first_batch: GetVirtualChainFromBlockResponse,
+        include_accepted_transaction_ids: bool,
+    ) where
+        C: Connection<Notification = Notification>,
+    {
+        let mut tip = start_hash;
+        let mut batch = Some(first_batch);
+        loop {
+            // Catch up with the current sink
+            loop {
+                let response = match batch.take() {
+                    Some(response) => response,
+                    None => match service.get_virtual_chain_from_block(tip, include_accepted_transaction_ids).await {
+                        Ok(response) => response,
+                        Err(err) => {
+                            debug!("[{}] replay to connection {} interrupted: {}", VIRTUAL_CHAIN_REPLAY, connection, err);
+                            return;
+                        }
+                    },
+                };
+                // The chain path always ends with the sink, so an empty added list means the subscriber is up to date
+                let Some(&last_added) = response.added_chain_block_hashes.last() else {
+                    break;
+                };
+                // The subscription might have ended while the chain was being fetched
+                if channel.is_closed() {
+                    return;
+                }
+                let notification = Self::notification(response);
+                if connection.send(C::into_message(&notification, &connection.encoding())).await.is_err() {
+                    return;
+                }
+                tip = last_added;
+            }
+
+            // Wait for the next live chain change, coalescing the ones already pending
+            if channel.recv().await.is_err() {
+                return;
+            }
+            while channel.try_recv().is_ok() {}
+        }
+    }
+
+    fn notification(response: GetVirtualChainFromBlockResponse) -> Notification {
+        Notification::VirtualChainChanged(VirtualChainChangedNotification {
+            removed_chain_block_hashes: response.removed_chain_block_hashes.into(),
+            added_chain_block_hashes: response.added_chain_block_hashes.into(),
+            accepted_transaction_ids: response.accepted_transaction_ids.into(),
+        })
+    }
+}
+
+impl Debug for VirtualChainReplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VirtualChainReplay").field("listener_id", &self.listener_id).finish()
+    }
+}
+
+impl Drop for VirtualChainReplay {
+    fn drop(&mut self) {
+        // Closing the channel ends the replay task, which unregisters the listener on its way out
+        self.channel.close();
+    }
+}
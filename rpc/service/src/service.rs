@@ -1,7 +1,10 @@
 //! Core server implementation for ClientAPI
 
 use super::collector::{CollectorFromConsensus, CollectorFromIndex};
-use crate::converter::feerate_estimate::{FeeEstimateConverter, FeeEstimateVerboseConverter};
+use crate::converter::entries_query::EntriesQueryConverter;
+use crate::converter::feerate_estimate::{FeeEstimateConverter, FeeEstimateVerboseConverter, HistoricalFeeEstimateConverter};
+use crate::converter::template_policy::TemplatePolicyConverter;
+use crate::converter::tx_acceptance::TransactionAcceptanceConverter;
 use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, protocol::ProtocolConverter};
 use crate::service::NetworkType::{Mainnet, Testnet};
 use async_trait::async_trait;
@@ -13,6 +16,7 @@ use std::{
     vec,
 };
 use tokio::join;
+use vecno_addressindex::{api::AddressIndexProxy, model::AddressHistoryCursor};
 use vecno_consensus_core::api::counters::ProcessingCounters;
 use vecno_consensus_core::errors::block::RuleError;
 use vecno_consensus_core::{
@@ -44,9 +48,13 @@ use vecno_index_core::{
     connection::IndexChannelConnection, indexed_utxos::UtxoSetByScriptPublicKey, notification::Notification as IndexNotification,
     notifier::IndexNotifier,
 };
+use vecno_mining::errors::MiningManagerError;
 use vecno_mining::feerate::FeeEstimateVerbose;
 use vecno_mining::model::tx_query::TransactionQuery;
-use vecno_mining::{manager::MiningManagerProxy, mempool::tx::Orphan};
+use vecno_mining::{
+    manager::MiningManagerProxy,
+    mempool::tx::{Orphan, RbfPolicy},
+};
 use vecno_notify::listener::ListenerLifespan;
 use vecno_notify::subscription::context::SubscriptionContext;
 use vecno_notify::subscription::{MutationPolicies, UtxosChangedMutationPolicy};
@@ -72,6 +80,8 @@ use vecno_rpc_core::{
     notify::connection::ChannelConnection,
     Notification, RpcError, RpcResult,
 };
+use vecno_stratum_server::stats::StratumStats;
+use vecno_txindex::api::TxIndexProxy;
 use vecno_txscript::{extract_script_pub_key_address, pay_to_address_script};
 use vecno_utils::expiring_cache::ExpiringCache;
 use vecno_utils::sysinfo::SystemInfo;
@@ -103,6 +113,8 @@ pub struct RpcCoreService {
     mining_manager: MiningManagerProxy,
     flow_context: Arc<FlowContext>,
     utxoindex: Option<UtxoIndexProxy>,
+    txindex: Option<TxIndexProxy>,
+    addressindex: Option<AddressIndexProxy>,
     config: Arc<Config>,
     consensus_converter: Arc<ConsensusConverter>,
     index_converter: Arc<IndexConverter>,
@@ -116,6 +128,7 @@ pub struct RpcCoreService {
     perf_monitor: Arc<PerfMonitor<Arc<TickService>>>,
     p2p_tower_counters: Arc<TowerConnectionCounters>,
     grpc_tower_counters: Arc<TowerConnectionCounters>,
+    stratum_stats: Option<Arc<StratumStats>>,
     system_info: SystemInfo,
     fee_estimate_cache: ExpiringCache<RpcFeeEstimate>,
     fee_estimate_verbose_cache: ExpiringCache<vecno_mining::errors::MiningManagerResult<GetFeeEstimateExperimentalResponse>>,
@@ -123,6 +136,12 @@ pub struct RpcCoreService {
 
 const RPC_CORE: &str = "rpc-core";
 
+/// Maximum number of address history entries requested per GetTransactionsByAddresses page
+const MAX_ADDRESS_HISTORY_PAGE_SIZE: u32 = 1000;
+
+/// Maximum number of chain block headers returned by a single GetChainBlocksInBlueScoreRange call
+const MAX_CHAIN_BLOCKS_IN_BLUE_SCORE_RANGE: usize = 1000;
+
 impl RpcCoreService {
     pub const IDENT: &'static str = "rpc-core-service";
 
@@ -135,6 +154,8 @@ impl RpcCoreService {
         flow_context: Arc<FlowContext>,
         subscription_context: SubscriptionContext,
         utxoindex: Option<UtxoIndexProxy>,
+        txindex: Option<TxIndexProxy>,
+        addressindex: Option<AddressIndexProxy>,
         config: Arc<Config>,
         core: Arc<Core>,
         processing_counters: Arc<ProcessingCounters>,
@@ -143,6 +164,7 @@ impl RpcCoreService {
         perf_monitor: Arc<PerfMonitor<Arc<TickService>>>,
         p2p_tower_counters: Arc<TowerConnectionCounters>,
         grpc_tower_counters: Arc<TowerConnectionCounters>,
+        stratum_stats: Option<Arc<StratumStats>>,
         system_info: SystemInfo,
     ) -> Self {
         // This notifier UTXOs subscription granularity to index-processor or consensus notifier
@@ -206,6 +228,8 @@ impl RpcCoreService {
             mining_manager,
             flow_context,
             utxoindex,
+            txindex,
+            addressindex,
             config,
             consensus_converter,
             index_converter,
@@ -219,6 +243,7 @@ impl RpcCoreService {
             perf_monitor,
             p2p_tower_counters,
             grpc_tower_counters,
+            stratum_stats,
             system_info,
             fee_estimate_cache: ExpiringCache::new(Duration::from_millis(500), Duration::from_millis(1000)),
             fee_estimate_verbose_cache: ExpiringCache::new(Duration::from_millis(500), Duration::from_millis(1000)),
@@ -270,6 +295,57 @@ impl RpcCoreService {
             .unwrap_or_default()
     }
 
+    /// Locates the given transactions in the transaction index, optionally fetching the transactions
+    /// from their including blocks. Transactions which are not indexed yield `None`.
+    async fn get_indexed_transactions(
+        &self,
+        transaction_ids: Vec<RpcTransactionId>,
+        include_transactions: bool,
+    ) -> RpcResult<Vec<Option<RpcIndexedTransaction>>> {
+        let entries = self
+            .txindex
+            .clone()
+            .unwrap()
+            .get_transaction_entries(transaction_ids.clone())
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?;
+
+        let session = self.consensus_manager.consensus().session().await;
+        // Blocks are fetched once, since transactions are frequently requested in batches of the same block
+        let mut blocks = HashMap::<RpcHash, Option<Block>>::new();
+        let mut transactions = Vec::with_capacity(entries.len());
+        for (transaction_id, entry) in transaction_ids.into_iter().zip(entries) {
+            let Some(entry) = entry else {
+                transactions.push(None);
+                continue;
+            };
+            let transaction = if include_transactions {
+                if !blocks.contains_key(&entry.including_block_hash) {
+                    // The block body is missing if it was pruned
+                    let block = session.async_get_block(entry.including_block_hash).await.ok();
+                    blocks.insert(entry.including_block_hash, block);
+                }
+                blocks[&entry.including_block_hash].as_ref().and_then(|block| {
+                    block
+                        .transactions
+                        .get(entry.index_within_block as usize)
+                        .map(|tx| self.consensus_converter.get_transaction(&session, tx, Some(&block.header), true))
+                })
+            } else {
+                None
+            };
+            transactions.push(Some(RpcIndexedTransaction {
+                transaction_id,
+                accepting_block_hash: entry.accepting_block_hash,
+                accepting_block_daa_score: entry.accepting_block_daa_score,
+                including_block_hash: entry.including_block_hash,
+                index_within_block: entry.index_within_block,
+                transaction,
+            }));
+        }
+        Ok(transactions)
+    }
+
     fn has_sufficient_peer_connectivity(&self) -> bool {
         // Other network types can be used in an isolated environment without peers
         !matches!(self.flow_context.config.net.network_type, Mainnet | Testnet) || self.flow_context.hub().has_peers()
@@ -372,7 +448,12 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         let extra_data = version().as_bytes().iter().chain(once(&(b'/'))).chain(&request.extra_data).cloned().collect::<Vec<_>>();
         let miner_data: MinerData = MinerData::new(script_public_key, extra_data);
         let session = self.consensus_manager.consensus().unguarded_session();
-        let block_template = self.mining_manager.clone().get_block_template(&session, miner_data).await?;
+        let block_template = match request.template_policy {
+            Some(template_policy) => {
+                self.mining_manager.clone().get_block_template_with_policy(&session, miner_data, template_policy.into_mining()).await?
+            }
+            None => self.mining_manager.clone().get_block_template(&session, miner_data).await?,
+        };
 
         // Check coinbase tx payload length
         if block_template.block.transactions[COINBASE_TRANSACTION_INDEX].payload.len() > self.config.max_coinbase_payload_len {
@@ -498,13 +579,10 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     ) -> RpcResult<GetMempoolEntriesResponse> {
         let query = self.extract_tx_query(request.filter_transaction_pool, request.include_orphan_pool)?;
         let session = self.consensus_manager.consensus().unguarded_session();
-        let (transactions, orphans) = self.mining_manager.clone().get_all_transactions(query).await;
-        let mempool_entries = transactions
-            .iter()
-            .chain(orphans.iter())
-            .map(|transaction| self.consensus_converter.get_mempool_entry(&session, transaction))
-            .collect();
-        Ok(GetMempoolEntriesResponse::new(mempool_entries))
+        let page = self.mining_manager.clone().get_mempool_entries(query, request.entries_query()).await;
+        let mempool_entries =
+            page.transactions.iter().map(|transaction| self.consensus_converter.get_mempool_entry(&session, transaction)).collect();
+        Ok(GetMempoolEntriesResponse::new(mempool_entries, page.total_count as u64, page.total_mass, page.total_fees))
     }
 
     async fn get_mempool_entries_by_addresses_call(
@@ -575,6 +653,53 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(SubmitTransactionReplacementResponse::new(transaction_id, (&*replaced_transaction).into()))
     }
 
+    async fn submit_transaction_package_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: SubmitTransactionPackageRequest,
+    ) -> RpcResult<SubmitTransactionPackageResponse> {
+        let transactions: Vec<Transaction> = request.transactions.into_iter().map(|tx| tx.try_into()).collect::<Result<_, _>>()?;
+        let transaction_ids = transactions.iter().map(|tx| tx.id()).collect::<Vec<_>>();
+        let session = self.consensus_manager.consensus().unguarded_session();
+        match self.flow_context.submit_rpc_transaction_package(&session, transactions).await {
+            Ok(()) => Ok(SubmitTransactionPackageResponse::new(transaction_ids, vec![])),
+            Err(ProtocolError::MiningManagerError(MiningManagerError::PackageRejected(rejections))) => {
+                // Per-transaction rejections are reported in the response so that the caller knows which transactions to fix
+                let rejections = rejections
+                    .into_iter()
+                    .map(|(transaction_id, err)| RpcTransactionRejection::new(transaction_id, err.to_string()))
+                    .collect::<Vec<_>>();
+                debug!("Rejected transaction package: {} rejections", rejections.len());
+                Ok(SubmitTransactionPackageResponse::new(vec![], rejections))
+            }
+            Err(err) => {
+                let err = RpcError::RejectedTransactionPackage(err.to_string());
+                debug!("{err}");
+                Err(err)
+            }
+        }
+    }
+
+    async fn test_mempool_accept_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: TestMempoolAcceptRequest,
+    ) -> RpcResult<TestMempoolAcceptResponse> {
+        let transactions: Vec<Transaction> = request.transactions.into_iter().map(|tx| tx.try_into()).collect::<Result<_, _>>()?;
+        // Nothing gets inserted into the mempool so testing orphans is safe whatever the RPC mode
+        let orphan = match request.allow_orphan {
+            true => Orphan::Allowed,
+            false => Orphan::Forbidden,
+        };
+        let rbf_policy = match request.replace_by_fee {
+            true => RbfPolicy::Mandatory,
+            false => RbfPolicy::Forbidden,
+        };
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let acceptances = self.mining_manager.clone().test_mempool_accept(&session, transactions, orphan, rbf_policy).await;
+        Ok(TestMempoolAcceptResponse::new(acceptances.into_iter().map(TransactionAcceptanceConverter::into_rpc).collect()))
+    }
+
     async fn get_current_network_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -795,6 +920,131 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         }
     }
 
+    async fn get_fee_estimate_historical_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetFeeEstimateHistoricalRequest,
+    ) -> RpcResult<GetFeeEstimateHistoricalResponse> {
+        let estimate = self.mining_manager.clone().get_historical_fee_estimate(request.window_seconds).await.into_rpc();
+        Ok(GetFeeEstimateHistoricalResponse { estimate })
+    }
+
+    async fn prioritise_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: PrioritiseTransactionRequest,
+    ) -> RpcResult<PrioritiseTransactionResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("PrioritiseTransaction RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let fee_delta = self.mining_manager.clone().prioritise_transaction(request.transaction_id, request.fee_delta).await;
+        Ok(PrioritiseTransactionResponse { fee_delta })
+    }
+
+    async fn get_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse> {
+        if !self.config.txindex {
+            return Err(RpcError::NoTxIndex);
+        }
+        let mut transactions = self.get_indexed_transactions(vec![request.transaction_id], request.include_transaction).await?;
+        match transactions.pop().flatten() {
+            Some(transaction) => Ok(GetTransactionResponse::new(transaction)),
+            None => Err(RpcError::TransactionNotFound(request.transaction_id)),
+        }
+    }
+
+    async fn get_transactions_by_ids_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetTransactionsByIdsRequest,
+    ) -> RpcResult<GetTransactionsByIdsResponse> {
+        if !self.config.txindex {
+            return Err(RpcError::NoTxIndex);
+        }
+        let transactions = self.get_indexed_transactions(request.transaction_ids, request.include_transactions).await?;
+        Ok(GetTransactionsByIdsResponse::new(transactions.into_iter().flatten().collect()))
+    }
+
+    async fn get_transactions_by_addresses_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetTransactionsByAddressesRequest,
+    ) -> RpcResult<GetTransactionsByAddressesResponse> {
+        if !self.config.addressindex {
+            return Err(RpcError::NoAddressIndex);
+        }
+        let limit = match request.limit {
+            0 => MAX_ADDRESS_HISTORY_PAGE_SIZE,
+            limit => limit.min(MAX_ADDRESS_HISTORY_PAGE_SIZE),
+        };
+        let addresses =
+            request.addresses.into_iter().map(|address| (pay_to_address_script(&address), address)).collect::<HashMap<_, _>>();
+        let cursor = request.cursor.map(|cursor| AddressHistoryCursor::new(cursor.accepting_block_daa_score, cursor.transaction_id));
+        let page = self
+            .addressindex
+            .clone()
+            .unwrap()
+            .get_history(addresses.keys().cloned().collect(), cursor, limit as usize)
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?;
+        let entries = page
+            .entries
+            .into_iter()
+            .map(|(script_public_key, entry)| RpcAddressTransaction {
+                address: addresses[&script_public_key].clone(),
+                transaction_id: entry.transaction_id,
+                accepting_block_hash: entry.accepting_block_hash,
+                accepting_block_daa_score: entry.accepting_block_daa_score,
+                received_amount: entry.received_amount,
+                spent_amount: entry.spent_amount,
+            })
+            .collect();
+        let next_cursor = page.next_cursor.map(|cursor| RpcAddressHistoryCursor {
+            accepting_block_daa_score: cursor.accepting_block_daa_score,
+            transaction_id: cursor.transaction_id,
+        });
+        Ok(GetTransactionsByAddressesResponse::new(entries, next_cursor))
+    }
+
+    async fn get_chain_block_by_daa_score_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetChainBlockByDaaScoreRequest,
+    ) -> RpcResult<GetChainBlockByDaaScoreResponse> {
+        let session = self.consensus_manager.consensus().session().await;
+        let header = session.async_get_chain_block_by_daa_score(request.daa_score).await?;
+        Ok(GetChainBlockByDaaScoreResponse::new(header.as_ref().into()))
+    }
+
+    async fn get_chain_blocks_in_blue_score_range_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetChainBlocksInBlueScoreRangeRequest,
+    ) -> RpcResult<GetChainBlocksInBlueScoreRangeResponse> {
+        if request.low_blue_score > request.high_blue_score {
+            return Err(RpcError::InvalidBlueScoreRange(request.low_blue_score, request.high_blue_score));
+        }
+        let session = self.consensus_manager.consensus().session().await;
+        // Query one extra header to learn whether the range gets truncated and where the next batch starts
+        let mut headers = session
+            .async_get_chain_blocks_in_blue_score_range(
+                request.low_blue_score,
+                request.high_blue_score,
+                MAX_CHAIN_BLOCKS_IN_BLUE_SCORE_RANGE + 1,
+            )
+            .await?;
+        let next_low_blue_score = headers.get(MAX_CHAIN_BLOCKS_IN_BLUE_SCORE_RANGE).map(|header| header.blue_score);
+        headers.truncate(MAX_CHAIN_BLOCKS_IN_BLUE_SCORE_RANGE);
+        Ok(GetChainBlocksInBlueScoreRangeResponse::new(
+            headers.iter().map(|header| header.as_ref().into()).collect(),
+            next_low_blue_score,
+        ))
+    }
+
     async fn ping_call(&self, _connection: Option<&DynRpcConnection>, _: PingRequest) -> RpcResult<PingResponse> {
         Ok(PingResponse {})
     }
@@ -868,7 +1118,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         }
         let peer_address = request.peer_address.normalize(self.config.net.default_p2p_port());
         if let Some(connection_manager) = self.flow_context.connection_manager() {
-            connection_manager.add_connection_request(peer_address.into(), request.is_permanent).await;
+            connection_manager.add_connection_request(peer_address, request.is_permanent).await;
         } else {
             return Err(RpcError::NoConnectionManager);
         }
@@ -894,7 +1144,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             if connection_manager.ip_has_permanent_connection(ip).await {
                 return Err(RpcError::IpHasPermanentConnection(request.ip));
             }
-            connection_manager.ban(ip).await;
+            connection_manager.ban(ip, self.flow_context.misbehavior_tracker().config().max_ban_duration()).await;
         } else {
             return Err(RpcError::NoConnectionManager);
         }
@@ -915,6 +1165,18 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(UnbanResponse {})
     }
 
+    async fn list_banned_call(&self, _connection: Option<&DynRpcConnection>, _: ListBannedRequest) -> RpcResult<ListBannedResponse> {
+        let banned_addresses = self
+            .flow_context
+            .address_manager
+            .lock()
+            .get_banned_addresses_with_expiry()
+            .into_iter()
+            .map(|(ip, banned_until)| RpcBannedAddress { ip, banned_until })
+            .collect();
+        Ok(ListBannedResponse::new(banned_addresses))
+    }
+
     async fn get_connected_peer_info_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -1054,6 +1316,8 @@ NOTE: This error usually indicates an RPC conversion error between the node and
 
         let custom_metrics: Option<HashMap<String, CustomMetricValue>> = None;
 
+        let stratum_metrics = if req.stratum_metrics { self.stratum_stats.as_ref().map(|stats| stats.metrics()) } else { None };
+
         let server_time = unix_now();
 
         let response = GetMetricsResponse {
@@ -1064,6 +1328,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             consensus_metrics,
             storage_metrics,
             custom_metrics,
+            stratum_metrics,
         };
 
         Ok(response)
@@ -1146,6 +1411,11 @@ NOTE: This error usually indicates an RPC conversion error between the node and
                 warn!("RPC subscription to blanket UtxosChanged called while node in safe RPC mode -- ignoring.");
                 Err(RpcError::UnavailableInSafeMode)
             }
+            Scope::MempoolTransactionRemoved(ref scope) if !self.config.unsafe_rpc && scope.addresses.is_empty() => {
+                // Same as for UtxosChanged, blanket subscriptions are restricted to unsafe mode only
+                warn!("RPC subscription to blanket MempoolTransactionRemoved called while node in safe RPC mode -- ignoring.");
+                Err(RpcError::UnavailableInSafeMode)
+            }
             _ => {
                 self.notifier.clone().start_notify(id, scope).await?;
                 Ok(())
@@ -0,0 +1,150 @@
+use std::time::{Duration, Instant};
+use vecno_math::Uint256;
+
+/// Number of fractional bits kept when converting a share difficulty into a target
+const FRACTION_BITS: u32 = 16;
+
+/// Number of shares after which the difficulty of a worker is reconsidered
+const RETARGET_SHARES: u32 = 12;
+
+/// Bound of the ratio between two successive difficulties of a worker
+const MAX_ADJUSTMENT: f64 = 4.0;
+
+/// Relative deviation from the targeted share rate below which the difficulty is left unchanged
+const ADJUSTMENT_TOLERANCE: f64 = 0.2;
+
+/// Conversion between share difficulties and targets.
+///
+/// Share difficulties follow the definition of the network difficulty reported by the node, so difficulty 1 matches
+/// the maximum difficulty target of the network and a share reaching the network difficulty is a block.
+#[derive(Clone, Copy, Debug)]
+pub struct DifficultyConverter {
+    max_target: Uint256,
+    hashes_per_difficulty: f64,
+}
+
+impl DifficultyConverter {
+    pub fn new(max_target: Uint256) -> Self {
+        Self { max_target, hashes_per_difficulty: 2f64.powi(256) / max_target.as_f64() }
+    }
+
+    /// Returns the target a share of `difficulty` must not exceed
+    pub fn target(&self, difficulty: f64) -> Uint256 {
+        let scaled_difficulty = (difficulty * (1u64 << FRACTION_BITS) as f64).round().max(1.0) as u64;
+        let target = self.max_target / scaled_difficulty;
+        if target.bits() + FRACTION_BITS > Uint256::BITS {
+            return Uint256::MAX;
+        }
+        target << FRACTION_BITS
+    }
+
+    /// Returns the expected number of hashes needed to find a share of `difficulty`
+    pub fn expected_hashes(&self, difficulty: f64) -> f64 {
+        difficulty * self.hashes_per_difficulty
+    }
+}
+
+/// Variable share difficulty of a worker, adjusted to get one share per target interval in average
+#[derive(Debug)]
+pub struct VarDiff {
+    difficulty: f64,
+    min_difficulty: f64,
+    target_share_interval: Duration,
+    window_start: Instant,
+    window_shares: u32,
+}
+
+impl VarDiff {
+    pub fn new(initial_difficulty: f64, min_difficulty: f64, target_share_interval: Duration, now: Instant) -> Self {
+        Self {
+            difficulty: initial_difficulty.max(min_difficulty),
+            min_difficulty,
+            target_share_interval,
+            window_start: now,
+            window_shares: 0,
+        }
+    }
+
+    pub fn difficulty(&self) -> f64 {
+        self.difficulty
+    }
+
+    pub fn add_share(&mut self) {
+        self.window_shares += 1;
+    }
+
+    /// Adjusts the difficulty to the share rate observed since the previous adjustment and returns the new difficulty,
+    /// if any.
+    ///
+    /// The share rate is only estimated once enough shares were found, or after as long as these shares should have taken,
+    /// so that a worker finding no share at all gets its difficulty lowered too.
+    pub fn retarget(&mut self, now: Instant) -> Option<f64> {
+        let elapsed = now.saturating_duration_since(self.window_start);
+        if self.window_shares < RETARGET_SHARES && elapsed < self.target_share_interval * RETARGET_SHARES {
+            return None;
+        }
+        let ratio = (self.window_shares as f64 * self.target_share_interval.as_secs_f64() / elapsed.as_secs_f64().max(f64::EPSILON))
+            .clamp(1.0 / MAX_ADJUSTMENT, MAX_ADJUSTMENT);
+        self.window_start = now;
+        self.window_shares = 0;
+
+        if (ratio - 1.0).abs() < ADJUSTMENT_TOLERANCE {
+            return None;
+        }
+        let difficulty = (self.difficulty * ratio).max(self.min_difficulty);
+        if difficulty == self.difficulty {
+            return None;
+        }
+        self.difficulty = difficulty;
+        Some(difficulty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vecno_consensus_core::config::params::MAINNET_PARAMS;
+
+    #[test]
+    fn test_share_target() {
+        let max_target = MAINNET_PARAMS.max_difficulty_target;
+        let converter = DifficultyConverter::new(max_target);
+        assert_eq!(converter.target(1.0), (max_target / (1u64 << FRACTION_BITS)) << FRACTION_BITS);
+        assert_eq!(converter.target(2.0), (max_target / (2u64 << FRACTION_BITS)) << FRACTION_BITS);
+        assert!(converter.target(1000.5) < converter.target(1000.0));
+        // Targets above the 256 bits range saturate
+        assert_eq!(converter.target(0.25), Uint256::MAX);
+        assert_eq!(converter.target(0.0), Uint256::MAX);
+        // Difficulty 1 is found with two hashes in average since the maximum target is 2^255 - 1
+        assert!((converter.expected_hashes(1.0) - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_vardiff() {
+        let interval = Duration::from_secs(10);
+        let start = Instant::now();
+        let mut vardiff = VarDiff::new(1000.0, 1.0, interval, start);
+
+        // Not enough shares nor time to estimate the share rate
+        (0..RETARGET_SHARES - 1).for_each(|_| vardiff.add_share());
+        assert_eq!(vardiff.retarget(start + Duration::from_secs(10)), None);
+
+        // Shares found twice as fast as targeted
+        vardiff.add_share();
+        assert_eq!(vardiff.retarget(start + interval * RETARGET_SHARES / 2), Some(2000.0));
+
+        // Shares found at the targeted rate
+        let start = start + interval * RETARGET_SHARES / 2;
+        (0..RETARGET_SHARES).for_each(|_| vardiff.add_share());
+        assert_eq!(vardiff.retarget(start + interval * RETARGET_SHARES), None);
+
+        // No share at all, the adjustment being bounded
+        let start = start + interval * RETARGET_SHARES;
+        assert_eq!(vardiff.retarget(start + interval * RETARGET_SHARES), Some(500.0));
+
+        // The difficulty never goes below the minimum
+        let mut vardiff = VarDiff::new(2.0, 1.0, interval, start);
+        assert_eq!(vardiff.retarget(start + interval * RETARGET_SHARES), Some(1.0));
+        assert_eq!(vardiff.retarget(start + interval * RETARGET_SHARES * 2), None);
+    }
+}
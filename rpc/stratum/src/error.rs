@@ -0,0 +1,44 @@
+use thiserror::Error;
+
+/// Errors reported to miners in the `error` field of a Stratum response
+#[derive(Clone, Debug, Error)]
+pub enum StratumError {
+    #[error("unsupported method {0}")]
+    UnsupportedMethod(String),
+
+    #[error("invalid params: {0}")]
+    InvalidParams(String),
+
+    #[error("job not found")]
+    JobNotFound,
+
+    #[error("job does not accept more shares")]
+    JobFull,
+
+    #[error("duplicate share")]
+    DuplicateShare,
+
+    #[error("low difficulty share")]
+    LowDifficultyShare,
+
+    #[error("unauthorized worker")]
+    Unauthorized,
+
+    #[error("nonce does not match the assigned extranonce")]
+    InvalidNonce,
+}
+
+impl StratumError {
+    /// Error code following the conventions of Stratum v1 pools
+    pub fn code(&self) -> i32 {
+        match self {
+            StratumError::UnsupportedMethod(_) | StratumError::InvalidParams(_) | StratumError::InvalidNonce => 20,
+            StratumError::JobNotFound | StratumError::JobFull => 21,
+            StratumError::DuplicateShare => 22,
+            StratumError::LowDifficultyShare => 23,
+            StratumError::Unauthorized => 24,
+        }
+    }
+}
+
+pub type StratumResult<T> = std::result::Result<T, StratumError>;
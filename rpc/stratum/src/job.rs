@@ -0,0 +1,107 @@
+use crate::error::{StratumError, StratumResult};
+use parking_lot::{Mutex, RwLock};
+use serde_json::{json, Value};
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use vecno_consensus_core::{
+    block::{Block, MutableBlock},
+    hashing,
+};
+use vecno_hashes::Hash;
+use vecno_math::Uint256;
+use vecno_pow::State;
+
+/// Number of recent jobs kept so that shares of a job replaced in the meantime are still credited
+pub(crate) const MAX_JOBS: usize = 16;
+
+/// Number of nonces of valid shares remembered per job to detect duplicates. Past it, the job no longer accepts shares
+/// and the miners have to move on to a newer job.
+pub(crate) const MAX_SUBMITTED_NONCES: usize = 1 << 16;
+
+/// A block template handed out to the miners
+pub struct Job {
+    pub id: u64,
+    block: MutableBlock,
+    pre_pow_hash: Hash,
+    pow_state: State,
+    submitted_nonces: Mutex<HashSet<u64>>,
+}
+
+impl Job {
+    fn new(id: u64, block: MutableBlock, pre_pow_hash: Hash) -> Self {
+        let pow_state = State::new(&block.header);
+        Self { id, block, pre_pow_hash, pow_state, submitted_nonces: Default::default() }
+    }
+
+    /// Parameters of the `mining.notify` message announcing the job
+    pub fn notify_params(&self) -> Value {
+        json!([self.id.to_string(), self.pre_pow_hash.to_le_u64(), self.block.header.timestamp])
+    }
+
+    /// Registers the nonce of a valid share, failing if it was already submitted for this job or if the job holds
+    /// too many shares already
+    pub fn register_nonce(&self, nonce: u64) -> StratumResult<()> {
+        let mut submitted_nonces = self.submitted_nonces.lock();
+        if submitted_nonces.contains(&nonce) {
+            return Err(StratumError::DuplicateShare);
+        }
+        if submitted_nonces.len() >= MAX_SUBMITTED_NONCES {
+            return Err(StratumError::JobFull);
+        }
+        submitted_nonces.insert(nonce);
+        Ok(())
+    }
+
+    /// Returns whether `nonce` solves the block of the job together with the proof of work
+    pub fn check_pow(&self, nonce: u64) -> (bool, Uint256) {
+        self.pow_state.check_pow(nonce)
+    }
+
+    pub fn to_block(&self, nonce: u64) -> Block {
+        let mut header = self.block.header.clone();
+        header.nonce = nonce;
+        header.finalize();
+        Block::new(header, self.block.transactions.clone())
+    }
+}
+
+/// Jobs recently handed out, the latest being at the back
+#[derive(Default)]
+pub struct Jobs {
+    jobs: RwLock<VecDeque<Arc<Job>>>,
+    next_id: AtomicU64,
+}
+
+impl Jobs {
+    /// Creates a job out of a new block template, unless the template has the same proof of work input as the latest job
+    pub fn update(&self, block: MutableBlock) -> Option<Arc<Job>> {
+        let pre_pow_hash = hashing::header::hash_override_nonce_time(&block.header, 0, 0);
+        if let Some(latest) = self.latest() {
+            if latest.pre_pow_hash == pre_pow_hash && latest.block.header.timestamp == block.header.timestamp {
+                return None;
+            }
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let job = Arc::new(Job::new(id, block, pre_pow_hash));
+        let mut jobs = self.jobs.write();
+        if jobs.len() == MAX_JOBS {
+            jobs.pop_front();
+        }
+        jobs.push_back(job.clone());
+        Some(job)
+    }
+
+    pub fn get(&self, id: u64) -> Option<Arc<Job>> {
+        self.jobs.read().iter().rev().find(|job| job.id == id).cloned()
+    }
+
+    pub fn latest(&self) -> Option<Arc<Job>> {
+        self.jobs.read().back().cloned()
+    }
+}
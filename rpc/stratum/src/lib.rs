@@ -0,0 +1,10 @@
+//!
+//! Stratum v1 mining server built into the node, letting miners connect directly without an external bridge.
+//!
+
+pub mod difficulty;
+pub mod error;
+pub mod job;
+pub mod protocol;
+pub mod server;
+pub mod stats;
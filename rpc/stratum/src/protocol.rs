@@ -0,0 +1,125 @@
+//!
+//! Stratum v1 messages, exchanged as JSON objects terminated by a new line.
+//!
+//! Jobs are announced with `mining.notify [job_id, pre_pow_hash, timestamp]` where `pre_pow_hash` is the header hash
+//! without nonce and timestamp, given as four little endian u64 words. Miners answer with
+//! `mining.submit [worker, job_id, nonce]`, the nonce being a hex encoded u64 whose leading bytes are the extranonce
+//! assigned to the connection with `mining.set_extranonce`.
+//!
+
+use crate::error::{StratumError, StratumResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+pub const SUBSCRIBE: &str = "mining.subscribe";
+pub const EXTRANONCE_SUBSCRIBE: &str = "mining.extranonce.subscribe";
+pub const AUTHORIZE: &str = "mining.authorize";
+pub const SUBMIT: &str = "mining.submit";
+
+pub const SET_EXTRANONCE: &str = "mining.set_extranonce";
+pub const SET_DIFFICULTY: &str = "mining.set_difficulty";
+pub const NOTIFY: &str = "mining.notify";
+
+/// Protocol name returned on subscription, for miners selecting their message format from it
+pub const PROTOCOL_VERSION: &str = "EthereumStratum/1.0.0";
+
+/// Size in bytes of the extranonce, which makes the leading bytes of the nonces submitted on a connection
+pub const EXTRANONCE_SIZE: usize = 2;
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Vec<Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct Response {
+    id: Value,
+    result: Value,
+    error: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct Notification<'a> {
+    id: Value,
+    method: &'a str,
+    params: Value,
+}
+
+/// Encodes the response to the request `id` as a line
+pub fn response(id: Value, result: StratumResult<Value>) -> String {
+    let response = match result {
+        Ok(result) => Response { id, result, error: Value::Null },
+        Err(err) => Response { id, result: Value::Null, error: json!([err.code(), err.to_string(), Value::Null]) },
+    };
+    line(&response)
+}
+
+/// Encodes a notification as a line
+pub fn notification(method: &str, params: Value) -> String {
+    line(&Notification { id: Value::Null, method, params })
+}
+
+fn line<T: Serialize>(message: &T) -> String {
+    let mut line = serde_json::to_string(message).expect("stratum messages are serializable");
+    line.push('\n');
+    line
+}
+
+pub fn parse_str<'a>(params: &'a [Value], index: usize, name: &str) -> StratumResult<&'a str> {
+    params.get(index).and_then(Value::as_str).ok_or_else(|| StratumError::InvalidParams(format!("missing {}", name)))
+}
+
+pub fn parse_job_id(params: &[Value]) -> StratumResult<u64> {
+    parse_str(params, 1, "job id")?.parse().map_err(|_| StratumError::InvalidParams("malformed job id".to_owned()))
+}
+
+/// Parses the submitted nonce, completing it with `extranonce` when the miner only sends the part it iterated over
+pub fn parse_nonce(params: &[Value], extranonce: u16) -> StratumResult<u64> {
+    let hex = parse_str(params, 2, "nonce")?;
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.is_empty() || hex.len() > 16 {
+        return Err(StratumError::InvalidParams("malformed nonce".to_owned()));
+    }
+    let nonce = u64::from_str_radix(hex, 16).map_err(|_| StratumError::InvalidParams("malformed nonce".to_owned()))?;
+    let extranonce_bits = (EXTRANONCE_SIZE * 8) as u32;
+    if hex.len() <= 16 - EXTRANONCE_SIZE * 2 {
+        return Ok(((extranonce as u64) << (64 - extranonce_bits)) | nonce);
+    }
+    if (nonce >> (64 - extranonce_bits)) as u16 != extranonce {
+        return Err(StratumError::InvalidNonce);
+    }
+    Ok(nonce)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nonce() {
+        let params = |nonce: &str| vec![json!("worker"), json!("1"), json!(nonce)];
+        assert_eq!(parse_nonce(&params("12340000deadbeef"), 0x1234).unwrap(), 0x12340000deadbeef);
+        assert_eq!(parse_nonce(&params("0x12340000deadbeef"), 0x1234).unwrap(), 0x12340000deadbeef);
+        assert_eq!(parse_nonce(&params("0000deadbeef"), 0x1234).unwrap(), 0x12340000deadbeef);
+        assert!(matches!(parse_nonce(&params("43210000deadbeef"), 0x1234), Err(StratumError::InvalidNonce)));
+        assert!(matches!(parse_nonce(&params("12340000deadbeef00"), 0x1234), Err(StratumError::InvalidParams(_))));
+        assert!(matches!(parse_nonce(&params("xyz"), 0x1234), Err(StratumError::InvalidParams(_))));
+    }
+
+    #[test]
+    fn test_response_encoding() {
+        assert_eq!(response(json!(1), Ok(json!(true))), "{\"id\":1,\"result\":true,\"error\":null}\n");
+        assert_eq!(
+            response(json!(2), Err(StratumError::JobNotFound)),
+            "{\"id\":2,\"result\":null,\"error\":[21,\"job not found\",null]}\n"
+        );
+        assert_eq!(
+            notification(SET_DIFFICULTY, json!([4.0])),
+            "{\"id\":null,\"method\":\"mining.set_difficulty\",\"params\":[4.0]}\n"
+        );
+    }
+}
@@ -0,0 +1,506 @@
+use crate::{
+    difficulty::{DifficultyConverter, VarDiff},
+    error::{StratumError, StratumResult},
+    job::{Job, Jobs, MAX_JOBS},
+    protocol::{self, Request},
+    stats::{StratumStats, WorkerStats},
+};
+use serde_json::{json, Value};
+use std::{
+    collections::VecDeque,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tokio::{
+    io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{tcp::OwnedWriteHalf, TcpListener, TcpStream},
+    select,
+    sync::watch,
+    time::interval,
+};
+use vecno_addresses::Address;
+use vecno_consensus_core::{block::BlockTemplate, coinbase::MinerData, network::NetworkType};
+use vecno_core::{
+    debug, info,
+    task::{
+        service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace,
+    vecnod_env::version,
+    warn,
+};
+use vecno_math::Uint256;
+use vecno_p2p_flows::flow_context::FlowContext;
+use vecno_utils::{networking::NetAddress, triggers::SingleTrigger};
+
+pub const SERVICE_NAME: &str = "stratum-server";
+
+/// Interval between two block template fetches
+const JOB_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Interval between two checks of the difficulty of a worker
+const RETARGET_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+const MAX_LINE_LENGTH: usize = 4 * 1024;
+const MAX_WORKER_NAME_LENGTH: usize = 128;
+
+pub const DEFAULT_INITIAL_DIFFICULTY: f64 = 16_777_216.0;
+pub const DEFAULT_MIN_DIFFICULTY: f64 = 1.0;
+pub const DEFAULT_TARGET_SHARE_INTERVAL: Duration = Duration::from_secs(10);
+
+pub struct Options {
+    pub listen_address: NetAddress,
+    /// Address the coinbase rewards of the mined blocks are paid to
+    pub pay_address: Address,
+    /// Share difficulty assigned to a newly connected worker
+    pub initial_difficulty: f64,
+    pub min_difficulty: f64,
+    /// Average interval between two shares of a worker the share difficulty gets adjusted to
+    pub target_share_interval: Duration,
+}
+
+impl Options {
+    pub fn new(listen_address: NetAddress, pay_address: Address) -> Self {
+        Self {
+            listen_address,
+            pay_address,
+            initial_difficulty: DEFAULT_INITIAL_DIFFICULTY,
+            min_difficulty: DEFAULT_MIN_DIFFICULTY,
+            target_share_interval: DEFAULT_TARGET_SHARE_INTERVAL,
+        }
+    }
+}
+
+/// Stratum v1 server handing out jobs built from the block templates of the mining manager and submitting the
+/// blocks found by the miners
+pub struct StratumServer {
+    options: Options,
+    flow_context: Arc<FlowContext>,
+    tick_service: Arc<TickService>,
+    stats: Arc<StratumStats>,
+    converter: DifficultyConverter,
+    jobs: Jobs,
+    job_sender: watch::Sender<Option<Arc<Job>>>,
+    next_session_id: AtomicU64,
+    shutdown: SingleTrigger,
+}
+
+/// State of a miner connection
+struct Session {
+    id: u64,
+    remote_address: SocketAddr,
+    /// Leading bytes of the nonces the miner is allowed to submit
+    extranonce: u16,
+    worker: Option<Arc<WorkerStats>>,
+    vardiff: VarDiff,
+    share_target: Uint256,
+    /// Difficulty and share target in force when each of the latest jobs was sent to the miner
+    job_targets: VecDeque<(u64, f64, Uint256)>,
+}
+
+impl Session {
+    fn job_target(&self, job_id: u64) -> Option<(f64, Uint256)> {
+        self.job_targets.iter().find(|(id, _, _)| *id == job_id).map(|(_, difficulty, target)| (*difficulty, *target))
+    }
+}
+
+impl StratumServer {
+    pub fn new(options: Options, flow_context: Arc<FlowContext>, tick_service: Arc<TickService>, stats: Arc<StratumStats>) -> Self {
+        let converter = DifficultyConverter::new(flow_context.config.max_difficulty_target);
+        Self {
+            options,
+            flow_context,
+            tick_service,
+            stats,
+            converter,
+            jobs: Jobs::default(),
+            job_sender: watch::channel(None).0,
+            next_session_id: AtomicU64::new(0),
+            shutdown: SingleTrigger::default(),
+        }
+    }
+
+    fn is_synced(&self, template: &BlockTemplate) -> bool {
+        let config = &self.flow_context.config;
+        // Other network types can be used in an isolated environment without peers
+        let has_peers =
+            !matches!(config.net.network_type, NetworkType::Mainnet | NetworkType::Testnet) || self.flow_context.hub().has_peers();
+        has_peers && config.is_nearly_synced(template.selected_parent_timestamp, template.selected_parent_daa_score)
+    }
+
+    async fn job_refresher(self: Arc<Self>) {
+        let script_public_key = vecno_txscript::pay_to_address_script(&self.options.pay_address);
+        let extra_data = version().as_bytes().iter().chain(b"/stratum").cloned().collect::<Vec<_>>();
+        let miner_data = MinerData::new(script_public_key, extra_data);
+        let mut is_synced = true;
+        while let TickReason::Wakeup = self.tick_service.tick(JOB_REFRESH_INTERVAL).await {
+            let session = self.flow_context.consensus().unguarded_session();
+            let template = match self.flow_context.mining_manager().clone().get_block_template(&session, miner_data.clone()).await {
+                Ok(template) => template,
+                Err(err) => {
+                    debug!("[{}] error getting a block template: {}", SERVICE_NAME, err);
+                    continue;
+                }
+            };
+
+            let was_synced = std::mem::replace(&mut is_synced, self.is_synced(&template));
+            if !is_synced && !self.flow_context.config.enable_unsynced_mining {
+                if was_synced {
+                    warn!("[{}] the node is not synced, no new jobs are handed out until it is", SERVICE_NAME);
+                }
+                continue;
+            }
+
+            if let Some(job) = self.jobs.update(template.block) {
+                trace!("[{}] new job {}", SERVICE_NAME, job.id);
+                self.job_sender.send_replace(Some(job));
+            }
+        }
+        trace!("[{}] job refresher exiting", SERVICE_NAME);
+    }
+
+    async fn serve(self: Arc<Self>, listener: TcpListener) {
+        let shutdown = self.shutdown.listener.clone();
+        loop {
+            select! {
+                _ = shutdown.clone() => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, remote_address)) => {
+                        let server = self.clone();
+                        tokio::spawn(async move {
+                            if let Err(err) = server.handle_connection(stream, remote_address).await {
+                                debug!("[{}] connection with {} closed: {}", SERVICE_NAME, remote_address, err);
+                            }
+                        });
+                    }
+                    Err(err) => {
+                        warn!("[{}] error accepting a connection: {}", SERVICE_NAME, err);
+                    }
+                },
+            }
+        }
+        trace!("[{}] listener exiting", SERVICE_NAME);
+    }
+
+    async fn handle_connection(self: Arc<Self>, stream: TcpStream, remote_address: SocketAddr) -> std::io::Result<()> {
+        let id = self.next_session_id.fetch_add(1, Ordering::Relaxed);
+        let vardiff = VarDiff::new(
+            self.options.initial_difficulty,
+            self.options.min_difficulty,
+            self.options.target_share_interval,
+            Instant::now(),
+        );
+        let mut session = Session {
+            id,
+            remote_address,
+            // Extranonces get reused only once 2^16 more connections were opened
+            extranonce: id as u16,
+            worker: None,
+            share_target: self.converter.target(vardiff.difficulty()),
+            vardiff,
+            job_targets: VecDeque::new(),
+        };
+        debug!("[{}] miner connected from {}", SERVICE_NAME, remote_address);
+
+        let result = self.run_session(&mut session, stream).await;
+        if let Some(worker) = session.worker {
+            self.stats.unregister(session.id);
+            info!("[{}] worker {} at {} disconnected", SERVICE_NAME, worker.name(), remote_address);
+        }
+        result
+    }
+
+    async fn run_session(&self, session: &mut Session, stream: TcpStream) -> std::io::Result<()> {
+        stream.set_nodelay(true)?;
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+        let mut line = Vec::with_capacity(256);
+        let mut job_receiver = self.job_sender.subscribe();
+        let mut retarget = interval(RETARGET_CHECK_INTERVAL);
+        let shutdown = self.shutdown.listener.clone();
+        loop {
+            select! {
+                _ = shutdown.clone() => return Ok(()),
+                read = read_line(&mut reader, &mut line) => {
+                    if read? == 0 {
+                        return Ok(());
+                    }
+                    let request = serde_json::from_slice::<Request>(&line);
+                    line.clear();
+                    let request = match request {
+                        Ok(request) => request,
+                        Err(err) => return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err)),
+                    };
+
+                    let was_authorized = session.worker.is_some();
+                    let result = self.handle_request(session, &request).await;
+                    writer.write_all(protocol::response(request.id, result).as_bytes()).await?;
+
+                    if !was_authorized && session.worker.is_some() {
+                        let extranonce = format!("{:0width$x}", session.extranonce, width = protocol::EXTRANONCE_SIZE * 2);
+                        let notification =
+                            protocol::notification(protocol::SET_EXTRANONCE, json!([extranonce, 8 - protocol::EXTRANONCE_SIZE]));
+                        writer.write_all(notification.as_bytes()).await?;
+                        let notification = protocol::notification(protocol::SET_DIFFICULTY, json!([session.vardiff.difficulty()]));
+                        writer.write_all(notification.as_bytes()).await?;
+                        let job = job_receiver.borrow_and_update().clone();
+                        if let Some(job) = job {
+                            self.send_job(session, &mut writer, &job).await?;
+                        }
+                    }
+                }
+                changed = job_receiver.changed(), if session.worker.is_some() => {
+                    if changed.is_err() {
+                        return Ok(());
+                    }
+                    let job = job_receiver.borrow_and_update().clone();
+                    if let Some(job) = job {
+                        self.send_job(session, &mut writer, &job).await?;
+                    }
+                }
+                _ = retarget.tick(), if session.worker.is_some() => {
+                    if let Some(difficulty) = session.vardiff.retarget(Instant::now()) {
+                        // The new difficulty applies to the next jobs
+                        session.share_target = self.converter.target(difficulty);
+                        if let Some(worker) = session.worker.as_ref() {
+                            worker.set_difficulty(difficulty);
+                        }
+                        let notification = protocol::notification(protocol::SET_DIFFICULTY, json!([difficulty]));
+                        writer.write_all(notification.as_bytes()).await?;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn send_job(&self, session: &mut Session, writer: &mut OwnedWriteHalf, job: &Job) -> std::io::Result<()> {
+        if session.job_targets.len() == MAX_JOBS {
+            session.job_targets.pop_front();
+        }
+        session.job_targets.push_back((job.id, session.vardiff.difficulty(), session.share_target));
+        writer.write_all(protocol::notification(protocol::NOTIFY, job.notify_params()).as_bytes()).await
+    }
+
+    async fn handle_request(&self, session: &mut Session, request: &Request) -> StratumResult<Value> {
+        match request.method.as_str() {
+            protocol::SUBSCRIBE => Ok(json!([true, protocol::PROTOCOL_VERSION])),
+            protocol::EXTRANONCE_SUBSCRIBE => Ok(Value::Bool(true)),
+            protocol::AUTHORIZE => self.authorize(session, &request.params),
+            protocol::SUBMIT => self.submit(session, &request.params).await,
+            method => Err(StratumError::UnsupportedMethod(method.to_owned())),
+        }
+    }
+
+    fn authorize(&self, session: &mut Session, params: &[Value]) -> StratumResult<Value> {
+        let name = protocol::parse_str(params, 0, "worker name")?;
+        if name.len() > MAX_WORKER_NAME_LENGTH {
+            return Err(StratumError::InvalidParams("worker name too long".to_owned()));
+        }
+        if session.worker.is_none() {
+            let worker = Arc::new(WorkerStats::new(name.to_owned(), session.remote_address.to_string(), session.vardiff.difficulty()));
+            self.stats.register(session.id, worker.clone());
+            session.worker = Some(worker);
+            info!("[{}] worker {} at {} authorized", SERVICE_NAME, name, session.remote_address);
+        }
+        Ok(Value::Bool(true))
+    }
+
+    async fn submit(&self, session: &mut Session, params: &[Value]) -> StratumResult<Value> {
+        let share = accept_share(&self.jobs, &self.converter, session, params)?;
+        if share.is_block {
+            self.submit_block(&share.worker, &share.job, share.nonce).await;
+        }
+        Ok(Value::Bool(true))
+    }
+
+    async fn submit_block(&self, worker: &WorkerStats, job: &Job, nonce: u64) {
+        let block = job.to_block(nonce);
+        let hash = block.hash();
+        let session = self.flow_context.consensus().unguarded_session();
+        match self.flow_context.submit_rpc_block(&session, block).await {
+            Ok(()) => {
+                worker.add_block();
+                info!("[{}] block {} found by worker {}", SERVICE_NAME, hash, worker.name());
+            }
+            Err(err) => {
+                warn!("[{}] block {} found by worker {} was rejected: {}", SERVICE_NAME, hash, worker.name(), err);
+            }
+        }
+    }
+}
+
+/// A share accepted from a worker
+struct Share {
+    worker: Arc<WorkerStats>,
+    job: Arc<Job>,
+    nonce: u64,
+    /// Whether the share also solves the block of the job
+    is_block: bool,
+}
+
+/// Validates a share submitted in `session` and credits it to the worker, accepted or not
+fn accept_share(jobs: &Jobs, converter: &DifficultyConverter, session: &mut Session, params: &[Value]) -> StratumResult<Share> {
+    let worker = session.worker.clone().ok_or(StratumError::Unauthorized)?;
+    let job_id = protocol::parse_job_id(params)?;
+    let nonce = protocol::parse_nonce(params, session.extranonce).inspect_err(|_| worker.add_rejected_share())?;
+
+    let (Some(job), Some((difficulty, share_target))) = (jobs.get(job_id), session.job_target(job_id)) else {
+        worker.add_stale_share();
+        return Err(StratumError::JobNotFound);
+    };
+    let (is_block, pow) = job.check_pow(nonce);
+    if pow > share_target {
+        worker.add_rejected_share();
+        return Err(StratumError::LowDifficultyShare);
+    }
+    // Only the nonces of valid shares are remembered, so that invalid ones cannot fill the job up
+    job.register_nonce(nonce).inspect_err(|err| match err {
+        StratumError::JobFull => worker.add_stale_share(),
+        _ => worker.add_rejected_share(),
+    })?;
+
+    worker.add_accepted_share(converter.expected_hashes(difficulty));
+    session.vardiff.add_share();
+    Ok(Share { worker, job, nonce, is_block })
+}
+
+/// Reads the next line into `line`, returning 0 at the end of the stream.
+///
+/// Bytes read before a cancellation are kept in `line`, so the caller must only clear it once a full line was processed.
+async fn read_line<R: AsyncBufRead + Unpin>(reader: &mut R, line: &mut Vec<u8>) -> std::io::Result<usize> {
+    let limit = MAX_LINE_LENGTH.saturating_sub(line.len()) as u64;
+    let read = (&mut *reader).take(limit).read_until(b'\n', line).await?;
+    if line.last() != Some(&b'\n') && line.len() >= MAX_LINE_LENGTH {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "line too long"));
+    }
+    if read == 0 && !line.is_empty() {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+    }
+    Ok(read)
+}
+
+impl AsyncService for StratumServer {
+    fn ident(self: Arc<Self>) -> &'static str {
+        SERVICE_NAME
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", SERVICE_NAME);
+        Box::pin(async move {
+            let config = &self.flow_context.config;
+            if config.net.is_mainnet() && !config.enable_mainnet_mining {
+                return Err(AsyncServiceError::Service(format!("{} cannot run while mining on mainnet is disabled", SERVICE_NAME)));
+            }
+            let listener = TcpListener::bind(self.options.listen_address.to_string()).await.map_err(|err| {
+                AsyncServiceError::Service(format!("{} failed to listen on {}: {}", SERVICE_NAME, self.options.listen_address, err))
+            })?;
+            info!("Stratum server listening on {}, paying to {}", self.options.listen_address, self.options.pay_address);
+
+            let job_refresher = tokio::spawn(self.clone().job_refresher());
+            self.clone().serve(listener).await;
+            job_refresher.await.map_err(|err| AsyncServiceError::Service(err.to_string()))?;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", SERVICE_NAME);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", SERVICE_NAME);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::MAX_SUBMITTED_NONCES;
+    use vecno_consensus_core::{block::MutableBlock, header::Header};
+    use vecno_hashes::Hash;
+
+    fn session(worker: &Arc<WorkerStats>) -> Session {
+        Session {
+            id: 0,
+            remote_address: "127.0.0.1:5555".parse().unwrap(),
+            extranonce: 0x1234,
+            worker: Some(worker.clone()),
+            vardiff: VarDiff::new(1.0, 1.0, DEFAULT_TARGET_SHARE_INTERVAL, Instant::now()),
+            share_target: Uint256::MAX,
+            job_targets: VecDeque::new(),
+        }
+    }
+
+    fn job(jobs: &Jobs, timestamp: u64) -> Arc<Job> {
+        let mut header = Header::from_precomputed_hash(Hash::from_u64_word(1), vec![]);
+        header.timestamp = timestamp;
+        jobs.update(MutableBlock::from_header(header)).unwrap()
+    }
+
+    fn submit_params(job: &Job, nonce: u64) -> Vec<Value> {
+        vec![json!("worker"), json!(job.id.to_string()), json!(format!("{:016x}", nonce))]
+    }
+
+    fn shares(worker: &WorkerStats) -> (u64, u64, u64) {
+        let metrics = worker.metrics();
+        (metrics.shares_accepted, metrics.shares_stale, metrics.shares_rejected)
+    }
+
+    #[test]
+    fn test_accept_share() {
+        let jobs = Jobs::default();
+        let converter = DifficultyConverter::new(Uint256::MAX);
+        let worker = Arc::new(WorkerStats::new("worker".to_owned(), "127.0.0.1:5555".to_owned(), 1.0));
+        let mut session = session(&worker);
+        let nonce = 0x1234_0000_0000_0001;
+
+        // A job never sent to the miner is stale
+        let first_job = job(&jobs, 1);
+        let result = accept_share(&jobs, &converter, &mut session, &submit_params(&first_job, nonce));
+        assert!(matches!(result, Err(StratumError::JobNotFound)));
+        assert_eq!(shares(&worker), (0, 1, 0));
+
+        // A share not reaching the share target is rejected and its nonce is not remembered
+        session.job_targets.push_back((first_job.id, 1.0, Uint256::ZERO));
+        let result = accept_share(&jobs, &converter, &mut session, &submit_params(&first_job, nonce));
+        assert!(matches!(result, Err(StratumError::LowDifficultyShare)));
+        assert_eq!(shares(&worker), (0, 1, 1));
+
+        // Once the share target is lowered, the same nonce is a valid share
+        let second_job = job(&jobs, 2);
+        session.job_targets.push_back((second_job.id, 1.0, Uint256::MAX));
+        let share = accept_share(&jobs, &converter, &mut session, &submit_params(&second_job, nonce)).unwrap();
+        assert_eq!((share.job.id, share.nonce, share.is_block), (second_job.id, nonce, false));
+        assert_eq!(shares(&worker), (1, 1, 1));
+
+        // Submitting it again is a duplicate
+        let result = accept_share(&jobs, &converter, &mut session, &submit_params(&second_job, nonce));
+        assert!(matches!(result, Err(StratumError::DuplicateShare)));
+        assert_eq!(shares(&worker), (1, 1, 2));
+
+        // A share of a job which is no longer kept is stale
+        (0..MAX_JOBS as u64).for_each(|timestamp| {
+            job(&jobs, 3 + timestamp);
+        });
+        let result = accept_share(&jobs, &converter, &mut session, &submit_params(&second_job, nonce + 1));
+        assert!(matches!(result, Err(StratumError::JobNotFound)));
+        assert_eq!(shares(&worker), (1, 2, 2));
+
+        // A job stops accepting shares once it holds too many of them
+        let last_job = jobs.latest().unwrap();
+        session.job_targets.push_back((last_job.id, 1.0, Uint256::MAX));
+        (0..MAX_SUBMITTED_NONCES as u64).for_each(|i| last_job.register_nonce(i).unwrap());
+        let result = accept_share(&jobs, &converter, &mut session, &submit_params(&last_job, nonce));
+        assert!(matches!(result, Err(StratumError::JobFull)));
+        assert_eq!(shares(&worker), (1, 3, 2));
+    }
+}
@@ -0,0 +1,114 @@
+use parking_lot::{Mutex, RwLock};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use vecno_core::time::unix_now;
+use vecno_rpc_core::{StratumMetrics, StratumWorkerMetrics};
+
+/// Time span of the accepted shares the hashrate of a worker is estimated from
+const HASHRATE_WINDOW: Duration = Duration::from_secs(300);
+
+/// Workers currently connected to the Stratum server, shared with the RPC service reporting them in `GetMetrics`
+#[derive(Debug, Default)]
+pub struct StratumStats {
+    workers: RwLock<HashMap<u64, Arc<WorkerStats>>>,
+}
+
+impl StratumStats {
+    pub(crate) fn register(&self, session_id: u64, worker: Arc<WorkerStats>) {
+        self.workers.write().insert(session_id, worker);
+    }
+
+    pub(crate) fn unregister(&self, session_id: u64) {
+        self.workers.write().remove(&session_id);
+    }
+
+    pub fn metrics(&self) -> StratumMetrics {
+        let mut workers = self.workers.read().values().map(|worker| worker.metrics()).collect::<Vec<_>>();
+        workers.sort_unstable_by(|a, b| a.worker.cmp(&b.worker).then_with(|| a.remote_address.cmp(&b.remote_address)));
+        StratumMetrics { hashrate: workers.iter().map(|worker| worker.hashrate).sum(), workers }
+    }
+}
+
+#[derive(Debug)]
+pub struct WorkerStats {
+    worker: String,
+    remote_address: String,
+    connected_since: u64,
+    started: Instant,
+    inner: Mutex<WorkerCounters>,
+}
+
+#[derive(Debug, Default)]
+struct WorkerCounters {
+    difficulty: f64,
+    shares_accepted: u64,
+    shares_stale: u64,
+    shares_rejected: u64,
+    blocks_found: u64,
+    /// Time and expected number of hashes of the shares accepted within the hashrate window
+    recent_shares: VecDeque<(Instant, f64)>,
+}
+
+impl WorkerStats {
+    pub fn new(worker: String, remote_address: String, difficulty: f64) -> Self {
+        Self {
+            worker,
+            remote_address,
+            connected_since: unix_now(),
+            started: Instant::now(),
+            inner: Mutex::new(WorkerCounters { difficulty, ..Default::default() }),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.worker
+    }
+
+    pub fn set_difficulty(&self, difficulty: f64) {
+        self.inner.lock().difficulty = difficulty;
+    }
+
+    pub fn add_accepted_share(&self, expected_hashes: f64) {
+        let mut inner = self.inner.lock();
+        inner.shares_accepted += 1;
+        inner.recent_shares.push_back((Instant::now(), expected_hashes));
+    }
+
+    pub fn add_stale_share(&self) {
+        self.inner.lock().shares_stale += 1;
+    }
+
+    pub fn add_rejected_share(&self) {
+        self.inner.lock().shares_rejected += 1;
+    }
+
+    pub fn add_block(&self) {
+        self.inner.lock().blocks_found += 1;
+    }
+
+    pub fn metrics(&self) -> StratumWorkerMetrics {
+        let now = Instant::now();
+        let mut inner = self.inner.lock();
+        while inner.recent_shares.front().is_some_and(|(time, _)| now.duration_since(*time) > HASHRATE_WINDOW) {
+            inner.recent_shares.pop_front();
+        }
+        // A worker connected for less than the window is measured over its connection time
+        let elapsed = now.duration_since(self.started).min(HASHRATE_WINDOW).as_secs_f64().max(1.0);
+        let hashrate = inner.recent_shares.iter().map(|(_, hashes)| hashes).sum::<f64>() / elapsed;
+
+        StratumWorkerMetrics {
+            worker: self.worker.clone(),
+            remote_address: self.remote_address.clone(),
+            difficulty: inner.difficulty,
+            hashrate,
+            shares_accepted: inner.shares_accepted,
+            shares_stale: inner.shares_stale,
+            shares_rejected: inner.shares_rejected,
+            blocks_found: inner.blocks_found,
+            connected_since: self.connected_since,
+        }
+    }
+}
@@ -76,6 +76,7 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::MempoolTransactionRemovedNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -623,6 +624,8 @@ impl RpcApi for VecnoRpcClient {
             GetBlockDagInfo,
             GetBlocks,
             GetBlockTemplate,
+            GetChainBlockByDaaScore,
+            GetChainBlocksInBlueScoreRange,
             GetCurrentBlockColor,
             GetCoinSupply,
             GetConnectedPeerInfo,
@@ -631,6 +634,7 @@ impl RpcApi for VecnoRpcClient {
             GetDaaScoreTimestampEstimate,
             GetFeeEstimate,
             GetFeeEstimateExperimental,
+            GetFeeEstimateHistorical,
             GetHeaders,
             GetInfo,
             GetMempoolEntries,
@@ -644,13 +648,20 @@ impl RpcApi for VecnoRpcClient {
             GetSubnetwork,
             GetSyncStatus,
             GetSystemInfo,
+            GetTransaction,
+            GetTransactionsByAddresses,
+            GetTransactionsByIds,
             GetUtxosByAddresses,
             GetVirtualChainFromBlock,
+            ListBanned,
+            PrioritiseTransaction,
             ResolveFinalityConflict,
             Shutdown,
             SubmitBlock,
             SubmitTransaction,
+            SubmitTransactionPackage,
             SubmitTransactionReplacement,
+            TestMempoolAccept,
             Unban,
         ]
     );
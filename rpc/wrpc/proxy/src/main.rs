@@ -82,7 +82,7 @@ async fn main() -> Result<()> {
 
     let counters = Arc::new(WebSocketCounters::default());
     let tasks = threads.unwrap_or_else(num_cpus::get);
-    let rpc_handler = Arc::new(VecnoRpcHandler::new(tasks, encoding, None, options.clone()));
+    let rpc_handler = Arc::new(VecnoRpcHandler::new(tasks, encoding, None, Default::default(), options.clone()));
 
     let router = Arc::new(Router::new(rpc_handler.server.clone()));
     let server = RpcServer::new_with_encoding::<Server, Connection, RpcApiOps, Id64>(
@@ -11,6 +11,7 @@ use vecno_notify::{
     notifier::Notify,
 };
 use vecno_rpc_core::{api::ops::RpcApiOps, notify::mode::NotificationMode, Notification};
+use vecno_rpc_service::replay::VirtualChainReplay;
 use workflow_log::log_trace;
 use workflow_rpc::{
     server::{prelude::*, result::Result as WrpcResult},
@@ -51,6 +52,8 @@ struct ConnectionInner {
     pub grpc_client: Option<Arc<GrpcClient>>,
     // not using an atomic in case an Id will change type in the future...
     pub listener_id: Mutex<Option<ListenerId>>,
+    // resumable VirtualChainChanged subscription, only used when connected to rpc core
+    pub virtual_chain_replay: Mutex<Option<VirtualChainReplay>>,
 }
 
 impl ConnectionInner {
@@ -88,7 +91,8 @@ impl Connection {
         assert!(grpc_client.is_none() || grpc_client.as_ref().unwrap().notification_mode() == NotificationMode::Direct);
         // Should a gRPC client be provided, no listener_id is required for subscriptions so the listener id is set to default
         let listener_id = Mutex::new(grpc_client.clone().map(|_| ListenerId::default()));
-        Connection { inner: Arc::new(ConnectionInner { id, peer: *peer, messenger, grpc_client, listener_id }) }
+        let virtual_chain_replay = Mutex::new(None);
+        Connection { inner: Arc::new(ConnectionInner { id, peer: *peer, messenger, grpc_client, listener_id, virtual_chain_replay }) }
     }
 
     /// Obtain the connection id
@@ -121,6 +125,11 @@ impl Connection {
         self.inner.listener_id.lock().unwrap().replace(listener_id);
     }
 
+    /// Sets the resumable VirtualChainChanged subscription of the connection, ending the previous one if any
+    pub fn set_virtual_chain_replay(&self, replay: Option<VirtualChainReplay>) {
+        *self.inner.virtual_chain_replay.lock().unwrap() = replay;
+    }
+
     pub fn peer(&self) -> &SocketAddr {
         &self.inner.peer
     }
@@ -44,6 +44,8 @@ impl Router {
                 GetBlockDagInfo,
                 GetBlocks,
                 GetBlockTemplate,
+                GetChainBlockByDaaScore,
+                GetChainBlocksInBlueScoreRange,
                 GetCurrentBlockColor,
                 GetCoinSupply,
                 GetConnectedPeerInfo,
@@ -51,6 +53,7 @@ impl Router {
                 GetDaaScoreTimestampEstimate,
                 GetFeeEstimate,
                 GetFeeEstimateExperimental,
+                GetFeeEstimateHistorical,
                 GetHeaders,
                 GetInfo,
                 GetInfo,
@@ -66,13 +69,20 @@ impl Router {
                 GetSubnetwork,
                 GetSyncStatus,
                 GetSystemInfo,
+                GetTransaction,
+                GetTransactionsByAddresses,
+                GetTransactionsByIds,
                 GetUtxosByAddresses,
                 GetVirtualChainFromBlock,
+                ListBanned,
+                PrioritiseTransaction,
                 ResolveFinalityConflict,
                 Shutdown,
                 SubmitBlock,
                 SubmitTransaction,
+                SubmitTransactionPackage,
                 SubmitTransactionReplacement,
+                TestMempoolAccept,
                 Unban,
             ]
         );
@@ -17,7 +17,7 @@ use vecno_notify::{
     events::EVENT_TYPE_ARRAY,
     listener::ListenerLifespan,
     notifier::Notifier,
-    scope::Scope,
+    scope::{Scope, VirtualChainChangedScope},
     subscriber::Subscriber,
     subscription::{MutationPolicies, UtxosChangedMutationPolicy},
 };
@@ -26,7 +26,7 @@ use vecno_rpc_core::{
     notify::{channel::NotificationChannel, connection::ChannelConnection, mode::NotificationMode},
     Notification, RpcResult,
 };
-use vecno_rpc_service::service::RpcCoreService;
+use vecno_rpc_service::{counters::RpcOpCounters, replay::VirtualChainReplay, service::RpcCoreService};
 use workflow_log::*;
 use workflow_rpc::server::prelude::*;
 
@@ -42,6 +42,7 @@ struct ServerInner {
     pub _encoding: Encoding,
     pub sockets: Mutex<HashMap<u64, Connection>>,
     pub rpc_core: Option<RpcCore>,
+    pub op_counters: Arc<RpcOpCounters>,
     pub options: Arc<Options>,
 }
 
@@ -53,7 +54,13 @@ pub struct Server {
 const WRPC_SERVER: &str = "wrpc-server";
 
 impl Server {
-    pub fn new(tasks: usize, encoding: Encoding, core_service: Option<Arc<RpcCoreService>>, options: Arc<Options>) -> Self {
+    pub fn new(
+        tasks: usize,
+        encoding: Encoding,
+        core_service: Option<Arc<RpcCoreService>>,
+        op_counters: Arc<RpcOpCounters>,
+        options: Arc<Options>,
+    ) -> Self {
         // This notifier UTXOs subscription granularity to rpc-core notifier
         let policies = MutationPolicies::new(UtxosChangedMutationPolicy::AddressSet);
 
@@ -97,6 +104,7 @@ impl Server {
                 _encoding: encoding,
                 sockets: Mutex::new(HashMap::new()),
                 rpc_core,
+                op_counters,
                 options,
             }),
         }
@@ -147,6 +155,7 @@ impl Server {
     pub async fn disconnect(&self, connection: Connection) {
         // log_info!("WebSocket disconnected: {}", connection.peer());
         if let Some(rpc_core) = &self.inner.rpc_core {
+            connection.set_virtual_chain_replay(None);
             if let Some(listener_id) = connection.listener_id() {
                 rpc_core.wrpc_notifier.unregister_listener(listener_id).unwrap_or_else(|err| {
                     log_error!("WebSocket {} (disconnected) error unregistering the notification listener: {err}", connection.peer());
@@ -168,6 +177,11 @@ impl Server {
         self.inner.rpc_core.as_ref().map(|x| x.wrpc_notifier.clone())
     }
 
+    #[inline(always)]
+    pub fn op_counters(&self) -> &Arc<RpcOpCounters> {
+        &self.inner.op_counters
+    }
+
     pub fn rpc_service(&self, connection: &Connection) -> DynRpcService {
         if let Some(rpc_core) = &self.inner.rpc_core {
             rpc_core.service.clone()
@@ -191,7 +205,30 @@ impl Server {
         };
         workflow_log::log_trace!("notification subscribe[0x{listener_id:x}] {scope:?}");
         if let Some(rpc_core) = &self.inner.rpc_core {
-            rpc_core.wrpc_notifier.clone().try_start_notify(listener_id, scope)?;
+            match scope {
+                Scope::VirtualChainChanged(VirtualChainChangedScope {
+                    start_hash: Some(start_hash),
+                    include_accepted_transaction_ids,
+                }) => {
+                    let replay = VirtualChainReplay::start(
+                        rpc_core.service.clone(),
+                        connection.clone(),
+                        start_hash,
+                        include_accepted_transaction_ids,
+                    )
+                    .await?;
+                    connection.set_virtual_chain_replay(Some(replay));
+                    // The replay supersedes any regular subscription of the connection
+                    rpc_core.wrpc_notifier.clone().try_stop_notify(listener_id, scope)?;
+                }
+                Scope::VirtualChainChanged(_) => {
+                    connection.set_virtual_chain_replay(None);
+                    rpc_core.wrpc_notifier.clone().try_start_notify(listener_id, scope)?;
+                }
+                _ => {
+                    rpc_core.wrpc_notifier.clone().try_start_notify(listener_id, scope)?;
+                }
+            }
         } else {
             connection.grpc_client().start_notify(listener_id, scope).await?;
         }
@@ -202,6 +239,9 @@ impl Server {
         if let Some(listener_id) = connection.listener_id() {
             workflow_log::log_trace!("notification unsubscribe[0x{listener_id:x}] {scope:?}");
             if let Some(rpc_core) = &self.inner.rpc_core {
+                if let Scope::VirtualChainChanged(_) = scope {
+                    connection.set_virtual_chain_replay(None);
+                }
                 rpc_core.wrpc_notifier.clone().try_stop_notify(listener_id, scope)?;
             } else {
                 connection.grpc_client().stop_notify(listener_id, scope).await?;
@@ -8,7 +8,7 @@ use vecno_core::{
     trace, warn,
 };
 use vecno_rpc_core::api::ops::RpcApiOps;
-use vecno_rpc_service::service::RpcCoreService;
+use vecno_rpc_service::{counters::RpcOpCounters, service::RpcCoreService};
 use vecno_utils::triggers::SingleTrigger;
 use workflow_rpc::server::prelude::*;
 pub use workflow_rpc::server::{Encoding as WrpcEncoding, WebSocketConfig, WebSocketCounters};
@@ -53,9 +53,10 @@ impl VecnoRpcHandler {
         tasks: usize,
         encoding: WrpcEncoding,
         core_service: Option<Arc<RpcCoreService>>,
+        op_counters: Arc<RpcOpCounters>,
         options: Arc<Options>,
     ) -> VecnoRpcHandler {
-        VecnoRpcHandler { server: Server::new(tasks, encoding, core_service, options.clone()), options }
+        VecnoRpcHandler { server: Server::new(tasks, encoding, core_service, op_counters, options.clone()), options }
     }
 }
 
@@ -109,11 +110,12 @@ impl WrpcService {
         core_service: Option<Arc<RpcCoreService>>,
         encoding: &Encoding,
         counters: Arc<WebSocketCounters>,
+        op_counters: Arc<RpcOpCounters>,
         options: Options,
     ) -> Self {
         let options = Arc::new(options);
         // Create handle to manage connections
-        let rpc_handler = Arc::new(VecnoRpcHandler::new(tasks, *encoding, core_service, options.clone()));
+        let rpc_handler = Arc::new(VecnoRpcHandler::new(tasks, *encoding, core_service, op_counters, options.clone()));
 
         // Create router (initializes Interface registering RPC method and notification handlers)
         let router = Arc::new(Router::new(rpc_handler.server.clone()));
@@ -841,18 +841,64 @@ impl RpcClient {
         Ok(())
     }
 
+    /// Subscribe for a mempool transaction removed notification event.
+    /// Mempool transaction removed notification event is produced when a
+    /// transaction leaves the mempool without being accepted in the Vecno
+    /// BlockDAG. The event notification will be scoped to the provided list
+    /// of addresses.
+    #[wasm_bindgen(js_name = subscribeMempoolTransactionRemoved)]
+    pub async fn subscribe_mempool_transaction_removed(&self, addresses: AddressOrStringArrayT) -> Result<()> {
+        if let Some(listener_id) = self.listener_id() {
+            let addresses: Vec<Address> = addresses.try_into()?;
+            self.inner
+                .client
+                .start_notify(listener_id, Scope::MempoolTransactionRemoved(MempoolTransactionRemovedScope { addresses }))
+                .await?;
+        } else {
+            log_error!("RPC subscribe on a closed connection");
+        }
+
+        Ok(())
+    }
+
+    /// Unsubscribe from mempool transaction removed notification event
+    /// for a specific set of addresses.
+    #[wasm_bindgen(js_name = unsubscribeMempoolTransactionRemoved)]
+    pub async fn unsubscribe_mempool_transaction_removed(&self, addresses: AddressOrStringArrayT) -> Result<()> {
+        if let Some(listener_id) = self.listener_id() {
+            let addresses: Vec<Address> = addresses.try_into()?;
+            self.inner
+                .client
+                .stop_notify(listener_id, Scope::MempoolTransactionRemoved(MempoolTransactionRemovedScope { addresses }))
+                .await?;
+        } else {
+            log_error!("RPC unsubscribe on a closed connection");
+        }
+        Ok(())
+    }
+
     // TODO: scope variant with field functions
 
     /// Manage subscription for a virtual chain changed notification event.
     /// Virtual chain changed notification event is produced when the virtual
-    /// chain changes in the Vecno BlockDAG.
+    /// chain changes in the Vecno BlockDAG. If `start_hash` is provided, the
+    /// chain changes since this chain block are replayed first, so that a
+    /// client can resume its subscription after a disconnection.
     #[wasm_bindgen(js_name = subscribeVirtualChainChanged)]
-    pub async fn subscribe_virtual_chain_changed(&self, include_accepted_transaction_ids: bool) -> Result<()> {
+    pub async fn subscribe_virtual_chain_changed(
+        &self,
+        include_accepted_transaction_ids: bool,
+        start_hash: Option<String>,
+    ) -> Result<()> {
         if let Some(listener_id) = self.listener_id() {
-            self.inner
-                .client
-                .start_notify(listener_id, Scope::VirtualChainChanged(VirtualChainChangedScope { include_accepted_transaction_ids }))
-                .await?;
+            let scope = match start_hash {
+                Some(start_hash) => {
+                    let start_hash = RpcHash::from_str(&start_hash).map_err(|err| err.to_string())?;
+                    VirtualChainChangedScope::with_start_hash(include_accepted_transaction_ids, start_hash)
+                }
+                None => VirtualChainChangedScope::new(include_accepted_transaction_ids),
+            };
+            self.inner.client.start_notify(listener_id, Scope::VirtualChainChanged(scope)).await?;
         } else {
             log_error!("RPC subscribe on a closed connection");
         }
@@ -867,7 +913,7 @@ impl RpcClient {
         if let Some(listener_id) = self.listener_id() {
             self.inner
                 .client
-                .stop_notify(listener_id, Scope::VirtualChainChanged(VirtualChainChangedScope { include_accepted_transaction_ids }))
+                .stop_notify(listener_id, Scope::VirtualChainChanged(VirtualChainChangedScope::new(include_accepted_transaction_ids)))
                 .await?;
         } else {
             log_error!("RPC unsubscribe on a closed connection");
@@ -881,6 +927,7 @@ build_wrpc_wasm_bindgen_subscriptions!([
     // Manually implemented subscriptions (above)
     // - VirtualChainChanged, // can't used this here due to non-C-style enum variant
     // - UtxosChanged, // can't used this here due to non-C-style enum variant
+    // - MempoolTransactionRemoved, // can't used this here due to non-C-style enum variant
     // - VirtualDaaScoreChanged,
     /// Manage subscription for a block added notification event.
     /// Block added notification event is produced when a new
@@ -1008,6 +1055,13 @@ build_wrpc_wasm_bindgen_interface!(
         /// Generates a new block template for mining.
         /// Returned information: Block template information.
         GetBlockTemplate,
+        /// Retrieves the selected chain block with the highest DAA score not
+        /// exceeding the given DAA score.
+        /// Returned information: Chain block header.
+        GetChainBlockByDaaScore,
+        /// Retrieves the selected chain blocks within a blue score range.
+        /// Returned information: List of chain block headers.
+        GetChainBlocksInBlueScoreRange,
         /// Checks if block is blue or not.
         /// Returned information: Block blueness.
         GetCurrentBlockColor,
@@ -1017,6 +1071,8 @@ build_wrpc_wasm_bindgen_interface!(
         GetDaaScoreTimestampEstimate,
         /// Feerate estimates (experimental)
         GetFeeEstimateExperimental,
+        /// Feerate estimates based on the observed confirmation times of recent transactions
+        GetFeeEstimateHistorical,
         /// Retrieves block headers from the Vecno BlockDAG.
         /// Returned information: List of block headers.
         GetHeaders,
@@ -1032,6 +1088,19 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves information about a subnetwork in the Vecno BlockDAG.
         /// Returned information: Subnetwork information.
         GetSubnetwork,
+        /// Retrieves the location of an accepted transaction from the
+        /// transaction index. Requires the node to run with `--txindex`.
+        /// Returned information: Indexed transaction.
+        GetTransaction,
+        /// Retrieves a page of the history of a list of addresses from the
+        /// address index. Requires the node to run with `--addressindex`.
+        /// Returned information: List of address transactions and the
+        /// cursor of the next page.
+        GetTransactionsByAddresses,
+        /// Retrieves the locations of a list of accepted transactions from
+        /// the transaction index. Requires the node to run with `--txindex`.
+        /// Returned information: List of indexed transactions.
+        GetTransactionsByIds,
         /// Retrieves unspent transaction outputs (UTXOs) associated with
         /// specific addresses.
         /// Returned information: List of UTXOs.
@@ -1039,6 +1108,13 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves the virtual chain corresponding to a specified block hash.
         /// Returned information: Virtual chain information.
         GetVirtualChainFromBlock,
+        /// Retrieves the IP addresses banned by the Vecno node.
+        /// Returned information: Banned IP addresses along with the expiry time of their ban.
+        ListBanned,
+        /// Adjusts the fee a transaction is ranked by when selecting the
+        /// transactions of block templates, without changing the fee paid.
+        /// Returned information: Overall fee delta of the transaction.
+        PrioritiseTransaction,
         /// Resolves a finality conflict in the Vecno BlockDAG.
         /// Returned information: None.
         ResolveFinalityConflict,
@@ -1048,9 +1124,15 @@ build_wrpc_wasm_bindgen_interface!(
         /// Submits a transaction to the Vecno network.
         /// Returned information: Submitted Transaction Id.
         SubmitTransaction,
+        /// Submits an ordered package of transactions to the Vecno network, accepted or rejected as a whole.
+        /// Returned information: Submitted Transaction Ids, or the rejected transactions with the reason of their rejection.
+        SubmitTransactionPackage,
         /// Submits an RBF transaction to the Vecno network.
         /// Returned information: Submitted Transaction Id, Transaction that was replaced.
         SubmitTransactionReplacement,
+        /// Tests whether transactions would be accepted to the mempool, without adding them to it nor relaying them.
+        /// Returned information: Acceptance of each transaction along with its masses, fee and feerate, or the reason of its rejection.
+        TestMempoolAccept,
         /// Unbans a previously banned peer, allowing it to connect
         /// to the Vecno node again.
         /// Returned information: None.
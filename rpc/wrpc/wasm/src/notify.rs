@@ -25,6 +25,7 @@ export enum RpcEventType {
     VirtualDaaScoreChanged = "virtual-daa-score-changed",
     PruningPointUtxoSetOverride = "pruning-point-utxo-set-override",
     NewBlockTemplate = "new-block-template",
+    MempoolTransactionRemoved = "mempool-transaction-removed",
 }
 
 /**
@@ -40,7 +41,8 @@ export type RpcEventData = IBlockAdded
     | ISinkBlueScoreChanged 
     | IVirtualDaaScoreChanged 
     | IPruningPointUtxoSetOverride 
-    | INewBlockTemplate;
+    | INewBlockTemplate 
+    | IMempoolTransactionRemoved;
 
 /**
  * RPC notification event data map.
@@ -59,6 +61,7 @@ export type RpcEventMap = {
     "virtual-daa-score-changed" : IVirtualDaaScoreChanged,
     "pruning-point-utxo-set-override" : IPruningPointUtxoSetOverride,
     "new-block-template" : INewBlockTemplate,
+    "mempool-transaction-removed" : IMempoolTransactionRemoved,
 }
 
 /**
@@ -84,6 +87,7 @@ export type RpcEvent = {
  * {@link RpcClient.subscribeSinkBlueScoreChanged},
  * {@link RpcClient.subscribePruningPointUtxoSetOverride},
  * {@link RpcClient.subscribeNewBlockTemplate},
+ * {@link RpcClient.subscribeMempoolTransactionRemoved},
  * 
  * @category Node RPC
  */
@@ -241,3 +245,22 @@ declare! {
     }
     "#,
 }
+
+declare! {
+    IMempoolTransactionRemoved,
+    r#"
+    /**
+     * Mempool transaction removed notification event is produced when a
+     * transaction leaves the mempool without being accepted in the Vecno
+     * BlockDAG. The event notification is scoped to the monitored list of
+     * addresses specified during the subscription.
+     * 
+     * @category Node RPC
+     */
+    export interface IMempoolTransactionRemoved {
+        transactionId : HexString;
+        reason : MempoolRemovalReason;
+        addresses : string[];
+    }
+    "#,
+}
@@ -962,6 +962,8 @@ async fn json_test(file_path: &str, concurrency: bool) {
         &notify_service.notifier(),
         subscription_context.clone(),
         Some(UtxoIndexProxy::new(utxoindex.clone())),
+        None,
+        None,
     ));
 
     let async_runtime = Arc::new(AsyncRuntime::new(2));
@@ -1709,6 +1711,52 @@ async fn selected_chain_test() {
     consensus.shutdown(wait_handles);
 }
 
+#[tokio::test]
+async fn selected_chain_score_lookup_test() {
+    init_allocator_with_default_settings();
+    vecno_core::log::try_init_logger("info");
+
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .edit_consensus_params(|p| {
+            p.min_difficulty_window_len = p.legacy_difficulty_window_size;
+        })
+        .build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    consensus.add_utxo_valid_block_with_parents(1.into(), vec![config.genesis.hash], vec![]).await.unwrap();
+    for i in 2..10 {
+        consensus.add_utxo_valid_block_with_parents(i.into(), vec![(i - 1).into()], vec![]).await.unwrap();
+    }
+    // Merge a side block so that the chain DAA scores do not simply increase by one
+    consensus.add_utxo_valid_block_with_parents(10.into(), vec![4.into()], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(11.into(), vec![9.into(), 10.into()], vec![]).await.unwrap();
+
+    let chain = (0..=10).map(|i| consensus.selected_chain_store.read().get_by_index(i).unwrap()).collect_vec();
+    let headers = chain.iter().map(|&hash| consensus.get_header(hash).unwrap()).collect_vec();
+
+    // Every DAA score between two consecutive chain blocks maps to the lower one
+    for pair in headers.windows(2) {
+        assert_eq!(consensus.get_chain_block_by_daa_score(pair[0].daa_score).unwrap().hash, pair[0].hash);
+        assert_eq!(consensus.get_chain_block_by_daa_score(pair[1].daa_score - 1).unwrap().hash, pair[0].hash);
+    }
+    let sink_header = headers.last().unwrap();
+    assert_eq!(consensus.get_chain_block_by_daa_score(sink_header.daa_score).unwrap().hash, sink_header.hash);
+    assert!(consensus.get_chain_block_by_daa_score(sink_header.daa_score + 1).is_err());
+
+    let range_hashes = |low, high, limit| {
+        consensus.get_chain_blocks_in_blue_score_range(low, high, limit).unwrap().iter().map(|header| header.hash).collect_vec()
+    };
+    assert_eq!(range_hashes(0, u64::MAX, usize::MAX), chain);
+    assert_eq!(range_hashes(headers[2].blue_score, headers[5].blue_score, usize::MAX), chain[2..=5]);
+    assert_eq!(range_hashes(headers[2].blue_score + 1, headers[5].blue_score, usize::MAX), chain[3..=5]);
+    assert_eq!(range_hashes(headers[2].blue_score, u64::MAX, 2), chain[2..4]);
+    assert!(range_hashes(sink_header.blue_score + 1, u64::MAX, usize::MAX).is_empty());
+
+    consensus.shutdown(wait_handles);
+}
+
 fn assert_selected_chain_store_matches_virtual_chain(consensus: &TestConsensus) {
     let pruning_point = consensus.pruning_point();
     let iter1 = selected_chain_store_iterator(consensus, pruning_point);
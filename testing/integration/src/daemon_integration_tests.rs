@@ -5,18 +5,30 @@ use crate::common::{
     utils::{fetch_spendable_utxos, generate_tx, mine_block, wait_for},
 };
 use rand::thread_rng;
-use std::{sync::Arc, time::Duration};
-use vecno_addresses::Address;
+use std::{
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::net::TcpListener;
+use vecno_addresses::{Address, Prefix};
 use vecno_alloc::init_allocator_with_default_settings;
-use vecno_consensus::params::SIMNET_PARAMS;
-use vecno_consensus_core::header::Header;
+use vecno_consensus::params::{MAINNET_PARAMS, SIMNET_GENESIS, SIMNET_PARAMS};
+use vecno_consensus_core::{config::ConfigBuilder, errors::config::ConfigError, header::Header, tx::Transaction};
 use vecno_consensusmanager::ConsensusManager;
 use vecno_core::{task::runtime::AsyncRuntime, trace};
 use vecno_grpc_client::GrpcClient;
-use vecno_notify::scope::{BlockAddedScope, UtxosChangedScope, VirtualDaaScoreChangedScope};
-use vecno_rpc_core::{api::rpc::RpcApi, Notification, RpcTransactionId};
+use vecno_notify::scope::{BlockAddedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope};
+use vecno_rpc_core::{api::rpc::RpcApi, Notification, RpcHash, RpcTransactionId};
 use vecno_txscript::pay_to_address_script;
-use vecnod_lib::args::Args;
+use vecno_utils::networking::ContextualNetAddress;
+use vecnod_lib::{
+    args::Args,
+    daemon::{seed_peers, validate_args},
+};
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn daemon_sanity_test() {
@@ -121,6 +133,198 @@ async fn daemon_mining_test() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_cpu_miner_test() {
+    init_allocator_with_default_settings();
+    vecno_core::log::try_init_logger("INFO");
+
+    let pay_address = Address::new(Prefix::Simnet, vecno_addresses::Version::PubKey, &[0; 32]);
+    let args = Args {
+        simnet: true,
+        unsafe_rpc: true,
+        enable_unsynced_mining: true,
+        disable_upnp: true, // UPnP registration might take some time and is not needed for this test
+        mine_to: Some(pay_address.to_string()),
+        ..Default::default()
+    };
+    let total_fd_limit = 10;
+
+    let mut vecnod = Daemon::new_random_with_args(args, total_fd_limit);
+    let rpc_client = vecnod.start().await;
+
+    // No block gets submitted through RPC, so any block must have been mined by the CPU miner
+    let check_client = rpc_client.clone();
+    wait_for(
+        100,
+        100,
+        move || {
+            async fn block_mined(client: GrpcClient) -> bool {
+                client.get_block_dag_info().await.unwrap().block_count > 0
+            }
+            Box::pin(block_mined(check_client.clone()))
+        },
+        "the CPU miner did not mine any block",
+    )
+    .await;
+
+    let sink = rpc_client.get_block_dag_info().await.unwrap().sink;
+    let block = rpc_client.get_block(sink, true).await.unwrap();
+    assert!(block.transactions[0].payload.ends_with(b"/cpu-miner"), "the coinbase of the mined block lacks the miner extra data");
+
+    rpc_client.disconnect().await.unwrap();
+    drop(rpc_client);
+    vecnod.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_proxied_node_test() {
+    init_allocator_with_default_settings();
+    vecno_core::log::try_init_logger("INFO");
+
+    // DNS seeds are resolved locally, outside of the proxy
+    let mainnet_config = ConfigBuilder::new(MAINNET_PARAMS).build();
+    let proxied_args = Args { proxy: Some(ContextualNetAddress::from_str("127.0.0.1:9050").unwrap()), ..Default::default() };
+    assert!(matches!(validate_args(&proxied_args), Err(ConfigError::DnsSeedingWithProxy)));
+    assert!(!seed_peers(&Args::default(), &mainnet_config).is_empty());
+    assert!(seed_peers(&proxied_args, &mainnet_config).is_empty());
+    assert!(validate_args(&Args { disable_dns_seeding: true, ..proxied_args }).is_ok());
+    // Neither can host-name peers be requested, the P2P adaptor of a proxied node refusing them as well
+    assert!(ContextualNetAddress::from_str("localhost:16111").is_err());
+
+    // A stand-in proxy counting the connections made through it, without relaying them
+    let proxy = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let proxy_address = proxy.local_addr().unwrap();
+    let proxied_connections = Arc::new(AtomicUsize::new(0));
+    let counter = proxied_connections.clone();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = proxy.accept().await {
+            counter.fetch_add(1, Ordering::SeqCst);
+            drop(stream);
+        }
+    });
+
+    let total_fd_limit = 10;
+    let args = Args { simnet: true, unsafe_rpc: true, disable_upnp: true, ..Default::default() };
+    let mut vecnod1 = Daemon::new_random_with_args(args.clone(), total_fd_limit);
+    let rpc_client1 = vecnod1.start().await;
+
+    // The proxied node reaches its peer through the proxy only, which never relays the connection
+    let args = Args {
+        proxy: Some(ContextualNetAddress::from_str(&proxy_address.to_string()).unwrap()),
+        disable_dns_seeding: true,
+        add_peers: vec![ContextualNetAddress::from_str(&format!("127.0.0.1:{}", vecnod1.p2p_port)).unwrap()],
+        ..args
+    };
+    let mut vecnod2 = Daemon::new_random_with_args(args, total_fd_limit);
+    let rpc_client2 = vecnod2.start().await;
+
+    let check_connections = proxied_connections.clone();
+    wait_for(
+        100,
+        100,
+        move || {
+            let connections = check_connections.load(Ordering::SeqCst);
+            Box::pin(async move { connections > 0 })
+        },
+        "the proxied node did not connect through the proxy",
+    )
+    .await;
+    assert!(rpc_client1.get_connected_peer_info().await.unwrap().peer_info.is_empty());
+    assert!(rpc_client2.get_connected_peer_info().await.unwrap().peer_info.is_empty());
+
+    rpc_client1.disconnect().await.unwrap();
+    rpc_client2.disconnect().await.unwrap();
+    drop(rpc_client1);
+    drop(rpc_client2);
+    vecnod1.shutdown();
+    vecnod2.shutdown();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_virtual_chain_replay_test() {
+    init_allocator_with_default_settings();
+    vecno_core::log::try_init_logger("INFO");
+
+    let args = Args {
+        simnet: true,
+        unsafe_rpc: true,
+        enable_unsynced_mining: true,
+        disable_upnp: true, // UPnP registration might take some time and is not needed for this test
+        ..Default::default()
+    };
+    let total_fd_limit = 10;
+
+    let mut vecnod = Daemon::new_random_with_args(args, total_fd_limit);
+    let rpc_client = vecnod.start().await;
+    let pay_address = Address::new(vecnod.network.into(), vecno_addresses::Version::PubKey, &[0; 32]);
+
+    // Mine blocks before any subscription
+    let first_blocks = mine_chain_blocks(&rpc_client, &pay_address, 5).await;
+
+    // A subscription from genesis gets the blocks mined so far replayed
+    let subscriber = vecnod.new_client().await;
+    let (sender, event_receiver) = async_channel::unbounded();
+    subscriber.start(Some(Arc::new(ChannelNotify::new(sender)))).await;
+    let scope = VirtualChainChangedScope::with_start_hash(false, SIMNET_GENESIS.hash);
+    subscriber.start_notify(Default::default(), scope.into()).await.unwrap();
+    assert_eq!(receive_chain_blocks(&event_receiver, first_blocks.len()).await, first_blocks);
+
+    // Blocks mined while the subscriber is disconnected get replayed once it resumes from the last chain block it got
+    subscriber.disconnect().await.unwrap();
+    drop(subscriber);
+    let missed_blocks = mine_chain_blocks(&rpc_client, &pay_address, 3).await;
+
+    let subscriber = vecnod.new_client().await;
+    let (sender, event_receiver) = async_channel::unbounded();
+    subscriber.start(Some(Arc::new(ChannelNotify::new(sender)))).await;
+    let scope = VirtualChainChangedScope::with_start_hash(false, *first_blocks.last().unwrap());
+    subscriber.start_notify(Default::default(), scope.into()).await.unwrap();
+    assert_eq!(receive_chain_blocks(&event_receiver, missed_blocks.len()).await, missed_blocks);
+
+    // Followed by the live chain changes
+    let live_blocks = mine_chain_blocks(&rpc_client, &pay_address, 2).await;
+    assert_eq!(receive_chain_blocks(&event_receiver, live_blocks.len()).await, live_blocks);
+
+    subscriber.disconnect().await.unwrap();
+    drop(subscriber);
+    rpc_client.disconnect().await.unwrap();
+    drop(rpc_client);
+    vecnod.shutdown();
+}
+
+/// Mines `count` blocks on top of each other, returning their hashes
+async fn mine_chain_blocks(client: &GrpcClient, pay_address: &Address, count: usize) -> Vec<RpcHash> {
+    let mut hashes = Vec::with_capacity(count);
+    for _ in 0..count {
+        let template = client.get_block_template(pay_address.clone(), vec![]).await.unwrap();
+        let header: Header = (&template.block.header).into();
+        client.submit_block(template.block, false).await.unwrap();
+        // Wait for the block to become the sink so that the next template builds on it
+        while client.get_block_dag_info().await.unwrap().sink != header.hash {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        hashes.push(header.hash);
+    }
+    hashes
+}
+
+/// Receives virtual chain changed notifications until `count` chain blocks got added, returning them
+async fn receive_chain_blocks(event_receiver: &async_channel::Receiver<Notification>, count: usize) -> Vec<RpcHash> {
+    let mut added = Vec::with_capacity(count);
+    while added.len() < count {
+        match tokio::time::timeout(Duration::from_secs(5), event_receiver.recv()).await {
+            Ok(Ok(Notification::VirtualChainChanged(msg))) => {
+                assert!(msg.removed_chain_block_hashes.is_empty(), "no chain block is expected to be removed");
+                added.extend(msg.added_chain_block_hashes.iter().copied());
+            }
+            Ok(Ok(notification)) => panic!("expected only virtual chain notifications, got {}", notification),
+            Ok(Err(err)) => panic!("the notification channel closed: {}", err),
+            Err(elapsed) => panic!("expected virtual chain notifications before {}", elapsed),
+        }
+    }
+    added
+}
+
 /// `cargo test --release --package vecno-testing-integration --lib -- daemon_integration_tests::daemon_utxos_propagation_test`
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn daemon_utxos_propagation_test() {
@@ -360,3 +564,140 @@ async fn daemon_cleaning_test() {
     assert_eq!(async_runtime.strong_count(), 0);
     assert_eq!(core.strong_count(), 0);
 }
+
+/// Starts simnet nodes relaying transactions with Dandelion++, each with its own fluff probability and embargo in seconds,
+/// and opens the outbound connections `(from, to)` between them. Coins are then mined to the first node until some
+/// mature, and the nodes are returned along with their RPC clients and a transaction spending some of these coins.
+async fn start_dandelion_nodes(nodes: &[(f64, u64)], connections: &[(usize, usize)]) -> (Vec<Daemon>, Vec<GrpcClient>, Transaction) {
+    let total_fd_limit = 10;
+    let mut daemons = nodes
+        .iter()
+        .map(|&(fluff_probability, embargo_sec)| {
+            let args = Args {
+                simnet: true,
+                unsafe_rpc: true,
+                enable_unsynced_mining: true,
+                disable_upnp: true,
+                utxoindex: true,
+                dandelion: true,
+                dandelion_fluff_probability: fluff_probability,
+                dandelion_embargo_sec: embargo_sec,
+                ..Default::default()
+            };
+            Daemon::new_random_with_args(args, total_fd_limit)
+        })
+        .collect::<Vec<_>>();
+    let mut rpc_clients = vec![];
+    for daemon in daemons.iter_mut() {
+        rpc_clients.push(daemon.start().await);
+    }
+
+    for &(from, to) in connections {
+        rpc_clients[from].add_peer(format!("127.0.0.1:{}", daemons[to].p2p_port).try_into().unwrap(), true).await.unwrap();
+    }
+    for (index, client) in rpc_clients.iter().enumerate() {
+        let peer_count = connections.iter().filter(|&&(from, to)| from == index || to == index).count();
+        let check_client = client.clone();
+        wait_for(
+            50,
+            40,
+            move || {
+                async fn peers_connected(client: GrpcClient, peer_count: usize) -> bool {
+                    client.get_connected_peer_info().await.unwrap().peer_info.len() >= peer_count
+                }
+                Box::pin(peers_connected(check_client.clone(), peer_count))
+            },
+            "the nodes did not connect to each other",
+        )
+        .await;
+    }
+
+    let (miner_sk, miner_pk) = secp256k1::generate_keypair(&mut thread_rng());
+    let miner_address =
+        Address::new(daemons[0].network.into(), vecno_addresses::Version::PubKey, &miner_pk.x_only_public_key().0.serialize());
+    let miner_schnorr_key = secp256k1::Keypair::from_secret_key(secp256k1::SECP256K1, &miner_sk);
+
+    let mut clients = vec![];
+    for daemon in daemons.iter() {
+        let mut client = ListeningClient::connect(daemon).await;
+        client.start_notify(BlockAddedScope {}.into()).await.unwrap();
+        client.start_notify(VirtualDaaScoreChangedScope {}.into()).await.unwrap();
+        clients.push(client);
+    }
+    for _ in 0..SIMNET_PARAMS.coinbase_maturity + 2 {
+        mine_block(miner_address.clone(), &rpc_clients[0], &clients).await;
+    }
+    for client in clients.iter() {
+        client.disconnect().await.unwrap();
+        client.join().await.unwrap();
+    }
+
+    let utxos = fetch_spendable_utxos(&rpc_clients[0], miner_address.clone(), SIMNET_PARAMS.coinbase_maturity).await;
+    assert!(!utxos.is_empty());
+    let transaction = generate_tx(miner_schnorr_key, &utxos[0..1], utxos[0].1.amount / 2, 1, &miner_address);
+    (daemons, rpc_clients, transaction)
+}
+
+async fn transaction_in_mempools(clients: Vec<GrpcClient>, transaction_id: RpcTransactionId) -> bool {
+    for client in clients {
+        if client.get_mempool_entry(transaction_id, false, false).await.is_err() {
+            return false;
+        }
+    }
+    true
+}
+
+/// `cargo test --release --package vecno-testing-integration --lib -- daemon_integration_tests::daemon_dandelion_embargo_test`
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_dandelion_embargo_test() {
+    vecno_core::log::try_init_logger("INFO");
+
+    // Nodes connected in a ring which never diffuse stem transactions, so that a transaction stays in the stem
+    // phase until it gets back to its origin, and is only diffused once an embargo expires
+    const EMBARGO_SEC: u64 = 3;
+    let (_daemons, rpc_clients, transaction) =
+        start_dandelion_nodes(&[(0.0, EMBARGO_SEC), (0.0, EMBARGO_SEC), (0.0, EMBARGO_SEC)], &[(0, 1), (1, 2), (2, 0)]).await;
+
+    let transaction_id = transaction.id();
+    rpc_clients[0].submit_transaction((&transaction).into(), false).await.unwrap();
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    for client in rpc_clients.iter() {
+        assert!(!transaction_in_mempools(vec![client.clone()], transaction_id).await);
+    }
+
+    wait_for(
+        100,
+        100,
+        move || Box::pin(transaction_in_mempools(rpc_clients.clone(), transaction_id)),
+        "the transaction was not diffused once its embargo expired",
+    )
+    .await;
+}
+
+/// `cargo test --release --package vecno-testing-integration --lib -- daemon_integration_tests::daemon_dandelion_stem_test`
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_dandelion_stem_test() {
+    vecno_core::log::try_init_logger("INFO");
+
+    // The origin stems the transaction to its outbound peer, which always diffuses the stem transactions it
+    // receives, long before the embargo of the origin expires
+    let (_daemons, rpc_clients, transaction) = start_dandelion_nodes(&[(0.0, 600), (1.0, 600)], &[(0, 1)]).await;
+
+    let transaction_id = transaction.id();
+    rpc_clients[0].submit_transaction((&transaction).into(), false).await.unwrap();
+    let check_clients = vec![rpc_clients[1].clone()];
+    wait_for(
+        100,
+        50,
+        move || Box::pin(transaction_in_mempools(check_clients.clone(), transaction_id)),
+        "the stem transaction was not diffused by the outbound peer",
+    )
+    .await;
+    wait_for(
+        100,
+        50,
+        move || Box::pin(transaction_in_mempools(rpc_clients.clone(), transaction_id)),
+        "the diffused transaction did not reach its origin",
+    )
+    .await;
+}
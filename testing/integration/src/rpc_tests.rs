@@ -6,14 +6,15 @@ use tokio::task::JoinHandle;
 use vecno_addresses::{Address, Prefix, Version};
 use vecno_consensus::params::SIMNET_GENESIS;
 use vecno_consensus_core::{constants::MAX_SOMPI, header::Header, subnets::SubnetworkId, tx::Transaction};
-use vecno_core::{assert_match, info};
+use vecno_core::{assert_match, info, time::unix_now};
 use vecno_grpc_core::ops::VecnodPayloadOps;
 use vecno_hashes::Hash;
 use vecno_notify::{
     connection::{ChannelConnection, ChannelType},
     scope::{
-        BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
-        SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
+        BlockAddedScope, FinalityConflictScope, MempoolTransactionRemovedScope, NewBlockTemplateScope,
+        PruningPointUtxoSetOverrideScope, Scope, SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope,
+        VirtualDaaScoreChangedScope,
     },
 };
 use vecno_rpc_core::{api::rpc::RpcApi, model::*, Notification};
@@ -49,6 +50,8 @@ async fn sanity_test() {
         enable_unsynced_mining: true,
         block_template_cache_lifetime: Some(0),
         utxoindex: true,
+        txindex: true,
+        addressindex: true,
         unsafe_rpc: true,
         ..Default::default()
     };
@@ -109,6 +112,7 @@ async fn sanity_test() {
                             GetBlockTemplateRequest {
                                 pay_address: Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]),
                                 extra_data: Vec::new(),
+                                template_policy: None,
                             },
                         )
                         .await
@@ -273,11 +277,15 @@ async fn sanity_test() {
                     let response = rpc_client
                         .get_mempool_entries_call(
                             None,
-                            GetMempoolEntriesRequest { include_orphan_pool: true, filter_transaction_pool: false },
+                            GetMempoolEntriesRequest::new(true, false)
+                                .with_page(0, 10)
+                                .with_sort(RpcMempoolEntrySortKey::Feerate, true)
+                                .with_min_feerate(1.0),
                         )
                         .await
                         .unwrap();
                     assert!(response.mempool_entries.is_empty());
+                    assert_eq!(response.total_count, 0);
                 })
             }
 
@@ -314,10 +322,15 @@ async fn sanity_test() {
 
                     let response = rpc_client.get_peer_addresses_call(None, GetPeerAddressesRequest {}).await.unwrap();
                     assert!(response.banned_addresses.contains(&ip));
+                    let response = rpc_client.list_banned_call(None, ListBannedRequest {}).await.unwrap();
+                    let banned_address = response.banned_addresses.iter().find(|x| x.ip == ip).unwrap();
+                    assert!(banned_address.banned_until > unix_now());
 
                     let _ = rpc_client.unban_call(None, UnbanRequest { ip }).await.unwrap();
                     let response = rpc_client.get_peer_addresses_call(None, GetPeerAddressesRequest {}).await.unwrap();
                     assert!(!response.banned_addresses.contains(&ip));
+                    let response = rpc_client.list_banned_call(None, ListBannedRequest {}).await.unwrap();
+                    assert!(response.banned_addresses.iter().all(|x| x.ip != ip));
                 })
             }
 
@@ -325,6 +338,10 @@ async fn sanity_test() {
                 tst!(op, "see Ban")
             }
 
+            VecnodPayloadOps::ListBanned => {
+                tst!(op, "see Ban")
+            }
+
             VecnodPayloadOps::SubmitTransaction => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -347,6 +364,38 @@ async fn sanity_test() {
                 })
             }
 
+            VecnodPayloadOps::SubmitTransactionPackage => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // An empty package...
+                    let result = rpc_client.submit_transaction_package(vec![]).await;
+                    // ...is rejected as a whole
+                    assert!(result.is_err());
+
+                    // Build an erroneous transaction...
+                    let transaction = Transaction::new(0, vec![], vec![], 0, SubnetworkId::default(), 0, vec![]);
+                    let response = rpc_client.submit_transaction_package(vec![(&transaction).into()]).await.unwrap();
+                    // ...that gets rejected by the consensus, along with its package
+                    assert!(response.transaction_ids.is_empty());
+                    assert_eq!(response.rejections.len(), 1);
+                    assert_eq!(response.rejections[0].transaction_id, transaction.id());
+                })
+            }
+
+            VecnodPayloadOps::TestMempoolAccept => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Build an erroneous transaction...
+                    let transaction = Transaction::new(0, vec![], vec![], 0, SubnetworkId::default(), 0, vec![]);
+                    let acceptances = rpc_client.test_mempool_accept(vec![(&transaction).into()], false, false).await.unwrap();
+                    // ...that would be rejected by the consensus
+                    assert_eq!(acceptances.len(), 1);
+                    assert_eq!(acceptances[0].transaction_id, transaction.id());
+                    assert!(!acceptances[0].accepted);
+                    assert!(acceptances[0].error.is_some());
+                })
+            }
+
             VecnodPayloadOps::GetSubnetwork => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -520,6 +569,7 @@ async fn sanity_test() {
                                 process_metrics: true,
                                 storage_metrics: true,
                                 custom_metrics: true,
+                                stratum_metrics: false,
                             },
                         )
                         .await
@@ -537,6 +587,7 @@ async fn sanity_test() {
                                 process_metrics: true,
                                 storage_metrics: true,
                                 custom_metrics: true,
+                                stratum_metrics: false,
                             },
                         )
                         .await
@@ -554,6 +605,7 @@ async fn sanity_test() {
                                 process_metrics: false,
                                 storage_metrics: false,
                                 custom_metrics: true,
+                                stratum_metrics: false,
                             },
                         )
                         .await
@@ -571,6 +623,7 @@ async fn sanity_test() {
                                 process_metrics: false,
                                 storage_metrics: false,
                                 custom_metrics: true,
+                                stratum_metrics: false,
                             },
                         )
                         .await
@@ -642,6 +695,27 @@ async fn sanity_test() {
                 })
             }
 
+            VecnodPayloadOps::GetFeeEstimateHistorical => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let estimate = rpc_client.get_fee_estimate_historical(0).await.unwrap();
+                    // No transaction went through the mempool of the test node
+                    assert_eq!(estimate.sample_count, 0);
+                    assert!(estimate.buckets.is_empty());
+                })
+            }
+
+            VecnodPayloadOps::PrioritiseTransaction => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Deltas add up, including for transactions not in the mempool yet
+                    let transaction_id = Hash::from_u64_word(158);
+                    assert_eq!(rpc_client.prioritise_transaction(transaction_id, 1_000).await.unwrap(), 1_000);
+                    assert_eq!(rpc_client.prioritise_transaction(transaction_id, -1_500).await.unwrap(), -500);
+                    assert_eq!(rpc_client.prioritise_transaction(transaction_id, 500).await.unwrap(), 0);
+                })
+            }
+
             VecnodPayloadOps::GetFeeEstimateExperimental => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -656,6 +730,59 @@ async fn sanity_test() {
                 })
             }
 
+            VecnodPayloadOps::GetTransaction => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Unknown transactions are reported as not found
+                    let result = rpc_client.get_transaction(0.into(), true).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            VecnodPayloadOps::GetTransactionsByIds => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Unknown transactions are omitted from the response
+                    let response = rpc_client.get_transactions_by_ids(vec![0.into(), 1.into()], false).await.unwrap();
+                    assert!(response.is_empty());
+                })
+            }
+
+            VecnodPayloadOps::GetTransactionsByAddresses => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let addresses = vec![Address::new(Prefix::Simnet, Version::PubKey, &[1u8; 32])];
+                    let response = rpc_client.get_transactions_by_addresses(addresses, None, 10).await.unwrap();
+                    assert!(response.entries.is_empty());
+                    assert!(response.next_cursor.is_none());
+                })
+            }
+
+            VecnodPayloadOps::GetChainBlockByDaaScore => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let header = rpc_client.get_chain_block_by_daa_score(SIMNET_GENESIS.daa_score).await.unwrap();
+                    assert_eq!(header.hash, SIMNET_GENESIS.hash);
+
+                    // DAA scores not yet reached by the selected chain are rejected
+                    let result = rpc_client.get_chain_block_by_daa_score(u64::MAX).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            VecnodPayloadOps::GetChainBlocksInBlueScoreRange => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_chain_blocks_in_blue_score_range(0, u64::MAX).await.unwrap();
+                    assert_eq!(response.headers.first().map(|header| header.hash), Some(SIMNET_GENESIS.hash));
+                    assert!(response.headers.windows(2).all(|pair| pair[0].blue_score < pair[1].blue_score));
+                    assert_eq!(response.next_low_blue_score, None);
+
+                    let response = rpc_client.get_chain_blocks_in_blue_score_range(u64::MAX, u64::MAX).await.unwrap();
+                    assert!(response.headers.is_empty());
+                })
+            }
+
             VecnodPayloadOps::NotifyBlockAdded => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -711,10 +838,14 @@ async fn sanity_test() {
                 let rpc_client = client.clone();
                 let id = listener_id;
                 tst!(op, {
-                    rpc_client
-                        .start_notify(id, VirtualChainChangedScope { include_accepted_transaction_ids: false }.into())
-                        .await
-                        .unwrap();
+                    rpc_client.start_notify(id, VirtualChainChangedScope::new(false).into()).await.unwrap();
+                })
+            }
+            VecnodPayloadOps::NotifyMempoolTransactionRemoved => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, MempoolTransactionRemovedScope::new(vec![]).into()).await.unwrap();
                 })
             }
             VecnodPayloadOps::StopNotifyingUtxosChanged => {
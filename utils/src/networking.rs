@@ -2,12 +2,14 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use ipnet::IpNet;
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
 use std::{
     fmt::Display,
     net::{AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
     ops::Deref,
     str::FromStr,
 };
+use thiserror::Error;
 use uuid::Uuid;
 use wasm_bindgen::prelude::*;
 
@@ -28,6 +30,10 @@ const TS_IP_ADDRESS: &'static str = r#"
          * Optional port number.
          */
         port?: number;
+        /**
+         * Optional onion v3 address, in which case `ip` is the virtual IP of the onion service.
+         */
+        onion?: string;
     }
 "#;
 
@@ -35,6 +41,7 @@ const TS_IP_ADDRESS: &'static str = r#"
 /// for ipv4 it consists of 6 leading zero bytes, and the first two octets,
 /// for ipv6 it consists of the first 8 octets,
 /// encoded into a big endian u64.
+/// All onion services share the bucket of the OnionCat prefix.
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
 pub struct PrefixBucket(u64);
 
@@ -66,6 +73,11 @@ impl From<&IpAddress> for PrefixBucket {
 
 impl From<&NetAddress> for PrefixBucket {
     fn from(net_address: &NetAddress) -> Self {
+        if net_address.is_onion() {
+            let mut prefix_bytes = [0u8; 8];
+            prefix_bytes[..ONION_IP_PREFIX.len()].copy_from_slice(&ONION_IP_PREFIX);
+            return Self(u64::from_be_bytes(prefix_bytes));
+        }
         Self::from(&net_address.ip)
     }
 }
@@ -128,6 +140,14 @@ impl IpAddress {
     pub fn prefix_bucket(&self) -> PrefixBucket {
         PrefixBucket::from(self)
     }
+
+    /// Whether the IP falls in the OnionCat range of the virtual IPs given to onion services
+    pub fn is_onion(&self) -> bool {
+        match self.0 {
+            IpAddr::V4(_) => false,
+            IpAddr::V6(ip) => ip.octets().starts_with(&ONION_IP_PREFIX),
+        }
+    }
 }
 
 impl From<IpAddr> for IpAddress {
@@ -218,16 +238,204 @@ impl BorshDeserialize for IpAddress {
     }
 }
 
-/// A network address, equivalent of a [SocketAddr].
+/// The OnionCat prefix fd87:d87e:eb43::/48 of the virtual IPs given to onion services
+const ONION_IP_PREFIX: [u8; 6] = [0xfd, 0x87, 0xd8, 0x7e, 0xeb, 0x43];
+
+/// Length of the host of onion v3 addresses, which encodes the public key, a checksum and the version in base32
+const ONION_HOST_LEN: usize = 56;
+
+const ONION_VERSION: u8 = 3;
+
+const ONION_DOMAIN: &str = ".onion";
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum NetAddressParseError {
+    #[error(transparent)]
+    AddrParseError(#[from] AddrParseError),
+
+    #[error("invalid onion address {0}")]
+    InvalidOnionAddress(String),
+
+    #[error("missing port in address {0}")]
+    MissingPort(String),
+}
+
+/// The address of a Tor onion service (v3), which is the ed25519 public key of the service.
+///
+/// Since onion services have no IP, each one is given a virtual IPv6 in the OnionCat range standing for it
+/// wherever peers are identified by IP, like peer keys or bans.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct OnionAddress([u8; 32]);
+
+impl OnionAddress {
+    pub fn new(public_key: [u8; 32]) -> Self {
+        Self(public_key)
+    }
+
+    pub fn public_key(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// The virtual IP of the onion service, made of the OnionCat prefix followed by the first bytes of the public key
+    pub fn virtual_ip(&self) -> IpAddress {
+        let mut octets = [0u8; 16];
+        octets[..ONION_IP_PREFIX.len()].copy_from_slice(&ONION_IP_PREFIX);
+        octets[ONION_IP_PREFIX.len()..].copy_from_slice(&self.0[..16 - ONION_IP_PREFIX.len()]);
+        Ipv6Addr::from(octets).into()
+    }
+
+    fn checksum(public_key: &[u8; 32]) -> [u8; 2] {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b".onion checksum");
+        hasher.update(public_key);
+        hasher.update([ONION_VERSION]);
+        let hash = hasher.finalize();
+        [hash[0], hash[1]]
+    }
+}
+
+impl FromStr for OnionAddress {
+    type Err = NetAddressParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || NetAddressParseError::InvalidOnionAddress(s.to_string());
+        let host = s.strip_suffix(ONION_DOMAIN).ok_or_else(invalid)?;
+        if host.len() != ONION_HOST_LEN {
+            return Err(invalid());
+        }
+
+        // Base32 decoding of the host, 56 characters of 5 bits making up exactly 35 bytes
+        let mut bytes = [0u8; 35];
+        let (mut buffer, mut bits, mut index) = (0u32, 0u32, 0usize);
+        for c in host.bytes() {
+            let value = BASE32_ALPHABET.iter().position(|&a| a == c.to_ascii_lowercase()).ok_or_else(invalid)?;
+            buffer = (buffer << 5) | value as u32;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                bytes[index] = (buffer >> bits) as u8;
+                index += 1;
+            }
+        }
+
+        let public_key: [u8; 32] = bytes[..32].try_into().unwrap();
+        if bytes[34] != ONION_VERSION || bytes[32..34] != Self::checksum(&public_key) {
+            return Err(invalid());
+        }
+        Ok(Self(public_key))
+    }
+}
+
+impl Display for OnionAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut bytes = [0u8; 35];
+        bytes[..32].copy_from_slice(&self.0);
+        bytes[32..34].copy_from_slice(&Self::checksum(&self.0));
+        bytes[34] = ONION_VERSION;
+
+        let mut host = String::with_capacity(ONION_HOST_LEN + ONION_DOMAIN.len());
+        let (mut buffer, mut bits) = (0u32, 0u32);
+        for byte in bytes {
+            buffer = (buffer << 8) | byte as u32;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                host.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+            }
+        }
+        host.push_str(ONION_DOMAIN);
+        f.write_str(&host)
+    }
+}
+
+impl Serialize for OnionAddress {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for OnionAddress {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        OnionAddress::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// The networks a peer address may belong to
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressNetwork {
+    Ipv4,
+    Ipv6,
+    Onion,
+}
+
+impl FromStr for AddressNetwork {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ipv4" => Ok(Self::Ipv4),
+            "ipv6" => Ok(Self::Ipv6),
+            "onion" => Ok(Self::Onion),
+            _ => Err(format!("unknown network {s}, expected one of ipv4, ipv6 or onion")),
+        }
+    }
+}
+
+impl Display for AddressNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ipv4 => f.write_str("ipv4"),
+            Self::Ipv6 => f.write_str("ipv6"),
+            Self::Onion => f.write_str("onion"),
+        }
+    }
+}
+
+/// Splits `s` into the host and the optional port of an onion address, `None` meaning that `s` is not an onion address
+fn split_onion_address(s: &str) -> Option<(&str, Option<&str>)> {
+    match s.rsplit_once(':') {
+        Some((host, port)) if host.ends_with(ONION_DOMAIN) => Some((host, Some(port))),
+        _ => s.ends_with(ONION_DOMAIN).then_some((s, None)),
+    }
+}
+
+/// A network address, equivalent of a [SocketAddr], possibly of an onion service.
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug, BorshSerialize, BorshDeserialize)]
 pub struct NetAddress {
+    /// The IP of the address, or the virtual IP of the onion service
     pub ip: IpAddress,
     pub port: u16,
+    /// Serialized apart from the other fields by the borsh encoded RPC messages for compatibility.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[borsh(skip)]
+    pub onion: Option<OnionAddress>,
 }
 
 impl NetAddress {
     pub fn new(ip: IpAddress, port: u16) -> Self {
-        Self { ip, port }
+        Self { ip, port, onion: None }
+    }
+
+    /// Creates the address of an onion service
+    pub fn new_onion(onion: OnionAddress, port: u16) -> Self {
+        Self { ip: onion.virtual_ip(), port, onion: Some(onion) }
+    }
+
+    pub fn is_onion(&self) -> bool {
+        self.onion.is_some()
+    }
+
+    pub fn network(&self) -> AddressNetwork {
+        match self.ip.0 {
+            _ if self.is_onion() => AddressNetwork::Onion,
+            IpAddr::V4(_) => AddressNetwork::Ipv4,
+            IpAddr::V6(ip) if ip.to_ipv4_mapped().is_some() => AddressNetwork::Ipv4,
+            IpAddr::V6(_) => AddressNetwork::Ipv6,
+        }
     }
 
     pub fn prefix_bucket(&self) -> PrefixBucket {
@@ -241,6 +449,7 @@ impl From<SocketAddr> for NetAddress {
     }
 }
 
+/// Onion services are converted to their virtual IP
 impl From<NetAddress> for SocketAddr {
     fn from(value: NetAddress) -> Self {
         Self::new(value.ip.0, value.port)
@@ -248,16 +457,26 @@ impl From<NetAddress> for SocketAddr {
 }
 
 impl FromStr for NetAddress {
-    type Err = AddrParseError;
+    type Err = NetAddressParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        SocketAddr::from_str(s).map(NetAddress::from)
+        match split_onion_address(s) {
+            Some((host, port)) => {
+                let port = port.ok_or_else(|| NetAddressParseError::MissingPort(s.to_string()))?;
+                let port = port.parse().map_err(|_| NetAddressParseError::InvalidOnionAddress(s.to_string()))?;
+                Ok(Self::new_onion(OnionAddress::from_str(host)?, port))
+            }
+            None => Ok(SocketAddr::from_str(s).map(NetAddress::from)?),
+        }
     }
 }
 
 impl Display for NetAddress {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        SocketAddr::from(self.to_owned()).fmt(f)
+        match self.onion {
+            Some(onion) => write!(f, "{}:{}", onion, self.port),
+            None => SocketAddr::from(self.to_owned()).fmt(f),
+        }
     }
 }
 
@@ -268,11 +487,24 @@ impl Display for NetAddress {
 pub struct ContextualNetAddress {
     ip: IpAddress,
     port: Option<u16>,
+    /// Serialized apart from the other fields by the borsh encoded RPC messages for compatibility.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[borsh(skip)]
+    onion: Option<OnionAddress>,
 }
 
 impl ContextualNetAddress {
     pub fn new(ip: IpAddress, port: Option<u16>) -> Self {
-        Self { ip, port }
+        Self { ip, port, onion: None }
+    }
+
+    /// Creates the address of an onion service
+    pub fn new_onion(onion: OnionAddress, port: Option<u16>) -> Self {
+        Self { ip: onion.virtual_ip(), port, onion: Some(onion) }
+    }
+
+    pub fn onion(&self) -> Option<OnionAddress> {
+        self.onion
     }
 
     pub fn has_port(&self) -> bool {
@@ -280,15 +512,15 @@ impl ContextualNetAddress {
     }
 
     pub fn normalize(&self, default_port: u16) -> NetAddress {
-        NetAddress::new(self.ip, self.port.unwrap_or(default_port))
+        NetAddress { ip: self.ip, port: self.port.unwrap_or(default_port), onion: self.onion }
     }
 
     pub fn unspecified() -> Self {
-        Self { ip: IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)).into(), port: None }
+        Self::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)).into(), None)
     }
 
     pub fn loopback() -> Self {
-        Self { ip: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)).into(), port: None }
+        Self::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)).into(), None)
     }
 
     pub fn port_not_specified(&self) -> bool {
@@ -296,20 +528,28 @@ impl ContextualNetAddress {
     }
 
     pub fn with_port(&self, port: u16) -> Self {
-        Self { ip: self.ip, port: Some(port) }
+        Self { port: Some(port), ..*self }
+    }
+
+    pub fn with_onion(&self, onion: OnionAddress) -> Self {
+        Self { ip: onion.virtual_ip(), onion: Some(onion), ..*self }
     }
 }
 
 impl From<NetAddress> for ContextualNetAddress {
     fn from(value: NetAddress) -> Self {
-        Self::new(value.ip, Some(value.port))
+        Self { ip: value.ip, port: Some(value.port), onion: value.onion }
     }
 }
 
 impl FromStr for ContextualNetAddress {
-    type Err = AddrParseError;
+    type Err = NetAddressParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((host, port)) = split_onion_address(s) {
+            let port = port.map(u16::from_str).transpose().map_err(|_| NetAddressParseError::InvalidOnionAddress(s.to_string()))?;
+            return Ok(Self::new_onion(OnionAddress::from_str(host)?, port));
+        }
         match SocketAddr::from_str(s) {
             Ok(socket) => Ok(Self::new(socket.ip().into(), Some(socket.port()))),
             Err(_) => Ok(Self::new(IpAddress::from_str(s)?, None)),
@@ -318,7 +558,7 @@ impl FromStr for ContextualNetAddress {
 }
 
 impl TryFrom<&str> for ContextualNetAddress {
-    type Error = AddrParseError;
+    type Error = NetAddressParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         ContextualNetAddress::from_str(s)
@@ -326,7 +566,7 @@ impl TryFrom<&str> for ContextualNetAddress {
 }
 
 impl TryFrom<String> for ContextualNetAddress {
-    type Error = AddrParseError;
+    type Error = NetAddressParseError;
 
     fn try_from(s: String) -> Result<Self, Self::Error> {
         ContextualNetAddress::from_str(&s)
@@ -335,12 +575,15 @@ impl TryFrom<String> for ContextualNetAddress {
 
 impl Display for ContextualNetAddress {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.port {
-            Some(port) => SocketAddr::new(self.ip.into(), port).fmt(f),
-            None => self.ip.fmt(f),
+        match (self.onion, self.port) {
+            (Some(onion), Some(port)) => write!(f, "{}:{}", onion, port),
+            (Some(onion), None) => onion.fmt(f),
+            (None, Some(port)) => SocketAddr::new(self.ip.into(), port).fmt(f),
+            (None, None) => self.ip.fmt(f),
         }
     }
 }
+
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug, Default)]
 #[repr(transparent)]
 pub struct PeerId(pub Uuid);
@@ -447,6 +690,54 @@ mod tests {
         assert!(addr_v6.is_ok());
     }
 
+    #[test]
+    fn test_onion_address() {
+        let host = "2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion";
+        let onion = OnionAddress::from_str(host).unwrap();
+        assert_eq!(onion.to_string(), host);
+        assert_eq!(OnionAddress::from_str(&host.to_uppercase().replace(".ONION", ".onion")).unwrap(), onion);
+        assert_eq!(onion.virtual_ip(), IpAddress::from_str("fd87:d87e:eb43:d1b3:8b83:a83b:3ed9:18c5").unwrap());
+        assert!(onion.virtual_ip().is_onion());
+        assert!(!IpAddress::from_str("fd87:d87e:eb44::").unwrap().is_onion());
+
+        // Wrong checksum, length or characters
+        assert!(OnionAddress::from_str("3gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion").is_err());
+        assert!(OnionAddress::from_str("gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion").is_err());
+        assert!(OnionAddress::from_str("1gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion").is_err());
+        assert!(OnionAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid").is_err());
+
+        let serialized = serde_json::to_string(&onion).unwrap();
+        assert_eq!(serialized, format!("\"{host}\""));
+        assert_eq!(serde_json::from_str::<OnionAddress>(&serialized).unwrap(), onion);
+    }
+
+    #[test]
+    fn test_onion_net_address() {
+        let host = "2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion";
+        let addr = NetAddress::from_str(&format!("{host}:7111")).unwrap();
+        assert!(addr.is_onion());
+        assert_eq!(addr.network(), AddressNetwork::Onion);
+        assert_eq!(addr.to_string(), format!("{host}:7111"));
+        assert_eq!(SocketAddr::from(addr).ip(), IpAddr::from(addr.onion.unwrap().virtual_ip()));
+        assert_eq!(NetAddress::from_str(host), Err(NetAddressParseError::MissingPort(host.to_string())));
+
+        let contextual = ContextualNetAddress::from_str(host).unwrap();
+        assert_eq!(contextual.onion(), addr.onion);
+        assert_eq!(contextual.normalize(7111), addr);
+        assert_eq!(contextual.to_string(), host);
+
+        assert_eq!(NetAddress::from_str("1.2.3.4:5678").unwrap().network(), AddressNetwork::Ipv4);
+        assert_eq!(NetAddress::from_str("[::ffff:1.2.3.4]:5678").unwrap().network(), AddressNetwork::Ipv4);
+        assert_eq!(NetAddress::from_str("[2a01:4f8:191:1143::2]:5678").unwrap().network(), AddressNetwork::Ipv6);
+        assert_eq!(AddressNetwork::from_str("Onion"), Ok(AddressNetwork::Onion));
+        assert!(AddressNetwork::from_str("i2p").is_err());
+
+        // All onion services share a prefix bucket
+        let other = NetAddress::new_onion(OnionAddress::new([7u8; 32]), 7111);
+        assert_eq!(addr.prefix_bucket(), other.prefix_bucket());
+        assert_ne!(addr.prefix_bucket(), NetAddress::new(addr.ip, 7111).prefix_bucket());
+    }
+
     #[test]
     fn test_prefix_bucket() {
         let prefix_bytes: [u8; 2] = [42u8, 43u8];
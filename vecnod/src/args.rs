@@ -1,15 +1,21 @@
 use clap::{arg, Arg, ArgAction, Command};
 use serde::Deserialize;
 use serde_with::{serde_as, DisplayFromStr};
-use std::{ffi::OsString, fs};
+use std::{ffi::OsString, fs, time::Duration};
 use toml::from_str;
 use vecno_consensus_core::{
     config::Config,
     network::{NetworkId, NetworkType},
 };
 use vecno_core::vecnod_env::version;
+use vecno_mining::{mempool::policy::AdmissionPolicyConfig, TemplatePolicy};
 use vecno_notify::address::tracker::Tracker;
-use vecno_utils::networking::ContextualNetAddress;
+use vecno_p2p_flows::{
+    dandelion::DandelionConfig,
+    misbehavior::{MisbehaviorConfig, MAX_BAN_DURATION_FACTOR},
+};
+use vecno_stratum_server::server::DEFAULT_INITIAL_DIFFICULTY;
+use vecno_utils::networking::{AddressNetwork, ContextualNetAddress};
 use vecno_wrpc_server::address::WrpcNetAddress;
 
 #[cfg(feature = "devnet-prealloc")]
@@ -21,6 +27,9 @@ use vecno_consensus_core::tx::{TransactionOutpoint, UtxoEntry};
 #[cfg(feature = "devnet-prealloc")]
 use vecno_txscript::pay_to_address_script;
 
+/// Port of the SOCKS5 proxies given without one, which is the SOCKS port of Tor daemons
+const DEFAULT_PROXY_PORT: u16 = 9050;
+
 #[serde_as]
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
@@ -53,11 +62,19 @@ pub struct Args {
     #[serde(rename = "uacomment")]
     pub user_agent_comments: Vec<String>,
     pub utxoindex: bool,
+    pub txindex: bool,
+    pub addressindex: bool,
     pub reset_db: bool,
     #[serde(rename = "outpeers")]
     pub outbound_target: usize,
     #[serde(rename = "maxinpeers")]
     pub inbound_limit: usize,
+    #[serde(rename = "banthreshold")]
+    pub ban_threshold: u32,
+    #[serde(rename = "banduration")]
+    pub ban_duration_sec: u64,
+    #[serde(rename = "banscorehalflife")]
+    pub ban_score_half_life_sec: u64,
     #[serde(rename = "rpcmaxclients")]
     pub rpc_max_clients: usize,
     pub max_tracked_addresses: usize,
@@ -75,6 +92,16 @@ pub struct Args {
     pub externalip: Option<ContextualNetAddress>,
     pub perf_metrics: bool,
     pub perf_metrics_interval_sec: u64,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub prometheus_listen: Option<ContextualNetAddress>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub stratum_listen: Option<ContextualNetAddress>,
+    pub stratum_pay_address: Option<String>,
+    pub stratum_difficulty: f64,
+    pub mine_to: Option<String>,
+    pub miner_threads: usize,
+    pub export_snapshot: Option<String>,
+    pub import_snapshot: Option<String>,
     pub block_template_cache_lifetime: Option<u64>,
 
     #[cfg(feature = "devnet-prealloc")]
@@ -85,11 +112,29 @@ pub struct Args {
     pub prealloc_amount: u64,
 
     pub disable_upnp: bool,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub proxy: Option<ContextualNetAddress>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub onion_proxy: Option<ContextualNetAddress>,
+    pub onlynet: Vec<AddressNetwork>,
+    #[serde(rename = "p2pencryption")]
+    pub p2p_encryption: bool,
+    #[serde(rename = "p2prequireencryption")]
+    pub p2p_require_encryption: bool,
+    pub dandelion: bool,
+    #[serde(rename = "dandelionembargo")]
+    pub dandelion_embargo_sec: u64,
+    #[serde(rename = "dandelionfluffprobability")]
+    pub dandelion_fluff_probability: f64,
     #[serde(rename = "nodnsseed")]
     pub disable_dns_seeding: bool,
     #[serde(rename = "nogrpc")]
     pub disable_grpc: bool,
     pub ram_scale: f64,
+    /// Mempool admission policy, only settable through the `[mempool-policy]` table of the config file
+    pub mempool_policy: AdmissionPolicyConfig,
+    /// Block template transaction selection policy, only settable through the `[template-policy]` table of the config file
+    pub template_policy: TemplatePolicy,
 }
 
 impl Default for Args {
@@ -102,9 +147,14 @@ impl Default for Args {
             unsafe_rpc: false,
             async_threads: num_cpus::get(),
             utxoindex: false,
+            txindex: false,
+            addressindex: false,
             reset_db: false,
             outbound_target: 8,
             inbound_limit: 128,
+            ban_threshold: 100,
+            ban_duration_sec: 3 * 60 * 60,
+            ban_score_half_life_sec: 60 * 60,
             rpc_max_clients: 128,
             max_tracked_addresses: 0,
             enable_unsynced_mining: false,
@@ -126,6 +176,14 @@ impl Default for Args {
             yes: false,
             perf_metrics: false,
             perf_metrics_interval_sec: 10,
+            prometheus_listen: None,
+            stratum_listen: None,
+            stratum_pay_address: None,
+            stratum_difficulty: DEFAULT_INITIAL_DIFFICULTY,
+            mine_to: None,
+            miner_threads: 1,
+            export_snapshot: None,
+            import_snapshot: None,
             externalip: None,
             block_template_cache_lifetime: None,
 
@@ -137,9 +195,19 @@ impl Default for Args {
             prealloc_amount: 10_000_000_000,
 
             disable_upnp: false,
+            proxy: None,
+            onion_proxy: None,
+            onlynet: vec![],
+            p2p_encryption: false,
+            p2p_require_encryption: false,
+            dandelion: false,
+            dandelion_embargo_sec: 30,
+            dandelion_fluff_probability: 0.1,
             disable_dns_seeding: false,
             disable_grpc: false,
             ram_scale: 1.0,
+            mempool_policy: Default::default(),
+            template_policy: Default::default(),
         }
     }
 }
@@ -147,7 +215,11 @@ impl Default for Args {
 impl Args {
     pub fn apply_to_config(&self, config: &mut Config) {
         config.utxoindex = self.utxoindex;
+        config.txindex = self.txindex;
+        config.addressindex = self.addressindex;
         config.disable_upnp = self.disable_upnp;
+        config.p2p_encryption = self.p2p_encryption || self.p2p_require_encryption;
+        config.p2p_require_encryption = self.p2p_require_encryption;
         config.unsafe_rpc = self.unsafe_rpc;
         config.enable_unsynced_mining = self.enable_unsynced_mining;
         config.enable_mainnet_mining = self.enable_mainnet_mining;
@@ -158,6 +230,10 @@ impl Args {
         config.block_template_cache_lifetime = self.block_template_cache_lifetime;
         config.p2p_listen_address = self.listen.unwrap_or(ContextualNetAddress::unspecified());
         config.externalip = self.externalip.map(|v| v.normalize(config.default_p2p_port()));
+        config.p2p_proxy = self.proxy.map(|v| v.normalize(DEFAULT_PROXY_PORT));
+        // Onion services are reached through the general proxy unless a dedicated one is set
+        config.p2p_onion_proxy = self.onion_proxy.map(|v| v.normalize(DEFAULT_PROXY_PORT)).or(config.p2p_proxy);
+        config.p2p_only_networks.clone_from(&self.onlynet);
         config.ram_scale = self.ram_scale;
 
         #[cfg(feature = "devnet-prealloc")]
@@ -189,6 +265,23 @@ impl Args {
             _ => panic!("only a single net should be activated"),
         }
     }
+
+    pub fn misbehavior_config(&self) -> MisbehaviorConfig {
+        MisbehaviorConfig {
+            ban_threshold: self.ban_threshold,
+            ban_duration: Duration::from_secs(self.ban_duration_sec),
+            score_half_life: Duration::from_secs(self.ban_score_half_life_sec),
+        }
+    }
+
+    pub fn dandelion_config(&self) -> DandelionConfig {
+        DandelionConfig {
+            enabled: self.dandelion,
+            embargo_duration: Duration::from_secs(self.dandelion_embargo_sec),
+            fluff_probability: self.dandelion_fluff_probability,
+            ..Default::default()
+        }
+    }
 }
 
 pub fn cli() -> Command {
@@ -293,6 +386,33 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(usize))
                 .help("Max number of inbound peers (default: 128)."),
         )
+        .arg(
+            Arg::new("banthreshold")
+                .long("banthreshold")
+                .value_name("banthreshold")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u32))
+                .help("Misbehavior score at which a peer gets disconnected and banned (default: 100)."),
+        )
+        .arg(
+            Arg::new("banduration")
+                .long("banduration")
+                .value_name("banduration")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help(format!(
+                    "Duration in seconds of the first ban of a misbehaving peer, doubled on each later ban up to {} times (default: 10800).",
+                    MAX_BAN_DURATION_FACTOR
+                )),
+        )
+        .arg(
+            Arg::new("banscorehalflife")
+                .long("banscorehalflife")
+                .value_name("banscorehalflife")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Time in seconds for the misbehavior score of a peer to halve (default: 3600)."),
+        )
         .arg(
             Arg::new("rpcmaxclients")
                 .long("rpcmaxclients")
@@ -311,6 +431,8 @@ pub fn cli() -> Command {
                 .help("Allow mainnet mining (currently enabled by default while the flag is kept for backwards compatibility)"),
         )
         .arg(arg!(--utxoindex "Enable the UTXO index"))
+        .arg(arg!(--txindex "Enable the transaction index"))
+        .arg(arg!(--addressindex "Enable the address history index"))
         .arg(
             Arg::new("max-tracked-addresses")
                 .long("max-tracked-addresses")
@@ -358,7 +480,117 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .value_parser(clap::value_parser!(u64))
                 .help("Interval in seconds for performance metrics collection."),
         )
+        .arg(
+            Arg::new("prometheus-listen")
+                .long("prometheus-listen")
+                .value_name("IP[:PORT]")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("127.0.0.1")
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Interface:port to serve Prometheus/OpenMetrics metrics on at /metrics (default port: 7112, testnet: 7212)."),
+        )
+        .arg(
+            Arg::new("stratum-listen")
+                .long("stratum-listen")
+                .value_name("IP[:PORT]")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("127.0.0.1")
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Interface:port to run the built-in Stratum mining server on (default port: 7113, testnet: 7213). Requires --stratum-pay-address."),
+        )
+        .arg(
+            Arg::new("stratum-pay-address")
+                .long("stratum-pay-address")
+                .value_name("ADDRESS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Address the rewards of the blocks mined through the Stratum server are paid to."),
+        )
+        .arg(
+            Arg::new("stratum-difficulty")
+                .long("stratum-difficulty")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help("Initial share difficulty of the Stratum workers, adjusted afterwards to their hashrate (default: 16777216)."),
+        )
+        .arg(
+            Arg::new("mine-to")
+                .long("mine-to")
+                .value_name("ADDRESS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Mine blocks paying to ADDRESS with the built-in CPU miner (devnet and simnet only)."),
+        )
+        .arg(
+            Arg::new("miner-threads")
+                .long("miner-threads")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of threads of the built-in CPU miner (default: 1)."),
+        )
+        .arg(
+            Arg::new("export-snapshot")
+                .long("export-snapshot")
+                .value_name("PATH")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Write the pruning point, its UTXO set, the pruning proof and the trusted anticone data to a snapshot file at PATH, then exit."),
+        )
+        .arg(
+            Arg::new("import-snapshot")
+                .long("import-snapshot")
+                .value_name("PATH")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Bootstrap the node from the snapshot file at PATH, validated the same way as a pruning point received during IBD."),
+        )
         .arg(arg!(--"disable-upnp" "Disable upnp"))
+        .arg(
+            Arg::new("proxy")
+                .long("proxy")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Connect to peers through the SOCKS5 proxy at IP[:PORT] (default port: 9050), disabling the discovery and advertisement of local addresses. Requires --nodnsseed unless --connect is used."),
+        )
+        .arg(
+            Arg::new("onion-proxy")
+                .long("onion-proxy")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Connect to onion peers through the SOCKS5 proxy at IP[:PORT] (default port: 9050, default: the --proxy setting)."),
+        )
+        .arg(
+            Arg::new("onlynet")
+                .long("onlynet")
+                .value_name("NETWORK")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(AddressNetwork))
+                .help("Make outgoing connections only to peers of NETWORK {ipv4, ipv6, onion}. Can be repeated to allow several networks."),
+        )
+        .arg(arg!(--"p2pencryption" "Offer peers to encrypt the p2p connections, falling back to plaintext for peers not supporting it"))
+        .arg(arg!(--"p2prequireencryption" "Encrypt the p2p connections, rejecting the peers not supporting it (implies --p2pencryption)"))
+        .arg(arg!(--"dandelion" "Relay the transactions submitted through RPC along a Dandelion++ stem of outbound peers before diffusing them, hiding their origin"))
+        .arg(
+            Arg::new("dandelionembargo")
+                .long("dandelionembargo")
+                .value_name("dandelionembargo")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Time in seconds after which a transaction in the Dandelion++ stem phase is diffused if it was not seen diffused by the network (default: 30)."),
+        )
+        .arg(
+            Arg::new("dandelionfluffprobability")
+                .long("dandelionfluffprobability")
+                .value_name("dandelionfluffprobability")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help("Probability for the node to diffuse the Dandelion++ stem transactions it receives during an epoch rather than relaying them (default: 0.1)."),
+        )
         .arg(arg!(--"nodnsseed" "Disable DNS seeding for peers"))
         .arg(arg!(--"nogrpc" "Disable gRPC server"))
         .arg(
@@ -425,12 +657,17 @@ impl Args {
             listen: m.get_one::<ContextualNetAddress>("listen").cloned().or(defaults.listen),
             outbound_target: arg_match_unwrap_or::<usize>(&m, "outpeers", defaults.outbound_target),
             inbound_limit: arg_match_unwrap_or::<usize>(&m, "maxinpeers", defaults.inbound_limit),
+            ban_threshold: arg_match_unwrap_or::<u32>(&m, "banthreshold", defaults.ban_threshold),
+            ban_duration_sec: arg_match_unwrap_or::<u64>(&m, "banduration", defaults.ban_duration_sec),
+            ban_score_half_life_sec: arg_match_unwrap_or::<u64>(&m, "banscorehalflife", defaults.ban_score_half_life_sec),
             rpc_max_clients: arg_match_unwrap_or::<usize>(&m, "rpcmaxclients", defaults.rpc_max_clients),
             max_tracked_addresses: arg_match_unwrap_or::<usize>(&m, "max-tracked-addresses", defaults.max_tracked_addresses),
             reset_db: arg_match_unwrap_or::<bool>(&m, "reset-db", defaults.reset_db),
             enable_unsynced_mining: arg_match_unwrap_or::<bool>(&m, "enable-unsynced-mining", defaults.enable_unsynced_mining),
             enable_mainnet_mining: arg_match_unwrap_or::<bool>(&m, "enable-mainnet-mining", defaults.enable_mainnet_mining),
             utxoindex: arg_match_unwrap_or::<bool>(&m, "utxoindex", defaults.utxoindex),
+            txindex: arg_match_unwrap_or::<bool>(&m, "txindex", defaults.txindex),
+            addressindex: arg_match_unwrap_or::<bool>(&m, "addressindex", defaults.addressindex),
             testnet: arg_match_unwrap_or::<bool>(&m, "testnet", defaults.testnet),
             testnet_suffix: arg_match_unwrap_or::<u32>(&m, "netsuffix", defaults.testnet_suffix),
             devnet: arg_match_unwrap_or::<bool>(&m, "devnet", defaults.devnet),
@@ -442,12 +679,34 @@ impl Args {
             externalip: m.get_one::<ContextualNetAddress>("externalip").cloned(),
             perf_metrics: arg_match_unwrap_or::<bool>(&m, "perf-metrics", defaults.perf_metrics),
             perf_metrics_interval_sec: arg_match_unwrap_or::<u64>(&m, "perf-metrics-interval-sec", defaults.perf_metrics_interval_sec),
+            prometheus_listen: m.get_one::<ContextualNetAddress>("prometheus-listen").cloned().or(defaults.prometheus_listen),
+            stratum_listen: m.get_one::<ContextualNetAddress>("stratum-listen").cloned().or(defaults.stratum_listen),
+            stratum_pay_address: m.get_one::<String>("stratum-pay-address").cloned().or(defaults.stratum_pay_address),
+            stratum_difficulty: arg_match_unwrap_or::<f64>(&m, "stratum-difficulty", defaults.stratum_difficulty),
+            mine_to: m.get_one::<String>("mine-to").cloned().or(defaults.mine_to),
+            miner_threads: arg_match_unwrap_or::<usize>(&m, "miner-threads", defaults.miner_threads),
+            export_snapshot: m.get_one::<String>("export-snapshot").cloned().or(defaults.export_snapshot),
+            import_snapshot: m.get_one::<String>("import-snapshot").cloned().or(defaults.import_snapshot),
             // Note: currently used programmatically by benchmarks and not exposed to CLI users
             block_template_cache_lifetime: defaults.block_template_cache_lifetime,
             disable_upnp: arg_match_unwrap_or::<bool>(&m, "disable-upnp", defaults.disable_upnp),
+            proxy: m.get_one::<ContextualNetAddress>("proxy").cloned().or(defaults.proxy),
+            onion_proxy: m.get_one::<ContextualNetAddress>("onion-proxy").cloned().or(defaults.onion_proxy),
+            onlynet: arg_match_many_unwrap_or::<AddressNetwork>(&m, "onlynet", defaults.onlynet),
+            p2p_encryption: arg_match_unwrap_or::<bool>(&m, "p2pencryption", defaults.p2p_encryption),
+            p2p_require_encryption: arg_match_unwrap_or::<bool>(&m, "p2prequireencryption", defaults.p2p_require_encryption),
+            dandelion: arg_match_unwrap_or::<bool>(&m, "dandelion", defaults.dandelion),
+            dandelion_embargo_sec: arg_match_unwrap_or::<u64>(&m, "dandelionembargo", defaults.dandelion_embargo_sec),
+            dandelion_fluff_probability: arg_match_unwrap_or::<f64>(
+                &m,
+                "dandelionfluffprobability",
+                defaults.dandelion_fluff_probability,
+            ),
             disable_dns_seeding: arg_match_unwrap_or::<bool>(&m, "nodnsseed", defaults.disable_dns_seeding),
             disable_grpc: arg_match_unwrap_or::<bool>(&m, "nogrpc", defaults.disable_grpc),
             ram_scale: arg_match_unwrap_or::<f64>(&m, "ram-scale", defaults.ram_scale),
+            mempool_policy: defaults.mempool_policy,
+            template_policy: defaults.template_policy,
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: m.get_one::<u64>("num-prealloc-utxos").cloned(),
@@ -549,6 +808,8 @@ fn arg_match_many_unwrap_or<T: Clone + Send + Sync + 'static>(m: &clap::ArgMatch
       --maxutxocachesize=                   Max size of loaded UTXO into ram from the disk in bytes (default:
                                             5000000000)
       --utxoindex                           Enable the UTXO index
+      --txindex                             Enable the transaction index
+      --addressindex                        Enable the address history index
       --archival                            Run as an archival node: don't delete old block data when moving the
                                             pruning point (Warning: heavy disk usage)'
       --protocol-version=                   Use non default p2p protocol version (default: 7)
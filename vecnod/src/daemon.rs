@@ -1,12 +1,18 @@
-use std::{fs, path::PathBuf, process::exit, sync::Arc, time::Duration};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::exit,
+    sync::Arc,
+    time::Duration,
+};
 
 use async_channel::unbounded;
 use vecno_consensus_core::{
-    config::ConfigBuilder,
+    config::{Config, ConfigBuilder},
     errors::config::{ConfigError, ConfigResult},
 };
 use vecno_consensus_notify::{root::ConsensusNotificationRoot, service::NotifyService};
-use vecno_core::{core::Core, debug, info, trace};
+use vecno_core::{core::Core, debug, error, info, trace};
 use vecno_core::{task::tick::TickService, vecnod_env::version};
 use vecno_database::{
     prelude::{CachePolicy, DbWriter, DirectDbWriter},
@@ -14,10 +20,10 @@ use vecno_database::{
 };
 use vecno_grpc_server::service::GrpcService;
 use vecno_notify::{address::tracker::Tracker, subscription::context::SubscriptionContext};
-use vecno_rpc_service::service::RpcCoreService;
+use vecno_rpc_service::{counters::RpcOpCounters, service::RpcCoreService};
 use vecno_txscript::caches::TxScriptCacheCounters;
 use vecno_utils::git;
-use vecno_utils::networking::ContextualNetAddress;
+use vecno_utils::networking::{AddressNetwork, ContextualNetAddress};
 use vecno_utils::sysinfo::SystemInfo;
 use vecno_utils_tower::counters::TowerConnectionCounters;
 
@@ -31,13 +37,27 @@ use vecno_core::task::runtime::AsyncRuntime;
 use vecno_index_processor::service::IndexService;
 use vecno_mining::{
     manager::{MiningManager, MiningManagerProxy},
+    mempool::policy::ConfigurableAdmissionPolicy,
     monitor::MiningMonitor,
-    MiningCounters,
+    persistence::{FeerateHistoryPersistence, MempoolPersistence},
+    MiningCounters, TemplatePolicy,
+};
+use vecno_p2p_flows::{
+    flow_context::FlowContext,
+    service::P2pService,
+    snapshot::{export_snapshot, import_snapshot},
 };
-use vecno_p2p_flows::{flow_context::FlowContext, service::P2pService};
 
 use itertools::Itertools;
+use vecno_addresses::{Address, Prefix};
+use vecno_addressindex::{api::AddressIndexProxy, AddressIndex};
+use vecno_metrics_prometheus::PrometheusExporter;
 use vecno_perf_monitor::{builder::Builder as PerfMonitorBuilder, counters::CountersSnapshot};
+use vecno_stratum_server::{
+    server::{Options as StratumServerOptions, StratumServer, DEFAULT_MIN_DIFFICULTY as STRATUM_MIN_DIFFICULTY},
+    stats::StratumStats,
+};
+use vecno_txindex::{api::TxIndexProxy, TxIndex};
 use vecno_utxoindex::{api::UtxoIndexProxy, UtxoIndex};
 use vecno_wrpc_server::service::{Options as WrpcServerOptions, WebSocketCounters as WrpcServerCounters, WrpcEncoding, WrpcService};
 
@@ -50,12 +70,16 @@ pub const DESIRED_DAEMON_SOFT_FD_LIMIT: u64 = 8 * 1024;
 /// this value may impact the database performance).
 pub const MINIMUM_DAEMON_SOFT_FD_LIMIT: u64 = 4 * 1024;
 
-use crate::args::Args;
+use crate::{args::Args, miner::CpuMiner};
 
 const DEFAULT_DATA_DIR: &str = "datadir";
 const CONSENSUS_DB: &str = "consensus";
 const UTXOINDEX_DB: &str = "utxoindex";
+const TXINDEX_DB: &str = "txindex";
+const ADDRESSINDEX_DB: &str = "addressindex";
 const META_DB: &str = "meta";
+const MEMPOOL_FILE: &str = "mempool.dat";
+const FEERATE_HISTORY_FILE: &str = "feerate_history.dat";
 const META_DB_FILE_LIMIT: i32 = 5;
 const DEFAULT_LOG_DIR: &str = "logs";
 
@@ -101,9 +125,69 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(Tracker::MAX_ADDRESS_UPPER_BOUND));
     }
+    if args.stratum_listen.is_some() {
+        let Some(pay_address) = args.stratum_pay_address.as_ref() else {
+            return Err(ConfigError::MissingStratumPayAddress);
+        };
+        if !Address::try_from(pay_address.as_str()).is_ok_and(|address| address.prefix == Prefix::from(args.network())) {
+            return Err(ConfigError::InvalidStratumPayAddress(pay_address.clone()));
+        }
+        if args.stratum_difficulty < STRATUM_MIN_DIFFICULTY {
+            return Err(ConfigError::StratumDifficultyTooLow(STRATUM_MIN_DIFFICULTY));
+        }
+    }
+    if let Some(mine_to) = args.mine_to.as_ref() {
+        if !args.devnet && !args.simnet {
+            return Err(ConfigError::MiningOnNonDevnet);
+        }
+        if !Address::try_from(mine_to.as_str()).is_ok_and(|address| address.prefix == Prefix::from(args.network())) {
+            return Err(ConfigError::InvalidMineToAddress(mine_to.clone()));
+        }
+        if args.miner_threads < 1 {
+            return Err(ConfigError::MinerThreadsTooLow);
+        }
+    }
+    if args.export_snapshot.is_some() && args.import_snapshot.is_some() {
+        return Err(ConfigError::MixedExportAndImportSnapshot);
+    }
+    if let Some(address) = args.mempool_policy.blocked_addresses.iter().find(|address| address.prefix != Prefix::from(args.network()))
+    {
+        return Err(ConfigError::InvalidMempoolPolicyAddress(address.to_string()));
+    }
+    if args.mempool_policy.minimum_feerate.is_some_and(|feerate| !(feerate.is_finite() && feerate > 0.0)) {
+        return Err(ConfigError::InvalidMempoolPolicyFeerate);
+    }
+    if !args.template_policy.is_valid() {
+        return Err(ConfigError::InvalidTemplatePolicyMassShare);
+    }
+    if !args.misbehavior_config().is_valid() {
+        return Err(ConfigError::InvalidBanSettings);
+    }
+    if !args.dandelion_config().is_valid() {
+        return Err(ConfigError::InvalidDandelionSettings);
+    }
+    if [args.proxy, args.onion_proxy].iter().flatten().any(|proxy| proxy.onion().is_some()) {
+        return Err(ConfigError::InvalidProxyAddress);
+    }
+    if args.onlynet.contains(&AddressNetwork::Onion) && args.proxy.is_none() && args.onion_proxy.is_none() {
+        return Err(ConfigError::OnionNetworkWithoutProxy);
+    }
+    if args.proxy.is_some() && !args.disable_dns_seeding && args.connect_peers.is_empty() {
+        return Err(ConfigError::DnsSeedingWithProxy);
+    }
     Ok(())
 }
 
+/// Returns the DNS seeds of the network, unless seeding is disabled. `--connect` means no DNS seeding, and so does
+/// a proxy, since the seeds would be resolved outside of it.
+pub fn seed_peers(args: &Args, config: &Config) -> &'static [&'static str] {
+    if args.connect_peers.is_empty() && !args.disable_dns_seeding && args.proxy.is_none() {
+        config.peers
+    } else {
+        &[]
+    }
+}
+
 fn get_user_approval_or_exit(message: &str, approve: bool) {
     if approve {
         return;
@@ -220,6 +304,20 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     } else {
         0
     };
+    let tx_files_limit = if args.txindex {
+        let tx_files_limit = fd_remaining * 10 / 100;
+        fd_remaining -= tx_files_limit;
+        tx_files_limit
+    } else {
+        0
+    };
+    let address_files_limit = if args.addressindex {
+        let address_files_limit = fd_remaining * 10 / 100;
+        fd_remaining -= address_files_limit;
+        address_files_limit
+    } else {
+        0
+    };
     // Make sure args forms a valid set of properties
     if let Err(err) = validate_args(args) {
         println!("{}", err);
@@ -255,6 +353,8 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
 
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
+    let txindex_db_dir = db_dir.join(TXINDEX_DB);
+    let addressindex_db_dir = db_dir.join(ADDRESSINDEX_DB);
     let meta_db_dir = db_dir.join(META_DB);
 
     let mut is_db_reset_needed = args.reset_db;
@@ -274,6 +374,14 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
         info!("Utxoindex Data directory {}", utxoindex_db_dir.display());
         fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
     }
+    if args.txindex {
+        info!("Txindex Data directory {}", txindex_db_dir.display());
+        fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+    }
+    if args.addressindex {
+        info!("Addressindex Data directory {}", addressindex_db_dir.display());
+        fs::create_dir_all(addressindex_db_dir.as_path()).unwrap();
+    }
 
     // DB used for addresses store and for multi-consensus management
     let mut meta_db = vecno_database::prelude::ConnBuilder::default()
@@ -439,6 +547,14 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
             fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
         }
 
+        if args.txindex {
+            fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+        }
+
+        if args.addressindex {
+            fs::create_dir_all(addressindex_db_dir.as_path()).unwrap();
+        }
+
         // Reopen the DB
         meta_db = vecno_database::prelude::ConnBuilder::default()
             .with_db_path(meta_db_dir)
@@ -456,9 +572,11 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
     let p2p_server_addr = args.listen.unwrap_or(ContextualNetAddress::unspecified()).normalize(config.default_p2p_port());
     // connect_peers means no DNS seeding and no outbound peers
     let outbound_target = if connect_peers.is_empty() { args.outbound_target } else { 0 };
-    let peers = if connect_peers.is_empty() && !args.disable_dns_seeding { config.peers } else { &[] };
+    let peers = seed_peers(args, &config);
 
     let grpc_server_addr = args.rpclisten.unwrap_or(ContextualNetAddress::loopback()).normalize(config.default_rpc_port());
+    let prometheus_server_addr = args.prometheus_listen.map(|x| x.normalize(config.net.default_prometheus_port()));
+    let stratum_server_addr = args.stratum_listen.map(|x| x.normalize(config.net.default_stratum_port()));
 
     let core = Arc::new(Core::new());
 
@@ -476,6 +594,8 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
     let tx_script_cache_counters = Arc::new(TxScriptCacheCounters::default());
     let p2p_tower_counters = Arc::new(TowerConnectionCounters::default());
     let grpc_tower_counters = Arc::new(TowerConnectionCounters::default());
+    let rpc_op_counters = Arc::new(RpcOpCounters::default());
+    let stratum_stats = stratum_server_addr.map(|_| Arc::new(StratumStats::default()));
 
     // Use `num_cpus` background threads for the consensus database as recommended by rocksdb
     let consensus_db_parallelism = num_cpus::get();
@@ -490,6 +610,28 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
         fd_remaining,
     ));
     let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
+
+    // Snapshots are handled before any service starts, on a runtime of their own
+    if let Some(path) = args.export_snapshot.as_ref() {
+        let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+        match runtime.block_on(export_snapshot(&consensus_manager.consensus(), &config, Path::new(path))) {
+            Ok(pruning_point) => {
+                info!("Exported a snapshot of pruning point {} to {}", pruning_point, path);
+                exit(0);
+            }
+            Err(err) => {
+                error!("Failed to export a snapshot to {}: {}", path, err);
+                exit(1);
+            }
+        }
+    }
+    if let Some(path) = args.import_snapshot.as_ref() {
+        let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+        if let Err(err) = runtime.block_on(import_snapshot(&consensus_manager, &config, Path::new(path))) {
+            error!("Failed to import the snapshot {}: {}", path, err);
+            exit(1);
+        }
+    }
     let consensus_monitor = Arc::new(ConsensusMonitor::new(processing_counters.clone(), tick_service.clone()));
 
     let perf_monitor_builder = PerfMonitorBuilder::new()
@@ -510,15 +652,34 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
     let system_info = SystemInfo::default();
 
     let notify_service = Arc::new(NotifyService::new(notification_root.clone(), notification_recv, subscription_context.clone()));
-    let index_service: Option<Arc<IndexService>> = if args.utxoindex {
+    let index_service: Option<Arc<IndexService>> = if args.utxoindex || args.txindex || args.addressindex {
         // Use only a single thread for none-consensus databases
-        let utxoindex_db = vecno_database::prelude::ConnBuilder::default()
-            .with_db_path(utxoindex_db_dir)
-            .with_files_limit(utxo_files_limit)
-            .build()
-            .unwrap();
-        let utxoindex = UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap());
-        let index_service = Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), Some(utxoindex)));
+        let utxoindex = args.utxoindex.then(|| {
+            let utxoindex_db = vecno_database::prelude::ConnBuilder::default()
+                .with_db_path(utxoindex_db_dir)
+                .with_files_limit(utxo_files_limit)
+                .build()
+                .unwrap();
+            UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap())
+        });
+        let txindex = args.txindex.then(|| {
+            let txindex_db = vecno_database::prelude::ConnBuilder::default()
+                .with_db_path(txindex_db_dir)
+                .with_files_limit(tx_files_limit)
+                .build()
+                .unwrap();
+            TxIndexProxy::new(TxIndex::new(consensus_manager.clone(), txindex_db).unwrap())
+        });
+        let addressindex = args.addressindex.then(|| {
+            let addressindex_db = vecno_database::prelude::ConnBuilder::default()
+                .with_db_path(addressindex_db_dir)
+                .with_files_limit(address_files_limit)
+                .build()
+                .unwrap();
+            AddressIndexProxy::new(AddressIndex::new(consensus_manager.clone(), addressindex_db).unwrap())
+        });
+        let index_service =
+            Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), utxoindex, txindex, addressindex));
         Some(index_service)
     } else {
         None
@@ -526,16 +687,30 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
 
     let (address_manager, port_mapping_extender_svc) = AddressManager::new(config.clone(), meta_db, tick_service.clone());
 
-    let mining_manager = MiningManagerProxy::new(Arc::new(MiningManager::new_with_extended_config(
+    let mut mining_manager = MiningManager::new_with_extended_config(
         config.target_time_per_block,
         false,
         config.max_block_mass,
         config.ram_scale,
         config.block_template_cache_lifetime,
         mining_counters.clone(),
-    )));
+    )
+    .with_notification_root(notification_root.clone());
+    if !args.mempool_policy.is_empty() {
+        info!("Applying the mempool admission policy of the config file");
+        mining_manager = mining_manager.with_admission_policy(Arc::new(ConfigurableAdmissionPolicy::new(args.mempool_policy.clone())));
+    }
+    if args.template_policy != TemplatePolicy::default() {
+        info!("Applying the block template policy of the config file");
+        mining_manager = mining_manager.with_template_policy(args.template_policy.clone());
+    }
+    let mining_manager = MiningManagerProxy::new(Arc::new(mining_manager));
     let mining_monitor =
         Arc::new(MiningMonitor::new(mining_manager.clone(), mining_counters, tx_script_cache_counters.clone(), tick_service.clone()));
+    let mempool_persistence =
+        Arc::new(MempoolPersistence::new(mining_manager.clone(), consensus_manager.clone(), db_dir.join(MEMPOOL_FILE)));
+    let feerate_history_persistence =
+        Arc::new(FeerateHistoryPersistence::new(mining_manager.clone(), db_dir.join(FEERATE_HISTORY_FILE)));
 
     let flow_context = Arc::new(FlowContext::new(
         consensus_manager.clone(),
@@ -544,6 +719,8 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
         mining_manager.clone(),
         tick_service.clone(),
         notification_root,
+        args.misbehavior_config(),
+        args.dandelion_config(),
     ));
     let p2p_service = Arc::new(P2pService::new(
         flow_context.clone(),
@@ -560,19 +737,23 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
     let rpc_core_service = Arc::new(RpcCoreService::new(
         consensus_manager.clone(),
         notify_service.notifier(),
-        index_service.as_ref().map(|x| x.notifier()),
+        // The index notifier only relays UTXO related events
+        index_service.as_ref().filter(|x| x.utxoindex().is_some()).map(|x| x.notifier()),
         mining_manager,
-        flow_context,
+        flow_context.clone(),
         subscription_context,
-        index_service.as_ref().map(|x| x.utxoindex().unwrap()),
+        index_service.as_ref().and_then(|x| x.utxoindex()),
+        index_service.as_ref().and_then(|x| x.txindex()),
+        index_service.as_ref().and_then(|x| x.addressindex()),
         config.clone(),
         core.clone(),
-        processing_counters,
+        processing_counters.clone(),
         wrpc_borsh_counters.clone(),
         wrpc_json_counters.clone(),
         perf_monitor.clone(),
         p2p_tower_counters.clone(),
         grpc_tower_counters.clone(),
+        stratum_stats.clone(),
         system_info,
     ));
     let grpc_service_broadcasters: usize = 3; // TODO: add a command line argument or derive from other arg/config/host-related fields
@@ -584,10 +765,35 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
             args.rpc_max_clients,
             grpc_service_broadcasters,
             grpc_tower_counters,
+            rpc_op_counters.clone(),
         )))
     } else {
         None
     };
+    let stratum_server = stratum_server_addr.zip(stratum_stats).map(|(listen_address, stratum_stats)| {
+        // The pay address is validated along with the other arguments
+        let pay_address = Address::try_from(args.stratum_pay_address.as_ref().unwrap().as_str()).unwrap();
+        let options = StratumServerOptions {
+            initial_difficulty: args.stratum_difficulty,
+            ..StratumServerOptions::new(listen_address, pay_address)
+        };
+        Arc::new(StratumServer::new(options, flow_context.clone(), tick_service.clone(), stratum_stats))
+    });
+    let cpu_miner = args.mine_to.as_ref().map(|mine_to| {
+        // The address is validated along with the other arguments
+        let pay_address = Address::try_from(mine_to.as_str()).unwrap();
+        Arc::new(CpuMiner::new(pay_address, args.miner_threads, flow_context.clone(), &notify_service.notifier()))
+    });
+    let prometheus_exporter = prometheus_server_addr.map(|listen_address| {
+        Arc::new(PrometheusExporter::new(
+            listen_address,
+            rpc_core_service.clone(),
+            flow_context,
+            processing_counters,
+            rpc_op_counters.clone(),
+            tick_service.clone(),
+        ))
+    });
 
     // Create an async runtime and register the top-level async services
     let async_runtime = Arc::new(AsyncRuntime::new(args.async_threads));
@@ -606,7 +812,18 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
     async_runtime.register(p2p_service);
     async_runtime.register(consensus_monitor);
     async_runtime.register(mining_monitor);
+    async_runtime.register(mempool_persistence);
+    async_runtime.register(feerate_history_persistence);
     async_runtime.register(perf_monitor);
+    if let Some(stratum_server) = stratum_server {
+        async_runtime.register(stratum_server)
+    }
+    if let Some(cpu_miner) = cpu_miner {
+        async_runtime.register(cpu_miner)
+    }
+    if let Some(prometheus_exporter) = prometheus_exporter {
+        async_runtime.register(prometheus_exporter)
+    }
     let wrpc_service_tasks: usize = 2; // num_cpus::get() / 2;
                                        // Register wRPC servers based on command line arguments
     [
@@ -621,6 +838,7 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
                 Some(rpc_core_service.clone()),
                 &encoding,
                 wrpc_server_counters,
+                rpc_op_counters.clone(),
                 WrpcServerOptions {
                     listen_address: listen_address.to_address(&network.network_type, &encoding).to_string(), // TODO: use a normalized ContextualNetAddress instead of a String
                     verbose: args.wrpc_verbose,
@@ -0,0 +1,275 @@
+//!
+//! In-process CPU miner for devnet and simnet, so that local networks and integration tests can produce blocks
+//! without running an external miner.
+//!
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, PoisonError, RwLock,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+use tokio::{runtime::Handle, select};
+use vecno_addresses::Address;
+use vecno_consensus_core::{
+    block::{Block, MutableBlock},
+    coinbase::MinerData,
+    hashing,
+};
+use vecno_consensus_notify::{
+    connection::ConsensusChannelConnection, notification::Notification as ConsensusNotification, notifier::ConsensusNotifier,
+};
+use vecno_core::{
+    debug, info,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace,
+    vecnod_env::version,
+    warn,
+};
+use vecno_hashes::Hash;
+use vecno_notify::{connection::ChannelType, listener::ListenerLifespan, scope::NewBlockTemplateScope};
+use vecno_p2p_flows::flow_context::FlowContext;
+use vecno_pow::State;
+use vecno_utils::{channel::Channel, triggers::SingleTrigger};
+
+pub const SERVICE_NAME: &str = "cpu-miner";
+
+/// Interval between two template refreshes in the absence of a new block template notification, so that the
+/// timestamp of the mined blocks keeps up with time and mempool changes get included
+const TEMPLATE_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Interval between two hashrate reports
+const HASHRATE_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Number of nonces a thread hashes in one batch before checking for a new template
+const NONCES_PER_ROUND: u64 = 1024;
+
+/// Pause of a thread having no template to mine on
+const IDLE_INTERVAL: Duration = Duration::from_millis(10);
+
+struct MinerJob {
+    block: MutableBlock,
+    pre_pow_hash: Hash,
+    pow_state: State,
+    /// Set once a thread found a nonce solving the block, so that no sibling gets mined on the same template
+    solved: AtomicBool,
+}
+
+impl MinerJob {
+    fn new(block: MutableBlock) -> Self {
+        let pre_pow_hash = hashing::header::hash_override_nonce_time(&block.header, 0, 0);
+        let pow_state = State::new(&block.header);
+        Self { block, pre_pow_hash, pow_state, solved: AtomicBool::new(false) }
+    }
+
+    fn to_block(&self, nonce: u64) -> Block {
+        let mut header = self.block.header.clone();
+        header.nonce = nonce;
+        header.finalize();
+        Block::new(header, self.block.transactions.clone())
+    }
+}
+
+pub struct CpuMiner {
+    pay_address: Address,
+    threads: usize,
+    flow_context: Arc<FlowContext>,
+    notification_channel: Channel<ConsensusNotification>,
+    job: RwLock<Option<Arc<MinerJob>>>,
+    hashes: AtomicU64,
+    workers: Mutex<Vec<JoinHandle<()>>>,
+    is_shutting_down: AtomicBool,
+    shutdown: SingleTrigger,
+}
+
+impl CpuMiner {
+    pub fn new(
+        pay_address: Address,
+        threads: usize,
+        flow_context: Arc<FlowContext>,
+        consensus_notifier: &Arc<ConsensusNotifier>,
+    ) -> Self {
+        // Get notified of every virtual change so that the threads switch to the new template right away
+        let notification_channel = Channel::<ConsensusNotification>::default();
+        let listener_id = consensus_notifier.register_new_listener(
+            ConsensusChannelConnection::new(SERVICE_NAME, notification_channel.sender(), ChannelType::Closable),
+            ListenerLifespan::Static(Default::default()),
+        );
+        consensus_notifier.try_start_notify(listener_id, NewBlockTemplateScope {}.into()).expect("the subscription always succeeds");
+
+        Self {
+            pay_address,
+            threads,
+            flow_context,
+            notification_channel,
+            job: RwLock::new(None),
+            hashes: AtomicU64::new(0),
+            workers: Mutex::new(Vec::with_capacity(threads)),
+            is_shutting_down: AtomicBool::new(false),
+            shutdown: SingleTrigger::default(),
+        }
+    }
+
+    async fn refresh_template(&self, miner_data: &MinerData) {
+        let session = self.flow_context.consensus().unguarded_session();
+        let template = match self.flow_context.mining_manager().clone().get_block_template(&session, miner_data.clone()).await {
+            Ok(template) => template,
+            Err(err) => {
+                debug!("[{}] error getting a block template: {}", SERVICE_NAME, err);
+                return;
+            }
+        };
+
+        let config = &self.flow_context.config;
+        if !config.enable_unsynced_mining
+            && !config.is_nearly_synced(template.selected_parent_timestamp, template.selected_parent_daa_score)
+        {
+            trace!("[{}] the node is not synced, waiting before mining", SERVICE_NAME);
+            self.job.write().unwrap_or_else(PoisonError::into_inner).take();
+            return;
+        }
+
+        // Keep the threads on the current job unless the proof of work input changed
+        let new_job = MinerJob::new(template.block);
+        let mut job = self.job.write().unwrap_or_else(PoisonError::into_inner);
+        if job.as_ref().map_or(true, |job| {
+            job.pre_pow_hash != new_job.pre_pow_hash || job.block.header.timestamp != new_job.block.header.timestamp
+        }) {
+            job.replace(Arc::new(new_job));
+        }
+    }
+
+    async fn drive(self: Arc<Self>) {
+        let script_public_key = vecno_txscript::pay_to_address_script(&self.pay_address);
+        let extra_data = version().as_bytes().iter().chain(b"/cpu-miner").cloned().collect::<Vec<_>>();
+        let miner_data = MinerData::new(script_public_key, extra_data);
+
+        let shutdown = self.shutdown.listener.clone();
+        let mut refresh = tokio::time::interval(TEMPLATE_REFRESH_INTERVAL);
+        let mut report = tokio::time::interval(HASHRATE_REPORT_INTERVAL);
+        let mut last_report = Instant::now();
+        loop {
+            select! {
+                _ = shutdown.clone() => break,
+                notification = self.notification_channel.recv() => {
+                    if notification.is_err() {
+                        break;
+                    }
+                    // Coalesce the notifications queued in the meantime
+                    while self.notification_channel.try_recv().is_ok() {}
+                    self.refresh_template(&miner_data).await;
+                }
+                _ = refresh.tick() => self.refresh_template(&miner_data).await,
+                _ = report.tick() => {
+                    let hashes = self.hashes.swap(0, Ordering::Relaxed);
+                    let hashrate = hashes as f64 / last_report.elapsed().as_secs_f64();
+                    last_report = Instant::now();
+                    debug!("[{}] hashrate: {:.2} KH/s over {} threads", SERVICE_NAME, hashrate / 1000.0, self.threads);
+                }
+            }
+        }
+        trace!("[{}] driver exiting", SERVICE_NAME);
+    }
+
+    fn mine(self: Arc<Self>, index: usize, runtime: Handle) {
+        // Threads iterate over distinct nonce ranges
+        let first_nonce = (u64::MAX / self.threads as u64) * index as u64;
+        let mut job: Option<Arc<MinerJob>> = None;
+        let mut nonce = first_nonce;
+        let mut nonces = Vec::with_capacity(NONCES_PER_ROUND as usize);
+        while !self.is_shutting_down.load(Ordering::Relaxed) {
+            let latest = self.job.read().unwrap_or_else(PoisonError::into_inner).clone();
+            let is_same_job = match (&job, &latest) {
+                (Some(job), Some(latest)) => Arc::ptr_eq(job, latest),
+                (None, None) => true,
+                _ => false,
+            };
+            if !is_same_job {
+                job = latest;
+                nonce = first_nonce;
+            }
+
+            let Some(current) = job.as_ref().filter(|job| !job.solved.load(Ordering::Relaxed)) else {
+                thread::sleep(IDLE_INTERVAL);
+                continue;
+            };
+            nonces.clear();
+            nonces.extend((0..NONCES_PER_ROUND).map(|i| nonce.wrapping_add(i)));
+            nonce = nonce.wrapping_add(NONCES_PER_ROUND);
+            let solution = current.pow_state.check_pow_batch(&nonces).into_iter().position(|(passed, _)| passed);
+            if let Some(index) = solution {
+                if !current.solved.swap(true, Ordering::Relaxed) {
+                    let block = current.to_block(nonces[index]);
+                    let miner = self.clone();
+                    runtime.spawn(async move { miner.submit_block(block).await });
+                }
+            }
+            self.hashes.fetch_add(NONCES_PER_ROUND, Ordering::Relaxed);
+        }
+        trace!("[{}] thread {} exiting", SERVICE_NAME, index);
+    }
+
+    fn join_workers(workers: Vec<JoinHandle<()>>) {
+        workers.into_iter().for_each(|worker| worker.join().unwrap_or_default())
+    }
+
+    async fn submit_block(&self, block: Block) {
+        let hash = block.hash();
+        let session = self.flow_context.consensus().unguarded_session();
+        match self.flow_context.submit_rpc_block(&session, block).await {
+            Ok(()) => info!("[{}] mined block {}", SERVICE_NAME, hash),
+            Err(err) => warn!("[{}] mined block {} was rejected: {}", SERVICE_NAME, hash, err),
+        }
+    }
+}
+
+impl AsyncService for CpuMiner {
+    fn ident(self: Arc<Self>) -> &'static str {
+        SERVICE_NAME
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", SERVICE_NAME);
+        Box::pin(async move {
+            info!("CPU miner mining to {} with {} threads", self.pay_address, self.threads);
+            let runtime = Handle::current();
+            let mut workers = Vec::with_capacity(self.threads);
+            for index in 0..self.threads {
+                let miner = self.clone();
+                let runtime = runtime.clone();
+                match thread::Builder::new().name(format!("{}-{}", SERVICE_NAME, index)).spawn(move || miner.mine(index, runtime)) {
+                    Ok(worker) => workers.push(worker),
+                    Err(err) => {
+                        // Stop the threads spawned so far
+                        self.is_shutting_down.store(true, Ordering::Relaxed);
+                        tokio::task::spawn_blocking(move || Self::join_workers(workers)).await.unwrap_or_default();
+                        return Err(AsyncServiceError::Service(format!("{} failed to spawn a thread: {}", SERVICE_NAME, err)));
+                    }
+                }
+            }
+            *self.workers.lock().unwrap_or_else(PoisonError::into_inner) = workers;
+
+            self.drive().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", SERVICE_NAME);
+        self.is_shutting_down.store(true, Ordering::Relaxed);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            let workers = std::mem::take(&mut *self.workers.lock().unwrap_or_else(PoisonError::into_inner));
+            tokio::task::spawn_blocking(move || Self::join_workers(workers))
+                .await
+                .map_err(|err| AsyncServiceError::Service(err.to_string()))?;
+            trace!("{} stopped", SERVICE_NAME);
+            Ok(())
+        })
+    }
+}
@@ -207,6 +207,22 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn submit_transaction_package_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: SubmitTransactionPackageRequest,
+    ) -> RpcResult<SubmitTransactionPackageResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn test_mempool_accept_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: TestMempoolAcceptRequest,
+    ) -> RpcResult<TestMempoolAcceptResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn add_peer_call(&self, _connection: Option<&DynRpcConnection>, _request: AddPeerRequest) -> RpcResult<AddPeerResponse> {
         Err(RpcError::NotImplemented)
     }
@@ -323,6 +339,14 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn list_banned_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: ListBannedRequest,
+    ) -> RpcResult<ListBannedResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn estimate_network_hashes_per_second_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -371,6 +395,22 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_fee_estimate_historical_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetFeeEstimateHistoricalRequest,
+    ) -> RpcResult<GetFeeEstimateHistoricalResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn prioritise_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: PrioritiseTransactionRequest,
+    ) -> RpcResult<PrioritiseTransactionResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn get_current_block_color_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -379,6 +419,46 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transactions_by_ids_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionsByIdsRequest,
+    ) -> RpcResult<GetTransactionsByIdsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transactions_by_addresses_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionsByAddressesRequest,
+    ) -> RpcResult<GetTransactionsByAddressesResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_chain_block_by_daa_score_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetChainBlockByDaaScoreRequest,
+    ) -> RpcResult<GetChainBlockByDaaScoreResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_chain_blocks_in_blue_score_range_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetChainBlocksInBlueScoreRangeRequest,
+    ) -> RpcResult<GetChainBlocksInBlueScoreRangeResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 